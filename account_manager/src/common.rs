@@ -1,6 +1,9 @@
+use account_utils::eth2_keystore::{keypair_from_secret, Keystore, KeystoreBuilder};
 use account_utils::PlainText;
 use account_utils::{read_input_from_user, strip_off_newlines};
+use bls::Keypair;
 use eth2_wallet::bip39::{Language, Mnemonic};
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType, ValidatorPath};
 use std::fs;
 use std::path::PathBuf;
 use std::str::from_utf8;
@@ -50,6 +53,39 @@ pub fn read_mnemonic_from_cli(
     Ok(mnemonic)
 }
 
+/// Reads a single mnemonic phrase from stdin, without prompting or retrying. For use by scripts,
+/// where there is no user to correct an invalid mnemonic.
+pub fn read_mnemonic_from_stdin() -> Result<Mnemonic, String> {
+    let phrase = read_input_from_user(true)?;
+    Mnemonic::from_phrase(phrase.trim(), Language::English)
+        .map_err(|e| format!("Unable to derive mnemonic from stdin: {:?}", e))
+}
+
+/// Derives the `key_type` keypair at `index` of the EIP-2334 HD wallet with the given `seed`.
+pub fn derive_keypair(
+    seed: &[u8],
+    index: u32,
+    key_type: KeyType,
+) -> Result<(Keypair, ValidatorPath), String> {
+    let (secret, path) = recover_validator_secret_from_mnemonic(seed, index, key_type)
+        .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+    let keypair = keypair_from_secret(secret.as_bytes())
+        .map_err(|e| format!("Unable to derive keypair: {:?}", e))?;
+    Ok((keypair, path))
+}
+
+/// Returns an EIP-2335 keystore of `keypair`, encrypted with `password`.
+pub fn build_keystore(
+    keypair: &Keypair,
+    password: &[u8],
+    path: &ValidatorPath,
+) -> Result<Keystore, String> {
+    KeystoreBuilder::new(keypair, password, format!("{}", path))
+        .map_err(|e| format!("Unable build keystore: {:?}", e))?
+        .build()
+        .map_err(|e| format!("Unable build keystore: {:?}", e))
+}
+
 /// Reads in a wallet name from the user. If the `--wallet-name` flag is provided, use it. Otherwise
 /// read from an interactive prompt using tty unless the `--stdin-inputs` flag is provided.
 pub fn read_wallet_name_from_cli(
@@ -1,4 +1,4 @@
-mod common;
+pub mod common;
 pub mod validator;
 pub mod wallet;
 
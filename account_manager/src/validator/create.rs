@@ -1,4 +1,8 @@
-use crate::common::read_wallet_name_from_cli;
+use super::deposit_data::{LaunchpadDepositData, LaunchpadDepositDataFile};
+use crate::common::{
+    build_keystore, derive_keypair, read_mnemonic_from_stdin, read_wallet_name_from_cli,
+};
+use crate::validator::recover::FIRST_INDEX_FLAG;
 use crate::wallet::create::STDIN_INPUTS_FLAG;
 use crate::{SECRETS_DIR_FLAG, WALLETS_DIR_FLAG};
 use account_utils::{
@@ -9,7 +13,9 @@ use directory::{
     ensure_dir_exists, parse_path_or_default_with_flag, DEFAULT_SECRET_DIR, DEFAULT_WALLET_DIR,
 };
 use environment::Environment;
-use eth2_wallet_manager::WalletManager;
+use eth2_wallet::bip39::Seed;
+use eth2_wallet::{KeyType, ValidatorKeystores};
+use eth2_wallet_manager::{LockedWallet, WalletManager};
 use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 use std::ffi::OsStr;
 use std::fs;
@@ -24,6 +30,8 @@ pub const DEPOSIT_GWEI_FLAG: &str = "deposit-gwei";
 pub const STORE_WITHDRAW_FLAG: &str = "store-withdrawal-keystore";
 pub const COUNT_FLAG: &str = "count";
 pub const AT_MOST_FLAG: &str = "at-most";
+pub const MNEMONIC_FROM_STDIN_FLAG: &str = "mnemonic-from-stdin";
+pub const DEPOSIT_DATA_FLAG: &str = "deposit-data-path";
 pub const WALLET_PASSWORD_PROMPT: &str = "Enter your wallet's password:";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
@@ -110,6 +118,50 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long(STDIN_INPUTS_FLAG)
                 .help("If present, read all user inputs from stdin instead of tty."),
         )
+        .arg(
+            Arg::with_name(MNEMONIC_FROM_STDIN_FLAG)
+                .long(MNEMONIC_FROM_STDIN_FLAG)
+                .help(
+                    "If present, derive the validator keys from a BIP-39 mnemonic read from \
+                    stdin, without prompting, instead of from a wallet. No wallet is read or \
+                    written. Requires --count.",
+                )
+                .conflicts_with_all(&[WALLET_NAME_FLAG, WALLET_PASSWORD_FLAG, AT_MOST_FLAG]),
+        )
+        .arg(
+            Arg::with_name(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help(
+                    "The EIP-2334 index of the first validator key to derive from the mnemonic. \
+                    Only used with --mnemonic-from-stdin.",
+                )
+                .takes_value(true)
+                .default_value("0"),
+        )
+        .arg(
+            Arg::with_name(DEPOSIT_DATA_FLAG)
+                .long(DEPOSIT_DATA_FLAG)
+                .value_name("DEPOSIT_DATA_PATH")
+                .help(
+                    "If present, write the deposits for the new validators to this path as a \
+                    JSON file which can be uploaded to the staking launchpad. The file must not \
+                    already exist. Requires --mnemonic-from-stdin.",
+                )
+                .requires(MNEMONIC_FROM_STDIN_FLAG)
+                .takes_value(true),
+        )
+}
+
+/// The source of the keys of new validators.
+enum KeySource {
+    /// The next unused keys of an EIP-2386 wallet, which is updated on disk.
+    Wallet {
+        wallet: LockedWallet,
+        password: PlainText,
+    },
+    /// Consecutive EIP-2334 indices of a mnemonic, starting at `next_index`.
+    Mnemonic { seed: Seed, next_index: u32 },
 }
 
 pub fn cli_run<T: EthSpec>(
@@ -139,9 +191,12 @@ pub fn cli_run<T: EthSpec>(
         .unwrap_or(spec.max_effective_balance);
     let count: Option<usize> = clap_utils::parse_optional(matches, COUNT_FLAG)?;
     let at_most: Option<usize> = clap_utils::parse_optional(matches, AT_MOST_FLAG)?;
+    let mnemonic_from_stdin = matches.is_present(MNEMONIC_FROM_STDIN_FLAG);
+    let deposit_data_path: Option<PathBuf> =
+        clap_utils::parse_optional(matches, DEPOSIT_DATA_FLAG)?;
 
     // The command will always fail if the wallet dir does not exist.
-    if !wallet_base_dir.exists() {
+    if !mnemonic_from_stdin && !wallet_base_dir.exists() {
         return Err(format!(
             "No wallet directory at {:?}. Use the `lighthouse --network {} {} {} {}` command to create a wallet",
             wallet_base_dir,
@@ -156,7 +211,9 @@ pub fn cli_run<T: EthSpec>(
     ensure_dir_exists(&secrets_dir)?;
 
     eprintln!("secrets-dir path {:?}", secrets_dir);
-    eprintln!("wallets-dir path {:?}", wallet_base_dir);
+    if !mnemonic_from_stdin {
+        eprintln!("wallets-dir path {:?}", wallet_base_dir);
+    }
 
     let starting_validator_count = existing_validator_count(&validator_dir)?;
 
@@ -181,18 +238,33 @@ pub fn cli_run<T: EthSpec>(
         return Ok(());
     }
 
-    let wallet_password_path: Option<PathBuf> =
-        clap_utils::parse_optional(matches, WALLET_PASSWORD_FLAG)?;
+    let mut key_source = if mnemonic_from_stdin {
+        let first_index = clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?;
+        let mnemonic = read_mnemonic_from_stdin()?;
+        KeySource::Mnemonic {
+            seed: Seed::new(&mnemonic, ""),
+            next_index: first_index,
+        }
+    } else {
+        let wallet_password_path: Option<PathBuf> =
+            clap_utils::parse_optional(matches, WALLET_PASSWORD_FLAG)?;
+
+        let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
+        let wallet_password = read_wallet_password_from_cli(wallet_password_path, stdin_inputs)?;
 
-    let wallet_name = read_wallet_name_from_cli(name, stdin_inputs)?;
-    let wallet_password = read_wallet_password_from_cli(wallet_password_path, stdin_inputs)?;
+        let mgr = WalletManager::open(&wallet_base_dir)
+            .map_err(|e| format!("Unable to open --{}: {:?}", WALLETS_DIR_FLAG, e))?;
 
-    let mgr = WalletManager::open(&wallet_base_dir)
-        .map_err(|e| format!("Unable to open --{}: {:?}", WALLETS_DIR_FLAG, e))?;
+        let wallet = mgr
+            .wallet_by_name(&wallet_name)
+            .map_err(|e| format!("Unable to open wallet: {:?}", e))?;
 
-    let mut wallet = mgr
-        .wallet_by_name(&wallet_name)
-        .map_err(|e| format!("Unable to open wallet: {:?}", e))?;
+        KeySource::Wallet {
+            wallet,
+            password: wallet_password,
+        }
+    };
+    let network_name = matches.value_of("network").map(String::from);
 
     let slashing_protection_path = validator_dir.join(SLASHING_PROTECTION_FILENAME);
     let slashing_protection =
@@ -212,17 +284,60 @@ pub fn cli_run<T: EthSpec>(
         )
     })?;
 
+    // Create the deposit data file before any keys, so that an existing file is never
+    // overwritten and no keys are created if the file cannot be written.
+    let mut deposit_data_file = deposit_data_path
+        .clone()
+        .map(LaunchpadDepositDataFile::create)
+        .transpose()?;
+
     for i in 0..n {
         let voting_password = random_password();
         let withdrawal_password = random_password();
 
-        let keystores = wallet
-            .next_validator(
-                wallet_password.as_bytes(),
-                voting_password.as_bytes(),
-                withdrawal_password.as_bytes(),
-            )
-            .map_err(|e| format!("Unable to create validator keys: {:?}", e))?;
+        let (keystores, deposit) = match &mut key_source {
+            KeySource::Wallet { wallet, password } => (
+                wallet
+                    .next_validator(
+                        password.as_bytes(),
+                        voting_password.as_bytes(),
+                        withdrawal_password.as_bytes(),
+                    )
+                    .map_err(|e| format!("Unable to create validator keys: {:?}", e))?,
+                None,
+            ),
+            KeySource::Mnemonic { seed, next_index } => {
+                let index = *next_index;
+                *next_index = index
+                    .checked_add(1)
+                    .ok_or("Exhausted validator key indices")?;
+
+                let (voting, voting_path) =
+                    derive_keypair(seed.as_bytes(), index, KeyType::Voting)?;
+                let (withdrawal, withdrawal_path) =
+                    derive_keypair(seed.as_bytes(), index, KeyType::Withdrawal)?;
+
+                let deposit = LaunchpadDepositData::new(
+                    &voting,
+                    &withdrawal.pk,
+                    deposit_gwei,
+                    network_name.clone(),
+                    &spec,
+                );
+
+                (
+                    ValidatorKeystores {
+                        voting: build_keystore(&voting, voting_password.as_bytes(), &voting_path)?,
+                        withdrawal: build_keystore(
+                            &withdrawal,
+                            withdrawal_password.as_bytes(),
+                            &withdrawal_path,
+                        )?,
+                    },
+                    Some(deposit),
+                )
+            }
+        };
 
         let voting_pubkey = keystores.voting.public_key().ok_or_else(|| {
             format!(
@@ -250,9 +365,17 @@ pub fn cli_run<T: EthSpec>(
             .build()
             .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
+        if let (Some(deposit_data_file), Some(deposit)) = (&mut deposit_data_file, deposit) {
+            deposit_data_file.push(deposit)?;
+        }
+
         println!("{}/{}\t{}", i + 1, n, voting_pubkey.as_hex_string());
     }
 
+    if let Some(path) = deposit_data_path {
+        eprintln!("Wrote deposit data for {} validators to {:?}", n, path);
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,220 @@
+use bls::{get_withdrawal_credentials, Keypair, PublicKey};
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom};
+use std::path::PathBuf;
+use tree_hash::TreeHash;
+use types::{ChainSpec, DepositData, Hash256, Signature};
+
+/// The `staking-deposit-cli` version whose `deposit_data-*.json` format is reproduced by
+/// `LaunchpadDepositData`. The launchpad rejects files which do not declare a version.
+pub const DEPOSIT_CLI_VERSION: &str = "2.3.0";
+
+/// A deposit in the format of the `deposit_data-*.json` files produced by the
+/// `staking-deposit-cli`, which can be uploaded to the staking launchpad.
+///
+/// Byte strings are hex-encoded without a `0x` prefix.
+#[derive(Debug, Serialize)]
+pub struct LaunchpadDepositData {
+    pub pubkey: String,
+    pub withdrawal_credentials: String,
+    pub amount: u64,
+    pub signature: String,
+    pub deposit_message_root: String,
+    pub deposit_data_root: String,
+    pub fork_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_name: Option<String>,
+    pub deposit_cli_version: String,
+}
+
+impl LaunchpadDepositData {
+    /// Returns a signed deposit of `amount` gwei for the `voting_keypair`, which may be withdrawn
+    /// with the `withdrawal_pubkey`.
+    pub fn new(
+        voting_keypair: &Keypair,
+        withdrawal_pubkey: &PublicKey,
+        amount: u64,
+        network_name: Option<String>,
+        spec: &ChainSpec,
+    ) -> Self {
+        let mut deposit_data = DepositData {
+            pubkey: voting_keypair.pk.clone().into(),
+            withdrawal_credentials: Hash256::from_slice(&get_withdrawal_credentials(
+                withdrawal_pubkey,
+                spec.bls_withdrawal_prefix_byte,
+            )),
+            amount,
+            signature: Signature::empty().into(),
+        };
+        deposit_data.signature = deposit_data.create_signature(&voting_keypair.sk, spec);
+
+        Self {
+            pubkey: hex::encode(deposit_data.pubkey.as_serialized()),
+            withdrawal_credentials: hex::encode(deposit_data.withdrawal_credentials),
+            amount,
+            signature: hex::encode(deposit_data.signature.serialize()),
+            deposit_message_root: hex::encode(deposit_data.as_deposit_message().tree_hash_root()),
+            deposit_data_root: hex::encode(deposit_data.tree_hash_root()),
+            fork_version: hex::encode(spec.genesis_fork_version),
+            network_name,
+            deposit_cli_version: DEPOSIT_CLI_VERSION.to_string(),
+        }
+    }
+}
+
+/// A `deposit_data-*.json` file, which can be uploaded to the staking launchpad.
+///
+/// The file is rewritten as each deposit is added, so that it always contains the deposits of the
+/// validators created so far, even if creating a later validator fails.
+pub struct LaunchpadDepositDataFile {
+    path: PathBuf,
+    file: File,
+    deposits: Vec<LaunchpadDepositData>,
+}
+
+impl LaunchpadDepositDataFile {
+    /// Creates a file at `path` containing no deposits, failing if the file already exists.
+    pub fn create(path: PathBuf) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| format!("Unable to create deposit data file {:?}: {:?}", path, e))?;
+
+        let mut deposit_data_file = Self {
+            path,
+            file,
+            deposits: vec![],
+        };
+        deposit_data_file.write()?;
+
+        Ok(deposit_data_file)
+    }
+
+    /// Adds `deposit` to the file.
+    pub fn push(&mut self, deposit: LaunchpadDepositData) -> Result<(), String> {
+        self.deposits.push(deposit);
+        self.write()
+    }
+
+    /// Replaces the contents of the file with all the deposits, as a JSON list.
+    fn write(&mut self) -> Result<(), String> {
+        let path = &self.path;
+        self.file
+            .set_len(0)
+            .and_then(|()| self.file.seek(SeekFrom::Start(0)))
+            .map_err(|e| format!("Unable to truncate deposit data file {:?}: {:?}", path, e))?;
+        serde_json::to_writer(&mut self.file, &self.deposits)
+            .map_err(|e| format!("Unable to write deposit data file {:?}: {:?}", path, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::derive_keypair;
+    use eth2_wallet::{
+        bip39::{Language, Mnemonic, Seed},
+        KeyType,
+    };
+    use types::{DepositMessage, MainnetEthSpec, PublicKeyBytes, SignedRoot};
+
+    const TEST_PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    /// The expected deposit of 32 ETH for index 0 of `TEST_PHRASE`, computed independently of
+    /// Lighthouse from the EIP-2333/EIP-2334 key derivation and the SSZ `DepositMessage` root.
+    const PUBKEY: &str = "b3e445d43871965d890a398f719348a1405ac72e35b92727cc570026f54471af7ea7b2040622a8fd0b5bfb2a209b5911";
+    const WITHDRAWAL_CREDENTIALS: &str =
+        "00eca1f12f398e3ceef109f5f76d8e99f9105e800a90390f1a18895919fd4b3b";
+    const DEPOSIT_MESSAGE_ROOT: &str =
+        "e5f649f0154082253653461a36815b23c934a01d894fdc1c6dd91785aeac1d24";
+
+    fn deposit(spec: &ChainSpec) -> LaunchpadDepositData {
+        let mnemonic = Mnemonic::from_phrase(TEST_PHRASE, Language::English).unwrap();
+        let seed = Seed::new(&mnemonic, "");
+        let (voting, _) = derive_keypair(seed.as_bytes(), 0, KeyType::Voting).unwrap();
+        let (withdrawal, _) = derive_keypair(seed.as_bytes(), 0, KeyType::Withdrawal).unwrap();
+
+        LaunchpadDepositData::new(
+            &voting,
+            &withdrawal.pk,
+            32_000_000_000,
+            Some("mainnet".to_string()),
+            spec,
+        )
+    }
+
+    #[test]
+    fn known_deposit() {
+        let spec = MainnetEthSpec::default_spec();
+        let deposit = deposit(&spec);
+
+        assert_eq!(deposit.pubkey, PUBKEY);
+        assert_eq!(deposit.withdrawal_credentials, WITHDRAWAL_CREDENTIALS);
+        assert_eq!(deposit.amount, 32_000_000_000);
+        assert_eq!(deposit.deposit_message_root, DEPOSIT_MESSAGE_ROOT);
+        assert_eq!(deposit.fork_version, "00000000");
+        assert_eq!(deposit.network_name.as_deref(), Some("mainnet"));
+        assert_eq!(deposit.deposit_cli_version, DEPOSIT_CLI_VERSION);
+
+        // The signature must be a valid deposit signature over the message.
+        let pubkey = PublicKeyBytes::deserialize(&hex::decode(&deposit.pubkey).unwrap())
+            .unwrap()
+            .decompress()
+            .unwrap();
+        let signature = Signature::deserialize(&hex::decode(&deposit.signature).unwrap()).unwrap();
+        let message = DepositMessage {
+            pubkey: pubkey.clone().into(),
+            withdrawal_credentials: Hash256::from_slice(
+                &hex::decode(&deposit.withdrawal_credentials).unwrap(),
+            ),
+            amount: deposit.amount,
+        };
+        assert_eq!(
+            hex::encode(message.tree_hash_root()),
+            deposit.deposit_message_root
+        );
+        assert!(signature.verify(&pubkey, message.signing_root(spec.get_deposit_domain())));
+
+        // The data root commits to the message and the signature.
+        let deposit_data = DepositData {
+            pubkey: message.pubkey,
+            withdrawal_credentials: message.withdrawal_credentials,
+            amount: message.amount,
+            signature: signature.into(),
+        };
+        assert_eq!(
+            hex::encode(deposit_data.tree_hash_root()),
+            deposit.deposit_data_root
+        );
+    }
+
+    #[test]
+    fn deposit_data_file() {
+        let spec = MainnetEthSpec::default_spec();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deposit_data.json");
+        let read = || -> Vec<serde_json::Value> {
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap()
+        };
+
+        let mut file = LaunchpadDepositDataFile::create(path.clone()).unwrap();
+        assert!(read().is_empty());
+
+        // Each deposit is written as soon as it is added.
+        file.push(deposit(&spec)).unwrap();
+        let deposits = read();
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0]["pubkey"], PUBKEY);
+        assert_eq!(deposits[0]["deposit_message_root"], DEPOSIT_MESSAGE_ROOT);
+        file.push(deposit(&spec)).unwrap();
+        assert_eq!(read().len(), 2);
+        drop(file);
+
+        // An existing file is never overwritten.
+        assert!(LaunchpadDepositDataFile::create(path.clone()).is_err());
+        assert_eq!(read().len(), 2);
+    }
+}
@@ -1,20 +1,30 @@
 use super::create::STORE_WITHDRAW_FLAG;
-use crate::common::read_mnemonic_from_cli;
+use crate::common::{build_keystore, derive_keypair, read_mnemonic_from_cli};
 use crate::validator::create::COUNT_FLAG;
+use crate::validator::exit::BEACON_SERVER_FLAG;
 use crate::wallet::create::STDIN_INPUTS_FLAG;
 use crate::SECRETS_DIR_FLAG;
-use account_utils::eth2_keystore::{keypair_from_secret, Keystore, KeystoreBuilder};
 use account_utils::random_password;
 use clap::{App, Arg, ArgMatches};
 use directory::ensure_dir_exists;
 use directory::{parse_path_or_default_with_flag, DEFAULT_SECRET_DIR};
+use environment::Environment;
+use eth2::{
+    types::{StateId, ValidatorId},
+    BeaconNodeHttpClient, Timeouts,
+};
 use eth2_wallet::bip39::Seed;
-use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType, ValidatorKeystores};
+use eth2_wallet::{KeyType, ValidatorKeystores};
+use safe_arith::SafeArith;
+use sensitive_url::SensitiveUrl;
 use std::path::PathBuf;
+use std::time::Duration;
+use types::{EthSpec, PublicKeyBytes};
 use validator_dir::Builder as ValidatorDirBuilder;
 pub const CMD: &str = "recover";
 pub const FIRST_INDEX_FLAG: &str = "first-index";
 pub const MNEMONIC_FLAG: &str = "mnemonic-path";
+pub const GAP_LIMIT_FLAG: &str = "gap-limit";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
@@ -76,9 +86,36 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .long(STDIN_INPUTS_FLAG)
                 .help("If present, read all user inputs from stdin instead of tty."),
         )
+        .arg(
+            Arg::with_name(BEACON_SERVER_FLAG)
+                .long(BEACON_SERVER_FLAG)
+                .value_name("NETWORK_ADDRESS")
+                .help(
+                    "If present, only the keys of validators which are known to the beacon chain \
+                    are recovered. Indices are scanned from `--first-index` until `--gap-limit` \
+                    consecutive indices are not known to the beacon node at this HTTP API \
+                    address. `--count` is ignored.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(GAP_LIMIT_FLAG)
+                .long(GAP_LIMIT_FLAG)
+                .value_name("GAP_LIMIT")
+                .help(
+                    "The number of consecutive indices without a validator on the beacon chain \
+                    after which to stop scanning. Only used with `--beacon-node`.",
+                )
+                .takes_value(true)
+                .default_value("20"),
+        )
 }
 
-pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), String> {
+pub fn cli_run<T: EthSpec>(
+    matches: &ArgMatches,
+    env: Environment<T>,
+    validator_dir: PathBuf,
+) -> Result<(), String> {
     let secrets_dir = if matches.value_of("datadir").is_some() {
         let path: PathBuf = clap_utils::parse_required(matches, "datadir")?;
         path.join(DEFAULT_SECRET_DIR)
@@ -89,6 +126,8 @@ pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), Strin
     let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
     let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
     let stdin_inputs = cfg!(windows) || matches.is_present(STDIN_INPUTS_FLAG);
+    let beacon_node: Option<String> = clap_utils::parse_optional(matches, BEACON_SERVER_FLAG)?;
+    let gap_limit: u32 = clap_utils::parse_required(matches, GAP_LIMIT_FLAG)?;
 
     eprintln!("secrets-dir path: {:?}", secrets_dir);
 
@@ -103,22 +142,26 @@ pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), Strin
 
     let seed = Seed::new(&mnemonic, "");
 
-    for index in first_index..first_index + count {
+    let indices = if let Some(server_url) = beacon_node {
+        let client = BeaconNodeHttpClient::new(
+            SensitiveUrl::parse(&server_url)
+                .map_err(|e| format!("Failed to parse beacon http server: {:?}", e))?,
+            Timeouts::set_all(Duration::from_secs(env.eth2_config.spec.seconds_per_slot)),
+        );
+        env.runtime()
+            .block_on(find_known_indices(&client, &seed, first_index, gap_limit))?
+    } else {
+        (first_index..first_index + count).collect()
+    };
+    let count = indices.len();
+
+    for (i, index) in indices.into_iter().enumerate() {
         let voting_password = random_password();
         let withdrawal_password = random_password();
 
-        let derive = |key_type: KeyType, password: &[u8]| -> Result<Keystore, String> {
-            let (secret, path) =
-                recover_validator_secret_from_mnemonic(seed.as_bytes(), index, key_type)
-                    .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
-
-            let keypair = keypair_from_secret(secret.as_bytes())
-                .map_err(|e| format!("Unable build keystore: {:?}", e))?;
-
-            KeystoreBuilder::new(&keypair, password, format!("{}", path))
-                .map_err(|e| format!("Unable build keystore: {:?}", e))?
-                .build()
-                .map_err(|e| format!("Unable build keystore: {:?}", e))
+        let derive = |key_type: KeyType, password: &[u8]| {
+            let (keypair, path) = derive_keypair(seed.as_bytes(), index, key_type)?;
+            build_keystore(&keypair, password, &path)
         };
 
         let keystores = ValidatorKeystores {
@@ -136,14 +179,182 @@ pub fn cli_run(matches: &ArgMatches, validator_dir: PathBuf) -> Result<(), Strin
             .build()
             .map_err(|e| format!("Unable to build validator directory: {:?}", e))?;
 
-        println!(
-            "{}/{}\tIndex: {}\t0x{}",
-            index - first_index,
-            count - first_index,
-            index,
-            voting_pubkey
-        );
+        println!("{}/{}\tIndex: {}\t0x{}", i, count, index, voting_pubkey);
     }
 
     Ok(())
 }
+
+/// Returns the voting public key at `index` of the HD wallet with the given `seed`.
+fn voting_pubkey(seed: &Seed, index: u32) -> Result<PublicKeyBytes, String> {
+    let (keypair, _) = derive_keypair(seed.as_bytes(), index, KeyType::Voting)?;
+    Ok(keypair.pk.into())
+}
+
+/// Scans the indices of the HD wallet with the given `seed`, starting at `first_index`, and
+/// returns those which have a validator known to the beacon node behind `client` (i.e., a
+/// processed deposit).
+///
+/// Scanning stops once `gap_limit` consecutive indices are unknown to the beacon node.
+async fn find_known_indices(
+    client: &BeaconNodeHttpClient,
+    seed: &Seed,
+    first_index: u32,
+    gap_limit: u32,
+) -> Result<Vec<u32>, String> {
+    let mut indices = vec![];
+    let mut index = first_index;
+    let mut gap = 0;
+
+    while gap < gap_limit {
+        let pubkey = voting_pubkey(seed, index)?;
+        let validator = client
+            .get_beacon_states_validator_id(StateId::Head, &ValidatorId::PublicKey(pubkey))
+            .await
+            .map_err(|e| format!("Failed to get validator details: {:?}", e))?;
+
+        if let Some(validator) = validator {
+            eprintln!(
+                "Index {} is validator {} ({:?})",
+                index, validator.data.index, validator.data.status
+            );
+            indices.push(index);
+            gap = 0;
+        } else {
+            gap += 1;
+        }
+
+        index
+            .safe_add_assign(1)
+            .map_err(|e| format!("Exhausted key indices: {:?}", e))?;
+    }
+
+    eprintln!(
+        "Found {} validators, stopped scanning at index {}",
+        indices.len(),
+        index
+    );
+
+    Ok(indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2::types::{GenericResponse, ValidatorData, ValidatorStatus};
+    use eth2_wallet::bip39::{Language, Mnemonic};
+    use std::net::Ipv4Addr;
+    use tokio::sync::oneshot;
+    use types::{Epoch, Hash256, Validator};
+    use warp::{http::StatusCode, Filter};
+
+    const TEST_PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn seed() -> Seed {
+        Seed::new(
+            &Mnemonic::from_phrase(TEST_PHRASE, Language::English).unwrap(),
+            "",
+        )
+    }
+
+    /// Serves a beacon node which knows the validators at the given wallet `indices`, returning
+    /// a client for it.
+    fn mock_beacon_node(
+        seed: &Seed,
+        indices: &[u32],
+    ) -> (BeaconNodeHttpClient, oneshot::Sender<()>) {
+        let known = indices
+            .iter()
+            .map(|&index| (voting_pubkey(seed, index).unwrap(), index))
+            .collect::<Vec<_>>();
+
+        let get_validator =
+            warp::path!("eth" / "v1" / "beacon" / "states" / "head" / "validators" / ValidatorId)
+                .and(warp::get())
+                .map(move |validator_id: ValidatorId| {
+                    let known_index = match validator_id {
+                        ValidatorId::PublicKey(pubkey) => known
+                            .iter()
+                            .find(|(known_pubkey, _)| *known_pubkey == pubkey)
+                            .map(|(_, index)| (pubkey, *index)),
+                        ValidatorId::Index(_) => None,
+                    };
+
+                    if let Some((pubkey, index)) = known_index {
+                        let data = ValidatorData {
+                            index: index.into(),
+                            balance: 32_000_000_000,
+                            status: ValidatorStatus::ActiveOngoing,
+                            validator: Validator {
+                                pubkey,
+                                withdrawal_credentials: Hash256::zero(),
+                                effective_balance: 32_000_000_000,
+                                slashed: false,
+                                activation_eligibility_epoch: Epoch::new(0),
+                                activation_epoch: Epoch::new(0),
+                                exit_epoch: Epoch::max_value(),
+                                withdrawable_epoch: Epoch::max_value(),
+                            },
+                        };
+                        warp::reply::with_status(
+                            warp::reply::json(&GenericResponse::from(data)),
+                            StatusCode::OK,
+                        )
+                    } else {
+                        warp::reply::with_status(
+                            warp::reply::json(&serde_json::json!({
+                                "code": 404,
+                                "message": "NOT_FOUND: unknown validator",
+                            })),
+                            StatusCode::NOT_FOUND,
+                        )
+                    }
+                });
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let (listening_socket, server) = warp::serve(get_validator).bind_with_graceful_shutdown(
+            (Ipv4Addr::new(127, 0, 0, 1), 0),
+            async {
+                let _ = shutdown_rx.await;
+            },
+        );
+        tokio::spawn(server);
+
+        let url = SensitiveUrl::parse(&format!(
+            "http://{}:{}",
+            listening_socket.ip(),
+            listening_socket.port()
+        ))
+        .unwrap();
+        let client = BeaconNodeHttpClient::new(url, Timeouts::set_all(Duration::from_secs(1)));
+
+        (client, shutdown_tx)
+    }
+
+    #[tokio::test]
+    async fn find_known_indices_stops_after_gap_limit() {
+        let seed = seed();
+        let (client, _shutdown) = mock_beacon_node(&seed, &[0, 2, 5]);
+
+        // A hit resets the gap, so indices separated by fewer than `gap_limit` misses are found.
+        assert_eq!(
+            find_known_indices(&client, &seed, 0, 3).await.unwrap(),
+            vec![0, 2, 5]
+        );
+
+        // Scanning stops after `gap_limit` consecutive misses.
+        assert_eq!(
+            find_known_indices(&client, &seed, 0, 2).await.unwrap(),
+            vec![0, 2]
+        );
+        assert_eq!(
+            find_known_indices(&client, &seed, 3, 2).await.unwrap(),
+            Vec::<u32>::new()
+        );
+        assert_eq!(
+            find_known_indices(&client, &seed, 3, 3).await.unwrap(),
+            vec![5]
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use clap::{App, Arg, ArgMatches};
 use environment::Environment;
 use slashing_protection::{
-    interchange::Interchange, InterchangeError, InterchangeImportOutcome, SlashingDatabase,
-    SLASHING_PROTECTION_FILENAME,
+    interchange::{Interchange, InterchangeConflict},
+    InterchangeError, InterchangeImportOutcome, SlashingDatabase, SLASHING_PROTECTION_FILENAME,
 };
 use std::fs::File;
 use std::path::PathBuf;
@@ -12,16 +12,21 @@ use types::{BeaconState, Epoch, EthSpec, PublicKeyBytes, Slot};
 pub const CMD: &str = "slashing-protection";
 pub const IMPORT_CMD: &str = "import";
 pub const EXPORT_CMD: &str = "export";
+pub const PRUNE_CMD: &str = "prune";
 
 pub const IMPORT_FILE_ARG: &str = "IMPORT-FILE";
 pub const EXPORT_FILE_ARG: &str = "EXPORT-FILE";
 
 pub const MINIFY_FLAG: &str = "minify";
 pub const PUBKEYS_FLAG: &str = "pubkeys";
+pub const ALLOW_CONFLICTS_FLAG: &str = "allow-conflicting-data";
 
 pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
     App::new(CMD)
-        .about("Import or export slashing protection data to or from another client")
+        .about(
+            "Import or export slashing protection data to or from another client, or prune the \
+             local slashing protection database",
+        )
         .subcommand(
             App::new(IMPORT_CMD)
                 .about("Import an interchange file")
@@ -40,6 +45,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                             "Deprecated: Lighthouse no longer requires minification on import \
                              because it always minifies",
                         ),
+                )
+                .arg(
+                    Arg::with_name(ALLOW_CONFLICTS_FLAG)
+                        .long(ALLOW_CONFLICTS_FLAG)
+                        .takes_value(false)
+                        .help(
+                            "Import the file even if it contains slashable data, such as two \
+                             different blocks signed at the same slot. Such data suggests that \
+                             the validators have been run by more than one client at once.",
+                        ),
                 ),
         )
         .subcommand(
@@ -73,6 +88,11 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                         ),
                 ),
         )
+        .subcommand(App::new(PRUNE_CMD).about(
+            "Remove all but the latest block and attestation of each validator from the slashing \
+             protection database and compact the database file. The validator client must be \
+             stopped while this command runs.",
+        ))
 }
 
 pub fn cli_run<T: EthSpec>(
@@ -113,6 +133,29 @@ pub fn cli_run<T: EthSpec>(
                 .map_err(|e| format!("Error parsing file for import: {:?}", e))?;
             eprintln!(" [done].");
 
+            let conflicts = interchange.conflicts();
+            if !conflicts.is_empty() {
+                eprintln!("WARNING: the import file contains slashable data:");
+                for conflict in &conflicts {
+                    eprintln!("- {}", display_conflict(conflict));
+                }
+                if !matches.is_present(ALLOW_CONFLICTS_FLAG) {
+                    return Err(format!(
+                        "ERROR: import aborted due to slashable data, see above.\n\
+                         This suggests that these validators have been run by more than one \
+                         client at once, which should be investigated before validating.\n\
+                         Once you are certain that no other client is running these validators, \
+                         the file may be imported with --{}.",
+                        ALLOW_CONFLICTS_FLAG
+                    ));
+                }
+                eprintln!(
+                    "Importing anyway due to --{}. Only the latest block and attestation of \
+                     each validator are retained.",
+                    ALLOW_CONFLICTS_FLAG
+                );
+            }
+
             if let Some(minify) = minify {
                 eprintln!(
                     "WARNING: --minify flag is deprecated and will be removed in a future release"
@@ -266,7 +309,75 @@ pub fn cli_run<T: EthSpec>(
 
             Ok(())
         }
+        (PRUNE_CMD, Some(_)) => {
+            if !slashing_protection_db_path.exists() {
+                return Err(format!(
+                    "No slashing protection database exists at: {}",
+                    slashing_protection_db_path.display()
+                ));
+            }
+
+            let db_size = || {
+                std::fs::metadata(&slashing_protection_db_path)
+                    .map(|metadata| metadata.len())
+                    .map_err(|e| format!("Unable to read database file size: {:?}", e))
+            };
+            let size_before = db_size()?;
+
+            let slashing_protection_database = SlashingDatabase::open(&slashing_protection_db_path)
+                .map_err(|e| {
+                    format!(
+                        "Unable to open database at {}, is the validator client running? {:?}",
+                        slashing_protection_db_path.display(),
+                        e
+                    )
+                })?;
+
+            eprint!("Pruning all but the latest block and attestation of each validator");
+            slashing_protection_database
+                .prune_all_to_latest()
+                .map_err(|e| format!("Error during pruning: {:?}", e))?;
+            eprintln!(" [done].");
+
+            eprint!("Compacting database file");
+            slashing_protection_database
+                .vacuum()
+                .map_err(|e| format!("Error during compaction: {:?}", e))?;
+            eprintln!(" [done].");
+            drop(slashing_protection_database);
+
+            eprintln!(
+                "Prune completed successfully, database size reduced from {} to {} bytes",
+                size_before,
+                db_size()?
+            );
+
+            Ok(())
+        }
         ("", _) => Err("No subcommand provided, see --help for options".to_string()),
         (command, _) => Err(format!("No such subcommand `{}`", command)),
     }
 }
+
+fn display_conflict(conflict: &InterchangeConflict) -> String {
+    match conflict {
+        InterchangeConflict::DoubleBlockProposal { pubkey, slot } => {
+            format!("{:?}: double block proposal at slot {}", pubkey, slot)
+        }
+        InterchangeConflict::DoubleVote {
+            pubkey,
+            target_epoch,
+        } => format!(
+            "{:?}: double vote for target epoch {}",
+            pubkey, target_epoch
+        ),
+        InterchangeConflict::SurroundVote {
+            pubkey,
+            surrounding,
+            surrounded,
+        } => format!(
+            "{:?}: attestation {} => {} surrounds attestation {} => {}",
+            pubkey, surrounding.0, surrounding.1, surrounded.0, surrounded.1
+        ),
+    }
+}
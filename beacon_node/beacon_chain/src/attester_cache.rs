@@ -241,6 +241,17 @@ impl AttesterCacheKey {
             decision_root,
         })
     }
+
+    /// Instantiate `Self` to key `epoch` for a chain in which `decision_root` is the root of the
+    /// latest block at or prior to the last slot of `epoch - 1`.
+    ///
+    /// The `decision_root` should be all-zeros for the genesis epoch, as per `Self::new`.
+    pub fn from_decision_root(epoch: Epoch, decision_root: Hash256) -> Self {
+        Self {
+            epoch,
+            decision_root,
+        }
+    }
 }
 
 /// Provides a cache for the justified checkpoint and committee length when producing an
@@ -15,10 +15,15 @@ use crate::chain_config::ChainConfig;
 use crate::early_attester_cache::EarlyAttesterCache;
 use crate::errors::{BeaconChainError as Error, BlockProductionError};
 use crate::eth1_chain::{Eth1Chain, Eth1ChainBackend};
-use crate::events::ServerSentEventHandler;
-use crate::execution_payload::get_execution_payload;
+use crate::events::{EventKind, ServerSentEventHandler, SseValidatorSlashing};
+use crate::execution_payload::{get_execution_payload, get_execution_payload_header};
 use crate::head_tracker::HeadTracker;
 use crate::historical_blocks::HistoricalBlockError;
+use crate::light_client_server::LightClientServerCache;
+use crate::light_client_update_verification::{
+    Error as LightClientUpdateError, VerifiedLightClientFinalityUpdate,
+    VerifiedLightClientOptimisticUpdate,
+};
 use crate::migrate::BackgroundMigrator;
 use crate::naive_aggregation_pool::{
     AggregatedAttestationMap, Error as NaiveAggregationError, NaiveAggregationPool,
@@ -34,6 +39,7 @@ use crate::observed_block_producers::ObservedBlockProducers;
 use crate::observed_operations::{ObservationOutcome, ObservedOperations};
 use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_BLOCK_ROOT};
 use crate::persisted_fork_choice::PersistedForkChoice;
+use crate::rewards::RewardsCache;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
 use crate::snapshot_cache::SnapshotCache;
 use crate::sync_committee_verification::{
@@ -51,14 +57,14 @@ use crate::{metrics, BeaconChainError};
 use eth2::types::{
     EventKind, SseBlock, SseChainReorg, SseFinalizedCheckpoint, SseHead, SseLateHead, SyncDuty,
 };
-use execution_layer::ExecutionLayer;
+use execution_layer::{ExecutionLayer, PayloadAttributes};
 use fork_choice::{AttestationFromBlock, ForkChoice};
 use futures::channel::mpsc::Sender;
 use itertools::process_results;
 use itertools::Itertools;
 use operation_pool::{OperationPool, PersistedOperationPool};
 use parking_lot::{Mutex, RwLock};
-use proto_array::ExecutionStatus;
+use proto_array::{Block as ProtoBlock, ExecutionStatus};
 use safe_arith::SafeArith;
 use slasher::Slasher;
 use slog::{crit, debug, error, info, trace, warn, Logger};
@@ -67,7 +73,9 @@ use ssz::Encode;
 use state_processing::{
     common::get_indexed_attestation,
     per_block_processing,
-    per_block_processing::{errors::AttestationValidationError, is_merge_transition_complete},
+    per_block_processing::{
+        compute_timestamp_at_slot, errors::AttestationValidationError, is_merge_transition_complete,
+    },
     per_slot_processing,
     state_advance::{complete_state_advance, partial_state_advance},
     BlockSignatureStrategy, SigVerifiedOp, VerifyBlockRoot,
@@ -82,6 +90,8 @@ use std::time::{Duration, Instant};
 use store::iter::{BlockRootsIterator, ParentRootBlockIterator, StateRootsIterator};
 use store::{Error as DBError, HotColdDB, KeyValueStore, KeyValueStoreOp, StoreItem, StoreOp};
 use task_executor::ShutdownReason;
+use tracing::{field, info_span};
+use tree_hash::TreeHash;
 use types::beacon_state::CloneConfig;
 use types::*;
 
@@ -328,6 +338,10 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub(crate) shuffling_cache: TimeoutRwLock<ShufflingCache>,
     /// Caches the beacon block proposer shuffling for a given epoch and shuffling key root.
     pub beacon_proposer_cache: Mutex<BeaconProposerCache>,
+    /// Caches the results of the reward computations served by the HTTP API.
+    pub(crate) rewards_cache: Mutex<RewardsCache>,
+    /// Caches the data served to light clients by the HTTP API.
+    pub(crate) light_client_server_cache: Mutex<LightClientServerCache<T::EthSpec>>,
     /// Caches a map of `validator_index -> validator_pubkey`.
     pub(crate) validator_pubkey_cache: TimeoutRwLock<ValidatorPubkeyCache<T>>,
     /// A cache used when producing attestations.
@@ -352,6 +366,16 @@ pub struct BeaconChain<T: BeaconChainTypes> {
 }
 
 type BeaconBlockAndState<T> = (BeaconBlock<T>, BeaconState<T>);
+type BlindedBeaconBlockAndState<T> = (BlindedBeaconBlock<T>, BeaconState<T>);
+
+/// The source of the execution payload of a block produced by the `BeaconChain`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PayloadSource {
+    /// Request a full payload from the execution layer.
+    Local,
+    /// Request a payload header from the external builder, for a blinded block.
+    Builder,
+}
 
 impl<T: BeaconChainTypes> BeaconChain<T> {
     /// Persists the head tracker and fork choice.
@@ -450,7 +474,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
     /// Persists `self.eth1_chain` and its caches to disk.
     pub fn persist_eth1_cache(&self) -> Result<(), Error> {
-        let _timer = metrics::start_timer(&metrics::PERSIST_OP_POOL);
+        let _timer = metrics::start_timer(&metrics::PERSIST_ETH1_CACHE);
 
         if let Some(eth1_chain) = self.eth1_chain.as_ref() {
             self.store
@@ -460,6 +484,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(())
     }
 
+    /// Persists the head, fork choice, op pool and eth1 cache to disk, as happens when `self` is
+    /// dropped.
+    pub fn persist_to_disk(&self) -> Result<(), Error> {
+        self.persist_head_and_fork_choice()?;
+        self.persist_op_pool()?;
+        self.persist_eth1_cache()
+    }
+
     /// Returns the slot _right now_ according to `self.slot_clock`. Returns `Err` if the slot is
     /// unavailable.
     ///
@@ -864,6 +896,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             return Ok(root_opt);
         }
 
+        // Use the frozen block root index for finalized slots, which avoids iterating the freezer.
+        if request_slot >= self.store.get_oldest_block_slot()
+            && request_slot < self.store.get_split_slot()
+        {
+            return Ok(self.store.get_frozen_block_root(request_slot)?);
+        }
+
         if let Some(((prev_root, _), (curr_root, curr_slot))) = process_results(
             self.forwards_iter_block_roots_until(prev_slot, request_slot)?,
             |iter| iter.tuple_windows().next(),
@@ -1808,6 +1847,26 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// Accepts a `LightClientFinalityUpdate` from the network and attempts to verify it,
+    /// returning `Ok(_)` if it is valid to be (re)broadcast on the gossip network.
+    pub fn verify_finality_update_for_gossip(
+        &self,
+        finality_update: LightClientFinalityUpdate<T::EthSpec>,
+        seen_timestamp: Duration,
+    ) -> Result<VerifiedLightClientFinalityUpdate<T>, LightClientUpdateError> {
+        VerifiedLightClientFinalityUpdate::verify(finality_update, self, seen_timestamp)
+    }
+
+    /// Accepts a `LightClientOptimisticUpdate` from the network and attempts to verify it,
+    /// returning `Ok(_)` if it is valid to be (re)broadcast on the gossip network.
+    pub fn verify_optimistic_update_for_gossip(
+        &self,
+        optimistic_update: LightClientOptimisticUpdate<T::EthSpec>,
+        seen_timestamp: Duration,
+    ) -> Result<VerifiedLightClientOptimisticUpdate<T>, LightClientUpdateError> {
+        VerifiedLightClientOptimisticUpdate::verify(optimistic_update, self, seen_timestamp)
+    }
+
     /// Accepts some attestation-type object and attempts to verify it in the context of fork
     /// choice. If it is valid it is applied to `self.fork_choice`.
     ///
@@ -2130,8 +2189,32 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         )?)
     }
 
+    /// Emit a `validator_slashing` event for each of `slashings`, as returned by the validator
+    /// monitor.
+    ///
+    /// The events are registered even if there are no subscribers, so that a validator client
+    /// which reconnects to the event stream may still receive them.
+    pub fn emit_validator_slashing_events(&self, slashings: Vec<SseValidatorSlashing>) {
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            for slashing in slashings {
+                event_handler.register(EventKind::ValidatorSlashing(slashing));
+            }
+        }
+    }
+
     /// Accept some proposer slashing and queue it for inclusion in an appropriate block.
+    ///
+    /// This method is called for slashings from gossip, the API and the slasher, so this covers
+    /// all proposer slashing events.
     pub fn import_proposer_slashing(&self, proposer_slashing: SigVerifiedOp<ProposerSlashing>) {
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_proposer_slashing_subscribers() {
+                event_handler.register(EventKind::ProposerSlashing(Box::new(
+                    proposer_slashing.as_inner().clone(),
+                )));
+            }
+        }
+
         if self.eth1_chain.is_some() {
             self.op_pool.insert_proposer_slashing(proposer_slashing)
         }
@@ -2151,10 +2234,21 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     }
 
     /// Accept some attester slashing and queue it for inclusion in an appropriate block.
+    ///
+    /// This method is called for slashings from gossip, the API and the slasher, so this covers
+    /// all attester slashing events.
     pub fn import_attester_slashing(
         &self,
         attester_slashing: SigVerifiedOp<AttesterSlashing<T::EthSpec>>,
     ) -> Result<(), Error> {
+        if let Some(event_handler) = self.event_handler.as_ref() {
+            if event_handler.has_attester_slashing_subscribers() {
+                event_handler.register(EventKind::AttesterSlashing(Box::new(
+                    attester_slashing.as_inner().clone(),
+                )));
+            }
+        }
+
         if self.eth1_chain.is_some() {
             self.op_pool
                 .insert_attester_slashing(attester_slashing, self.head_info()?.fork)
@@ -2340,9 +2434,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     ) -> Result<GossipVerifiedBlock<T>, BlockError<T::EthSpec>> {
         let slot = block.slot();
         let graffiti_string = block.message().body().graffiti().as_utf8_lossy();
+        let _span = info_span!("verify_block_for_gossip", slot = %slot).entered();
 
         match GossipVerifiedBlock::new(block, self) {
             Ok(verified) => {
+                self.set_block_time(
+                    verified.block_root(),
+                    slot,
+                    BlockTimesCache::set_time_gossip_verified,
+                );
+
                 debug!(
                     self.log,
                     "Successfully processed gossip block";
@@ -2392,16 +2493,26 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // Clone the block so we can provide it to the event handler.
         let block = unverified_block.block().clone();
 
+        let span = info_span!(
+            "process_block",
+            slot = %block.slot(),
+            block_root = field::Empty,
+        );
+        let _span_guard = span.enter();
+
         // A small closure to group the verification and import errors.
         let import_block = |unverified_block: B| -> Result<Hash256, BlockError<T::EthSpec>> {
-            let fully_verified = unverified_block.into_fully_verified_block(self)?;
-            self.import_block(fully_verified)
+            let fully_verified = info_span!("verify_block")
+                .in_scope(|| unverified_block.into_fully_verified_block(self))?;
+            info_span!("import_block").in_scope(|| self.import_block(fully_verified))
         };
 
         // Verify and import the block.
         match import_block(unverified_block) {
             // The block was successfully verified and imported. Yay.
             Ok(block_root) => {
+                span.record("block_root", &field::debug(block_root));
+
                 trace!(
                     self.log,
                     "Beacon block imported";
@@ -2513,6 +2624,28 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        // If the block is from the current epoch, ensure we have its proposer shuffling in our
+        // cache so that proposer duties can be served without loading a state.
+        if state.current_epoch() == current_epoch {
+            let proposer_shuffling_decision_root =
+                state.proposer_shuffling_decision_root(block_root)?;
+
+            let proposers_are_cached = self
+                .beacon_proposer_cache
+                .lock()
+                .contains(current_epoch, proposer_shuffling_decision_root);
+
+            if !proposers_are_cached {
+                let proposers = state.get_beacon_proposer_indices(&self.spec)?;
+                self.beacon_proposer_cache.lock().insert(
+                    current_epoch,
+                    proposer_shuffling_decision_root,
+                    proposers,
+                    state.fork(),
+                )?;
+            }
+        }
+
         // Apply the state to the attester cache, only if it is from the previous epoch or later.
         //
         // In a perfect scenario there should be no need to add previous-epoch states to the cache.
@@ -2588,6 +2721,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 .map_err(|e| BlockError::BeaconChainError(e.into()))?;
         }
 
+        self.set_block_time(
+            block_root,
+            block.slot(),
+            BlockTimesCache::set_time_fork_choice_updated,
+        );
+
         // Allow the validator monitor to learn about a new valid state.
         self.validator_monitor
             .write()
@@ -2712,16 +2851,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             validator_monitor.register_block_voluntary_exit(&exit.message)
         }
 
+        let mut validator_slashings = vec![];
+
         for slashing in block.body().attester_slashings() {
-            validator_monitor.register_block_attester_slashing(slashing)
+            validator_slashings.extend(validator_monitor.register_block_attester_slashing(slashing))
         }
 
         for slashing in block.body().proposer_slashings() {
-            validator_monitor.register_block_proposer_slashing(slashing)
+            validator_slashings.extend(validator_monitor.register_block_proposer_slashing(slashing))
         }
 
         drop(validator_monitor);
 
+        self.emit_validator_slashing_events(validator_slashings);
+
         // Only present some metrics for blocks from the previous epoch or later.
         //
         // This helps avoid noise in the metrics during sync.
@@ -2773,10 +2916,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
         drop(txn_lock);
 
+        self.set_block_time(
+            block_root,
+            block.slot(),
+            BlockTimesCache::set_time_persisted,
+        );
+
         // The fork choice write-lock is dropped *after* the on-disk database has been updated.
         // This prevents inconsistency between the two at the expense of concurrency.
         drop(fork_choice);
 
+        if self.config.enable_light_client_server {
+            if let Err(e) =
+                self.process_block_for_light_client_server(block_root, block.to_ref(), &mut state)
+            {
+                warn!(
+                    self.log,
+                    "Failed to update light client data";
+                    "error" => ?e,
+                    "block_root" => ?block_root,
+                );
+            }
+        }
+
         // We're declaring the block "imported" at this point, since fork choice and the DB know
         // about it.
         let block_time_imported = timestamp_now();
@@ -2860,6 +3022,29 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(block_root)
     }
 
+    /// Store the current time in the block times cache using `set_time`, which records the time at
+    /// which the block reached some stage of import.
+    ///
+    /// Blocks older than 2 epochs are ignored, this helps reduce writes to the cache during sync.
+    pub(crate) fn set_block_time(
+        &self,
+        block_root: Hash256,
+        slot: Slot,
+        set_time: fn(&mut BlockTimesCache, Hash256, Slot, Duration),
+    ) {
+        let timestamp = timestamp_now();
+        if get_slot_delay_ms(timestamp, slot, &self.slot_clock)
+            < self.slot_clock.slot_duration() * 64
+        {
+            set_time(
+                &mut self.block_times_cache.write(),
+                block_root,
+                slot,
+                timestamp,
+            );
+        }
+    }
+
     /// Produce a new block at the given `slot`.
     ///
     /// The produced block will not be inherently valid, it must be signed by a block producer.
@@ -2873,12 +3058,75 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         metrics::inc_counter(&metrics::BLOCK_PRODUCTION_REQUESTS);
         let _complete_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_TIMES);
 
+        let (state, state_root_opt, state_load_time) =
+            self.load_state_for_block_production(slot)?;
+
+        let (block, state, _) = self.produce_block_on_state_with_payload_source(
+            state,
+            state_root_opt,
+            slot,
+            randao_reveal,
+            validator_graffiti,
+            PayloadSource::Local,
+            Some(state_load_time),
+        )?;
+
+        Ok((block, state))
+    }
+
+    /// Produce a new blinded block at the given `slot`, using an execution payload header from
+    /// the external builder.
+    ///
+    /// The produced block will not be inherently valid, it must be signed by a block producer and
+    /// then have its execution payload revealed by the builder.
+    pub fn produce_blinded_block(
+        &self,
+        randao_reveal: Signature,
+        slot: Slot,
+        validator_graffiti: Option<Graffiti>,
+    ) -> Result<BlindedBeaconBlockAndState<T::EthSpec>, BlockProductionError> {
+        metrics::inc_counter(&metrics::BLOCK_PRODUCTION_REQUESTS);
+        let _complete_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_TIMES);
+
+        if self.spec.fork_name_at_slot::<T::EthSpec>(slot) != ForkName::Merge {
+            return Err(BlockProductionError::MergeTransitionIncomplete);
+        }
+
+        let (state, state_root_opt, state_load_time) =
+            self.load_state_for_block_production(slot)?;
+
+        let (block, state, execution_payload_header) = self
+            .produce_block_on_state_with_payload_source(
+                state,
+                state_root_opt,
+                slot,
+                randao_reveal,
+                validator_graffiti,
+                PayloadSource::Builder,
+                Some(state_load_time),
+            )?;
+
+        match (block, execution_payload_header) {
+            (BeaconBlock::Merge(block), Some(execution_payload_header)) => Ok((
+                BlindedBeaconBlock::from_block(block, execution_payload_header),
+                state,
+            )),
+            _ => Err(BlockProductionError::MergeTransitionIncomplete),
+        }
+    }
+
+    /// Load a state suitable for producing a block at `slot`, along with its state root if it is
+    /// known and the time taken to load it.
+    fn load_state_for_block_production(
+        &self,
+        slot: Slot,
+    ) -> Result<(BeaconState<T::EthSpec>, Option<Hash256>, Duration), BlockProductionError> {
         // Producing a block requires the tree hash cache, so clone a full state corresponding to
         // the head from the snapshot cache. Unfortunately we can't move the snapshot out of the
         // cache (which would be fast), because we need to re-process the block after it has been
         // signed. If we miss the cache or we're producing a block that conflicts with the head,
         // fall back to getting the head from `slot - 1`.
-        let state_load_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_STATE_LOAD_TIMES);
+        let state_load_start = Instant::now();
         let head_info = self
             .head_info()
             .map_err(BlockProductionError::UnableToGetHeadInfo)?;
@@ -2918,15 +3166,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
             (state, None)
         };
-        drop(state_load_timer);
+        let state_load_time = state_load_start.elapsed();
+        metrics::observe_duration(&metrics::BLOCK_PRODUCTION_STATE_LOAD_TIMES, state_load_time);
 
-        self.produce_block_on_state(
-            state,
-            state_root_opt,
-            slot,
-            randao_reveal,
-            validator_graffiti,
-        )
+        Ok((state, state_root_opt, state_load_time))
     }
 
     /// Produce a block for some `slot` upon the given `state`.
@@ -2943,12 +3186,52 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// performing a tree hash in some scenarios.
     pub fn produce_block_on_state(
         &self,
-        mut state: BeaconState<T::EthSpec>,
+        state: BeaconState<T::EthSpec>,
         state_root_opt: Option<Hash256>,
         produce_at_slot: Slot,
         randao_reveal: Signature,
         validator_graffiti: Option<Graffiti>,
     ) -> Result<BeaconBlockAndState<T::EthSpec>, BlockProductionError> {
+        let (block, state, _) = self.produce_block_on_state_with_payload_source(
+            state,
+            state_root_opt,
+            produce_at_slot,
+            randao_reveal,
+            validator_graffiti,
+            PayloadSource::Local,
+            None,
+        )?;
+
+        Ok((block, state))
+    }
+
+    /// Produce a block for some `slot` upon the given `state`, with an execution payload from the
+    /// given `payload_source`.
+    ///
+    /// When the payload is sourced from the builder, the returned block contains a placeholder
+    /// payload and the header from the builder is returned alongside it. The state root of the
+    /// block is that of the blinded block made from the two.
+    ///
+    /// The time taken by each stage is logged along with `state_load_time`, the time taken to load
+    /// `state`, if it is known.
+    #[allow(clippy::too_many_arguments)]
+    fn produce_block_on_state_with_payload_source(
+        &self,
+        mut state: BeaconState<T::EthSpec>,
+        state_root_opt: Option<Hash256>,
+        produce_at_slot: Slot,
+        randao_reveal: Signature,
+        validator_graffiti: Option<Graffiti>,
+        payload_source: PayloadSource,
+        state_load_time: Option<Duration>,
+    ) -> Result<
+        (
+            BeaconBlock<T::EthSpec>,
+            BeaconState<T::EthSpec>,
+            Option<ExecutionPayloadHeader<T::EthSpec>>,
+        ),
+        BlockProductionError,
+    > {
         let eth1_chain = self
             .eth1_chain
             .as_ref()
@@ -2962,12 +3245,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             });
         }
 
-        let slot_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_SLOT_PROCESS_TIMES);
+        let slot_process_start = Instant::now();
 
         // Ensure the state has performed a complete transition into the required slot.
         complete_state_advance(&mut state, state_root_opt, produce_at_slot, &self.spec)?;
 
-        drop(slot_timer);
+        let slot_process_time = slot_process_start.elapsed();
+        metrics::observe_duration(
+            &metrics::BLOCK_PRODUCTION_SLOT_PROCESS_TIMES,
+            slot_process_time,
+        );
 
         state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
 
@@ -3014,8 +3301,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             None => self.graffiti,
         };
 
-        let attestation_packing_timer =
-            metrics::start_timer(&metrics::BLOCK_PRODUCTION_ATTESTATION_TIMES);
+        let attestation_packing_start = Instant::now();
 
         let mut prev_filter_cache = HashMap::new();
         let prev_attestation_filter = |att: &&Attestation<T::EthSpec>| {
@@ -3036,10 +3322,16 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             )
             .map_err(BlockProductionError::OpPoolError)?
             .into();
-        drop(attestation_packing_timer);
+        let attestation_packing_time = attestation_packing_start.elapsed();
+        metrics::observe_duration(
+            &metrics::BLOCK_PRODUCTION_ATTESTATION_TIMES,
+            attestation_packing_time,
+        );
 
         let slot = state.slot();
         let proposer_index = state.get_beacon_proposer_index(state.slot(), &self.spec)? as u64;
+        let mut execution_payload_header = None;
+        let mut execution_payload_time = None;
 
         // Closure to fetch a sync aggregate in cases where it is required.
         let get_sync_aggregate = || -> Result<SyncAggregate<_>, BlockProductionError> {
@@ -3096,7 +3388,40 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
             BeaconState::Merge(_) => {
                 let sync_aggregate = get_sync_aggregate()?;
-                let execution_payload = get_execution_payload(self, &state)?;
+                let execution_payload_start = Instant::now();
+                let execution_payload = match payload_source {
+                    PayloadSource::Local => get_execution_payload(self, &state, proposer_index)?,
+                    PayloadSource::Builder => {
+                        let header = get_execution_payload_header(self, &state, proposer_index)?;
+                        // The state transition requires a full payload, so the block is processed
+                        // with a placeholder payload which matches the header in all but its
+                        // transactions. The state is corrected after processing.
+                        let payload = ExecutionPayload {
+                            parent_hash: header.parent_hash,
+                            fee_recipient: header.fee_recipient,
+                            state_root: header.state_root,
+                            receipt_root: header.receipt_root,
+                            logs_bloom: header.logs_bloom.clone(),
+                            random: header.random,
+                            block_number: header.block_number,
+                            gas_limit: header.gas_limit,
+                            gas_used: header.gas_used,
+                            timestamp: header.timestamp,
+                            extra_data: header.extra_data.clone(),
+                            base_fee_per_gas: header.base_fee_per_gas,
+                            block_hash: header.block_hash,
+                            transactions: <_>::default(),
+                        };
+                        execution_payload_header = Some(header);
+                        payload
+                    }
+                };
+                let elapsed = execution_payload_start.elapsed();
+                metrics::observe_duration(
+                    &metrics::BLOCK_PRODUCTION_EXECUTION_PAYLOAD_TIMES,
+                    elapsed,
+                );
+                execution_payload_time = Some(elapsed);
                 BeaconBlock::Merge(BeaconBlockMerge {
                     slot,
                     proposer_index,
@@ -3137,7 +3462,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             return Err(BlockProductionError::BlockTooLarge(block_size));
         }
 
-        let process_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_PROCESS_TIMES);
+        let process_start = Instant::now();
         per_block_processing(
             &mut state,
             &block,
@@ -3146,26 +3471,45 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             VerifyBlockRoot::True,
             &self.spec,
         )?;
-        drop(process_timer);
+        let process_time = process_start.elapsed();
+        metrics::observe_duration(&metrics::BLOCK_PRODUCTION_PROCESS_TIMES, process_time);
 
-        let state_root_timer = metrics::start_timer(&metrics::BLOCK_PRODUCTION_STATE_ROOT_TIMES);
+        let (mut block, _) = block.deconstruct();
+
+        // Replace the effects of the placeholder payload on the state with those of the header,
+        // as if the blinded block had been processed. The root of the blinded body is equal to
+        // the root of the body containing the real payload.
+        if let (Some(header), BeaconBlock::Merge(merge_block)) = (&execution_payload_header, &block)
+        {
+            let blinded_block = BlindedBeaconBlock::from_block(merge_block.clone(), header.clone());
+            state.latest_block_header_mut().body_root = blinded_block.body.tree_hash_root();
+            *state.latest_execution_payload_header_mut()? = header.clone();
+        }
+
+        let state_root_start = Instant::now();
         let state_root = state.update_tree_hash_cache()?;
-        drop(state_root_timer);
+        let state_root_time = state_root_start.elapsed();
+        metrics::observe_duration(&metrics::BLOCK_PRODUCTION_STATE_ROOT_TIMES, state_root_time);
 
-        let (mut block, _) = block.deconstruct();
         *block.state_root_mut() = state_root;
 
         metrics::inc_counter(&metrics::BLOCK_PRODUCTION_SUCCESSES);
 
-        trace!(
+        debug!(
             self.log,
             "Produced beacon block";
             "parent" => ?block.parent_root(),
             "attestations" => block.body().attestations().len(),
-            "slot" => block.slot()
+            "slot" => block.slot(),
+            "state_load_time" => ?state_load_time,
+            "slot_process_time" => ?slot_process_time,
+            "attestation_packing_time" => ?attestation_packing_time,
+            "execution_payload_time" => ?execution_payload_time,
+            "block_processing_time" => ?process_time,
+            "state_root_time" => ?state_root_time,
         );
 
-        Ok((block, state))
+        Ok((block, state, execution_payload_header))
     }
 
     /// Execute the fork choice algorithm and enthrone the result as the canonical head.
@@ -3405,6 +3749,34 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     .unwrap_or_else(|| Duration::from_secs(0)),
             );
 
+            // Observe the time taken by each stage of import, skipping those the block didn't go
+            // through (e.g., gossip verification for a block received via RPC).
+            for (stage, delay) in block_delays.stages() {
+                if let Some(delay) = delay {
+                    metrics::observe_timer_vec(
+                        &metrics::BEACON_BLOCK_IMPORT_STAGE_DELAY_TIME,
+                        &[stage],
+                        delay,
+                    );
+                }
+            }
+
+            debug!(
+                self.log,
+                "Block delay breakdown";
+                "block_root" => ?beacon_block_root,
+                "slot" => head_slot,
+                "block_delay" => ?block_delay_total,
+                "observed_delay" => ?block_delays.observed,
+                "gossip_verified_delay" => ?block_delays.gossip_verified,
+                "state_advanced_delay" => ?block_delays.state_advanced,
+                "payload_verified_delay" => ?block_delays.payload_verified,
+                "executed_delay" => ?block_delays.executed,
+                "fork_choice_updated_delay" => ?block_delays.fork_choice_updated,
+                "persisted_delay" => ?block_delays.persisted,
+                "set_as_head_delay" => ?block_delays.set_as_head,
+            );
+
             // If the block was enshrined as head too late for attestations to be created for it,
             // log a debug warning and increment a metric.
             if late_head {
@@ -3503,6 +3875,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .ok_or(Error::MissingFinalizedStateRoot(new_finalized_slot))?;
 
             self.after_finalization(&head.beacon_state, new_finalized_state_root)?;
+            drop(head);
+
+            if self.config.enable_light_client_server {
+                if let Err(e) = self.prune_light_client_updates(
+                    old_finalized_checkpoint.epoch,
+                    new_finalized_checkpoint.epoch,
+                ) {
+                    warn!(
+                        self.log,
+                        "Failed to prune light client updates";
+                        "error" => ?e,
+                    );
+                }
+            }
         }
 
         // Register a server-sent event if necessary
@@ -3585,6 +3971,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                             store,
                             new_finalized_checkpoint.root,
                             new_head_execution_block_hash,
+                            None,
                         )
                         .await
                         {
@@ -3608,6 +3995,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         store: BeaconStore<T>,
         finalized_beacon_block_root: Hash256,
         head_execution_block_hash: Hash256,
+        payload_attributes: Option<PayloadAttributes>,
     ) -> Result<(), Error> {
         // Loading the finalized block from the store is not ideal. Perhaps it would be better to
         // store it on fork-choice so we can do a lookup without hitting the database.
@@ -3629,12 +4017,83 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             .notify_forkchoice_updated(
                 head_execution_block_hash,
                 finalized_execution_block_hash,
-                None,
+                payload_attributes,
             )
             .await
             .map_err(Error::ExecutionForkChoiceUpdateFailed)
     }
 
+    /// Announce the attributes of the payload for a proposal at `state.slot()` to the execution
+    /// engines, so that they can start building it ahead of time.
+    ///
+    /// The `state` should be the head state, advanced to the slot of the proposal. The
+    /// attributes are only sent if a validator client has supplied preparation data for the
+    /// proposer, otherwise the proposer is presumed to be connected to some other node.
+    ///
+    /// The update is spawned on the execution layer's runtime, without waiting for it to
+    /// complete.
+    pub fn prepare_beacon_proposer(&self, state: &BeaconState<T::EthSpec>) -> Result<(), Error> {
+        let execution_layer = match &self.execution_layer {
+            Some(execution_layer) => execution_layer.clone(),
+            None => return Ok(()),
+        };
+
+        // Prior to the merge there is no execution head to build upon.
+        if !is_merge_transition_complete(state) {
+            return Ok(());
+        }
+
+        let proposer_index = state.get_beacon_proposer_index(state.slot(), &self.spec)? as u64;
+        let timestamp =
+            compute_timestamp_at_slot(state, &self.spec).map_err(BeaconStateError::from)?;
+        let random = *state.get_randao_mix(state.current_epoch())?;
+        let head_execution_block_hash = state.latest_execution_payload_header()?.block_hash;
+        let finalized_beacon_block_root = state.finalized_checkpoint().root;
+        let current_epoch = state.current_epoch();
+        let store = self.store.clone();
+        let log = self.log.clone();
+
+        execution_layer.spawn(
+            move |execution_layer| async move {
+                execution_layer.clean_proposer_caches(current_epoch).await;
+
+                let payload_attributes = match execution_layer
+                    .payload_attributes(timestamp, random, proposer_index)
+                    .await
+                {
+                    Some(payload_attributes) => payload_attributes,
+                    None => return,
+                };
+
+                debug!(
+                    log,
+                    "Preparing execution payload";
+                    "proposer_index" => proposer_index,
+                    "timestamp" => timestamp,
+                );
+
+                if let Err(e) = Self::update_execution_engine_forkchoice(
+                    execution_layer,
+                    store,
+                    finalized_beacon_block_root,
+                    head_execution_block_hash,
+                    Some(payload_attributes),
+                )
+                .await
+                {
+                    debug!(
+                        log,
+                        "Failed to prepare execution payload";
+                        "error" => ?e
+                    );
+                }
+            },
+            "prepare_beacon_proposer",
+        );
+
+        Ok(())
+    }
+
     /// Returns the status of the current head block, regarding the validity of the execution
     /// payload.
     pub fn head_safety_status(&self) -> Result<HeadSafetyStatus, BeaconChainError> {
@@ -3655,6 +4114,144 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         Ok(status)
     }
 
+    /// Returns the most recent ancestor of `block_root` (inclusive) which has either been verified
+    /// by an EL or does not require EL verification.
+    pub fn latest_verified_ancestor(
+        &self,
+        block_root: Hash256,
+    ) -> Result<ProtoBlock, BeaconChainError> {
+        let fork_choice = self.fork_choice.read();
+        let mut block = fork_choice
+            .get_block(&block_root)
+            .ok_or(BeaconChainError::MissingBeaconBlock(block_root))?;
+
+        loop {
+            match block.execution_status {
+                ExecutionStatus::Valid(_) | ExecutionStatus::Irrelevant(_) => return Ok(block),
+                // The ancestors of a block with an invalid payload cannot be verified.
+                ExecutionStatus::Invalid(_) => {
+                    return Err(BeaconChainError::NoVerifiedAncestor(block_root))
+                }
+                ExecutionStatus::Unknown(_) => {
+                    block = block
+                        .parent_root
+                        .and_then(|parent_root| fork_choice.get_block(&parent_root))
+                        .ok_or(BeaconChainError::NoVerifiedAncestor(block_root))?;
+                }
+            }
+        }
+    }
+
+    /// Produces an unaggregated attestation as per `Self::produce_unaggregated_attestation`,
+    /// except that if the head block has not yet been verified by an EL (i.e., it was imported
+    /// optimistically), the attestation is to the most recent ancestor of the head which has been
+    /// verified.
+    ///
+    /// Attesting to an ancestor is equivalent to treating the unverified blocks as missing, which
+    /// avoids voting for a chain which may later be found to be invalid.
+    pub fn produce_unaggregated_attestation_for_verified_ancestor(
+        &self,
+        request_slot: Slot,
+        request_index: CommitteeIndex,
+    ) -> Result<Attestation<T::EthSpec>, Error> {
+        let head_block_root = self.head_info()?.block_root;
+        let ancestor = self.latest_verified_ancestor(head_block_root)?;
+
+        // Attestations to slots prior to the verified ancestor are for blocks in the chain of the
+        // verified ancestor, so they may be produced from the head as usual.
+        if ancestor.root == head_block_root || request_slot < ancestor.slot {
+            return self.produce_unaggregated_attestation(request_slot, request_index);
+        }
+
+        debug!(
+            self.log,
+            "Attesting to verified ancestor of head";
+            "head_block_root" => ?head_block_root,
+            "ancestor_root" => ?ancestor.root,
+            "ancestor_slot" => ancestor.slot,
+            "request_slot" => request_slot,
+        );
+
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let request_epoch = request_slot.epoch(slots_per_epoch);
+        let target_slot = request_epoch.start_slot(slots_per_epoch);
+        let decision_slot = target_slot.saturating_sub(1_u64);
+
+        // Find the target and the attester shuffling decision blocks in the chain of the verified
+        // ancestor, treating all of its descendants as missing. These blocks are only absent from
+        // fork choice if they precede the finalized block.
+        let (target_root, decision_root) = {
+            let fork_choice = self.fork_choice.read();
+            let root_at_slot = |slot: Slot| {
+                let mut block = ancestor.clone();
+                while block.slot > slot {
+                    block = fork_choice.get_block(&block.parent_root?)?;
+                }
+                Some(block.root)
+            };
+            let decision_root = if decision_slot.epoch(slots_per_epoch) == request_epoch {
+                // All-zeros is used as an alias to the genesis block, as per `AttesterCacheKey`.
+                Some(Hash256::zero())
+            } else {
+                root_at_slot(decision_slot)
+            };
+            (root_at_slot(target_slot), decision_root)
+        };
+
+        let (target_root, decision_root) = match (target_root, decision_root) {
+            (Some(target_root), Some(decision_root)) => (target_root, decision_root),
+            _ => {
+                let mut state = self
+                    .get_state(&ancestor.state_root, Some(ancestor.slot))?
+                    .ok_or(Error::MissingBeaconState(ancestor.state_root))?;
+                state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+
+                return self.produce_unaggregated_attestation_for_block(
+                    request_slot,
+                    request_index,
+                    ancestor.root,
+                    Cow::Owned(state),
+                    ancestor.state_root,
+                );
+            }
+        };
+
+        // The justified checkpoint and committee length are shared by every chain with the same
+        // decision block, so they are usually cached already by attestation production for the
+        // head. If not, the state of the ancestor is loaded to prime the cache.
+        let attester_cache_key = AttesterCacheKey::from_decision_root(request_epoch, decision_root);
+        let (justified_checkpoint, committee_len) = match self.attester_cache.get::<T::EthSpec>(
+            &attester_cache_key,
+            request_slot,
+            request_index,
+            &self.spec,
+        )? {
+            Some(cached_values) => cached_values,
+            None => self.attester_cache.load_and_cache_state(
+                ancestor.state_root,
+                attester_cache_key,
+                request_slot,
+                request_index,
+                self,
+            )?,
+        };
+
+        Ok(Attestation {
+            aggregation_bits: BitList::with_capacity(committee_len)?,
+            data: AttestationData {
+                slot: request_slot,
+                index: request_index,
+                beacon_block_root: ancestor.root,
+                source: justified_checkpoint,
+                target: Checkpoint {
+                    epoch: request_epoch,
+                    root: target_root,
+                },
+            },
+            signature: AggregateSignature::empty(),
+        })
+    }
+
     /// This function takes a configured weak subjectivity `Checkpoint` and the latest finalized `Checkpoint`.
     /// If the weak subjectivity checkpoint and finalized checkpoint share the same epoch, we compare
     /// roots. If we the weak subjectivity checkpoint is from an older epoch, we iterate back through
@@ -3768,12 +4365,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.attester_cache
             .prune_below(new_finalized_checkpoint.epoch);
 
+        // Persist the eth1 caches so that the deposit logs don't need to be downloaded again if
+        // the node restarts without a clean shutdown.
+        if let Err(e) = self.persist_eth1_cache() {
+            error!(
+                self.log,
+                "Failed to persist eth1 cache";
+                "error" => ?e
+            );
+        }
+
         if let Some(event_handler) = self.event_handler.as_ref() {
             if event_handler.has_finalized_subscribers() {
                 event_handler.register(EventKind::FinalizedCheckpoint(SseFinalizedCheckpoint {
                     epoch: new_finalized_checkpoint.epoch,
                     block: new_finalized_checkpoint.root,
                     state: new_finalized_state_root,
+                    head_slot: Some(head_state.slot()),
+                    justified_epoch: Some(head_state.current_justified_checkpoint().epoch),
                 }));
             }
         }
@@ -4165,13 +4774,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
 impl<T: BeaconChainTypes> Drop for BeaconChain<T> {
     fn drop(&mut self) {
-        let drop = || -> Result<(), Error> {
-            self.persist_head_and_fork_choice()?;
-            self.persist_op_pool()?;
-            self.persist_eth1_cache()
-        };
-
-        if let Err(e) = drop() {
+        if let Err(e) = self.persist_to_disk() {
             error!(
                 self.log,
                 "Failed to persist on BeaconChain drop";
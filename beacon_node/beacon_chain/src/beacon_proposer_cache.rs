@@ -98,6 +98,12 @@ impl BeaconProposerCache {
         self.cache.get(&key).map(|cache| &cache.proposers)
     }
 
+    /// Returns `true` if the proposers for `epoch` are cached for the given
+    /// `shuffling_decision_block`.
+    pub fn contains(&self, epoch: Epoch, shuffling_decision_block: Hash256) -> bool {
+        self.cache.contains(&(epoch, shuffling_decision_block))
+    }
+
     /// Insert the proposers into the cache.
     ///
     /// See `Self::get` for a description of `shuffling_decision_block`.
@@ -8,6 +8,9 @@
 //! - The block was observed late.
 //! - We were too slow to import it.
 //! - We were too slow to set it as head.
+//!
+//! The time taken to import a block is further broken down into stages (see `BlockDelays::stages`),
+//! which shows whether a slow import was bound by the CPU, the database or the execution layer.
 
 use eth2::types::{Hash256, Slot};
 use std::collections::HashMap;
@@ -18,14 +21,37 @@ type BlockRoot = Hash256;
 #[derive(Clone, Default)]
 pub struct Timestamps {
     pub observed: Option<Duration>,
+    pub gossip_verified: Option<Duration>,
+    pub state_advanced: Option<Duration>,
+    pub payload_verified: Option<Duration>,
+    pub executed: Option<Duration>,
+    pub fork_choice_updated: Option<Duration>,
+    pub persisted: Option<Duration>,
     pub imported: Option<Duration>,
     pub set_as_head: Option<Duration>,
 }
 
 // Helps arrange delay data so it is more relevant to metrics.
+//
+// The delays of the import stages, from `gossip_verified` to `persisted`, are relative to the
+// previous stage which the block went through. E.g., a block received via RPC is not gossip
+// verified, so its `state_advanced` delay is relative to when it was observed.
 #[derive(Default)]
 pub struct BlockDelays {
     pub observed: Option<Duration>,
+    /// Time taken to verify the block for propagation on gossip.
+    pub gossip_verified: Option<Duration>,
+    /// Time taken to verify the signatures, load the parent state and advance it to the block's
+    /// slot.
+    pub state_advanced: Option<Duration>,
+    /// Time taken for the execution layer to verify the execution payload.
+    pub payload_verified: Option<Duration>,
+    /// Time taken to apply the block to the state and compute the state root.
+    pub executed: Option<Duration>,
+    /// Time taken to apply the block to fork choice.
+    pub fork_choice_updated: Option<Duration>,
+    /// Time taken to write the block and state to the database.
+    pub persisted: Option<Duration>,
     pub imported: Option<Duration>,
     pub set_as_head: Option<Duration>,
 }
@@ -35,6 +61,21 @@ impl BlockDelays {
         let observed = times
             .observed
             .and_then(|observed_time| observed_time.checked_sub(slot_start_time));
+
+        let mut previous = times.observed;
+        let mut stage_delay = |stage_time: Option<Duration>| {
+            let stage_time = stage_time?;
+            let delay = previous.and_then(|previous| stage_time.checked_sub(previous));
+            previous = Some(stage_time);
+            delay
+        };
+        let gossip_verified = stage_delay(times.gossip_verified);
+        let state_advanced = stage_delay(times.state_advanced);
+        let payload_verified = stage_delay(times.payload_verified);
+        let executed = stage_delay(times.executed);
+        let fork_choice_updated = stage_delay(times.fork_choice_updated);
+        let persisted = stage_delay(times.persisted);
+
         let imported = times
             .imported
             .and_then(|imported_time| imported_time.checked_sub(times.observed?));
@@ -43,10 +84,28 @@ impl BlockDelays {
             .and_then(|set_as_head_time| set_as_head_time.checked_sub(times.imported?));
         BlockDelays {
             observed,
+            gossip_verified,
+            state_advanced,
+            payload_verified,
+            executed,
+            fork_choice_updated,
+            persisted,
             imported,
             set_as_head,
         }
     }
+
+    /// Returns the name and delay of each stage of import, in order.
+    pub fn stages(&self) -> [(&'static str, Option<Duration>); 6] {
+        [
+            ("gossip_verified", self.gossip_verified),
+            ("state_advanced", self.state_advanced),
+            ("payload_verified", self.payload_verified),
+            ("executed", self.executed),
+            ("fork_choice_updated", self.fork_choice_updated),
+            ("persisted", self.persisted),
+        ]
+    }
 }
 
 // If the block was received via gossip, we can record the client type of the peer which sent us
@@ -99,20 +158,64 @@ impl BlockTimesCache {
         };
     }
 
-    pub fn set_time_imported(&mut self, block_root: BlockRoot, slot: Slot, timestamp: Duration) {
-        let block_times = self
+    fn timestamps_mut(&mut self, block_root: BlockRoot, slot: Slot) -> &mut Timestamps {
+        &mut self
             .cache
             .entry(block_root)
-            .or_insert_with(|| BlockTimesCacheValue::new(slot));
-        block_times.timestamps.imported = Some(timestamp);
+            .or_insert_with(|| BlockTimesCacheValue::new(slot))
+            .timestamps
+    }
+
+    pub fn set_time_gossip_verified(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        timestamp: Duration,
+    ) {
+        self.timestamps_mut(block_root, slot).gossip_verified = Some(timestamp);
+    }
+
+    pub fn set_time_state_advanced(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        timestamp: Duration,
+    ) {
+        self.timestamps_mut(block_root, slot).state_advanced = Some(timestamp);
+    }
+
+    pub fn set_time_payload_verified(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        timestamp: Duration,
+    ) {
+        self.timestamps_mut(block_root, slot).payload_verified = Some(timestamp);
+    }
+
+    pub fn set_time_executed(&mut self, block_root: BlockRoot, slot: Slot, timestamp: Duration) {
+        self.timestamps_mut(block_root, slot).executed = Some(timestamp);
+    }
+
+    pub fn set_time_fork_choice_updated(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        timestamp: Duration,
+    ) {
+        self.timestamps_mut(block_root, slot).fork_choice_updated = Some(timestamp);
+    }
+
+    pub fn set_time_persisted(&mut self, block_root: BlockRoot, slot: Slot, timestamp: Duration) {
+        self.timestamps_mut(block_root, slot).persisted = Some(timestamp);
+    }
+
+    pub fn set_time_imported(&mut self, block_root: BlockRoot, slot: Slot, timestamp: Duration) {
+        self.timestamps_mut(block_root, slot).imported = Some(timestamp);
     }
 
     pub fn set_time_set_as_head(&mut self, block_root: BlockRoot, slot: Slot, timestamp: Duration) {
-        let block_times = self
-            .cache
-            .entry(block_root)
-            .or_insert_with(|| BlockTimesCacheValue::new(slot));
-        block_times.timestamps.set_as_head = Some(timestamp);
+        self.timestamps_mut(block_root, slot).set_as_head = Some(timestamp);
     }
 
     pub fn get_block_delays(
@@ -141,3 +244,120 @@ impl BlockTimesCache {
             .retain(|_, cache| cache.slot > current_slot.saturating_sub(64_u64));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Option<Duration> {
+        Some(Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn stage_delays_in_order() {
+        let times = Timestamps {
+            observed: ms(1_100),
+            gossip_verified: ms(1_150),
+            state_advanced: ms(1_300),
+            payload_verified: ms(1_700),
+            executed: ms(1_750),
+            fork_choice_updated: ms(1_760),
+            persisted: ms(1_800),
+            imported: ms(1_810),
+            set_as_head: ms(1_900),
+        };
+        let delays = BlockDelays::new(times, Duration::from_secs(1));
+
+        assert_eq!(delays.observed, ms(100));
+        assert_eq!(
+            delays.stages(),
+            [
+                ("gossip_verified", ms(50)),
+                ("state_advanced", ms(150)),
+                ("payload_verified", ms(400)),
+                ("executed", ms(50)),
+                ("fork_choice_updated", ms(10)),
+                ("persisted", ms(40)),
+            ]
+        );
+        assert_eq!(delays.imported, ms(710));
+        assert_eq!(delays.set_as_head, ms(90));
+    }
+
+    #[test]
+    fn stage_delays_missing_stages() {
+        // A block received via RPC is neither gossip verified nor, before the merge, verified by
+        // the execution layer.
+        let times = Timestamps {
+            observed: ms(1_100),
+            state_advanced: ms(1_300),
+            executed: ms(1_350),
+            persisted: ms(1_400),
+            ..Timestamps::default()
+        };
+        let delays = BlockDelays::new(times, Duration::from_secs(1));
+
+        assert_eq!(
+            delays.stages(),
+            [
+                ("gossip_verified", None),
+                ("state_advanced", ms(200)),
+                ("payload_verified", None),
+                ("executed", ms(50)),
+                ("fork_choice_updated", None),
+                ("persisted", ms(50)),
+            ]
+        );
+        assert_eq!(delays.imported, None);
+        assert_eq!(delays.set_as_head, None);
+
+        // Without an observation time there is nothing to measure the first stage against.
+        let times = Timestamps {
+            gossip_verified: ms(1_150),
+            state_advanced: ms(1_300),
+            imported: ms(1_400),
+            set_as_head: ms(1_500),
+            ..Timestamps::default()
+        };
+        let delays = BlockDelays::new(times, Duration::from_secs(1));
+
+        assert_eq!(delays.observed, None);
+        assert_eq!(delays.gossip_verified, None);
+        assert_eq!(delays.state_advanced, ms(150));
+        assert_eq!(delays.imported, None);
+        assert_eq!(delays.set_as_head, ms(100));
+    }
+
+    #[test]
+    fn stage_delays_out_of_order() {
+        // A stage recorded before the previous one has no delay, and the next stage is measured
+        // from it.
+        let times = Timestamps {
+            observed: ms(900),
+            gossip_verified: ms(1_150),
+            state_advanced: ms(1_100),
+            executed: ms(1_200),
+            persisted: ms(1_250),
+            imported: ms(800),
+            set_as_head: ms(1_300),
+            ..Timestamps::default()
+        };
+        let delays = BlockDelays::new(times, Duration::from_secs(1));
+
+        // The block was observed before the start of its slot.
+        assert_eq!(delays.observed, None);
+        assert_eq!(
+            delays.stages(),
+            [
+                ("gossip_verified", ms(250)),
+                ("state_advanced", None),
+                ("payload_verified", None),
+                ("executed", ms(100)),
+                ("fork_choice_updated", None),
+                ("persisted", ms(50)),
+            ]
+        );
+        assert_eq!(delays.imported, None);
+        assert_eq!(delays.set_as_head, ms(500));
+    }
+}
@@ -40,8 +40,9 @@
 //!            END
 //!
 //! ```
+use crate::block_times_cache::BlockTimesCache;
 use crate::execution_payload::{
-    execute_payload, validate_execution_payload_for_gossip, validate_merge_block,
+    notify_new_payload, validate_execution_payload_for_gossip, validate_merge_block,
 };
 use crate::snapshot_cache::PreProcessingSnapshot;
 use crate::validator_monitor::HISTORIC_EPOCHS as VALIDATOR_MONITOR_HISTORIC_EPOCHS;
@@ -72,6 +73,7 @@ use std::fs;
 use std::io::Write;
 use std::time::Duration;
 use store::{Error as DBError, HotColdDB, HotStateSummary, KeyValueStore, StoreOp};
+use strum::AsRefStr;
 use tree_hash::TreeHash;
 use types::{
     BeaconBlockRef, BeaconState, BeaconStateError, ChainSpec, CloneConfig, Epoch, EthSpec, Hash256,
@@ -92,7 +94,7 @@ const WRITE_BLOCK_PROCESSING_SSZ: bool = cfg!(feature = "write_ssz_files");
 ///
 /// - The block is malformed/invalid (indicated by all results other than `BeaconChainError`.
 /// - We encountered an error whilst trying to verify the block (a `BeaconChainError`).
-#[derive(Debug)]
+#[derive(Debug, AsRefStr)]
 pub enum BlockError<T: EthSpec> {
     /// The parent block was unknown.
     ///
@@ -257,7 +259,7 @@ pub enum ExecutionPayloadError {
     ///
     /// As this is our fault, do not penalize the peer
     NoExecutionConnection,
-    /// Error occurred during engine_executePayload
+    /// Error occurred during engine_newPayload
     ///
     /// ## Peer scoring
     ///
@@ -1114,6 +1116,12 @@ impl<'a, T: BeaconChainTypes> FullyVerifiedBlock<'a, T> {
         //   early.
         // - Doing the check here means we can keep our fork-choice implementation "pure". I.e., no
         //   calls to remote servers.
+        chain.set_block_time(
+            block_root,
+            block.slot(),
+            BlockTimesCache::set_time_state_advanced,
+        );
+
         if is_merge_transition_block(&state, block.message().body()) {
             validate_merge_block(chain, block.message())?
         }
@@ -1124,7 +1132,13 @@ impl<'a, T: BeaconChainTypes> FullyVerifiedBlock<'a, T> {
         //
         // It is important that this function is called *after* `per_slot_processing`, since the
         // `randao` may change.
-        let payload_verification_status = execute_payload(chain, &state, block.message())?;
+        let payload_verification_status = notify_new_payload(chain, &state, block.message())?;
+
+        chain.set_block_time(
+            block_root,
+            block.slot(),
+            BlockTimesCache::set_time_payload_verified,
+        );
 
         // If the block is sufficiently recent, notify the validator monitor.
         if let Some(slot) = chain.slot_clock.now() {
@@ -1225,6 +1239,8 @@ impl<'a, T: BeaconChainTypes> FullyVerifiedBlock<'a, T> {
             });
         }
 
+        chain.set_block_time(block_root, block.slot(), BlockTimesCache::set_time_executed);
+
         Ok(Self {
             block,
             block_root,
@@ -20,9 +20,10 @@ use state_processing::{
     },
     signature_sets::Error as SignatureSetError,
     state_advance::Error as StateAdvanceError,
-    BlockProcessingError, BlockReplayError, SlotProcessingError,
+    BlockProcessingError, BlockReplayError, EpochProcessingError, SlotProcessingError,
 };
 use std::time::Duration;
+use strum::AsRefStr;
 use task_executor::ShutdownReason;
 use types::*;
 
@@ -36,7 +37,7 @@ macro_rules! easy_from_to {
     };
 }
 
-#[derive(Debug)]
+#[derive(Debug, AsRefStr)]
 pub enum BeaconChainError {
     InsufficientValidators,
     UnableToReadSlot,
@@ -138,8 +139,17 @@ pub enum BeaconChainError {
     ExecutionLayerMissing,
     ExecutionForkChoiceUpdateFailed(execution_layer::Error),
     HeadMissingFromForkChoice(Hash256),
+    /// No ancestor of the block has an execution payload which has been verified by an EL.
+    NoVerifiedAncestor(Hash256),
     FinalizedBlockMissingFromForkChoice(Hash256),
     InvalidFinalizedPayloadShutdownError(TrySendError<ShutdownReason>),
+    BlockProcessingError(BlockProcessingError),
+    EpochProcessingError(EpochProcessingError),
+    RewardsUnsupportedPreAltair,
+    AttestationRewardsEpochIncomplete {
+        epoch: Epoch,
+        current_epoch: Epoch,
+    },
 }
 
 easy_from_to!(SlotProcessingError, BeaconChainError);
@@ -162,6 +172,8 @@ easy_from_to!(ForkChoiceStoreError, BeaconChainError);
 easy_from_to!(HistoricalBlockError, BeaconChainError);
 easy_from_to!(StateAdvanceError, BeaconChainError);
 easy_from_to!(BlockReplayError, BeaconChainError);
+easy_from_to!(BlockProcessingError, BeaconChainError);
+easy_from_to!(EpochProcessingError, BeaconChainError);
 
 #[derive(Debug)]
 pub enum BlockProductionError {
@@ -186,6 +198,9 @@ pub enum BlockProductionError {
     BlockingFailed(execution_layer::Error),
     TerminalPoWBlockLookupFailed(execution_layer::Error),
     GetPayloadFailed(execution_layer::Error),
+    GetPayloadHeaderFailed(execution_layer::Error),
+    /// Blinded blocks can only be produced once the merge transition is complete.
+    MergeTransitionIncomplete,
     FailedToReadFinalizedBlock(store::Error),
     MissingFinalizedBlock(Hash256),
     BlockTooLarge(usize),
@@ -1,29 +1,93 @@
-pub use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
+pub use eth2::types::{
+    EventKind, EventTopic, IdentifiedEvent, SseBlock, SseBlockGossip, SseFinalizedCheckpoint,
+    SseHead, SseValidatorSlashing, ValidatorSlashingKind,
+};
+use parking_lot::Mutex;
 use slog::{trace, Logger};
+use std::collections::{HashMap, VecDeque};
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{error::SendError, Receiver, Sender};
 use types::EthSpec;
 
 const DEFAULT_CHANNEL_CAPACITY: usize = 16;
 
+/// Assigns IDs to events and retains the most recent events of each topic, so that they may be
+/// replayed to subscribers which reconnect after missing them.
+struct ReplayBuffer<T: EthSpec> {
+    next_id: u64,
+    /// The number of events to retain for each topic.
+    window: usize,
+    events: HashMap<EventTopic, VecDeque<IdentifiedEvent<T>>>,
+}
+
+impl<T: EthSpec> ReplayBuffer<T> {
+    fn new(window: usize) -> Self {
+        Self {
+            next_id: 0,
+            window,
+            events: HashMap::new(),
+        }
+    }
+
+    /// Assigns the next ID to `event`, retaining it if the window allows.
+    fn push(&mut self, event: EventKind<T>) -> IdentifiedEvent<T> {
+        let event = IdentifiedEvent {
+            id: self.next_id,
+            event,
+        };
+        self.next_id += 1;
+
+        if self.window > 0 {
+            let events = self.events.entry(event.event.topic()).or_default();
+            if events.len() >= self.window {
+                events.pop_front();
+            }
+            events.push_back(event.clone());
+        }
+
+        event
+    }
+
+    /// Returns the retained events of `topics` with IDs greater than `last_event_id`, ordered by
+    /// ID.
+    fn events_since(&self, topics: &[EventTopic], last_event_id: u64) -> Vec<IdentifiedEvent<T>> {
+        let mut events = topics
+            .iter()
+            .filter_map(|topic| self.events.get(topic))
+            .flat_map(|events| events.iter().filter(|event| event.id > last_event_id))
+            .cloned()
+            .collect::<Vec<_>>();
+        events.sort_unstable_by_key(|event| event.id);
+        events.dedup_by_key(|event| event.id);
+        events
+    }
+}
+
 pub struct ServerSentEventHandler<T: EthSpec> {
-    attestation_tx: Sender<EventKind<T>>,
-    block_tx: Sender<EventKind<T>>,
-    finalized_tx: Sender<EventKind<T>>,
-    head_tx: Sender<EventKind<T>>,
-    exit_tx: Sender<EventKind<T>>,
-    chain_reorg_tx: Sender<EventKind<T>>,
-    contribution_tx: Sender<EventKind<T>>,
-    late_head: Sender<EventKind<T>>,
+    attestation_tx: Sender<IdentifiedEvent<T>>,
+    block_tx: Sender<IdentifiedEvent<T>>,
+    finalized_tx: Sender<IdentifiedEvent<T>>,
+    head_tx: Sender<IdentifiedEvent<T>>,
+    exit_tx: Sender<IdentifiedEvent<T>>,
+    chain_reorg_tx: Sender<IdentifiedEvent<T>>,
+    contribution_tx: Sender<IdentifiedEvent<T>>,
+    late_head: Sender<IdentifiedEvent<T>>,
+    block_gossip_tx: Sender<IdentifiedEvent<T>>,
+    validator_slashing_tx: Sender<IdentifiedEvent<T>>,
+    attester_slashing_tx: Sender<IdentifiedEvent<T>>,
+    proposer_slashing_tx: Sender<IdentifiedEvent<T>>,
+    replay_window: usize,
+    replay_buffer: Mutex<ReplayBuffer<T>>,
     log: Logger,
 }
 
 impl<T: EthSpec> ServerSentEventHandler<T> {
-    pub fn new(log: Logger) -> Self {
-        Self::new_with_capacity(log, DEFAULT_CHANNEL_CAPACITY)
+    /// Create a new handler which retains the `replay_window` most recent events of each topic.
+    pub fn new(log: Logger, replay_window: usize) -> Self {
+        Self::new_with_capacity(log, DEFAULT_CHANNEL_CAPACITY, replay_window)
     }
 
-    pub fn new_with_capacity(log: Logger, capacity: usize) -> Self {
+    pub fn new_with_capacity(log: Logger, capacity: usize, replay_window: usize) -> Self {
         let (attestation_tx, _) = broadcast::channel(capacity);
         let (block_tx, _) = broadcast::channel(capacity);
         let (finalized_tx, _) = broadcast::channel(capacity);
@@ -32,6 +96,10 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
         let (chain_reorg_tx, _) = broadcast::channel(capacity);
         let (contribution_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
+        let (block_gossip_tx, _) = broadcast::channel(capacity);
+        let (validator_slashing_tx, _) = broadcast::channel(capacity);
+        let (attester_slashing_tx, _) = broadcast::channel(capacity);
+        let (proposer_slashing_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -42,98 +110,237 @@ impl<T: EthSpec> ServerSentEventHandler<T> {
             chain_reorg_tx,
             contribution_tx,
             late_head,
+            block_gossip_tx,
+            validator_slashing_tx,
+            attester_slashing_tx,
+            proposer_slashing_tx,
+            replay_window,
+            replay_buffer: Mutex::new(ReplayBuffer::new(replay_window)),
             log,
         }
     }
 
     pub fn register(&self, kind: EventKind<T>) {
-        let result = match kind {
-            EventKind::Attestation(attestation) => self
-                .attestation_tx
-                .send(EventKind::Attestation(attestation))
-                .map(|count| trace!(self.log, "Registering server-sent attestation event"; "receiver_count" => count)),
-            EventKind::Block(block) => self.block_tx.send(EventKind::Block(block))
-                .map(|count| trace!(self.log, "Registering server-sent block event"; "receiver_count" => count)),
-            EventKind::FinalizedCheckpoint(checkpoint) => self.finalized_tx
-                .send(EventKind::FinalizedCheckpoint(checkpoint))
-                .map(|count| trace!(self.log, "Registering server-sent finalized checkpoint event"; "receiver_count" => count)),
-            EventKind::Head(head) => self.head_tx.send(EventKind::Head(head))
-                .map(|count| trace!(self.log, "Registering server-sent head event"; "receiver_count" => count)),
-            EventKind::VoluntaryExit(exit) => self.exit_tx.send(EventKind::VoluntaryExit(exit))
-                .map(|count| trace!(self.log, "Registering server-sent voluntary exit event"; "receiver_count" => count)),
-            EventKind::ChainReorg(reorg) => self.chain_reorg_tx.send(EventKind::ChainReorg(reorg))
-                .map(|count| trace!(self.log, "Registering server-sent chain reorg event"; "receiver_count" => count)),
-            EventKind::ContributionAndProof(contribution_and_proof) => self.contribution_tx.send(EventKind::ContributionAndProof(contribution_and_proof))
-                .map(|count| trace!(self.log, "Registering server-sent contribution and proof event"; "receiver_count" => count)),
-            EventKind::LateHead(late_head) => self.late_head.send(EventKind::LateHead(late_head))
-                .map(|count| trace!(self.log, "Registering server-sent late head event"; "receiver_count" => count)),
-        };
+        let topic = kind.topic();
+
+        // Hold the lock whilst sending so that events are sent in order of their IDs, and so that
+        // a subscriber replaying events neither misses nor duplicates an event.
+        let mut replay_buffer = self.replay_buffer.lock();
+        let event = replay_buffer.push(kind);
+
+        let result = self.sender(topic).send(event).map(|count| {
+            trace!(
+                self.log,
+                "Registering server-sent event";
+                "topic" => %topic,
+                "receiver_count" => count
+            )
+        });
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
         }
     }
 
-    pub fn subscribe_attestation(&self) -> Receiver<EventKind<T>> {
+    fn sender(&self, topic: EventTopic) -> &Sender<IdentifiedEvent<T>> {
+        match topic {
+            EventTopic::Attestation => &self.attestation_tx,
+            EventTopic::Block => &self.block_tx,
+            EventTopic::FinalizedCheckpoint => &self.finalized_tx,
+            EventTopic::Head => &self.head_tx,
+            EventTopic::VoluntaryExit => &self.exit_tx,
+            EventTopic::ChainReorg => &self.chain_reorg_tx,
+            EventTopic::ContributionAndProof => &self.contribution_tx,
+            EventTopic::LateHead => &self.late_head,
+            EventTopic::BlockGossip => &self.block_gossip_tx,
+            EventTopic::ValidatorSlashing => &self.validator_slashing_tx,
+            EventTopic::AttesterSlashing => &self.attester_slashing_tx,
+            EventTopic::ProposerSlashing => &self.proposer_slashing_tx,
+        }
+    }
+
+    /// Subscribes to each of `topics`.
+    ///
+    /// Also returns the retained events of those topics with IDs greater than `last_event_id`,
+    /// ordered by ID. No event is both returned and received by the subscriptions.
+    pub fn subscribe_with_replay(
+        &self,
+        topics: &[EventTopic],
+        last_event_id: Option<u64>,
+    ) -> (Vec<IdentifiedEvent<T>>, Vec<Receiver<IdentifiedEvent<T>>>) {
+        let replay_buffer = self.replay_buffer.lock();
+
+        let receivers = topics
+            .iter()
+            .map(|topic| self.sender(*topic).subscribe())
+            .collect();
+        let replayed = match last_event_id {
+            Some(last_event_id) => replay_buffer.events_since(topics, last_event_id),
+            None => vec![],
+        };
+
+        (replayed, receivers)
+    }
+
+    pub fn subscribe_attestation(&self) -> Receiver<IdentifiedEvent<T>> {
         self.attestation_tx.subscribe()
     }
 
-    pub fn subscribe_block(&self) -> Receiver<EventKind<T>> {
+    pub fn subscribe_block(&self) -> Receiver<IdentifiedEvent<T>> {
         self.block_tx.subscribe()
     }
 
-    pub fn subscribe_finalized(&self) -> Receiver<EventKind<T>> {
+    pub fn subscribe_finalized(&self) -> Receiver<IdentifiedEvent<T>> {
         self.finalized_tx.subscribe()
     }
 
-    pub fn subscribe_head(&self) -> Receiver<EventKind<T>> {
+    pub fn subscribe_head(&self) -> Receiver<IdentifiedEvent<T>> {
         self.head_tx.subscribe()
     }
 
-    pub fn subscribe_exit(&self) -> Receiver<EventKind<T>> {
+    pub fn subscribe_exit(&self) -> Receiver<IdentifiedEvent<T>> {
         self.exit_tx.subscribe()
     }
 
-    pub fn subscribe_reorgs(&self) -> Receiver<EventKind<T>> {
+    pub fn subscribe_reorgs(&self) -> Receiver<IdentifiedEvent<T>> {
         self.chain_reorg_tx.subscribe()
     }
 
-    pub fn subscribe_contributions(&self) -> Receiver<EventKind<T>> {
+    pub fn subscribe_contributions(&self) -> Receiver<IdentifiedEvent<T>> {
         self.contribution_tx.subscribe()
     }
 
-    pub fn subscribe_late_head(&self) -> Receiver<EventKind<T>> {
+    pub fn subscribe_late_head(&self) -> Receiver<IdentifiedEvent<T>> {
         self.late_head.subscribe()
     }
 
+    pub fn subscribe_block_gossip(&self) -> Receiver<IdentifiedEvent<T>> {
+        self.block_gossip_tx.subscribe()
+    }
+
+    pub fn subscribe_validator_slashing(&self) -> Receiver<IdentifiedEvent<T>> {
+        self.validator_slashing_tx.subscribe()
+    }
+
+    pub fn subscribe_attester_slashing(&self) -> Receiver<IdentifiedEvent<T>> {
+        self.attester_slashing_tx.subscribe()
+    }
+
+    pub fn subscribe_proposer_slashing(&self) -> Receiver<IdentifiedEvent<T>> {
+        self.proposer_slashing_tx.subscribe()
+    }
+
+    /// Returns whether an event sent on `tx` would be observed, either by a current subscriber or
+    /// by a subscriber which reconnects and has it replayed.
+    ///
+    /// Events must be registered whilst nobody is subscribed if they are to be replayed, so the
+    /// `has_*_subscribers` methods are always true when events are retained.
+    fn is_observed(&self, tx: &Sender<IdentifiedEvent<T>>) -> bool {
+        self.replay_window > 0 || tx.receiver_count() > 0
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
-        self.attestation_tx.receiver_count() > 0
+        self.is_observed(&self.attestation_tx)
     }
 
     pub fn has_block_subscribers(&self) -> bool {
-        self.block_tx.receiver_count() > 0
+        self.is_observed(&self.block_tx)
     }
 
     pub fn has_finalized_subscribers(&self) -> bool {
-        self.finalized_tx.receiver_count() > 0
+        self.is_observed(&self.finalized_tx)
     }
 
     pub fn has_head_subscribers(&self) -> bool {
-        self.head_tx.receiver_count() > 0
+        self.is_observed(&self.head_tx)
     }
 
     pub fn has_exit_subscribers(&self) -> bool {
-        self.exit_tx.receiver_count() > 0
+        self.is_observed(&self.exit_tx)
     }
 
     pub fn has_reorg_subscribers(&self) -> bool {
-        self.chain_reorg_tx.receiver_count() > 0
+        self.is_observed(&self.chain_reorg_tx)
     }
 
     pub fn has_contribution_subscribers(&self) -> bool {
-        self.contribution_tx.receiver_count() > 0
+        self.is_observed(&self.contribution_tx)
     }
 
     pub fn has_late_head_subscribers(&self) -> bool {
-        self.late_head.receiver_count() > 0
+        self.is_observed(&self.late_head)
+    }
+
+    pub fn has_block_gossip_subscribers(&self) -> bool {
+        self.is_observed(&self.block_gossip_tx)
+    }
+
+    pub fn has_validator_slashing_subscribers(&self) -> bool {
+        self.is_observed(&self.validator_slashing_tx)
+    }
+
+    pub fn has_attester_slashing_subscribers(&self) -> bool {
+        self.is_observed(&self.attester_slashing_tx)
+    }
+
+    pub fn has_proposer_slashing_subscribers(&self) -> bool {
+        self.is_observed(&self.proposer_slashing_tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Epoch, Hash256, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    fn finalized_event(epoch: u64) -> EventKind<E> {
+        EventKind::FinalizedCheckpoint(SseFinalizedCheckpoint {
+            block: Hash256::repeat_byte(1),
+            state: Hash256::repeat_byte(2),
+            epoch: Epoch::new(epoch),
+            head_slot: None,
+        })
+    }
+
+    fn handler(replay_window: usize) -> ServerSentEventHandler<E> {
+        ServerSentEventHandler::new(Logger::root(slog::Discard, slog::o!()), replay_window)
+    }
+
+    #[test]
+    fn events_without_subscribers_are_replayed() {
+        let handler = handler(4);
+        let topics = [EventTopic::FinalizedCheckpoint];
+
+        // Receive the first event, then disconnect.
+        let (_, mut receivers) = handler.subscribe_with_replay(&topics, None);
+        assert!(handler.has_finalized_subscribers());
+        handler.register(finalized_event(1));
+        let first_event = receivers[0].try_recv().unwrap();
+        drop(receivers);
+
+        // Events which occur whilst disconnected are still registered.
+        assert!(handler.has_finalized_subscribers());
+        handler.register(finalized_event(2));
+        handler.register(finalized_event(3));
+
+        // Upon reconnecting, the missed events are replayed in order.
+        let (replayed, _receivers) = handler.subscribe_with_replay(&topics, Some(first_event.id));
+        assert_eq!(
+            replayed
+                .into_iter()
+                .map(|event| event.event)
+                .collect::<Vec<_>>(),
+            vec![finalized_event(2), finalized_event(3)]
+        );
+    }
+
+    #[test]
+    fn events_without_subscribers_are_skipped_without_replay() {
+        let handler = handler(0);
+        assert!(!handler.has_finalized_subscribers());
+
+        let _receiver = handler.subscribe_finalized();
+        assert!(handler.has_finalized_subscribers());
     }
 }
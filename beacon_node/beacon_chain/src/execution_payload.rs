@@ -27,11 +27,11 @@ use types::*;
 ///
 /// ## Specification
 ///
-/// Equivalent to the `execute_payload` function in the merge Beacon Chain Changes, although it
+/// Equivalent to the `notify_new_payload` function in the merge Beacon Chain Changes, although it
 /// contains a few extra checks by running `partially_verify_execution_payload` first:
 ///
-/// https://github.com/ethereum/consensus-specs/blob/v1.1.5/specs/merge/beacon-chain.md#execute_payload
-pub fn execute_payload<T: BeaconChainTypes>(
+/// https://github.com/ethereum/consensus-specs/blob/v1.1.6/specs/merge/beacon-chain.md#notify_new_payload
+pub fn notify_new_payload<T: BeaconChainTypes>(
     chain: &BeaconChain<T>,
     state: &BeaconState<T::EthSpec>,
     block: BeaconBlockRef<T::EthSpec>,
@@ -53,10 +53,10 @@ pub fn execute_payload<T: BeaconChainTypes>(
         .execution_layer
         .as_ref()
         .ok_or(ExecutionPayloadError::NoExecutionConnection)?;
-    let execute_payload_response = execution_layer
-        .block_on(|execution_layer| execution_layer.execute_payload(execution_payload));
+    let new_payload_response = execution_layer
+        .block_on(|execution_layer| execution_layer.notify_new_payload(execution_payload));
 
-    match execute_payload_response {
+    match new_payload_response {
         Ok((status, _latest_valid_hash)) => match status {
             ExecutePayloadResponseStatus::Valid => Ok(PayloadVerificationStatus::Verified),
             // TODO(merge): invalidate any invalid ancestors of this block in fork choice.
@@ -65,7 +65,9 @@ pub fn execute_payload<T: BeaconChainTypes>(
             }
             ExecutePayloadResponseStatus::Syncing => Ok(PayloadVerificationStatus::NotVerified),
         },
-        Err(_) => Err(ExecutionPayloadError::RejectedByExecutionEngine.into()),
+        // The payload may be valid, it just can't be verified whilst the execution engines are
+        // unavailable.
+        Err(e) => Err(ExecutionPayloadError::RequestFailed(e).into()),
     }
 }
 
@@ -204,14 +206,16 @@ pub fn validate_execution_payload_for_gossip<T: BeaconChainTypes>(
 pub fn get_execution_payload<T: BeaconChainTypes>(
     chain: &BeaconChain<T>,
     state: &BeaconState<T::EthSpec>,
+    proposer_index: u64,
 ) -> Result<ExecutionPayload<T::EthSpec>, BlockProductionError> {
-    Ok(prepare_execution_payload_blocking(chain, state)?.unwrap_or_default())
+    Ok(prepare_execution_payload_blocking(chain, state, proposer_index)?.unwrap_or_default())
 }
 
 /// Wraps the async `prepare_execution_payload` function as a blocking task.
 pub fn prepare_execution_payload_blocking<T: BeaconChainTypes>(
     chain: &BeaconChain<T>,
     state: &BeaconState<T::EthSpec>,
+    proposer_index: u64,
 ) -> Result<Option<ExecutionPayload<T::EthSpec>>, BlockProductionError> {
     let execution_layer = chain
         .execution_layer
@@ -219,7 +223,9 @@ pub fn prepare_execution_payload_blocking<T: BeaconChainTypes>(
         .ok_or(BlockProductionError::ExecutionLayerMissing)?;
 
     execution_layer
-        .block_on_generic(|_| async { prepare_execution_payload(chain, state).await })
+        .block_on_generic(|_| async {
+            prepare_execution_payload(chain, state, proposer_index).await
+        })
         .map_err(BlockProductionError::BlockingFailed)?
 }
 
@@ -240,6 +246,7 @@ pub fn prepare_execution_payload_blocking<T: BeaconChainTypes>(
 pub async fn prepare_execution_payload<T: BeaconChainTypes>(
     chain: &BeaconChain<T>,
     state: &BeaconState<T::EthSpec>,
+    proposer_index: u64,
 ) -> Result<Option<ExecutionPayload<T::EthSpec>>, BlockProductionError> {
     let spec = &chain.spec;
     let execution_layer = chain
@@ -293,16 +300,74 @@ pub async fn prepare_execution_payload<T: BeaconChainTypes>(
                 .map(|ep| ep.block_hash)
         };
 
-    // Note: the suggested_fee_recipient is stored in the `execution_layer`, it will add this parameter.
+    // Note: the suggested_fee_recipient is stored in the `execution_layer`, it will add this
+    // parameter based upon the `proposer_index`.
     let execution_payload = execution_layer
         .get_payload(
             parent_hash,
             timestamp,
             random,
             finalized_block_hash.unwrap_or_else(Hash256::zero),
+            proposer_index,
         )
         .await
         .map_err(BlockProductionError::GetPayloadFailed)?;
 
     Ok(Some(execution_payload))
 }
+
+/// Gets the header of an execution payload from the external builder, for inclusion in a blinded
+/// block proposed by `proposer_index`.
+///
+/// Will return an error if the merge transition is not yet complete, since the builder is unable
+/// to build the transition payload.
+pub fn get_execution_payload_header<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    state: &BeaconState<T::EthSpec>,
+    proposer_index: u64,
+) -> Result<ExecutionPayloadHeader<T::EthSpec>, BlockProductionError> {
+    let execution_layer = chain
+        .execution_layer
+        .as_ref()
+        .ok_or(BlockProductionError::ExecutionLayerMissing)?;
+
+    execution_layer
+        .block_on_generic(|_| async {
+            prepare_execution_payload_header(chain, state, proposer_index).await
+        })
+        .map_err(BlockProductionError::BlockingFailed)?
+}
+
+/// Requests the header of an execution payload from the external builder.
+///
+/// Unlike `prepare_execution_payload`, the payload is built by the builder and so the fee
+/// recipient is determined by the registration of the proposer with the builder.
+pub async fn prepare_execution_payload_header<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    state: &BeaconState<T::EthSpec>,
+    proposer_index: u64,
+) -> Result<ExecutionPayloadHeader<T::EthSpec>, BlockProductionError> {
+    let spec = &chain.spec;
+    let execution_layer = chain
+        .execution_layer
+        .as_ref()
+        .ok_or(BlockProductionError::ExecutionLayerMissing)?;
+
+    if !is_merge_transition_complete(state) {
+        return Err(BlockProductionError::MergeTransitionIncomplete);
+    }
+
+    let parent_hash = state.latest_execution_payload_header()?.block_hash;
+    let timestamp = compute_timestamp_at_slot(state, spec).map_err(BeaconStateError::from)?;
+    let random = *state.get_randao_mix(state.current_epoch())?;
+    let pubkey = state
+        .validators()
+        .get(proposer_index as usize)
+        .map(|validator| validator.pubkey)
+        .ok_or(BeaconStateError::UnknownValidator(proposer_index as usize))?;
+
+    execution_layer
+        .get_payload_header(parent_hash, timestamp, random, state.slot(), pubkey, spec)
+        .await
+        .map_err(BlockProductionError::GetPayloadHeaderFailed)
+}
@@ -113,6 +113,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             for slot in (block.slot().as_usize()..prev_block_slot.as_usize()).rev() {
                 chunk_writer.set(slot, block_root, &mut cold_batch)?;
             }
+            self.store
+                .store_frozen_block_root_index(block_root, block.slot(), &mut cold_batch);
 
             prev_block_slot = block.slot();
             expected_block_root = block.message().parent_root();
@@ -126,6 +128,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     self.genesis_block_root,
                     &mut cold_batch,
                 )?;
+                self.store.store_frozen_block_root_index(
+                    self.genesis_block_root,
+                    genesis_slot,
+                    &mut cold_batch,
+                );
                 prev_block_slot = genesis_slot;
                 expected_block_root = Hash256::zero();
                 break;
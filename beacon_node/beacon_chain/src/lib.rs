@@ -17,6 +17,8 @@ mod execution_payload;
 pub mod fork_revert;
 mod head_tracker;
 pub mod historical_blocks;
+mod light_client_server;
+pub mod light_client_update_verification;
 mod metrics;
 pub mod migrate;
 mod naive_aggregation_pool;
@@ -26,6 +28,7 @@ mod observed_block_producers;
 pub mod observed_operations;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
+mod rewards;
 pub mod schema_change;
 mod shuffling_cache;
 mod snapshot_cache;
@@ -0,0 +1,577 @@
+//! Produces the data served by the standard `beacon/light_client` API endpoints.
+//!
+//! A light client update is derived from a block (the *attested* block) and the sync aggregate of
+//! its child, which signs over it. By the time the child is imported the post-state of the
+//! attested block is no longer readily available, so the parts of it required by light clients
+//! are cached for recent blocks until their children arrive.
+//!
+//! The best update for each sync committee period is persisted to the database, so that updates
+//! for past periods can be served after a restart. The latest finality and optimistic updates are
+//! only kept in memory, so they are rebuilt as blocks are imported after a restart.
+//!
+//! Blocks are imported before it is known whether they will remain canonical, so the best update
+//! for a period may attest to a block which is later orphaned. Such updates are never served, and
+//! are removed once their period is finalized so that they can't stand in for the period forever.
+
+use crate::light_client_update_verification::light_client_update_delay;
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
+use lru::LruCache;
+use slot_clock::SlotClock;
+use ssz::{Decode, Encode};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::light_client_update::{
+    FinalizedRootProofLen, NextSyncCommitteeProofLen, CURRENT_SYNC_COMMITTEE_INDEX,
+    FINALIZED_ROOT_INDEX, NEXT_SYNC_COMMITTEE_INDEX,
+};
+use types::{
+    BeaconBlockHeader, BeaconBlockRef, BeaconState, ChainSpec, Epoch, EthSpec, FixedVector,
+    Hash256, LightClientBootstrap, LightClientFinalityUpdate, LightClientHeader,
+    LightClientOptimisticUpdate, LightClientUpdate, Slot, SyncAggregate, SyncCommittee,
+};
+
+/// The number of recently imported blocks for which the data required to produce updates is
+/// retained. Only a block's children can sign over it, so this need not be large.
+const PREV_BLOCK_CACHE_SIZE: usize = 32;
+
+/// The maximum number of sync committee periods for which the best update is retained, and the
+/// maximum number of updates which may be requested at once.
+const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+/// The best light client update for a sync committee period, as persisted in the database.
+struct PersistedLightClientUpdate<E: EthSpec>(LightClientUpdate<E>);
+
+impl<E: EthSpec> PersistedLightClientUpdate<E> {
+    fn db_key(period: u64) -> Hash256 {
+        Hash256::from_low_u64_be(period)
+    }
+}
+
+impl<E: EthSpec> StoreItem for PersistedLightClientUpdate<E> {
+    fn db_column() -> DBColumn {
+        DBColumn::LightClientUpdate
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.0.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        LightClientUpdate::from_ssz_bytes(bytes)
+            .map(Self)
+            .map_err(Into::into)
+    }
+}
+
+/// The parts of the post-state of a block which are required to produce light client updates
+/// attesting to it.
+#[derive(Clone)]
+struct LightClientCachedData<E: EthSpec> {
+    header: BeaconBlockHeader,
+    finalized_block_root: Hash256,
+    finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
+    next_sync_committee: Arc<SyncCommittee<E>>,
+    next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+}
+
+impl<E: EthSpec> LightClientCachedData<E> {
+    fn new(block: BeaconBlockRef<E>, state: &mut BeaconState<E>) -> Result<Self, BeaconChainError> {
+        Ok(Self {
+            header: block.block_header(),
+            finalized_block_root: state.finalized_checkpoint().root,
+            finality_branch: FixedVector::new(state.compute_merkle_proof(FINALIZED_ROOT_INDEX)?)?,
+            next_sync_committee: state.next_sync_committee()?.clone(),
+            next_sync_committee_branch: FixedVector::new(
+                state.compute_merkle_proof(NEXT_SYNC_COMMITTEE_INDEX)?,
+            )?,
+        })
+    }
+}
+
+/// Caches the data served to light clients.
+pub struct LightClientServerCache<E: EthSpec> {
+    prev_block_data: LruCache<Hash256, LightClientCachedData<E>>,
+    latest_finality_update: Option<LightClientFinalityUpdate<E>>,
+    latest_optimistic_update: Option<LightClientOptimisticUpdate<E>>,
+    /// The best update for each sync committee period, keyed by period.
+    best_updates: BTreeMap<u64, LightClientUpdate<E>>,
+    /// The finalized slot of the latest finality update forwarded or published on gossip.
+    latest_forwarded_finalized_slot: Option<Slot>,
+    /// The attested slot of the latest optimistic update forwarded or published on gossip.
+    latest_forwarded_attested_slot: Option<Slot>,
+}
+
+impl<E: EthSpec> Default for LightClientServerCache<E> {
+    fn default() -> Self {
+        Self {
+            prev_block_data: LruCache::new(PREV_BLOCK_CACHE_SIZE),
+            latest_finality_update: None,
+            latest_optimistic_update: None,
+            best_updates: BTreeMap::new(),
+            latest_forwarded_finalized_slot: None,
+            latest_forwarded_attested_slot: None,
+        }
+    }
+}
+
+impl<E: EthSpec> LightClientServerCache<E> {
+    pub(crate) fn latest_finality_update(&self) -> Option<&LightClientFinalityUpdate<E>> {
+        self.latest_finality_update.as_ref()
+    }
+
+    pub(crate) fn latest_optimistic_update(&self) -> Option<&LightClientOptimisticUpdate<E>> {
+        self.latest_optimistic_update.as_ref()
+    }
+
+    /// Records that a finality update for `finalized_slot` is being forwarded on gossip.
+    ///
+    /// Returns `false` if an update with the same or a later finalized slot was already forwarded.
+    pub(crate) fn observe_forwarded_finality_update(&mut self, finalized_slot: Slot) -> bool {
+        if self
+            .latest_forwarded_finalized_slot
+            .map_or(false, |latest| latest >= finalized_slot)
+        {
+            return false;
+        }
+        self.latest_forwarded_finalized_slot = Some(finalized_slot);
+        true
+    }
+
+    /// Records that an optimistic update for `attested_slot` is being forwarded on gossip.
+    ///
+    /// Returns `false` if an update with the same or a later attested slot was already forwarded.
+    pub(crate) fn observe_forwarded_optimistic_update(&mut self, attested_slot: Slot) -> bool {
+        if self
+            .latest_forwarded_attested_slot
+            .map_or(false, |latest| latest >= attested_slot)
+        {
+            return false;
+        }
+        self.latest_forwarded_attested_slot = Some(attested_slot);
+        true
+    }
+
+    /// Produces updates from `sync_aggregate`, which was included at `signature_slot` and signs
+    /// over the block described by `attested`.
+    ///
+    /// `finalized_header` is the header of `attested.finalized_block_root`, if it is known.
+    ///
+    /// Returns the sync committee period and update if it is the new best update for its period.
+    fn process_sync_aggregate(
+        &mut self,
+        attested: LightClientCachedData<E>,
+        finalized_header: Option<LightClientHeader>,
+        sync_aggregate: &SyncAggregate<E>,
+        signature_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<Option<(u64, LightClientUpdate<E>)>, BeaconChainError> {
+        let attested_header = LightClientHeader::from(attested.header);
+        let attested_slot = attested_header.beacon.slot;
+
+        if self
+            .latest_optimistic_update
+            .as_ref()
+            .map_or(true, |latest| {
+                latest.attested_header.beacon.slot < attested_slot
+            })
+        {
+            self.latest_optimistic_update = Some(LightClientOptimisticUpdate {
+                attested_header: attested_header.clone(),
+                sync_aggregate: sync_aggregate.clone(),
+                signature_slot,
+            });
+        }
+
+        if let Some(finalized_header) = &finalized_header {
+            if self.latest_finality_update.as_ref().map_or(true, |latest| {
+                latest.attested_header.beacon.slot < attested_slot
+            }) {
+                self.latest_finality_update = Some(LightClientFinalityUpdate {
+                    attested_header: attested_header.clone(),
+                    finalized_header: finalized_header.clone(),
+                    finality_branch: attested.finality_branch.clone(),
+                    sync_aggregate: sync_aggregate.clone(),
+                    signature_slot,
+                });
+            }
+        }
+
+        // The next sync committee is only useful to a light client if the signing committee is
+        // the one from the same period as the attested header.
+        let attested_period = attested_slot
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(spec)?;
+        let signature_period = signature_slot
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(spec)?;
+        if attested_period != signature_period {
+            return Ok(None);
+        }
+
+        // An update without finality has an empty finalized header and branch.
+        let (finalized_header, finality_branch) = match finalized_header {
+            Some(header) => (header, attested.finality_branch),
+            None => (
+                LightClientHeader::from(BeaconBlockHeader {
+                    slot: Slot::new(0),
+                    proposer_index: 0,
+                    parent_root: Hash256::zero(),
+                    state_root: Hash256::zero(),
+                    body_root: Hash256::zero(),
+                }),
+                FixedVector::default(),
+            ),
+        };
+        let update = LightClientUpdate {
+            attested_header,
+            next_sync_committee: attested.next_sync_committee,
+            next_sync_committee_branch: attested.next_sync_committee_branch,
+            finalized_header,
+            finality_branch,
+            sync_aggregate: sync_aggregate.clone(),
+            signature_slot,
+        };
+
+        let is_better = self
+            .best_updates
+            .get(&attested_period)
+            .map_or(true, |best| update_rank(&update) > update_rank(best));
+        if !is_better {
+            return Ok(None);
+        }
+
+        self.best_updates.insert(attested_period, update.clone());
+        while self.best_updates.len() > MAX_REQUEST_LIGHT_CLIENT_UPDATES as usize {
+            let oldest_period = *self.best_updates.keys().next().expect("map is not empty");
+            self.best_updates.remove(&oldest_period);
+        }
+
+        Ok(Some((attested_period, update)))
+    }
+}
+
+/// Ranks updates for the same period such that a greater rank is more useful to a light client.
+///
+/// In order of priority, an update is preferred if it has a supermajority of the sync committee,
+/// if it proves finality, if it has more participants, and finally if it is older.
+fn update_rank<E: EthSpec>(update: &LightClientUpdate<E>) -> (bool, bool, usize, Reverse<Slot>) {
+    let participants = update.sync_aggregate.num_set_bits();
+    let has_supermajority = participants * 3 >= E::sync_committee_size() * 2;
+    let has_finality = update.finality_branch.iter().any(|node| !node.is_zero());
+
+    (
+        has_supermajority,
+        has_finality,
+        participants,
+        Reverse(update.attested_header.beacon.slot),
+    )
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Updates the light client data with a newly imported `block` and its post-`state`.
+    ///
+    /// Does nothing for blocks prior to the Altair fork.
+    pub(crate) fn process_block_for_light_client_server(
+        &self,
+        block_root: Hash256,
+        block: BeaconBlockRef<T::EthSpec>,
+        state: &mut BeaconState<T::EthSpec>,
+    ) -> Result<(), BeaconChainError> {
+        if let BeaconState::Base(_) = state {
+            return Ok(());
+        }
+
+        let cached_data = LightClientCachedData::new(block, state)?;
+
+        // A block's sync aggregate signs over its parent.
+        let attested = match block.body().sync_aggregate() {
+            Ok(sync_aggregate)
+                if sync_aggregate.num_set_bits() as u64
+                    >= self.spec.min_sync_committee_participants =>
+            {
+                self.light_client_server_cache
+                    .lock()
+                    .prev_block_data
+                    .get(&block.parent_root())
+                    .cloned()
+                    .map(|attested| (attested, sync_aggregate))
+            }
+            _ => None,
+        };
+
+        if let Some((attested, sync_aggregate)) = attested {
+            let finalized_header = if attested.finalized_block_root.is_zero() {
+                None
+            } else {
+                self.get_block(&attested.finalized_block_root)?
+                    .map(|block| LightClientHeader::from(block.message().block_header()))
+            };
+
+            // After a restart, the best update for the period is only known to the database.
+            let attested_period = attested
+                .header
+                .slot
+                .epoch(T::EthSpec::slots_per_epoch())
+                .sync_committee_period(&self.spec)?;
+            if !self
+                .light_client_server_cache
+                .lock()
+                .best_updates
+                .contains_key(&attested_period)
+            {
+                if let Some(persisted) = self
+                    .store
+                    .get_item::<PersistedLightClientUpdate<T::EthSpec>>(
+                        &PersistedLightClientUpdate::<T::EthSpec>::db_key(attested_period),
+                    )?
+                {
+                    self.light_client_server_cache
+                        .lock()
+                        .best_updates
+                        .entry(attested_period)
+                        .or_insert(persisted.0);
+                }
+            }
+
+            let new_best = self
+                .light_client_server_cache
+                .lock()
+                .process_sync_aggregate(
+                    attested,
+                    finalized_header,
+                    sync_aggregate,
+                    block.slot(),
+                    &self.spec,
+                )?;
+            if let Some((period, update)) = new_best {
+                self.store.put_item(
+                    &PersistedLightClientUpdate::<T::EthSpec>::db_key(period),
+                    &PersistedLightClientUpdate(update),
+                )?;
+            }
+        }
+
+        self.light_client_server_cache
+            .lock()
+            .prev_block_data
+            .put(block_root, cached_data);
+
+        Ok(())
+    }
+
+    /// Returns the data required for a light client to start syncing from `block_root`.
+    ///
+    /// Returns `None` if the block or its state is unknown, or if the block is prior to the Altair
+    /// fork.
+    pub fn get_light_client_bootstrap(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<LightClientBootstrap<T::EthSpec>>, BeaconChainError> {
+        let block = match self.get_block(block_root)? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let mut state = match self
+            .store
+            .get_state(&block.state_root(), Some(block.slot()))?
+        {
+            Some(state) => state,
+            None => return Ok(None),
+        };
+
+        if let BeaconState::Base(_) = state {
+            return Ok(None);
+        }
+
+        Ok(Some(LightClientBootstrap {
+            header: block.message().block_header().into(),
+            current_sync_committee: state.current_sync_committee()?.clone(),
+            current_sync_committee_branch: FixedVector::new(
+                state.compute_merkle_proof(CURRENT_SYNC_COMMITTEE_INDEX)?,
+            )?,
+        }))
+    }
+
+    /// Returns the best known update for each of the `count` sync committee periods starting at
+    /// `start_period`, skipping periods for which no update is known or the best update is not
+    /// from the canonical chain.
+    ///
+    /// Updates for periods which are no longer cached are read from the database.
+    pub fn get_light_client_updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> Result<Vec<LightClientUpdate<T::EthSpec>>, BeaconChainError> {
+        let count = std::cmp::min(count, MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+        let mut updates = vec![];
+        for period in start_period..start_period.saturating_add(count) {
+            if let Some(update) = self.best_light_client_update(period)? {
+                if self.is_canonical_light_client_update(&update)? {
+                    updates.push(update);
+                }
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Removes the best update for each sync committee period between `old_finalized_epoch` and
+    /// `new_finalized_epoch` if its attested block was finalized out of the canonical chain.
+    ///
+    /// This leaves the period free to be filled by a canonical update, if any more blocks are
+    /// imported for it.
+    pub(crate) fn prune_light_client_updates(
+        &self,
+        old_finalized_epoch: Epoch,
+        new_finalized_epoch: Epoch,
+    ) -> Result<(), BeaconChainError> {
+        let finalized_slot = new_finalized_epoch.start_slot(T::EthSpec::slots_per_epoch());
+        let start_period = old_finalized_epoch.sync_committee_period(&self.spec)?;
+        let end_period = new_finalized_epoch.sync_committee_period(&self.spec)?;
+
+        for period in start_period..=end_period {
+            let update = match self.best_light_client_update(period)? {
+                Some(update) => update,
+                None => continue,
+            };
+            if update.attested_header.beacon.slot < finalized_slot
+                && !self.is_canonical_light_client_update(&update)?
+            {
+                self.light_client_server_cache
+                    .lock()
+                    .best_updates
+                    .remove(&period);
+                self.store
+                    .delete_item::<PersistedLightClientUpdate<T::EthSpec>>(
+                        &PersistedLightClientUpdate::<T::EthSpec>::db_key(period),
+                    )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the best known update for `period`, reading it from the database if it is no
+    /// longer cached.
+    fn best_light_client_update(
+        &self,
+        period: u64,
+    ) -> Result<Option<LightClientUpdate<T::EthSpec>>, BeaconChainError> {
+        let cached = self
+            .light_client_server_cache
+            .lock()
+            .best_updates
+            .get(&period)
+            .cloned();
+        match cached {
+            Some(update) => Ok(Some(update)),
+            None => {
+                Ok(self
+                    .store
+                    .get_item::<PersistedLightClientUpdate<T::EthSpec>>(
+                        &PersistedLightClientUpdate::<T::EthSpec>::db_key(period),
+                    )?
+                    .map(|persisted| persisted.0))
+            }
+        }
+    }
+
+    /// Returns `true` if the attested block of `update` is in the canonical chain.
+    fn is_canonical_light_client_update(
+        &self,
+        update: &LightClientUpdate<T::EthSpec>,
+    ) -> Result<bool, BeaconChainError> {
+        let attested_header = &update.attested_header.beacon;
+        Ok(
+            self.block_root_at_slot(attested_header.slot, WhenSlotSkipped::None)?
+                == Some(attested_header.canonical_root()),
+        )
+    }
+
+    /// Returns the finality update with the most recent attested header.
+    pub fn latest_light_client_finality_update(
+        &self,
+    ) -> Option<LightClientFinalityUpdate<T::EthSpec>> {
+        self.light_client_server_cache
+            .lock()
+            .latest_finality_update
+            .clone()
+    }
+
+    /// Returns our latest finality update if it should be published on gossip, marking it as
+    /// forwarded.
+    ///
+    /// An update is only published during the slot of the block containing its sync aggregate,
+    /// once that block has been given time to propagate, and only if no update with the same or a
+    /// later finalized header has been forwarded from gossip already.
+    pub fn light_client_finality_update_to_publish(
+        &self,
+    ) -> Result<Option<LightClientFinalityUpdate<T::EthSpec>>, BeaconChainError> {
+        let mut cache = self.light_client_server_cache.lock();
+        let update = match cache.latest_finality_update() {
+            Some(update) if self.is_light_client_publish_time(update.signature_slot)? => {
+                update.clone()
+            }
+            _ => return Ok(None),
+        };
+
+        if !cache.observe_forwarded_finality_update(update.finalized_header.beacon.slot) {
+            return Ok(None);
+        }
+
+        Ok(Some(update))
+    }
+
+    /// Returns our latest optimistic update if it should be published on gossip, marking it as
+    /// forwarded.
+    ///
+    /// As for finality updates, except that the update is not published if an update with the
+    /// same or a later attested header has been forwarded from gossip already.
+    pub fn light_client_optimistic_update_to_publish(
+        &self,
+    ) -> Result<Option<LightClientOptimisticUpdate<T::EthSpec>>, BeaconChainError> {
+        let mut cache = self.light_client_server_cache.lock();
+        let update = match cache.latest_optimistic_update() {
+            Some(update) if self.is_light_client_publish_time(update.signature_slot)? => {
+                update.clone()
+            }
+            _ => return Ok(None),
+        };
+
+        if !cache.observe_forwarded_optimistic_update(update.attested_header.beacon.slot) {
+            return Ok(None);
+        }
+
+        Ok(Some(update))
+    }
+
+    /// Returns `true` if updates signed at `signature_slot` should be published now, which is
+    /// during that slot once its block has been given time to propagate.
+    fn is_light_client_publish_time(&self, signature_slot: Slot) -> Result<bool, BeaconChainError> {
+        let now = self
+            .slot_clock
+            .now_duration()
+            .ok_or(BeaconChainError::UnableToReadSlot)?;
+        if signature_slot != self.slot()? {
+            return Ok(false);
+        }
+
+        let publish_time = self
+            .slot_clock
+            .start_of(signature_slot)
+            .ok_or(BeaconChainError::UnableToReadSlot)?
+            + light_client_update_delay(self);
+        Ok(now >= publish_time)
+    }
+
+    /// Returns the optimistic update with the most recent attested header.
+    pub fn latest_light_client_optimistic_update(
+        &self,
+    ) -> Option<LightClientOptimisticUpdate<T::EthSpec>> {
+        self.light_client_server_cache
+            .lock()
+            .latest_optimistic_update
+            .clone()
+    }
+}
@@ -0,0 +1,204 @@
+//! Provides verification for the light client updates received on gossip:
+//!
+//! - `LightClientFinalityUpdate` received on the `light_client_finality_update` topic.
+//! - `LightClientOptimisticUpdate` received on the `light_client_optimistic_update` topic.
+//!
+//! Rather than verifying the sync aggregate of an update, an update is only forwarded if it
+//! exactly matches the update we computed locally whilst importing blocks. Each topic only
+//! forwards updates which are newer than all updates previously forwarded on it.
+
+use crate::{
+    beacon_chain::MAXIMUM_GOSSIP_CLOCK_DISPARITY, BeaconChain, BeaconChainError, BeaconChainTypes,
+};
+use derivative::Derivative;
+use slot_clock::SlotClock;
+use std::time::Duration;
+use strum::AsRefStr;
+use types::{LightClientFinalityUpdate, LightClientOptimisticUpdate, Slot};
+
+/// Returned when a light client update was not successfully verified. It might not have been
+/// verified for two reasons:
+///
+/// - The update is malformed or inappropriate for the context (indicated by all variants other
+///   than `BeaconChainError`).
+/// - The application encountered an internal error whilst attempting to determine validity
+///   (the `BeaconChainError` variant)
+#[derive(Debug, AsRefStr)]
+pub enum Error {
+    /// The light client server is not enabled, so there is no local update to compare against.
+    ///
+    /// ## Peer scoring
+    ///
+    /// We should not be subscribed to the topic, the peer is not at fault.
+    LightClientServerDisabled,
+    /// The headers of the update are not in the order required of a valid update. The
+    /// `signature_slot` must be later than the attested header, which must be no earlier than the
+    /// finalized header.
+    ///
+    /// ## Peer scoring
+    ///
+    /// The peer has sent an invalid message.
+    InvalidSlotOrdering {
+        signature_slot: Slot,
+        attested_slot: Slot,
+    },
+    /// The update was received before the block at `signature_slot` was given a third of a slot to
+    /// propagate.
+    ///
+    /// ## Peer scoring
+    ///
+    /// The peer may have a clock which is slightly ahead of ours, or the update may have been
+    /// received early from a peer which is not at fault. The update may still be valid.
+    TooEarly { signature_slot: Slot },
+    /// An update at least as recent as this one has already been forwarded on the topic.
+    ///
+    /// ## Peer scoring
+    ///
+    /// The update may be valid, however we have already forwarded one which is no older.
+    UpdateAlreadySeen { slot: Slot },
+    /// The update does not match the update we computed locally.
+    ///
+    /// ## Peer scoring
+    ///
+    /// It's unclear if the update is valid, we may not have imported the same blocks as the peer.
+    UpdateMismatch,
+    /// There was an error whilst processing the update. It is not known if it is valid or
+    /// invalid.
+    ///
+    /// ## Peer scoring
+    ///
+    /// We were unable to process this update due to an internal error. It's unclear if the update
+    /// is valid.
+    BeaconChainError(BeaconChainError),
+}
+
+impl From<BeaconChainError> for Error {
+    fn from(e: BeaconChainError) -> Self {
+        Error::BeaconChainError(e)
+    }
+}
+
+/// Wraps a `LightClientFinalityUpdate` that has been verified for propagation on the gossip
+/// network.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "T: BeaconChainTypes"))]
+pub struct VerifiedLightClientFinalityUpdate<T: BeaconChainTypes> {
+    update: LightClientFinalityUpdate<T::EthSpec>,
+}
+
+impl<T: BeaconChainTypes> VerifiedLightClientFinalityUpdate<T> {
+    /// Returns `Ok(Self)` if `update` is valid to be (re)published on the gossip network.
+    pub fn verify(
+        update: LightClientFinalityUpdate<T::EthSpec>,
+        chain: &BeaconChain<T>,
+        seen_timestamp: Duration,
+    ) -> Result<Self, Error> {
+        if !chain.config.enable_light_client_server {
+            return Err(Error::LightClientServerDisabled);
+        }
+
+        let attested_slot = update.attested_header.beacon.slot;
+        if update.signature_slot <= attested_slot
+            || update.finalized_header.beacon.slot > attested_slot
+        {
+            return Err(Error::InvalidSlotOrdering {
+                signature_slot: update.signature_slot,
+                attested_slot,
+            });
+        }
+
+        verify_propagation_delay(chain, update.signature_slot, seen_timestamp)?;
+
+        let finalized_slot = update.finalized_header.beacon.slot;
+        let mut cache = chain.light_client_server_cache.lock();
+        if cache.latest_finality_update() != Some(&update) {
+            return Err(Error::UpdateMismatch);
+        }
+        if !cache.observe_forwarded_finality_update(finalized_slot) {
+            return Err(Error::UpdateAlreadySeen {
+                slot: finalized_slot,
+            });
+        }
+
+        Ok(Self { update })
+    }
+
+    /// Returns the underlying update.
+    pub fn update(&self) -> &LightClientFinalityUpdate<T::EthSpec> {
+        &self.update
+    }
+}
+
+/// Wraps a `LightClientOptimisticUpdate` that has been verified for propagation on the gossip
+/// network.
+#[derive(Derivative)]
+#[derivative(Clone(bound = "T: BeaconChainTypes"))]
+pub struct VerifiedLightClientOptimisticUpdate<T: BeaconChainTypes> {
+    update: LightClientOptimisticUpdate<T::EthSpec>,
+}
+
+impl<T: BeaconChainTypes> VerifiedLightClientOptimisticUpdate<T> {
+    /// Returns `Ok(Self)` if `update` is valid to be (re)published on the gossip network.
+    pub fn verify(
+        update: LightClientOptimisticUpdate<T::EthSpec>,
+        chain: &BeaconChain<T>,
+        seen_timestamp: Duration,
+    ) -> Result<Self, Error> {
+        if !chain.config.enable_light_client_server {
+            return Err(Error::LightClientServerDisabled);
+        }
+
+        let attested_slot = update.attested_header.beacon.slot;
+        if update.signature_slot <= attested_slot {
+            return Err(Error::InvalidSlotOrdering {
+                signature_slot: update.signature_slot,
+                attested_slot,
+            });
+        }
+
+        verify_propagation_delay(chain, update.signature_slot, seen_timestamp)?;
+
+        let mut cache = chain.light_client_server_cache.lock();
+        if cache.latest_optimistic_update() != Some(&update) {
+            return Err(Error::UpdateMismatch);
+        }
+        if !cache.observe_forwarded_optimistic_update(attested_slot) {
+            return Err(Error::UpdateAlreadySeen {
+                slot: attested_slot,
+            });
+        }
+
+        Ok(Self { update })
+    }
+
+    /// Returns the underlying update.
+    pub fn update(&self) -> &LightClientOptimisticUpdate<T::EthSpec> {
+        &self.update
+    }
+}
+
+/// Returns the time into `signature_slot` after which updates signed in that slot may be
+/// forwarded, giving the block containing the signatures time to propagate.
+pub fn light_client_update_delay<T: BeaconChainTypes>(chain: &BeaconChain<T>) -> Duration {
+    chain.slot_clock.slot_duration() / 3
+}
+
+/// Verify that an update signed at `signature_slot` was not seen before the block at that slot
+/// was given time to propagate, with respect to the gossip clock disparity.
+fn verify_propagation_delay<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    signature_slot: Slot,
+    seen_timestamp: Duration,
+) -> Result<(), Error> {
+    let earliest_permissible_time = chain
+        .slot_clock
+        .start_of(signature_slot)
+        .ok_or(BeaconChainError::UnableToReadSlot)?
+        + light_client_update_delay(chain);
+
+    if seen_timestamp + MAXIMUM_GOSSIP_CLOCK_DISPARITY < earliest_permissible_time {
+        Err(Error::TooEarly { signature_slot })
+    } else {
+        Ok(())
+    }
+}
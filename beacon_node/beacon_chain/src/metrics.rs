@@ -3,6 +3,7 @@ use crate::types::consts::altair::SYNC_COMMITTEE_SUBNET_COUNT;
 use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use lazy_static::lazy_static;
 pub use lighthouse_metrics::*;
+use operation_pool::AttestationStats;
 use slot_clock::SlotClock;
 use std::time::Duration;
 use types::{BeaconState, Epoch, EthSpec, Hash256, Slot};
@@ -106,6 +107,10 @@ lazy_static! {
         "beacon_block_production_attestation_seconds",
         "Time taken to pack attestations into a block"
     );
+    pub static ref BLOCK_PRODUCTION_EXECUTION_PAYLOAD_TIMES: Result<Histogram> = try_create_histogram(
+        "beacon_block_production_execution_payload_seconds",
+        "Time taken to get the execution payload (or the builder's payload header) for a block"
+    );
     pub static ref BLOCK_PRODUCTION_PROCESS_TIMES: Result<Histogram> = try_create_histogram(
         "beacon_block_production_process_seconds",
         "Time taken to process the block produced"
@@ -406,6 +411,20 @@ lazy_static! {
         try_create_int_gauge("beacon_op_pool_voluntary_exits_total", "Count of voluntary exits in the op pool");
     pub static ref OP_POOL_NUM_SYNC_CONTRIBUTIONS: Result<IntGauge> =
         try_create_int_gauge("beacon_op_pool_sync_contributions_total", "Count of sync contributions in the op pool");
+    pub static ref OP_POOL_ATTESTATIONS_PER_SLOT: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "beacon_op_pool_attestations_per_slot",
+        "Count of attestations in the op pool for the slot `slot_offset` slots before the current slot",
+        &["slot_offset", "type"]
+    );
+    pub static ref OP_POOL_NEXT_BLOCK_ATTESTATION_CANDIDATES: Result<IntGauge> = try_create_int_gauge(
+        "beacon_op_pool_next_block_attestation_candidates",
+        "Estimated count of attestations in the op pool which would be valid for inclusion in a \
+        block at the next slot"
+    );
+    pub static ref OP_POOL_NEXT_BLOCK_ATTESTATIONS: Result<IntGauge> = try_create_int_gauge(
+        "beacon_op_pool_next_block_attestations",
+        "Estimated count of attestations from the op pool which would fit in a block at the next slot"
+    );
 
 
     /*
@@ -664,6 +683,18 @@ lazy_static! {
             "Is the validator in the current sync committee (1 for true and 0 for false)",
             &["validator"]
         );
+    pub static ref VALIDATOR_MONITOR_PREV_EPOCH_ATTESTATION_EFFECTIVENESS: Result<GaugeVec> =
+        try_create_float_gauge_vec(
+            "validator_monitor_prev_epoch_attestation_effectiveness_ratio",
+            "The proportion of the source, target and head votes of the validator's attestation which were included on-chain and correct, scaled by the optimal inclusion distance over the actual inclusion distance where it is known.",
+            &["validator"]
+        );
+    pub static ref VALIDATOR_MONITOR_CURRENT_EPOCH_SYNC_COMMITTEE_PARTICIPATION: Result<GaugeVec> =
+        try_create_float_gauge_vec(
+            "validator_monitor_current_epoch_sync_committee_participation_ratio",
+            "The proportion of the slots in the epoch for which the validator's sync committee signature was included in a block. Only updated whilst the validator is in the current sync committee.",
+            &["validator"]
+        );
 
     /*
      * Validator Monitor Metrics (real-time)
@@ -779,6 +810,12 @@ lazy_static! {
         "beacon_block_imported_observed_delay_time",
         "Duration between the time the block was observed and the time when it was imported.",
     );
+    pub static ref BEACON_BLOCK_IMPORT_STAGE_DELAY_TIME: Result<HistogramVec> = try_create_histogram_vec(
+        "beacon_block_import_stage_delay_time",
+        "Duration between the time the block completed the previous stage of import and the time \
+        it completed this stage.",
+        &["stage"]
+    );
     pub static ref BEACON_BLOCK_HEAD_IMPORTED_DELAY_TIME: Result<Histogram> = try_create_histogram(
         "beacon_block_head_imported_delay_time",
         "Duration between the time the block was imported and the time when it was set as head.",
@@ -911,16 +948,27 @@ lazy_static! {
 pub fn scrape_for_metrics<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>) {
     let _ = beacon_chain.with_head(|head| {
         scrape_head_state(&head.beacon_state, head.beacon_state_root());
+
+        let forecast = beacon_chain
+            .op_pool
+            .forecast_attestation_packing(&head.beacon_state, &beacon_chain.spec);
+        set_gauge_by_usize(
+            &OP_POOL_NEXT_BLOCK_ATTESTATION_CANDIDATES,
+            forecast.num_candidates,
+        );
+        set_gauge_by_usize(&OP_POOL_NEXT_BLOCK_ATTESTATIONS, forecast.num_included);
+
         Ok::<_, BeaconChainError>(())
     });
 
+    let attestation_stats = beacon_chain.op_pool.attestation_stats();
+
     if let Some(slot) = beacon_chain.slot_clock.now() {
         scrape_attestation_observation(slot, beacon_chain);
         scrape_sync_committee_observation(slot, beacon_chain);
+        scrape_op_pool_attestations_per_slot::<T::EthSpec>(slot, &attestation_stats);
     }
 
-    let attestation_stats = beacon_chain.op_pool.attestation_stats();
-
     if let Some(snapshot_cache) = beacon_chain
         .snapshot_cache
         .try_write_for(SNAPSHOT_CACHE_TIMEOUT)
@@ -966,6 +1014,35 @@ pub fn scrape_for_metrics<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>) {
         .scrape_metrics(&beacon_chain.slot_clock, &beacon_chain.spec);
 }
 
+/// Scrape the number of attestations in the op pool for each of the slots from which attestations
+/// are retained.
+fn scrape_op_pool_attestations_per_slot<E: EthSpec>(
+    current_slot: Slot,
+    attestation_stats: &AttestationStats,
+) {
+    // Attestations are retained until the end of the epoch after their target epoch.
+    for slot_offset in 0..2 * E::slots_per_epoch() {
+        let counts = current_slot
+            .as_u64()
+            .checked_sub(slot_offset)
+            .and_then(|slot| attestation_stats.per_slot.get(&Slot::new(slot)))
+            .copied()
+            .unwrap_or_default();
+        let slot_offset = slot_offset.to_string();
+
+        set_gauge_vec(
+            &OP_POOL_ATTESTATIONS_PER_SLOT,
+            &[&slot_offset, "unaggregated"],
+            counts.unaggregated as i64,
+        );
+        set_gauge_vec(
+            &OP_POOL_ATTESTATIONS_PER_SLOT,
+            &[&slot_offset, "aggregated"],
+            counts.aggregated as i64,
+        );
+    }
+}
+
 /// Scrape the given `state` assuming it's the head state, updating the `DEFAULT_REGISTRY`.
 fn scrape_head_state<T: EthSpec>(state: &BeaconState<T>, state_root: Hash256) {
     set_gauge_by_slot(&HEAD_STATE_SLOT, state.slot());
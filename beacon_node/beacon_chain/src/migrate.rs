@@ -6,6 +6,7 @@ use parking_lot::Mutex;
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -31,6 +32,8 @@ pub struct BackgroundMigrator<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>
     db: Arc<HotColdDB<E, Hot, Cold>>,
     #[allow(clippy::type_complexity)]
     tx_thread: Option<Mutex<(mpsc::Sender<Notification>, thread::JoinHandle<()>)>>,
+    /// Set once the background thread has been stopped by `Self::shutdown`.
+    shut_down: AtomicBool,
     /// Genesis block root, for persisting the `PersistedBeaconChain`.
     genesis_block_root: Hash256,
     log: Logger,
@@ -101,11 +104,36 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         Self {
             db,
             tx_thread,
+            shut_down: AtomicBool::new(false),
             genesis_block_root,
             log,
         }
     }
 
+    /// Stop the background thread, waiting for it to complete the notifications it has already
+    /// been sent, so that the process doesn't exit part-way through a migration.
+    ///
+    /// Notifications sent afterwards are ignored.
+    pub fn shutdown(&self) {
+        if let Some(tx_thread) = &self.tx_thread {
+            let (ref mut tx, ref mut thread) = *tx_thread.lock();
+            if self.shut_down.swap(true, Ordering::SeqCst) {
+                return;
+            }
+
+            // Dropping the sender ends the thread once its channel is empty.
+            *tx = mpsc::channel().0;
+            let thread = mem::replace(thread, thread::spawn(|| ()));
+            if let Err(e) = thread.join() {
+                warn!(
+                    self.log,
+                    "Migration thread failed during shutdown";
+                    "reason" => format!("{:?}", e)
+                );
+            }
+        }
+    }
+
     /// Process a finalized checkpoint from the `BeaconChain`.
     ///
     /// If successful, all forks descending from before the `finalized_checkpoint` will be
@@ -161,6 +189,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
         if let Some(tx_thread) = &self.tx_thread {
             let (ref mut tx, ref mut thread) = *tx_thread.lock();
 
+            if self.shut_down.load(Ordering::SeqCst) {
+                debug!(self.log, "Ignoring database migration during shutdown");
+                return None;
+            }
+
             // Restart the background thread if it has crashed.
             if let Err(tx_err) = tx.send(notif) {
                 let (new_tx, new_thread) = Self::spawn_thread(self.db.clone(), self.log.clone());
@@ -256,6 +289,13 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
             }
         };
 
+        // Drop the execution payloads of blocks which have passed the retention period.
+        if let Some(retention_epochs) = db.payload_retention_epochs() {
+            if let Err(e) = db.prune_payloads(retention_epochs) {
+                warn!(log, "Execution payload pruning failed"; "error" => ?e);
+            }
+        }
+
         // Finally, compact the database so that new free space is properly reclaimed.
         if let Err(e) = Self::run_compaction(
             db,
@@ -0,0 +1,488 @@
+//! Computes the validator rewards served by the standard `beacon/rewards` API endpoints.
+//!
+//! Rewards are computed by replaying a block (or the end of an epoch) on top of its pre-state.
+//! Since the rewards for some block or epoch never change, the results are kept in a small LRU
+//! cache so that repeated queries from explorers don't repeat the replay.
+
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes, StateSkipConfig, WhenSlotSkipped};
+use eth2::types::{
+    IdealAttestationRewards, StandardAttestationRewards, StandardBlockReward, SyncCommitteeReward,
+    TotalAttestationRewards, ValidatorId,
+};
+use lru::LruCache;
+use safe_arith::SafeArith;
+use state_processing::{
+    common::altair::get_base_reward_per_increment,
+    per_block_processing::{
+        compute_sync_aggregate_rewards,
+        process_operations::{
+            process_attestations, process_attester_slashings, process_proposer_slashings,
+        },
+    },
+    per_epoch_processing::{
+        altair::{
+            process_inactivity_updates, process_justification_and_finalization,
+            rewards_and_penalties::{
+                get_flag_index_deltas, get_flag_weight, get_inactivity_penalty_deltas,
+            },
+            ParticipationCache,
+        },
+        Delta,
+    },
+    state_advance::complete_state_advance,
+    BlockProcessingError, EpochProcessingError, VerifySignatures,
+};
+use std::collections::{HashMap, HashSet};
+use types::consts::altair::{
+    TIMELY_HEAD_FLAG_INDEX, TIMELY_SOURCE_FLAG_INDEX, TIMELY_TARGET_FLAG_INDEX, WEIGHT_DENOMINATOR,
+};
+use types::{
+    BeaconState, BeaconStateError, ChainSpec, Epoch, EthSpec, Hash256, RelativeEpoch,
+    SignedBeaconBlock,
+};
+
+/// The number of blocks for which block and sync committee rewards are cached.
+const BLOCK_CACHE_SIZE: usize = 64;
+
+/// The number of epochs for which attestation rewards are cached. Each entry holds a reward for
+/// every eligible validator, so this is kept small.
+const EPOCH_CACHE_SIZE: usize = 4;
+
+/// The attestation rewards for all eligible validators in some epoch.
+#[derive(Clone)]
+struct EpochAttestationRewards {
+    ideal_rewards: Vec<IdealAttestationRewards>,
+    total_rewards: Vec<TotalAttestationRewards>,
+}
+
+/// Caches the results of reward computations.
+///
+/// Block and sync committee rewards are keyed by block root. Attestation rewards are keyed by the
+/// epoch and the canonical block root at the end of the following epoch.
+pub struct RewardsCache {
+    block_rewards: LruCache<Hash256, StandardBlockReward>,
+    sync_committee_rewards: LruCache<Hash256, Vec<SyncCommitteeReward>>,
+    attestation_rewards: LruCache<(Epoch, Hash256), EpochAttestationRewards>,
+}
+
+impl Default for RewardsCache {
+    fn default() -> Self {
+        Self {
+            block_rewards: LruCache::new(BLOCK_CACHE_SIZE),
+            sync_committee_rewards: LruCache::new(BLOCK_CACHE_SIZE),
+            attestation_rewards: LruCache::new(EPOCH_CACHE_SIZE),
+        }
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Returns the rewards earned by the proposer of `block` for its inclusion of attestations,
+    /// slashings and the sync aggregate.
+    pub fn compute_block_reward(
+        &self,
+        block: &SignedBeaconBlock<T::EthSpec>,
+        block_root: Hash256,
+    ) -> Result<StandardBlockReward, BeaconChainError> {
+        if let Some(reward) = self.rewards_cache.lock().block_rewards.get(&block_root) {
+            return Ok(reward.clone());
+        }
+
+        if let SignedBeaconBlock::Base(_) = block {
+            return Err(BeaconChainError::RewardsUnsupportedPreAltair);
+        }
+
+        let mut state = self.rewards_pre_state(block)?;
+        let reward = block_reward(&mut state, block, &self.spec)?;
+
+        self.rewards_cache
+            .lock()
+            .block_rewards
+            .put(block_root, reward.clone());
+
+        Ok(reward)
+    }
+
+    /// Returns the rewards (and penalties) of the sync committee members for their participation
+    /// in the sync aggregate of `block`.
+    ///
+    /// Only validators in `ids` are returned, or all members of the committee if `ids` is empty.
+    pub fn compute_sync_committee_rewards(
+        &self,
+        block: &SignedBeaconBlock<T::EthSpec>,
+        block_root: Hash256,
+        ids: &[ValidatorId],
+    ) -> Result<Vec<SyncCommitteeReward>, BeaconChainError> {
+        let cached = self
+            .rewards_cache
+            .lock()
+            .sync_committee_rewards
+            .get(&block_root)
+            .cloned();
+
+        let rewards = if let Some(rewards) = cached {
+            rewards
+        } else {
+            if let SignedBeaconBlock::Base(_) = block {
+                return Err(BeaconChainError::RewardsUnsupportedPreAltair);
+            }
+
+            let mut state = self.rewards_pre_state(block)?;
+            let rewards = sync_committee_rewards(&mut state, block, &self.spec)?;
+
+            self.rewards_cache
+                .lock()
+                .sync_committee_rewards
+                .put(block_root, rewards.clone());
+
+            rewards
+        };
+
+        let filter = self.reward_validator_indices(ids)?;
+        Ok(rewards
+            .into_iter()
+            .filter(|reward| filter_contains(&filter, reward.validator_index))
+            .collect())
+    }
+
+    /// Returns the attestation rewards (and penalties) of validators for `epoch`, along with the
+    /// ideal rewards for each effective balance.
+    ///
+    /// Only validators in `ids` are returned, or all eligible validators if `ids` is empty.
+    pub fn compute_attestation_rewards(
+        &self,
+        epoch: Epoch,
+        ids: &[ValidatorId],
+    ) -> Result<StandardAttestationRewards, BeaconChainError> {
+        // Attestations for `epoch` can be included until the end of the following epoch, so the
+        // rewards are only known once that epoch has passed.
+        let current_epoch = self.epoch()?;
+        if epoch.safe_add(1)? >= current_epoch {
+            return Err(BeaconChainError::AttestationRewardsEpochIncomplete {
+                epoch,
+                current_epoch,
+            });
+        }
+
+        // The last slot of the following epoch, prior to the epoch transition that applies the
+        // rewards.
+        let end_slot = epoch
+            .safe_add(2)?
+            .start_slot(T::EthSpec::slots_per_epoch())
+            .safe_sub(1)?;
+        let dependent_root = self
+            .block_root_at_slot(end_slot, WhenSlotSkipped::Prev)?
+            .ok_or(BeaconChainError::NoStateForSlot(end_slot))?;
+
+        let cached = self
+            .rewards_cache
+            .lock()
+            .attestation_rewards
+            .get(&(epoch, dependent_root))
+            .cloned();
+
+        let rewards = if let Some(rewards) = cached {
+            rewards
+        } else {
+            let mut state = self.state_at_slot(end_slot, StateSkipConfig::WithoutStateRoots)?;
+            if let BeaconState::Base(_) = state {
+                return Err(BeaconChainError::RewardsUnsupportedPreAltair);
+            }
+
+            let rewards = epoch_attestation_rewards(&mut state, &self.spec)?;
+
+            self.rewards_cache
+                .lock()
+                .attestation_rewards
+                .put((epoch, dependent_root), rewards.clone());
+
+            rewards
+        };
+
+        let filter = self.reward_validator_indices(ids)?;
+        Ok(StandardAttestationRewards {
+            ideal_rewards: rewards.ideal_rewards,
+            total_rewards: rewards
+                .total_rewards
+                .into_iter()
+                .filter(|reward| filter_contains(&filter, reward.validator_index))
+                .collect(),
+        })
+    }
+
+    /// Loads the state of the parent of `block`, advanced to the slot of `block`.
+    fn rewards_pre_state(
+        &self,
+        block: &SignedBeaconBlock<T::EthSpec>,
+    ) -> Result<BeaconState<T::EthSpec>, BeaconChainError> {
+        let parent_root = block.parent_root();
+        let parent_block = self
+            .get_block(&parent_root)?
+            .ok_or(BeaconChainError::MissingBeaconBlock(parent_root))?;
+        let parent_state_root = parent_block.state_root();
+        let mut state = self
+            .get_state(&parent_state_root, Some(parent_block.slot()))?
+            .ok_or(BeaconChainError::MissingBeaconState(parent_state_root))?;
+
+        complete_state_advance(
+            &mut state,
+            Some(parent_state_root),
+            block.slot(),
+            &self.spec,
+        )?;
+        state.build_committee_cache(RelativeEpoch::Previous, &self.spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, &self.spec)?;
+
+        Ok(state)
+    }
+
+    /// Resolves `ids` to validator indices, returning `None` if `ids` is empty (i.e., all
+    /// validators were requested). Unknown public keys are ignored.
+    fn reward_validator_indices(
+        &self,
+        ids: &[ValidatorId],
+    ) -> Result<Option<HashSet<u64>>, BeaconChainError> {
+        if ids.is_empty() {
+            return Ok(None);
+        }
+
+        let mut indices = HashSet::with_capacity(ids.len());
+        for id in ids {
+            match id {
+                ValidatorId::Index(index) => {
+                    indices.insert(*index);
+                }
+                ValidatorId::PublicKey(pubkey) => {
+                    if let Some(index) = self.validator_index(pubkey)? {
+                        indices.insert(index as u64);
+                    }
+                }
+            }
+        }
+
+        Ok(Some(indices))
+    }
+}
+
+fn filter_contains(filter: &Option<HashSet<u64>>, validator_index: u64) -> bool {
+    filter
+        .as_ref()
+        .map_or(true, |indices| indices.contains(&validator_index))
+}
+
+/// Converts a `Delta` to a signed reward.
+fn signed_reward(delta: &Delta) -> i64 {
+    (delta.rewards as i64).saturating_sub(delta.penalties as i64)
+}
+
+/// Computes the proposer reward of `block` by applying its operations to the pre-`state` and
+/// observing the change in the proposer's balance.
+fn block_reward<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    block: &SignedBeaconBlock<E>,
+    spec: &ChainSpec,
+) -> Result<StandardBlockReward, BlockProcessingError> {
+    let block = block.message();
+    let body = block.body();
+    let proposer_index = block.proposer_index();
+    let proposer_balance = |state: &BeaconState<E>| {
+        state
+            .balances()
+            .get(proposer_index as usize)
+            .copied()
+            .ok_or(BeaconStateError::BalancesOutOfBounds(
+                proposer_index as usize,
+            ))
+    };
+
+    let initial_balance = proposer_balance(state)?;
+
+    process_proposer_slashings(
+        state,
+        body.proposer_slashings(),
+        VerifySignatures::False,
+        spec,
+    )?;
+    let post_proposer_slashings_balance = proposer_balance(state)?;
+
+    process_attester_slashings(
+        state,
+        body.attester_slashings(),
+        VerifySignatures::False,
+        spec,
+    )?;
+    let post_attester_slashings_balance = proposer_balance(state)?;
+
+    process_attestations(state, body, proposer_index, VerifySignatures::False, spec)?;
+    let post_attestations_balance = proposer_balance(state)?;
+
+    let (_, proposer_reward_per_bit) = compute_sync_aggregate_rewards(state, spec)?;
+    let sync_aggregate_reward = proposer_reward_per_bit
+        .safe_mul(body.sync_aggregate()?.sync_committee_bits.num_set_bits() as u64)?;
+
+    let proposer_slashings = post_proposer_slashings_balance.saturating_sub(initial_balance);
+    let attester_slashings =
+        post_attester_slashings_balance.saturating_sub(post_proposer_slashings_balance);
+    let attestations = post_attestations_balance.saturating_sub(post_attester_slashings_balance);
+
+    Ok(StandardBlockReward {
+        proposer_index,
+        total: proposer_slashings
+            .safe_add(attester_slashings)?
+            .safe_add(attestations)?
+            .safe_add(sync_aggregate_reward)?,
+        attestations,
+        sync_aggregate: sync_aggregate_reward,
+        proposer_slashings,
+        attester_slashings,
+    })
+}
+
+/// Computes the reward of each sync committee member for the sync aggregate of `block`, in
+/// committee order. Validators appearing in the committee multiple times are combined.
+fn sync_committee_rewards<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    block: &SignedBeaconBlock<E>,
+    spec: &ChainSpec,
+) -> Result<Vec<SyncCommitteeReward>, BlockProcessingError> {
+    let (participant_reward, _) = compute_sync_aggregate_rewards(state, spec)?;
+    let participant_reward = participant_reward as i64;
+
+    let sync_committee = state.current_sync_committee()?.clone();
+    let committee_indices = state.get_sync_committee_indices(&sync_committee)?;
+    let aggregate = block.message().body().sync_aggregate()?;
+
+    let mut rewards: Vec<SyncCommitteeReward> = Vec::with_capacity(committee_indices.len());
+    let mut positions = HashMap::with_capacity(committee_indices.len());
+
+    for (validator_index, participated) in committee_indices
+        .into_iter()
+        .zip(aggregate.sync_committee_bits.iter())
+    {
+        let reward = if participated {
+            participant_reward
+        } else {
+            -participant_reward
+        };
+
+        let position = *positions.entry(validator_index).or_insert_with(|| {
+            rewards.push(SyncCommitteeReward {
+                validator_index: validator_index as u64,
+                reward: 0,
+            });
+            rewards.len() - 1
+        });
+        rewards[position].reward = rewards[position].reward.saturating_add(reward);
+    }
+
+    Ok(rewards)
+}
+
+/// Computes the attestation rewards for the previous epoch of `state`, which must be the state at
+/// the last slot of the epoch following the epoch of interest.
+///
+/// This mirrors Altair epoch processing: justification, finalization and inactivity scores are
+/// updated prior to computing the deltas.
+fn epoch_attestation_rewards<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<EpochAttestationRewards, EpochProcessingError> {
+    state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+    state.build_committee_cache(RelativeEpoch::Current, spec)?;
+
+    let participation_cache = ParticipationCache::new(state, spec)?;
+    process_justification_and_finalization(state, &participation_cache)?;
+    process_inactivity_updates(state, &participation_cache, spec)?;
+
+    let previous_epoch = state.previous_epoch();
+    let total_active_balance = participation_cache.current_epoch_total_active_balance();
+    let active_increments = total_active_balance.safe_div(spec.effective_balance_increment)?;
+    let base_reward_per_increment = get_base_reward_per_increment(total_active_balance, spec)?;
+    let in_inactivity_leak = state.is_in_inactivity_leak(spec);
+
+    let flag_deltas = |flag_index| -> Result<Vec<Delta>, EpochProcessingError> {
+        let mut deltas = vec![Delta::default(); state.validators().len()];
+        get_flag_index_deltas(
+            &mut deltas,
+            state,
+            flag_index,
+            total_active_balance,
+            &participation_cache,
+            spec,
+        )?;
+        Ok(deltas)
+    };
+    let head_deltas = flag_deltas(TIMELY_HEAD_FLAG_INDEX)?;
+    let target_deltas = flag_deltas(TIMELY_TARGET_FLAG_INDEX)?;
+    let source_deltas = flag_deltas(TIMELY_SOURCE_FLAG_INDEX)?;
+
+    let mut inactivity_deltas = vec![Delta::default(); state.validators().len()];
+    get_inactivity_penalty_deltas(&mut inactivity_deltas, state, &participation_cache, spec)?;
+
+    let total_rewards = participation_cache
+        .eligible_validator_indices()
+        .iter()
+        .map(|&index| {
+            let delta = |deltas: &[Delta]| {
+                deltas
+                    .get(index)
+                    .map(signed_reward)
+                    .ok_or(EpochProcessingError::DeltaOutOfBounds(index))
+            };
+            Ok(TotalAttestationRewards {
+                validator_index: index as u64,
+                head: delta(&head_deltas)?,
+                target: delta(&target_deltas)?,
+                source: delta(&source_deltas)?,
+                inactivity: delta(&inactivity_deltas)?,
+            })
+        })
+        .collect::<Result<Vec<_>, EpochProcessingError>>()?;
+
+    // The `(weight, unslashed_participating_increments)` of each flag.
+    let flag_participation = |flag_index| -> Result<(u64, u64), EpochProcessingError> {
+        let unslashed_participating_increments = participation_cache
+            .get_unslashed_participating_indices(flag_index, previous_epoch)?
+            .total_balance()?
+            .safe_div(spec.effective_balance_increment)?;
+        Ok((
+            get_flag_weight(flag_index)?,
+            unslashed_participating_increments,
+        ))
+    };
+    let head_participation = flag_participation(TIMELY_HEAD_FLAG_INDEX)?;
+    let target_participation = flag_participation(TIMELY_TARGET_FLAG_INDEX)?;
+    let source_participation = flag_participation(TIMELY_SOURCE_FLAG_INDEX)?;
+
+    // Equivalent to the reward of a participating validator in `get_flag_index_deltas`.
+    let ideal_reward = |base_reward: u64,
+                        (weight, unslashed_participating_increments): (u64, u64)|
+     -> Result<u64, EpochProcessingError> {
+        if in_inactivity_leak {
+            return Ok(0);
+        }
+        Ok(base_reward
+            .safe_mul(weight)?
+            .safe_mul(unslashed_participating_increments)?
+            .safe_div(active_increments.safe_mul(WEIGHT_DENOMINATOR)?)?)
+    };
+
+    let max_increments = spec
+        .max_effective_balance
+        .safe_div(spec.effective_balance_increment)?;
+    let ideal_rewards = (1..=max_increments)
+        .map(|increments| {
+            let base_reward = increments.safe_mul(base_reward_per_increment)?;
+            Ok(IdealAttestationRewards {
+                effective_balance: increments.safe_mul(spec.effective_balance_increment)?,
+                head: ideal_reward(base_reward, head_participation)?,
+                target: ideal_reward(base_reward, target_participation)?,
+                source: ideal_reward(base_reward, source_participation)?,
+            })
+        })
+        .collect::<Result<Vec<_>, EpochProcessingError>>()?;
+
+    Ok(EpochAttestationRewards {
+        ideal_rewards,
+        total_rewards,
+    })
+}
@@ -181,6 +181,21 @@ pub fn migrate_schema<T: BeaconChainTypes>(
 
             Ok(())
         }
+        // Migration to add the index of frozen block roots.
+        (SchemaVersion(8), SchemaVersion(9)) => {
+            db.build_frozen_block_root_index()?;
+            db.store_schema_version(to)?;
+            Ok(())
+        }
+        // Downgrade to remove the index of frozen block roots.
+        //
+        // Schema v8 neither reads nor writes the index, so its entries can be left in place. The
+        // index may fall behind the split while the database is at v8, but upgrading back to v9
+        // rebuilds it for every frozen slot, overwriting any stale entries.
+        (SchemaVersion(9), SchemaVersion(8)) => {
+            db.store_schema_version(to)?;
+            Ok(())
+        }
         // Anything else is an error.
         (_, _) => Err(HotColdDBError::UnsupportedSchemaVersion {
             target_version: to,
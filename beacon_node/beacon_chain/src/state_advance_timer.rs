@@ -329,6 +329,17 @@ fn advance_head<T: BeaconChainTypes>(
         );
     }
 
+    // Let the execution engines start building a payload for the next proposer, if it is one of
+    // ours.
+    if let Err(e) = beacon_chain.prepare_beacon_proposer(&state) {
+        debug!(
+            log,
+            "Unable to prepare beacon proposer";
+            "error" => ?e,
+            "slot" => state.slot(),
+        );
+    }
+
     // Apply the state to the attester cache, if the cache deems it interesting.
     beacon_chain
         .attester_cache
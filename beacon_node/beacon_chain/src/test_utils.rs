@@ -344,6 +344,8 @@ where
             .unwrap();
         let execution_layer = ExecutionLayer::from_urls(
             urls,
+            vec![],
+            None,
             Some(Address::repeat_byte(42)),
             el_runtime.task_executor.clone(),
             el_runtime.log.clone(),
@@ -402,6 +404,7 @@ where
             .event_handler(Some(ServerSentEventHandler::new_with_capacity(
                 log.clone(),
                 5,
+                64,
             )))
             .monitor_validators(true, vec![], log);
 
@@ -1603,6 +1606,51 @@ where
         last_produced_block_hash.into()
     }
 
+    /// Extend the `BeaconChain` on its canonical head with `num_blocks` blocks, attested to by all
+    /// validators. Each block includes a sync aggregate from the whole sync committee, signing
+    /// over its parent. Returns the root of the last-produced block.
+    pub fn extend_chain_with_sync_aggregates(&self, num_blocks: usize) -> Hash256 {
+        let mut head_block_root = self.chain.head_info().unwrap().block_root;
+        for _ in 0..num_blocks {
+            let head = self.chain.head().unwrap();
+            let head_slot = head.beacon_state.slot();
+
+            // The first block of a sync committee period is signed by the committee of that
+            // period, which is the next committee of the head state.
+            let period = |slot: Slot| {
+                slot.epoch(E::slots_per_epoch())
+                    .sync_committee_period(&self.spec)
+                    .unwrap()
+            };
+            let relative_sync_committee = if period(self.get_current_slot()) > period(head_slot) {
+                RelativeSyncCommittee::Next
+            } else {
+                RelativeSyncCommittee::Current
+            };
+
+            let contributions = self.make_sync_contributions(
+                &head.beacon_state,
+                head.beacon_block_root,
+                head_slot,
+                relative_sync_committee,
+            );
+            for contribution in contributions.into_iter().filter_map(|(_, c)| c) {
+                self.chain
+                    .op_pool
+                    .insert_sync_contribution(contribution.message.contribution)
+                    .unwrap();
+            }
+
+            head_block_root = self.extend_chain(
+                1,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::AllValidators,
+            );
+            self.advance_slot();
+        }
+        head_block_root
+    }
+
     /// Deprecated: Use add_attested_blocks_at_slots() instead
     ///
     /// Creates two forks:
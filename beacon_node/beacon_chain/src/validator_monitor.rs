@@ -2,6 +2,7 @@
 //!
 //! This component should not affect consensus.
 
+use crate::events::{SseValidatorSlashing, ValidatorSlashingKind};
 use crate::metrics;
 use parking_lot::RwLock;
 use slog::{crit, debug, error, info, warn, Logger};
@@ -164,14 +165,69 @@ impl EpochSummary {
 
 type SummaryMap = HashMap<Epoch, EpochSummary>;
 
+/// The on-chain attestation performance of a validator, accumulated over every epoch since the
+/// validator began to be monitored.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ValidatorPerformance {
+    pub attestation_hits: u64,
+    pub attestation_misses: u64,
+    pub attestation_head_hits: u64,
+    pub attestation_head_misses: u64,
+    pub attestation_target_hits: u64,
+    pub attestation_target_misses: u64,
+    /// The inclusion distance of the attestation for the most recently processed epoch.
+    pub latest_attestation_inclusion_distance: Option<u64>,
+}
+
+impl ValidatorPerformance {
+    fn register_epoch(
+        &mut self,
+        matched_any: bool,
+        matched_head: bool,
+        matched_target: bool,
+        inclusion_distance: Option<u64>,
+    ) {
+        fn register(hits: &mut u64, misses: &mut u64, hit: bool) {
+            if hit {
+                *hits += 1;
+            } else {
+                *misses += 1;
+            }
+        }
+
+        register(
+            &mut self.attestation_hits,
+            &mut self.attestation_misses,
+            matched_any,
+        );
+        register(
+            &mut self.attestation_head_hits,
+            &mut self.attestation_head_misses,
+            matched_head,
+        );
+        register(
+            &mut self.attestation_target_hits,
+            &mut self.attestation_target_misses,
+            matched_target,
+        );
+        if inclusion_distance.is_some() {
+            self.latest_attestation_inclusion_distance = inclusion_distance;
+        }
+    }
+}
+
 /// A validator that is being monitored by the `ValidatorMonitor`.
 struct MonitoredValidator {
     /// A human-readable identifier for the validator.
     pub id: String,
+    /// The public key of the validator.
+    pub pubkey: PublicKeyBytes,
     /// The validator index in the state.
     pub index: Option<u64>,
     /// A history of the validator over time.
     pub summaries: RwLock<SummaryMap>,
+    /// The performance of the validator over all monitored epochs.
+    pub performance: RwLock<ValidatorPerformance>,
 }
 
 impl MonitoredValidator {
@@ -180,8 +236,10 @@ impl MonitoredValidator {
             id: index
                 .map(|i| i.to_string())
                 .unwrap_or_else(|| pubkey.to_string()),
+            pubkey,
             index,
             summaries: <_>::default(),
+            performance: <_>::default(),
         }
     }
 
@@ -512,6 +570,26 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         .previous_epoch_inclusion_info(i)
                         .map(|info| info.delay),
                 );
+
+                monitored_validator.performance.write().register_epoch(
+                    previous_epoch_matched_any,
+                    previous_epoch_matched_head,
+                    previous_epoch_matched_target,
+                    min_inclusion_distance,
+                );
+
+                metrics::set_float_gauge_vec(
+                    &metrics::VALIDATOR_MONITOR_PREV_EPOCH_ATTESTATION_EFFECTIVENESS,
+                    &[id],
+                    attestation_effectiveness(
+                        previous_epoch_matched_source,
+                        previous_epoch_matched_target,
+                        previous_epoch_matched_head,
+                        min_inclusion_distance,
+                        spec,
+                    ),
+                );
+
                 if let Some(inclusion_delay) = min_inclusion_distance {
                     if inclusion_delay > spec.min_attestation_inclusion_delay {
                         suboptimal_inclusion.push(id);
@@ -547,6 +625,12 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                         );
                         let epoch_summary = monitored_validator.summaries.read();
                         if let Some(summary) = epoch_summary.get(&current_epoch) {
+                            metrics::set_float_gauge_vec(
+                                &metrics::VALIDATOR_MONITOR_CURRENT_EPOCH_SYNC_COMMITTEE_PARTICIPATION,
+                                &[id],
+                                summary.sync_signature_block_inclusions as f64
+                                    / T::slots_per_epoch() as f64,
+                            );
                             info!(
                                 self.log,
                                 "Current epoch sync signatures";
@@ -640,6 +724,12 @@ impl<T: EthSpec> ValidatorMonitor<T> {
         self.validators.len()
     }
 
+    /// Returns the performance of the validator with `validator_index`, if it is monitored.
+    pub fn get_validator_performance(&self, validator_index: u64) -> Option<ValidatorPerformance> {
+        self.get_validator(validator_index)
+            .map(|validator| *validator.performance.read())
+    }
+
     /// If `self.auto_register == true`, add the `validator_index` to `self.monitored_validators`.
     /// Otherwise, do nothing.
     pub fn auto_register_local_validator(&mut self, validator_index: u64) {
@@ -1218,21 +1308,40 @@ impl<T: EthSpec> ValidatorMonitor<T> {
     }
 
     /// Register a proposer slashing from the gossip network.
-    pub fn register_gossip_proposer_slashing(&self, slashing: &ProposerSlashing) {
+    ///
+    /// Returns the slashings of monitored validators, for which events should be emitted.
+    pub fn register_gossip_proposer_slashing(
+        &self,
+        slashing: &ProposerSlashing,
+    ) -> Vec<SseValidatorSlashing> {
         self.register_proposer_slashing("gossip", slashing)
     }
 
     /// Register a proposer slashing from the HTTP API.
-    pub fn register_api_proposer_slashing(&self, slashing: &ProposerSlashing) {
+    ///
+    /// Returns the slashings of monitored validators, for which events should be emitted.
+    pub fn register_api_proposer_slashing(
+        &self,
+        slashing: &ProposerSlashing,
+    ) -> Vec<SseValidatorSlashing> {
         self.register_proposer_slashing("api", slashing)
     }
 
     /// Register a proposer slashing included in a *valid* `BeaconBlock`.
-    pub fn register_block_proposer_slashing(&self, slashing: &ProposerSlashing) {
+    ///
+    /// Returns the slashings of monitored validators, for which events should be emitted.
+    pub fn register_block_proposer_slashing(
+        &self,
+        slashing: &ProposerSlashing,
+    ) -> Vec<SseValidatorSlashing> {
         self.register_proposer_slashing("block", slashing)
     }
 
-    fn register_proposer_slashing(&self, src: &str, slashing: &ProposerSlashing) {
+    fn register_proposer_slashing(
+        &self,
+        src: &str,
+        slashing: &ProposerSlashing,
+    ) -> Vec<SseValidatorSlashing> {
         let proposer = slashing.signed_header_1.message.proposer_index;
         let slot = slashing.signed_header_1.message.slot;
         let epoch = slot.epoch(T::slots_per_epoch());
@@ -1258,25 +1367,54 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             );
 
             validator.with_epoch_summary(epoch, |summary| summary.register_proposer_slashing());
+
+            vec![SseValidatorSlashing {
+                validator_index: proposer,
+                pubkey: validator.pubkey,
+                kind: ValidatorSlashingKind::Proposer,
+                slot,
+                source: src.to_string(),
+            }]
+        } else {
+            vec![]
         }
     }
 
     /// Register an attester slashing from the gossip network.
-    pub fn register_gossip_attester_slashing(&self, slashing: &AttesterSlashing<T>) {
+    ///
+    /// Returns the slashings of monitored validators, for which events should be emitted.
+    pub fn register_gossip_attester_slashing(
+        &self,
+        slashing: &AttesterSlashing<T>,
+    ) -> Vec<SseValidatorSlashing> {
         self.register_attester_slashing("gossip", slashing)
     }
 
     /// Register an attester slashing from the HTTP API.
-    pub fn register_api_attester_slashing(&self, slashing: &AttesterSlashing<T>) {
+    ///
+    /// Returns the slashings of monitored validators, for which events should be emitted.
+    pub fn register_api_attester_slashing(
+        &self,
+        slashing: &AttesterSlashing<T>,
+    ) -> Vec<SseValidatorSlashing> {
         self.register_attester_slashing("api", slashing)
     }
 
     /// Register an attester slashing included in a *valid* `BeaconBlock`.
-    pub fn register_block_attester_slashing(&self, slashing: &AttesterSlashing<T>) {
+    ///
+    /// Returns the slashings of monitored validators, for which events should be emitted.
+    pub fn register_block_attester_slashing(
+        &self,
+        slashing: &AttesterSlashing<T>,
+    ) -> Vec<SseValidatorSlashing> {
         self.register_attester_slashing("block", slashing)
     }
 
-    fn register_attester_slashing(&self, src: &str, slashing: &AttesterSlashing<T>) {
+    fn register_attester_slashing(
+        &self,
+        src: &str,
+        slashing: &AttesterSlashing<T>,
+    ) -> Vec<SseValidatorSlashing> {
         let data = &slashing.attestation_1.data;
         let attestation_1_indices: HashSet<u64> = slashing
             .attestation_1
@@ -1290,8 +1428,8 @@ impl<T: EthSpec> ValidatorMonitor<T> {
             .attesting_indices
             .iter()
             .filter(|index| attestation_1_indices.contains(index))
-            .filter_map(|index| self.get_validator(*index))
-            .for_each(|validator| {
+            .filter_map(|index| Some((*index, self.get_validator(*index)?)))
+            .map(|(index, validator)| {
                 let id = &validator.id;
                 let epoch = data.slot.epoch(T::slots_per_epoch());
 
@@ -1310,7 +1448,16 @@ impl<T: EthSpec> ValidatorMonitor<T> {
                 );
 
                 validator.with_epoch_summary(epoch, |summary| summary.register_attester_slashing());
+
+                SseValidatorSlashing {
+                    validator_index: index,
+                    pubkey: validator.pubkey,
+                    kind: ValidatorSlashingKind::Attester,
+                    slot: data.slot,
+                    source: src.to_string(),
+                }
             })
+            .collect()
     }
 
     /// Scrape `self` for metrics.
@@ -1526,6 +1673,31 @@ fn get_message_delay_ms<S: SlotClock>(
         .unwrap_or_else(|| Duration::from_secs(0))
 }
 
+/// Returns the effectiveness of an attestation, between 0 and 1.
+///
+/// The effectiveness is the proportion of the source, target and head votes which matched,
+/// multiplied by the ratio of the optimal inclusion distance to the `inclusion_distance`, if it is
+/// known. In Altair the inclusion distance is only known if the attestation was observed by the
+/// validator monitor, however an unobserved attestation only matches if it was timely.
+fn attestation_effectiveness(
+    matched_source: bool,
+    matched_target: bool,
+    matched_head: bool,
+    inclusion_distance: Option<u64>,
+    spec: &ChainSpec,
+) -> f64 {
+    let matched = [matched_source, matched_target, matched_head]
+        .iter()
+        .filter(|matched| **matched)
+        .count();
+    let timeliness = inclusion_distance
+        .filter(|distance| *distance > 0)
+        .map_or(1.0, |distance| {
+            (spec.min_attestation_inclusion_delay as f64 / distance as f64).min(1.0)
+        });
+    matched as f64 / 3.0 * timeliness
+}
+
 /// Returns minimum value from the two options if both are `Some` or the
 /// value contained if only one of them is Some. Returns `None` if both options are `None`
 fn min_opt<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
@@ -1536,3 +1708,55 @@ fn min_opt<T: Ord>(a: Option<T>, b: Option<T>) -> Option<T> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    #[test]
+    fn attestation_effectiveness_values() {
+        let spec = MainnetEthSpec::default_spec();
+        assert_eq!(spec.min_attestation_inclusion_delay, 1);
+
+        // A perfect attestation, whether or not its inclusion distance is known.
+        assert_eq!(
+            attestation_effectiveness(true, true, true, Some(1), &spec),
+            1.0
+        );
+        assert_eq!(
+            attestation_effectiveness(true, true, true, None, &spec),
+            1.0
+        );
+
+        // Each missed vote costs a third.
+        assert_eq!(
+            attestation_effectiveness(true, true, false, Some(1), &spec),
+            2.0 / 3.0
+        );
+        assert_eq!(
+            attestation_effectiveness(true, false, false, None, &spec),
+            1.0 / 3.0
+        );
+        assert_eq!(
+            attestation_effectiveness(false, false, false, Some(1), &spec),
+            0.0
+        );
+
+        // Late inclusion scales the votes by the optimal inclusion distance.
+        assert_eq!(
+            attestation_effectiveness(true, true, true, Some(4), &spec),
+            0.25
+        );
+        assert_eq!(
+            attestation_effectiveness(true, true, false, Some(2), &spec),
+            1.0 / 3.0
+        );
+
+        // An inclusion distance of zero is invalid, and is ignored rather than dividing by zero.
+        assert_eq!(
+            attestation_effectiveness(true, true, true, Some(0), &spec),
+            1.0
+        );
+    }
+}
@@ -0,0 +1,299 @@
+#![cfg(not(debug_assertions))]
+
+use beacon_chain::light_client_update_verification::{light_client_update_delay, Error};
+use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+use beacon_chain::ChainConfig;
+use lazy_static::lazy_static;
+use slot_clock::SlotClock;
+use std::time::Duration;
+use types::{
+    Epoch, EthSpec, ForkName, Keypair, LightClientFinalityUpdate, LightClientOptimisticUpdate,
+    MinimalEthSpec, Slot,
+};
+
+type E = MinimalEthSpec;
+
+pub const VALIDATOR_COUNT: usize = 24;
+
+lazy_static! {
+    /// A cached set of keys.
+    static ref KEYPAIRS: Vec<Keypair> = types::test_utils::generate_deterministic_keypairs(VALIDATOR_COUNT);
+}
+
+/// Returns a beacon chain harness which starts at the Altair fork.
+fn get_harness(enable_light_client_server: bool) -> BeaconChainHarness<EphemeralHarnessType<E>> {
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(ForkName::Altair.make_genesis_spec(E::default_spec()))
+        .keypairs(KEYPAIRS[..].to_vec())
+        .fresh_ephemeral_store()
+        .chain_config(ChainConfig {
+            enable_light_client_server,
+            ..ChainConfig::default()
+        })
+        .build();
+
+    harness.advance_slot();
+
+    harness
+}
+
+/// Returns a harness which has finalized, and so has both a finality and an optimistic update.
+fn get_finalized_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+    let harness = get_harness(true);
+    harness.extend_chain_with_sync_aggregates(E::slots_per_epoch() as usize * 5);
+
+    assert!(
+        harness
+            .chain
+            .head()
+            .expect("should read head")
+            .beacon_state
+            .finalized_checkpoint()
+            .epoch
+            > Epoch::new(0),
+        "the chain should have finalized"
+    );
+
+    harness
+}
+
+/// Returns the time at which updates signed at `signature_slot` may first be forwarded.
+fn publish_time(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+    signature_slot: Slot,
+) -> Duration {
+    harness
+        .chain
+        .slot_clock
+        .start_of(signature_slot)
+        .expect("should get slot start")
+        + light_client_update_delay(&harness.chain)
+}
+
+fn latest_finality_update(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+) -> LightClientFinalityUpdate<E> {
+    harness
+        .chain
+        .latest_light_client_finality_update()
+        .expect("should have a finality update")
+}
+
+fn latest_optimistic_update(
+    harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+) -> LightClientOptimisticUpdate<E> {
+    harness
+        .chain
+        .latest_light_client_optimistic_update()
+        .expect("should have an optimistic update")
+}
+
+#[test]
+fn finality_update_valid() {
+    let harness = get_finalized_harness();
+    let update = latest_finality_update(&harness);
+    let seen_timestamp = publish_time(&harness, update.signature_slot);
+
+    let verified = harness
+        .chain
+        .verify_finality_update_for_gossip(update.clone(), seen_timestamp)
+        .expect("should verify the update");
+    assert_eq!(verified.update(), &update);
+
+    // The same update should not be forwarded twice.
+    assert!(matches!(
+        harness
+            .chain
+            .verify_finality_update_for_gossip(update.clone(), seen_timestamp),
+        Err(Error::UpdateAlreadySeen { slot })
+            if slot == update.finalized_header.beacon.slot
+    ));
+}
+
+#[test]
+fn finality_update_early() {
+    let harness = get_finalized_harness();
+    let update = latest_finality_update(&harness);
+    let seen_timestamp = harness
+        .chain
+        .slot_clock
+        .start_of(update.signature_slot)
+        .expect("should get slot start");
+
+    assert!(matches!(
+        harness
+            .chain
+            .verify_finality_update_for_gossip(update.clone(), seen_timestamp),
+        Err(Error::TooEarly { signature_slot }) if signature_slot == update.signature_slot
+    ));
+
+    // An update rejected for being early may be forwarded once the block has had time to
+    // propagate.
+    assert!(harness
+        .chain
+        .verify_finality_update_for_gossip(
+            update.clone(),
+            publish_time(&harness, update.signature_slot)
+        )
+        .is_ok());
+}
+
+#[test]
+fn finality_update_mismatched() {
+    let harness = get_finalized_harness();
+    let mut update = latest_finality_update(&harness);
+    update.attested_header.beacon.proposer_index += 1;
+    let seen_timestamp = publish_time(&harness, update.signature_slot);
+
+    assert!(matches!(
+        harness
+            .chain
+            .verify_finality_update_for_gossip(update, seen_timestamp),
+        Err(Error::UpdateMismatch)
+    ));
+}
+
+#[test]
+fn finality_update_invalid_slot_ordering() {
+    let harness = get_finalized_harness();
+    let mut update = latest_finality_update(&harness);
+    update.finalized_header.beacon.slot = update.attested_header.beacon.slot + 1;
+    let seen_timestamp = publish_time(&harness, update.signature_slot);
+
+    assert!(matches!(
+        harness
+            .chain
+            .verify_finality_update_for_gossip(update, seen_timestamp),
+        Err(Error::InvalidSlotOrdering { .. })
+    ));
+}
+
+#[test]
+fn optimistic_update_valid() {
+    let harness = get_harness(true);
+    harness.extend_chain_with_sync_aggregates(E::slots_per_epoch() as usize);
+    let update = latest_optimistic_update(&harness);
+    let seen_timestamp = publish_time(&harness, update.signature_slot);
+
+    let verified = harness
+        .chain
+        .verify_optimistic_update_for_gossip(update.clone(), seen_timestamp)
+        .expect("should verify the update");
+    assert_eq!(verified.update(), &update);
+
+    // The same update should not be forwarded twice.
+    assert!(matches!(
+        harness
+            .chain
+            .verify_optimistic_update_for_gossip(update.clone(), seen_timestamp),
+        Err(Error::UpdateAlreadySeen { slot })
+            if slot == update.attested_header.beacon.slot
+    ));
+}
+
+#[test]
+fn optimistic_update_early() {
+    let harness = get_harness(true);
+    harness.extend_chain_with_sync_aggregates(E::slots_per_epoch() as usize);
+    let update = latest_optimistic_update(&harness);
+    let seen_timestamp = harness
+        .chain
+        .slot_clock
+        .start_of(update.signature_slot)
+        .expect("should get slot start");
+
+    assert!(matches!(
+        harness
+            .chain
+            .verify_optimistic_update_for_gossip(update.clone(), seen_timestamp),
+        Err(Error::TooEarly { signature_slot }) if signature_slot == update.signature_slot
+    ));
+}
+
+#[test]
+fn optimistic_update_mismatched() {
+    let harness = get_harness(true);
+    harness.extend_chain_with_sync_aggregates(E::slots_per_epoch() as usize);
+    let mut update = latest_optimistic_update(&harness);
+    update.signature_slot += 1;
+    let seen_timestamp = publish_time(&harness, update.signature_slot);
+
+    assert!(matches!(
+        harness
+            .chain
+            .verify_optimistic_update_for_gossip(update, seen_timestamp),
+        Err(Error::UpdateMismatch)
+    ));
+}
+
+#[test]
+fn optimistic_update_light_client_server_disabled() {
+    let harness = get_harness(true);
+    harness.extend_chain_with_sync_aggregates(E::slots_per_epoch() as usize);
+    let update = latest_optimistic_update(&harness);
+    let seen_timestamp = publish_time(&harness, update.signature_slot);
+
+    let disabled_harness = get_harness(false);
+    assert!(matches!(
+        disabled_harness
+            .chain
+            .verify_optimistic_update_for_gossip(update, seen_timestamp),
+        Err(Error::LightClientServerDisabled)
+    ));
+}
+
+#[test]
+fn updates_published_once_block_propagated() {
+    let harness = get_finalized_harness();
+    let finality_update = latest_finality_update(&harness);
+    let optimistic_update = latest_optimistic_update(&harness);
+    let signature_slot = optimistic_update.signature_slot;
+    assert_eq!(finality_update.signature_slot, signature_slot);
+
+    // Nothing is published before the block at the signature slot has had time to propagate.
+    harness
+        .chain
+        .slot_clock
+        .set_current_time(publish_time(&harness, signature_slot) - Duration::from_millis(1));
+    assert_eq!(
+        harness
+            .chain
+            .light_client_finality_update_to_publish()
+            .expect("should read update"),
+        None
+    );
+    assert_eq!(
+        harness
+            .chain
+            .light_client_optimistic_update_to_publish()
+            .expect("should read update"),
+        None
+    );
+
+    // Both updates are published once, at the publish time.
+    harness
+        .chain
+        .slot_clock
+        .set_current_time(publish_time(&harness, signature_slot));
+    assert_eq!(
+        harness
+            .chain
+            .light_client_finality_update_to_publish()
+            .expect("should read update"),
+        Some(finality_update)
+    );
+    assert_eq!(
+        harness
+            .chain
+            .light_client_optimistic_update_to_publish()
+            .expect("should read update"),
+        Some(optimistic_update)
+    );
+    assert_eq!(
+        harness
+            .chain
+            .light_client_optimistic_update_to_publish()
+            .expect("should read update"),
+        None
+    );
+}
@@ -1,6 +1,7 @@
 mod attestation_production;
 mod attestation_verification;
 mod block_verification;
+mod light_client_update_verification;
 mod merge;
 mod op_verification;
 mod store_tests;
@@ -1,7 +1,8 @@
 #![cfg(not(debug_assertions))] // Tests run too slow in debug.
 
-use beacon_chain::test_utils::BeaconChainHarness;
+use beacon_chain::{test_utils::BeaconChainHarness, HeadSafetyStatus};
 use execution_layer::test_utils::{generate_pow_block, DEFAULT_TERMINAL_BLOCK};
+use std::borrow::Cow;
 use types::*;
 
 const VALIDATOR_COUNT: usize = 32;
@@ -180,3 +181,116 @@ fn base_altair_merge_with_terminal_block_after_fork() {
 
     verify_execution_payload_chain(&execution_payloads);
 }
+
+#[test]
+fn attest_to_verified_ancestor_of_optimistic_head() {
+    let altair_fork_epoch = Epoch::new(0);
+    let bellatrix_fork_epoch = Epoch::new(1);
+    let merge_fork_slot = bellatrix_fork_epoch.start_slot(E::slots_per_epoch());
+
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(altair_fork_epoch);
+    spec.bellatrix_fork_epoch = Some(bellatrix_fork_epoch);
+
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(spec)
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    harness.extend_to_slot(merge_fork_slot);
+    harness
+        .execution_block_generator()
+        .move_to_terminal_block()
+        .unwrap();
+
+    /*
+     * Import some blocks with payloads which are verified by the EL.
+     */
+
+    harness.extend_slots(2);
+
+    let verified_head = harness.chain.head_info().unwrap();
+    assert!(matches!(
+        harness.chain.head_safety_status().unwrap(),
+        HeadSafetyStatus::Safe(Some(_))
+    ));
+
+    /*
+     * Import some blocks optimistically.
+     */
+
+    harness
+        .mock_execution_layer
+        .as_ref()
+        .unwrap()
+        .server
+        .all_payloads_syncing();
+    harness.extend_slots(2);
+
+    let head = harness.chain.head_info().unwrap();
+    assert_eq!(head.slot, verified_head.slot + 2);
+    assert!(matches!(
+        harness.chain.head_safety_status().unwrap(),
+        HeadSafetyStatus::Unsafe(_)
+    ));
+    assert_eq!(
+        harness
+            .chain
+            .latest_verified_ancestor(head.block_root)
+            .unwrap()
+            .root,
+        verified_head.block_root
+    );
+
+    let slot = harness.chain.slot().unwrap();
+    let attestation = harness
+        .chain
+        .produce_unaggregated_attestation_for_verified_ancestor(slot, 0)
+        .unwrap();
+    assert_eq!(attestation.data.slot, slot);
+    assert_eq!(attestation.data.beacon_block_root, verified_head.block_root);
+
+    // The attestation matches one produced from the state of the verified ancestor, both in this
+    // epoch and the next.
+    let mut verified_state = harness
+        .chain
+        .get_state(&verified_head.state_root, Some(verified_head.slot))
+        .unwrap()
+        .unwrap();
+    verified_state
+        .build_committee_cache(RelativeEpoch::Current, &harness.chain.spec)
+        .unwrap();
+    for slot in [slot, slot + E::slots_per_epoch()] {
+        assert_eq!(
+            harness
+                .chain
+                .produce_unaggregated_attestation_for_verified_ancestor(slot, 0)
+                .unwrap(),
+            harness
+                .chain
+                .produce_unaggregated_attestation_for_block(
+                    slot,
+                    0,
+                    verified_head.block_root,
+                    Cow::Borrowed(&verified_state),
+                    verified_head.state_root,
+                )
+                .unwrap()
+        );
+    }
+
+    // Attestations to slots prior to the verified ancestor are unaffected.
+    let prior_slot = verified_head.slot - 1;
+    assert_eq!(
+        harness
+            .chain
+            .produce_unaggregated_attestation_for_verified_ancestor(prior_slot, 0)
+            .unwrap(),
+        harness
+            .chain
+            .produce_unaggregated_attestation(prior_slot, 0)
+            .unwrap()
+    );
+}
@@ -22,7 +22,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use store::{
     iter::{BlockRootsIterator, StateRootsIterator},
-    HotColdDB, LevelDB, StoreConfig,
+    DBColumn, HotColdDB, KeyValueStore, LevelDB, StoreConfig,
 };
 use tempfile::{tempdir, TempDir};
 use tree_hash::TreeHash;
@@ -48,10 +48,17 @@ fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
 fn get_store_with_spec(
     db_path: &TempDir,
     spec: ChainSpec,
+) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+    get_store_with_spec_and_config(db_path, spec, StoreConfig::default())
+}
+
+fn get_store_with_spec_and_config(
+    db_path: &TempDir,
+    spec: ChainSpec,
+    config: StoreConfig,
 ) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
     let log = test_logger();
 
     HotColdDB::open(&hot_path, &cold_path, |_, _, _| Ok(()), config, spec, log)
@@ -467,6 +474,49 @@ fn block_replay_with_inaccurate_state_roots() {
     );
 }
 
+#[test]
+fn hot_state_diffs() {
+    let num_blocks_produced = E::slots_per_epoch() * 2 + 5;
+    let db_path = tempdir().unwrap();
+    let config = StoreConfig {
+        hot_state_diffs: true,
+        ..StoreConfig::default()
+    };
+    let store = get_store_with_spec_and_config(&db_path, test_spec::<E>(), config);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    // Don't attest, so that all of the states remain in the hot database.
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(vec![]),
+    );
+
+    for snapshot in harness.chain.chain_dump().unwrap() {
+        let state_root = snapshot.beacon_state_root();
+        let slot = snapshot.beacon_block.slot();
+
+        // Intermediate states should have diffs, and the state loaded from the diff should be
+        // identical to the one loaded by replaying blocks.
+        if slot % E::slots_per_epoch() != 0 {
+            assert!(store
+                .hot_db
+                .key_exists(DBColumn::BeaconStateDiff.into(), state_root.as_bytes())
+                .unwrap());
+        }
+
+        let state = store.get_state(&state_root, Some(slot)).unwrap().unwrap();
+        assert_eq!(state.canonical_root(), state_root);
+
+        // Deleting the state should delete its diff.
+        store.delete_state(&state_root, slot).unwrap();
+        assert!(!store
+            .hot_db
+            .key_exists(DBColumn::BeaconStateDiff.into(), state_root.as_bytes())
+            .unwrap());
+    }
+}
+
 #[test]
 fn block_replayer_hooks() {
     let db_path = tempdir().unwrap();
@@ -2018,6 +2068,7 @@ fn weak_subjectivity_sync() {
         .event_handler(Some(ServerSentEventHandler::new_with_capacity(
             log.clone(),
             1,
+            0,
         )))
         .monitor_validators(true, vec![], log)
         .build()
@@ -2228,6 +2279,84 @@ fn finalizes_after_resuming_from_db() {
     );
 }
 
+#[test]
+fn light_client_updates_persist_across_restart() {
+    let num_blocks_produced = E::slots_per_epoch() * 2;
+    let db_path = tempdir().unwrap();
+    let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+    let store = get_store_with_spec(&db_path, spec.clone());
+    let chain_config = ChainConfig {
+        enable_light_client_server: true,
+        ..ChainConfig::default()
+    };
+
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(spec.clone())
+        .keypairs(KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec())
+        .fresh_disk_store(store.clone())
+        .chain_config(chain_config.clone())
+        .build();
+    harness.advance_slot();
+
+    harness.extend_chain_with_sync_aggregates(num_blocks_produced as usize);
+
+    let updates = harness
+        .chain
+        .get_light_client_updates(0, 1)
+        .expect("should get updates");
+    assert_eq!(
+        updates.len(),
+        1,
+        "should have an update for the first period"
+    );
+    let head_root = harness
+        .chain
+        .head()
+        .expect("should read head")
+        .beacon_block_root;
+    assert_eq!(
+        harness
+            .chain
+            .block_root_at_slot(
+                updates[0].attested_header.beacon.slot,
+                WhenSlotSkipped::None
+            )
+            .expect("should get block root"),
+        Some(updates[0].attested_header.beacon.canonical_root()),
+        "the update should attest to a canonical block"
+    );
+
+    harness
+        .chain
+        .persist_head_and_fork_choice()
+        .expect("should persist the head and fork choice");
+    drop(harness);
+
+    let resumed_harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .spec(spec)
+        .keypairs(KEYPAIRS[0..LOW_VALIDATOR_COUNT].to_vec())
+        .resumed_disk_store(store)
+        .chain_config(chain_config)
+        .build();
+    assert_eq!(
+        resumed_harness
+            .chain
+            .head()
+            .expect("should read head")
+            .beacon_block_root,
+        head_root
+    );
+
+    // Nothing is cached by the resumed chain, so the update must be read from the database.
+    assert_eq!(
+        resumed_harness
+            .chain
+            .get_light_client_updates(0, 1)
+            .expect("should get updates"),
+        updates
+    );
+}
+
 #[test]
 fn revert_minority_fork_on_resume() {
     let validator_count = 16;
@@ -2515,6 +2644,40 @@ fn check_chain_dump(harness: &TestHarness, expected_len: u64) {
             harness.chain.store.get_split_slot()
         );
     }
+
+    // Check the frozen block root index against the chain dump.
+    let store = &harness.chain.store;
+    let split_slot = store.get_split_slot();
+    for (block_root, slot) in &chain_dump_block_roots {
+        let frozen = *slot < split_slot;
+        assert_eq!(
+            store.get_frozen_block_slot(block_root).unwrap(),
+            frozen.then(|| *slot),
+            "frozen slot of block {:?}",
+            block_root
+        );
+        assert_eq!(
+            store.get_frozen_block_root(*slot).unwrap(),
+            frozen.then(|| *block_root),
+            "frozen block root at slot {}",
+            slot
+        );
+    }
+
+    // Skipped slots should not be indexed.
+    let num_frozen_blocks = chain_dump_block_roots
+        .iter()
+        .filter(|(_, slot)| *slot < split_slot)
+        .count();
+    let num_indexed_slots = (0..split_slot.as_u64())
+        .filter(|slot| {
+            store
+                .get_frozen_block_root(Slot::new(*slot))
+                .unwrap()
+                .is_some()
+        })
+        .count();
+    assert_eq!(num_indexed_slots, num_frozen_blocks);
 }
 
 /// Check that every state from the canonical chain is in the database, and that the
@@ -2,9 +2,10 @@
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    eth1_chain::SszEth1,
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
-        OP_POOL_DB_KEY,
+        ETH1_CACHE_DB_KEY, OP_POOL_DB_KEY,
     },
     StateSkipConfig, WhenSlotSkipped,
 };
@@ -438,6 +439,38 @@ fn roundtrip_operation_pool() {
     assert_eq!(harness.chain.op_pool, restored_op_pool);
 }
 
+#[test]
+fn eth1_cache_persisted_on_finalization() {
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
+
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let persisted_eth1_cache = || {
+        harness
+            .chain
+            .store
+            .get_item::<SszEth1>(&ETH1_CACHE_DB_KEY)
+            .expect("should read db")
+    };
+    assert!(persisted_eth1_cache().is_none());
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let state = &harness.chain.head().expect("should get head").beacon_state;
+    assert!(
+        state.finalized_checkpoint().epoch > 0,
+        "the chain should have finalized"
+    );
+    assert!(
+        persisted_eth1_cache().is_some(),
+        "the eth1 cache should be persisted on finalization"
+    );
+}
+
 #[test]
 fn unaggregated_attestations_added_to_fork_choice_some_none() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() / 2;
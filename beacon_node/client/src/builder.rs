@@ -1,3 +1,4 @@
+use crate::clock_drift::spawn_clock_drift_monitor;
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
 use crate::Client;
@@ -28,6 +29,7 @@ use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
+use task_executor::ShutdownStage;
 use timer::spawn_timer;
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
 use types::{
@@ -145,7 +147,10 @@ where
         let context = runtime_context.service_context("beacon".into());
         let spec = chain_spec.ok_or("beacon_chain_start_method requires a chain spec")?;
         let event_handler = if self.http_api_config.enabled {
-            Some(ServerSentEventHandler::new(context.log().clone()))
+            Some(ServerSentEventHandler::new(
+                context.log().clone(),
+                self.http_api_config.sse_replay_window,
+            ))
         } else {
             None
         };
@@ -154,6 +159,8 @@ where
             let context = runtime_context.service_context("exec".into());
             let execution_layer = ExecutionLayer::from_urls(
                 execution_endpoints,
+                config.execution_jwt_secrets,
+                config.builder_url,
                 config.suggested_fee_recipient,
                 context.executor.clone(),
                 context.log().clone(),
@@ -376,6 +383,7 @@ where
                         network_tx: None,
                         network_globals: None,
                         eth1_service: Some(genesis_service.eth1_service.clone()),
+                        log_levels: context.log_levels.clone(),
                         log: context.log().clone(),
                     });
 
@@ -547,7 +555,10 @@ where
     }
 
     /// Immediately starts the service that periodically logs information each slot.
-    pub fn notifier(self) -> Result<Self, String> {
+    ///
+    /// A warning is logged each slot whilst the disk containing the database has less than
+    /// `low_disk_space_threshold_gb` free.
+    pub fn notifier(self, low_disk_space_threshold_gb: u64) -> Result<Self, String> {
         let context = self
             .runtime_context
             .as_ref()
@@ -572,12 +583,38 @@ where
             beacon_chain,
             network_globals,
             seconds_per_slot,
+            self.db_path.clone(),
+            low_disk_space_threshold_gb,
         )
         .map_err(|e| format!("Unable to start slot notifier: {}", e))?;
 
         Ok(self)
     }
 
+    /// Immediately starts the service that periodically estimates the offset of the system clock.
+    ///
+    /// A warning is logged whilst the offset is greater than `threshold`. The NTP server at
+    /// `ntp_server` is only queried if it is provided.
+    pub fn clock_drift_monitor(
+        self,
+        ntp_server: Option<String>,
+        threshold: Duration,
+    ) -> Result<Self, String> {
+        let context = self
+            .runtime_context
+            .as_ref()
+            .ok_or("clock_drift_monitor requires a runtime_context")?
+            .service_context("clock_drift".into());
+        let beacon_chain = self
+            .beacon_chain
+            .clone()
+            .ok_or("clock_drift_monitor requires a beacon chain")?;
+
+        spawn_clock_drift_monitor(context.executor, beacon_chain, ntp_server, threshold);
+
+        Ok(self)
+    }
+
     /// Consumes the builder, returning a `Client` if all necessary components have been
     /// specified.
     ///
@@ -600,6 +637,7 @@ where
                 network_tx: self.network_send.clone(),
                 network_globals: self.network_globals.clone(),
                 eth1_service: self.eth1_service.clone(),
+                log_levels: runtime_context.log_levels.clone(),
                 log: log.clone(),
             });
 
@@ -663,6 +701,32 @@ where
                 state_advance_log,
             );
 
+            // Once the beacon processor has drained, wait for any database migration to finish
+            // and then persist the head and fork choice, so that a restart resumes from here.
+            let persist_chain = beacon_chain.clone();
+            let persist_log = log.clone();
+            runtime_context.executor.on_shutdown(
+                ShutdownStage::Persist,
+                "persist_beacon_chain",
+                async move {
+                    let result = tokio::task::spawn_blocking(move || {
+                        persist_chain.store_migrator.shutdown();
+                        persist_chain.persist_to_disk()
+                    })
+                    .await;
+
+                    match result {
+                        Ok(Ok(())) => debug!(persist_log, "Persisted beacon chain to disk"),
+                        Ok(Err(e)) => {
+                            warn!(persist_log, "Failed to persist beacon chain"; "error" => ?e)
+                        }
+                        Err(e) => {
+                            warn!(persist_log, "Failed to persist beacon chain"; "error" => %e)
+                        }
+                    }
+                },
+            );
+
             if let Some(execution_layer) = beacon_chain.execution_layer.as_ref() {
                 let store = beacon_chain.store.clone();
                 let inner_execution_layer = execution_layer.clone();
@@ -683,6 +747,7 @@ where
                                 store,
                                 head.finalized_checkpoint.root,
                                 block_hash,
+                                None,
                             )
                             .await;
 
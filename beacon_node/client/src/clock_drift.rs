@@ -0,0 +1,382 @@
+//! Estimates the offset of the system clock, which the slot clock is derived from.
+//!
+//! A skewed clock causes blocks and attestations to be produced at the wrong time and causes valid
+//! blocks from peers to be rejected (e.g., as `FutureSlot`), so it is worth warning loudly about.
+//!
+//! The offset is estimated in two ways:
+//!
+//! - By querying an NTP server, if one is configured.
+//! - From the times at which blocks arrive from peers. Blocks are produced at the start of their
+//!   slot, so blocks which consistently arrive *before* the start of their slot indicate that the
+//!   system clock is behind. A clock which is ahead can't be told apart from slow propagation, so
+//!   this only detects a clock which is behind.
+use crate::metrics;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use slog::{crit, debug, Logger};
+use slot_clock::SlotClock;
+use std::convert::TryInto;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::{lookup_host, UdpSocket};
+use tokio::time::{interval, timeout};
+use types::Slot;
+
+/// The interval between estimates of the clock offset.
+const CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// The time to wait for a response from the NTP server.
+const NTP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The port used if the NTP server is given without one.
+const DEFAULT_NTP_PORT: u16 = 123;
+
+/// The number of seconds between the NTP epoch (1900) and the unix epoch (1970).
+const NTP_UNIX_EPOCH_OFFSET_SECS: f64 = 2_208_988_800.0;
+
+/// The length of an SNTP packet without any extension fields.
+const NTP_PACKET_LEN: usize = 48;
+
+/// The minimum number of block arrivals required to estimate the clock offset.
+const MIN_BLOCK_ARRIVALS: usize = 8;
+
+/// Spawns a service which periodically estimates the offset of the system clock, warning if it is
+/// greater than `threshold`.
+///
+/// The NTP server at `ntp_server` (e.g. `pool.ntp.org`) is only queried if it is provided.
+pub fn spawn_clock_drift_monitor<T: BeaconChainTypes>(
+    executor: task_executor::TaskExecutor,
+    beacon_chain: Arc<BeaconChain<T>>,
+    ntp_server: Option<String>,
+    threshold: Duration,
+) {
+    let log = executor.log().clone();
+    let ntp_server = ntp_server.map(|server| with_default_port(&server));
+
+    let monitor_future = async move {
+        let mut interval = interval(CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            if let Some(ntp_server) = ntp_server.as_ref() {
+                match query_ntp_offset_ms(ntp_server).await {
+                    Ok(offset_ms) => {
+                        metrics::set_gauge(&metrics::CLOCK_DRIFT_NTP_OFFSET_MS, offset_ms);
+                        check_offset(offset_ms, threshold, "ntp", &log);
+                    }
+                    Err(e) => debug!(
+                        log,
+                        "Unable to query NTP server";
+                        "server" => ntp_server,
+                        "error" => e,
+                    ),
+                }
+            }
+
+            let arrivals = beacon_chain
+                .block_times_cache
+                .read()
+                .cache
+                .values()
+                .filter_map(|block_times| {
+                    Some((block_times.slot, block_times.timestamps.observed?))
+                })
+                .collect::<Vec<_>>();
+            if let Some(delay_ms) =
+                median_block_arrival_delay_ms(&arrivals, &beacon_chain.slot_clock)
+            {
+                metrics::set_gauge(&metrics::CLOCK_DRIFT_BLOCK_ARRIVAL_DELAY_MS, delay_ms);
+                // Only negative delays can be attributed to the clock.
+                if delay_ms < 0 {
+                    check_offset(delay_ms, threshold, "block_arrivals", &log);
+                }
+            }
+        }
+    };
+
+    executor.spawn(monitor_future, "clock_drift_monitor");
+}
+
+/// Warn if the system clock is offset from the true time by more than `threshold`.
+///
+/// A positive `offset_ms` means the system clock is behind.
+fn check_offset(offset_ms: i64, threshold: Duration, source: &str, log: &Logger) {
+    if offset_ms.unsigned_abs() as u128 > threshold.as_millis() {
+        crit!(
+            log,
+            "System clock is out of sync";
+            "msg" => "blocks and attestations will be late or rejected by peers",
+            "advice" => "ensure the system clock is synchronized, e.g. with NTP",
+            "offset_ms" => offset_ms,
+            "threshold_ms" => threshold.as_millis(),
+            "source" => source,
+        );
+    }
+}
+
+/// Returns the median delay between the start of a block's slot and the time at which it was
+/// observed, in milliseconds, over the `(slot, observed)` times of the blocks in the block times
+/// cache.
+///
+/// A negative delay means the block was observed before the start of its slot.
+fn median_block_arrival_delay_ms<S: SlotClock>(
+    arrivals: &[(Slot, Duration)],
+    slot_clock: &S,
+) -> Option<i64> {
+    let mut delays = arrivals
+        .iter()
+        .filter_map(|(slot, observed)| {
+            let slot_start = slot_clock.start_of(*slot)?;
+            Some(observed.as_millis() as i64 - slot_start.as_millis() as i64)
+        })
+        .collect::<Vec<_>>();
+
+    if delays.len() < MIN_BLOCK_ARRIVALS {
+        return None;
+    }
+    delays.sort_unstable();
+    Some(delays[delays.len() / 2])
+}
+
+/// Appends the default NTP port to `server` if it does not specify one.
+fn with_default_port(server: &str) -> String {
+    match server.parse::<IpAddr>() {
+        // A bare IPv6 address contains colons, but no port.
+        Ok(ip) => SocketAddr::new(ip, DEFAULT_NTP_PORT).to_string(),
+        Err(_) if server.contains(':') => server.to_string(),
+        Err(_) => format!("{}:{}", server, DEFAULT_NTP_PORT),
+    }
+}
+
+/// Query the SNTP server at `server`, returning the offset of the system clock in milliseconds.
+///
+/// A positive offset means the system clock is behind the server's.
+async fn query_ntp_offset_ms(server: &str) -> Result<i64, String> {
+    let address = lookup_host(server)
+        .await
+        .map_err(|e| format!("Unable to resolve address: {:?}", e))?
+        .next()
+        .ok_or_else(|| "Unable to resolve address".to_string())?;
+    let bind_address: SocketAddr = if address.is_ipv6() {
+        (Ipv6Addr::UNSPECIFIED, 0).into()
+    } else {
+        (Ipv4Addr::UNSPECIFIED, 0).into()
+    };
+    let socket = UdpSocket::bind(bind_address)
+        .await
+        .map_err(|e| format!("Unable to bind socket: {:?}", e))?;
+    socket
+        .connect(address)
+        .await
+        .map_err(|e| format!("Unable to connect: {:?}", e))?;
+
+    let request = ntp_request(unix_now_secs());
+    socket
+        .send(&request)
+        .await
+        .map_err(|e| format!("Unable to send request: {:?}", e))?;
+
+    let mut response = [0; NTP_PACKET_LEN];
+    let len = timeout(NTP_TIMEOUT, socket.recv(&mut response))
+        .await
+        .map_err(|_| "Timed out".to_string())?
+        .map_err(|e| format!("Unable to receive response: {:?}", e))?;
+    let destination = unix_now_secs();
+    if len < response.len() {
+        return Err(format!("Response too short: {} bytes", len));
+    }
+
+    ntp_offset_ms(&request, &response, destination)
+}
+
+/// Returns an SNTP request, sent at `transmit` seconds since the unix epoch.
+fn ntp_request(transmit: f64) -> [u8; NTP_PACKET_LEN] {
+    // Leap indicator 0, version 4, mode 3 (client).
+    let mut request = [0; NTP_PACKET_LEN];
+    request[0] = 0x23;
+    request[40..48].copy_from_slice(&to_ntp_timestamp(transmit).to_be_bytes());
+    request
+}
+
+/// Returns the offset of the system clock in milliseconds from the server's `response` to
+/// `request`, which was received at `destination` seconds since the unix epoch.
+///
+/// A positive offset means the system clock is behind the server's.
+fn ntp_offset_ms(
+    request: &[u8; NTP_PACKET_LEN],
+    response: &[u8; NTP_PACKET_LEN],
+    destination: f64,
+) -> Result<i64, String> {
+    let timestamp = |packet: &[u8; NTP_PACKET_LEN], offset: usize| {
+        packet[offset..offset + 8]
+            .try_into()
+            .map(|bytes| from_ntp_timestamp(u64::from_be_bytes(bytes)))
+            .map_err(|e| format!("Invalid timestamp: {:?}", e))
+    };
+
+    // The server echoes the transmit timestamp of the request as the originate timestamp of the
+    // response, which distinguishes its response from stale or spoofed packets.
+    if response[24..32] != request[40..48] {
+        return Err("Response does not match the request".to_string());
+    }
+
+    let originate = timestamp(request, 40)?;
+    let receive = timestamp(response, 32)?;
+    let transmit = timestamp(response, 40)?;
+    if transmit == 0.0 {
+        return Err("Server did not provide a time".to_string());
+    }
+
+    let offset_secs = ((receive - originate) + (transmit - destination)) / 2.0;
+    Ok((offset_secs * 1000.0).round() as i64)
+}
+
+fn unix_now_secs() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_else(|_| Duration::from_secs(0))
+        .as_secs_f64()
+}
+
+/// Convert seconds since the unix epoch to a 64-bit NTP timestamp.
+fn to_ntp_timestamp(unix_secs: f64) -> u64 {
+    let ntp_secs = unix_secs + NTP_UNIX_EPOCH_OFFSET_SECS;
+    let secs = ntp_secs.trunc() as u64;
+    let fraction = (ntp_secs.fract() * (1_u64 << 32) as f64) as u64;
+    (secs << 32) | fraction
+}
+
+/// Convert a 64-bit NTP timestamp to seconds since the unix epoch.
+fn from_ntp_timestamp(timestamp: u64) -> f64 {
+    if timestamp == 0 {
+        return 0.0;
+    }
+    let secs = (timestamp >> 32) as f64;
+    let fraction = (timestamp & 0xffff_ffff) as f64 / (1_u64 << 32) as f64;
+    secs + fraction - NTP_UNIX_EPOCH_OFFSET_SECS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use slot_clock::ManualSlotClock;
+
+    /// Returns a response to `request` with the given receive and transmit times.
+    fn ntp_response(
+        request: &[u8; NTP_PACKET_LEN],
+        receive: f64,
+        transmit: f64,
+    ) -> [u8; NTP_PACKET_LEN] {
+        let mut response = [0; NTP_PACKET_LEN];
+        // Leap indicator 0, version 4, mode 4 (server).
+        response[0] = 0x24;
+        response[24..32].copy_from_slice(&request[40..48]);
+        response[32..40].copy_from_slice(&to_ntp_timestamp(receive).to_be_bytes());
+        response[40..48].copy_from_slice(&to_ntp_timestamp(transmit).to_be_bytes());
+        response
+    }
+
+    #[test]
+    fn ntp_timestamp_round_trip() {
+        assert_eq!(to_ntp_timestamp(0.0), 2_208_988_800 << 32);
+        assert_eq!(to_ntp_timestamp(0.5), (2_208_988_800 << 32) | (1 << 31));
+
+        for unix_secs in [0.5, 1.0, 1_650_000_000.0, 1_650_000_000.123_456] {
+            let round_trip = from_ntp_timestamp(to_ntp_timestamp(unix_secs));
+            assert!(
+                (round_trip - unix_secs).abs() < 1e-6,
+                "{} != {}",
+                round_trip,
+                unix_secs
+            );
+        }
+
+        // An all-zero timestamp means the time is unknown.
+        assert_eq!(from_ntp_timestamp(0), 0.0);
+    }
+
+    #[test]
+    fn ntp_offset() {
+        let originate = 1_650_000_000.0;
+        let request = ntp_request(originate);
+
+        // The server's clock is 500ms ahead, with 100ms of latency in each direction and 100ms
+        // spent processing the request.
+        let response = ntp_response(&request, originate + 0.6, originate + 0.7);
+        assert_eq!(ntp_offset_ms(&request, &response, originate + 0.3), Ok(500));
+
+        // The server's clock is 500ms behind.
+        let response = ntp_response(&request, originate - 0.4, originate - 0.3);
+        assert_eq!(
+            ntp_offset_ms(&request, &response, originate + 0.3),
+            Ok(-500)
+        );
+    }
+
+    #[test]
+    fn ntp_response_must_echo_request() {
+        let originate = 1_650_000_000.0;
+        let request = ntp_request(originate);
+
+        let stale_request = ntp_request(originate - 300.0);
+        let response = ntp_response(&stale_request, originate, originate);
+        assert!(ntp_offset_ms(&request, &response, originate).is_err());
+
+        let mut response = ntp_response(&request, originate, originate);
+        response[40..48].copy_from_slice(&[0; 8]);
+        assert!(ntp_offset_ms(&request, &response, originate).is_err());
+    }
+
+    #[test]
+    fn default_port() {
+        assert_eq!(with_default_port("pool.ntp.org"), "pool.ntp.org:123");
+        assert_eq!(with_default_port("pool.ntp.org:1123"), "pool.ntp.org:1123");
+        assert_eq!(with_default_port("127.0.0.1"), "127.0.0.1:123");
+        assert_eq!(with_default_port("2001:db8::1"), "[2001:db8::1]:123");
+        assert_eq!(
+            with_default_port("[2001:db8::1]:1123"),
+            "[2001:db8::1]:1123"
+        );
+    }
+
+    #[test]
+    fn median_block_arrival_delay() {
+        let slot_clock = ManualSlotClock::new(
+            Slot::new(0),
+            Duration::from_secs(1_000),
+            Duration::from_secs(12),
+        );
+        let arrival = |slot: u64, delay_ms: i64| {
+            let slot_start = slot_clock.start_of(Slot::new(slot)).unwrap();
+            let observed = if delay_ms < 0 {
+                slot_start - Duration::from_millis(delay_ms.unsigned_abs())
+            } else {
+                slot_start + Duration::from_millis(delay_ms as u64)
+            };
+            (Slot::new(slot), observed)
+        };
+
+        // Too few blocks to estimate the offset.
+        let mut arrivals = (1..MIN_BLOCK_ARRIVALS as u64)
+            .map(|slot| arrival(slot, -2_000))
+            .collect::<Vec<_>>();
+        assert_eq!(median_block_arrival_delay_ms(&arrivals, &slot_clock), None);
+
+        // The median is robust to a minority of outliers.
+        arrivals.push(arrival(20, 4_000));
+        arrivals.push(arrival(21, 8_000));
+        arrivals.push(arrival(22, 6_000));
+        assert_eq!(
+            median_block_arrival_delay_ms(&arrivals, &slot_clock),
+            Some(-2_000)
+        );
+
+        let arrivals = (1..=9)
+            .map(|slot| arrival(slot, slot as i64 * 100))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            median_block_arrival_delay_ms(&arrivals, &slot_clock),
+            Some(500)
+        );
+    }
+}
@@ -4,6 +4,7 @@ use sensitive_url::SensitiveUrl;
 use serde_derive::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 use types::{Address, Graffiti, PublicKeyBytes};
 
 /// Default directory name for the freezer database under the top-level data dir.
@@ -75,11 +76,24 @@ pub struct Config {
     pub chain: beacon_chain::ChainConfig,
     pub eth1: eth1::Config,
     pub execution_endpoints: Option<Vec<SensitiveUrl>>,
+    /// Paths to the JWT secrets used to authenticate with each of the `execution_endpoints`.
+    pub execution_jwt_secrets: Vec<PathBuf>,
+    pub builder_url: Option<SensitiveUrl>,
     pub suggested_fee_recipient: Option<Address>,
     pub http_api: http_api::Config,
     pub http_metrics: http_metrics::Config,
     pub monitoring_api: Option<monitoring_api::Config>,
     pub slasher: Option<slasher::Config>,
+    /// If set, the node runs in light mode instead of importing blocks.
+    pub light_client: Option<light_client::Config>,
+    /// The maximum time to allow each stage of a graceful shutdown.
+    pub shutdown_timeout: Duration,
+    /// Log a warning when the disk containing the database has less than this many GB free.
+    pub low_disk_space_threshold_gb: u64,
+    /// An NTP server to query when estimating the offset of the system clock.
+    pub ntp_server: Option<String>,
+    /// Log a warning when the system clock is estimated to be offset by more than this.
+    pub clock_drift_threshold: Duration,
 }
 
 impl Default for Config {
@@ -97,6 +111,8 @@ impl Default for Config {
             sync_eth1_chain: false,
             eth1: <_>::default(),
             execution_endpoints: None,
+            execution_jwt_secrets: vec![],
+            builder_url: None,
             suggested_fee_recipient: None,
             disabled_forks: Vec::new(),
             graffiti: Graffiti::default(),
@@ -104,8 +120,13 @@ impl Default for Config {
             http_metrics: <_>::default(),
             monitoring_api: None,
             slasher: None,
+            light_client: None,
             validator_monitor_auto: false,
             validator_monitor_pubkeys: vec![],
+            shutdown_timeout: Duration::from_secs(15),
+            low_disk_space_threshold_gb: 10,
+            ntp_server: None,
+            clock_drift_threshold: Duration::from_millis(500),
         }
     }
 }
@@ -1,5 +1,6 @@
 extern crate slog;
 
+mod clock_drift;
 pub mod config;
 mod metrics;
 mod notifier;
@@ -16,4 +16,14 @@ lazy_static! {
         "notifier_head_slot",
         "The head slot sourced from the beacon chain notifier"
     );
+
+    pub static ref CLOCK_DRIFT_NTP_OFFSET_MS: Result<IntGauge> = try_create_int_gauge(
+        "clock_drift_ntp_offset_milliseconds",
+        "The offset of the system clock from the NTP server's clock, positive if the system clock is behind"
+    );
+
+    pub static ref CLOCK_DRIFT_BLOCK_ARRIVAL_DELAY_MS: Result<IntGauge> = try_create_int_gauge(
+        "clock_drift_block_arrival_median_delay_milliseconds",
+        "The median delay between the start of a block's slot and its arrival, negative if blocks arrive early"
+    );
 }
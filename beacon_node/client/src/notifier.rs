@@ -1,9 +1,11 @@
 use crate::metrics;
 use beacon_chain::{BeaconChain, BeaconChainTypes, HeadSafetyStatus};
+use eth2::lighthouse::DiskHealth;
 use lighthouse_network::{types::SyncState, NetworkGlobals};
 use parking_lot::Mutex;
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
@@ -12,6 +14,8 @@ use types::{EthSpec, Slot};
 /// Create a warning log whenever the peer count is at or below this value.
 pub const WARN_PEER_COUNT: usize = 1;
 
+const BYTES_PER_GB: u64 = 1_000_000_000;
+
 const DAYS_PER_WEEK: i64 = 7;
 const HOURS_PER_DAY: i64 = 24;
 const MINUTES_PER_HOUR: i64 = 60;
@@ -28,6 +32,8 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
     beacon_chain: Arc<BeaconChain<T>>,
     network: Arc<NetworkGlobals<T::EthSpec>>,
     seconds_per_slot: u64,
+    db_path: Option<PathBuf>,
+    low_disk_space_threshold_gb: u64,
 ) -> Result<(), String> {
     let slot_duration = Duration::from_secs(seconds_per_slot);
     let duration_to_next_slot = beacon_chain
@@ -173,6 +179,10 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
                 warn!(log, "Low peer count"; "peer_count" => peer_count_pretty(connected_peer_count));
             }
 
+            if let Some(db_path) = db_path.as_ref() {
+                disk_space_logging(db_path, low_disk_space_threshold_gb, &log);
+            }
+
             debug!(
                 log,
                 "Slot timer";
@@ -329,6 +339,32 @@ pub fn spawn_notifier<T: BeaconChainTypes>(
     Ok(())
 }
 
+/// Warn if the volume containing the database at `db_path` has less than
+/// `low_disk_space_threshold_gb` free. A threshold of zero disables the warning.
+fn disk_space_logging(db_path: &Path, low_disk_space_threshold_gb: u64, log: &Logger) {
+    if low_disk_space_threshold_gb == 0 {
+        return;
+    }
+
+    match DiskHealth::observe(db_path) {
+        Ok(health)
+            if health.disk_bytes_free
+                < low_disk_space_threshold_gb.saturating_mul(BYTES_PER_GB) =>
+        {
+            warn!(
+                log,
+                "Low disk space";
+                "msg" => "the node will stop working when the disk is full",
+                "free_gb" => health.disk_bytes_free / BYTES_PER_GB,
+                "threshold_gb" => low_disk_space_threshold_gb,
+                "path" => ?db_path,
+            );
+        }
+        Ok(_) => {}
+        Err(e) => debug!(log, "Unable to check disk space"; "error" => e),
+    }
+}
+
 fn eth1_logging<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>, log: &Logger) {
     let current_slot_opt = beacon_chain.slot().ok();
 
@@ -26,6 +26,12 @@ lazy_static! {
     pub static ref ENDPOINT_REQUESTS: Result<IntCounterVec> = try_create_int_counter_vec(
         "eth1_endpoint_requests", "The number of eth1 requests for each endpoint", &["endpoint"]
     );
+    pub static ref ENDPOINT_CONSECUTIVE_FAILURES: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "eth1_endpoint_consecutive_failures",
+        "The number of consecutive failed eth1 requests for each endpoint. Endpoints which have \
+         failed recently are tried after the others",
+        &["endpoint"]
+    );
 
     /*
      * Eth1 rpc connection
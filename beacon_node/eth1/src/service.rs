@@ -10,7 +10,7 @@ use crate::{
 };
 use fallback::{Fallback, FallbackError};
 use futures::future::TryFutureExt;
-use parking_lot::{RwLock, RwLockReadGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, trace, warn, Logger};
@@ -44,6 +44,11 @@ const WARNING_MSG: &str = "BLOCK PROPOSALS WILL FAIL WITHOUT VALID, SYNCED ETH1
 /// A factor used to reduce the eth1 follow distance to account for discrepancies in the block time.
 const ETH1_BLOCK_TIME_TOLERANCE_FACTOR: u64 = 4;
 
+/// The time for which an endpoint is tried after the others, following a failure. This doubles
+/// with each consecutive failure, up to `MAX_LIVENESS_BACKOFF`.
+const LIVENESS_BACKOFF: Duration = Duration::from_secs(60);
+const MAX_LIVENESS_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum EndpointError {
     RequestFailed(String),
@@ -54,9 +59,41 @@ pub enum EndpointError {
 
 type EndpointState = Result<(), EndpointError>;
 
+/// Tracks the recent failures of an endpoint, so that an endpoint which keeps failing (e.g.,
+/// because it is rate-limited) is tried after the other endpoints until it has had time to recover.
+#[derive(Default)]
+struct Liveness {
+    consecutive_failures: u32,
+    last_failure: Option<Instant>,
+}
+
+impl Liveness {
+    fn backoff(&self) -> Duration {
+        let exponent = self.consecutive_failures.saturating_sub(1).min(16);
+        std::cmp::min(LIVENESS_BACKOFF * 2_u32.pow(exponent), MAX_LIVENESS_BACKOFF)
+    }
+
+    /// Returns `true` if the endpoint has failed recently and should be tried after the others.
+    fn is_backing_off(&self, now: Instant) -> bool {
+        self.last_failure
+            .map_or(false, |last_failure| now < last_failure + self.backoff())
+    }
+
+    fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.last_failure = None;
+    }
+
+    fn record_failure(&mut self, now: Instant) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_failure = Some(now);
+    }
+}
+
 pub struct EndpointWithState {
     endpoint: SensitiveUrl,
     state: TRwLock<Option<EndpointState>>,
+    liveness: Mutex<Liveness>,
 }
 
 impl EndpointWithState {
@@ -64,8 +101,35 @@ impl EndpointWithState {
         Self {
             endpoint,
             state: TRwLock::new(None),
+            liveness: Mutex::new(Liveness::default()),
         }
     }
+
+    fn record_success(&self) {
+        self.liveness.lock().record_success();
+        metrics::set_gauge_vec(
+            &metrics::ENDPOINT_CONSECUTIVE_FAILURES,
+            &[&self.endpoint.to_string()],
+            0,
+        );
+    }
+
+    fn record_failure(&self, log: &Logger) {
+        let mut liveness = self.liveness.lock();
+        liveness.record_failure(Instant::now());
+        metrics::set_gauge_vec(
+            &metrics::ENDPOINT_CONSECUTIVE_FAILURES,
+            &[&self.endpoint.to_string()],
+            liveness.consecutive_failures as i64,
+        );
+        debug!(
+            log,
+            "Deprioritising eth1 endpoint";
+            "endpoint" => %self.endpoint,
+            "consecutive_failures" => liveness.consecutive_failures,
+            "backoff_seconds" => liveness.backoff().as_secs(),
+        );
+    }
 }
 
 async fn reset_endpoint_state(endpoint: &EndpointWithState) {
@@ -121,8 +185,20 @@ impl EndpointsCache {
         state
     }
 
+    /// Returns the indices of the endpoints in the order in which they should be tried: those
+    /// which haven't failed recently in the configured order, followed by the others.
+    fn endpoint_order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut order: Vec<usize> = (0..self.fallback.servers.len()).collect();
+        // The sort is stable, so the configured order is kept within each group.
+        order.sort_by_key(|&i| self.fallback.servers[i].liveness.lock().is_backing_off(now));
+        order
+    }
+
     /// Return the first successful result along with number of previous errors encountered
     /// or all the errors encountered if every none of the fallback endpoints return required output.
+    ///
+    /// Endpoints which have failed recently are tried last, see `Self::endpoint_order`.
     pub async fn first_success<'a, F, O, R>(
         &'a self,
         func: F,
@@ -133,7 +209,7 @@ impl EndpointsCache {
     {
         let func = &func;
         self.fallback
-            .first_success(|endpoint| async move {
+            .first_success_in_order(&self.endpoint_order(), |endpoint| async move {
                 match self.state(endpoint).await {
                     Ok(()) => {
                         let endpoint_str = &endpoint.endpoint.to_string();
@@ -142,12 +218,16 @@ impl EndpointsCache {
                             &[endpoint_str],
                         );
                         match func(&endpoint.endpoint).await {
-                            Ok(t) => Ok(t),
+                            Ok(t) => {
+                                endpoint.record_success();
+                                Ok(t)
+                            }
                             Err(t) => {
                                 crate::metrics::inc_counter_vec(
                                     &crate::metrics::ENDPOINT_ERRORS,
                                     &[endpoint_str],
                                 );
+                                endpoint.record_failure(&self.log);
                                 if let SingleEndpointError::EndpointError(e) = &t {
                                     *endpoint.state.write().await = Some(Err(e.clone()));
                                 } else {
@@ -158,7 +238,10 @@ impl EndpointsCache {
                             }
                         }
                     }
-                    Err(e) => Err(SingleEndpointError::EndpointError(e)),
+                    Err(e) => {
+                        endpoint.record_failure(&self.log);
+                        Err(SingleEndpointError::EndpointError(e))
+                    }
                 }
             })
             .await
@@ -389,6 +472,10 @@ pub struct Config {
     ///
     /// Note: this should be less than or equal to the specification's `ETH1_FOLLOW_DISTANCE`.
     pub follow_distance: u64,
+    /// Defines how far behind the Eth1 node's head blocks are imported into the caches.
+    ///
+    /// If `None`, `follow_distance` is reduced by a tolerance for irregular Eth1 block times.
+    pub cache_follow_distance: Option<u64>,
     /// Specifies the seconds when we consider the head of a node far behind.
     /// This should be less than `ETH1_FOLLOW_DISTANCE * SECONDS_PER_ETH1_BLOCK`.
     pub node_far_behind_seconds: u64,
@@ -443,6 +530,7 @@ impl Default for Config {
             deposit_contract_deploy_block: 1,
             lowest_cached_block_number: 1,
             follow_distance: 128,
+            cache_follow_distance: None,
             node_far_behind_seconds: 128 * 14,
             block_cache_truncation: Some(4_096),
             auto_update_interval_millis: 60_000,
@@ -491,9 +579,14 @@ impl Service {
     ///
     /// This is useful since the spec declares `SECONDS_PER_ETH1_BLOCK` to be `14`, whilst it is
     /// actually `15` on Goerli.
+    ///
+    /// The `cache_follow_distance` is used instead, if it has been configured.
     pub fn reduced_follow_distance(&self) -> u64 {
-        let full = self.config().follow_distance;
-        full.saturating_sub(full / ETH1_BLOCK_TIME_TOLERANCE_FACTOR)
+        let config = self.config();
+        config.cache_follow_distance.unwrap_or_else(|| {
+            let full = config.follow_distance;
+            full.saturating_sub(full / ETH1_BLOCK_TIME_TOLERANCE_FACTOR)
+        })
     }
 
     /// Return byte representation of deposit and block caches.
@@ -1284,6 +1377,46 @@ mod tests {
         toml::from_str::<Config>(&serialized).expect("Should serde decode default config");
     }
 
+    #[test]
+    fn liveness_backoff() {
+        let now = Instant::now();
+        let mut liveness = Liveness::default();
+        assert!(!liveness.is_backing_off(now));
+
+        liveness.record_failure(now);
+        assert_eq!(liveness.backoff(), LIVENESS_BACKOFF);
+        assert!(liveness.is_backing_off(now + LIVENESS_BACKOFF / 2));
+        assert!(!liveness.is_backing_off(now + LIVENESS_BACKOFF));
+
+        liveness.record_failure(now);
+        assert_eq!(liveness.backoff(), LIVENESS_BACKOFF * 2);
+
+        for _ in 0..100 {
+            liveness.record_failure(now);
+        }
+        assert_eq!(liveness.backoff(), MAX_LIVENESS_BACKOFF);
+
+        liveness.record_success();
+        assert!(!liveness.is_backing_off(now));
+    }
+
+    #[test]
+    fn cache_follow_distance() {
+        let log = environment::null_logger().unwrap();
+        let spec = MainnetEthSpec::default_spec();
+        let mut config = Config {
+            follow_distance: 2048,
+            ..Config::default()
+        };
+
+        let service = Service::new(config.clone(), log.clone(), spec.clone());
+        assert_eq!(service.reduced_follow_distance(), 1536);
+
+        config.cache_follow_distance = Some(1024);
+        let service = Service::new(config, log, spec);
+        assert_eq!(service.reduced_follow_distance(), 1024);
+    }
+
     #[test]
     fn block_cache_size() {
         let mut config = Config::default();
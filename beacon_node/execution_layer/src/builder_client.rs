@@ -0,0 +1,133 @@
+//! Contains a client for servers implementing the builder API, such as MEV-boost.
+//!
+//! See: https://github.com/ethereum/builder-specs
+
+use reqwest::{Client, StatusCode};
+use sensitive_url::SensitiveUrl;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::time::Duration;
+use types::{
+    EthSpec, ExecutionPayload, Hash256, PublicKeyBytes, SignedBlindedBeaconBlock, SignedBuilderBid,
+    SignedValidatorRegistrationData, Slot,
+};
+
+pub const BUILDER_REGISTER_VALIDATORS_TIMEOUT: Duration = Duration::from_secs(4);
+pub const BUILDER_GET_HEADER_TIMEOUT: Duration = Duration::from_secs(1);
+pub const BUILDER_PROPOSE_BLINDED_BLOCK_TIMEOUT: Duration = Duration::from_secs(4);
+
+#[derive(Debug)]
+pub enum Error {
+    Reqwest(reqwest::Error),
+    InvalidUrl(SensitiveUrl),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Reqwest(e)
+    }
+}
+
+/// The `data` of a response from the builder API. The `version` field is ignored.
+#[derive(Deserialize)]
+#[serde(bound = "T: DeserializeOwned")]
+struct BuilderResponse<T> {
+    data: T,
+}
+
+pub struct BuilderHttpClient {
+    client: Client,
+    server: SensitiveUrl,
+}
+
+impl BuilderHttpClient {
+    pub fn new(server: SensitiveUrl) -> Result<Self, Error> {
+        Ok(Self {
+            client: Client::builder().build()?,
+            server,
+        })
+    }
+
+    /// Returns the URL for `segments` beneath `/eth/v1/builder/`.
+    fn builder_path(&self, segments: &[&str]) -> Result<reqwest::Url, Error> {
+        let mut path = self.server.full.clone();
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .pop_if_empty()
+            .extend(&["eth", "v1", "builder"])
+            .extend(segments);
+        Ok(path)
+    }
+
+    async fn post_with_timeout<T: Serialize + ?Sized>(
+        &self,
+        url: reqwest::Url,
+        body: &T,
+        timeout: Duration,
+    ) -> Result<reqwest::Response, Error> {
+        Ok(self
+            .client
+            .post(url)
+            .timeout(timeout)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?)
+    }
+
+    /// `POST /eth/v1/builder/validators`
+    pub async fn post_builder_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        let path = self.builder_path(&["validators"])?;
+        self.post_with_timeout(path, registrations, BUILDER_REGISTER_VALIDATORS_TIMEOUT)
+            .await?;
+        Ok(())
+    }
+
+    /// `GET /eth/v1/builder/header/{slot}/{parent_hash}/{pubkey}`
+    ///
+    /// Returns `Ok(None)` if the builder has no bid for the given parameters.
+    pub async fn get_builder_header<E: EthSpec>(
+        &self,
+        slot: Slot,
+        parent_hash: Hash256,
+        pubkey: &PublicKeyBytes,
+    ) -> Result<Option<SignedBuilderBid<E>>, Error> {
+        let path = self.builder_path(&[
+            "header",
+            &slot.to_string(),
+            &format!("{:?}", parent_hash),
+            &pubkey.as_hex_string(),
+        ])?;
+
+        let response = self
+            .client
+            .get(path)
+            .timeout(BUILDER_GET_HEADER_TIMEOUT)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None);
+        }
+
+        let response: BuilderResponse<SignedBuilderBid<E>> = response.json().await?;
+        Ok(Some(response.data))
+    }
+
+    /// `POST /eth/v1/builder/blinded_blocks`
+    pub async fn post_builder_blinded_blocks<E: EthSpec>(
+        &self,
+        block: &SignedBlindedBeaconBlock<E>,
+    ) -> Result<ExecutionPayload<E>, Error> {
+        let path = self.builder_path(&["blinded_blocks"])?;
+        let response: BuilderResponse<ExecutionPayload<E>> = self
+            .post_with_timeout(path, block, BUILDER_PROPOSE_BLINDED_BLOCK_TIMEOUT)
+            .await?
+            .json()
+            .await?;
+        Ok(response.data)
+    }
+}
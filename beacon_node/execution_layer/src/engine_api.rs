@@ -1,12 +1,14 @@
 use async_trait::async_trait;
 use eth1::http::RpcError;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 pub const LATEST_TAG: &str = "latest";
 
 use crate::engines::ForkChoiceState;
 pub use types::{Address, EthSpec, ExecutionPayload, Hash256, Uint256};
 
+pub mod auth;
 pub mod http;
 pub mod json_structures;
 
@@ -19,13 +21,19 @@ pub enum Error {
     RequestFailed(String),
     JsonRpc(RpcError),
     Json(serde_json::Error),
-    ServerMessage { code: i64, message: String },
+    ServerMessage {
+        code: i64,
+        message: String,
+    },
     Eip155Failure,
     IsSyncing,
     ExecutionBlockNotFound(Hash256),
     ExecutionHeadBlockNotFound,
     ParentHashEqualsBlockHash(Hash256),
     PayloadIdUnavailable,
+    Auth(auth::Error),
+    /// The execution engine did not advertise support for the method.
+    UnsupportedMethod(&'static str),
 }
 
 impl From<reqwest::Error> for Error {
@@ -34,6 +42,12 @@ impl From<reqwest::Error> for Error {
     }
 }
 
+impl From<auth::Error> for Error {
+    fn from(e: auth::Error) -> Self {
+        Error::Auth(e)
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(e: serde_json::Error) -> Self {
         Error::Json(e)
@@ -55,7 +69,7 @@ pub trait EngineApi {
         block_hash: Hash256,
     ) -> Result<Option<ExecutionBlock>, Error>;
 
-    async fn execute_payload_v1<T: EthSpec>(
+    async fn new_payload_v1<T: EthSpec>(
         &self,
         execution_payload: ExecutionPayload<T>,
     ) -> Result<ExecutePayloadResponse, Error>;
@@ -70,6 +84,46 @@ pub trait EngineApi {
         forkchoice_state: ForkChoiceState,
         payload_attributes: Option<PayloadAttributes>,
     ) -> Result<ForkchoiceUpdatedResponse, Error>;
+
+    async fn exchange_capabilities(&self) -> Result<EngineCapabilities, Error>;
+}
+
+/// The Engine API methods supported by an execution engine, as advertised in response to
+/// `engine_exchangeCapabilities`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EngineCapabilities {
+    methods: HashSet<String>,
+}
+
+impl EngineCapabilities {
+    pub fn new<I: IntoIterator<Item = String>>(methods: I) -> Self {
+        Self {
+            methods: methods.into_iter().collect(),
+        }
+    }
+
+    /// The capabilities assumed of an engine which predates `engine_exchangeCapabilities`, which
+    /// is presumed to support the V1 methods only.
+    pub fn legacy() -> Self {
+        Self::new(
+            http::LEGACY_CAPABILITIES
+                .iter()
+                .map(|method| method.to_string()),
+        )
+    }
+
+    pub fn supports(&self, method: &str) -> bool {
+        self.methods.contains(method)
+    }
+
+    /// Returns the methods used by Lighthouse which the engine does not support.
+    pub fn missing_methods(&self) -> Vec<&'static str> {
+        http::LIGHTHOUSE_CAPABILITIES
+            .iter()
+            .copied()
+            .filter(|method| !self.supports(method))
+            .collect()
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
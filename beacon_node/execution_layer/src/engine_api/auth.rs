@@ -0,0 +1,196 @@
+//! JWT authentication of requests to the Engine API.
+//!
+//! The execution engine and the beacon node share a 32 byte secret, stored hex-encoded in a file.
+//! Each request carries a fresh HS256 token in its `Authorization` header, containing an `iat`
+//! (issued-at) claim which the execution engine checks against its own clock.
+//!
+//! https://github.com/ethereum/execution-apis/blob/main/src/engine/authentication.md
+use jsonwebtoken::{encode, get_current_timestamp, Algorithm, EncodingKey, Header};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The length of the shared secret, in bytes.
+pub const JWT_SECRET_LENGTH: usize = 32;
+
+#[derive(Debug)]
+pub enum Error {
+    InvalidSecretLength(usize),
+    InvalidSecretHex(hex::FromHexError),
+    UnableToReadSecret(String),
+    UnableToWriteSecret(String),
+    InvalidToken(String),
+}
+
+impl From<jsonwebtoken::errors::Error> for Error {
+    fn from(e: jsonwebtoken::errors::Error) -> Self {
+        Error::InvalidToken(format!("{:?}", e))
+    }
+}
+
+/// The secret shared between the beacon node and an execution engine.
+#[derive(Clone, PartialEq)]
+pub struct JwtKey([u8; JWT_SECRET_LENGTH]);
+
+impl JwtKey {
+    pub fn from_slice(key: &[u8]) -> Result<Self, Error> {
+        if key.len() != JWT_SECRET_LENGTH {
+            return Err(Error::InvalidSecretLength(key.len()));
+        }
+        let mut bytes = [0; JWT_SECRET_LENGTH];
+        bytes.copy_from_slice(key);
+        Ok(Self(bytes))
+    }
+
+    /// Generate a new random secret.
+    pub fn random() -> Self {
+        Self(rand::thread_rng().gen())
+    }
+
+    /// Parse a hex-encoded secret, with or without a `0x` prefix.
+    pub fn from_hex(hex_str: &str) -> Result<Self, Error> {
+        let hex_str = hex_str.trim();
+        let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+        Self::from_slice(&hex::decode(hex_str).map_err(Error::InvalidSecretHex)?)
+    }
+
+    pub fn hex_string(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Load the secret at `path`.
+///
+/// If no file exists at `path`, a new random secret is generated and written there so that it
+/// can be supplied to the execution engine.
+pub fn load_or_create_secret(path: &Path) -> Result<JwtKey, Error> {
+    if path.exists() {
+        let hex_str = fs::read_to_string(path)
+            .map_err(|e| Error::UnableToReadSecret(format!("{:?}: {:?}", path, e)))?;
+        JwtKey::from_hex(&hex_str)
+    } else {
+        let key = JwtKey::random();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| Error::UnableToWriteSecret(format!("{:?}: {:?}", parent, e)))?;
+        }
+        // The secret is only readable by its owner, like the other secrets in the datadir.
+        filesystem::create_with_600_perms(path, key.hex_string().as_bytes())
+            .map_err(|e| Error::UnableToWriteSecret(format!("{:?}: {:?}", path, e)))?;
+        Ok(key)
+    }
+}
+
+/// The claims contained in each token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Claims {
+    /// Issued-at time, in seconds since the UNIX epoch.
+    pub iat: u64,
+}
+
+/// Generates tokens for requests to a single execution engine.
+pub struct Auth {
+    key: EncodingKey,
+}
+
+impl Auth {
+    pub fn new(secret: JwtKey) -> Self {
+        Self {
+            key: EncodingKey::from_secret(secret.as_bytes()),
+        }
+    }
+
+    /// Generate a token with the current time as its `iat` claim.
+    pub fn generate_token(&self) -> Result<String, Error> {
+        let claims = Claims {
+            iat: get_current_timestamp(),
+        };
+        self.generate_token_with_claims(&claims)
+    }
+
+    pub fn generate_token_with_claims(&self, claims: &Claims) -> Result<String, Error> {
+        encode(&Header::new(Algorithm::HS256), claims, &self.key).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{decode, DecodingKey, Validation};
+
+    fn validate(token: &str, secret: &JwtKey) -> Result<Claims, jsonwebtoken::errors::Error> {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.validate_exp = false;
+        validation.required_spec_claims.remove("exp");
+        decode::<Claims>(
+            token,
+            &DecodingKey::from_secret(secret.as_bytes()),
+            &validation,
+        )
+        .map(|token_data| token_data.claims)
+    }
+
+    #[test]
+    fn token_round_trip() {
+        let secret = JwtKey::random();
+        let auth = Auth::new(secret.clone());
+        let claims = Claims { iat: 1_640_000_000 };
+
+        let token = auth.generate_token_with_claims(&claims).unwrap();
+
+        assert_eq!(validate(&token, &secret).unwrap(), claims);
+        assert!(validate(&token, &JwtKey::random()).is_err());
+    }
+
+    #[test]
+    fn generated_token_is_current() {
+        let secret = JwtKey::random();
+        let token = Auth::new(secret.clone()).generate_token().unwrap();
+
+        let iat = validate(&token, &secret).unwrap().iat;
+        assert!(get_current_timestamp().saturating_sub(iat) <= 1);
+    }
+
+    #[test]
+    fn secret_hex() {
+        let secret = JwtKey::random();
+        assert!(JwtKey::from_hex(&secret.hex_string()).unwrap() == secret);
+        assert!(JwtKey::from_hex(&format!("0x{}\n", secret.hex_string())).unwrap() == secret);
+        assert!(matches!(
+            JwtKey::from_hex("0x1234"),
+            Err(Error::InvalidSecretLength(2))
+        ));
+        assert!(matches!(
+            JwtKey::from_hex("zz"),
+            Err(Error::InvalidSecretHex(_))
+        ));
+    }
+
+    #[test]
+    fn load_or_create() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("jwt").join("secret.hex");
+
+        let created = load_or_create_secret(&path).unwrap();
+        assert!(path.exists());
+        assert!(load_or_create_secret(&path).unwrap() == created);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn created_secret_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret.hex");
+
+        load_or_create_secret(&path).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -1,5 +1,6 @@
 //! Contains an implementation of `EngineAPI` using the JSON-RPC API via HTTP.
 
+use super::auth::Auth;
 use super::*;
 use crate::json_structures::*;
 use async_trait::async_trait;
@@ -27,8 +28,8 @@ pub const ETH_GET_BLOCK_BY_HASH_TIMEOUT: Duration = Duration::from_secs(1);
 pub const ETH_SYNCING: &str = "eth_syncing";
 pub const ETH_SYNCING_TIMEOUT: Duration = Duration::from_millis(250);
 
-pub const ENGINE_EXECUTE_PAYLOAD_V1: &str = "engine_executePayloadV1";
-pub const ENGINE_EXECUTE_PAYLOAD_TIMEOUT: Duration = Duration::from_secs(2);
+pub const ENGINE_NEW_PAYLOAD_V1: &str = "engine_newPayloadV1";
+pub const ENGINE_NEW_PAYLOAD_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub const ENGINE_GET_PAYLOAD_V1: &str = "engine_getPayloadV1";
 pub const ENGINE_GET_PAYLOAD_TIMEOUT: Duration = Duration::from_secs(2);
@@ -36,9 +37,30 @@ pub const ENGINE_GET_PAYLOAD_TIMEOUT: Duration = Duration::from_secs(2);
 pub const ENGINE_FORKCHOICE_UPDATED_V1: &str = "engine_forkchoiceUpdatedV1";
 pub const ENGINE_FORKCHOICE_UPDATED_TIMEOUT: Duration = Duration::from_millis(500);
 
+pub const ENGINE_EXCHANGE_CAPABILITIES: &str = "engine_exchangeCapabilities";
+pub const ENGINE_EXCHANGE_CAPABILITIES_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The JSON-RPC error code returned for an unknown method.
+pub const METHOD_NOT_FOUND_CODE: i64 = -32601;
+
+/// The Engine API methods used by Lighthouse, which are advertised to the execution engine.
+pub const LIGHTHOUSE_CAPABILITIES: &[&str] = &[
+    ENGINE_NEW_PAYLOAD_V1,
+    ENGINE_GET_PAYLOAD_V1,
+    ENGINE_FORKCHOICE_UPDATED_V1,
+];
+
+/// The methods supported by engines which don't implement `engine_exchangeCapabilities`.
+pub const LEGACY_CAPABILITIES: &[&str] = &[
+    ENGINE_NEW_PAYLOAD_V1,
+    ENGINE_GET_PAYLOAD_V1,
+    ENGINE_FORKCHOICE_UPDATED_V1,
+];
+
 pub struct HttpJsonRpc {
     pub client: Client,
     pub url: SensitiveUrl,
+    auth: Option<Auth>,
 }
 
 impl HttpJsonRpc {
@@ -46,6 +68,16 @@ impl HttpJsonRpc {
         Ok(Self {
             client: Client::builder().build()?,
             url,
+            auth: None,
+        })
+    }
+
+    /// Instantiate a client which authenticates each request with a JWT generated by `auth`.
+    pub fn new_with_auth(url: SensitiveUrl, auth: Auth) -> Result<Self, Error> {
+        Ok(Self {
+            client: Client::builder().build()?,
+            url,
+            auth: Some(auth),
         })
     }
 
@@ -62,17 +94,20 @@ impl HttpJsonRpc {
             id: STATIC_ID,
         };
 
-        let body: JsonResponseBody = self
+        let mut request = self
             .client
             .post(self.url.full.clone())
             .timeout(timeout)
             .header(CONTENT_TYPE, "application/json")
-            .json(&body)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
+            .json(&body);
+
+        // Generate a fresh token for each request, since the execution engine rejects tokens
+        // whose `iat` claim is too far from its own clock.
+        if let Some(auth) = &self.auth {
+            request = request.bearer_auth(auth.generate_token()?);
+        }
+
+        let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
 
         match (body.result, body.error) {
             (result, None) => serde_json::from_value(result).map_err(Into::into),
@@ -133,18 +168,14 @@ impl EngineApi for HttpJsonRpc {
             .await
     }
 
-    async fn execute_payload_v1<T: EthSpec>(
+    async fn new_payload_v1<T: EthSpec>(
         &self,
         execution_payload: ExecutionPayload<T>,
     ) -> Result<ExecutePayloadResponse, Error> {
         let params = json!([JsonExecutionPayloadV1::from(execution_payload)]);
 
         let response: JsonExecutePayloadV1Response = self
-            .rpc_request(
-                ENGINE_EXECUTE_PAYLOAD_V1,
-                params,
-                ENGINE_EXECUTE_PAYLOAD_TIMEOUT,
-            )
+            .rpc_request(ENGINE_NEW_PAYLOAD_V1, params, ENGINE_NEW_PAYLOAD_TIMEOUT)
             .await?;
 
         Ok(response.into())
@@ -183,6 +214,26 @@ impl EngineApi for HttpJsonRpc {
 
         Ok(response.into())
     }
+
+    async fn exchange_capabilities(&self) -> Result<EngineCapabilities, Error> {
+        let params = json!([LIGHTHOUSE_CAPABILITIES]);
+
+        let response: Result<Vec<String>, _> = self
+            .rpc_request(
+                ENGINE_EXCHANGE_CAPABILITIES,
+                params,
+                ENGINE_EXCHANGE_CAPABILITIES_TIMEOUT,
+            )
+            .await;
+
+        match response {
+            Ok(methods) => Ok(EngineCapabilities::new(methods)),
+            Err(Error::ServerMessage { code, .. }) if code == METHOD_NOT_FOUND_CODE => {
+                Ok(EngineCapabilities::legacy())
+            }
+            Err(e) => Err(e),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -486,12 +537,68 @@ mod test {
     }
 
     #[tokio::test]
-    async fn execute_payload_v1_request() {
+    async fn exchange_capabilities_request() {
+        Tester::new()
+            .assert_request_equals(
+                |client| async move {
+                    let _ = client.exchange_capabilities().await;
+                },
+                json!({
+                    "id": STATIC_ID,
+                    "jsonrpc": JSONRPC_VERSION,
+                    "method": ENGINE_EXCHANGE_CAPABILITIES,
+                    "params": [[
+                        ENGINE_NEW_PAYLOAD_V1,
+                        ENGINE_GET_PAYLOAD_V1,
+                        ENGINE_FORKCHOICE_UPDATED_V1
+                    ]]
+                }),
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn exchange_capabilities_response() {
+        Tester::new()
+            .with_preloaded_responses(
+                vec![json!({
+                    "id": STATIC_ID,
+                    "jsonrpc": JSONRPC_VERSION,
+                    "result": [ENGINE_NEW_PAYLOAD_V1, ENGINE_FORKCHOICE_UPDATED_V1]
+                })],
+                |client| async move {
+                    let capabilities = client.exchange_capabilities().await.unwrap();
+                    assert!(capabilities.supports(ENGINE_NEW_PAYLOAD_V1));
+                    assert_eq!(capabilities.missing_methods(), vec![ENGINE_GET_PAYLOAD_V1]);
+                },
+            )
+            .await
+            // An engine without `engine_exchangeCapabilities` supports the V1 methods.
+            .with_preloaded_responses(
+                vec![json!({
+                    "id": STATIC_ID,
+                    "jsonrpc": JSONRPC_VERSION,
+                    "error": {
+                        "code": METHOD_NOT_FOUND_CODE,
+                        "message": "the method engine_exchangeCapabilities does not exist"
+                    }
+                })],
+                |client| async move {
+                    let capabilities = client.exchange_capabilities().await.unwrap();
+                    assert_eq!(capabilities, EngineCapabilities::legacy());
+                    assert!(capabilities.missing_methods().is_empty());
+                },
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    async fn new_payload_v1_request() {
         Tester::new()
             .assert_request_equals(
                 |client| async move {
                     let _ = client
-                        .execute_payload_v1::<MainnetEthSpec>(ExecutionPayload {
+                        .new_payload_v1::<MainnetEthSpec>(ExecutionPayload {
                             parent_hash: Hash256::repeat_byte(0),
                             fee_recipient: Address::repeat_byte(1),
                             state_root: Hash256::repeat_byte(1),
@@ -512,7 +619,7 @@ mod test {
                 json!({
                     "id": STATIC_ID,
                     "jsonrpc": JSONRPC_VERSION,
-                    "method": ENGINE_EXECUTE_PAYLOAD_V1,
+                    "method": ENGINE_NEW_PAYLOAD_V1,
                     "params": [{
                         "parentHash": HASH_00,
                         "feeRecipient": ADDRESS_01,
@@ -720,10 +827,10 @@ mod test {
             )
             .await
             .assert_request_equals(
-                // engine_executePayloadV1 REQUEST validation
+                // engine_newPayloadV1 REQUEST validation
                 |client| async move {
                     let _ = client
-                        .execute_payload_v1::<MainnetEthSpec>(ExecutionPayload {
+                        .new_payload_v1::<MainnetEthSpec>(ExecutionPayload {
                             parent_hash: Hash256::from_str("0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a").unwrap(),
                             fee_recipient: Address::from_str("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b").unwrap(),
                             state_root: Hash256::from_str("0xca3149fa9e37db08d1cd49c9061db1002ef1cd58db2210f2115c8c989b2bdf45").unwrap(),
@@ -744,7 +851,7 @@ mod test {
                 json!({
                     "id": STATIC_ID,
                     "jsonrpc": JSONRPC_VERSION,
-                    "method": ENGINE_EXECUTE_PAYLOAD_V1,
+                    "method": ENGINE_NEW_PAYLOAD_V1,
                     "params": [{
                         "parentHash":"0x3b8fb240d288781d4aac94d3fd16809ee413bc99294a085798a589dae51ddd4a",
                         "feeRecipient":"0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
@@ -765,7 +872,7 @@ mod test {
             )
             .await
             .with_preloaded_responses(
-                // engine_executePayloadV1 RESPONSE validation
+                // engine_newPayloadV1 RESPONSE validation
                 vec![json!({
                     "jsonrpc": JSONRPC_VERSION,
                     "id": STATIC_ID,
@@ -776,7 +883,7 @@ mod test {
                 })],
                 |client| async move {
                     let response = client
-                        .execute_payload_v1::<MainnetEthSpec>(ExecutionPayload::default())
+                        .new_payload_v1::<MainnetEthSpec>(ExecutionPayload::default())
                         .await
                         .unwrap();
 
@@ -1,12 +1,18 @@
 //! Provides generic behaviour for multiple execution engines, specifically fallback behaviour.
 
-use crate::engine_api::{EngineApi, Error as EngineApiError, PayloadAttributes, PayloadId};
+use crate::engine_api::{
+    http::{ENGINE_FORKCHOICE_UPDATED_V1, ENGINE_GET_PAYLOAD_V1, ENGINE_NEW_PAYLOAD_V1},
+    EngineApi, EngineCapabilities, Error as EngineApiError, ExecutePayloadResponse,
+    PayloadAttributes, PayloadId,
+};
+use crate::metrics;
 use futures::future::join_all;
 use lru::LruCache;
 use slog::{crit, debug, info, warn, Logger};
 use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::sync::{Mutex, RwLock};
-use types::{Address, Hash256};
+use types::{Address, EthSpec, ExecutionPayload, Hash256};
 
 /// The number of payload IDs that will be stored for each `Engine`.
 ///
@@ -58,6 +64,8 @@ pub struct Engine<T> {
     pub api: T,
     payload_id_cache: Mutex<LruCache<PayloadIdCacheKey, PayloadId>>,
     state: RwLock<EngineState>,
+    /// The methods supported by the engine, if they have been exchanged.
+    capabilities: RwLock<Option<EngineCapabilities>>,
 }
 
 impl<T> Engine<T> {
@@ -68,6 +76,19 @@ impl<T> Engine<T> {
             api,
             payload_id_cache: Mutex::new(LruCache::new(PAYLOAD_ID_LRU_CACHE_SIZE)),
             state: RwLock::new(EngineState::Offline),
+            capabilities: RwLock::new(None),
+        }
+    }
+
+    /// Returns an error if the engine is known not to support `method`.
+    ///
+    /// Calls are permitted until capabilities have been exchanged.
+    async fn check_capability(&self, method: &'static str) -> Result<(), EngineApiError> {
+        match &*self.capabilities.read().await {
+            Some(capabilities) if !capabilities.supports(method) => {
+                Err(EngineApiError::UnsupportedMethod(method))
+            }
+            _ => Ok(()),
         }
     }
 
@@ -92,12 +113,60 @@ impl<T> Engine<T> {
 }
 
 impl<T: EngineApi> Engine<T> {
+    /// Learn which methods the engine supports, warning if any of those used by Lighthouse are
+    /// missing.
+    async fn exchange_capabilities(&self, log: &Logger) {
+        let capabilities = match self.api.exchange_capabilities().await {
+            Ok(capabilities) => capabilities,
+            Err(e) => {
+                debug!(
+                    log,
+                    "Failed to exchange capabilities with execution engine";
+                    "error" => ?e,
+                    "id" => &self.id,
+                );
+                return;
+            }
+        };
+
+        let missing_methods = capabilities.missing_methods();
+        if !missing_methods.is_empty() {
+            warn!(
+                log,
+                "Execution engine is incompatible";
+                "info" => "the engine does not support some methods required by Lighthouse, \
+                           check that its version is compatible with this version of Lighthouse",
+                "missing_methods" => ?missing_methods,
+                "id" => &self.id,
+            );
+        }
+
+        *self.capabilities.write().await = Some(capabilities);
+    }
+
+    pub async fn new_payload<E: EthSpec>(
+        &self,
+        execution_payload: ExecutionPayload<E>,
+    ) -> Result<ExecutePayloadResponse, EngineApiError> {
+        self.check_capability(ENGINE_NEW_PAYLOAD_V1).await?;
+        self.api.new_payload_v1(execution_payload).await
+    }
+
+    pub async fn get_payload<E: EthSpec>(
+        &self,
+        payload_id: PayloadId,
+    ) -> Result<ExecutionPayload<E>, EngineApiError> {
+        self.check_capability(ENGINE_GET_PAYLOAD_V1).await?;
+        self.api.get_payload_v1(payload_id).await
+    }
+
     pub async fn notify_forkchoice_updated(
         &self,
         forkchoice_state: ForkChoiceState,
         payload_attributes: Option<PayloadAttributes>,
         log: &Logger,
     ) -> Result<Option<PayloadId>, EngineApiError> {
+        self.check_capability(ENGINE_FORKCHOICE_UPDATED_V1).await?;
         let response = self
             .api
             .forkchoice_updated_v1(forkchoice_state, payload_attributes)
@@ -126,6 +195,8 @@ impl<T: EngineApi> Engine<T> {
 pub struct Engines<T> {
     pub engines: Vec<Engine<T>>,
     pub latest_forkchoice_state: RwLock<Option<ForkChoiceState>>,
+    /// Set when the most recent upcheck found every engine to be offline.
+    pub offline: AtomicBool,
     pub log: Logger,
 }
 
@@ -158,8 +229,7 @@ impl<T: EngineApi> Engines<T> {
             // For simplicity, payload attributes are never included in this call. It may be
             // reasonable to include them in the future.
             if let Err(e) = engine
-                .api
-                .forkchoice_updated_v1(forkchoice_state, None)
+                .notify_forkchoice_updated(forkchoice_state, None, &self.log)
                 .await
             {
                 debug!(
@@ -178,6 +248,25 @@ impl<T: EngineApi> Engines<T> {
         }
     }
 
+    /// Returns `true` if no engine was reachable at the most recent upcheck.
+    ///
+    /// Engines are presumed to be online until they are first upchecked.
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    /// Logging for the upchecks which are triggered by failed requests.
+    ///
+    /// Whilst all engines are offline every request fails, so the transition is logged once by
+    /// `Self::upcheck` rather than for each request.
+    fn retry_logging(&self) -> Logging {
+        if self.is_offline() {
+            Logging::Disabled
+        } else {
+            Logging::Enabled
+        }
+    }
+
     /// Returns `true` if there is at least one engine with a "synced" status.
     pub async fn any_synced(&self) -> bool {
         for engine in &self.engines {
@@ -192,10 +281,28 @@ impl<T: EngineApi> Engines<T> {
     ///
     /// This can be used to try and recover any offline nodes.
     pub async fn upcheck_not_synced(&self, logging: Logging) {
+        self.upcheck(logging, false).await
+    }
+
+    /// Run the `EngineApi::upcheck` function on all nodes, including those which are synced.
+    ///
+    /// This detects synced nodes which have gone offline or started syncing (e.g., after a
+    /// restart) so that requests are sent to a fallback without first waiting for them to fail.
+    pub async fn upcheck_all(&self, logging: Logging) {
+        self.upcheck(logging, true).await
+    }
+
+    async fn upcheck(&self, logging: Logging, include_synced: bool) {
         let upcheck_futures = self.engines.iter().map(|engine| async move {
             let mut state_lock = engine.state.write().await;
-            if *state_lock != EngineState::Synced {
+            let was_synced = *state_lock == EngineState::Synced;
+            let was_offline = *state_lock == EngineState::Offline;
+            if include_synced || !was_synced {
+                // Always log when a synced node is lost, since requests will fall back to
+                // another node until it recovers.
+                let log_change = logging.is_enabled() || was_synced;
                 match engine.api.upcheck().await {
+                    Ok(()) if was_synced => (),
                     Ok(()) => {
                         if logging.is_enabled() {
                             info!(
@@ -205,13 +312,18 @@ impl<T: EngineApi> Engines<T> {
                             );
                         }
 
+                        // The engine may have been upgraded whilst it was offline.
+                        if was_offline {
+                            engine.exchange_capabilities(&self.log).await;
+                        }
+
                         // Send the node our latest forkchoice_state.
                         self.send_latest_forkchoice_state(engine).await;
 
                         *state_lock = EngineState::Synced
                     }
                     Err(EngineApiError::IsSyncing) => {
-                        if logging.is_enabled() {
+                        if log_change {
                             warn!(
                                 self.log,
                                 "Execution engine syncing";
@@ -219,13 +331,17 @@ impl<T: EngineApi> Engines<T> {
                             )
                         }
 
+                        if was_offline {
+                            engine.exchange_capabilities(&self.log).await;
+                        }
+
                         // Send the node our latest forkchoice_state, it may assist with syncing.
                         self.send_latest_forkchoice_state(engine).await;
 
                         *state_lock = EngineState::Syncing
                     }
                     Err(e) => {
-                        if logging.is_enabled() {
+                        if log_change {
                             warn!(
                                 self.log,
                                 "Execution engine offline";
@@ -233,19 +349,43 @@ impl<T: EngineApi> Engines<T> {
                                 "id" => &engine.id
                             )
                         }
+
+                        *state_lock = EngineState::Offline
                     }
                 }
             }
             *state_lock
         });
 
-        let num_synced = join_all(upcheck_futures)
-            .await
-            .into_iter()
-            .filter(|state: &EngineState| *state == EngineState::Synced)
-            .count();
+        let states = join_all(upcheck_futures).await;
+        let count = |state| states.iter().filter(|s| **s == state).count() as i64;
+        let num_synced = count(EngineState::Synced);
+        let num_offline = count(EngineState::Offline);
+        metrics::set_gauge(&metrics::EXECUTION_ENGINES_SYNCED, num_synced);
+        metrics::set_gauge(
+            &metrics::EXECUTION_ENGINES_SYNCING,
+            count(EngineState::Syncing),
+        );
+        metrics::set_gauge(&metrics::EXECUTION_ENGINES_OFFLINE, num_offline);
+
+        let offline = num_offline == states.len() as i64;
+        let was_offline = self.offline.swap(offline, Ordering::Relaxed);
+        if offline && !was_offline {
+            crit!(
+                self.log,
+                "Execution layer offline";
+                "info" => "block proposals and payload verification are paused until an \
+                           execution engine is reachable",
+            );
+        } else if !offline && was_offline {
+            info!(
+                self.log,
+                "Execution layer back online";
+                "synced_engines" => num_synced,
+            );
+        }
 
-        if num_synced == 0 && logging.is_enabled() {
+        if num_synced == 0 && !offline && logging.is_enabled() {
             crit!(
                 self.log,
                 "No synced execution engines";
@@ -267,7 +407,7 @@ impl<T: EngineApi> Engines<T> {
             Ok(result) => Ok(result),
             Err(mut first_errors) => {
                 // Try to recover some nodes.
-                self.upcheck_not_synced(Logging::Enabled).await;
+                self.upcheck_not_synced(self.retry_logging()).await;
                 // Retry the call on all nodes.
                 match self.first_success_without_retry(func).await {
                     Ok(result) => Ok(result),
@@ -296,7 +436,18 @@ impl<T: EngineApi> Engines<T> {
             let engine_synced = *engine.state.read().await == EngineState::Synced;
             if engine_synced {
                 match func(engine).await {
-                    Ok(result) => return Ok(result),
+                    Ok(result) => {
+                        if !errors.is_empty() {
+                            metrics::inc_counter(&metrics::EXECUTION_ENGINE_FAILOVERS);
+                            debug!(
+                                self.log,
+                                "Execution engine call served by fallback";
+                                "id" => &engine.id,
+                                "failed_engines" => errors.len(),
+                            );
+                        }
+                        return Ok(result);
+                    }
                     Err(error) => {
                         debug!(
                             self.log,
@@ -304,7 +455,12 @@ impl<T: EngineApi> Engines<T> {
                             "error" => ?error,
                             "id" => &engine.id
                         );
-                        *engine.state.write().await = EngineState::Offline;
+                        metrics::inc_counter_vec(&metrics::EXECUTION_ENGINE_ERRORS, &[&engine.id]);
+                        // An unsupported method was never sent, so says nothing about the
+                        // engine's health.
+                        if !matches!(error, EngineApiError::UnsupportedMethod(_)) {
+                            *engine.state.write().await = EngineState::Offline;
+                        }
                         errors.push(EngineError::Api {
                             id: engine.id.clone(),
                             error,
@@ -343,7 +499,7 @@ impl<T: EngineApi> Engines<T> {
         }
 
         if any_offline {
-            self.upcheck_not_synced(Logging::Enabled).await;
+            self.upcheck_not_synced(self.retry_logging()).await;
             self.broadcast_without_retry(func).await
         } else {
             first_results
@@ -370,6 +526,7 @@ impl<T: EngineApi> Engines<T> {
                         "error" => ?error,
                         "id" => &engine.id
                     );
+                    metrics::inc_counter_vec(&metrics::EXECUTION_ENGINE_ERRORS, &[&engine.id]);
                     EngineError::Api {
                         id: engine.id.clone(),
                         error,
@@ -4,13 +4,20 @@
 //! This crate only provides useful functionality for "The Merge", it does not provide any of the
 //! deposit-contract functionality that the `beacon_node/eth1` crate already provides.
 
+#[macro_use]
+extern crate lazy_static;
+
+use builder_client::BuilderHttpClient;
+use engine_api::auth::{load_or_create_secret, Auth};
 use engine_api::{Error as ApiError, *};
 use engines::{Engine, EngineError, Engines, ForkChoiceState, Logging};
 use lru::LruCache;
 use sensitive_url::SensitiveUrl;
 use slog::{crit, debug, error, info, Logger};
 use slot_clock::SlotClock;
+use std::collections::HashMap;
 use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use task_executor::TaskExecutor;
@@ -18,26 +25,47 @@ use tokio::{
     sync::{Mutex, MutexGuard},
     time::{sleep, sleep_until, Instant},
 };
-use types::ChainSpec;
+use types::{
+    ChainSpec, Epoch, ExecutionPayloadHeader, ProposerPreparationData, PublicKeyBytes,
+    SignedBlindedBeaconBlock, SignedValidatorRegistrationData, Slot,
+};
 
-pub use engine_api::{http::HttpJsonRpc, ExecutePayloadResponseStatus};
+pub use engine_api::{http::HttpJsonRpc, ExecutePayloadResponseStatus, PayloadAttributes};
 
+pub mod builder_client;
 mod engine_api;
 mod engines;
+mod metrics;
 pub mod test_utils;
 
 /// Each time the `ExecutionLayer` retrieves a block from an execution node, it stores that block
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: usize = 128;
 
+/// The number of payload verification results to remember, so that the payloads of blocks which
+/// are processed more than once (e.g., after a re-org) are not sent to the engines again.
+const PAYLOAD_STATUSES_LRU_CACHE_SIZE: usize = 128;
+
+/// The number of epochs for which the preparation data supplied by a validator client is kept,
+/// after the epoch in which it was last supplied.
+///
+/// Validator clients re-send their preparation data each epoch, so this allows for a missed update.
+const PROPOSER_PREPARATION_LIFESPAN_EPOCHS: u64 = 2;
+
 #[derive(Debug)]
 pub enum Error {
     NoEngines,
+    JwtSecretsCountMismatch { endpoints: usize, secrets: usize },
     ApiError(ApiError),
     EngineErrors(Vec<EngineError>),
     NotSynced,
     ShuttingDown,
     FeeRecipientUnspecified,
+    NoBuilder,
+    Builder(builder_client::Error),
+    NoBuilderBid,
+    InvalidBuilderBid(String),
+    InvalidBuilderPayload,
 }
 
 impl From<ApiError> for Error {
@@ -46,10 +74,22 @@ impl From<ApiError> for Error {
     }
 }
 
+/// The preparation data for a proposer, along with the epoch in which it was last supplied.
+struct ProposerPreparationDataEntry {
+    update_epoch: Epoch,
+    fee_recipient: Address,
+}
+
 struct Inner {
     engines: Engines<HttpJsonRpc>,
+    builder: Option<BuilderHttpClient>,
     suggested_fee_recipient: Option<Address>,
+    proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
     execution_blocks: Mutex<LruCache<Hash256, ExecutionBlock>>,
+    /// The definitive (i.e., valid or invalid) results of `engine_newPayload`, by block hash.
+    payload_statuses: Mutex<LruCache<Hash256, ExecutePayloadResponseStatus>>,
+    /// The most recent forkchoice state which every engine accepted.
+    last_forkchoice_update: Mutex<Option<ForkChoiceState>>,
     executor: TaskExecutor,
     log: Logger,
 }
@@ -70,8 +110,17 @@ pub struct ExecutionLayer {
 
 impl ExecutionLayer {
     /// Instantiate `Self` with `urls.len()` engines, all using the JSON-RPC via HTTP.
+    ///
+    /// If `jwt_secrets` is non-empty it must contain one path per URL, and requests to each engine
+    /// are authenticated using the secret at the corresponding path. A random secret is created
+    /// at any path which doesn't exist.
+    ///
+    /// If `builder_url` is supplied, blinded blocks may be produced using payloads from the
+    /// external builder at that URL.
     pub fn from_urls(
         urls: Vec<SensitiveUrl>,
+        jwt_secrets: Vec<PathBuf>,
+        builder_url: Option<SensitiveUrl>,
         suggested_fee_recipient: Option<Address>,
         executor: TaskExecutor,
         log: Logger,
@@ -80,23 +129,53 @@ impl ExecutionLayer {
             return Err(Error::NoEngines);
         }
 
+        if !jwt_secrets.is_empty() && jwt_secrets.len() != urls.len() {
+            return Err(Error::JwtSecretsCountMismatch {
+                endpoints: urls.len(),
+                secrets: jwt_secrets.len(),
+            });
+        }
+
+        let mut jwt_secrets = jwt_secrets.into_iter();
         let engines = urls
             .into_iter()
             .map(|url| {
                 let id = url.to_string();
-                let api = HttpJsonRpc::new(url)?;
+                let api = match jwt_secrets.next() {
+                    Some(path) => {
+                        let auth = Auth::new(load_or_create_secret(&path)?);
+                        info!(
+                            log,
+                            "Using JWT authentication for execution engine";
+                            "endpoint" => &id,
+                            "jwt_secret" => ?path,
+                        );
+                        HttpJsonRpc::new_with_auth(url, auth)?
+                    }
+                    None => HttpJsonRpc::new(url)?,
+                };
                 Ok(Engine::new(id, api))
             })
             .collect::<Result<_, ApiError>>()?;
 
+        let builder = builder_url
+            .map(BuilderHttpClient::new)
+            .transpose()
+            .map_err(Error::Builder)?;
+
         let inner = Inner {
             engines: Engines {
                 engines,
                 latest_forkchoice_state: <_>::default(),
+                offline: <_>::default(),
                 log: log.clone(),
             },
+            builder,
             suggested_fee_recipient,
+            proposer_preparation_data: Mutex::new(HashMap::new()),
             execution_blocks: Mutex::new(LruCache::new(EXECUTION_BLOCKS_LRU_CACHE_SIZE)),
+            payload_statuses: Mutex::new(LruCache::new(PAYLOAD_STATUSES_LRU_CACHE_SIZE)),
+            last_forkchoice_update: Mutex::new(None),
             executor,
             log,
         };
@@ -116,12 +195,88 @@ impl ExecutionLayer {
         &self.inner.executor
     }
 
-    fn suggested_fee_recipient(&self) -> Result<Address, Error> {
+    fn builder(&self) -> Result<&BuilderHttpClient, Error> {
+        self.inner.builder.as_ref().ok_or(Error::NoBuilder)
+    }
+
+    /// Returns `true` if an external builder has been configured.
+    pub fn has_builder(&self) -> bool {
+        self.inner.builder.is_some()
+    }
+
+    /// Returns the fee recipient supplied by the validator client for `proposer_index`, falling
+    /// back to the fee recipient configured on the beacon node.
+    async fn suggested_fee_recipient(&self, proposer_index: u64) -> Result<Address, Error> {
+        if let Some(entry) = self
+            .inner
+            .proposer_preparation_data
+            .lock()
+            .await
+            .get(&proposer_index)
+        {
+            return Ok(entry.fee_recipient);
+        }
+
         self.inner
             .suggested_fee_recipient
             .ok_or(Error::FeeRecipientUnspecified)
     }
 
+    /// Stores the fee recipients supplied by a validator client in `update_epoch`, to be used when
+    /// producing execution payloads for those validators.
+    pub async fn update_proposer_preparation(
+        &self,
+        update_epoch: Epoch,
+        preparation_data: &[ProposerPreparationData],
+    ) {
+        let mut proposer_preparation_data = self.inner.proposer_preparation_data.lock().await;
+        for preparation in preparation_data {
+            proposer_preparation_data.insert(
+                preparation.validator_index,
+                ProposerPreparationDataEntry {
+                    update_epoch,
+                    fee_recipient: preparation.fee_recipient,
+                },
+            );
+        }
+    }
+
+    /// Removes the preparation data which hasn't been supplied again for
+    /// `PROPOSER_PREPARATION_LIFESPAN_EPOCHS` epochs, e.g. because the validator client has been
+    /// shut down or moved to another beacon node.
+    pub async fn clean_proposer_caches(&self, current_epoch: Epoch) {
+        self.inner
+            .proposer_preparation_data
+            .lock()
+            .await
+            .retain(|_, entry| {
+                entry.update_epoch + PROPOSER_PREPARATION_LIFESPAN_EPOCHS >= current_epoch
+            });
+    }
+
+    /// Returns the payload attributes for a proposal by `proposer_index`, if a validator client
+    /// has supplied preparation data for it. Otherwise, the proposer is presumed not to be
+    /// connected to this node and `None` is returned.
+    pub async fn payload_attributes(
+        &self,
+        timestamp: u64,
+        random: Hash256,
+        proposer_index: u64,
+    ) -> Option<PayloadAttributes> {
+        let fee_recipient = self
+            .inner
+            .proposer_preparation_data
+            .lock()
+            .await
+            .get(&proposer_index)?
+            .fee_recipient;
+        Some(PayloadAttributes {
+            timestamp,
+            random,
+            suggested_fee_recipient: fee_recipient,
+        })
+    }
+
     /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
     async fn execution_blocks(&self) -> MutexGuard<'_, LruCache<Hash256, ExecutionBlock>> {
         self.inner.execution_blocks.lock().await
@@ -200,8 +355,10 @@ impl ExecutionLayer {
                     let second_execution = first_execution + interval;
                     let third_execution = second_execution + interval;
 
+                    // Check synced engines once per slot, so that an engine which restarts is
+                    // noticed before the next payload is requested from it.
                     sleep_until(now + first_execution).await;
-                    el.engines().upcheck_not_synced(Logging::Disabled).await;
+                    el.engines().upcheck_all(Logging::Disabled).await;
 
                     sleep_until(now + second_execution).await;
                     el.engines().upcheck_not_synced(Logging::Disabled).await;
@@ -234,6 +391,13 @@ impl ExecutionLayer {
         self.engines().upcheck_not_synced(Logging::Disabled).await;
     }
 
+    /// Returns `true` if none of the execution engines are reachable.
+    ///
+    /// Whilst offline, execution payloads can be neither produced nor verified.
+    pub fn is_offline(&self) -> bool {
+        self.engines().is_offline()
+    }
+
     /// Returns `true` if there is at least one synced and reachable engine.
     pub async fn is_synced(&self) -> bool {
         self.engines().any_synced().await
@@ -254,8 +418,9 @@ impl ExecutionLayer {
         timestamp: u64,
         random: Hash256,
         finalized_block_hash: Hash256,
+        proposer_index: u64,
     ) -> Result<ExecutionPayload<T>, Error> {
-        let suggested_fee_recipient = self.suggested_fee_recipient()?;
+        let suggested_fee_recipient = self.suggested_fee_recipient(proposer_index).await?;
         debug!(
             self.log(),
             "Issuing engine_getPayload";
@@ -300,13 +465,111 @@ impl ExecutionLayer {
                         .ok_or(ApiError::PayloadIdUnavailable)?
                 };
 
-                engine.api.get_payload_v1(payload_id).await
+                engine.get_payload(payload_id).await
             })
             .await
             .map_err(Error::EngineErrors)
     }
 
-    /// Maps to the `engine_executePayload` JSON-RPC call.
+    /// Registers validators with the external builder, so that it may build payloads which pay
+    /// their fee recipients.
+    pub async fn register_validators(
+        &self,
+        registrations: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        self.builder()?
+            .post_builder_validators(registrations)
+            .await
+            .map_err(Error::Builder)
+    }
+
+    /// Requests the header of a payload from the external builder, for inclusion in a blinded
+    /// block.
+    ///
+    /// Returns an error if there is no builder, if the builder does not offer a bid or if the bid
+    /// is not valid for the given parameters.
+    pub async fn get_payload_header<T: EthSpec>(
+        &self,
+        parent_hash: Hash256,
+        timestamp: u64,
+        random: Hash256,
+        slot: Slot,
+        pubkey: PublicKeyBytes,
+        spec: &ChainSpec,
+    ) -> Result<ExecutionPayloadHeader<T>, Error> {
+        debug!(
+            self.log(),
+            "Requesting payload header from builder";
+            "slot" => slot,
+            "parent_hash" => ?parent_hash,
+        );
+
+        let bid = self
+            .builder()?
+            .get_builder_header::<T>(slot, parent_hash, &pubkey)
+            .await
+            .map_err(Error::Builder)?
+            .ok_or(Error::NoBuilderBid)?;
+
+        if !bid.verify_signature(spec) {
+            return Err(Error::InvalidBuilderBid("invalid signature".to_string()));
+        }
+
+        let value = bid.message.value;
+        let header = bid.message.header;
+        if header.parent_hash != parent_hash {
+            return Err(Error::InvalidBuilderBid(format!(
+                "parent hash {:?} does not match {:?}",
+                header.parent_hash, parent_hash
+            )));
+        }
+        if header.timestamp != timestamp {
+            return Err(Error::InvalidBuilderBid(format!(
+                "timestamp {} does not match {}",
+                header.timestamp, timestamp
+            )));
+        }
+        if header.random != random {
+            return Err(Error::InvalidBuilderBid(format!(
+                "random {:?} does not match {:?}",
+                header.random, random
+            )));
+        }
+
+        info!(
+            self.log(),
+            "Received payload header from builder";
+            "value" => %value,
+            "block_hash" => ?header.block_hash,
+            "fee_recipient" => ?header.fee_recipient,
+            "slot" => slot,
+        );
+
+        Ok(header)
+    }
+
+    /// Submits a signed blinded block to the external builder, which reveals the full execution
+    /// payload in return.
+    ///
+    /// Returns an error if the payload does not match the header in the blinded block.
+    pub async fn propose_blinded_beacon_block<T: EthSpec>(
+        &self,
+        block: &SignedBlindedBeaconBlock<T>,
+    ) -> Result<ExecutionPayload<T>, Error> {
+        let payload = self
+            .builder()?
+            .post_builder_blinded_blocks(block)
+            .await
+            .map_err(Error::Builder)?;
+
+        if ExecutionPayloadHeader::from(&payload) != block.message.body.execution_payload_header {
+            return Err(Error::InvalidBuilderPayload);
+        }
+
+        Ok(payload)
+    }
+
+    /// Maps to the `engine_newPayload` JSON-RPC call.
     ///
     /// ## Fallback Behaviour
     ///
@@ -318,13 +581,47 @@ impl ExecutionLayer {
     /// - Invalid, if any nodes return invalid.
     /// - Syncing, if any nodes return syncing.
     /// - An error, if all nodes return an error.
-    pub async fn execute_payload<T: EthSpec>(
+    ///
+    /// ## Caching
+    ///
+    /// Valid and invalid results are cached by block hash, and returned without contacting the
+    /// nodes if the same payload is verified again. Syncing results are not cached, since the
+    /// nodes may be able to verify the payload later.
+    pub async fn notify_new_payload<T: EthSpec>(
+        &self,
+        execution_payload: &ExecutionPayload<T>,
+    ) -> Result<(ExecutePayloadResponseStatus, Option<Hash256>), Error> {
+        let block_hash = execution_payload.block_hash;
+        if let Some(status) = self.inner.payload_statuses.lock().await.get(&block_hash) {
+            metrics::inc_counter_vec(&metrics::EXECUTION_LAYER_CACHE_HITS, &["new_payload"]);
+            return Ok(match status {
+                ExecutePayloadResponseStatus::Valid => (*status, Some(block_hash)),
+                _ => (*status, None),
+            });
+        }
+
+        let result = self.notify_new_payload_uncached(execution_payload).await;
+
+        if let Ok((status, _)) = result {
+            if status != ExecutePayloadResponseStatus::Syncing {
+                self.inner
+                    .payload_statuses
+                    .lock()
+                    .await
+                    .put(block_hash, status);
+            }
+        }
+
+        result
+    }
+
+    async fn notify_new_payload_uncached<T: EthSpec>(
         &self,
         execution_payload: &ExecutionPayload<T>,
     ) -> Result<(ExecutePayloadResponseStatus, Option<Hash256>), Error> {
         debug!(
             self.log(),
-            "Issuing engine_executePayload";
+            "Issuing engine_newPayload";
             "parent_hash" => ?execution_payload.parent_hash,
             "block_hash" => ?execution_payload.block_hash,
             "block_number" => execution_payload.block_number,
@@ -332,26 +629,28 @@ impl ExecutionLayer {
 
         let broadcast_results = self
             .engines()
-            .broadcast(|engine| engine.api.execute_payload_v1(execution_payload.clone()))
+            .broadcast(|engine| engine.new_payload(execution_payload.clone()))
             .await;
 
         let mut errors = vec![];
-        let mut valid = 0;
-        let mut invalid = 0;
+        let mut valid = vec![];
+        let mut invalid = vec![];
         let mut syncing = 0;
         let mut invalid_latest_valid_hash = vec![];
-        for result in broadcast_results {
+        // The results of a broadcast are in the same order as the engines.
+        let ids = self.engines().engines.iter().map(|engine| &engine.id);
+        for (id, result) in ids.zip(broadcast_results) {
             match result.map(|response| (response.latest_valid_hash, response.status)) {
                 Ok((Some(latest_hash), ExecutePayloadResponseStatus::Valid)) => {
                     if latest_hash == execution_payload.block_hash {
-                        valid += 1;
+                        valid.push(id);
                     } else {
-                        invalid += 1;
+                        invalid.push(id);
                         errors.push(EngineError::Api {
-                            id: "unknown".to_string(),
+                            id: id.clone(),
                             error: engine_api::Error::BadResponse(
                                 format!(
-                                    "execute_payload: response.status = Valid but invalid latest_valid_hash. Expected({:?}) Found({:?})",
+                                    "notify_new_payload: response.status = Valid but invalid latest_valid_hash. Expected({:?}) Found({:?})",
                                     execution_payload.block_hash,
                                     latest_hash,
                                 )
@@ -361,14 +660,14 @@ impl ExecutionLayer {
                     }
                 }
                 Ok((Some(latest_hash), ExecutePayloadResponseStatus::Invalid)) => {
-                    invalid += 1;
+                    invalid.push(id);
                     invalid_latest_valid_hash.push(latest_hash);
                 }
                 Ok((_, ExecutePayloadResponseStatus::Syncing)) => syncing += 1,
                 Ok((None, status)) => errors.push(EngineError::Api {
-                    id: "unknown".to_string(),
+                    id: id.clone(),
                     error: engine_api::Error::BadResponse(format!(
-                        "execute_payload: status {:?} returned with null latest_valid_hash",
+                        "notify_new_payload: status {:?} returned with null latest_valid_hash",
                         status
                     )),
                 }),
@@ -376,20 +675,24 @@ impl ExecutionLayer {
             }
         }
 
-        if valid > 0 && invalid > 0 {
+        if !valid.is_empty() && !invalid.is_empty() {
+            metrics::inc_counter(&metrics::EXECUTION_ENGINE_PAYLOAD_DISAGREEMENTS);
             crit!(
                 self.log(),
                 "Consensus failure between execution nodes";
-                "method" => "execute_payload"
+                "method" => "notify_new_payload",
+                "block_hash" => ?execution_payload.block_hash,
+                "valid_engines" => ?valid,
+                "invalid_engines" => ?invalid,
             );
         }
 
-        if valid > 0 {
+        if !valid.is_empty() {
             Ok((
                 ExecutePayloadResponseStatus::Valid,
                 Some(execution_payload.block_hash),
             ))
-        } else if invalid > 0 {
+        } else if !invalid.is_empty() {
             Ok((ExecutePayloadResponseStatus::Invalid, None))
         } else if syncing > 0 {
             Ok((ExecutePayloadResponseStatus::Syncing, None))
@@ -408,19 +711,17 @@ impl ExecutionLayer {
     ///
     /// - Ok, if any node returns successfully.
     /// - An error, if all nodes return an error.
+    ///
+    /// ## Caching
+    ///
+    /// An update without payload attributes is not sent if every node has already accepted the
+    /// same forkchoice state, as happens when fork choice runs again without changing the head.
     pub async fn notify_forkchoice_updated(
         &self,
         head_block_hash: Hash256,
         finalized_block_hash: Hash256,
         payload_attributes: Option<PayloadAttributes>,
     ) -> Result<(), Error> {
-        debug!(
-            self.log(),
-            "Issuing engine_forkchoiceUpdated";
-            "finalized_block_hash" => ?finalized_block_hash,
-            "head_block_hash" => ?head_block_hash,
-        );
-
         // see https://hackmd.io/@n0ble/kintsugi-spec#Engine-API
         // for now, we must set safe_block_hash = head_block_hash
         let forkchoice_state = ForkChoiceState {
@@ -429,6 +730,22 @@ impl ExecutionLayer {
             finalized_block_hash,
         };
 
+        let mut last_forkchoice_update = self.inner.last_forkchoice_update.lock().await;
+        if payload_attributes.is_none() && *last_forkchoice_update == Some(forkchoice_state) {
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_CACHE_HITS,
+                &["forkchoice_updated"],
+            );
+            return Ok(());
+        }
+
+        debug!(
+            self.log(),
+            "Issuing engine_forkchoiceUpdated";
+            "finalized_block_hash" => ?finalized_block_hash,
+            "head_block_hash" => ?head_block_hash,
+        );
+
         self.engines()
             .set_latest_forkchoice_state(forkchoice_state)
             .await;
@@ -442,6 +759,14 @@ impl ExecutionLayer {
             })
             .await;
 
+        // Any node which missed the update must receive it next time.
+        *last_forkchoice_update = if broadcast_results.iter().all(Result::is_ok) {
+            Some(forkchoice_state)
+        } else {
+            None
+        };
+        drop(last_forkchoice_update);
+
         if broadcast_results.iter().any(Result::is_ok) {
             Ok(())
         } else {
@@ -673,6 +998,7 @@ impl ExecutionLayer {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::engine_api::http::{ENGINE_FORKCHOICE_UPDATED_V1, JSONRPC_VERSION};
     use crate::test_utils::MockExecutionLayer as GenericMockExecutionLayer;
     use types::MainnetEthSpec;
 
@@ -690,6 +1016,174 @@ mod test {
             .await;
     }
 
+    #[tokio::test]
+    async fn produce_blocks_with_offline_primary_engine() {
+        MockExecutionLayer::default_params()
+            .with_offline_primary_engine()
+            .move_to_terminal_block()
+            .produce_valid_execution_payload_on_head()
+            .await
+            .produce_valid_execution_payload_on_head()
+            .await;
+    }
+
+    #[tokio::test]
+    async fn offline_engines() {
+        let mock = MockExecutionLayer::default_params().move_to_terminal_block();
+        // Engines are presumed to be online until they are upchecked.
+        assert!(!mock.el.is_offline());
+        mock.el.watchdog_task().await;
+        assert!(!mock.el.is_offline());
+
+        let mock = mock.with_offline_engine();
+        mock.el.watchdog_task().await;
+        assert!(mock.el.is_offline());
+        assert!(mock
+            .el
+            .get_terminal_pow_block_hash(&mock.spec)
+            .await
+            .is_err());
+        assert!(mock.el.is_offline());
+    }
+
+    #[tokio::test]
+    async fn caches_definitive_payload_statuses() {
+        let mock = MockExecutionLayer::default_params();
+        let payload = ExecutionPayload::<MainnetEthSpec> {
+            block_hash: Hash256::repeat_byte(1),
+            ..<_>::default()
+        };
+        let notify_new_payload = || mock.el.notify_new_payload(&payload);
+
+        // A syncing result is not remembered, so the payload is verified again.
+        mock.server.all_payloads_syncing();
+        assert_eq!(
+            notify_new_payload().await.unwrap(),
+            (ExecutePayloadResponseStatus::Syncing, None)
+        );
+        mock.server.all_payloads_valid();
+        assert_eq!(
+            notify_new_payload().await.unwrap(),
+            (
+                ExecutePayloadResponseStatus::Valid,
+                Some(payload.block_hash)
+            )
+        );
+
+        // A valid result is remembered, without asking the engine again.
+        mock.server.all_payloads_syncing();
+        assert_eq!(
+            notify_new_payload().await.unwrap(),
+            (
+                ExecutePayloadResponseStatus::Valid,
+                Some(payload.block_hash)
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_repeated_forkchoice_updates() {
+        let mock = MockExecutionLayer::default_params().move_to_terminal_block();
+        // Bring the engine online first, so that the upcheck does not send any updates.
+        mock.el.watchdog_task().await;
+        let head = mock
+            .server
+            .execution_block_generator()
+            .latest_block()
+            .unwrap()
+            .block_hash();
+        let requests = || mock.server.request_count(ENGINE_FORKCHOICE_UPDATED_V1);
+
+        mock.el
+            .notify_forkchoice_updated(head, Hash256::zero(), None)
+            .await
+            .unwrap();
+        assert_eq!(requests(), 1);
+
+        // The same forkchoice state is not sent again.
+        mock.el
+            .notify_forkchoice_updated(head, Hash256::zero(), None)
+            .await
+            .unwrap();
+        assert_eq!(requests(), 1);
+
+        // A forkchoice state which the engine failed to accept is sent again.
+        mock.server.push_preloaded_response(serde_json::json!({
+            "id": 1,
+            "jsonrpc": JSONRPC_VERSION,
+            "error": { "code": -1234, "message": "forkchoice update failed" }
+        }));
+        assert!(mock
+            .el
+            .notify_forkchoice_updated(head, head, None)
+            .await
+            .is_err());
+        assert_eq!(requests(), 2);
+        mock.el
+            .notify_forkchoice_updated(head, head, None)
+            .await
+            .unwrap();
+        assert_eq!(requests(), 3);
+        mock.el
+            .notify_forkchoice_updated(head, head, None)
+            .await
+            .unwrap();
+        assert_eq!(requests(), 3);
+    }
+
+    #[tokio::test]
+    async fn resends_forkchoice_updates_missed_by_an_engine() {
+        let mock = MockExecutionLayer::default_params()
+            .with_offline_primary_engine()
+            .move_to_terminal_block();
+        mock.el.watchdog_task().await;
+        let head = mock
+            .server
+            .execution_block_generator()
+            .latest_block()
+            .unwrap()
+            .block_hash();
+        let requests = || mock.server.request_count(ENGINE_FORKCHOICE_UPDATED_V1);
+
+        // The offline engine fails to receive the update, so it is sent again every time.
+        for expected_requests in 1..=2 {
+            mock.el
+                .notify_forkchoice_updated(head, Hash256::zero(), None)
+                .await
+                .unwrap();
+            assert_eq!(requests(), expected_requests);
+        }
+    }
+
+    #[tokio::test]
+    async fn proposer_preparation_expires() {
+        let mock = MockExecutionLayer::default_params();
+        let el = &mock.el;
+        let fee_recipient = Address::repeat_byte(1);
+        let preparation = ProposerPreparationData {
+            validator_index: 3,
+            fee_recipient,
+        };
+        let attributes = || el.payload_attributes(1, Hash256::zero(), 3);
+
+        assert!(attributes().await.is_none());
+
+        el.update_proposer_preparation(Epoch::new(1), &[preparation])
+            .await;
+        assert_eq!(
+            attributes().await.map(|pa| pa.suggested_fee_recipient),
+            Some(fee_recipient)
+        );
+
+        el.clean_proposer_caches(Epoch::new(1 + PROPOSER_PREPARATION_LIFESPAN_EPOCHS))
+            .await;
+        assert!(attributes().await.is_some());
+
+        el.clean_proposer_caches(Epoch::new(2 + PROPOSER_PREPARATION_LIFESPAN_EPOCHS))
+            .await;
+        assert!(attributes().await.is_none());
+    }
+
     #[tokio::test]
     async fn finds_valid_terminal_block_hash() {
         MockExecutionLayer::default_params()
@@ -0,0 +1,45 @@
+pub use lighthouse_metrics::*;
+
+lazy_static! {
+    /*
+     * Engine states
+     */
+    pub static ref EXECUTION_ENGINES_SYNCED: Result<IntGauge> = try_create_int_gauge(
+        "execution_layer_engines_synced",
+        "Number of execution engines which are online and synced"
+    );
+    pub static ref EXECUTION_ENGINES_SYNCING: Result<IntGauge> = try_create_int_gauge(
+        "execution_layer_engines_syncing",
+        "Number of execution engines which are online but syncing"
+    );
+    pub static ref EXECUTION_ENGINES_OFFLINE: Result<IntGauge> = try_create_int_gauge(
+        "execution_layer_engines_offline",
+        "Number of execution engines which are offline"
+    );
+
+    /*
+     * Engine errors and failover
+     */
+    pub static ref EXECUTION_ENGINE_ERRORS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_engine_errors_total",
+        "The number of failed requests to each execution engine",
+        &["endpoint"]
+    );
+    pub static ref EXECUTION_ENGINE_FAILOVERS: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_engine_failovers_total",
+        "The number of requests which were served by a fallback execution engine"
+    );
+    pub static ref EXECUTION_ENGINE_PAYLOAD_DISAGREEMENTS: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_payload_validity_disagreements_total",
+        "The number of payloads which some execution engines found valid and others invalid"
+    );
+
+    /*
+     * Request caching
+     */
+    pub static ref EXECUTION_LAYER_CACHE_HITS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "execution_layer_request_cache_hits_total",
+        "The number of requests which were answered from cache rather than sent to the engines",
+        &["method"]
+    );
+}
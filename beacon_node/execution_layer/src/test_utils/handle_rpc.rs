@@ -54,7 +54,7 @@ pub async fn handle_rpc<T: EthSpec>(
             )
             .unwrap())
         }
-        ENGINE_EXECUTE_PAYLOAD_V1 => {
+        ENGINE_NEW_PAYLOAD_V1 => {
             let request: JsonExecutionPayloadV1<T> = get_param(params, 0)?;
 
             let response = if let Some(status) = *ctx.static_execute_payload_response.lock() {
@@ -64,17 +64,22 @@ pub async fn handle_rpc<T: EthSpec>(
                         latest_valid_hash: Some(request.block_hash),
                         validation_error: None,
                     },
-                    ExecutePayloadResponseStatus::Syncing => ExecutePayloadResponse {
-                        status,
-                        latest_valid_hash: None,
-                        validation_error: None,
-                    },
-                    _ => unimplemented!("invalid static executePayloadResponse"),
+                    ExecutePayloadResponseStatus::Syncing => {
+                        ctx.execution_block_generator
+                            .write()
+                            .new_payload(request.into());
+                        ExecutePayloadResponse {
+                            status,
+                            latest_valid_hash: None,
+                            validation_error: None,
+                        }
+                    }
+                    _ => unimplemented!("invalid static newPayloadResponse"),
                 }
             } else {
                 ctx.execution_block_generator
                     .write()
-                    .execute_payload(request.into())
+                    .new_payload(request.into())
             };
 
             Ok(serde_json::to_value(JsonExecutePayloadV1Response::from(response)).unwrap())
@@ -108,6 +113,7 @@ pub async fn handle_rpc<T: EthSpec>(
             })
             .unwrap())
         }
+        ENGINE_EXCHANGE_CAPABILITIES => Ok(serde_json::to_value(LIGHTHOUSE_CAPABILITIES).unwrap()),
         other => Err(format!(
             "The method {} does not exist/is not available",
             other
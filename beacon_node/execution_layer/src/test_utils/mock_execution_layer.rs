@@ -88,6 +88,8 @@ impl<T: EthSpec> MockExecutionLayer<T> {
 
         let el = ExecutionLayer::from_urls(
             vec![url],
+            vec![],
+            None,
             Some(Address::repeat_byte(42)),
             el_runtime.task_executor.clone(),
             el_runtime.log.clone(),
@@ -102,6 +104,40 @@ impl<T: EthSpec> MockExecutionLayer<T> {
         }
     }
 
+    /// Replace the execution layer with one whose first engine is unreachable, so that all
+    /// requests must fall back to the mock server.
+    pub fn with_offline_primary_engine(mut self) -> Self {
+        let offline_url = SensitiveUrl::parse("http://127.0.0.1:1").unwrap();
+        let url = SensitiveUrl::parse(&self.server.url()).unwrap();
+
+        self.el = ExecutionLayer::from_urls(
+            vec![offline_url, url],
+            vec![],
+            None,
+            Some(Address::repeat_byte(42)),
+            self.el_runtime.task_executor.clone(),
+            self.el_runtime.log.clone(),
+        )
+        .unwrap();
+        self
+    }
+
+    /// Replace the execution layer with one whose only engine is unreachable.
+    pub fn with_offline_engine(mut self) -> Self {
+        let offline_url = SensitiveUrl::parse("http://127.0.0.1:1").unwrap();
+
+        self.el = ExecutionLayer::from_urls(
+            vec![offline_url],
+            vec![],
+            None,
+            Some(Address::repeat_byte(42)),
+            self.el_runtime.task_executor.clone(),
+            self.el_runtime.log.clone(),
+        )
+        .unwrap();
+        self
+    }
+
     pub async fn produce_valid_execution_payload_on_head(self) -> Self {
         let latest_execution_block = {
             let block_gen = self.server.execution_block_generator();
@@ -113,6 +149,7 @@ impl<T: EthSpec> MockExecutionLayer<T> {
         let timestamp = block_number;
         let random = Hash256::from_low_u64_be(block_number);
         let finalized_block_hash = parent_hash;
+        let proposer_index = 0;
 
         self.el
             .notify_forkchoice_updated(
@@ -129,7 +166,13 @@ impl<T: EthSpec> MockExecutionLayer<T> {
 
         let payload = self
             .el
-            .get_payload::<T>(parent_hash, timestamp, random, finalized_block_hash)
+            .get_payload::<T>(
+                parent_hash,
+                timestamp,
+                random,
+                finalized_block_hash,
+                proposer_index,
+            )
             .await
             .unwrap();
         let block_hash = payload.block_hash;
@@ -139,7 +182,7 @@ impl<T: EthSpec> MockExecutionLayer<T> {
         assert_eq!(payload.random, random);
 
         let (payload_response, latest_valid_hash) =
-            self.el.execute_payload(&payload).await.unwrap();
+            self.el.notify_new_payload(&payload).await.unwrap();
         assert_eq!(payload_response, ExecutePayloadResponseStatus::Valid);
         assert_eq!(latest_valid_hash, Some(payload.block_hash));
 
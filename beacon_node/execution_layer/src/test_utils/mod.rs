@@ -10,6 +10,7 @@ use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use slog::{info, Logger};
+use std::collections::HashMap;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
@@ -63,6 +64,7 @@ impl<T: EthSpec> MockServer<T> {
             execution_block_generator: RwLock::new(execution_block_generator),
             preloaded_responses,
             static_execute_payload_response: <_>::default(),
+            request_counts: <_>::default(),
             _phantom: PhantomData,
         });
 
@@ -116,10 +118,34 @@ impl<T: EthSpec> MockServer<T> {
         self.ctx.preloaded_responses.lock().push(response)
     }
 
+    /// Returns the number of JSON-RPC requests received for `method`, including those answered
+    /// with a preloaded response.
+    pub fn request_count(&self, method: &str) -> usize {
+        self.ctx
+            .request_counts
+            .lock()
+            .get(method)
+            .copied()
+            .unwrap_or(0)
+    }
+
     pub fn all_payloads_valid(&self) {
         *self.ctx.static_execute_payload_response.lock() = Some(ExecutePayloadResponseStatus::Valid)
     }
 
+    /// Respond to all payloads with "syncing", as if the EL were unable to verify them. The
+    /// payloads are still imported by the mock EL so that it can build upon them.
+    pub fn all_payloads_syncing(&self) {
+        *self.ctx.static_execute_payload_response.lock() =
+            Some(ExecutePayloadResponseStatus::Syncing)
+    }
+
+    /// Remove any static response set by `Self::all_payloads_valid` or
+    /// `Self::all_payloads_syncing`.
+    pub fn clear_static_payload_response(&self) {
+        *self.ctx.static_execute_payload_response.lock() = None
+    }
+
     pub fn insert_pow_block(
         &self,
         block_number: u64,
@@ -188,6 +214,8 @@ pub struct Context<T: EthSpec> {
     pub execution_block_generator: RwLock<ExecutionBlockGenerator<T>>,
     pub preloaded_responses: Arc<Mutex<Vec<serde_json::Value>>>,
     pub static_execute_payload_response: Arc<Mutex<Option<ExecutePayloadResponseStatus>>>,
+    /// The number of JSON-RPC requests received, by method.
+    pub request_counts: Arc<Mutex<HashMap<String, usize>>>,
     pub _phantom: PhantomData<T>,
 }
 
@@ -244,6 +272,13 @@ pub fn serve<T: EthSpec>(
                 .and_then(serde_json::Value::as_u64)
                 .ok_or_else(|| warp::reject::custom(MissingIdField))?;
 
+            if let Some(method) = body.get("method").and_then(serde_json::Value::as_str) {
+                *ctx.request_counts
+                    .lock()
+                    .entry(method.to_string())
+                    .or_default() += 1;
+            }
+
             let preloaded_response = {
                 let mut preloaded_responses = ctx.preloaded_responses.lock();
                 if !preloaded_responses.is_empty() {
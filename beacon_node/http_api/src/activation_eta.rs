@@ -0,0 +1,146 @@
+//! Handler for the `lighthouse/validators/{validator_id}/activation_eta` endpoint, which estimates
+//! when a validator will be activated.
+//!
+//! The estimate is made against the head state and assumes that the churn limit stays constant
+//! and that the eligibility of each queued validator is finalized in time for it to be dequeued.
+
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{ActivationEta, ActivationStatus};
+use eth2::types::ValidatorId;
+use slot_clock::SlotClock;
+use std::cmp;
+use types::{Epoch, EthSpec};
+
+/// Handles a request for the activation ETA of the validator with `validator_id`.
+///
+/// A validator index must be known to the head state, whereas a public key may belong to a
+/// validator which is not yet known to the beacon chain. The deposit cache is only consulted when
+/// `eth1_service` is present.
+pub fn get_activation_eta<T: BeaconChainTypes>(
+    validator_id: &ValidatorId,
+    chain: &BeaconChain<T>,
+    eth1_service: Option<eth1::Service>,
+) -> Result<ActivationEta, warp::Rejection> {
+    let pubkey = match validator_id {
+        ValidatorId::PublicKey(pubkey) => *pubkey,
+        ValidatorId::Index(index) => chain
+            .with_head(|head| {
+                Ok::<_, BeaconChainError>(
+                    head.beacon_state
+                        .validators()
+                        .get(*index as usize)
+                        .map(|validator| validator.pubkey),
+                )
+            })
+            .map_err(warp_utils::reject::beacon_chain_error)?
+            .ok_or_else(|| {
+                warp_utils::reject::custom_not_found(format!("unknown validator: {}", index))
+            })?,
+    };
+    let deposit_seen = eth1_service.map(|eth1_service| {
+        eth1_service
+            .deposits()
+            .read()
+            .cache
+            .iter()
+            .any(|log| log.signature_is_valid && log.deposit_data.pubkey == pubkey)
+    });
+    let validator_index = chain
+        .validator_index(&pubkey)
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+    let spec = &chain.spec;
+
+    let mut eta = chain
+        .with_head(|head| {
+            let state = &head.beacon_state;
+            let current_epoch = state.current_epoch();
+
+            // The activation queue, in the order in which it is processed by
+            // `process_registry_updates`.
+            let mut queue = vec![];
+            let mut active_validator_count = 0;
+            for (index, validator) in state.validators().iter().enumerate() {
+                if validator.is_active_at(current_epoch) {
+                    active_validator_count += 1;
+                }
+                if validator.activation_eligibility_epoch != spec.far_future_epoch
+                    && validator.activation_epoch == spec.far_future_epoch
+                {
+                    queue.push((validator.activation_eligibility_epoch, index));
+                }
+            }
+            queue.sort_unstable();
+
+            // Computed directly rather than with `BeaconState::get_churn_limit`, which requires
+            // the committee cache.
+            let churn_limit = cmp::max(
+                spec.min_per_epoch_churn_limit,
+                active_validator_count / spec.churn_limit_quotient,
+            );
+
+            let mut eta = ActivationEta {
+                pubkey,
+                status: ActivationStatus::Unknown,
+                deposit_seen,
+                validator_index: None,
+                queue_position: None,
+                queue_length: queue.len() as u64,
+                churn_limit,
+                eta_epoch: None,
+                eta_timestamp: None,
+            };
+
+            // The pubkey cache may be ahead of the head state.
+            let validator =
+                validator_index.and_then(|index| Some((index, state.validators().get(index)?)));
+
+            match validator {
+                None => {
+                    if deposit_seen == Some(true) {
+                        eta.status = ActivationStatus::DepositPending;
+                    }
+                }
+                Some((index, validator)) => {
+                    eta.validator_index = Some(index as u64);
+
+                    if validator.activation_epoch != spec.far_future_epoch {
+                        eta.status = if validator.activation_epoch <= current_epoch {
+                            ActivationStatus::Active
+                        } else {
+                            ActivationStatus::Scheduled
+                        };
+                        eta.eta_epoch = Some(validator.activation_epoch);
+                    } else if validator.activation_eligibility_epoch == spec.far_future_epoch {
+                        eta.status = ActivationStatus::PendingEligibility;
+                    } else {
+                        eta.status = ActivationStatus::Queued;
+                        eta.queue_position = queue
+                            .binary_search(&(validator.activation_eligibility_epoch, index))
+                            .ok()
+                            .map(|position| position as u64);
+
+                        if let Some(position) = eta.queue_position {
+                            // Validators ahead in the queue are dequeued `churn_limit` per epoch.
+                            let dequeue_epoch = current_epoch + position / churn_limit;
+                            eta.eta_epoch =
+                                Some(state.compute_activation_exit_epoch(dequeue_epoch, spec)?);
+                        }
+                    }
+                }
+            }
+
+            Ok::<_, BeaconChainError>(eta)
+        })
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+    eta.eta_timestamp = eta
+        .eta_epoch
+        .and_then(|epoch: Epoch| {
+            chain
+                .slot_clock
+                .start_of(epoch.start_slot(T::EthSpec::slots_per_epoch()))
+        })
+        .map(|duration| duration.as_secs());
+
+    Ok(eta)
+}
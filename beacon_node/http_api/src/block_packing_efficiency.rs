@@ -0,0 +1,200 @@
+//! Handler for the `lighthouse/analysis/block_packing` endpoint, which measures how well the
+//! proposers of canonical blocks packed the attestations available to them.
+//!
+//! The attestations which were on gossip when a block was proposed are not recorded, so they are
+//! approximated by the attestations which were eventually included in *some* canonical block. An
+//! attestation is considered available to a block if:
+//!
+//! - It is from one of the `SLOTS_PER_EPOCH` slots prior to the block, within which it could have
+//!   been included.
+//! - It had not already been included by an earlier block.
+//!
+//! Attestations are identified by their committee and position within it, so that the analysis
+//! does not require any states. An attestation which was published too late to be included by a
+//! block is counted against that block, so the efficiency of individual blocks should be read as
+//! a lower bound.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    BlockPackingEfficiency, BlockPackingEfficiencyReport, ProposerPackingEfficiency,
+};
+use std::collections::{BTreeMap, HashMap};
+use types::{BitList, Epoch, EthSpec, Slot};
+
+type CommitteeBits<E> = BitList<<E as EthSpec>::MaxValidatorsPerCommittee>;
+
+/// The maximum number of epochs which may be analysed by a single request, since every block in
+/// the range is loaded from the database.
+const MAX_REQUEST_EPOCHS: u64 = 100;
+
+/// Handles a request for the packing efficiency of the canonical blocks between the start of
+/// `start_epoch` and the end of `end_epoch`.
+pub fn get_block_packing_efficiency<T: BeaconChainTypes>(
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+    chain: &BeaconChain<T>,
+) -> Result<BlockPackingEfficiencyReport, warp::Rejection> {
+    if start_epoch > end_epoch {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "start epoch {} is after end epoch {}",
+            start_epoch, end_epoch
+        )));
+    }
+    if end_epoch - start_epoch >= MAX_REQUEST_EPOCHS {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "range of {} epochs exceeds the maximum of {}",
+            end_epoch - start_epoch + 1,
+            MAX_REQUEST_EPOCHS
+        )));
+    }
+
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let start_slot = start_epoch.start_slot(slots_per_epoch);
+    let end_slot = end_epoch.end_slot(slots_per_epoch);
+
+    let oldest_block_slot = chain.store.get_oldest_block_slot();
+    if start_slot < oldest_block_slot {
+        return Err(warp_utils::reject::custom_bad_request(format!(
+            "blocks prior to slot {} are not available, start epoch {} begins at slot {}",
+            oldest_block_slot, start_epoch, start_slot
+        )));
+    }
+
+    // Blocks from the epoch before the range determine which attestations were already included,
+    // and blocks from the epoch after the range determine which were eventually included.
+    let lookback_slot = std::cmp::max(
+        start_slot.saturating_sub(slots_per_epoch),
+        oldest_block_slot,
+    );
+    let lookahead_slot = end_slot + slots_per_epoch;
+
+    let mut blocks = vec![];
+    let mut prev_block_root = None;
+    for result in chain
+        .forwards_iter_block_roots(lookback_slot)
+        .map_err(warp_utils::reject::beacon_chain_error)?
+    {
+        let (block_root, slot) = result.map_err(warp_utils::reject::beacon_chain_error)?;
+        if slot > lookahead_slot {
+            break;
+        }
+        // Skipped slots repeat the root of the previous block.
+        if prev_block_root == Some(block_root) {
+            continue;
+        }
+        prev_block_root = Some(block_root);
+
+        let block = chain
+            .get_block(&block_root)
+            .map_err(warp_utils::reject::beacon_chain_error)?
+            .ok_or_else(|| {
+                warp_utils::reject::custom_server_error(format!(
+                    "missing canonical block {:?}",
+                    block_root
+                ))
+            })?;
+        blocks.push((block_root, block));
+    }
+
+    // The attestations eventually included for each committee, keyed by `(slot, index)`.
+    let mut eventually_included = BTreeMap::<(Slot, u64), CommitteeBits<T::EthSpec>>::new();
+    for (_, block) in &blocks {
+        for attestation in block.message().body().attestations() {
+            let key = (attestation.data.slot, attestation.data.index);
+            let bits = match eventually_included.remove(&key) {
+                Some(bits) => bits.union(&attestation.aggregation_bits),
+                None => attestation.aggregation_bits.clone(),
+            };
+            eventually_included.insert(key, bits);
+        }
+    }
+
+    let mut included_so_far = HashMap::<(Slot, u64), CommitteeBits<T::EthSpec>>::new();
+    let mut block_efficiencies = vec![];
+    let mut proposers = BTreeMap::<u64, ProposerPackingEfficiency>::new();
+
+    for (block_root, block) in blocks {
+        let block = block.message();
+        let slot = block.slot();
+
+        let window_start = (slot.saturating_sub(slots_per_epoch), 0);
+        let window_end = (slot, 0);
+        let available_attestations = eventually_included
+            .range(window_start..window_end)
+            .map(|(key, bits)| match included_so_far.get(key) {
+                Some(included) => bits.difference(included).num_set_bits(),
+                None => bits.num_set_bits(),
+            })
+            .sum::<usize>() as u64;
+
+        let mut included_attestations = 0;
+        for attestation in block.body().attestations() {
+            let key = (attestation.data.slot, attestation.data.index);
+            let new_bits = match included_so_far.get(&key) {
+                Some(included) => attestation.aggregation_bits.difference(included),
+                None => attestation.aggregation_bits.clone(),
+            };
+            included_attestations += new_bits.num_set_bits() as u64;
+
+            let bits = match included_so_far.remove(&key) {
+                Some(included) => included.union(&new_bits),
+                None => new_bits,
+            };
+            included_so_far.insert(key, bits);
+        }
+
+        // The genesis block has no proposer.
+        if slot < start_slot || slot > end_slot || slot == 0 {
+            continue;
+        }
+
+        let proposer_index = block.proposer_index();
+        let proposer = proposers
+            .entry(proposer_index)
+            .or_insert(ProposerPackingEfficiency {
+                proposer_index,
+                blocks: 0,
+                available_attestations: 0,
+                included_attestations: 0,
+                efficiency: 0.0,
+            });
+        proposer.blocks += 1;
+        proposer.available_attestations += available_attestations;
+        proposer.included_attestations += included_attestations;
+
+        block_efficiencies.push(BlockPackingEfficiency {
+            slot,
+            block_root,
+            proposer_index,
+            available_attestations,
+            included_attestations,
+            efficiency: efficiency(included_attestations, available_attestations),
+        });
+    }
+
+    let proposers = proposers
+        .into_values()
+        .map(|mut proposer| {
+            proposer.efficiency = efficiency(
+                proposer.included_attestations,
+                proposer.available_attestations,
+            );
+            proposer
+        })
+        .collect();
+
+    Ok(BlockPackingEfficiencyReport {
+        blocks: block_efficiencies,
+        proposers,
+    })
+}
+
+/// Returns the percentage of the `available` attestations which were `included`, or 100 if none
+/// were available.
+fn efficiency(included: u64, available: u64) -> f64 {
+    if available == 0 {
+        100.0
+    } else {
+        (included as f64 / available as f64) * 100.0
+    }
+}
@@ -5,13 +5,24 @@
 //! There are also some additional, non-standard endpoints behind the `/lighthouse/` path which are
 //! used for development.
 
+mod activation_eta;
 mod attester_duties;
 mod block_id;
+mod block_packing_efficiency;
 mod database;
+mod light_client;
+mod limits;
+mod log_levels;
 mod metrics;
+mod op_pool;
 mod proposer_duties;
+mod rewards;
+mod slasher;
+mod ssz_stream;
 mod state_id;
+mod state_regen;
 mod sync_committees;
+mod ui;
 mod validator_inclusion;
 mod version;
 
@@ -26,26 +37,31 @@ use block_id::BlockId;
 use eth2::types::{self as api_types, EndpointVersion, ValidatorId};
 use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
 use lighthouse_version::version_with_platform;
+use limits::{body_size_limit_filter, concurrency_limit_filter, ConcurrencyLimits};
 use network::NetworkMessage;
+use proto_array::ExecutionStatus;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use ssz::Encode;
 use state_id::StateId;
+use state_regen::{StateRegenQueue, StateRegenStatus};
 use std::borrow::Cow;
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::future::Future;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
-use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_stream::{wrappers::BroadcastStream, StreamExt};
 use types::{
-    Attestation, AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch,
-    EthSpec, ForkName, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof, SignedBeaconBlock,
-    SignedContributionAndProof, SignedVoluntaryExit, Slot, SyncCommitteeMessage,
-    SyncContributionData,
+    Attestation, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec, CommitteeCache,
+    ConfigAndPreset, Epoch, EthSpec, ForkName, ProposerSlashing, RelativeEpoch,
+    SignedAggregateAndProof, SignedBeaconBlock, SignedBlindedBeaconBlock,
+    SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
+    SyncCommitteeMessage, SyncContributionData,
 };
 use version::{
     add_consensus_version_header, fork_versioned_response, inconsistent_fork_rejection,
@@ -55,8 +71,11 @@ use warp::http::StatusCode;
 use warp::sse::Event;
 use warp::Reply;
 use warp::{http::Response, Filter};
+use warp_utils::cors::CorsDefaults;
 use warp_utils::task::{blocking_json_task, blocking_task};
 
+pub use warp_utils::tls::TlsConfig;
+
 const API_PREFIX: &str = "eth";
 
 /// If the node is within this many epochs from the head, we declare it to be synced regardless of
@@ -66,16 +85,38 @@ const API_PREFIX: &str = "eth";
 /// finalized head.
 const SYNC_TOLERANCE_EPOCHS: u64 = 8;
 
+/// The default maximum size of a request body, in bytes.
+pub const DEFAULT_MAX_BODY_SIZE: u64 = 32 * 1024 * 1024;
+
+/// The default maximum number of concurrent requests to routes which are not critical to
+/// validator duties.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 64;
+
+/// The default maximum number of states to regenerate concurrently for the `debug/beacon/states`
+/// endpoint.
+pub const DEFAULT_MAX_CONCURRENT_STATE_REGENS: usize = 2;
+
+/// The default number of regenerated states to cache for the `debug/beacon/states` endpoint.
+///
+/// The cache only needs to hold each state until its request is retried, so one entry per
+/// concurrent regeneration suffices. A mainnet state occupies hundreds of megabytes of memory.
+pub const DEFAULT_STATE_REGEN_CACHE_SIZE: usize = DEFAULT_MAX_CONCURRENT_STATE_REGENS;
+
+/// The default time to wait for a state to be regenerated before responding with
+/// `202 Accepted`.
+pub const DEFAULT_STATE_REGEN_MAX_WAIT: Duration = Duration::from_secs(12);
+
+/// The default number of recent events of each topic which are retained, so that they may be
+/// replayed to event stream subscribers which reconnect with a `Last-Event-ID`.
+pub const DEFAULT_SSE_REPLAY_WINDOW: usize = 64;
+
+/// The default number of connected peers required for `lighthouse/health/ready` to report that the
+/// node is ready.
+pub const DEFAULT_READY_MIN_PEERS: usize = 1;
+
 /// A custom type which allows for both unsecured and TLS-enabled HTTP servers.
 type HttpServer = (SocketAddr, Pin<Box<dyn Future<Output = ()> + Send>>);
 
-/// Configuration used when serving the HTTP server over TLS.
-#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
-pub struct TlsConfig {
-    pub cert: PathBuf,
-    pub key: PathBuf,
-}
-
 /// A wrapper around all the items required to spawn the HTTP server.
 ///
 /// The server will gracefully handle the case where any fields are `None`.
@@ -85,6 +126,7 @@ pub struct Context<T: BeaconChainTypes> {
     pub network_tx: Option<UnboundedSender<NetworkMessage<T::EthSpec>>>,
     pub network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     pub eth1_service: Option<eth1::Service>,
+    pub log_levels: Option<logging::LogLevels>,
     pub log: Logger,
 }
 
@@ -95,9 +137,40 @@ pub struct Config {
     pub listen_addr: Ipv4Addr,
     pub listen_port: u16,
     pub allow_origin: Option<String>,
+    /// A comma-separated list of the methods allowed by CORS, replacing the defaults.
+    pub allow_methods: Option<String>,
+    /// A comma-separated list of the headers allowed by CORS, replacing the defaults.
+    pub allow_headers: Option<String>,
+    /// Whether CORS requests may include credentials.
+    pub allow_credentials: bool,
     pub serve_legacy_spec: bool,
     pub tls_config: Option<TlsConfig>,
     pub allow_sync_stalled: bool,
+    /// Requests which declare a body larger than this many bytes are rejected, as are requests
+    /// with a body of unknown length.
+    pub max_body_size: u64,
+    /// The maximum number of requests to non-critical routes to serve concurrently, or `None` for
+    /// no limit. Excess requests wait for earlier requests to complete.
+    pub max_concurrent_requests: Option<usize>,
+    /// The maximum number of requests to validator-critical routes (e.g., block production and
+    /// attestation data) to serve concurrently, or `None` for no limit. These requests are never
+    /// delayed by requests to other routes.
+    pub max_concurrent_priority_requests: Option<usize>,
+    /// The maximum number of states to regenerate concurrently for the `debug/beacon/states`
+    /// endpoint. Excess regenerations wait for earlier regenerations to complete.
+    pub max_concurrent_state_regens: usize,
+    /// The number of regenerated states to cache, so that retried requests are served
+    /// immediately.
+    pub state_regen_cache_size: usize,
+    /// The time to wait for a state to be regenerated before responding with `202 Accepted` and
+    /// continuing the regeneration in the background.
+    pub state_regen_max_wait: Duration,
+    /// The number of recent events of each topic to retain for replay to event stream subscribers
+    /// which reconnect with a `Last-Event-ID`.
+    pub sse_replay_window: usize,
+    /// The number of connected peers required for `lighthouse/health/ready` to report that the
+    /// node is ready.
+    pub ready_min_peers: usize,
 }
 
 impl Default for Config {
@@ -107,9 +180,20 @@ impl Default for Config {
             listen_addr: Ipv4Addr::new(127, 0, 0, 1),
             listen_port: 5052,
             allow_origin: None,
+            allow_methods: None,
+            allow_headers: None,
+            allow_credentials: false,
             serve_legacy_spec: true,
             tls_config: None,
             allow_sync_stalled: false,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            max_concurrent_requests: Some(DEFAULT_MAX_CONCURRENT_REQUESTS),
+            max_concurrent_priority_requests: None,
+            max_concurrent_state_regens: DEFAULT_MAX_CONCURRENT_STATE_REGENS,
+            state_regen_cache_size: DEFAULT_STATE_REGEN_CACHE_SIZE,
+            state_regen_max_wait: DEFAULT_STATE_REGEN_MAX_WAIT,
+            sse_replay_window: DEFAULT_SSE_REPLAY_WINDOW,
+            ready_min_peers: DEFAULT_READY_MIN_PEERS,
         }
     }
 }
@@ -243,17 +327,17 @@ pub fn serve<T: BeaconChainTypes>(
     let log = ctx.log.clone();
 
     // Configure CORS.
-    let cors_builder = {
-        let builder = warp::cors()
-            .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
-
-        warp_utils::cors::set_builder_origins(
-            builder,
-            config.allow_origin.as_deref(),
-            (config.listen_addr, config.listen_port),
-        )?
-    };
+    let cors_builder = warp_utils::cors::build_cors(
+        config.allow_origin.as_deref(),
+        config.allow_methods.as_deref(),
+        config.allow_headers.as_deref(),
+        config.allow_credentials,
+        CorsDefaults {
+            origin: (config.listen_addr, config.listen_port),
+            methods: &["GET", "POST"],
+            headers: &["Content-Type"],
+        },
+    )?;
 
     // Sanity check.
     if !config.enabled {
@@ -340,6 +424,23 @@ pub fn serve<T: BeaconChainTypes>(
             }
         });
 
+    // Create a `warp` filter that provides access to the Eth1 service, if it is running.
+    let inner_ctx = ctx.clone();
+    let eth1_service_opt_filter = warp::any().map(move || inner_ctx.eth1_service.clone());
+
+    // Create a `warp` filter that provides access to the overrides of the log levels.
+    let inner_ctx = ctx.clone();
+    let log_levels_filter = warp::any()
+        .map(move || inner_ctx.log_levels.clone())
+        .and_then(|log_levels| async move {
+            match log_levels {
+                Some(log_levels) => Ok(log_levels),
+                None => Err(warp_utils::reject::custom_not_found(
+                    "Log levels cannot be changed at runtime by this node.".to_string(),
+                )),
+            }
+        });
+
     // Create a `warp` filter that rejects requests whilst the node is syncing.
     let not_while_syncing_filter =
         warp::any()
@@ -414,10 +515,42 @@ pub fn serve<T: BeaconChainTypes>(
         })
         .untuple_one();
 
+    // Create a `warp` filter that rejects requests which require an execution payload whilst all
+    // of the execution engines are offline.
+    let only_with_execution_layer_online = warp::any()
+        .and(chain_filter.clone())
+        .and_then(move |chain: Arc<BeaconChain<T>>| async move {
+            let execution_layer = match &chain.execution_layer {
+                Some(execution_layer) => execution_layer,
+                None => return Ok(()),
+            };
+            // Blocks prior to the merge fork don't contain a payload.
+            let requires_payload = match (chain.spec.bellatrix_fork_epoch, chain.epoch()) {
+                (Some(fork_epoch), Ok(epoch)) => epoch >= fork_epoch,
+                _ => false,
+            };
+            if requires_payload && execution_layer.is_offline() {
+                Err(warp_utils::reject::execution_layer_offline(
+                    "unable to produce an execution payload".to_string(),
+                ))
+            } else {
+                Ok(())
+            }
+        })
+        .untuple_one();
+
     // Create a `warp` filter that provides access to the logger.
     let inner_ctx = ctx.clone();
     let log_filter = warp::any().map(move || inner_ctx.log.clone());
 
+    // Create a `warp` filter that provides access to the state regeneration queue.
+    let state_regen_queue = StateRegenQueue::new(
+        config.max_concurrent_state_regens,
+        config.state_regen_cache_size,
+        config.state_regen_max_wait,
+    );
+    let state_regen_filter = warp::any().map(move || state_regen_queue.clone());
+
     /*
      *
      * Start of HTTP method definitions.
@@ -555,53 +688,40 @@ pub fn serve<T: BeaconChainTypes>(
                 blocking_json_task(move || {
                     state_id
                         .map_state(&chain, |state| {
-                            let epoch = state.current_epoch();
-                            let far_future_epoch = chain.spec.far_future_epoch;
-
-                            Ok(state
-                                .validators()
-                                .iter()
-                                .zip(state.balances().iter())
-                                .enumerate()
-                                // filter by validator id(s) if provided
-                                .filter(|(index, (validator, _))| {
-                                    query.id.as_ref().map_or(true, |ids| {
-                                        ids.0.iter().any(|id| match id {
-                                            ValidatorId::PublicKey(pubkey) => {
-                                                &validator.pubkey == pubkey
-                                            }
-                                            ValidatorId::Index(param_index) => {
-                                                *param_index == *index as u64
-                                            }
-                                        })
-                                    })
-                                })
-                                // filter by status(es) if provided and map the result
-                                .filter_map(|(index, (validator, balance))| {
-                                    let status = api_types::ValidatorStatus::from_validator(
-                                        validator,
-                                        epoch,
-                                        far_future_epoch,
-                                    );
+                            Ok(filter_state_validators(
+                                state,
+                                &chain.spec,
+                                query.id.as_ref().map(|ids| ids.0.as_slice()),
+                                query.status.as_ref().map(|statuses| statuses.0.as_slice()),
+                            ))
+                        })
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
 
-                                    let status_matches =
-                                        query.status.as_ref().map_or(true, |statuses| {
-                                            statuses.0.contains(&status)
-                                                || statuses.0.contains(&status.superstatus())
-                                        });
-
-                                    if status_matches {
-                                        Some(api_types::ValidatorData {
-                                            index: index as u64,
-                                            balance: *balance,
-                                            status,
-                                            validator: validator.clone(),
-                                        })
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect::<Vec<_>>())
+    // POST beacon/states/{state_id}/validators
+    //
+    // Equivalent to the `GET` variant, but accepts the filters in the request body so that large
+    // numbers of validator ids do not hit URL length limits.
+    let post_beacon_state_validators = beacon_states_path
+        .clone()
+        .and(warp::path("validators"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and_then(
+            |state_id: StateId,
+             chain: Arc<BeaconChain<T>>,
+             request: api_types::ValidatorsRequestBody| {
+                blocking_json_task(move || {
+                    state_id
+                        .map_state(&chain, |state| {
+                            Ok(filter_state_validators(
+                                state,
+                                &chain.spec,
+                                request.ids.as_deref(),
+                                request.statuses.as_deref(),
+                            ))
                         })
                         .map(api_types::GenericResponse::from)
                 })
@@ -890,10 +1010,20 @@ pub fn serve<T: BeaconChainTypes>(
                 let root = block_id.root(&chain)?;
                 let block = BlockId::from_root(root).block(&chain)?;
 
-                let canonical = chain
-                    .block_root_at_slot(block.slot(), WhenSlotSkipped::None)
-                    .map_err(warp_utils::reject::beacon_chain_error)?
-                    .map_or(false, |canonical| root == canonical);
+                // Frozen blocks are indexed by root only if they are canonical.
+                let canonical = if chain
+                    .store
+                    .get_frozen_block_slot(&root)
+                    .map_err(|e| warp_utils::reject::beacon_chain_error(e.into()))?
+                    .is_some()
+                {
+                    true
+                } else {
+                    chain
+                        .block_root_at_slot(block.slot(), WhenSlotSkipped::None)
+                        .map_err(warp_utils::reject::beacon_chain_error)?
+                        .map_or(false, |canonical| root == canonical)
+                };
 
                 let data = api_types::BlockHeaderData {
                     root,
@@ -926,90 +1056,43 @@ pub fn serve<T: BeaconChainTypes>(
              chain: Arc<BeaconChain<T>>,
              network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
              log: Logger| {
-                blocking_json_task(move || {
-                    let seen_timestamp = timestamp_now();
-
-                    // Send the block, regardless of whether or not it is valid. The API
-                    // specification is very clear that this is the desired behaviour.
-                    publish_pubsub_message(
-                        &network_tx,
-                        PubsubMessage::BeaconBlock(Box::new(block.clone())),
-                    )?;
-
-                    // Determine the delay after the start of the slot, register it with metrics.
-                    let delay =
-                        get_block_delay_ms(seen_timestamp, block.message(), &chain.slot_clock);
-                    metrics::observe_duration(
-                        &metrics::HTTP_API_BLOCK_BROADCAST_DELAY_TIMES,
-                        delay,
-                    );
-
-
-                    match chain.process_block(block.clone()) {
-                        Ok(root) => {
-                            info!(
-                                log,
-                                "Valid block from HTTP API";
-                                "block_delay" => ?delay,
-                                "root" => format!("{}", root),
-                                "proposer_index" => block.message().proposer_index(),
-                                "slot" => block.slot(),
-                            );
-
-                            // Notify the validator monitor.
-                            chain.validator_monitor.read().register_api_block(
-                                seen_timestamp,
-                                block.message(),
-                                root,
-                                &chain.slot_clock,
-                            );
+                blocking_json_task(move || publish_block(block, chain, &network_tx, log))
+            },
+        );
 
-                            // Update the head since it's likely this block will become the new
-                            // head.
-                            chain
-                                .fork_choice()
-                                .map_err(warp_utils::reject::beacon_chain_error)?;
+    // POST beacon/blinded_blocks
+    let post_beacon_blinded_blocks = eth1_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("blinded_blocks"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and(network_tx_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |block: SignedBlindedBeaconBlock<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>,
+             log: Logger| async move {
+                let execution_layer = chain
+                    .execution_layer
+                    .as_ref()
+                    .ok_or(BeaconChainError::ExecutionLayerMissing)
+                    .map_err(warp_utils::reject::beacon_chain_error)?;
 
-                            // Perform some logging to inform users if their blocks are being produced
-                            // late.
-                            //
-                            // Check to see the thresholds are non-zero to avoid logging errors with small
-                            // slot times (e.g., during testing)
-                            let crit_threshold = chain.slot_clock.unagg_attestation_production_delay();
-                            let error_threshold = crit_threshold / 2;
-                            if delay >= crit_threshold {
-                                crit!(
-                                    log,
-                                    "Block was broadcast too late";
-                                    "msg" => "system may be overloaded, block likely to be orphaned",
-                                    "delay_ms" => delay.as_millis(),
-                                    "slot" => block.slot(),
-                                    "root" => ?root,
-                                )
-                            } else if delay >= error_threshold  {
-                                error!(
-                                    log,
-                                    "Block broadcast was delayed";
-                                    "msg" => "system may be overloaded, block may be orphaned",
-                                    "delay_ms" => delay.as_millis(),
-                                    "slot" => block.slot(),
-                                    "root" => ?root,
-                                )
-                            }
+                // Reveal the payload by submitting the signed blinded block to the builder.
+                let payload = execution_layer
+                    .propose_blinded_beacon_block(&block)
+                    .await
+                    .map_err(|e| {
+                        warp_utils::reject::custom_server_error(format!(
+                            "unable to reveal payload: {:?}",
+                            e
+                        ))
+                    })?;
+                let block = block.into_full_block(payload);
 
-                            Ok(())
-                        }
-                        Err(e) => {
-                            let msg = format!("{:?}", e);
-                            error!(
-                                log,
-                                "Invalid block provided to HTTP API";
-                                "reason" => &msg
-                            );
-                            Err(warp_utils::reject::broadcast_without_import(msg))
-                        }
-                    }
-                })
+                blocking_json_task(move || publish_block(block, chain, &network_tx, log)).await
             },
         );
 
@@ -1093,6 +1176,106 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    /*
+     * beacon/rewards
+     */
+
+    let beacon_rewards_path = eth1_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("rewards"))
+        .and(chain_filter.clone());
+
+    // GET beacon/rewards/blocks/{block_id}
+    let get_beacon_rewards_blocks = beacon_rewards_path
+        .clone()
+        .and(warp::path("blocks"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and_then(|chain: Arc<BeaconChain<T>>, block_id: BlockId| {
+            blocking_json_task(move || rewards::block_rewards(block_id, &chain))
+        });
+
+    // POST beacon/rewards/sync_committee/{block_id}
+    let post_beacon_rewards_sync_committee = beacon_rewards_path
+        .clone()
+        .and(warp::path("sync_committee"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, block_id: BlockId, ids: Vec<ValidatorId>| {
+                blocking_json_task(move || rewards::sync_committee_rewards(block_id, &ids, &chain))
+            },
+        );
+
+    // POST beacon/rewards/attestations/{epoch}
+    let post_beacon_rewards_attestations = beacon_rewards_path
+        .clone()
+        .and(warp::path("attestations"))
+        .and(warp::path::param::<Epoch>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid epoch".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, epoch: Epoch, ids: Vec<ValidatorId>| {
+                blocking_json_task(move || rewards::attestation_rewards(epoch, &ids, &chain))
+            },
+        );
+
+    /*
+     * beacon/light_client
+     */
+
+    let beacon_light_client_path = eth1_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("light_client"))
+        .and(chain_filter.clone());
+
+    // GET beacon/light_client/bootstrap/{block_root}
+    let get_beacon_light_client_bootstrap = beacon_light_client_path
+        .clone()
+        .and(warp::path("bootstrap"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and_then(|chain: Arc<BeaconChain<T>>, block_id: BlockId| {
+            blocking_json_task(move || light_client::bootstrap(block_id, &chain))
+        });
+
+    // GET beacon/light_client/updates?start_period,count
+    let get_beacon_light_client_updates = beacon_light_client_path
+        .clone()
+        .and(warp::path("updates"))
+        .and(warp::path::end())
+        .and(warp::query::<api_types::LightClientUpdatesQuery>())
+        .and_then(
+            |chain: Arc<BeaconChain<T>>, query: api_types::LightClientUpdatesQuery| {
+                blocking_json_task(move || {
+                    light_client::updates(query.start_period, query.count, &chain)
+                })
+            },
+        );
+
+    // GET beacon/light_client/finality_update
+    let get_beacon_light_client_finality_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("finality_update"))
+        .and(warp::path::end())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || light_client::finality_update(&chain))
+        });
+
+    // GET beacon/light_client/optimistic_update
+    let get_beacon_light_client_optimistic_update = beacon_light_client_path
+        .clone()
+        .and(warp::path("optimistic_update"))
+        .and(warp::path::end())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || light_client::optimistic_update(&chain))
+        });
+
     /*
      * beacon/pool
      */
@@ -1256,10 +1439,11 @@ pub fn serve<T: BeaconChainTypes>(
                         })?;
 
                     // Notify the validator monitor.
-                    chain
+                    let validator_slashings = chain
                         .validator_monitor
                         .read()
                         .register_api_attester_slashing(&slashing);
+                    chain.emit_validator_slashing_events(validator_slashings);
 
                     if let ObservationOutcome::New(slashing) = outcome {
                         publish_pubsub_message(
@@ -1313,10 +1497,11 @@ pub fn serve<T: BeaconChainTypes>(
                         })?;
 
                     // Notify the validator monitor.
-                    chain
+                    let validator_slashings = chain
                         .validator_monitor
                         .read()
                         .register_api_proposer_slashing(&slashing);
+                    chain.emit_validator_slashing_events(validator_slashings);
 
                     if let ObservationOutcome::New(slashing) = outcome {
                         publish_pubsub_message(
@@ -1492,40 +1677,83 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(warp::header::optional::<api_types::Accept>("accept"))
         .and(chain_filter.clone())
+        .and(state_regen_filter)
         .and_then(
             |endpoint_version: EndpointVersion,
              state_id: StateId,
              accept_header: Option<api_types::Accept>,
-             chain: Arc<BeaconChain<T>>| {
-                blocking_task(move || match accept_header {
-                    Some(api_types::Accept::Ssz) => {
-                        let state = state_id.state(&chain)?;
-                        let fork_name = state
-                            .fork_name(&chain.spec)
-                            .map_err(inconsistent_fork_rejection)?;
-                        Response::builder()
+             chain: Arc<BeaconChain<T>>,
+             state_regen_queue: StateRegenQueue<T::EthSpec>| async move {
+                // Non-head states may take a long time to regenerate, so they are loaded via the
+                // regeneration queue rather than in a blocking task.
+                let state = if state_id.is_head() {
+                    let inner_chain = chain.clone();
+                    Arc::new(blocking_task(move || state_id.state(&inner_chain)).await?)
+                } else {
+                    let inner_chain = chain.clone();
+                    let (state_root, slot_opt) =
+                        blocking_task(move || state_id.root_and_slot(&inner_chain)).await?;
+
+                    match state_regen_queue
+                        .get_state(chain.clone(), state_root, slot_opt)
+                        .await
+                        .map_err(warp_utils::reject::custom_server_error)?
+                    {
+                        StateRegenStatus::Ready(state) => state,
+                        StateRegenStatus::NotFound => {
+                            return Err(warp_utils::reject::custom_not_found(format!(
+                                "beacon state at root {}",
+                                state_root
+                            )))
+                        }
+                        StateRegenStatus::Pending { retry_after } => {
+                            return Response::builder()
+                                .status(StatusCode::ACCEPTED)
+                                .header("Retry-After", retry_after.as_secs().max(1))
+                                .body(warp::hyper::Body::empty())
+                                .map_err(|e| {
+                                    warp_utils::reject::custom_server_error(format!(
+                                        "failed to create response: {}",
+                                        e
+                                    ))
+                                })
+                        }
+                    }
+                };
+
+                blocking_task(move || {
+                    let fork_name = state
+                        .fork_name(&chain.spec)
+                        .map_err(inconsistent_fork_rejection)?;
+                    match accept_header {
+                        // The encoding is streamed to the client as it is produced, rather than
+                        // being built in memory in full.
+                        Some(api_types::Accept::Ssz) => Response::builder()
                             .status(200)
                             .header("Content-Type", "application/octet-stream")
-                            .body(state.as_ssz_bytes().into())
-                            .map(|resp| add_consensus_version_header(resp, fork_name))
+                            .body(warp::hyper::Body::wrap_stream(
+                                ssz_stream::ssz_state_stream(state),
+                            ))
+                            .map(|resp| {
+                                add_consensus_version_header(resp, fork_name).into_response()
+                            })
                             .map_err(|e| {
                                 warp_utils::reject::custom_server_error(format!(
                                     "failed to create response: {}",
                                     e
                                 ))
-                            })
+                            }),
+                        _ => {
+                            let res =
+                                fork_versioned_response(endpoint_version, fork_name, &*state)?;
+                            Ok(
+                                add_consensus_version_header(warp::reply::json(&res), fork_name)
+                                    .into_response(),
+                            )
+                        }
                     }
-                    _ => state_id.map_state(&chain, |state| {
-                        let fork_name = state
-                            .fork_name(&chain.spec)
-                            .map_err(inconsistent_fork_rejection)?;
-                        let res = fork_versioned_response(endpoint_version, fork_name, &state)?;
-                        Ok(add_consensus_version_header(
-                            warp::reply::json(&res).into_response(),
-                            fork_name,
-                        ))
-                    }),
                 })
+                .await
             },
         );
 
@@ -1547,6 +1775,64 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET debug/fork_choice
+    let get_debug_fork_choice = eth1_v1
+        .and(warp::path("debug"))
+        .and(warp::path("fork_choice"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                let fork_choice = chain.fork_choice.read();
+                let proto_array = fork_choice.proto_array().core_proto_array();
+
+                let fork_choice_nodes = proto_array
+                    .nodes
+                    .iter()
+                    .map(|node| {
+                        let execution_status = match node.execution_status {
+                            ExecutionStatus::Valid(_) => {
+                                api_types::ForkChoiceNodeExecutionStatus::Valid
+                            }
+                            ExecutionStatus::Invalid(_) => {
+                                api_types::ForkChoiceNodeExecutionStatus::Invalid
+                            }
+                            ExecutionStatus::Unknown(_) => {
+                                api_types::ForkChoiceNodeExecutionStatus::Optimistic
+                            }
+                            ExecutionStatus::Irrelevant(_) => {
+                                api_types::ForkChoiceNodeExecutionStatus::Irrelevant
+                            }
+                        };
+
+                        api_types::ForkChoiceNode {
+                            slot: node.slot,
+                            block_root: node.root,
+                            parent_root: node
+                                .parent
+                                .and_then(|index| proto_array.nodes.get(index))
+                                .map(|parent| parent.root),
+                            justified_epoch: node
+                                .justified_checkpoint
+                                .map(|checkpoint| checkpoint.epoch),
+                            finalized_epoch: node
+                                .finalized_checkpoint
+                                .map(|checkpoint| checkpoint.epoch),
+                            weight: node.weight,
+                            execution_status,
+                            execution_block_hash: node.execution_status.block_hash(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+
+                Ok(api_types::ForkChoice {
+                    justified_checkpoint: proto_array.justified_checkpoint,
+                    finalized_checkpoint: proto_array.finalized_checkpoint,
+                    fork_choice_nodes,
+                })
+            })
+        });
+
     /*
      * node
      */
@@ -1625,6 +1911,7 @@ pub fn serve<T: BeaconChainTypes>(
 
                     let syncing_data = api_types::SyncingData {
                         is_syncing: network_globals.sync_state.read().is_syncing(),
+                        el_offline: chain.execution_layer.as_ref().map(|el| el.is_offline()),
                         head_slot,
                         sync_distance,
                     };
@@ -1791,33 +2078,9 @@ pub fn serve<T: BeaconChainTypes>(
         .and(network_globals.clone())
         .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
             blocking_json_task(move || {
-                let mut connected: u64 = 0;
-                let mut connecting: u64 = 0;
-                let mut disconnected: u64 = 0;
-                let mut disconnecting: u64 = 0;
-
-                network_globals
-                    .peers
-                    .read()
-                    .peers()
-                    .for_each(|(_, peer_info)| {
-                        let state = api_types::PeerState::from_peer_connection_status(
-                            peer_info.connection_status(),
-                        );
-                        match state {
-                            api_types::PeerState::Connected => connected += 1,
-                            api_types::PeerState::Connecting => connecting += 1,
-                            api_types::PeerState::Disconnected => disconnected += 1,
-                            api_types::PeerState::Disconnecting => disconnecting += 1,
-                        }
-                    });
-
-                Ok(api_types::GenericResponse::from(api_types::PeerCount {
-                    connected,
-                    connecting,
-                    disconnected,
-                    disconnecting,
-                }))
+                Ok(api_types::GenericResponse::from(ui::peer_count(
+                    &network_globals,
+                )))
             })
         });
     /*
@@ -1853,6 +2116,7 @@ pub fn serve<T: BeaconChainTypes>(
         }))
         .and(warp::path::end())
         .and(not_while_syncing_filter.clone())
+        .and(only_with_execution_layer_online.clone())
         .and(warp::query::<api_types::ValidatorBlocksQuery>())
         .and(chain_filter.clone())
         .and_then(
@@ -1880,6 +2144,42 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET validator/blinded_blocks/{slot}
+    let get_validator_blinded_blocks = eth1_v1
+        .and(warp::path("validator"))
+        .and(warp::path("blinded_blocks"))
+        .and(warp::path::param::<Slot>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid slot".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(not_while_syncing_filter.clone())
+        .and(only_with_execution_layer_online.clone())
+        .and(warp::query::<api_types::ValidatorBlocksQuery>())
+        .and(chain_filter.clone())
+        .and_then(
+            |slot: Slot, query: api_types::ValidatorBlocksQuery, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    let randao_reveal = (&query.randao_reveal).try_into().map_err(|e| {
+                        warp_utils::reject::custom_bad_request(format!(
+                            "randao reveal is not valid BLS signature: {:?}",
+                            e
+                        ))
+                    })?;
+
+                    let (block, _) = chain
+                        .produce_blinded_block(randao_reveal, slot, query.graffiti.map(Into::into))
+                        .map_err(warp_utils::reject::block_production_error)?;
+                    // Blinded blocks only exist from the merge, so the version is always known.
+                    Ok(api_types::ForkVersionedResponse {
+                        version: Some(ForkName::Merge),
+                        data: block,
+                    })
+                })
+            },
+        );
+
     // GET validator/attestation_data?slot,committee_index
     let get_validator_attestation_data = eth1_v1
         .and(warp::path("validator"))
@@ -1887,7 +2187,6 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(warp::query::<api_types::ValidatorAttestationDataQuery>())
         .and(not_while_syncing_filter.clone())
-        .and(only_with_safe_head.clone())
         .and(chain_filter.clone())
         .and_then(
             |query: api_types::ValidatorAttestationDataQuery, chain: Arc<BeaconChain<T>>| {
@@ -1904,8 +2203,30 @@ pub fn serve<T: BeaconChainTypes>(
                         )));
                     }
 
-                    chain
-                        .produce_unaggregated_attestation(query.slot, query.committee_index)
+                    // Unless permitted by the request, only attest to blocks which have been
+                    // verified by the EL.
+                    let head_safety_status = chain
+                        .head_safety_status()
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    let attestation = match head_safety_status {
+                        HeadSafetyStatus::Safe(_) => chain
+                            .produce_unaggregated_attestation(query.slot, query.committee_index),
+                        HeadSafetyStatus::Unsafe(_) if query.allow_optimistic => chain
+                            .produce_unaggregated_attestation(query.slot, query.committee_index),
+                        HeadSafetyStatus::Unsafe(_) => chain
+                            .produce_unaggregated_attestation_for_verified_ancestor(
+                                query.slot,
+                                query.committee_index,
+                            ),
+                        HeadSafetyStatus::Invalid(hash) => {
+                            return Err(warp_utils::reject::custom_server_error(format!(
+                                "the head block has an invalid payload {:?}, this may be unrecoverable",
+                                hash
+                            )));
+                        }
+                    };
+
+                    attestation
                         .map(|attestation| attestation.data)
                         .map(api_types::GenericResponse::from)
                         .map_err(warp_utils::reject::beacon_chain_error)
@@ -1920,7 +2241,6 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(warp::query::<api_types::ValidatorAggregateAttestationQuery>())
         .and(not_while_syncing_filter.clone())
-        .and(only_with_safe_head.clone())
         .and(chain_filter.clone())
         .and_then(
             |query: api_types::ValidatorAggregateAttestationQuery, chain: Arc<BeaconChain<T>>| {
@@ -1984,6 +2304,50 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST validator/liveness/{epoch}
+    let post_validator_liveness_epoch = eth1_v1
+        .and(warp::path("validator"))
+        .and(warp::path("liveness"))
+        .and(warp::path::param::<Epoch>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid epoch".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |epoch: Epoch, indices: api_types::ValidatorIndexData, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    // Ensure the request is for either the current, previous or next epoch.
+                    let current_epoch = chain
+                        .epoch()
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    let prev_epoch = current_epoch.saturating_sub(Epoch::new(1));
+                    let next_epoch = current_epoch.saturating_add(Epoch::new(1));
+
+                    if epoch < prev_epoch || epoch > next_epoch {
+                        return Err(warp_utils::reject::custom_bad_request(format!(
+                            "request epoch {} is more than one epoch from the current epoch {}",
+                            epoch, current_epoch
+                        )));
+                    }
+
+                    let liveness: Vec<api_types::StandardLivenessResponseData> = indices
+                        .0
+                        .iter()
+                        .cloned()
+                        .map(|index| {
+                            let is_live = chain.validator_seen_at_epoch(index as usize, epoch);
+                            api_types::StandardLivenessResponseData { index, is_live }
+                        })
+                        .collect();
+
+                    Ok(api_types::GenericResponse::from(liveness))
+                })
+            },
+        );
+
     // GET validator/sync_committee_contribution
     let get_validator_sync_committee_contribution = eth1_v1
         .and(warp::path("validator"))
@@ -2181,7 +2545,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("sync_committee_subscriptions"))
         .and(warp::path::end())
         .and(warp::body::json())
-        .and(network_tx_filter)
+        .and(network_tx_filter.clone())
         .and(chain_filter.clone())
         .and_then(
             |subscriptions: Vec<types::SyncCommitteeSubscription>,
@@ -2207,6 +2571,66 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST validator/prepare_beacon_proposer
+    let post_validator_prepare_beacon_proposer =
+        eth1_v1
+            .and(warp::path("validator"))
+            .and(warp::path("prepare_beacon_proposer"))
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and(chain_filter.clone())
+            .and_then(
+                |preparation_data: Vec<types::ProposerPreparationData>,
+                 chain: Arc<BeaconChain<T>>| async move {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+                    let current_epoch = chain
+                        .epoch()
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+                    execution_layer
+                        .update_proposer_preparation(current_epoch, &preparation_data)
+                        .await;
+
+                    Ok::<_, warp::Rejection>(warp::reply::json(&()))
+                },
+            );
+
+    // POST validator/register_validator
+    let post_validator_register_validator =
+        eth1_v1
+            .and(warp::path("validator"))
+            .and(warp::path("register_validator"))
+            .and(warp::path::end())
+            .and(warp::body::json())
+            .and(chain_filter.clone())
+            .and_then(
+                |registrations: Vec<SignedValidatorRegistrationData>,
+                 chain: Arc<BeaconChain<T>>| async move {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+                    execution_layer
+                        .register_validators(&registrations)
+                        .await
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "unable to register validators with the builder: {:?}",
+                                e
+                            ))
+                        })?;
+
+                    Ok::<_, warp::Rejection>(warp::reply::json(&()))
+                },
+            );
+
     // POST lighthouse/liveness
     let post_lighthouse_liveness = warp::path("lighthouse")
         .and(warp::path("liveness"))
@@ -2262,6 +2686,183 @@ pub fn serve<T: BeaconChainTypes>(
             })
         });
 
+    // GET lighthouse/health/live
+    //
+    // Responds as long as the server's event loop is responsive, for use as a liveness probe.
+    let get_lighthouse_health_live = warp::path("lighthouse")
+        .and(warp::path("health"))
+        .and(warp::path("live"))
+        .and(warp::path::end())
+        .and_then(|| async {
+            Ok::<_, warp::Rejection>(warp::reply::with_status(
+                warp::reply(),
+                warp::http::StatusCode::OK,
+            ))
+        });
+
+    // GET lighthouse/health/ready
+    //
+    // Responds with `200 OK` if the node is synced, has an online execution engine (if one is
+    // configured) and has enough peers, or `503 Service Unavailable` otherwise, for use as a
+    // readiness probe.
+    let ready_min_peers = config.ready_min_peers;
+    let get_lighthouse_health_ready =
+        warp::path("lighthouse")
+            .and(warp::path("health"))
+            .and(warp::path("ready"))
+            .and(warp::path::end())
+            .and(network_globals.clone())
+            .and(chain_filter.clone())
+            .and_then(
+                move |network_globals: Arc<NetworkGlobals<T::EthSpec>>,
+                      chain: Arc<BeaconChain<T>>| {
+                    async move {
+                        let synced = network_globals.sync_state.read().is_synced();
+                        let execution_layer_online = chain
+                            .execution_layer
+                            .as_ref()
+                            .map(|execution_layer| !execution_layer.is_offline());
+                        let connected_peers = network_globals.connected_peers();
+
+                        let ready = synced
+                            && execution_layer_online.unwrap_or(true)
+                            && connected_peers >= ready_min_peers;
+                        let status = if ready {
+                            warp::http::StatusCode::OK
+                        } else {
+                            warp::http::StatusCode::SERVICE_UNAVAILABLE
+                        };
+
+                        let readiness = eth2::lighthouse::Readiness {
+                            ready,
+                            synced,
+                            execution_layer_online,
+                            connected_peers,
+                            min_peers: ready_min_peers,
+                        };
+                        Ok::<_, warp::Rejection>(warp::reply::with_status(
+                            warp::reply::json(&api_types::GenericResponse::from(readiness)),
+                            status,
+                        ))
+                    }
+                },
+            );
+
+    // GET lighthouse/ui/health
+    let get_lighthouse_ui_health = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(network_globals.clone())
+        .and(chain_filter.clone())
+        .and_then(
+            |network_globals: Arc<NetworkGlobals<T::EthSpec>>, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    ui::get_health(&network_globals, &chain).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // POST lighthouse/ui/validator_metrics
+    let post_lighthouse_ui_validator_metrics = warp::path("lighthouse")
+        .and(warp::path("ui"))
+        .and(warp::path("validator_metrics"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(chain_filter.clone())
+        .and_then(
+            |request_data: eth2::lighthouse::ValidatorMetricsRequestData,
+             chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    Ok(api_types::GenericResponse::from(
+                        ui::post_validator_metrics(request_data, &chain),
+                    ))
+                })
+            },
+        );
+
+    // GET lighthouse/operation_pool
+    let get_lighthouse_operation_pool = warp::path("lighthouse")
+        .and(warp::path("operation_pool"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                op_pool::get_summary(&chain).map(api_types::GenericResponse::from)
+            })
+        });
+
+    // GET lighthouse/slasher/status
+    let get_lighthouse_slasher_status = warp::path("lighthouse")
+        .and(warp::path("slasher"))
+        .and(warp::path("status"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                slasher::status(&chain).map(api_types::GenericResponse::from)
+            })
+        });
+
+    // GET lighthouse/operation_pool/attestations/{slot}
+    let get_lighthouse_operation_pool_attestations = warp::path("lighthouse")
+        .and(warp::path("operation_pool"))
+        .and(warp::path("attestations"))
+        .and(warp::path::param::<Slot>().or_else(|_| async {
+            Err(warp_utils::reject::custom_bad_request(
+                "Invalid slot".to_string(),
+            ))
+        }))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(|slot: Slot, chain: Arc<BeaconChain<T>>| {
+            blocking_json_task(move || {
+                Ok(api_types::GenericResponse::from(
+                    op_pool::get_attestations_by_committee(slot, &chain),
+                ))
+            })
+        });
+
+    // GET lighthouse/analysis/block_packing/{start_epoch}/{end_epoch}
+    let get_lighthouse_block_packing_efficiency = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("block_packing"))
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::param::<Epoch>())
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and_then(
+            |start_epoch: Epoch, end_epoch: Epoch, chain: Arc<BeaconChain<T>>| {
+                blocking_json_task(move || {
+                    block_packing_efficiency::get_block_packing_efficiency(
+                        start_epoch,
+                        end_epoch,
+                        &chain,
+                    )
+                    .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/validators/{validator_id}/activation_eta
+    let get_lighthouse_validator_activation_eta = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<ValidatorId>())
+        .and(warp::path("activation_eta"))
+        .and(warp::path::end())
+        .and(chain_filter.clone())
+        .and(eth1_service_opt_filter)
+        .and_then(
+            |validator_id: ValidatorId,
+             chain: Arc<BeaconChain<T>>,
+             eth1_service: Option<eth1::Service>| {
+                blocking_json_task(move || {
+                    activation_eta::get_activation_eta(&validator_id, &chain, eth1_service)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/syncing
     let get_lighthouse_syncing = warp::path("lighthouse")
         .and(warp::path("syncing"))
@@ -2298,13 +2899,15 @@ pub fn serve<T: BeaconChainTypes>(
         .and(network_globals.clone())
         .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
             blocking_json_task(move || {
-                Ok(network_globals
-                    .peers
-                    .read()
+                let peers = network_globals.peers.read();
+                let totals = peers.first_message_delivery_totals();
+                Ok(peers
                     .peers()
                     .map(|(peer_id, peer_info)| eth2::lighthouse::Peer {
                         peer_id: peer_id.to_string(),
                         peer_info: peer_info.clone(),
+                        first_message_delivery_share: peers
+                            .first_message_delivery_share(peer_id, &totals),
                     })
                     .collect::<Vec<_>>())
             })
@@ -2318,13 +2921,15 @@ pub fn serve<T: BeaconChainTypes>(
         .and(network_globals)
         .and_then(|network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
             blocking_json_task(move || {
-                Ok(network_globals
-                    .peers
-                    .read()
+                let peers = network_globals.peers.read();
+                let totals = peers.first_message_delivery_totals();
+                Ok(peers
                     .connected_peers()
                     .map(|(peer_id, peer_info)| eth2::lighthouse::Peer {
                         peer_id: peer_id.to_string(),
                         peer_info: peer_info.clone(),
+                        first_message_delivery_share: peers
+                            .first_message_delivery_share(peer_id, &totals),
                     })
                     .collect::<Vec<_>>())
             })
@@ -2459,7 +3064,9 @@ pub fn serve<T: BeaconChainTypes>(
                 Response::builder()
                     .status(200)
                     .header("Content-Type", "application/ssz")
-                    .body(state.as_ssz_bytes())
+                    .body(warp::hyper::Body::wrap_stream(
+                        ssz_stream::ssz_state_stream(Arc::new(state)),
+                    ))
                     .map_err(|e| {
                         warp_utils::reject::custom_server_error(format!(
                             "failed to create response: {}",
@@ -2531,66 +3138,115 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    let backfill_path = warp::path("lighthouse")
+        .and(warp::path("sync"))
+        .and(warp::path("backfill"));
+
+    // POST lighthouse/sync/backfill/pause
+    let post_lighthouse_sync_backfill_pause = backfill_path
+        .and(warp::path("pause"))
+        .and(warp::path::end())
+        .and(network_tx_filter.clone())
+        .and_then(|network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+            blocking_json_task(move || {
+                publish_network_message(
+                    &network_tx,
+                    NetworkMessage::SetBackfillPaused { paused: true },
+                )?;
+                Ok("success")
+            })
+        });
+
+    // POST lighthouse/sync/backfill/resume
+    let post_lighthouse_sync_backfill_resume = backfill_path
+        .and(warp::path("resume"))
+        .and(warp::path::end())
+        .and(network_tx_filter)
+        .and_then(|network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+            blocking_json_task(move || {
+                publish_network_message(
+                    &network_tx,
+                    NetworkMessage::SetBackfillPaused { paused: false },
+                )?;
+                Ok("success")
+            })
+        });
+
+    // GET lighthouse/logging
+    let get_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(log_levels_filter.clone())
+        .and_then(|log_levels: logging::LogLevels| {
+            blocking_json_task(move || {
+                Ok(api_types::GenericResponse::from(log_levels::overrides(
+                    &log_levels,
+                )))
+            })
+        });
+
+    // POST lighthouse/logging
+    let post_lighthouse_logging = warp::path("lighthouse")
+        .and(warp::path("logging"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(log_levels_filter)
+        .and(log_filter.clone())
+        .and_then(
+            |request: eth2::lighthouse::LogLevelRequest,
+             log_levels: logging::LogLevels,
+             log: Logger| {
+                blocking_json_task(move || {
+                    log_levels::update(&log_levels, request, &log)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     let get_events = eth1_v1
         .and(warp::path("events"))
         .and(warp::path::end())
         .and(warp::query::<api_types::EventQuery>())
+        .and(warp::header::optional::<u64>("last-event-id"))
         .and(chain_filter)
         .and_then(
-            |topics: api_types::EventQuery, chain: Arc<BeaconChain<T>>| {
+            |topics: api_types::EventQuery,
+             last_event_id: Option<u64>,
+             chain: Arc<BeaconChain<T>>| {
                 blocking_task(move || {
-                    // for each topic subscribed spawn a new subscription
-                    let mut receivers = Vec::with_capacity(topics.topics.0.len());
-
-                    if let Some(event_handler) = chain.event_handler.as_ref() {
-                        for topic in topics.topics.0.clone() {
-                            let receiver = match topic {
-                                api_types::EventTopic::Head => event_handler.subscribe_head(),
-                                api_types::EventTopic::Block => event_handler.subscribe_block(),
-                                api_types::EventTopic::Attestation => {
-                                    event_handler.subscribe_attestation()
-                                }
-                                api_types::EventTopic::VoluntaryExit => {
-                                    event_handler.subscribe_exit()
-                                }
-                                api_types::EventTopic::FinalizedCheckpoint => {
-                                    event_handler.subscribe_finalized()
-                                }
-                                api_types::EventTopic::ChainReorg => {
-                                    event_handler.subscribe_reorgs()
-                                }
-                                api_types::EventTopic::ContributionAndProof => {
-                                    event_handler.subscribe_contributions()
-                                }
-                                api_types::EventTopic::LateHead => {
-                                    event_handler.subscribe_late_head()
-                                }
-                            };
-
-                            receivers.push(BroadcastStream::new(receiver).map(|msg| {
-                                match msg {
-                                    Ok(data) => Event::default()
-                                        .event(data.topic_name())
-                                        .json_data(data)
-                                        .map_err(|e| {
-                                            warp_utils::reject::server_sent_event_error(format!(
-                                                "{:?}",
-                                                e
-                                            ))
-                                        }),
-                                    Err(e) => Err(warp_utils::reject::server_sent_event_error(
-                                        format!("{:?}", e),
-                                    )),
-                                }
-                            }));
-                        }
-                    } else {
-                        return Err(warp_utils::reject::custom_server_error(
+                    let event_handler = chain.event_handler.as_ref().ok_or_else(|| {
+                        warp_utils::reject::custom_server_error(
                             "event handler was not initialized".to_string(),
-                        ));
-                    }
+                        )
+                    })?;
+
+                    // Subscribe to each topic, and replay the events missed since the client's
+                    // last event if it is reconnecting.
+                    let (replayed, receivers) =
+                        event_handler.subscribe_with_replay(&topics.topics.0, last_event_id);
+
+                    let to_sse_event = |event: api_types::IdentifiedEvent<T::EthSpec>| {
+                        Event::default()
+                            .id(event.id.to_string())
+                            .event(event.event.topic_name())
+                            .json_data(event.event)
+                            .map_err(|e| {
+                                warp_utils::reject::server_sent_event_error(format!("{:?}", e))
+                            })
+                    };
+
+                    let live = receivers.into_iter().map(|receiver| {
+                        BroadcastStream::new(receiver).map(move |msg| match msg {
+                            Ok(event) => to_sse_event(event),
+                            Err(e) => Err(warp_utils::reject::server_sent_event_error(format!(
+                                "{:?}",
+                                e
+                            ))),
+                        })
+                    });
 
-                    let s = futures::stream::select_all(receivers);
+                    let s = futures::stream::iter(replayed.into_iter().map(to_sse_event))
+                        .chain(futures::stream::select_all(live));
 
                     Ok::<_, warp::Rejection>(warp::sse::reply(warp::sse::keep_alive().stream(s)))
                 })
@@ -2598,81 +3254,123 @@ pub fn serve<T: BeaconChainTypes>(
         );
 
     // Define the ultimate set of routes that will be provided to the server.
-    let routes = warp::get()
+    // Wait for capacity to serve each request, according to its route.
+    let concurrency_limit = concurrency_limit_filter(ConcurrencyLimits::new(
+        config.max_concurrent_requests,
+        config.max_concurrent_priority_requests,
+    ));
+
+    let routes = concurrency_limit
         .and(
-            get_beacon_genesis
-                .boxed()
-                .or(get_beacon_state_root.boxed())
-                .or(get_beacon_state_fork.boxed())
-                .or(get_beacon_state_finality_checkpoints.boxed())
-                .or(get_beacon_state_validator_balances.boxed())
-                .or(get_beacon_state_validators.boxed())
-                .or(get_beacon_state_validators_id.boxed())
-                .or(get_beacon_state_committees.boxed())
-                .or(get_beacon_state_sync_committees.boxed())
-                .or(get_beacon_headers.boxed())
-                .or(get_beacon_headers_block_id.boxed())
-                .or(get_beacon_block.boxed())
-                .or(get_beacon_block_attestations.boxed())
-                .or(get_beacon_block_root.boxed())
-                .or(get_beacon_pool_attestations.boxed())
-                .or(get_beacon_pool_attester_slashings.boxed())
-                .or(get_beacon_pool_proposer_slashings.boxed())
-                .or(get_beacon_pool_voluntary_exits.boxed())
-                .or(get_config_fork_schedule.boxed())
-                .or(get_config_spec.boxed())
-                .or(get_config_deposit_contract.boxed())
-                .or(get_debug_beacon_states.boxed())
-                .or(get_debug_beacon_heads.boxed())
-                .or(get_node_identity.boxed())
-                .or(get_node_version.boxed())
-                .or(get_node_syncing.boxed())
-                .or(get_node_health.boxed())
-                .or(get_node_peers_by_id.boxed())
-                .or(get_node_peers.boxed())
-                .or(get_node_peer_count.boxed())
-                .or(get_validator_duties_proposer.boxed())
-                .or(get_validator_blocks.boxed())
-                .or(get_validator_attestation_data.boxed())
-                .or(get_validator_aggregate_attestation.boxed())
-                .or(get_validator_sync_committee_contribution.boxed())
-                .or(get_lighthouse_health.boxed())
-                .or(get_lighthouse_syncing.boxed())
-                .or(get_lighthouse_nat.boxed())
-                .or(get_lighthouse_peers.boxed())
-                .or(get_lighthouse_peers_connected.boxed())
-                .or(get_lighthouse_proto_array.boxed())
-                .or(get_lighthouse_validator_inclusion_global.boxed())
-                .or(get_lighthouse_validator_inclusion.boxed())
-                .or(get_lighthouse_eth1_syncing.boxed())
-                .or(get_lighthouse_eth1_block_cache.boxed())
-                .or(get_lighthouse_eth1_deposit_cache.boxed())
-                .or(get_lighthouse_beacon_states_ssz.boxed())
-                .or(get_lighthouse_staking.boxed())
-                .or(get_lighthouse_database_info.boxed())
-                .or(get_events.boxed()),
+            warp::get()
+                .and(
+                    get_beacon_genesis
+                        .boxed()
+                        .or(get_beacon_state_root.boxed())
+                        .or(get_beacon_state_fork.boxed())
+                        .or(get_beacon_state_finality_checkpoints.boxed())
+                        .or(get_beacon_state_validator_balances.boxed())
+                        .or(get_beacon_state_validators.boxed())
+                        .or(get_beacon_state_validators_id.boxed())
+                        .or(get_beacon_state_committees.boxed())
+                        .or(get_beacon_state_sync_committees.boxed())
+                        .or(get_beacon_headers.boxed())
+                        .or(get_beacon_headers_block_id.boxed())
+                        .or(get_beacon_block.boxed())
+                        .or(get_beacon_block_attestations.boxed())
+                        .or(get_beacon_rewards_blocks.boxed())
+                        .or(get_beacon_light_client_bootstrap.boxed())
+                        .or(get_beacon_light_client_updates.boxed())
+                        .or(get_beacon_light_client_finality_update.boxed())
+                        .or(get_beacon_light_client_optimistic_update.boxed())
+                        .or(get_beacon_block_root.boxed())
+                        .or(get_beacon_pool_attestations.boxed())
+                        .or(get_beacon_pool_attester_slashings.boxed())
+                        .or(get_beacon_pool_proposer_slashings.boxed())
+                        .or(get_beacon_pool_voluntary_exits.boxed())
+                        .or(get_config_fork_schedule.boxed())
+                        .or(get_config_spec.boxed())
+                        .or(get_config_deposit_contract.boxed())
+                        .or(get_debug_beacon_states.boxed())
+                        .or(get_debug_beacon_heads.boxed())
+                        .or(get_debug_fork_choice.boxed())
+                        .or(get_node_identity.boxed())
+                        .or(get_node_version.boxed())
+                        .or(get_node_syncing.boxed())
+                        .or(get_node_health.boxed())
+                        .or(get_node_peers_by_id.boxed())
+                        .or(get_node_peers.boxed())
+                        .or(get_node_peer_count.boxed())
+                        .or(get_validator_duties_proposer.boxed())
+                        .or(get_validator_blocks.boxed())
+                        .or(get_validator_blinded_blocks.boxed())
+                        .or(get_validator_attestation_data.boxed())
+                        .or(get_validator_aggregate_attestation.boxed())
+                        .or(get_validator_sync_committee_contribution.boxed())
+                        .or(get_lighthouse_health.boxed())
+                        .or(get_lighthouse_health_live.boxed())
+                        .or(get_lighthouse_health_ready.boxed())
+                        .or(get_lighthouse_ui_health.boxed())
+                        .or(get_lighthouse_operation_pool.boxed())
+                        .or(get_lighthouse_operation_pool_attestations.boxed())
+                        .or(get_lighthouse_slasher_status.boxed())
+                        .or(get_lighthouse_block_packing_efficiency.boxed())
+                        .or(get_lighthouse_validator_activation_eta.boxed())
+                        .or(get_lighthouse_syncing.boxed())
+                        .or(get_lighthouse_nat.boxed())
+                        .or(get_lighthouse_peers.boxed())
+                        .or(get_lighthouse_peers_connected.boxed())
+                        .or(get_lighthouse_proto_array.boxed())
+                        .or(get_lighthouse_validator_inclusion_global.boxed())
+                        .or(get_lighthouse_validator_inclusion.boxed())
+                        .or(get_lighthouse_eth1_syncing.boxed())
+                        .or(get_lighthouse_eth1_block_cache.boxed())
+                        .or(get_lighthouse_eth1_deposit_cache.boxed())
+                        .or(get_lighthouse_beacon_states_ssz.boxed())
+                        .or(get_lighthouse_staking.boxed())
+                        .or(get_lighthouse_database_info.boxed())
+                        .or(get_lighthouse_logging.boxed())
+                        .or(get_events.boxed()),
+                )
+                .or(warp::post()
+                    .and(body_size_limit_filter(config.max_body_size))
+                    .and(
+                        post_beacon_blocks
+                            .boxed()
+                            .or(post_beacon_blinded_blocks.boxed())
+                            .or(post_beacon_state_validators.boxed())
+                            .or(post_beacon_rewards_sync_committee.boxed())
+                            .or(post_beacon_rewards_attestations.boxed())
+                            .or(post_beacon_pool_attestations.boxed())
+                            .or(post_beacon_pool_attester_slashings.boxed())
+                            .or(post_beacon_pool_proposer_slashings.boxed())
+                            .or(post_beacon_pool_voluntary_exits.boxed())
+                            .or(post_beacon_pool_sync_committees.boxed())
+                            .or(post_validator_duties_attester.boxed())
+                            .or(post_validator_duties_sync.boxed())
+                            .or(post_validator_liveness_epoch.boxed())
+                            .or(post_validator_aggregate_and_proofs.boxed())
+                            .or(post_validator_contribution_and_proofs.boxed())
+                            .or(post_validator_beacon_committee_subscriptions.boxed())
+                            .or(post_validator_sync_committee_subscriptions.boxed())
+                            .or(post_validator_prepare_beacon_proposer.boxed())
+                            .or(post_validator_register_validator.boxed())
+                            .or(post_lighthouse_liveness.boxed())
+                            .or(post_lighthouse_ui_validator_metrics.boxed())
+                            .or(post_lighthouse_database_reconstruct.boxed())
+                            .or(post_lighthouse_database_historical_blocks.boxed())
+                            .or(post_lighthouse_sync_backfill_pause.boxed())
+                            .or(post_lighthouse_sync_backfill_resume.boxed())
+                            .or(post_lighthouse_logging.boxed()),
+                    )),
         )
-        .or(warp::post().and(
-            post_beacon_blocks
-                .boxed()
-                .or(post_beacon_pool_attestations.boxed())
-                .or(post_beacon_pool_attester_slashings.boxed())
-                .or(post_beacon_pool_proposer_slashings.boxed())
-                .or(post_beacon_pool_voluntary_exits.boxed())
-                .or(post_beacon_pool_sync_committees.boxed())
-                .or(post_validator_duties_attester.boxed())
-                .or(post_validator_duties_sync.boxed())
-                .or(post_validator_aggregate_and_proofs.boxed())
-                .or(post_validator_contribution_and_proofs.boxed())
-                .or(post_validator_beacon_committee_subscriptions.boxed())
-                .or(post_validator_sync_committee_subscriptions.boxed())
-                .or(post_lighthouse_liveness.boxed())
-                .or(post_lighthouse_database_reconstruct.boxed())
-                .or(post_lighthouse_database_historical_blocks.boxed()),
-        ))
+        // Release the concurrency permit once the response has been produced.
+        .map(|_permit, reply| reply)
         .recover(warp_utils::reject::handle_rejection)
         .with(slog_logging(log.clone()))
         .with(prometheus_metrics())
+        // Emit a `tracing` span for each request.
+        .with(warp::trace::request())
         // Add a `Server` header.
         .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
         .with(cors_builder.build());
@@ -2710,6 +3408,66 @@ pub fn serve<T: BeaconChainTypes>(
     Ok(http_server)
 }
 
+/// Returns the validators in `state`, filtered by `ids` and `statuses` when they are provided.
+///
+/// Shared by the `GET` and `POST` variants of `beacon/states/{state_id}/validators`.
+fn filter_state_validators<E: EthSpec>(
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+    ids: Option<&[ValidatorId]>,
+    statuses: Option<&[api_types::ValidatorStatus]>,
+) -> Vec<api_types::ValidatorData> {
+    let epoch = state.current_epoch();
+    let far_future_epoch = spec.far_future_epoch;
+
+    // Resolve the ids once, so that the cost of filtering does not grow with the product of the
+    // number of ids and the number of validators.
+    let id_sets = ids.map(|ids| {
+        let mut indices = HashSet::new();
+        let mut pubkeys = HashSet::new();
+        for id in ids {
+            match id {
+                ValidatorId::PublicKey(pubkey) => pubkeys.insert(pubkey),
+                ValidatorId::Index(index) => indices.insert(*index),
+            };
+        }
+        (indices, pubkeys)
+    });
+
+    state
+        .validators()
+        .iter()
+        .zip(state.balances().iter())
+        .enumerate()
+        // filter by validator id(s) if provided
+        .filter(|(index, (validator, _))| {
+            id_sets.as_ref().map_or(true, |(indices, pubkeys)| {
+                indices.contains(&(*index as u64)) || pubkeys.contains(&validator.pubkey)
+            })
+        })
+        // filter by status(es) if provided and map the result
+        .filter_map(|(index, (validator, balance))| {
+            let status =
+                api_types::ValidatorStatus::from_validator(validator, epoch, far_future_epoch);
+
+            let status_matches = statuses.map_or(true, |statuses| {
+                statuses.contains(&status) || statuses.contains(&status.superstatus())
+            });
+
+            if status_matches {
+                Some(api_types::ValidatorData {
+                    index: index as u64,
+                    balance: *balance,
+                    status,
+                    validator: validator.clone(),
+                })
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
 /// Publish a message to the libp2p pubsub network.
 fn publish_pubsub_message<T: EthSpec>(
     network_tx: &UnboundedSender<NetworkMessage<T>>,
@@ -2735,3 +3493,91 @@ fn publish_network_message<T: EthSpec>(
         ))
     })
 }
+
+/// Publish `block` to the network and import it, logging if it was broadcast late.
+///
+/// Shared by the endpoints which accept full and blinded blocks.
+fn publish_block<T: BeaconChainTypes>(
+    block: SignedBeaconBlock<T::EthSpec>,
+    chain: Arc<BeaconChain<T>>,
+    network_tx: &UnboundedSender<NetworkMessage<T::EthSpec>>,
+    log: Logger,
+) -> Result<(), warp::Rejection> {
+    let seen_timestamp = timestamp_now();
+
+    // Send the block, regardless of whether or not it is valid. The API
+    // specification is very clear that this is the desired behaviour.
+    publish_pubsub_message(
+        network_tx,
+        PubsubMessage::BeaconBlock(Box::new(block.clone())),
+    )?;
+
+    // Determine the delay after the start of the slot, register it with metrics.
+    let delay = get_block_delay_ms(seen_timestamp, block.message(), &chain.slot_clock);
+    metrics::observe_duration(&metrics::HTTP_API_BLOCK_BROADCAST_DELAY_TIMES, delay);
+
+    match chain.process_block(block.clone()) {
+        Ok(root) => {
+            info!(
+                log,
+                "Valid block from HTTP API";
+                "block_delay" => ?delay,
+                "root" => format!("{}", root),
+                "proposer_index" => block.message().proposer_index(),
+                "slot" => block.slot(),
+            );
+
+            // Notify the validator monitor.
+            chain.validator_monitor.read().register_api_block(
+                seen_timestamp,
+                block.message(),
+                root,
+                &chain.slot_clock,
+            );
+
+            // Update the head since it's likely this block will become the new
+            // head.
+            chain
+                .fork_choice()
+                .map_err(warp_utils::reject::beacon_chain_error)?;
+
+            // Perform some logging to inform users if their blocks are being produced
+            // late.
+            //
+            // Check to see the thresholds are non-zero to avoid logging errors with small
+            // slot times (e.g., during testing)
+            let crit_threshold = chain.slot_clock.unagg_attestation_production_delay();
+            let error_threshold = crit_threshold / 2;
+            if delay >= crit_threshold {
+                crit!(
+                    log,
+                    "Block was broadcast too late";
+                    "msg" => "system may be overloaded, block likely to be orphaned",
+                    "delay_ms" => delay.as_millis(),
+                    "slot" => block.slot(),
+                    "root" => ?root,
+                )
+            } else if delay >= error_threshold {
+                error!(
+                    log,
+                    "Block broadcast was delayed";
+                    "msg" => "system may be overloaded, block may be orphaned",
+                    "delay_ms" => delay.as_millis(),
+                    "slot" => block.slot(),
+                    "root" => ?root,
+                )
+            }
+
+            Ok(())
+        }
+        Err(e) => {
+            let msg = format!("{:?}", e);
+            error!(
+                log,
+                "Invalid block provided to HTTP API";
+                "reason" => &msg
+            );
+            Err(warp_utils::reject::broadcast_without_import(msg))
+        }
+    }
+}
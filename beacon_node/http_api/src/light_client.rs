@@ -0,0 +1,98 @@
+//! Handlers for the standard `beacon/light_client` endpoints.
+
+use crate::block_id::BlockId;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::types::ForkVersionedResponse;
+use types::{
+    LightClientBootstrap, LightClientFinalityUpdate, LightClientOptimisticUpdate,
+    LightClientUpdate, Slot,
+};
+
+/// Handles a request for the data required to start syncing a light client from a block.
+pub fn bootstrap<T: BeaconChainTypes>(
+    block_id: BlockId,
+    chain: &BeaconChain<T>,
+) -> Result<ForkVersionedResponse<LightClientBootstrap<T::EthSpec>>, warp::Rejection> {
+    check_enabled(chain)?;
+
+    let block_root = block_id.root(chain)?;
+    let bootstrap = chain
+        .get_light_client_bootstrap(&block_root)
+        .map_err(warp_utils::reject::beacon_chain_error)?
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(format!(
+                "no light client bootstrap available for block {:?}",
+                block_root
+            ))
+        })?;
+
+    Ok(versioned(chain, bootstrap.header.beacon.slot, bootstrap))
+}
+
+/// Handles a request for the best updates for a range of sync committee periods.
+pub fn updates<T: BeaconChainTypes>(
+    start_period: u64,
+    count: u64,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<ForkVersionedResponse<LightClientUpdate<T::EthSpec>>>, warp::Rejection> {
+    check_enabled(chain)?;
+
+    Ok(chain
+        .get_light_client_updates(start_period, count)
+        .map_err(warp_utils::reject::beacon_chain_error)?
+        .into_iter()
+        .map(|update| versioned(chain, update.attested_header.beacon.slot, update))
+        .collect())
+}
+
+/// Handles a request for the latest finality update.
+pub fn finality_update<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<ForkVersionedResponse<LightClientFinalityUpdate<T::EthSpec>>, warp::Rejection> {
+    check_enabled(chain)?;
+
+    let update = chain.latest_light_client_finality_update().ok_or_else(|| {
+        warp_utils::reject::custom_not_found("no light client finality update available".into())
+    })?;
+
+    Ok(versioned(chain, update.attested_header.beacon.slot, update))
+}
+
+/// Handles a request for the latest optimistic update.
+pub fn optimistic_update<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<ForkVersionedResponse<LightClientOptimisticUpdate<T::EthSpec>>, warp::Rejection> {
+    check_enabled(chain)?;
+
+    let update = chain
+        .latest_light_client_optimistic_update()
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(
+                "no light client optimistic update available".into(),
+            )
+        })?;
+
+    Ok(versioned(chain, update.attested_header.beacon.slot, update))
+}
+
+fn check_enabled<T: BeaconChainTypes>(chain: &BeaconChain<T>) -> Result<(), warp::Rejection> {
+    if chain.config.enable_light_client_server {
+        Ok(())
+    } else {
+        Err(warp_utils::reject::custom_not_found(
+            "light client server is disabled, enable it with --light-client-server".to_string(),
+        ))
+    }
+}
+
+/// Wraps `data` with the name of the fork at `slot`.
+fn versioned<T: BeaconChainTypes, D>(
+    chain: &BeaconChain<T>,
+    slot: Slot,
+    data: D,
+) -> ForkVersionedResponse<D> {
+    ForkVersionedResponse {
+        version: Some(chain.spec.fork_name_at_slot::<T::EthSpec>(slot)),
+        data,
+    }
+}
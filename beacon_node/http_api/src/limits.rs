@@ -0,0 +1,284 @@
+//! Limits on the requests served by the HTTP API.
+//!
+//! Requests are divided into two classes, each with its own concurrency limit:
+//!
+//! - *Priority* requests, which validators depend upon to produce blocks and attestations on
+//!   time.
+//! - *General* requests, which covers everything else.
+//!
+//! Keeping the classes separate ensures that an expensive workload on the general routes (e.g.,
+//! repeatedly downloading the validator set) cannot delay block proposals or attestations.
+//!
+//! The liveness and readiness probes are never limited, so that a busy node is not mistaken for
+//! an unresponsive one.
+
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use warp::{path::FullPath, Filter, Rejection};
+
+/// Routes which validators depend upon to perform their duties in a timely manner, matched
+/// exactly.
+const PRIORITY_PATHS: &[&str] = &[
+    // Block publication, `POST /eth/v1/beacon/blocks`.
+    "/eth/v1/beacon/blocks",
+    "/eth/v1/beacon/blinded_blocks",
+    "/eth/v1/beacon/pool/attestations",
+    "/eth/v1/beacon/pool/sync_committees",
+    "/eth/v1/validator/aggregate_and_proofs",
+    "/eth/v1/validator/contribution_and_proofs",
+];
+
+/// Routes which validators depend upon to perform their duties in a timely manner, matched by
+/// prefix since they take path parameters.
+const PRIORITY_PATH_PREFIXES: &[&str] = &[
+    "/eth/v1/validator/attestation_data",
+    "/eth/v1/validator/aggregate_attestation",
+    "/eth/v1/validator/sync_committee_contribution",
+    "/eth/v1/validator/duties/",
+    "/eth/v1/validator/blocks/",
+    "/eth/v2/validator/blocks/",
+    "/eth/v1/validator/blinded_blocks/",
+];
+
+/// Returns `true` if `path` is a route that validators depend upon to perform their duties in a
+/// timely manner.
+fn is_priority_path(path: &str) -> bool {
+    PRIORITY_PATHS.contains(&path)
+        || PRIORITY_PATH_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+}
+
+/// Returns `true` if `path` is a liveness or readiness probe, e.g. `/lighthouse/health/live`.
+fn is_probe_path(path: &str) -> bool {
+    path.starts_with("/lighthouse/health/")
+}
+
+/// The semaphores which limit the number of concurrent requests for each class of route.
+///
+/// A `None` value indicates that the class is unlimited.
+#[derive(Clone)]
+pub struct ConcurrencyLimits {
+    general: Option<Arc<Semaphore>>,
+    priority: Option<Arc<Semaphore>>,
+}
+
+impl ConcurrencyLimits {
+    pub fn new(max_general: Option<usize>, max_priority: Option<usize>) -> Self {
+        Self {
+            general: max_general.map(|n| Arc::new(Semaphore::new(n))),
+            priority: max_priority.map(|n| Arc::new(Semaphore::new(n))),
+        }
+    }
+
+    /// Waits until a request for `path` may be served, returning a permit which must be held
+    /// until the response has been produced.
+    ///
+    /// Returns `Ok(None)` if the class of `path` is unlimited.
+    async fn acquire(&self, path: &str) -> Result<Option<OwnedSemaphorePermit>, String> {
+        let semaphore = if is_probe_path(path) {
+            return Ok(None);
+        } else if is_priority_path(path) {
+            &self.priority
+        } else {
+            &self.general
+        };
+
+        match semaphore {
+            Some(semaphore) => semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .map(Some)
+                .map_err(|e| format!("unable to acquire request permit: {:?}", e)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Creates a `warp` filter which waits until the request may be served within the given
+/// `limits`.
+///
+/// The extracted permit must be held until the response has been produced.
+pub fn concurrency_limit_filter(
+    limits: ConcurrencyLimits,
+) -> impl Filter<Extract = (Option<OwnedSemaphorePermit>,), Error = Rejection> + Clone {
+    warp::path::full().and_then(move |path: FullPath| {
+        let limits = limits.clone();
+        async move {
+            limits
+                .acquire(path.as_str())
+                .await
+                .map_err(warp_utils::reject::custom_server_error)
+        }
+    })
+}
+
+/// Creates a `warp` filter which rejects any request which declares a body larger than
+/// `max_body_size` bytes.
+///
+/// Bodies which are streamed without a `content-length` (i.e., chunked) are also rejected, since
+/// their size is not known until they have been read in full.
+pub fn body_size_limit_filter(
+    max_body_size: u64,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::header::optional::<u64>("content-length")
+        .and(warp::header::optional::<String>("transfer-encoding"))
+        .and_then(
+            move |content_length: Option<u64>, transfer_encoding: Option<String>| async move {
+                match (content_length, transfer_encoding) {
+                    (Some(length), _) if length > max_body_size => {
+                        Err(warp_utils::reject::payload_too_large(format!(
+                            "request body of {} bytes exceeds the limit of {} bytes",
+                            length, max_body_size
+                        )))
+                    }
+                    (None, Some(_)) => Err(warp_utils::reject::length_required(
+                        "request body must declare a content-length".to_string(),
+                    )),
+                    _ => Ok(()),
+                }
+            },
+        )
+        .untuple_one()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const GENERAL_PATH: &str = "/eth/v1/beacon/states/head/validators";
+    const PRIORITY_PATH: &str = "/eth/v1/validator/attestation_data";
+    const PROBE_PATH: &str = "/lighthouse/health/live";
+
+    /// Returns the permit extracted for a request to `path`, or `None` if the request is still
+    /// waiting for a permit after a short timeout.
+    async fn acquire(
+        limits: &ConcurrencyLimits,
+        path: &str,
+    ) -> Option<Option<OwnedSemaphorePermit>> {
+        let filter = concurrency_limit_filter(limits.clone());
+        tokio::time::timeout(
+            Duration::from_millis(100),
+            warp::test::request().path(path).filter(&filter),
+        )
+        .await
+        .ok()
+        .map(|result| result.expect("filter should not reject"))
+    }
+
+    #[tokio::test]
+    async fn general_requests_wait_for_permit() {
+        let limits = ConcurrencyLimits::new(Some(1), Some(1));
+
+        let permit = acquire(&limits, GENERAL_PATH)
+            .await
+            .expect("first request should be served");
+        assert!(permit.is_some());
+        assert!(
+            acquire(&limits, GENERAL_PATH).await.is_none(),
+            "second request should wait"
+        );
+
+        // Other classes of request are not delayed by general requests.
+        assert!(acquire(&limits, PRIORITY_PATH).await.is_some());
+        assert!(acquire(&limits, PROBE_PATH).await.is_some());
+
+        drop(permit);
+        assert!(acquire(&limits, GENERAL_PATH).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn priority_requests_wait_for_permit() {
+        let limits = ConcurrencyLimits::new(Some(1), Some(1));
+
+        let permit = acquire(&limits, PRIORITY_PATH)
+            .await
+            .expect("first request should be served");
+        assert!(
+            acquire(&limits, PRIORITY_PATH).await.is_none(),
+            "second request should wait"
+        );
+        assert!(acquire(&limits, GENERAL_PATH).await.is_some());
+
+        drop(permit);
+        assert!(acquire(&limits, PRIORITY_PATH).await.is_some());
+    }
+
+    #[test]
+    fn priority_path_classification() {
+        let priority = [
+            "/eth/v1/beacon/blocks",
+            "/eth/v1/beacon/blinded_blocks",
+            "/eth/v1/beacon/pool/attestations",
+            "/eth/v1/beacon/pool/sync_committees",
+            "/eth/v1/validator/attestation_data",
+            "/eth/v1/validator/aggregate_attestation",
+            "/eth/v1/validator/aggregate_and_proofs",
+            "/eth/v1/validator/sync_committee_contribution",
+            "/eth/v1/validator/contribution_and_proofs",
+            "/eth/v1/validator/duties/attester/1",
+            "/eth/v1/validator/duties/proposer/1",
+            "/eth/v1/validator/duties/sync/1",
+            "/eth/v1/validator/blocks/1",
+            "/eth/v2/validator/blocks/1",
+            "/eth/v1/validator/blinded_blocks/1",
+        ];
+        for path in priority {
+            assert!(is_priority_path(path), "{} should be priority", path);
+        }
+
+        let general = [
+            GENERAL_PATH,
+            PROBE_PATH,
+            "/eth/v1/beacon/blocks/head",
+            "/eth/v2/beacon/blocks/head",
+            "/eth/v1/beacon/blinded_blocks/head",
+            "/eth/v1/beacon/pool/voluntary_exits",
+            "/eth/v1/validator/prepare_beacon_proposer",
+            "/eth/v1/node/syncing",
+        ];
+        for path in general {
+            assert!(!is_priority_path(path), "{} should be general", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn unlimited_requests_do_not_wait() {
+        let limits = ConcurrencyLimits::new(None, None);
+
+        let first = acquire(&limits, GENERAL_PATH).await;
+        assert!(matches!(first, Some(None)));
+        assert!(matches!(acquire(&limits, PRIORITY_PATH).await, Some(None)));
+    }
+
+    #[tokio::test]
+    async fn body_size_limit() {
+        let filter = body_size_limit_filter(4);
+
+        assert!(
+            warp::test::request()
+                .method("POST")
+                .body("1234")
+                .matches(&filter)
+                .await
+        );
+        assert!(
+            !warp::test::request()
+                .method("POST")
+                .body("12345")
+                .matches(&filter)
+                .await
+        );
+        assert!(
+            !warp::test::request()
+                .method("POST")
+                .header("transfer-encoding", "chunked")
+                .matches(&filter)
+                .await
+        );
+        // Requests without a body are accepted.
+        assert!(warp::test::request().method("POST").matches(&filter).await);
+    }
+}
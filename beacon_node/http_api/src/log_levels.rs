@@ -0,0 +1,46 @@
+use eth2::lighthouse::{LogLevelOverride, LogLevelRequest};
+use logging::{level_name, parse_level, LogLevels};
+use slog::{info, Logger};
+
+/// Returns the overrides of the configured log levels.
+pub fn overrides(log_levels: &LogLevels) -> Vec<LogLevelOverride> {
+    log_levels
+        .overrides()
+        .into_iter()
+        .map(|(module, level)| LogLevelOverride {
+            module,
+            level: level_name(level).to_string(),
+        })
+        .collect()
+}
+
+/// Apply `request`, returning the overrides which result.
+pub fn update(
+    log_levels: &LogLevels,
+    request: LogLevelRequest,
+    log: &Logger,
+) -> Result<Vec<LogLevelOverride>, warp::Rejection> {
+    match request.level {
+        Some(level) => {
+            let level = parse_level(&level).map_err(warp_utils::reject::custom_bad_request)?;
+            info!(
+                log,
+                "Overriding log level";
+                "module" => &request.module,
+                "level" => level_name(level),
+            );
+            log_levels.set(request.module, level);
+        }
+        None => {
+            if !log_levels.remove(&request.module) {
+                return Err(warp_utils::reject::custom_not_found(format!(
+                    "no log level override for module {:?}",
+                    request.module
+                )));
+            }
+            info!(log, "Restored configured log level"; "module" => &request.module);
+        }
+    }
+
+    Ok(overrides(log_levels))
+}
@@ -0,0 +1,153 @@
+//! Handlers for the `lighthouse/operation_pool` endpoints, which allow operators to check that the
+//! slashings and exits they have submitted are being held for inclusion in a block, and allow
+//! relays and builders to see which attestations the node could contribute to a block.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    CommitteeAttestations, OperationForecast, OperationPoolPruneStats, OperationPoolSizes,
+    OperationPoolSummary,
+};
+use operation_pool::InclusionForecast;
+use std::collections::{BTreeMap, HashSet};
+use types::{Attestation, Slot};
+
+/// Summarises the operation pool, forecasting the inclusion of its slashings and exits in a block
+/// produced upon the head.
+pub fn get_summary<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<OperationPoolSummary, warp::Rejection> {
+    let op_pool = &chain.op_pool;
+    let forecasts = chain
+        .with_head(|head| {
+            Ok::<_, beacon_chain::BeaconChainError>(
+                op_pool.forecast_slashings_and_exits(&head.beacon_state, &chain.spec),
+            )
+        })
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+    let proposer_slashings = forecasts
+        .proposer_slashings
+        .into_iter()
+        .map(|(slashing, forecast)| {
+            to_api_forecast(
+                vec![slashing.signed_header_1.message.proposer_index],
+                forecast,
+            )
+        })
+        .collect();
+
+    let attester_slashings = forecasts
+        .attester_slashings
+        .into_iter()
+        .map(|(slashing, forecast)| {
+            // Validators are only slashable if they attested to both conflicting attestations.
+            let indices_1 = slashing
+                .attestation_1
+                .attesting_indices
+                .iter()
+                .copied()
+                .collect::<HashSet<_>>();
+            let mut validator_indices = slashing
+                .attestation_2
+                .attesting_indices
+                .iter()
+                .copied()
+                .filter(|index| indices_1.contains(index))
+                .collect::<Vec<_>>();
+            validator_indices.sort_unstable();
+
+            to_api_forecast(validator_indices, forecast)
+        })
+        .collect();
+
+    let voluntary_exits = forecasts
+        .voluntary_exits
+        .into_iter()
+        .map(|(exit, forecast)| to_api_forecast(vec![exit.message.validator_index], forecast))
+        .collect();
+
+    let prune_stats = op_pool.prune_stats();
+
+    Ok(OperationPoolSummary {
+        sizes: OperationPoolSizes {
+            attestations: op_pool.num_attestations(),
+            sync_contributions: op_pool.num_sync_contributions(),
+            attester_slashings: op_pool.num_attester_slashings(),
+            proposer_slashings: op_pool.num_proposer_slashings(),
+            voluntary_exits: op_pool.num_voluntary_exits(),
+        },
+        proposer_slashings,
+        attester_slashings,
+        voluntary_exits,
+        pruned: OperationPoolPruneStats {
+            last_prune_slot: prune_stats.last_prune_slot,
+            attestations: prune_stats.attestations,
+            sync_contributions: prune_stats.sync_contributions,
+            attester_slashings: prune_stats.attester_slashings,
+            proposer_slashings: prune_stats.proposer_slashings,
+            voluntary_exits: prune_stats.voluntary_exits,
+        },
+    })
+}
+
+/// Returns the aggregate attestations for `slot` held by the node, grouped by committee.
+///
+/// Attestations in the naive aggregation pool are included, since they are imported into the
+/// operation pool during block production.
+pub fn get_attestations_by_committee<T: BeaconChainTypes>(
+    slot: Slot,
+    chain: &BeaconChain<T>,
+) -> Vec<CommitteeAttestations<T::EthSpec>> {
+    let slot_filter = |attestation: &Attestation<T::EthSpec>| attestation.data.slot == slot;
+
+    let mut attestations = chain.op_pool.get_filtered_attestations(slot_filter);
+    attestations.extend(
+        chain
+            .naive_aggregation_pool
+            .read()
+            .iter()
+            .filter(|attestation| slot_filter(attestation))
+            .cloned(),
+    );
+
+    let mut committees = BTreeMap::<_, Vec<_>>::new();
+    for attestation in attestations {
+        let aggregates = committees.entry(attestation.data.index).or_default();
+        if !aggregates.contains(&attestation) {
+            aggregates.push(attestation);
+        }
+    }
+
+    committees
+        .into_iter()
+        .filter_map(|(committee_index, aggregates)| {
+            let coverage = aggregates
+                .iter()
+                .map(|attestation| attestation.aggregation_bits.clone())
+                .reduce(|coverage, bits| coverage.union(&bits))?;
+
+            Some(CommitteeAttestations {
+                committee_index,
+                covered_validators: coverage.num_set_bits() as u64,
+                committee_size: coverage.len() as u64,
+                coverage,
+                aggregates,
+            })
+        })
+        .collect()
+}
+
+fn to_api_forecast(validator_indices: Vec<u64>, forecast: InclusionForecast) -> OperationForecast {
+    match forecast {
+        InclusionForecast::NextBlock => OperationForecast {
+            validator_indices,
+            included_in_next_block: true,
+            exclusion_reason: None,
+        },
+        InclusionForecast::Excluded(reason) => OperationForecast {
+            validator_indices,
+            included_in_next_block: false,
+            exclusion_reason: Some(reason.to_string()),
+        },
+    }
+}
@@ -0,0 +1,65 @@
+//! Handlers for the standard `beacon/rewards` endpoints.
+
+use crate::block_id::BlockId;
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::types::{self as api_types, ValidatorId};
+use types::Epoch;
+
+/// Handles a request for the rewards earned by the proposer of a block.
+pub fn block_rewards<T: BeaconChainTypes>(
+    block_id: BlockId,
+    chain: &BeaconChain<T>,
+) -> Result<api_types::GenericResponse<api_types::StandardBlockReward>, warp::Rejection> {
+    let block = block_id.block(chain)?;
+    let block_root = block.canonical_root();
+
+    chain
+        .compute_block_reward(&block, block_root)
+        .map(api_types::GenericResponse::from)
+        .map_err(convert_error)
+}
+
+/// Handles a request for the rewards of sync committee members for a block.
+pub fn sync_committee_rewards<T: BeaconChainTypes>(
+    block_id: BlockId,
+    ids: &[ValidatorId],
+    chain: &BeaconChain<T>,
+) -> Result<api_types::GenericResponse<Vec<api_types::SyncCommitteeReward>>, warp::Rejection> {
+    let block = block_id.block(chain)?;
+    let block_root = block.canonical_root();
+
+    chain
+        .compute_sync_committee_rewards(&block, block_root, ids)
+        .map(api_types::GenericResponse::from)
+        .map_err(convert_error)
+}
+
+/// Handles a request for the attestation rewards of validators in an epoch.
+pub fn attestation_rewards<T: BeaconChainTypes>(
+    epoch: Epoch,
+    ids: &[ValidatorId],
+    chain: &BeaconChain<T>,
+) -> Result<api_types::GenericResponse<api_types::StandardAttestationRewards>, warp::Rejection> {
+    chain
+        .compute_attestation_rewards(epoch, ids)
+        .map(api_types::GenericResponse::from)
+        .map_err(convert_error)
+}
+
+fn convert_error(e: BeaconChainError) -> warp::Rejection {
+    match e {
+        BeaconChainError::RewardsUnsupportedPreAltair => warp_utils::reject::custom_bad_request(
+            "rewards are not supported prior to the Altair fork".to_string(),
+        ),
+        BeaconChainError::AttestationRewardsEpochIncomplete {
+            epoch,
+            current_epoch,
+        } => warp_utils::reject::custom_bad_request(format!(
+            "rewards for epoch {} are not final until the end of epoch {}, current epoch is {}",
+            epoch,
+            epoch + 1,
+            current_epoch
+        )),
+        e => warp_utils::reject::beacon_chain_error(e),
+    }
+}
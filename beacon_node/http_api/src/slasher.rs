@@ -0,0 +1,35 @@
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{SlasherBatchStatus, SlasherStatus};
+
+/// Handles `GET lighthouse/slasher/status`.
+pub fn status<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+) -> Result<SlasherStatus, warp::Rejection> {
+    let slasher = chain.slasher.as_ref().ok_or_else(|| {
+        warp_utils::reject::custom_not_found(
+            "Slasher is disabled. See the --slasher CLI flag.".to_string(),
+        )
+    })?;
+    let config = slasher.config();
+    let last_batch = slasher.last_batch();
+
+    Ok(SlasherStatus {
+        current_epoch: last_batch.map(|batch| batch.current_epoch),
+        oldest_epoch: last_batch
+            .map(|batch| (batch.current_epoch + 1).saturating_sub(config.history_length as u64)),
+        history_length: config.history_length,
+        queued_attestations: slasher.num_queued_attestations(),
+        queued_blocks: slasher.num_queued_blocks(),
+        database_size: directory::size_of_dir(&config.database_path),
+        last_batch: last_batch.map(|batch| SlasherBatchStatus {
+            epoch: batch.current_epoch,
+            time_taken_ms: batch.time_taken.as_millis() as u64,
+            attestations: batch.num_attestations,
+            blocks: batch.num_blocks,
+        }),
+        attester_slashings_found: slasher.num_attester_slashings_found(),
+        proposer_slashings_found: slasher.num_proposer_slashings_found(),
+        op_pool_attester_slashings: chain.op_pool.num_attester_slashings(),
+        op_pool_proposer_slashings: chain.op_pool.num_proposer_slashings(),
+    })
+}
@@ -0,0 +1,327 @@
+//! Streaming SSZ encoding of states for the `debug/beacon/states` endpoint.
+//!
+//! The SSZ encoding of a mainnet state is hundreds of megabytes, so rather than encoding the whole
+//! state into a single buffer, it is encoded a chunk at a time by a blocking task and sent to the
+//! client as each chunk is produced. The channel between the two is bounded, so at most a few
+//! chunks are held in memory at once regardless of the speed of the client.
+//!
+//! The large lists and vectors of a state (e.g., `validators` and `balances`) are encoded a few
+//! items at a time. All other fields are small and are encoded in one piece.
+
+use ssz::{Encode, BYTES_PER_LENGTH_OFFSET};
+use std::convert::Infallible;
+use std::ops::Range;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use types::{BeaconState, EthSpec};
+
+/// The approximate size of each chunk sent to the client.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// The number of chunks which may be encoded ahead of the client.
+const CHANNEL_CAPACITY: usize = 2;
+
+/// An object-safe view of the SSZ encoding of a top-level field of a state, as a sequence of
+/// items which can be encoded separately.
+trait StreamedField {
+    /// Returns `true` if the field is encoded in the fixed-length part of the state.
+    fn is_fixed_len(&self) -> bool;
+
+    fn num_items(&self) -> usize;
+
+    /// The number of bytes occupied by each item, used to size chunks.
+    fn item_len(&self) -> usize;
+
+    fn append_items(&self, items: Range<usize>, buf: &mut Vec<u8>);
+
+    fn encoded_len(&self) -> usize {
+        self.num_items() * self.item_len()
+    }
+}
+
+/// A field which is encoded in one piece.
+struct Whole<'a, T>(&'a T);
+
+impl<'a, T: Encode> StreamedField for Whole<'a, T> {
+    fn is_fixed_len(&self) -> bool {
+        T::is_ssz_fixed_len()
+    }
+
+    fn num_items(&self) -> usize {
+        1
+    }
+
+    fn item_len(&self) -> usize {
+        self.0.ssz_bytes_len()
+    }
+
+    fn append_items(&self, _: Range<usize>, buf: &mut Vec<u8>) {
+        self.0.ssz_append(buf)
+    }
+}
+
+/// A list or vector of fixed-length items, which is encoded as the concatenation of its items.
+struct Items<'a, T> {
+    items: &'a [T],
+    /// `true` for a vector, `false` for a list.
+    is_vector: bool,
+}
+
+impl<'a, T: Encode> StreamedField for Items<'a, T> {
+    fn is_fixed_len(&self) -> bool {
+        self.is_vector
+    }
+
+    fn num_items(&self) -> usize {
+        self.items.len()
+    }
+
+    fn item_len(&self) -> usize {
+        T::ssz_fixed_len()
+    }
+
+    fn append_items(&self, items: Range<usize>, buf: &mut Vec<u8>) {
+        for item in &self.items[items] {
+            item.ssz_append(buf);
+        }
+    }
+}
+
+fn whole<T: Encode>(field: &T) -> Box<dyn StreamedField + '_> {
+    Box::new(Whole(field))
+}
+
+fn vector<T: Encode>(items: &[T]) -> Box<dyn StreamedField + '_> {
+    debug_assert!(T::is_ssz_fixed_len());
+    Box::new(Items {
+        items,
+        is_vector: true,
+    })
+}
+
+fn list<T: Encode>(items: &[T]) -> Box<dyn StreamedField + '_> {
+    debug_assert!(T::is_ssz_fixed_len());
+    Box::new(Items {
+        items,
+        is_vector: false,
+    })
+}
+
+/// Returns the top-level fields of `state` in the order in which they are encoded.
+fn fields<E: EthSpec>(state: &BeaconState<E>) -> Vec<Box<dyn StreamedField + '_>> {
+    match state {
+        BeaconState::Base(state) => vec![
+            whole(&state.genesis_time),
+            whole(&state.genesis_validators_root),
+            whole(&state.slot),
+            whole(&state.fork),
+            whole(&state.latest_block_header),
+            vector(&state.block_roots),
+            vector(&state.state_roots),
+            list(&state.historical_roots),
+            whole(&state.eth1_data),
+            list(&state.eth1_data_votes),
+            whole(&state.eth1_deposit_index),
+            list(&state.validators),
+            list(&state.balances),
+            vector(&state.randao_mixes),
+            vector(&state.slashings),
+            whole(&state.previous_epoch_attestations),
+            whole(&state.current_epoch_attestations),
+            whole(&state.justification_bits),
+            whole(&state.previous_justified_checkpoint),
+            whole(&state.current_justified_checkpoint),
+            whole(&state.finalized_checkpoint),
+        ],
+        BeaconState::Altair(state) => vec![
+            whole(&state.genesis_time),
+            whole(&state.genesis_validators_root),
+            whole(&state.slot),
+            whole(&state.fork),
+            whole(&state.latest_block_header),
+            vector(&state.block_roots),
+            vector(&state.state_roots),
+            list(&state.historical_roots),
+            whole(&state.eth1_data),
+            list(&state.eth1_data_votes),
+            whole(&state.eth1_deposit_index),
+            list(&state.validators),
+            list(&state.balances),
+            vector(&state.randao_mixes),
+            vector(&state.slashings),
+            list(&state.previous_epoch_participation),
+            list(&state.current_epoch_participation),
+            whole(&state.justification_bits),
+            whole(&state.previous_justified_checkpoint),
+            whole(&state.current_justified_checkpoint),
+            whole(&state.finalized_checkpoint),
+            list(&state.inactivity_scores),
+            whole(&*state.current_sync_committee),
+            whole(&*state.next_sync_committee),
+        ],
+        BeaconState::Merge(state) => vec![
+            whole(&state.genesis_time),
+            whole(&state.genesis_validators_root),
+            whole(&state.slot),
+            whole(&state.fork),
+            whole(&state.latest_block_header),
+            vector(&state.block_roots),
+            vector(&state.state_roots),
+            list(&state.historical_roots),
+            whole(&state.eth1_data),
+            list(&state.eth1_data_votes),
+            whole(&state.eth1_deposit_index),
+            list(&state.validators),
+            list(&state.balances),
+            vector(&state.randao_mixes),
+            vector(&state.slashings),
+            list(&state.previous_epoch_participation),
+            list(&state.current_epoch_participation),
+            whole(&state.justification_bits),
+            whole(&state.previous_justified_checkpoint),
+            whole(&state.current_justified_checkpoint),
+            whole(&state.finalized_checkpoint),
+            list(&state.inactivity_scores),
+            whole(&*state.current_sync_committee),
+            whole(&*state.next_sync_committee),
+            whole(&state.latest_execution_payload_header),
+        ],
+    }
+}
+
+/// The position of the encoder within the encoding of a state.
+struct Cursor {
+    /// Whether the fixed-length part of the state has been encoded.
+    in_variable_part: bool,
+    field: usize,
+    item: usize,
+}
+
+/// Encodes a state a chunk at a time, yielding the same bytes as `state.as_ssz_bytes()`.
+pub struct SszStateEncoder<E: EthSpec> {
+    state: Arc<BeaconState<E>>,
+    /// The offsets of the variable-length fields, in order.
+    offsets: Vec<usize>,
+    cursor: Cursor,
+}
+
+impl<E: EthSpec> SszStateEncoder<E> {
+    pub fn new(state: Arc<BeaconState<E>>) -> Self {
+        let fields = fields(&state);
+
+        let fixed_part_len = fields
+            .iter()
+            .map(|field| {
+                if field.is_fixed_len() {
+                    field.encoded_len()
+                } else {
+                    BYTES_PER_LENGTH_OFFSET
+                }
+            })
+            .sum::<usize>();
+
+        let mut offset = fixed_part_len;
+        let offsets = fields
+            .iter()
+            .filter(|field| !field.is_fixed_len())
+            .map(|field| {
+                let field_offset = offset;
+                offset += field.encoded_len();
+                field_offset
+            })
+            .collect();
+        drop(fields);
+
+        Self {
+            state,
+            offsets,
+            cursor: Cursor {
+                in_variable_part: false,
+                field: 0,
+                item: 0,
+            },
+        }
+    }
+
+    /// Returns the next chunk of the encoding, or `None` if the state has been fully encoded.
+    pub fn next_chunk(&mut self) -> Option<Vec<u8>> {
+        let fields = fields(&self.state);
+        let cursor = &mut self.cursor;
+        let mut buf = Vec::with_capacity(CHUNK_SIZE);
+
+        while buf.len() < CHUNK_SIZE {
+            let field = match fields.get(cursor.field) {
+                Some(field) => field,
+                None if !cursor.in_variable_part => {
+                    cursor.in_variable_part = true;
+                    cursor.field = 0;
+                    continue;
+                }
+                None => break,
+            };
+
+            if !cursor.in_variable_part && !field.is_fixed_len() {
+                let variable_index = fields[..cursor.field]
+                    .iter()
+                    .filter(|field| !field.is_fixed_len())
+                    .count();
+                buf.extend_from_slice(&ssz::encode_length(self.offsets[variable_index]));
+                cursor.field += 1;
+                continue;
+            }
+
+            if cursor.in_variable_part && field.is_fixed_len() {
+                cursor.field += 1;
+                continue;
+            }
+
+            // Encode as many items as fit in the remainder of the chunk, and at least one.
+            let remaining = CHUNK_SIZE.saturating_sub(buf.len());
+            let num_items = std::cmp::max(remaining / std::cmp::max(field.item_len(), 1), 1);
+            let end = std::cmp::min(cursor.item + num_items, field.num_items());
+            field.append_items(cursor.item..end, &mut buf);
+
+            if end == field.num_items() {
+                cursor.field += 1;
+                cursor.item = 0;
+            } else {
+                cursor.item = end;
+            }
+        }
+
+        if buf.is_empty() {
+            None
+        } else {
+            Some(buf)
+        }
+    }
+}
+
+impl<E: EthSpec> Iterator for SszStateEncoder<E> {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        self.next_chunk()
+    }
+}
+
+/// Returns a stream of the SSZ encoding of `state`, which is encoded by a blocking task as the
+/// stream is consumed.
+///
+/// Encoding stops early if the stream is dropped, e.g. because the client disconnected.
+pub fn ssz_state_stream<E: EthSpec>(
+    state: Arc<BeaconState<E>>,
+) -> ReceiverStream<Result<Vec<u8>, Infallible>> {
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+
+    tokio::task::spawn_blocking(move || {
+        for chunk in SszStateEncoder::new(state) {
+            if tx.blocking_send(Ok(chunk)).is_err() {
+                break;
+            }
+        }
+    });
+
+    ReceiverStream::new(rx)
+}
@@ -56,20 +56,35 @@ impl StateId {
         self.map_state(chain, |state| Ok(state.fork()))
     }
 
+    /// Returns `true` if `self` refers to the head of the chain.
+    pub fn is_head(&self) -> bool {
+        matches!(self.0, CoreStateId::Head)
+    }
+
+    /// Return the state root identified by `self`, along with the slot of the state if it is
+    /// known without loading the state.
+    pub fn root_and_slot<T: BeaconChainTypes>(
+        &self,
+        chain: &BeaconChain<T>,
+    ) -> Result<(Hash256, Option<Slot>), warp::Rejection> {
+        let slot_opt = match &self.0 {
+            CoreStateId::Slot(slot) => Some(*slot),
+            _ => None,
+        };
+        Ok((self.root(chain)?, slot_opt))
+    }
+
     /// Return the `BeaconState` identified by `self`.
     pub fn state<T: BeaconChainTypes>(
         &self,
         chain: &BeaconChain<T>,
     ) -> Result<BeaconState<T::EthSpec>, warp::Rejection> {
-        let (state_root, slot_opt) = match &self.0 {
-            CoreStateId::Head => {
-                return chain
-                    .head_beacon_state()
-                    .map_err(warp_utils::reject::beacon_chain_error)
-            }
-            CoreStateId::Slot(slot) => (self.root(chain)?, Some(*slot)),
-            _ => (self.root(chain)?, None),
-        };
+        if self.is_head() {
+            return chain
+                .head_beacon_state()
+                .map_err(warp_utils::reject::beacon_chain_error);
+        }
+        let (state_root, slot_opt) = self.root_and_slot(chain)?;
 
         chain
             .get_state(&state_root, slot_opt)
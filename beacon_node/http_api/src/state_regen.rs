@@ -0,0 +1,162 @@
+//! Regeneration of states for the `debug/beacon/states` endpoint.
+//!
+//! States which are not in the hot database must be reconstructed by replaying blocks on top of a
+//! restore point, which can take minutes for states far from a restore point. Serving these
+//! requests from a blocking task would occupy a worker for the whole regeneration, so instead:
+//!
+//! - Regenerations run in a background queue and at most `max_concurrent` run at once.
+//! - A request waits for its regeneration for up to `max_wait`. If it is not complete by then the
+//!   request is answered with `202 Accepted` and a `Retry-After` header, whilst the regeneration
+//!   carries on in the background.
+//! - Completed states are cached by state root, so that a retried request is served immediately.
+//!
+//! Concurrent requests for the same state share a single regeneration.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use types::{BeaconState, EthSpec, Hash256, Slot};
+
+/// The result of a regeneration, shared between all requests waiting upon it.
+///
+/// `Ok(None)` indicates that the state is unknown.
+type RegenResult<E> = Result<Option<Arc<BeaconState<E>>>, String>;
+
+type PendingRegen<E> = Shared<BoxFuture<'static, RegenResult<E>>>;
+
+/// The outcome of a request for a state.
+pub enum StateRegenStatus<E: EthSpec> {
+    /// The state is available.
+    Ready(Arc<BeaconState<E>>),
+    /// The state is unknown.
+    NotFound,
+    /// The state is still being regenerated and should be requested again after the given
+    /// duration.
+    Pending { retry_after: Duration },
+}
+
+struct Inner<E: EthSpec> {
+    cache: LruCache<Hash256, Arc<BeaconState<E>>>,
+    pending: HashMap<Hash256, PendingRegen<E>>,
+}
+
+/// A queue of state regenerations with a concurrency budget and a cache of results.
+pub struct StateRegenQueue<E: EthSpec> {
+    budget: Arc<Semaphore>,
+    max_wait: Duration,
+    inner: Arc<Mutex<Inner<E>>>,
+}
+
+impl<E: EthSpec> Clone for StateRegenQueue<E> {
+    fn clone(&self) -> Self {
+        Self {
+            budget: self.budget.clone(),
+            max_wait: self.max_wait,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<E: EthSpec> StateRegenQueue<E> {
+    pub fn new(max_concurrent: usize, cache_size: usize, max_wait: Duration) -> Self {
+        Self {
+            budget: Arc::new(Semaphore::new(max_concurrent)),
+            max_wait,
+            inner: Arc::new(Mutex::new(Inner {
+                cache: LruCache::new(cache_size),
+                pending: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Returns the state with `state_root`, waiting for up to `self.max_wait` for it to be
+    /// regenerated if it is not cached.
+    ///
+    /// `slot` is an optional hint of the slot of the state, which avoids a database lookup.
+    pub async fn get_state<T>(
+        &self,
+        chain: Arc<BeaconChain<T>>,
+        state_root: Hash256,
+        slot: Option<Slot>,
+    ) -> Result<StateRegenStatus<E>, String>
+    where
+        T: BeaconChainTypes<EthSpec = E>,
+    {
+        let pending = {
+            let mut inner = self.inner.lock();
+
+            if let Some(state) = inner.cache.get(&state_root) {
+                return Ok(StateRegenStatus::Ready(state.clone()));
+            }
+
+            match inner.pending.get(&state_root) {
+                Some(pending) => pending.clone(),
+                None => {
+                    let pending = self.spawn_regen(chain, state_root, slot);
+                    inner.pending.insert(state_root, pending.clone());
+                    pending
+                }
+            }
+        };
+
+        match tokio::time::timeout(self.max_wait, pending).await {
+            Ok(Ok(Some(state))) => Ok(StateRegenStatus::Ready(state)),
+            Ok(Ok(None)) => Ok(StateRegenStatus::NotFound),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Ok(StateRegenStatus::Pending {
+                retry_after: self.max_wait,
+            }),
+        }
+    }
+
+    /// Spawns a task which regenerates the state once the concurrency budget allows, and caches
+    /// it upon completion.
+    ///
+    /// The task runs to completion even if every request waiting upon it has been answered.
+    fn spawn_regen<T>(
+        &self,
+        chain: Arc<BeaconChain<T>>,
+        state_root: Hash256,
+        slot: Option<Slot>,
+    ) -> PendingRegen<E>
+    where
+        T: BeaconChainTypes<EthSpec = E>,
+    {
+        let budget = self.budget.clone();
+        let inner = self.inner.clone();
+
+        let handle = tokio::spawn(async move {
+            let result = match budget.acquire_owned().await {
+                Ok(_permit) => tokio::task::spawn_blocking(move || {
+                    chain
+                        .get_state(&state_root, slot)
+                        .map(|state_opt| state_opt.map(Arc::new))
+                        .map_err(|e| format!("unable to load state: {:?}", e))
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("state regeneration failed: {:?}", e))),
+                Err(e) => Err(format!("unable to acquire regeneration permit: {:?}", e)),
+            };
+
+            let mut inner = inner.lock();
+            inner.pending.remove(&state_root);
+            if let Ok(Some(state)) = &result {
+                inner.cache.put(state_root, state.clone());
+            }
+
+            result
+        });
+
+        handle
+            .map(|result| {
+                result.unwrap_or_else(|e| Err(format!("state regeneration failed: {:?}", e)))
+            })
+            .boxed()
+            .shared()
+    }
+}
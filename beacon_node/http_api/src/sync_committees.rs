@@ -25,6 +25,10 @@ use types::{
 type SyncDuties = api_types::GenericResponse<Vec<SyncDuty>>;
 
 /// Handles a request from the HTTP API for sync committee duties.
+///
+/// The next sync committee is fixed at the start of the current period, so duties for any epoch of
+/// the next period can be served for the whole of the current period. Validator clients use this
+/// to look ahead by up to a full period (256 epochs on mainnet).
 pub fn sync_committee_duties<T: BeaconChainTypes>(
     request_epoch: Epoch,
     request_indices: &[u64],
@@ -55,7 +59,8 @@ pub fn sync_committee_duties<T: BeaconChainTypes>(
             current_epoch,
             ..
         }) => warp_utils::reject::custom_bad_request(format!(
-            "invalid epoch: {}, current epoch: {}",
+            "invalid epoch: {}, current epoch: {}, duties are only known up to the end of the \
+             next sync committee period",
             request_epoch, current_epoch
         )),
         e => warp_utils::reject::beacon_chain_error(e),
@@ -0,0 +1,115 @@
+//! Handlers for the `lighthouse/ui` endpoints, which aggregate information for user interfaces
+//! such that a dashboard can be populated without making many requests.
+
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    Health, UiHealth, ValidatorMetrics, ValidatorMetricsRequestData, ValidatorMetricsResponseData,
+};
+use eth2::types::{PeerCount, PeerState};
+use lighthouse_network::NetworkGlobals;
+use std::collections::HashMap;
+use types::EthSpec;
+
+/// Counts the peers known to `network_globals` by connection state.
+pub fn peer_count<E: EthSpec>(network_globals: &NetworkGlobals<E>) -> PeerCount {
+    let mut count = PeerCount {
+        connected: 0,
+        connecting: 0,
+        disconnected: 0,
+        disconnecting: 0,
+    };
+
+    network_globals
+        .peers
+        .read()
+        .peers()
+        .for_each(|(_, peer_info)| {
+            match PeerState::from_peer_connection_status(peer_info.connection_status()) {
+                PeerState::Connected => count.connected += 1,
+                PeerState::Connecting => count.connecting += 1,
+                PeerState::Disconnected => count.disconnected += 1,
+                PeerState::Disconnecting => count.disconnecting += 1,
+            }
+        });
+
+    count
+}
+
+/// Handles a request for the health of the node.
+pub fn get_health<T: BeaconChainTypes>(
+    network_globals: &NetworkGlobals<T::EthSpec>,
+    chain: &BeaconChain<T>,
+) -> Result<UiHealth, warp::Rejection> {
+    let head_info = chain
+        .head_info()
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+    let current_slot = chain
+        .slot()
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+
+    Ok(UiHealth {
+        // Host metrics are unavailable on some platforms, which should not prevent the remainder
+        // of the response from being served.
+        health: Health::observe().ok(),
+        sync_state: network_globals.sync_state(),
+        head_slot: head_info.slot,
+        // Taking advantage of saturating subtraction on slot.
+        sync_distance: current_slot - head_info.slot,
+        finalized_epoch: head_info.finalized_checkpoint.epoch,
+        peers: peer_count(network_globals),
+        monitored_validators: chain.validator_monitor.read().num_validators() as u64,
+    })
+}
+
+/// Handles a request for the performance of validators known to the validator monitor.
+///
+/// Validators which are not monitored are omitted from the response.
+pub fn post_validator_metrics<T: BeaconChainTypes>(
+    request: ValidatorMetricsRequestData,
+    chain: &BeaconChain<T>,
+) -> ValidatorMetricsResponseData {
+    let validator_monitor = chain.validator_monitor.read();
+
+    let validators = request
+        .indices
+        .iter()
+        .filter_map(|&index| {
+            let performance = validator_monitor.get_validator_performance(index)?;
+            let metrics = ValidatorMetrics {
+                attestation_hits: performance.attestation_hits,
+                attestation_misses: performance.attestation_misses,
+                attestation_hit_percentage: hit_percentage(
+                    performance.attestation_hits,
+                    performance.attestation_misses,
+                ),
+                attestation_head_hits: performance.attestation_head_hits,
+                attestation_head_misses: performance.attestation_head_misses,
+                attestation_head_hit_percentage: hit_percentage(
+                    performance.attestation_head_hits,
+                    performance.attestation_head_misses,
+                ),
+                attestation_target_hits: performance.attestation_target_hits,
+                attestation_target_misses: performance.attestation_target_misses,
+                attestation_target_hit_percentage: hit_percentage(
+                    performance.attestation_target_hits,
+                    performance.attestation_target_misses,
+                ),
+                latest_attestation_inclusion_distance: performance
+                    .latest_attestation_inclusion_distance,
+            };
+            Some((index.to_string(), metrics))
+        })
+        .collect::<HashMap<_, _>>();
+
+    ValidatorMetricsResponseData { validators }
+}
+
+/// Returns the percentage of `hits` out of all attempts, or 0 if there were no attempts.
+fn hit_percentage(hits: u64, misses: u64) -> f64 {
+    let total = hits + misses;
+    if total == 0 {
+        0.0
+    } else {
+        (hits as f64 / total as f64) * 100.0
+    }
+}
@@ -1,6 +1,6 @@
 use beacon_chain::{
     test_utils::{BeaconChainHarness, EphemeralHarnessType},
-    BeaconChain, BeaconChainTypes,
+    BeaconChain, BeaconChainTypes, ChainConfig,
 };
 use eth2::{BeaconNodeHttpClient, Timeouts};
 use http_api::{Config, Context};
@@ -13,13 +13,14 @@ use lighthouse_network::{
 };
 use network::NetworkMessage;
 use sensitive_url::SensitiveUrl;
+use slasher::Slasher;
 use slog::Logger;
 use std::future::Future;
-use std::net::{Ipv4Addr, SocketAddr};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, oneshot};
-use types::{ChainSpec, EthSpec};
+use types::{BeaconState, ChainSpec, EthSpec};
 
 pub const TCP_PORT: u16 = 42;
 pub const UDP_PORT: u16 = 42;
@@ -48,19 +49,89 @@ pub struct ApiServer<E: EthSpec, SFut: Future<Output = ()>> {
 
 impl<E: EthSpec> InteractiveTester<E> {
     pub async fn new(spec: Option<ChainSpec>, validator_count: usize) -> Self {
+        Self::new_with_chain_config(spec, validator_count, ChainConfig::default()).await
+    }
+
+    pub async fn new_with_chain_config(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        chain_config: ChainConfig,
+    ) -> Self {
+        Self::new_with_config(spec, validator_count, chain_config, test_config()).await
+    }
+
+    pub async fn new_with_http_config(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        http_config: Config,
+    ) -> Self {
+        Self::new_with_config(spec, validator_count, ChainConfig::default(), http_config).await
+    }
+
+    pub async fn new_with_config(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        chain_config: ChainConfig,
+        http_config: Config,
+    ) -> Self {
+        let harness = BeaconChainHarness::builder(E::default())
+            .spec_or_default(spec)
+            .chain_config(chain_config)
+            .deterministic_keypairs(validator_count)
+            .fresh_ephemeral_store()
+            .build();
+
+        Self::from_harness(harness, http_config).await
+    }
+
+    /// Create a tester whose beacon chain feeds blocks and attestations to `slasher`.
+    pub async fn new_with_slasher(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        slasher: Arc<Slasher<E>>,
+    ) -> Self {
         let harness = BeaconChainHarness::builder(E::default())
             .spec_or_default(spec)
             .deterministic_keypairs(validator_count)
             .fresh_ephemeral_store()
+            .initial_mutator(Box::new(move |builder| builder.slasher(slasher)))
             .build();
 
+        Self::from_harness(harness, test_config()).await
+    }
+
+    /// Create a tester whose chain starts from `genesis_state`, which must contain the first
+    /// `validator_count` deterministic validators.
+    pub async fn new_with_genesis_state(
+        spec: Option<ChainSpec>,
+        validator_count: usize,
+        genesis_state: BeaconState<E>,
+    ) -> Self {
+        let harness = BeaconChainHarness::builder(E::default())
+            .spec_or_default(spec)
+            .deterministic_keypairs(validator_count)
+            .genesis_state_ephemeral_store(genesis_state)
+            .build();
+
+        Self::from_harness(harness, test_config()).await
+    }
+
+    async fn from_harness(
+        harness: BeaconChainHarness<EphemeralHarnessType<E>>,
+        http_config: Config,
+    ) -> Self {
         let ApiServer {
             server,
             listening_socket,
             shutdown_tx: _server_shutdown,
             network_rx,
             ..
-        } = create_api_server(harness.chain.clone(), harness.logger().clone()).await;
+        } = create_api_server_with_config(
+            harness.chain.clone(),
+            http_config,
+            harness.logger().clone(),
+        )
+        .await;
 
         tokio::spawn(server);
 
@@ -83,9 +154,26 @@ impl<E: EthSpec> InteractiveTester<E> {
     }
 }
 
+/// Returns the HTTP API config used by `create_api_server`.
+pub fn test_config() -> Config {
+    Config {
+        enabled: true,
+        listen_port: 0,
+        ..Config::default()
+    }
+}
+
 pub async fn create_api_server<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
     log: Logger,
+) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
+    create_api_server_with_config(chain, test_config(), log).await
+}
+
+pub async fn create_api_server_with_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    config: Config,
+    log: Logger,
 ) -> ApiServer<T::EthSpec, impl Future<Output = ()>> {
     let (network_tx, network_rx) = mpsc::unbounded_channel();
 
@@ -126,19 +214,12 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     let eth1_service = eth1::Service::new(eth1::Config::default(), log.clone(), chain.spec.clone());
 
     let context = Arc::new(Context {
-        config: Config {
-            enabled: true,
-            listen_addr: Ipv4Addr::new(127, 0, 0, 1),
-            listen_port: 0,
-            allow_origin: None,
-            serve_legacy_spec: true,
-            tls_config: None,
-            allow_sync_stalled: false,
-        },
+        config,
         chain: Some(chain.clone()),
         network_tx: Some(network_tx),
         network_globals: Some(network_globals),
         eth1_service: Some(eth1_service),
+        log_levels: Some(logging::LogLevels::default()),
         log,
     });
     let ctx = context.clone();
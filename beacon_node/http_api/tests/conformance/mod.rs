@@ -0,0 +1,251 @@
+//! Checks the JSON returned by our standard routes against the response examples published in the
+//! beacon-APIs specification.
+//!
+//! The examples in `examples/` are copied from the OpenAPI definition of the standard API. Each
+//! response is diffed against its example by field names and JSON types (rather than by value), so
+//! a renamed field, a missing field or a number which is no longer quoted is caught in-tree rather
+//! than by downstream validator clients.
+//!
+//! To cover a new route, add its example to `examples/` and a `Case` to `CASES`.
+use crate::common::*;
+use beacon_chain::test_utils::{AttestationStrategy, BlockStrategy};
+use serde_json::Value;
+use types::{Epoch, EthSpec, MinimalEthSpec};
+
+type E = MinimalEthSpec;
+
+/// A route and the example response it must conform to.
+struct Case {
+    path: &'static str,
+    example: &'static str,
+    /// Fields which Lighthouse may return in addition to those in the example, e.g. fields from a
+    /// newer version of the specification. They may be absent from either side.
+    optional_fields: &'static [&'static str],
+}
+
+const CASES: &[Case] = &[
+    Case {
+        path: "/eth/v1/beacon/genesis",
+        example: include_str!("examples/get_genesis.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/beacon/states/head/root",
+        example: include_str!("examples/get_state_root.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/beacon/states/head/fork",
+        example: include_str!("examples/get_state_fork.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/beacon/states/head/finality_checkpoints",
+        example: include_str!("examples/get_state_finality_checkpoints.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/beacon/states/head/validators/0",
+        example: include_str!("examples/get_state_validator.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/beacon/headers/head",
+        example: include_str!("examples/get_block_header.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v2/beacon/blocks/head",
+        example: include_str!("examples/get_block_v2_altair.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/config/fork_schedule",
+        example: include_str!("examples/get_fork_schedule.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/config/deposit_contract",
+        example: include_str!("examples/get_deposit_contract.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/node/version",
+        example: include_str!("examples/get_node_version.json"),
+        optional_fields: &[],
+    },
+    Case {
+        path: "/eth/v1/node/syncing",
+        example: include_str!("examples/get_node_syncing.json"),
+        optional_fields: &["el_offline"],
+    },
+    Case {
+        path: "/eth/v1/node/peer_count",
+        example: include_str!("examples/get_peer_count.json"),
+        optional_fields: &[],
+    },
+];
+
+/// Returns the name of the JSON type of `value`.
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Appends a description of each difference between the fields and types of `example` and
+/// `actual` to `diffs`.
+///
+/// The elements of an array are compared with the first element of the example, so arrays which
+/// are empty on either side are not inspected.
+fn diff_shapes(
+    path: &str,
+    example: &Value,
+    actual: &Value,
+    optional_fields: &[&str],
+    diffs: &mut Vec<String>,
+) {
+    match (example, actual) {
+        (Value::Object(example), Value::Object(actual)) => {
+            for (field, example_value) in example {
+                let field_path = format!("{}.{}", path, field);
+                match actual.get(field) {
+                    Some(actual_value) => diff_shapes(
+                        &field_path,
+                        example_value,
+                        actual_value,
+                        optional_fields,
+                        diffs,
+                    ),
+                    None if optional_fields.contains(&field.as_str()) => (),
+                    None => diffs.push(format!("{}: missing from response", field_path)),
+                }
+            }
+            for field in actual.keys() {
+                if !example.contains_key(field) && !optional_fields.contains(&field.as_str()) {
+                    diffs.push(format!("{}.{}: not in example", path, field));
+                }
+            }
+        }
+        (Value::Array(example), Value::Array(actual)) => {
+            if let Some(example_element) = example.first() {
+                for (i, actual_element) in actual.iter().enumerate() {
+                    diff_shapes(
+                        &format!("{}[{}]", path, i),
+                        example_element,
+                        actual_element,
+                        optional_fields,
+                        diffs,
+                    );
+                }
+            }
+        }
+        (example, actual) if json_type(example) != json_type(actual) => diffs.push(format!(
+            "{}: expected {}, got {}",
+            path,
+            json_type(example),
+            json_type(actual)
+        )),
+        _ => (),
+    }
+}
+
+/// Returns the differences between the fields and types of `example` and `actual`.
+fn shape_diffs(example: &Value, actual: &Value, optional_fields: &[&str]) -> Vec<String> {
+    let mut diffs = vec![];
+    diff_shapes("", example, actual, optional_fields, &mut diffs);
+    diffs
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn standard_routes_conform_to_examples() {
+    let validator_count = 32;
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+
+    let tester = InteractiveTester::<E>::new(Some(spec), validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    // Build a chain with attestations in its blocks so that array elements are checked too.
+    harness.advance_slot();
+    harness.extend_chain(
+        E::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let server = client.as_ref().trim_end_matches('/');
+    let mut failures = vec![];
+    for case in CASES {
+        let example: Value = serde_json::from_str(case.example)
+            .unwrap_or_else(|e| panic!("invalid example for {}: {:?}", case.path, e));
+        let actual: Value = client
+            .get_response(format!("{}{}", server, case.path), |b| b)
+            .await
+            .unwrap_or_else(|e| panic!("request to {} failed: {:?}", case.path, e))
+            .json()
+            .await
+            .unwrap_or_else(|e| panic!("invalid JSON from {}: {:?}", case.path, e));
+
+        for diff in shape_diffs(&example, &actual, case.optional_fields) {
+            failures.push(format!("{} {}", case.path, diff));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "responses differ from the specification:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn shape_diffs_detects_drift() {
+    let example = serde_json::json!({
+        "data": {
+            "slot": "1",
+            "is_syncing": true,
+            "items": [{ "epoch": "1" }],
+        }
+    });
+
+    let conforming = serde_json::json!({
+        "data": {
+            "slot": "42",
+            "is_syncing": false,
+            "items": [{ "epoch": "2" }, { "epoch": "3" }],
+        }
+    });
+    assert!(shape_diffs(&example, &conforming, &[]).is_empty());
+
+    let drifted = serde_json::json!({
+        "data": {
+            "slot": 42,
+            "items": [{ "epoch": "2", "root": "0x00" }],
+            "extra": null,
+        }
+    });
+    let mut diffs = shape_diffs(&example, &drifted, &[]);
+    diffs.sort();
+    assert_eq!(
+        diffs,
+        vec![
+            ".data.extra: not in example".to_string(),
+            ".data.is_syncing: missing from response".to_string(),
+            ".data.items[0].root: not in example".to_string(),
+            ".data.slot: expected string, got number".to_string(),
+        ]
+    );
+
+    // Optional fields may be absent from the example.
+    assert_eq!(
+        shape_diffs(&example, &drifted, &["is_syncing", "root", "extra"]).len(),
+        1
+    );
+}
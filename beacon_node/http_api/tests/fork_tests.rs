@@ -1,7 +1,11 @@
 //! Tests for API behaviour across fork boundaries.
 use crate::common::*;
-use beacon_chain::{test_utils::RelativeSyncCommittee, StateSkipConfig};
+use beacon_chain::{
+    test_utils::{AttestationStrategy, BlockStrategy, RelativeSyncCommittee},
+    StateSkipConfig,
+};
 use eth2::types::{StateId, SyncSubcommittee};
+use ssz::Encode;
 use types::{ChainSpec, Epoch, EthSpec, MinimalEthSpec, Slot};
 
 type E = MinimalEthSpec;
@@ -97,6 +101,47 @@ async fn sync_committee_duties_across_fork() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn ssz_states_across_fork() {
+    let validator_count = 32;
+    let fork_epoch = Epoch::new(2);
+    let spec = altair_spec(fork_epoch);
+    let tester = InteractiveTester::<E>::new(Some(spec.clone()), validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    let num_slots = (fork_epoch + 1).start_slot(E::slots_per_epoch()).as_usize();
+    harness.advance_slot();
+    harness.extend_chain(
+        num_slots,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // The streamed SSZ encoding must match the encoding of the state in one piece, both before and
+    // after the fork.
+    for slot in [
+        fork_epoch.start_slot(E::slots_per_epoch()) - 1,
+        harness.get_current_slot(),
+    ] {
+        let expected = harness
+            .chain
+            .state_at_slot(slot, StateSkipConfig::WithStateRoots)
+            .unwrap();
+        let state = client
+            .get_debug_beacon_states_ssz::<E>(StateId::Slot(slot), &spec)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            state.fork_name(&spec).unwrap(),
+            expected.fork_name(&spec).unwrap()
+        );
+        assert_eq!(state.as_ssz_bytes(), expected.as_ssz_bytes());
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn attestations_across_fork_with_skip_slots() {
     let validator_count = E::sync_committee_size();
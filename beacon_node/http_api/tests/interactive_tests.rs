@@ -1,7 +1,24 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use crate::common::*;
-use eth2::types::DepositContractData;
-use types::{EthSpec, MainnetEthSpec};
+use beacon_chain::{
+    test_utils::{
+        generate_deterministic_keypairs, interop_genesis_state, AttestationStrategy, BlockStrategy,
+        DEFAULT_ETH1_BLOCK_HASH, HARNESS_GENESIS_TIME,
+    },
+    ChainConfig,
+};
+use eth2::lighthouse::ActivationStatus;
+use eth2::types::{BlockId, DepositContractData, ValidatorId};
+use eth2::StatusCode;
+use http_api::Config;
+use logging::test_logger;
+use slasher::{
+    test_utils::{block, indexed_att},
+    Slasher,
+};
+use std::sync::Arc;
+use tempfile::tempdir;
+use types::{Epoch, EthSpec, ForkName, Hash256, MainnetEthSpec, MinimalEthSpec};
 
 type E = MainnetEthSpec;
 
@@ -30,3 +47,439 @@ async fn deposit_contract_custom_network() {
 
     assert_eq!(result, expected);
 }
+
+// Test the standard rewards endpoints on an Altair chain in which every validator attests.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rewards_altair() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 32;
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+
+    let tester = InteractiveTester::<E>::new(Some(spec.clone()), validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    harness.advance_slot();
+    harness.extend_chain(
+        E::slots_per_epoch() as usize * 4,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Block rewards should be attributed to the proposer and sum to the total.
+    let head_block = harness.chain.head_beacon_block().unwrap();
+    let block_reward = client
+        .get_beacon_rewards_blocks(BlockId::Head)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(
+        block_reward.proposer_index,
+        head_block.message().proposer_index()
+    );
+    assert!(block_reward.attestations > 0);
+    assert_eq!(
+        block_reward.total,
+        block_reward.attestations
+            + block_reward.sync_aggregate
+            + block_reward.proposer_slashings
+            + block_reward.attester_slashings
+    );
+
+    // The harness does not produce sync aggregates, so every member is penalized.
+    let sync_rewards = client
+        .post_beacon_rewards_sync_committee(BlockId::Head, &[])
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(!sync_rewards.is_empty());
+    assert!(sync_rewards.iter().all(|reward| reward.reward < 0));
+
+    let member = sync_rewards[0].validator_index;
+    let filtered = client
+        .post_beacon_rewards_sync_committee(BlockId::Head, &[ValidatorId::Index(member)])
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(filtered, vec![sync_rewards[0].clone()]);
+
+    // All validators attested perfectly during epoch 1, so all flags are rewarded.
+    let attestation_rewards = client
+        .post_beacon_rewards_attestations(Epoch::new(1), &[])
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(attestation_rewards.total_rewards.len(), validator_count);
+    assert!(attestation_rewards.total_rewards.iter().all(|reward| {
+        reward.head > 0 && reward.target > 0 && reward.source > 0 && reward.inactivity == 0
+    }));
+    assert_eq!(
+        attestation_rewards.ideal_rewards.len() as u64,
+        spec.max_effective_balance / spec.effective_balance_increment
+    );
+
+    // The rewards for the current epoch are not yet known.
+    let current_epoch = harness.chain.epoch().unwrap();
+    client
+        .post_beacon_rewards_attestations(current_epoch, &[])
+        .await
+        .unwrap_err();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn light_client_altair() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 32;
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+    let chain_config = ChainConfig {
+        enable_light_client_server: true,
+        ..ChainConfig::default()
+    };
+
+    let tester =
+        InteractiveTester::<E>::new_with_chain_config(Some(spec), validator_count, chain_config)
+            .await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+
+    harness.advance_slot();
+    harness.extend_chain(
+        E::slots_per_epoch() as usize * 4,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head = harness.chain.head_info().unwrap();
+    let bootstrap = client
+        .get_light_client_bootstrap::<E>(head.block_root)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bootstrap.version, Some(ForkName::Altair));
+    assert_eq!(bootstrap.data.header.beacon.slot, head.slot);
+
+    // The optimistic update attests to the parent of the head.
+    let optimistic_update = client
+        .get_light_client_optimistic_update::<E>()
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(optimistic_update.signature_slot, head.slot);
+    assert_eq!(optimistic_update.attested_header.beacon.slot, head.slot - 1);
+
+    let finality_update = client
+        .get_light_client_finality_update::<E>()
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(finality_update.attested_header.beacon.slot, head.slot - 1);
+    let finalized_slot = finality_update.finalized_header.beacon.slot;
+    assert!(finalized_slot > 0);
+    assert_eq!(finalized_slot % E::slots_per_epoch(), 0);
+
+    // All blocks so far are in the first sync committee period.
+    let updates = client.get_light_client_updates::<E>(0, 4).await.unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(
+        updates[0].data.sync_aggregate.num_set_bits(),
+        E::sync_committee_size()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn light_client_disabled() {
+    type E = MinimalEthSpec;
+
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(0));
+
+    let tester = InteractiveTester::<E>::new(Some(spec), 32).await;
+    tester.harness.advance_slot();
+    tester.harness.extend_chain(
+        E::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    assert!(tester
+        .client
+        .get_light_client_optimistic_update::<E>()
+        .await
+        .unwrap()
+        .is_none());
+}
+
+// Test that requests with bodies larger than the configured limit are rejected.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn body_size_limit() {
+    let validator_count = 24;
+    let http_config = Config {
+        max_body_size: 1024,
+        ..test_config()
+    };
+    let tester =
+        InteractiveTester::<E>::new_with_http_config(None, validator_count, http_config).await;
+    let client = &tester.client;
+    let epoch = Epoch::new(0);
+
+    let small_indices = vec![0, 1, 2];
+    let result = client
+        .post_lighthouse_liveness(&small_indices, epoch)
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(result.len(), small_indices.len());
+
+    // Each index is serialized as a quoted integer, so this body is well over 1 KiB.
+    let large_indices = (0..1024).collect::<Vec<u64>>();
+    let error = client
+        .post_lighthouse_liveness(&large_indices, epoch)
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::PAYLOAD_TOO_LARGE));
+}
+
+/// Requests `state_id` from the `debug/beacon/states` endpoint, returning the status of the
+/// response.
+async fn get_debug_beacon_state_status(
+    tester: &InteractiveTester<MinimalEthSpec>,
+    state_id: &str,
+) -> StatusCode {
+    let url = format!(
+        "{}/eth/v2/debug/beacon/states/{}",
+        tester.client.as_ref().trim_end_matches('/'),
+        state_id
+    );
+    eth2::reqwest::get(url).await.unwrap().status()
+}
+
+/// Requests `state_id` until it has been regenerated, returning the status of the first response.
+async fn get_debug_beacon_state_eventually(
+    tester: &InteractiveTester<MinimalEthSpec>,
+    state_id: &str,
+) -> StatusCode {
+    let first_status = get_debug_beacon_state_status(tester, state_id).await;
+    let mut status = first_status;
+    for _ in 0..50 {
+        if status != StatusCode::ACCEPTED {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        status = get_debug_beacon_state_status(tester, state_id).await;
+    }
+    assert_eq!(
+        status,
+        StatusCode::OK,
+        "state {} should be regenerated",
+        state_id
+    );
+    first_status
+}
+
+// Test that historical states are regenerated in the background, and that a retried request is
+// served from the cache until the state is evicted.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn debug_beacon_states_regen_queue() {
+    type E = MinimalEthSpec;
+
+    let http_config = Config {
+        // Never wait for a regeneration, so that every uncached state is `202 Accepted`.
+        state_regen_max_wait: std::time::Duration::from_secs(0),
+        state_regen_cache_size: 1,
+        ..test_config()
+    };
+    let tester = InteractiveTester::<E>::new_with_http_config(None, 32, http_config).await;
+    tester.harness.advance_slot();
+    tester.harness.extend_chain(
+        E::slots_per_epoch() as usize * 2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // The first request for a state is accepted and the retry is served from the cache.
+    assert_eq!(
+        get_debug_beacon_state_eventually(&tester, "1").await,
+        StatusCode::ACCEPTED
+    );
+    assert_eq!(
+        get_debug_beacon_state_status(&tester, "1").await,
+        StatusCode::OK
+    );
+
+    // Regenerating another state evicts the first from the cache.
+    assert_eq!(
+        get_debug_beacon_state_eventually(&tester, "2").await,
+        StatusCode::ACCEPTED
+    );
+    assert_eq!(
+        get_debug_beacon_state_status(&tester, "2").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_debug_beacon_state_status(&tester, "1").await,
+        StatusCode::ACCEPTED
+    );
+
+    // The head state is never queued.
+    assert_eq!(
+        get_debug_beacon_state_status(&tester, "head").await,
+        StatusCode::OK
+    );
+}
+
+// Test the activation ETA of validators which are queued, pending eligibility or unknown.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn activation_eta_pending_validators() {
+    let validator_count = 32;
+    let queued_index = 30;
+    let ineligible_index = 31;
+    let spec = E::default_spec();
+
+    let keypairs = generate_deterministic_keypairs(validator_count);
+    let mut genesis_state = interop_genesis_state::<E>(
+        &keypairs,
+        HARNESS_GENESIS_TIME,
+        Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+        None,
+        &spec,
+    )
+    .unwrap();
+    for &(index, activation_eligibility_epoch) in &[
+        (queued_index, Epoch::new(0)),
+        (ineligible_index, spec.far_future_epoch),
+    ] {
+        let validator = genesis_state.get_validator_mut(index).unwrap();
+        validator.activation_eligibility_epoch = activation_eligibility_epoch;
+        validator.activation_epoch = spec.far_future_epoch;
+    }
+
+    let tester = InteractiveTester::<E>::new_with_genesis_state(
+        Some(spec.clone()),
+        validator_count,
+        genesis_state,
+    )
+    .await;
+    let client = &tester.client;
+
+    // The queued validator is at the front of the queue, so it is dequeued in the current epoch
+    // and activated after the activation delay.
+    let queued_pubkey = keypairs[queued_index].pk.compress();
+    for validator_id in &[
+        ValidatorId::PublicKey(queued_pubkey),
+        ValidatorId::Index(queued_index as u64),
+    ] {
+        let eta = client
+            .get_lighthouse_validator_activation_eta(validator_id)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(eta.pubkey, queued_pubkey);
+        assert_eq!(eta.status, ActivationStatus::Queued);
+        assert_eq!(eta.validator_index, Some(queued_index as u64));
+        assert_eq!(eta.queue_position, Some(0));
+        assert_eq!(eta.queue_length, 1);
+        assert_eq!(eta.churn_limit, spec.min_per_epoch_churn_limit);
+        assert_eq!(
+            eta.eta_epoch,
+            Some(Epoch::new(1 + spec.max_seed_lookahead.as_u64()))
+        );
+        assert!(eta.eta_timestamp.is_some());
+    }
+
+    let eta = client
+        .get_lighthouse_validator_activation_eta(&ValidatorId::Index(ineligible_index as u64))
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(eta.pubkey, keypairs[ineligible_index].pk.compress());
+    assert_eq!(eta.status, ActivationStatus::PendingEligibility);
+    assert_eq!(eta.validator_index, Some(ineligible_index as u64));
+    assert_eq!(eta.queue_position, None);
+    assert_eq!(eta.eta_epoch, None);
+    assert_eq!(eta.eta_timestamp, None);
+
+    // An index beyond the registry is not known, unlike a public key without a validator.
+    let error = client
+        .get_lighthouse_validator_activation_eta(&ValidatorId::Index(validator_count as u64))
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+}
+
+// Test the slasher status of a beacon chain which feeds blocks and attestations to the slasher.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn slasher_status() {
+    let tempdir = tempdir().unwrap();
+    let slasher_config = slasher::Config::new(tempdir.path().into());
+    let history_length = slasher_config.history_length;
+    let slasher = Arc::new(Slasher::open(slasher_config, test_logger()).unwrap());
+
+    let tester = InteractiveTester::<E>::new_with_slasher(None, 32, slasher.clone()).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+    harness.advance_slot();
+    harness.extend_chain(
+        E::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    // Blocks and attestations imported by the chain are queued until the first batch update.
+    let status = client.get_lighthouse_slasher_status().await.unwrap().data;
+    assert_eq!(status.current_epoch, None);
+    assert_eq!(status.oldest_epoch, None);
+    assert_eq!(status.last_batch, None);
+    assert_eq!(status.history_length, history_length);
+    assert!(status.queued_blocks > 0);
+    assert!(status.queued_attestations > 0);
+    assert_eq!(status.attester_slashings_found, 0);
+    assert_eq!(status.proposer_slashings_found, 0);
+
+    // Queue a double vote and a double proposal by a validator unknown to the chain, so that they
+    // can't conflict with the chain's own blocks and attestations.
+    let validator_index = 1000;
+    slasher.accept_attestation(indexed_att(&[validator_index], 0, 1, 0));
+    slasher.accept_attestation(indexed_att(&[validator_index], 0, 1, 1));
+    slasher.accept_block_header(block(1, validator_index, 0));
+    slasher.accept_block_header(block(1, validator_index, 1));
+
+    let current_epoch = harness.chain.epoch().unwrap();
+    slasher.process_queued(current_epoch).unwrap();
+
+    let status = client.get_lighthouse_slasher_status().await.unwrap().data;
+    assert_eq!(status.current_epoch, Some(current_epoch));
+    assert_eq!(
+        status.oldest_epoch,
+        Some((current_epoch + 1).saturating_sub(history_length as u64))
+    );
+    assert_eq!(status.queued_blocks, 0);
+    assert_eq!(status.queued_attestations, 0);
+    assert!(status.database_size > 0);
+    let last_batch = status.last_batch.unwrap();
+    assert_eq!(last_batch.epoch, current_epoch);
+    assert!(last_batch.blocks > 0);
+    assert!(last_batch.attestations > 0);
+    assert_eq!(status.attester_slashings_found, 1);
+    assert_eq!(status.proposer_slashings_found, 1);
+
+    // The operation pool counts are independent of the slasher, which isn't connected to the pool
+    // without the slasher service.
+    assert_eq!(status.op_pool_attester_slashings, 0);
+    assert_eq!(status.op_pool_proposer_slashings, 0);
+    client
+        .post_beacon_pool_attester_slashings(&harness.make_attester_slashing(vec![0, 1]))
+        .await
+        .unwrap();
+    let status = client.get_lighthouse_slasher_status().await.unwrap().data;
+    assert_eq!(status.op_pool_attester_slashings, 1);
+    assert_eq!(status.attester_slashings_found, 1);
+}
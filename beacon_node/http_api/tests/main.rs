@@ -2,6 +2,7 @@
 #![recursion_limit = "256"]
 
 pub mod common;
+pub mod conformance;
 pub mod fork_tests;
 pub mod interactive_tests;
 pub mod tests;
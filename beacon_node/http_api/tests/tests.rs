@@ -6,6 +6,7 @@ use beacon_chain::{
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::{ActivationStatus, LogLevelOverride, LogLevelRequest},
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::*,
@@ -637,8 +638,25 @@ impl ApiTester {
                         validators
                     });
 
+                    let result_post = self
+                        .client
+                        .post_beacon_states_validators(
+                            state_id,
+                            Some(
+                                validator_index_ids
+                                    .into_iter()
+                                    .chain(validator_pubkey_ids)
+                                    .collect(),
+                            ),
+                            None,
+                        )
+                        .await
+                        .unwrap()
+                        .map(|res| res.data);
+
                     assert_eq!(result_index_ids, expected, "{:?}", state_id);
                     assert_eq!(result_pubkey_ids, expected, "{:?}", state_id);
+                    assert_eq!(result_post, expected, "{:?}", state_id);
                 }
             }
         }
@@ -1312,6 +1330,11 @@ impl ApiTester {
 
         let expected = SyncingData {
             is_syncing: false,
+            el_offline: self
+                .chain
+                .execution_layer
+                .as_ref()
+                .map(|el| el.is_offline()),
             head_slot,
             sync_distance,
         };
@@ -1520,6 +1543,39 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_debug_fork_choice(self) -> Self {
+        let result = self.client.get_debug_fork_choice().await.unwrap();
+
+        let fork_choice = self.chain.fork_choice.read();
+        let proto_array = fork_choice.proto_array().core_proto_array();
+
+        assert_eq!(
+            result.justified_checkpoint,
+            proto_array.justified_checkpoint
+        );
+        assert_eq!(
+            result.finalized_checkpoint,
+            proto_array.finalized_checkpoint
+        );
+
+        let expected = proto_array
+            .nodes
+            .iter()
+            .map(|node| (node.root, node.slot, node.weight))
+            .collect::<Vec<_>>();
+        let nodes = result
+            .fork_choice_nodes
+            .iter()
+            .map(|node| (node.block_root, node.slot, node.weight))
+            .collect::<Vec<_>>();
+
+        assert_eq!(nodes, expected);
+
+        drop(fork_choice);
+
+        self
+    }
+
     fn validator_count(&self) -> usize {
         self.chain.head().unwrap().beacon_state.validators().len()
     }
@@ -1670,6 +1726,12 @@ impl ApiTester {
 
     pub async fn test_get_validator_duties_proposer(self) -> Self {
         let current_epoch = self.chain.epoch().unwrap();
+        let head_epoch = self
+            .chain
+            .head_info()
+            .unwrap()
+            .slot
+            .epoch(E::slots_per_epoch());
 
         for epoch in 0..=self.chain.epoch().unwrap().as_u64() {
             let epoch = Epoch::from(epoch);
@@ -1683,16 +1745,17 @@ impl ApiTester {
                 .unwrap()
                 .unwrap_or(self.chain.head_beacon_block_root().unwrap());
 
-            // Presently, the beacon chain harness never runs the code that primes the proposer
-            // cache. If this changes in the future then we'll need some smarter logic here, but
-            // this is succinct and effective for the time being.
-            assert!(
+            // Block import primes the proposer cache for the epoch of each block, so only the
+            // epochs without blocks should miss initially.
+            let primed_by_import = epoch <= head_epoch;
+            assert_eq!(
                 self.chain
                     .beacon_proposer_cache
                     .lock()
                     .get_epoch::<E>(dependent_root, epoch)
-                    .is_none(),
-                "the proposer cache should miss initially"
+                    .is_some(),
+                primed_by_import,
+                "the proposer cache should only hit for epochs with imported blocks"
             );
 
             let result = self
@@ -1713,12 +1776,13 @@ impl ApiTester {
                     "a current-epoch request should prime the proposer cache"
                 );
             } else {
-                assert!(
+                assert_eq!(
                     self.chain
                         .beacon_proposer_cache
                         .lock()
                         .get_epoch::<E>(dependent_root, epoch)
-                        .is_none(),
+                        .is_some(),
+                    primed_by_import,
                     "a non-current-epoch request should not prime the proposer cache"
                 );
             }
@@ -1907,7 +1971,7 @@ impl ApiTester {
         for index in 0..state.get_committee_count_at_slot(slot).unwrap() {
             let result = self
                 .client
-                .get_validator_attestation_data(slot, index)
+                .get_validator_attestation_data(slot, index, false)
                 .await
                 .unwrap()
                 .data;
@@ -2013,7 +2077,7 @@ impl ApiTester {
 
         let attestation_data = self
             .client
-            .get_validator_attestation_data(duty.slot, duty.committee_index)
+            .get_validator_attestation_data(duty.slot, duty.committee_index, false)
             .await
             .unwrap()
             .data;
@@ -2106,12 +2170,253 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_health_live(self) -> Self {
+        self.client.get_lighthouse_health_live().await.unwrap();
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_health_ready(self) -> Self {
+        let readiness = self
+            .client
+            .get_lighthouse_health_ready()
+            .await
+            .unwrap()
+            .data;
+
+        assert!(readiness.synced);
+        assert!(readiness.connected_peers >= readiness.min_peers);
+        assert_eq!(
+            readiness.ready,
+            readiness.execution_layer_online.unwrap_or(true)
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_syncing(self) -> Self {
         self.client.get_lighthouse_syncing().await.unwrap();
 
         self
     }
 
+    pub async fn test_get_lighthouse_ui_health(self) -> Self {
+        let health = self.client.get_lighthouse_ui_health().await.unwrap().data;
+
+        let head = self.chain.head_info().unwrap();
+        assert_eq!(health.head_slot, head.slot);
+        assert_eq!(health.finalized_epoch, head.finalized_checkpoint.epoch);
+        assert_eq!(
+            health.monitored_validators,
+            self.chain.validator_monitor.read().num_validators() as u64
+        );
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_ui_validator_metrics(self) -> Self {
+        // No validators are monitored, so none should be returned.
+        let metrics = self
+            .client
+            .post_lighthouse_ui_validator_metrics(vec![0, 1])
+            .await
+            .unwrap()
+            .data;
+        assert!(metrics.validators.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_operation_pool(self) -> Self {
+        self.client
+            .post_beacon_pool_proposer_slashings(&self.proposer_slashing)
+            .await
+            .unwrap();
+        self.client
+            .post_beacon_pool_voluntary_exits(&self.voluntary_exit)
+            .await
+            .unwrap();
+
+        let summary = self
+            .client
+            .get_lighthouse_operation_pool()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(summary.sizes.proposer_slashings, 1);
+        assert_eq!(summary.sizes.voluntary_exits, 1);
+        assert_eq!(
+            summary.sizes.attestations,
+            self.chain.op_pool.num_attestations()
+        );
+
+        let proposer_index = self
+            .proposer_slashing
+            .signed_header_1
+            .message
+            .proposer_index;
+        assert_eq!(summary.proposer_slashings.len(), 1);
+        assert_eq!(
+            summary.proposer_slashings[0].validator_indices,
+            vec![proposer_index]
+        );
+        assert!(summary.proposer_slashings[0].included_in_next_block);
+        assert_eq!(summary.proposer_slashings[0].exclusion_reason, None);
+
+        assert_eq!(summary.voluntary_exits.len(), 1);
+        assert_eq!(
+            summary.voluntary_exits[0].validator_indices,
+            vec![self.voluntary_exit.message.validator_index]
+        );
+        assert_eq!(
+            summary.voluntary_exits[0].included_in_next_block,
+            summary.voluntary_exits[0].exclusion_reason.is_none()
+        );
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_slasher_status_disabled(self) -> Self {
+        let error = self
+            .client
+            .get_lighthouse_slasher_status()
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_operation_pool_attestations(self) -> Self {
+        self.client
+            .post_beacon_pool_attestations(self.attestations.as_slice())
+            .await
+            .unwrap();
+
+        let slot = self.attestations[0].data.slot;
+        let committees = self
+            .client
+            .get_lighthouse_operation_pool_attestations::<E>(slot)
+            .await
+            .unwrap()
+            .data;
+
+        for attestation in &self.attestations {
+            assert!(committees
+                .iter()
+                .any(|committee| committee.committee_index == attestation.data.index));
+        }
+
+        for committee in &committees {
+            assert!(!committee.aggregates.is_empty());
+            assert_eq!(committee.committee_size, committee.coverage.len() as u64);
+            assert_eq!(
+                committee.covered_validators,
+                committee.coverage.num_set_bits() as u64
+            );
+
+            for aggregate in &committee.aggregates {
+                assert_eq!(aggregate.data.slot, slot);
+                assert_eq!(aggregate.data.index, committee.committee_index);
+                assert_eq!(
+                    aggregate.aggregation_bits.union(&committee.coverage),
+                    committee.coverage
+                );
+            }
+        }
+
+        let empty = self
+            .client
+            .get_lighthouse_operation_pool_attestations::<E>(slot + 1)
+            .await
+            .unwrap()
+            .data;
+        assert!(empty.is_empty());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_analysis_block_packing(self) -> Self {
+        let end_epoch = self.chain.epoch().unwrap() - 1;
+        let report = self
+            .client
+            .get_lighthouse_analysis_block_packing(Epoch::new(0), end_epoch)
+            .await
+            .unwrap()
+            .data;
+
+        assert!(!report.blocks.is_empty());
+        for block in &report.blocks {
+            assert!(block.slot > 0);
+            assert!(block.slot <= end_epoch.end_slot(E::slots_per_epoch()));
+            assert!(!SKIPPED_SLOTS.contains(&block.slot.as_u64()));
+            assert!(block.included_attestations <= block.available_attestations);
+            assert!(block.efficiency >= 0.0 && block.efficiency <= 100.0);
+        }
+
+        let proposer_blocks = report
+            .proposers
+            .iter()
+            .map(|proposer| proposer.blocks)
+            .sum::<u64>();
+        assert_eq!(proposer_blocks, report.blocks.len() as u64);
+
+        // The start epoch must not be after the end epoch.
+        assert!(self
+            .client
+            .get_lighthouse_analysis_block_packing(end_epoch, Epoch::new(0))
+            .await
+            .is_err());
+
+        // The range must not exceed 100 epochs.
+        let error = self
+            .client
+            .get_lighthouse_analysis_block_packing(Epoch::new(0), Epoch::new(100))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_validator_activation_eta(self) -> Self {
+        let pubkey = self.validator_keypairs[0].pk.compress();
+
+        // Genesis validators are active from the first epoch.
+        for validator_id in &[ValidatorId::PublicKey(pubkey), ValidatorId::Index(0)] {
+            let eta = self
+                .client
+                .get_lighthouse_validator_activation_eta(validator_id)
+                .await
+                .unwrap()
+                .data;
+
+            assert_eq!(eta.pubkey, pubkey);
+            assert_eq!(eta.status, ActivationStatus::Active);
+            assert_eq!(eta.deposit_seen, Some(false));
+            assert_eq!(eta.validator_index, Some(0));
+            assert_eq!(eta.queue_position, None);
+            assert_eq!(eta.queue_length, 0);
+            assert_eq!(eta.eta_epoch, Some(Epoch::new(0)));
+            assert!(eta.eta_timestamp.is_some());
+        }
+
+        let unknown_pubkey = Keypair::random().pk.compress();
+        let eta = self
+            .client
+            .get_lighthouse_validator_activation_eta(&ValidatorId::PublicKey(unknown_pubkey))
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(eta.status, ActivationStatus::Unknown);
+        assert_eq!(eta.validator_index, None);
+        assert_eq!(eta.eta_epoch, None);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_proto_array(self) -> Self {
         self.client.get_lighthouse_proto_array().await.unwrap();
 
@@ -2212,6 +2517,105 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_sync_backfill_pause_resume(mut self) -> Self {
+        let response = self
+            .client
+            .post_lighthouse_sync_backfill_pause()
+            .await
+            .unwrap();
+        assert_eq!(response, "success");
+        assert!(
+            matches!(
+                self.network_rx.recv().await,
+                Some(NetworkMessage::SetBackfillPaused { paused: true })
+            ),
+            "pause should be sent to network"
+        );
+
+        let response = self
+            .client
+            .post_lighthouse_sync_backfill_resume()
+            .await
+            .unwrap();
+        assert_eq!(response, "success");
+        assert!(
+            matches!(
+                self.network_rx.recv().await,
+                Some(NetworkMessage::SetBackfillPaused { paused: false })
+            ),
+            "resume should be sent to network"
+        );
+
+        self
+    }
+
+    pub async fn test_lighthouse_logging(self) -> Self {
+        let request = |module: &str, level: Option<&str>| LogLevelRequest {
+            module: module.to_string(),
+            level: level.map(str::to_string),
+        };
+        let expected = |overrides: &[(&str, &str)]| {
+            overrides
+                .iter()
+                .map(|(module, level)| LogLevelOverride {
+                    module: module.to_string(),
+                    level: level.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(self
+            .client
+            .get_lighthouse_logging()
+            .await
+            .unwrap()
+            .data
+            .is_empty());
+
+        self.client
+            .post_lighthouse_logging(&request("network::sync", Some("trace")))
+            .await
+            .unwrap();
+        let overrides = self
+            .client
+            .post_lighthouse_logging(&request("", Some("WARN")))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(
+            overrides,
+            expected(&[("", "warn"), ("network::sync", "trace")])
+        );
+        assert_eq!(
+            self.client.get_lighthouse_logging().await.unwrap().data,
+            overrides
+        );
+
+        // Invalid levels and unknown overrides are rejected.
+        let error = self
+            .client
+            .post_lighthouse_logging(&request("network", Some("verbose")))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::BAD_REQUEST));
+        let error = self
+            .client
+            .post_lighthouse_logging(&request("network", None))
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+
+        let overrides = self
+            .client
+            .post_lighthouse_logging(&request("", None))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(overrides, expected(&[("network::sync", "trace")]));
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state().unwrap();
@@ -2277,6 +2681,47 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_validator_liveness_epoch(self) -> Self {
+        let epoch = self.chain.epoch().unwrap();
+        let head_state = self.chain.head_beacon_state().unwrap();
+        let indices = (0..head_state.validators().len())
+            .map(|i| i as u64)
+            .collect::<Vec<_>>();
+
+        // The standard endpoint should agree with the Lighthouse endpoint.
+        let expected: Vec<StandardLivenessResponseData> = self
+            .client
+            .post_lighthouse_liveness(indices.as_slice(), epoch)
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|a| StandardLivenessResponseData {
+                index: a.index,
+                is_live: a.is_live,
+            })
+            .collect();
+
+        let result = self
+            .client
+            .post_validator_liveness_epoch(epoch, indices.as_slice())
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(result, expected);
+
+        // Epochs too far from the current epoch should be rejected.
+        let result = self
+            .client
+            .post_validator_liveness_epoch(epoch + 2, indices.as_slice())
+            .await;
+
+        assert!(result.is_err());
+
+        self
+    }
+
     pub async fn test_get_events(self) -> Self {
         // Subscribe to all events
         let topics = vec![
@@ -2367,12 +2812,23 @@ impl ApiTester {
                 .unwrap()
                 .unwrap(),
             epoch: Epoch::new(3),
+            head_slot: Some(next_slot),
+            justified_epoch: Some(Epoch::new(4)),
         });
 
         self.client
             .post_beacon_blocks(&self.next_block)
             .await
             .unwrap();
+        assert_eq!(
+            self.chain
+                .head_info()
+                .unwrap()
+                .current_justified_checkpoint
+                .epoch,
+            Epoch::new(4),
+            "precondition: the finalizing block justifies the previous epoch"
+        );
 
         let block_events = poll_events(&mut events_future, 3, Duration::from_millis(10000)).await;
         assert_eq!(
@@ -2413,6 +2869,154 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_events_slashings(self) -> Self {
+        let topics = vec![EventTopic::AttesterSlashing, EventTopic::ProposerSlashing];
+        let mut events_future = self
+            .client
+            .get_events::<E>(topics.as_slice())
+            .await
+            .unwrap();
+
+        self.client
+            .post_beacon_pool_attester_slashings(&self.attester_slashing)
+            .await
+            .unwrap();
+        self.client
+            .post_beacon_pool_proposer_slashings(&self.proposer_slashing)
+            .await
+            .unwrap();
+
+        let slashing_events =
+            poll_events(&mut events_future, 2, Duration::from_millis(10000)).await;
+        assert_eq!(
+            slashing_events.as_slice(),
+            &[
+                EventKind::AttesterSlashing(Box::new(self.attester_slashing.clone())),
+                EventKind::ProposerSlashing(Box::new(self.proposer_slashing.clone())),
+            ]
+        );
+
+        self
+    }
+
+    pub async fn test_get_events_since(self) -> Self {
+        let topics = vec![EventTopic::Attestation];
+        let expected_events = self
+            .attestations
+            .clone()
+            .into_iter()
+            .map(|attestation| EventKind::Attestation(Box::new(attestation)))
+            .collect::<Vec<_>>();
+        assert!(expected_events.len() > 1);
+
+        // Receive the first event, then disconnect.
+        let first_event = {
+            let mut events_future = self
+                .client
+                .get_events_since::<E>(topics.as_slice(), None)
+                .await
+                .unwrap();
+
+            self.client
+                .post_beacon_pool_attestations(self.attestations.as_slice())
+                .await
+                .unwrap();
+
+            poll_events(&mut events_future, 1, Duration::from_millis(10000))
+                .await
+                .pop()
+                .unwrap()
+        };
+        assert_eq!(first_event.event, expected_events[0]);
+
+        // Upon reconnecting, the remaining events should be replayed in order.
+        let mut events_future = self
+            .client
+            .get_events_since::<E>(topics.as_slice(), Some(first_event.id))
+            .await
+            .unwrap();
+        let replayed_events = poll_events(
+            &mut events_future,
+            expected_events.len() - 1,
+            Duration::from_millis(10000),
+        )
+        .await;
+
+        assert_eq!(
+            replayed_events
+                .iter()
+                .map(|event| event.event.clone())
+                .collect::<Vec<_>>(),
+            expected_events[1..]
+        );
+        assert!(replayed_events[0].id > first_event.id);
+        assert!(replayed_events
+            .windows(2)
+            .all(|pair| pair[0].id < pair[1].id));
+
+        self
+    }
+
+    pub async fn test_get_events_since_disconnected(self) -> Self {
+        let topics = vec![EventTopic::Attestation];
+        let expected_events = self
+            .attestations
+            .clone()
+            .into_iter()
+            .map(|attestation| EventKind::Attestation(Box::new(attestation)))
+            .collect::<Vec<_>>();
+        assert!(expected_events.len() > 1);
+
+        // Receive the event of the first attestation, then disconnect.
+        let first_event = {
+            let mut events_future = self
+                .client
+                .get_events_since::<E>(topics.as_slice(), None)
+                .await
+                .unwrap();
+
+            self.client
+                .post_beacon_pool_attestations(&self.attestations[..1])
+                .await
+                .unwrap();
+
+            poll_events(&mut events_future, 1, Duration::from_millis(10000))
+                .await
+                .pop()
+                .unwrap()
+        };
+        assert_eq!(first_event.event, expected_events[0]);
+
+        // Publish the remaining attestations whilst disconnected.
+        self.client
+            .post_beacon_pool_attestations(&self.attestations[1..])
+            .await
+            .unwrap();
+
+        // Upon reconnecting, the events missed whilst disconnected should be replayed in order.
+        let mut events_future = self
+            .client
+            .get_events_since::<E>(topics.as_slice(), Some(first_event.id))
+            .await
+            .unwrap();
+        let replayed_events = poll_events(
+            &mut events_future,
+            expected_events.len() - 1,
+            Duration::from_millis(10000),
+        )
+        .await;
+
+        assert_eq!(
+            replayed_events
+                .into_iter()
+                .map(|event| event.event)
+                .collect::<Vec<_>>(),
+            expected_events[1..]
+        );
+
+        self
+    }
+
     pub async fn test_get_events_altair(self) -> Self {
         let topics = vec![EventTopic::ContributionAndProof];
         let mut events_future = self
@@ -2484,11 +3088,11 @@ impl ApiTester {
     }
 }
 
-async fn poll_events<S: Stream<Item = Result<EventKind<T>, eth2::Error>> + Unpin, T: EthSpec>(
+async fn poll_events<S: Stream<Item = Result<V, eth2::Error>> + Unpin, V>(
     stream: &mut S,
     num_events: usize,
     timeout: Duration,
-) -> Vec<EventKind<T>> {
+) -> Vec<V> {
     let mut events = Vec::new();
 
     let collect_stream_fut = async {
@@ -2513,6 +3117,24 @@ async fn get_events() {
     ApiTester::new().await.test_get_events().await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_events_slashings() {
+    ApiTester::new().await.test_get_events_slashings().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_events_since() {
+    ApiTester::new().await.test_get_events_since().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_events_since_disconnected() {
+    ApiTester::new()
+        .await
+        .test_get_events_since_disconnected()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_events_altair() {
     let mut spec = E::default_spec();
@@ -2669,6 +3291,8 @@ async fn debug_get() {
         .test_get_debug_beacon_states()
         .await
         .test_get_debug_beacon_heads()
+        .await
+        .test_get_debug_fork_choice()
         .await;
 }
 
@@ -2830,8 +3454,26 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_health()
         .await
+        .test_get_lighthouse_health_live()
+        .await
+        .test_get_lighthouse_health_ready()
+        .await
         .test_get_lighthouse_syncing()
         .await
+        .test_get_lighthouse_ui_health()
+        .await
+        .test_post_lighthouse_ui_validator_metrics()
+        .await
+        .test_get_lighthouse_operation_pool()
+        .await
+        .test_get_lighthouse_validator_activation_eta()
+        .await
+        .test_get_lighthouse_operation_pool_attestations()
+        .await
+        .test_get_lighthouse_slasher_status_disabled()
+        .await
+        .test_get_lighthouse_analysis_block_packing()
+        .await
         .test_get_lighthouse_proto_array()
         .await
         .test_get_lighthouse_validator_inclusion()
@@ -2852,6 +3494,12 @@ async fn lighthouse_endpoints() {
         .await
         .test_post_lighthouse_database_reconstruct()
         .await
+        .test_post_lighthouse_sync_backfill_pause_resume()
+        .await
+        .test_lighthouse_logging()
+        .await
         .test_post_lighthouse_liveness()
+        .await
+        .test_post_validator_liveness_epoch()
         .await;
 }
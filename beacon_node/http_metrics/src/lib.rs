@@ -11,8 +11,15 @@ use slog::{crit, info, Logger};
 use std::future::Future;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::Arc;
 use warp::{http::Response, Filter};
+use warp_utils::cors::CorsDefaults;
+
+pub use warp_utils::tls::TlsConfig;
+
+/// A custom type which allows for both unsecured and TLS-enabled HTTP servers.
+type HttpServer = (SocketAddr, Pin<Box<dyn Future<Output = ()> + Send>>);
 
 #[derive(Debug)]
 pub enum Error {
@@ -51,7 +58,14 @@ pub struct Config {
     pub listen_addr: Ipv4Addr,
     pub listen_port: u16,
     pub allow_origin: Option<String>,
+    /// A comma-separated list of the methods allowed by CORS, replacing the defaults.
+    pub allow_methods: Option<String>,
+    /// A comma-separated list of the headers allowed by CORS, replacing the defaults.
+    pub allow_headers: Option<String>,
+    /// Whether CORS requests may include credentials.
+    pub allow_credentials: bool,
     pub allocator_metrics_enabled: bool,
+    pub tls_config: Option<TlsConfig>,
 }
 
 impl Default for Config {
@@ -61,7 +75,11 @@ impl Default for Config {
             listen_addr: Ipv4Addr::new(127, 0, 0, 1),
             listen_port: 5054,
             allow_origin: None,
+            allow_methods: None,
+            allow_headers: None,
+            allow_credentials: false,
             allocator_metrics_enabled: true,
+            tls_config: None,
         }
     }
 }
@@ -84,22 +102,22 @@ impl Default for Config {
 pub fn serve<T: BeaconChainTypes>(
     ctx: Arc<Context<T>>,
     shutdown: impl Future<Output = ()> + Send + Sync + 'static,
-) -> Result<(SocketAddr, impl Future<Output = ()>), Error> {
+) -> Result<HttpServer, Error> {
     let config = &ctx.config;
     let log = ctx.log.clone();
 
     // Configure CORS.
-    let cors_builder = {
-        let builder = warp::cors()
-            .allow_method("GET")
-            .allow_headers(vec!["Content-Type"]);
-
-        warp_utils::cors::set_builder_origins(
-            builder,
-            config.allow_origin.as_deref(),
-            (config.listen_addr, config.listen_port),
-        )?
-    };
+    let cors_builder = warp_utils::cors::build_cors(
+        config.allow_origin.as_deref(),
+        config.allow_methods.as_deref(),
+        config.allow_headers.as_deref(),
+        config.allow_credentials,
+        CorsDefaults {
+            origin: (config.listen_addr, config.listen_port),
+            methods: &["GET"],
+            headers: &["Content-Type"],
+        },
+    )?;
 
     // Sanity check.
     if !config.enabled {
@@ -130,12 +148,29 @@ pub fn serve<T: BeaconChainTypes>(
         .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
         .with(cors_builder.build());
 
-    let (listening_socket, server) = warp::serve(routes).try_bind_with_graceful_shutdown(
-        SocketAddrV4::new(config.listen_addr, config.listen_port),
-        async {
-            shutdown.await;
-        },
-    )?;
+    let http_socket = SocketAddrV4::new(config.listen_addr, config.listen_port);
+    let (listening_socket, server): HttpServer = match &config.tls_config {
+        Some(tls_config) => {
+            let (socket, server) = warp::serve(routes)
+                .tls()
+                .cert_path(&tls_config.cert)
+                .key_path(&tls_config.key)
+                .try_bind_with_graceful_shutdown(http_socket, async {
+                    shutdown.await;
+                })?;
+
+            info!(log, "Metrics HTTP server is being served over TLS";);
+
+            (socket, Box::pin(server))
+        }
+        None => {
+            let (socket, server) =
+                warp::serve(routes).try_bind_with_graceful_shutdown(http_socket, async {
+                    shutdown.await;
+                })?;
+            (socket, Box::pin(server))
+        }
+    };
 
     info!(
         log,
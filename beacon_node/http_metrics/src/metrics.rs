@@ -37,6 +37,7 @@ pub fn gather_prometheus_metrics<T: BeaconChainTypes>(
         (ctx.db_path.as_ref(), ctx.freezer_db_path.as_ref())
     {
         store::scrape_for_metrics(db_path, freezer_db_path);
+        warp_utils::metrics::scrape_datadir_metrics(db_path);
     }
 
     lighthouse_network::scrape_discovery_metrics();
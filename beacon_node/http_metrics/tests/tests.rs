@@ -20,7 +20,11 @@ async fn returns_200_ok() {
                 listen_addr: Ipv4Addr::new(127, 0, 0, 1),
                 listen_port: 0,
                 allow_origin: None,
+                allow_methods: None,
+                allow_headers: None,
+                allow_credentials: false,
                 allocator_metrics_enabled: true,
+                tls_config: None,
             },
             chain: None,
             db_path: None,
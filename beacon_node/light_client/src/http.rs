@@ -0,0 +1,224 @@
+//! Serves the subset of the beacon node API which can be answered from light client data.
+//!
+//! This is enough for a validator client to check the node's network and sync status, and for
+//! light clients to follow the chain through this node. Any other route is rejected with a 404.
+
+use crate::{Config, LightClient};
+use eth2::types::{ForkVersionedResponse, GenericResponse, SyncingData, VersionData};
+use lighthouse_version::version_with_platform;
+use slog::{info, Logger};
+use std::future::Future;
+use std::net::{SocketAddr, SocketAddrV4};
+use std::pin::Pin;
+use std::sync::Arc;
+use types::EthSpec;
+use warp::Filter;
+use warp_utils::cors::CorsDefaults;
+use warp_utils::task::{blocking_json_task, blocking_task};
+
+/// A custom type which allows for both unsecured and TLS-enabled HTTP servers.
+type HttpServer = (SocketAddr, Pin<Box<dyn Future<Output = ()> + Send>>);
+
+#[derive(Debug)]
+pub enum Error {
+    Warp(warp::Error),
+    Other(String),
+}
+
+impl From<warp::Error> for Error {
+    fn from(e: warp::Error) -> Self {
+        Error::Warp(e)
+    }
+}
+
+impl From<String> for Error {
+    fn from(e: String) -> Self {
+        Error::Other(e)
+    }
+}
+
+/// Creates a server that will serve requests using information from `light_client`.
+///
+/// The server will shut down gracefully when the `shutdown` future resolves.
+pub fn serve<E: EthSpec>(
+    config: &Config,
+    light_client: Arc<LightClient<E>>,
+    shutdown: impl Future<Output = ()> + Send + Sync + 'static,
+    log: Logger,
+) -> Result<HttpServer, Error> {
+    // Configure CORS.
+    let cors_builder = warp_utils::cors::build_cors(
+        config.allow_origin.as_deref(),
+        config.allow_methods.as_deref(),
+        config.allow_headers.as_deref(),
+        config.allow_credentials,
+        CorsDefaults {
+            origin: (config.listen_addr, config.listen_port),
+            methods: &["GET"],
+            headers: &["Content-Type"],
+        },
+    )?;
+
+    let light_client_filter = warp::any().map(move || light_client.clone());
+    let eth1_v1 = warp::path("eth").and(warp::path("v1"));
+
+    // GET beacon/genesis
+    let get_beacon_genesis = eth1_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("genesis"))
+        .and(warp::path::end())
+        .and(light_client_filter.clone())
+        .and_then(|light_client: Arc<LightClient<E>>| {
+            blocking_json_task(move || Ok(GenericResponse::from(light_client.genesis.clone())))
+        });
+
+    // GET beacon/light_client/finality_update
+    let get_beacon_light_client_finality_update = eth1_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("light_client"))
+        .and(warp::path("finality_update"))
+        .and(warp::path::end())
+        .and(light_client_filter.clone())
+        .and_then(|light_client: Arc<LightClient<E>>| {
+            blocking_json_task(move || {
+                let update = light_client
+                    .latest_finality_update
+                    .read()
+                    .clone()
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(
+                            "no light client finality update available".into(),
+                        )
+                    })?;
+                let fork_name = light_client
+                    .spec
+                    .fork_name_at_slot::<E>(update.attested_header.beacon.slot);
+                Ok(ForkVersionedResponse {
+                    version: Some(fork_name),
+                    data: update,
+                })
+            })
+        });
+
+    // GET beacon/light_client/optimistic_update
+    let get_beacon_light_client_optimistic_update = eth1_v1
+        .and(warp::path("beacon"))
+        .and(warp::path("light_client"))
+        .and(warp::path("optimistic_update"))
+        .and(warp::path::end())
+        .and(light_client_filter.clone())
+        .and_then(|light_client: Arc<LightClient<E>>| {
+            blocking_json_task(move || {
+                let update = light_client
+                    .latest_optimistic_update
+                    .read()
+                    .clone()
+                    .ok_or_else(|| {
+                        warp_utils::reject::custom_not_found(
+                            "no light client optimistic update available".into(),
+                        )
+                    })?;
+                let fork_name = light_client
+                    .spec
+                    .fork_name_at_slot::<E>(update.attested_header.beacon.slot);
+                Ok(ForkVersionedResponse {
+                    version: Some(fork_name),
+                    data: update,
+                })
+            })
+        });
+
+    // GET node/version
+    let get_node_version = eth1_v1
+        .and(warp::path("node"))
+        .and(warp::path("version"))
+        .and(warp::path::end())
+        .and_then(|| {
+            blocking_json_task(move || {
+                Ok(GenericResponse::from(VersionData {
+                    version: version_with_platform(),
+                }))
+            })
+        });
+
+    // GET node/syncing
+    let get_node_syncing = eth1_v1
+        .and(warp::path("node"))
+        .and(warp::path("syncing"))
+        .and(warp::path::end())
+        .and(light_client_filter.clone())
+        .and_then(|light_client: Arc<LightClient<E>>| {
+            blocking_json_task(move || {
+                Ok(GenericResponse::from(SyncingData {
+                    is_syncing: !light_client.is_synced(),
+                    el_offline: None,
+                    head_slot: light_client.head_slot(),
+                    sync_distance: light_client.sync_distance(),
+                }))
+            })
+        });
+
+    // GET node/health
+    let get_node_health = eth1_v1
+        .and(warp::path("node"))
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(light_client_filter)
+        .and_then(|light_client: Arc<LightClient<E>>| {
+            blocking_task(move || {
+                let status = if light_client.is_synced() {
+                    warp::http::StatusCode::OK
+                } else {
+                    warp::http::StatusCode::PARTIAL_CONTENT
+                };
+                Ok(warp::reply::with_status(warp::reply(), status))
+            })
+        });
+
+    let routes = warp::get()
+        .and(
+            get_beacon_genesis
+                .boxed()
+                .or(get_beacon_light_client_finality_update.boxed())
+                .or(get_beacon_light_client_optimistic_update.boxed())
+                .or(get_node_version.boxed())
+                .or(get_node_syncing.boxed())
+                .or(get_node_health.boxed()),
+        )
+        .recover(warp_utils::reject::handle_rejection)
+        // Add a `Server` header.
+        .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
+        .with(cors_builder.build());
+
+    let http_socket = SocketAddrV4::new(config.listen_addr, config.listen_port);
+    let (listening_socket, server): HttpServer = match &config.tls_config {
+        Some(tls_config) => {
+            let (socket, server) = warp::serve(routes)
+                .tls()
+                .cert_path(&tls_config.cert)
+                .key_path(&tls_config.key)
+                .try_bind_with_graceful_shutdown(http_socket, async {
+                    shutdown.await;
+                })?;
+
+            info!(log, "Light client HTTP server is being served over TLS";);
+
+            (socket, Box::pin(server))
+        }
+        None => {
+            let (socket, server) =
+                warp::serve(routes).try_bind_with_graceful_shutdown(http_socket, async {
+                    shutdown.await;
+                })?;
+            (socket, Box::pin(server))
+        }
+    };
+
+    info!(
+        log,
+        "Light client HTTP server started";
+        "listen_address" => listening_socket.to_string(),
+    );
+
+    Ok((listening_socket, server))
+}
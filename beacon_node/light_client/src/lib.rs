@@ -0,0 +1,317 @@
+//! Runs the beacon node in "light" mode, where the chain is tracked using light client updates
+//! fetched from an upstream beacon node rather than by importing blocks.
+//!
+//! Updates are verified against the sync committee (see `store`), so the upstream node only needs
+//! to be trusted for availability. A restricted subset of the beacon node API is served from the
+//! verified headers (see `http`), which allows the node to act as a cheap fallback for a validator
+//! client whilst its full beacon node is resyncing.
+mod http;
+pub mod store;
+
+use eth2::{types::GenesisData, BeaconNodeHttpClient, Timeouts};
+use parking_lot::RwLock;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slog::{debug, info, warn, Logger};
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::Duration;
+use store::LightClientStore;
+use task_executor::TaskExecutor;
+use types::{
+    ChainSpec, EthSpec, Hash256, LightClientFinalityUpdate, LightClientOptimisticUpdate, Slot,
+};
+
+pub use warp_utils::tls::TlsConfig;
+
+/// The maximum number of updates which may be requested from the upstream node at once.
+const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+/// The timeout for requests to the upstream node.
+const UPSTREAM_TIMEOUT: Duration = Duration::from_secs(12);
+
+/// The node is considered synced if its optimistic header is at most this many slots behind the
+/// wall-clock slot.
+const SYNC_TOLERANCE_SLOTS: u64 = 8;
+
+/// Configuration for running in light mode.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// The beacon node which serves the light client data.
+    pub upstream: SensitiveUrl,
+    /// The root of a block which is trusted to be canonical, from which to start syncing.
+    pub trusted_block_root: Hash256,
+    pub listen_addr: Ipv4Addr,
+    pub listen_port: u16,
+    pub allow_origin: Option<String>,
+    /// A comma-separated list of the methods allowed by CORS, replacing the defaults.
+    pub allow_methods: Option<String>,
+    /// A comma-separated list of the headers allowed by CORS, replacing the defaults.
+    pub allow_headers: Option<String>,
+    /// Whether CORS requests may include credentials.
+    pub allow_credentials: bool,
+    pub tls_config: Option<TlsConfig>,
+}
+
+impl Config {
+    /// Returns a config for `upstream` and `trusted_block_root`, serving the API on the default
+    /// address of the beacon node API.
+    pub fn new(upstream: SensitiveUrl, trusted_block_root: Hash256) -> Self {
+        Self {
+            upstream,
+            trusted_block_root,
+            listen_addr: Ipv4Addr::new(127, 0, 0, 1),
+            listen_port: 5052,
+            allow_origin: None,
+            allow_methods: None,
+            allow_headers: None,
+            allow_credentials: false,
+            tls_config: None,
+        }
+    }
+}
+
+/// The state of a light client, shared between the sync task and the HTTP server.
+pub struct LightClient<E: EthSpec> {
+    store: RwLock<LightClientStore<E>>,
+    /// The most recent updates which were successfully applied, served via the HTTP API.
+    latest_finality_update: RwLock<Option<LightClientFinalityUpdate<E>>>,
+    latest_optimistic_update: RwLock<Option<LightClientOptimisticUpdate<E>>>,
+    genesis: GenesisData,
+    slot_clock: SystemTimeSlotClock,
+    spec: ChainSpec,
+}
+
+impl<E: EthSpec> LightClient<E> {
+    /// Returns the slot of the most recent header signed by the sync committee.
+    pub fn head_slot(&self) -> Slot {
+        self.store.read().optimistic_header().slot
+    }
+
+    /// Returns the slot of the most recent finalized header.
+    pub fn finalized_slot(&self) -> Slot {
+        self.store.read().finalized_header().slot
+    }
+
+    /// Returns the number of slots the optimistic header is behind the wall-clock slot.
+    pub fn sync_distance(&self) -> Slot {
+        self.slot_clock
+            .now()
+            .map_or(Slot::new(0), |current_slot| current_slot - self.head_slot())
+    }
+
+    /// Returns `true` if the optimistic header is close enough to the wall-clock slot for the node
+    /// to be considered synced.
+    pub fn is_synced(&self) -> bool {
+        self.sync_distance() <= SYNC_TOLERANCE_SLOTS
+    }
+}
+
+/// Bootstraps a light client from `config.upstream`, then spawns a task which follows the chain
+/// and the HTTP server which serves it.
+pub async fn start<E: EthSpec>(
+    config: Config,
+    spec: ChainSpec,
+    executor: TaskExecutor,
+) -> Result<(), String> {
+    let log = executor.log().clone();
+    let upstream =
+        BeaconNodeHttpClient::new(config.upstream.clone(), Timeouts::set_all(UPSTREAM_TIMEOUT));
+
+    let genesis = upstream
+        .get_beacon_genesis()
+        .await
+        .map_err(|e| format!("Unable to fetch genesis from upstream node: {:?}", e))?
+        .data;
+    if genesis.genesis_fork_version != spec.genesis_fork_version {
+        return Err(format!(
+            "Upstream node is on a different network, genesis fork version {:?} != {:?}",
+            genesis.genesis_fork_version, spec.genesis_fork_version
+        ));
+    }
+
+    let slot_clock = SystemTimeSlotClock::new(
+        spec.genesis_slot,
+        Duration::from_secs(genesis.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+
+    let bootstrap = upstream
+        .get_light_client_bootstrap::<E>(config.trusted_block_root)
+        .await
+        .map_err(|e| format!("Unable to fetch light client bootstrap: {:?}", e))?
+        .ok_or_else(|| {
+            format!(
+                "Upstream node has no light client bootstrap for block {:?}",
+                config.trusted_block_root
+            )
+        })?
+        .data;
+    let store = LightClientStore::from_bootstrap(
+        config.trusted_block_root,
+        bootstrap,
+        genesis.genesis_validators_root,
+    )
+    .map_err(|e| format!("Invalid light client bootstrap: {:?}", e))?;
+
+    info!(
+        log,
+        "Light client bootstrapped";
+        "slot" => store.finalized_header().slot,
+        "block_root" => ?config.trusted_block_root,
+    );
+
+    let light_client = Arc::new(LightClient {
+        store: RwLock::new(store),
+        latest_finality_update: RwLock::new(None),
+        latest_optimistic_update: RwLock::new(None),
+        genesis,
+        slot_clock,
+        spec,
+    });
+
+    let exit = executor.exit();
+    let (_, server) = http::serve(&config, light_client.clone(), exit, log.clone())
+        .map_err(|e| format!("Unable to start light client HTTP server: {:?}", e))?;
+    executor.spawn_without_exit(async move { server.await }, "light-client-http");
+
+    executor.spawn(sync_task(light_client, upstream, log), "light-client-sync");
+
+    Ok(())
+}
+
+/// Follows the chain by polling the upstream node once per slot.
+async fn sync_task<E: EthSpec>(
+    light_client: Arc<LightClient<E>>,
+    upstream: BeaconNodeHttpClient,
+    log: Logger,
+) {
+    loop {
+        if let Err(e) = sync_step(&light_client, &upstream).await {
+            warn!(log, "Light client sync failed"; "error" => e);
+        } else {
+            debug!(
+                log,
+                "Light client synced";
+                "head_slot" => light_client.head_slot(),
+                "finalized_slot" => light_client.finalized_slot(),
+            );
+        }
+
+        let sleep_duration = light_client
+            .slot_clock
+            .duration_to_next_slot()
+            .unwrap_or_else(|| light_client.slot_clock.slot_duration());
+        // Poll a third of the way into the slot, once the sync aggregate of the previous slot has
+        // been included in a block.
+        tokio::time::sleep(sleep_duration + light_client.slot_clock.slot_duration() / 3).await;
+    }
+}
+
+/// Applies any updates the upstream node has which advance the store.
+async fn sync_step<E: EthSpec>(
+    light_client: &LightClient<E>,
+    upstream: &BeaconNodeHttpClient,
+) -> Result<(), String> {
+    let spec = &light_client.spec;
+    let current_slot = light_client
+        .slot_clock
+        .now()
+        .ok_or("Unable to read slot clock")?;
+    let current_period = store::period_at_slot::<E>(current_slot, spec)
+        .map_err(|e| format!("Unable to compute period: {:?}", e))?;
+
+    // Catch up with the sync committee of the current period, one period at a time.
+    loop {
+        let (finalized_period, knows_next) = {
+            let store = light_client.store.read();
+            (
+                store
+                    .finalized_period(spec)
+                    .map_err(|e| format!("Unable to compute period: {:?}", e))?,
+                store.knows_next_sync_committee(),
+            )
+        };
+        if finalized_period >= current_period && knows_next {
+            break;
+        }
+
+        let count = std::cmp::min(
+            current_period.saturating_sub(finalized_period) + 1,
+            MAX_REQUEST_LIGHT_CLIENT_UPDATES,
+        );
+        let updates = upstream
+            .get_light_client_updates::<E>(finalized_period, count)
+            .await
+            .map_err(|e| format!("Unable to fetch updates: {:?}", e))?;
+
+        let mut advanced = false;
+        for update in updates {
+            let mut store = light_client.store.write();
+            let knew_next = store.knows_next_sync_committee();
+            let finalized_advanced = match store.process_update(update.data, current_slot, spec) {
+                Ok(finalized_advanced) => finalized_advanced,
+                Err(store::Error::Irrelevant) => false,
+                Err(e) => return Err(format!("Invalid light client update: {:?}", e)),
+            };
+            advanced |= finalized_advanced || knew_next != store.knows_next_sync_committee();
+        }
+        if !advanced {
+            break;
+        }
+    }
+
+    if let Some(update) = upstream
+        .get_light_client_finality_update::<E>()
+        .await
+        .map_err(|e| format!("Unable to fetch finality update: {:?}", e))?
+    {
+        let is_applied = applied(light_client.store.write().process_finality_update(
+            update.data.clone(),
+            current_slot,
+            spec,
+        ))
+        .map_err(|e| format!("Invalid light client finality update: {:?}", e))?;
+        if is_applied {
+            *light_client.latest_finality_update.write() = Some(update.data);
+        }
+    }
+
+    if let Some(update) = upstream
+        .get_light_client_optimistic_update::<E>()
+        .await
+        .map_err(|e| format!("Unable to fetch optimistic update: {:?}", e))?
+    {
+        let is_applied = applied(light_client.store.write().process_optimistic_update(
+            update.data.clone(),
+            current_slot,
+            spec,
+        ))
+        .map_err(|e| format!("Invalid light client optimistic update: {:?}", e))?;
+        if is_applied {
+            *light_client.latest_optimistic_update.write() = Some(update.data);
+        }
+    }
+
+    // Without finality, the store can only follow the sync committee by forcing an update.
+    light_client
+        .store
+        .write()
+        .process_force_update(current_slot, spec)
+        .map_err(|e| format!("Unable to force light client update: {:?}", e))?;
+
+    Ok(())
+}
+
+/// Returns `Ok(true)` if an update was applied to the store.
+///
+/// Updates which do not advance the store are expected whilst polling, so are not treated as an
+/// error.
+fn applied<T>(result: Result<T, store::Error>) -> Result<bool, store::Error> {
+    match result {
+        Ok(_) => Ok(true),
+        Err(store::Error::Irrelevant) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
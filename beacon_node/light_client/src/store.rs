@@ -0,0 +1,690 @@
+//! Tracks the chain from light client data, following the light client sync protocol.
+//!
+//! The store is initialized from a `LightClientBootstrap` for a trusted block root. From then on,
+//! each update is only applied once its Merkle branches have been verified against the state root
+//! of its attested header and its sync aggregate has been verified against the sync committee
+//! known to the store. No state transitions are performed.
+//!
+//! If the chain does not finalize for a whole sync committee period, the store falls back to the
+//! best update seen since it last advanced (see `LightClientStore::process_force_update`).
+
+use bls::PublicKey;
+use merkle_proof::verify_merkle_proof;
+use std::cmp::Reverse;
+use std::sync::Arc;
+use tree_hash::TreeHash;
+use types::light_client_update::{
+    CURRENT_SYNC_COMMITTEE_INDEX, CURRENT_SYNC_COMMITTEE_PROOF_LEN, FINALIZED_ROOT_INDEX,
+    FINALIZED_ROOT_PROOF_LEN, NEXT_SYNC_COMMITTEE_INDEX, NEXT_SYNC_COMMITTEE_PROOF_LEN,
+};
+use types::{
+    BeaconBlockHeader, ChainSpec, Domain, EthSpec, Hash256, LightClientBootstrap,
+    LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate, SignedRoot, Slot,
+    SyncAggregate, SyncCommittee,
+};
+
+#[derive(Debug)]
+pub enum Error {
+    /// The header of the bootstrap does not have the trusted block root.
+    BootstrapRootMismatch {
+        expected: Hash256,
+        found: Hash256,
+    },
+    /// The sync committee of the bootstrap could not be proven against its header.
+    InvalidCurrentSyncCommitteeBranch,
+    /// Fewer than `MIN_SYNC_COMMITTEE_PARTICIPANTS` signed the update.
+    InsufficientParticipants {
+        participants: usize,
+    },
+    /// The slots of the update are not ordered as required, or are in the future.
+    InvalidSlotOrdering,
+    /// The update was signed by a sync committee which is unknown to the store.
+    UnknownSigningSyncCommittee {
+        signature_period: u64,
+    },
+    /// The update would not advance the store.
+    Irrelevant,
+    InvalidFinalityBranch,
+    InvalidNextSyncCommitteeBranch,
+    /// The update provides a next sync committee which conflicts with the one already known.
+    ConflictingNextSyncCommittee,
+    InvalidPubkey(bls::Error),
+    InvalidSignature,
+    ArithError(safe_arith::ArithError),
+}
+
+impl From<safe_arith::ArithError> for Error {
+    fn from(e: safe_arith::ArithError) -> Self {
+        Error::ArithError(e)
+    }
+}
+
+/// The parts of any of the light client update types, where the parts which are not present in
+/// all types are optional.
+#[derive(Clone)]
+struct GenericUpdate<E: EthSpec> {
+    attested_header: BeaconBlockHeader,
+    next_sync_committee: Option<(Arc<SyncCommittee<E>>, Vec<Hash256>)>,
+    finalized_header: Option<(BeaconBlockHeader, Vec<Hash256>)>,
+    sync_aggregate: SyncAggregate<E>,
+    signature_slot: Slot,
+}
+
+impl<E: EthSpec> From<LightClientUpdate<E>> for GenericUpdate<E> {
+    fn from(update: LightClientUpdate<E>) -> Self {
+        // Updates without finality have an empty finality branch.
+        let has_finality = update.finality_branch.iter().any(|node| !node.is_zero());
+        Self {
+            attested_header: update.attested_header.beacon,
+            next_sync_committee: Some((
+                update.next_sync_committee,
+                update.next_sync_committee_branch.to_vec(),
+            )),
+            finalized_header: if has_finality {
+                Some((
+                    update.finalized_header.beacon,
+                    update.finality_branch.to_vec(),
+                ))
+            } else {
+                None
+            },
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
+
+impl<E: EthSpec> From<LightClientFinalityUpdate<E>> for GenericUpdate<E> {
+    fn from(update: LightClientFinalityUpdate<E>) -> Self {
+        Self {
+            attested_header: update.attested_header.beacon,
+            next_sync_committee: None,
+            finalized_header: Some((
+                update.finalized_header.beacon,
+                update.finality_branch.to_vec(),
+            )),
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
+
+impl<E: EthSpec> From<LightClientOptimisticUpdate<E>> for GenericUpdate<E> {
+    fn from(update: LightClientOptimisticUpdate<E>) -> Self {
+        Self {
+            attested_header: update.attested_header.beacon,
+            next_sync_committee: None,
+            finalized_header: None,
+            sync_aggregate: update.sync_aggregate,
+            signature_slot: update.signature_slot,
+        }
+    }
+}
+
+/// The view of the chain held by a light client.
+pub struct LightClientStore<E: EthSpec> {
+    finalized_header: BeaconBlockHeader,
+    optimistic_header: BeaconBlockHeader,
+    current_sync_committee: Arc<SyncCommittee<E>>,
+    next_sync_committee: Option<Arc<SyncCommittee<E>>>,
+    /// The greatest number of participants in an update of the previous and current periods, used
+    /// to decide whether an update is safe to apply to the optimistic header.
+    previous_max_active_participants: usize,
+    current_max_active_participants: usize,
+    /// The most useful valid update since the finalized header last advanced, which is applied if
+    /// the finalized header does not advance for `update_timeout` slots.
+    best_valid_update: Option<GenericUpdate<E>>,
+    genesis_validators_root: Hash256,
+}
+
+impl<E: EthSpec> LightClientStore<E> {
+    /// Initializes the store from `bootstrap`, which must be for the block `trusted_block_root`.
+    pub fn from_bootstrap(
+        trusted_block_root: Hash256,
+        bootstrap: LightClientBootstrap<E>,
+        genesis_validators_root: Hash256,
+    ) -> Result<Self, Error> {
+        let header = bootstrap.header.beacon;
+        let header_root = header.tree_hash_root();
+        if header_root != trusted_block_root {
+            return Err(Error::BootstrapRootMismatch {
+                expected: trusted_block_root,
+                found: header_root,
+            });
+        }
+
+        if !verify_merkle_proof(
+            bootstrap.current_sync_committee.tree_hash_root(),
+            &bootstrap.current_sync_committee_branch,
+            CURRENT_SYNC_COMMITTEE_PROOF_LEN,
+            CURRENT_SYNC_COMMITTEE_INDEX % (1 << CURRENT_SYNC_COMMITTEE_PROOF_LEN),
+            header.state_root,
+        ) {
+            return Err(Error::InvalidCurrentSyncCommitteeBranch);
+        }
+
+        Ok(Self {
+            finalized_header: header.clone(),
+            optimistic_header: header,
+            current_sync_committee: bootstrap.current_sync_committee,
+            next_sync_committee: None,
+            previous_max_active_participants: 0,
+            current_max_active_participants: 0,
+            best_valid_update: None,
+            genesis_validators_root,
+        })
+    }
+
+    pub fn finalized_header(&self) -> &BeaconBlockHeader {
+        &self.finalized_header
+    }
+
+    pub fn optimistic_header(&self) -> &BeaconBlockHeader {
+        &self.optimistic_header
+    }
+
+    /// Returns `true` if the sync committee of the period after that of the finalized header is
+    /// known.
+    pub fn knows_next_sync_committee(&self) -> bool {
+        self.next_sync_committee.is_some()
+    }
+
+    /// Returns the sync committee period of the finalized header.
+    pub fn finalized_period(&self, spec: &ChainSpec) -> Result<u64, Error> {
+        period_at_slot::<E>(self.finalized_header.slot, spec)
+    }
+
+    /// Verifies `update` and applies it to the store.
+    ///
+    /// Returns `true` if the finalized header advanced.
+    pub fn process_update(
+        &mut self,
+        update: LightClientUpdate<E>,
+        current_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<bool, Error> {
+        self.process_generic_update(update.into(), current_slot, spec)
+    }
+
+    /// Verifies `update` and applies it to the store.
+    ///
+    /// Returns `true` if the finalized header advanced.
+    pub fn process_finality_update(
+        &mut self,
+        update: LightClientFinalityUpdate<E>,
+        current_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<bool, Error> {
+        self.process_generic_update(update.into(), current_slot, spec)
+    }
+
+    /// Verifies `update` and applies it to the store.
+    pub fn process_optimistic_update(
+        &mut self,
+        update: LightClientOptimisticUpdate<E>,
+        current_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<(), Error> {
+        self.process_generic_update(update.into(), current_slot, spec)
+            .map(|_| ())
+    }
+
+    fn process_generic_update(
+        &mut self,
+        update: GenericUpdate<E>,
+        current_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<bool, Error> {
+        self.validate_update(&update, current_slot, spec)?;
+
+        if self
+            .best_valid_update
+            .as_ref()
+            .map_or(true, |best| update_rank(&update) > update_rank(best))
+        {
+            self.best_valid_update = Some(update.clone());
+        }
+
+        let participants = update.sync_aggregate.num_set_bits();
+        self.current_max_active_participants =
+            std::cmp::max(self.current_max_active_participants, participants);
+
+        // Only follow the optimistic header if enough of the committee signed it.
+        if participants > self.safety_threshold()
+            && update.attested_header.slot > self.optimistic_header.slot
+        {
+            self.optimistic_header = update.attested_header.clone();
+        }
+
+        // Finality and sync committee changes require a supermajority of the committee.
+        if !has_supermajority::<E>(participants) {
+            return Ok(false);
+        }
+
+        let finalized_advanced = self.apply_update(update, spec)?;
+        if finalized_advanced {
+            self.best_valid_update = None;
+        }
+        Ok(finalized_advanced)
+    }
+
+    /// Applies the best valid update if the finalized header has not advanced for a whole sync
+    /// committee period, as per `process_light_client_store_force_update` in the light client
+    /// sync protocol.
+    ///
+    /// The attested header of the update is treated as finalized if the update does not advance
+    /// finality, so that the store can follow the sync committee through periods of
+    /// non-finality.
+    ///
+    /// Returns `true` if an update was applied.
+    pub fn process_force_update(
+        &mut self,
+        current_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<bool, Error> {
+        let update_timeout = spec
+            .epochs_per_sync_committee_period
+            .start_slot(E::slots_per_epoch());
+        if current_slot <= self.finalized_header.slot + update_timeout {
+            return Ok(false);
+        }
+
+        let mut update = match self.best_valid_update.take() {
+            Some(update) => update,
+            None => return Ok(false),
+        };
+        let advances_finality = update
+            .finalized_header
+            .as_ref()
+            .map_or(false, |(header, _)| {
+                header.slot > self.finalized_header.slot
+            });
+        if !advances_finality {
+            update.finalized_header = Some((update.attested_header.clone(), vec![]));
+        }
+
+        self.apply_update(update, spec)
+    }
+
+    /// Applies the sync committee and finalized header of a verified `update` to the store.
+    ///
+    /// Returns `true` if the finalized header advanced.
+    fn apply_update(&mut self, update: GenericUpdate<E>, spec: &ChainSpec) -> Result<bool, Error> {
+        let store_period = self.finalized_period(spec)?;
+        let attested_period = period_at_slot::<E>(update.attested_header.slot, spec)?;
+        if let Some((next_sync_committee, _)) = &update.next_sync_committee {
+            if self.next_sync_committee.is_none() && attested_period == store_period {
+                self.next_sync_committee = Some(next_sync_committee.clone());
+            }
+        }
+
+        let (finalized_header, _) = match update.finalized_header {
+            Some(finalized) if finalized.0.slot > self.finalized_header.slot => finalized,
+            _ => return Ok(false),
+        };
+
+        let finalized_period = period_at_slot::<E>(finalized_header.slot, spec)?;
+        if finalized_period > store_period {
+            // Validation ensures that the next sync committee is known.
+            if let Some(next_sync_committee) = self.next_sync_committee.take() {
+                self.current_sync_committee = next_sync_committee;
+                // An update attesting to the new period may already prove its next committee.
+                self.next_sync_committee = update
+                    .next_sync_committee
+                    .filter(|_| attested_period == finalized_period)
+                    .map(|(committee, _)| committee);
+                self.previous_max_active_participants = self.current_max_active_participants;
+                self.current_max_active_participants = 0;
+            }
+        }
+
+        if finalized_header.slot > self.optimistic_header.slot {
+            self.optimistic_header = finalized_header.clone();
+        }
+        self.finalized_header = finalized_header;
+
+        Ok(true)
+    }
+
+    /// The number of participants an update must exceed to be applied to the optimistic header.
+    fn safety_threshold(&self) -> usize {
+        std::cmp::max(
+            self.previous_max_active_participants,
+            self.current_max_active_participants,
+        ) / 2
+    }
+
+    /// Verifies `update` against the store, as per `validate_light_client_update` in the light
+    /// client sync protocol.
+    fn validate_update(
+        &self,
+        update: &GenericUpdate<E>,
+        current_slot: Slot,
+        spec: &ChainSpec,
+    ) -> Result<(), Error> {
+        let sync_aggregate = &update.sync_aggregate;
+        let participants = sync_aggregate.num_set_bits();
+        if (participants as u64) < spec.min_sync_committee_participants {
+            return Err(Error::InsufficientParticipants { participants });
+        }
+
+        let attested_slot = update.attested_header.slot;
+        let finalized_slot = update
+            .finalized_header
+            .as_ref()
+            .map_or(Slot::new(0), |(header, _)| header.slot);
+        if !(current_slot >= update.signature_slot
+            && update.signature_slot > attested_slot
+            && attested_slot >= finalized_slot)
+        {
+            return Err(Error::InvalidSlotOrdering);
+        }
+
+        let store_period = self.finalized_period(spec)?;
+        let signature_period = period_at_slot::<E>(update.signature_slot, spec)?;
+        let sync_committee = if signature_period == store_period {
+            &self.current_sync_committee
+        } else {
+            match &self.next_sync_committee {
+                Some(next) if signature_period == store_period + 1 => next,
+                _ => return Err(Error::UnknownSigningSyncCommittee { signature_period }),
+            }
+        };
+
+        let attested_period = period_at_slot::<E>(attested_slot, spec)?;
+        let learns_next_sync_committee = self.next_sync_committee.is_none()
+            && update.next_sync_committee.is_some()
+            && attested_period == store_period;
+        if attested_slot <= self.finalized_header.slot && !learns_next_sync_committee {
+            return Err(Error::Irrelevant);
+        }
+
+        if let Some((finalized_header, finality_branch)) = &update.finalized_header {
+            if !verify_merkle_proof(
+                finalized_header.tree_hash_root(),
+                finality_branch,
+                FINALIZED_ROOT_PROOF_LEN,
+                FINALIZED_ROOT_INDEX % (1 << FINALIZED_ROOT_PROOF_LEN),
+                update.attested_header.state_root,
+            ) {
+                return Err(Error::InvalidFinalityBranch);
+            }
+        }
+
+        if let Some((next_sync_committee, next_sync_committee_branch)) = &update.next_sync_committee
+        {
+            if attested_period == store_period {
+                if let Some(known) = &self.next_sync_committee {
+                    if known != next_sync_committee {
+                        return Err(Error::ConflictingNextSyncCommittee);
+                    }
+                }
+            }
+            if !verify_merkle_proof(
+                next_sync_committee.tree_hash_root(),
+                next_sync_committee_branch,
+                NEXT_SYNC_COMMITTEE_PROOF_LEN,
+                NEXT_SYNC_COMMITTEE_INDEX % (1 << NEXT_SYNC_COMMITTEE_PROOF_LEN),
+                update.attested_header.state_root,
+            ) {
+                return Err(Error::InvalidNextSyncCommitteeBranch);
+            }
+        }
+
+        let participant_pubkeys = sync_committee
+            .pubkeys
+            .iter()
+            .zip(sync_aggregate.sync_committee_bits.iter())
+            .filter(|(_, bit)| *bit)
+            .map(|(pubkey, _)| pubkey.decompress().map_err(Error::InvalidPubkey))
+            .collect::<Result<Vec<PublicKey>, _>>()?;
+
+        // The committee signs with the fork of the slot prior to the signature slot.
+        let fork_epoch = std::cmp::max(update.signature_slot, Slot::new(1))
+            .saturating_sub(1u64)
+            .epoch(E::slots_per_epoch());
+        let domain = spec.compute_domain(
+            Domain::SyncCommittee,
+            spec.fork_version_for_name(spec.fork_name_at_epoch(fork_epoch)),
+            self.genesis_validators_root,
+        );
+        let signing_root = update.attested_header.signing_root(domain);
+        if !sync_aggregate
+            .sync_committee_signature
+            .fast_aggregate_verify(
+                signing_root,
+                &participant_pubkeys.iter().collect::<Vec<_>>(),
+            )
+        {
+            return Err(Error::InvalidSignature);
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if `participants` is a supermajority of the sync committee.
+fn has_supermajority<E: EthSpec>(participants: usize) -> bool {
+    participants * 3 >= E::sync_committee_size() * 2
+}
+
+/// Ranks updates such that a greater rank is more useful to the store.
+///
+/// In order of priority, an update is preferred if it has a supermajority of the sync committee,
+/// if it proves finality, if it has more participants, and finally if it is older.
+fn update_rank<E: EthSpec>(update: &GenericUpdate<E>) -> (bool, bool, usize, Reverse<Slot>) {
+    let participants = update.sync_aggregate.num_set_bits();
+    (
+        has_supermajority::<E>(participants),
+        update.finalized_header.is_some(),
+        participants,
+        Reverse(update.attested_header.slot),
+    )
+}
+
+/// Returns the sync committee period of `slot`.
+pub fn period_at_slot<E: EthSpec>(slot: Slot, spec: &ChainSpec) -> Result<u64, Error> {
+    slot.epoch(E::slots_per_epoch())
+        .sync_committee_period(spec)
+        .map_err(Into::into)
+}
+
+#[cfg(all(test, not(debug_assertions)))]
+mod release_tests {
+    use super::*;
+    use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+    use beacon_chain::ChainConfig;
+    use types::{ForkName, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 24;
+
+    /// Returns a harness serving light client data which has produced `num_blocks` blocks, each
+    /// signed by the whole sync committee.
+    fn get_harness(num_blocks: u64) -> BeaconChainHarness<EphemeralHarnessType<E>> {
+        let harness = BeaconChainHarness::builder(E::default())
+            .spec(ForkName::Altair.make_genesis_spec(E::default_spec()))
+            .deterministic_keypairs(VALIDATOR_COUNT)
+            .fresh_ephemeral_store()
+            .chain_config(ChainConfig {
+                enable_light_client_server: true,
+                ..ChainConfig::default()
+            })
+            .build();
+        harness.advance_slot();
+        harness.extend_chain_with_sync_aggregates(num_blocks as usize);
+        harness
+    }
+
+    fn genesis_bootstrap(
+        harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+    ) -> LightClientBootstrap<E> {
+        harness
+            .chain
+            .get_light_client_bootstrap(&harness.chain.genesis_block_root)
+            .unwrap()
+            .expect("should have a bootstrap for the genesis block")
+    }
+
+    /// Returns a store bootstrapped from the genesis block of `harness`.
+    fn genesis_store(harness: &BeaconChainHarness<EphemeralHarnessType<E>>) -> LightClientStore<E> {
+        LightClientStore::from_bootstrap(
+            harness.chain.genesis_block_root,
+            genesis_bootstrap(harness),
+            harness.chain.genesis_validators_root,
+        )
+        .unwrap()
+    }
+
+    fn best_update(
+        harness: &BeaconChainHarness<EphemeralHarnessType<E>>,
+        period: u64,
+    ) -> LightClientUpdate<E> {
+        harness
+            .chain
+            .get_light_client_updates(period, 1)
+            .unwrap()
+            .pop()
+            .expect("should have an update for the period")
+    }
+
+    #[test]
+    fn valid_finality_update_applied() {
+        let harness = get_harness(E::slots_per_epoch() * 5);
+        let spec = &harness.chain.spec;
+        let current_slot = harness.chain.slot().unwrap();
+        let mut store = genesis_store(&harness);
+
+        let update = harness
+            .chain
+            .latest_light_client_finality_update()
+            .expect("the chain should have finalized");
+        assert!(store
+            .process_finality_update(update.clone(), current_slot, spec)
+            .unwrap());
+        assert_eq!(store.finalized_header(), &update.finalized_header.beacon);
+        assert_eq!(store.optimistic_header(), &update.attested_header.beacon);
+
+        // Applying the same update again does not advance the finalized header.
+        assert!(!store
+            .process_finality_update(update, current_slot, spec)
+            .unwrap());
+    }
+
+    #[test]
+    fn invalid_branches_rejected() {
+        let harness = get_harness(E::slots_per_epoch() * 5);
+        let spec = &harness.chain.spec;
+        let current_slot = harness.chain.slot().unwrap();
+
+        let mut bootstrap = genesis_bootstrap(&harness);
+        bootstrap.current_sync_committee_branch[0] = Hash256::repeat_byte(1);
+        assert!(matches!(
+            LightClientStore::from_bootstrap(
+                harness.chain.genesis_block_root,
+                bootstrap,
+                harness.chain.genesis_validators_root,
+            ),
+            Err(Error::InvalidCurrentSyncCommitteeBranch)
+        ));
+
+        let mut store = genesis_store(&harness);
+
+        let mut finality_update = harness
+            .chain
+            .latest_light_client_finality_update()
+            .expect("the chain should have finalized");
+        finality_update.finality_branch[0] = Hash256::repeat_byte(1);
+        assert!(matches!(
+            store.process_finality_update(finality_update, current_slot, spec),
+            Err(Error::InvalidFinalityBranch)
+        ));
+
+        let mut update = best_update(&harness, 0);
+        update.next_sync_committee_branch[0] = Hash256::repeat_byte(1);
+        assert!(matches!(
+            store.process_update(update, current_slot, spec),
+            Err(Error::InvalidNextSyncCommitteeBranch)
+        ));
+
+        // The store is unchanged by the invalid updates.
+        assert_eq!(store.finalized_header().slot, Slot::new(0));
+        assert!(!store.knows_next_sync_committee());
+    }
+
+    #[test]
+    fn sync_committee_rotates_at_period_boundary() {
+        let slots_per_period = harness_slots_per_period();
+        // Run far enough into the second period for it to be finalized.
+        let harness = get_harness(slots_per_period + E::slots_per_epoch() * 4);
+        let spec = &harness.chain.spec;
+        let current_slot = harness.chain.slot().unwrap();
+        let head_state = harness.chain.head_beacon_state().unwrap();
+        let mut store = genesis_store(&harness);
+        let genesis_sync_committee = store.current_sync_committee.clone();
+
+        // The best update of the first period proves the committee of the second.
+        store
+            .process_update(best_update(&harness, 0), current_slot, spec)
+            .unwrap();
+        assert_eq!(store.finalized_period(spec).unwrap(), 0);
+        assert!(store.knows_next_sync_committee());
+
+        // An update signed by the second committee, finalizing a block in the second period.
+        let update = harness
+            .chain
+            .latest_light_client_finality_update()
+            .expect("the chain should have finalized");
+        assert_eq!(period_at_slot::<E>(update.signature_slot, spec).unwrap(), 1);
+        assert!(store
+            .process_finality_update(update, current_slot, spec)
+            .unwrap());
+
+        assert_eq!(store.finalized_period(spec).unwrap(), 1);
+        assert_eq!(
+            &store.current_sync_committee,
+            head_state.current_sync_committee().unwrap()
+        );
+        assert_ne!(store.current_sync_committee, genesis_sync_committee);
+    }
+
+    #[test]
+    fn force_update_after_timeout() {
+        // Too few blocks for the chain to finalize.
+        let harness = get_harness(E::slots_per_epoch() * 2);
+        let spec = &harness.chain.spec;
+        let current_slot = harness.chain.slot().unwrap();
+        let mut store = genesis_store(&harness);
+
+        let update = best_update(&harness, 0);
+        assert!(update.finality_branch.iter().all(|node| node.is_zero()));
+        assert!(!store
+            .process_update(update.clone(), current_slot, spec)
+            .unwrap());
+        assert_eq!(store.finalized_header().slot, Slot::new(0));
+
+        // The finalized header is only forced once a whole period passes without finality.
+        let update_timeout = Slot::new(harness_slots_per_period());
+        assert!(!store.process_force_update(update_timeout, spec).unwrap());
+        assert_eq!(store.finalized_header().slot, Slot::new(0));
+
+        assert!(store
+            .process_force_update(update_timeout + 1, spec)
+            .unwrap());
+        assert_eq!(store.finalized_header(), &update.attested_header.beacon);
+
+        // The best update is only applied once.
+        assert!(!store
+            .process_force_update(update_timeout + update_timeout, spec)
+            .unwrap());
+    }
+
+    fn harness_slots_per_period() -> u64 {
+        ForkName::Altair
+            .make_genesis_spec(E::default_spec())
+            .epochs_per_sync_committee_period
+            .as_u64()
+            * E::slots_per_epoch()
+    }
+}
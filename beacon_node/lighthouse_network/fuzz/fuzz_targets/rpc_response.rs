@@ -0,0 +1,8 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use lighthouse_network::fuzz::decode_rpc_response;
+use types::MainnetEthSpec;
+
+fuzz_target!(|data: &[u8]| {
+    decode_rpc_response::<MainnetEthSpec>(data);
+});
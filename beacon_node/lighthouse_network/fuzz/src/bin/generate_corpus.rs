@@ -0,0 +1,71 @@
+//! Generates corpora for the `gossip_message` and `rpc_response` fuzz targets from recorded
+//! blocks.
+//!
+//! Usage: `cargo run --bin generate_corpus -- <blocks-dir> <corpus-dir>`
+//!
+//! Each file in `<blocks-dir>` must contain an SSZ-encoded `SignedBeaconBlock` with the mainnet
+//! preset, such as those served by `GET /eth/v2/beacon/blocks/{block_id}` with an
+//! `application/octet-stream` `Accept` header. Blocks are decoded without a fork schedule, so they
+//! may be taken from any network and fork, including forks which are not yet scheduled on mainnet. The blocks are written to `<corpus-dir>/<target>/` in the encodings in which
+//! they are received over gossip and RPC.
+use lighthouse_network::fuzz::{gossip_block_corpus_input, rpc_block_corpus_inputs};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use types::{MainnetEthSpec, SignedBeaconBlock};
+
+type E = MainnetEthSpec;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("Usage: {} <blocks-dir> <corpus-dir>", args[0]);
+        exit(1);
+    }
+
+    if let Err(e) = generate(Path::new(&args[1]), Path::new(&args[2])) {
+        eprintln!("{}", e);
+        exit(1);
+    }
+}
+
+fn generate(blocks_dir: &Path, corpus_dir: &Path) -> Result<(), String> {
+    let gossip_dir = target_dir(corpus_dir, "gossip_message")?;
+    let rpc_dir = target_dir(corpus_dir, "rpc_response")?;
+
+    let entries = fs::read_dir(blocks_dir)
+        .map_err(|e| format!("Unable to read {:?}: {:?}", blocks_dir, e))?;
+    let mut count = 0;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Unable to read {:?}: {:?}", blocks_dir, e))?
+            .path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| format!("Invalid file name {:?}", path))?
+            .to_string();
+        let bytes = fs::read(&path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+        let block = SignedBeaconBlock::<E>::any_from_ssz_bytes(&bytes)
+            .map_err(|e| format!("Unable to decode block {:?}: {:?}", path, e))?;
+
+        write(&gossip_dir.join(&name), &gossip_block_corpus_input(&block))?;
+        for (i, input) in rpc_block_corpus_inputs(&block).iter().enumerate() {
+            write(&rpc_dir.join(format!("{}-{}", name, i)), input)?;
+        }
+        count += 1;
+    }
+
+    println!("Generated corpus inputs from {} blocks", count);
+    Ok(())
+}
+
+fn target_dir(corpus_dir: &Path, target: &str) -> Result<PathBuf, String> {
+    let dir = corpus_dir.join(target);
+    fs::create_dir_all(&dir).map_err(|e| format!("Unable to create {:?}: {:?}", dir, e))?;
+    Ok(dir)
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    fs::write(path, bytes).map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+}
@@ -35,7 +35,7 @@ use libp2p::{
 };
 use slog::{crit, debug, o, trace, warn};
 use ssz::Encode;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -57,6 +57,15 @@ pub const TARGET_SUBNET_PEERS: usize = 2;
 
 const MAX_IDENTIFY_ADDRESSES: usize = 10;
 
+/// The maximum number of gossip messages awaiting validation whose kinds are retained. Messages
+/// which are never validated (e.g., because they were dropped by a full processing queue) would
+/// otherwise be retained forever.
+const MAX_UNVALIDATED_MESSAGE_KINDS: usize = 16_384;
+
+/// The number of first deliveries of accepted gossip messages to buffer before recording them in
+/// the peer database, so that its lock is not taken for every message.
+const FIRST_DELIVERY_BATCH_SIZE: usize = 256;
+
 /// Identifier of requests sent by a peer.
 pub type PeerRequestId = (ConnectionId, SubstreamId);
 
@@ -177,6 +186,13 @@ pub struct Behaviour<TSpec: EthSpec> {
     /// The interval for updating gossipsub scores
     #[behaviour(ignore)]
     update_gossipsub_scores: tokio::time::Interval,
+    /// The kinds of the gossip messages which are awaiting validation, keyed by `message_key`.
+    #[behaviour(ignore)]
+    unvalidated_message_kinds: HashMap<u64, &'static str>,
+    /// The peers which first delivered accepted gossip messages, and the kinds of the messages,
+    /// which are yet to be recorded in the peer database.
+    #[behaviour(ignore)]
+    first_deliveries: Vec<(PeerId, &'static str)>,
     /// Logger for behaviour actions.
     #[behaviour(ignore)]
     log: slog::Logger,
@@ -277,6 +293,8 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             discovery_enabled: !config.disable_discovery,
             metrics_enabled: config.metrics_enabled,
             target_peer_count: config.target_peers,
+            score_config: config.peer_score.clone(),
+            admission: config.peer_admission.clone(),
             ..Default::default()
         };
 
@@ -298,6 +316,8 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             score_settings,
             fork_context: ctx.fork_context,
             update_gossipsub_scores,
+            unvalidated_message_kinds: HashMap::new(),
+            first_deliveries: Vec::with_capacity(FIRST_DELIVERY_BATCH_SIZE),
         })
     }
 
@@ -369,6 +389,14 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         }
     }
 
+    /// Unsubscribe from every topic, e.g. before shutting down.
+    pub fn unsubscribe_all(&mut self) {
+        let subscriptions = self.network_globals.gossipsub_subscriptions.read().clone();
+        for topic in subscriptions {
+            self.unsubscribe(topic);
+        }
+    }
+
     /// Subscribes to a gossipsub topic.
     ///
     /// Returns `true` if the subscription was successful and `false` otherwise.
@@ -458,6 +486,17 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         message_id: MessageId,
         validation_result: MessageAcceptance,
     ) {
+        let kind = self
+            .unvalidated_message_kinds
+            .remove(&message_key(&message_id));
+        if let (MessageAcceptance::Accept, Some(kind)) = (&validation_result, kind) {
+            // The propagation source of a message is the peer which first delivered it.
+            self.first_deliveries.push((*propagation_source, kind));
+            if self.first_deliveries.len() >= FIRST_DELIVERY_BATCH_SIZE {
+                self.record_first_deliveries();
+            }
+        }
+
         if let Some(result) = match validation_result {
             MessageAcceptance::Accept => None,
             MessageAcceptance::Ignore => Some("ignore"),
@@ -486,6 +525,24 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         }
     }
 
+    /// Records the buffered first deliveries of accepted gossip messages in the peer database.
+    fn record_first_deliveries(&mut self) {
+        if self.first_deliveries.is_empty() {
+            return;
+        }
+
+        let mut peers = self.network_globals.peers.write();
+        for (peer_id, kind) in self.first_deliveries.drain(..) {
+            peers.register_first_message_delivery(&peer_id, kind);
+            if let Some(info) = peers.peer_info(&peer_id) {
+                metrics::inc_counter_vec(
+                    &metrics::GOSSIP_FIRST_DELIVERIES_PER_CLIENT,
+                    &[info.client().kind.as_ref(), kind],
+                );
+            }
+        }
+    }
+
     /// Updates the current gossipsub scoring parameters based on the validator count and current
     /// slot.
     pub fn update_gossipsub_parameters(
@@ -568,6 +625,11 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         self.peer_manager.goodbye_peer(peer_id, reason, source);
     }
 
+    /// Disconnects from every connected peer without banning them, providing a reason.
+    pub fn disconnect_all_peers(&mut self, reason: GoodbyeReason) {
+        self.peer_manager.disconnect_all_peers(reason);
+    }
+
     /// Returns an iterator over all enr entries in the DHT.
     pub fn enr_entries(&mut self) -> Vec<Enr> {
         self.discovery.table_entries_enr()
@@ -857,6 +919,14 @@ impl<TSpec: EthSpec> NetworkBehaviourEventProcess<GossipsubEvent> for Behaviour<
                         }
                     }
                     Ok(msg) => {
+                        // Gossipsub only emits the first delivery of each message. Remember its
+                        // kind so that the delivery can be recorded if the message is accepted.
+                        if self.unvalidated_message_kinds.len() >= MAX_UNVALIDATED_MESSAGE_KINDS {
+                            self.unvalidated_message_kinds.clear();
+                        }
+                        self.unvalidated_message_kinds
+                            .insert(message_key(&id), msg.kind().into());
+
                         // Notify the network
                         self.add_event(BehaviourEvent::PubsubMessage {
                             id,
@@ -1123,6 +1193,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
         // perform gossipsub score updates when necessary
         while self.update_gossipsub_scores.poll_tick(cx).is_ready() {
             self.peer_manager.update_gossipsub_scores(&self.gossipsub);
+            self.record_first_deliveries();
         }
 
         Poll::Pending
@@ -1172,6 +1243,7 @@ impl<TSpec: EthSpec> NetworkBehaviourEventProcess<PeerManagerEvent> for Behaviou
             PeerManagerEvent::DisconnectPeer(peer_id, reason) => {
                 debug!(self.log, "Peer Manager disconnecting peer";
                        "peer_id" => %peer_id, "reason" => %reason);
+                metrics::inc_counter_vec(&metrics::PEER_GOODBYES_SENT, &[&reason.to_string()]);
                 // send one goodbye
                 self.eth2_rpc.shutdown(peer_id, reason);
             }
@@ -1238,6 +1310,17 @@ impl<TSpec: EthSpec> std::convert::From<Response<TSpec>> for RPCCodedResponse<TS
     }
 }
 
+/// Returns a key for the gossip message with the given `id`, without copying the id.
+///
+/// Message ids are prefixes of a hash of the message, so their leading bytes are unique for all
+/// practical purposes.
+fn message_key(id: &MessageId) -> u64 {
+    let mut key = [0; 8];
+    let len = std::cmp::min(id.0.len(), key.len());
+    key[..len].copy_from_slice(&id.0[..len]);
+    u64::from_le_bytes(key)
+}
+
 /// Persist metadata to disk
 pub fn save_metadata_to_disk<E: EthSpec>(dir: &Path, metadata: MetaData<E>, log: &slog::Logger) {
     let _ = std::fs::create_dir_all(&dir);
@@ -1257,3 +1340,23 @@ pub fn save_metadata_to_disk<E: EthSpec>(dir: &Path, metadata: MetaData<E>, log:
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_key_uses_leading_bytes() {
+        let id = MessageId::new(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(
+            message_key(&id),
+            u64::from_le_bytes([1, 2, 3, 4, 5, 6, 7, 8])
+        );
+        assert_ne!(
+            message_key(&id),
+            message_key(&MessageId::new(&[1, 2, 3, 4, 5, 6, 7, 9, 9, 10]))
+        );
+        // Short ids are padded.
+        assert_eq!(message_key(&MessageId::new(&[1])), 1);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::peer_manager::admission::AdmissionConfig;
+use crate::peer_manager::peerdb::score::PeerScoreConfig;
 use crate::types::GossipKind;
 use crate::{Enr, PeerIdSerialized};
 use directory::{
@@ -114,6 +116,9 @@ pub struct Config {
     /// runtime.
     pub import_all_attestations: bool,
 
+    /// Subscribe to the light client gossip topics, serving updates to light clients.
+    pub enable_light_client_server: bool,
+
     /// A setting specifying a range of values that tune the network parameters of lighthouse. The
     /// lower the value the less bandwidth used, but the slower messages will be received.
     pub network_load: u8,
@@ -125,11 +130,24 @@ pub struct Config {
     /// Shutdown beacon node after sync is completed.
     pub shutdown_after_sync: bool,
 
+    /// Do not perform a backfill sync until it is resumed via the HTTP API.
+    pub disable_backfill: bool,
+
+    /// If set, chain segments that fail to process due to an internal error are written to this
+    /// directory for inclusion in bug reports.
+    pub failed_batch_dir: Option<PathBuf>,
+
     /// List of extra topics to initially subscribe to as strings.
     pub topics: Vec<GossipKind>,
 
     /// Whether metrics are enabled.
     pub metrics_enabled: bool,
+
+    /// The penalties applied to peers for each `PeerAction` and whether they are applied.
+    pub peer_score: PeerScoreConfig,
+
+    /// The limits on inbound peers sharing an IP prefix or ASN.
+    pub peer_admission: AdmissionConfig,
 }
 
 impl Default for Config {
@@ -203,9 +221,14 @@ impl Default for Config {
             private: false,
             subscribe_all_subnets: false,
             import_all_attestations: false,
+            enable_light_client_server: false,
             shutdown_after_sync: false,
+            disable_backfill: false,
+            failed_batch_dir: None,
             topics: Vec::new(),
             metrics_enabled: false,
+            peer_score: PeerScoreConfig::default(),
+            peer_admission: AdmissionConfig::default(),
         }
     }
 }
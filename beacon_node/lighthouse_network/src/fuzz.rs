@@ -0,0 +1,283 @@
+//! Entrypoints for fuzzing the decoders which handle bytes received from peers.
+//!
+//! These back the `cargo-fuzz` targets in `fuzz/` and are only compiled with the `fuzzing`
+//! feature, or for the smoke tests below. No input to an entrypoint should ever cause a panic; a panic is a bug, since a peer can
+//! send us the same bytes.
+//!
+//! The first byte of each input selects what the remaining bytes are decoded as (e.g. which RPC
+//! protocol or gossip topic), so that a single target covers every message type. The `*_corpus_*`
+//! functions build inputs in the same format from known-good messages, which gives the fuzzer a
+//! corpus of realistic messages to mutate.
+use crate::rpc::codec::{
+    base::{BaseInboundCodec, BaseOutboundCodec},
+    ssz_snappy::{SSZSnappyInboundCodec, SSZSnappyOutboundCodec},
+    InboundCodec, OutboundCodec,
+};
+use crate::rpc::protocol::{supported_protocol_ids, Protocol, ProtocolId, Version};
+use crate::rpc::{max_rpc_size, RPCCodedResponse, RPCResponse};
+use crate::types::{GossipEncoding, GossipKind, GossipTopic, PubsubMessage, SnappyTransform};
+use crate::{gossip_max_size, IdentTopic};
+use libp2p::bytes::BytesMut;
+use ssz::Encode;
+use std::sync::Arc;
+use tokio_util::codec::{Decoder, Encoder};
+use types::{
+    Epoch, EthSpec, ForkContext, ForkName, Hash256, SignedBeaconBlock, Slot, SubnetId, SyncSubnetId,
+};
+
+/// Returns a `ForkContext` in which every fork is enabled, so that messages of every fork can be
+/// decoded.
+fn fork_context<E: EthSpec>() -> Arc<ForkContext> {
+    let mut spec = E::default_spec();
+    spec.altair_fork_epoch = Some(Epoch::new(1));
+    spec.bellatrix_fork_epoch = Some(Epoch::new(2));
+    Arc::new(ForkContext::new::<E>(Slot::new(0), Hash256::zero(), &spec))
+}
+
+/// Returns the fork of `block`, according to its variant rather than its slot.
+fn block_fork_name<E: EthSpec>(block: &SignedBeaconBlock<E>) -> ForkName {
+    match block {
+        SignedBeaconBlock::Base { .. } => ForkName::Base,
+        SignedBeaconBlock::Altair { .. } => ForkName::Altair,
+        SignedBeaconBlock::Merge { .. } => ForkName::Merge,
+    }
+}
+
+/// Returns the protocol selected by `selector`.
+fn select_protocol(selector: u8) -> ProtocolId {
+    let protocols = supported_protocol_ids();
+    protocols[selector as usize % protocols.len()].clone()
+}
+
+/// Returns the gossip topics which are selected between by the first byte of an input to
+/// `decode_gossip_message`, for a single fork.
+fn gossip_kinds() -> Vec<GossipKind> {
+    vec![
+        GossipKind::BeaconBlock,
+        GossipKind::BeaconAggregateAndProof,
+        GossipKind::Attestation(SubnetId::new(0)),
+        GossipKind::VoluntaryExit,
+        GossipKind::ProposerSlashing,
+        GossipKind::AttesterSlashing,
+        GossipKind::SignedContributionAndProof,
+        GossipKind::SyncCommitteeMessage(SyncSubnetId::new(0)),
+        GossipKind::LightClientFinalityUpdate,
+        GossipKind::LightClientOptimisticUpdate,
+    ]
+}
+
+/// Returns the topic selected by `selector`.
+fn select_gossip_topic(selector: u8, fork_context: &ForkContext) -> Option<IdentTopic> {
+    let kinds = gossip_kinds();
+    let forks = ForkName::list_all();
+    let kind = kinds[selector as usize % kinds.len()].clone();
+    let fork_name = forks[(selector as usize / kinds.len()) % forks.len()];
+    let fork_digest = fork_context.to_context_bytes(fork_name)?;
+    Some(GossipTopic::new(kind, GossipEncoding::SSZSnappy, fork_digest).into())
+}
+
+/// Decodes `data` as an RPC request received on an inbound stream.
+pub fn decode_rpc_request<E: EthSpec>(data: &[u8]) {
+    let (selector, bytes) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let fork_context = fork_context::<E>();
+    let mut codec =
+        InboundCodec::<E>::SSZSnappy(BaseInboundCodec::new(SSZSnappyInboundCodec::new(
+            select_protocol(*selector),
+            max_rpc_size(&fork_context),
+            fork_context,
+        )));
+
+    // Only a single request is read from an inbound stream.
+    let _ = codec.decode(&mut BytesMut::from(bytes));
+}
+
+/// Decodes `data` as the chunks of an RPC response received on an outbound stream.
+pub fn decode_rpc_response<E: EthSpec>(data: &[u8]) {
+    let (selector, bytes) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    decode_rpc_response_chunks::<E>(*selector, bytes);
+}
+
+/// Decodes `bytes` as in `decode_rpc_response`, returning the chunks which were decoded.
+fn decode_rpc_response_chunks<E: EthSpec>(selector: u8, bytes: &[u8]) -> Vec<RPCCodedResponse<E>> {
+    let fork_context = fork_context::<E>();
+    let mut codec =
+        OutboundCodec::<E>::SSZSnappy(BaseOutboundCodec::new(SSZSnappyOutboundCodec::new(
+            select_protocol(selector),
+            max_rpc_size(&fork_context),
+            fork_context,
+        )));
+
+    // Decode chunks until the stream is exhausted or the codec stops making progress.
+    let mut src = BytesMut::from(bytes);
+    let mut chunks = vec![];
+    loop {
+        let remaining = src.len();
+        match codec.decode(&mut src) {
+            Ok(Some(chunk)) if src.len() < remaining => chunks.push(chunk),
+            _ => break,
+        }
+    }
+    chunks
+}
+
+/// Decompresses and decodes `data` as a gossip message.
+pub fn decode_gossip_message<E: EthSpec>(data: &[u8]) {
+    let (selector, bytes) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    let _ = decode_gossip::<E>(*selector, bytes);
+}
+
+/// Decodes `bytes` as in `decode_gossip_message`, returning the message if it is valid.
+fn decode_gossip<E: EthSpec>(selector: u8, bytes: &[u8]) -> Option<PubsubMessage<E>> {
+    let fork_context = fork_context::<E>();
+    let topic = select_gossip_topic(selector, &fork_context)?;
+
+    let transform = SnappyTransform::new(gossip_max_size(true));
+    let decompressed = transform.decompress(bytes).ok()?;
+    PubsubMessage::<E>::decode(&topic.hash(), &decompressed, &fork_context).ok()
+}
+
+/// Returns an input for `decode_gossip_message` which contains `block`, as published on the
+/// beacon block topic.
+pub fn gossip_block_corpus_input<E: EthSpec>(block: &SignedBeaconBlock<E>) -> Vec<u8> {
+    let kinds = gossip_kinds();
+    let fork_index = ForkName::list_all()
+        .iter()
+        .position(|fork_name| *fork_name == block_fork_name(block))
+        .unwrap_or(0);
+    let kind_index = kinds
+        .iter()
+        .position(|kind| *kind == GossipKind::BeaconBlock)
+        .unwrap_or(0);
+    let selector = (kind_index + fork_index * kinds.len()) as u8;
+
+    let mut input = vec![selector];
+    input.extend(
+        snap::raw::Encoder::new()
+            .compress_vec(&block.as_ssz_bytes())
+            .unwrap_or_default(),
+    );
+    input
+}
+
+/// Returns inputs for `decode_rpc_response` which contain `block`, as sent in a response to each
+/// blocks by range and blocks by root protocol which supports its fork.
+pub fn rpc_block_corpus_inputs<E: EthSpec>(block: &SignedBeaconBlock<E>) -> Vec<Vec<u8>> {
+    let fork_context = fork_context::<E>();
+    supported_protocol_ids()
+        .into_iter()
+        .enumerate()
+        .filter(|(_, protocol)| {
+            matches!(
+                protocol.message_name,
+                Protocol::BlocksByRange | Protocol::BlocksByRoot
+            ) && (protocol.version == Version::V2 || block_fork_name(block) == ForkName::Base)
+        })
+        .filter_map(|(selector, protocol)| {
+            let response = match protocol.message_name {
+                Protocol::BlocksByRange => RPCResponse::BlocksByRange(Box::new(block.clone())),
+                _ => RPCResponse::BlocksByRoot(Box::new(block.clone())),
+            };
+            let mut codec =
+                InboundCodec::<E>::SSZSnappy(BaseInboundCodec::new(SSZSnappyInboundCodec::new(
+                    protocol,
+                    max_rpc_size(&fork_context),
+                    fork_context.clone(),
+                )));
+            let mut dst = BytesMut::new();
+            codec
+                .encode(RPCCodedResponse::Success(response), &mut dst)
+                .ok()?;
+
+            let mut input = vec![selector as u8];
+            input.extend_from_slice(&dst);
+            Some(input)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        BeaconBlock, BeaconBlockAltair, BeaconBlockBase, BeaconBlockMerge, MainnetEthSpec,
+        Signature,
+    };
+
+    type E = MainnetEthSpec;
+
+    /// Returns an empty block of each fork.
+    fn blocks() -> Vec<SignedBeaconBlock<E>> {
+        let spec = E::default_spec();
+        vec![
+            BeaconBlock::Base(BeaconBlockBase::empty(&spec)),
+            BeaconBlock::Altair(BeaconBlockAltair::empty(&spec)),
+            BeaconBlock::Merge(BeaconBlockMerge::empty(&spec)),
+        ]
+        .into_iter()
+        .map(|block| SignedBeaconBlock::from_block(block, Signature::empty()))
+        .collect()
+    }
+
+    #[test]
+    fn rpc_block_corpus_inputs_decode() {
+        for block in blocks() {
+            let inputs = rpc_block_corpus_inputs(&block);
+            // Base blocks are sent on both versions of each protocol, later forks only on V2.
+            let expected_inputs = if block_fork_name(&block) == ForkName::Base {
+                4
+            } else {
+                2
+            };
+            assert_eq!(inputs.len(), expected_inputs);
+
+            for input in inputs {
+                let (selector, bytes) = input.split_first().unwrap();
+                let chunks = decode_rpc_response_chunks::<E>(*selector, bytes);
+                assert_eq!(chunks.len(), 1);
+                match &chunks[0] {
+                    RPCCodedResponse::Success(RPCResponse::BlocksByRange(decoded))
+                    | RPCCodedResponse::Success(RPCResponse::BlocksByRoot(decoded)) => {
+                        assert_eq!(**decoded, block)
+                    }
+                    other => panic!("unexpected response {:?}", other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn gossip_block_corpus_input_decodes() {
+        for block in blocks() {
+            let input = gossip_block_corpus_input(&block);
+            let (selector, bytes) = input.split_first().unwrap();
+            match decode_gossip::<E>(*selector, bytes) {
+                Some(PubsubMessage::BeaconBlock(decoded)) => assert_eq!(*decoded, block),
+                other => panic!("unexpected message {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn truncated_corpus_inputs_do_not_panic() {
+        for block in blocks() {
+            let mut inputs = rpc_block_corpus_inputs(&block);
+            inputs.push(gossip_block_corpus_input(&block));
+            for input in inputs {
+                for len in 0..input.len() {
+                    decode_rpc_request::<E>(&input[..len]);
+                    decode_rpc_response::<E>(&input[..len]);
+                    decode_gossip_message::<E>(&input[..len]);
+                }
+            }
+        }
+    }
+}
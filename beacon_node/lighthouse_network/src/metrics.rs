@@ -43,6 +43,19 @@ lazy_static! {
         "The number of active discovery sessions with peers"
     );
 
+    pub static ref PEERS_DISTINCT_IP_PREFIXES: Result<IntGauge> = try_create_int_gauge(
+        "libp2p_peers_distinct_ip_prefixes",
+        "The number of distinct /24 (IPv4) or /48 (IPv6) prefixes of the connected peers"
+    );
+    pub static ref PEERS_DISTINCT_ASNS: Result<IntGauge> = try_create_int_gauge(
+        "libp2p_peers_distinct_asns",
+        "The number of distinct ASNs of the connected peers, if an ASN database is configured"
+    );
+    pub static ref PEERS_REJECTED_BY_ADMISSION: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_peers_rejected_by_admission_total",
+        "Count of inbound peers rejected for sharing an IP prefix or ASN with too many connected peers",
+        &["reason"]
+    );
     pub static ref PEERS_PER_CLIENT: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "libp2p_peers_per_client",
         "The connected peers via client implementation",
@@ -75,6 +88,12 @@ lazy_static! {
             "Score reports per client",
             &["client", "action", "source"]
         );
+    pub static ref GOSSIP_FIRST_DELIVERIES_PER_CLIENT: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "gossipsub_first_deliveries_per_client",
+            "Gossipsub messages first delivered to us by a peer, per client and kind of message",
+            &["client", "kind"]
+        );
     pub static ref GOSSIP_UNACCEPTED_MESSAGES_PER_CLIENT: Result<IntCounterVec> =
         try_create_int_counter_vec(
             "gossipsub_unaccepted_messages_per_client",
@@ -96,6 +115,38 @@ lazy_static! {
             &["client"]
         );
 
+    pub static ref PEERS_PER_SCORE_BUCKET: Result<IntGaugeVec> =
+        try_create_int_gauge_vec(
+            "peers_per_score_bucket",
+            "The number of connected peers with a score in each range",
+            &["bucket"]
+        );
+
+    /*
+     * Peer churn
+     */
+    pub static ref PEER_BAN_EVENTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_peer_ban_events_total",
+        "Count of peers banned for falling below the ban score, per score action and report msg",
+        &["action", "reason"]
+    );
+    pub static ref PEER_UNBAN_EVENTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_peer_unban_events_total",
+        "Count of peers unbanned after their score recovered",
+        &["reason"]
+    );
+    pub static ref PEER_CONNECT_EVENTS_PER_DIRECTION: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "libp2p_peer_connect_events_per_direction_total",
+            "Count of libp2p peer connect events, per direction of the connection",
+            &["direction"]
+        );
+    pub static ref PEER_GOODBYES_SENT: Result<IntCounterVec> = try_create_int_counter_vec(
+        "libp2p_peer_goodbyes_sent_total",
+        "Count of peers we disconnected from, per goodbye reason",
+        &["reason"]
+    );
+
     /*
      * Inbound/Outbound peers
      */
@@ -115,6 +166,18 @@ lazy_static! {
         "Number of peer reports per msg",
         &["msg"]
     );
+    pub static ref PEER_SCORE_DRY_RUN_PENALTIES: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "libp2p_peer_score_dry_run_penalties_total",
+            "Penalties computed but not applied to peer scores in dry-run mode",
+            &["action", "source"]
+        );
+    pub static ref PEER_SCORE_DRY_RUN_STATE_TRANSITIONS: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "libp2p_peer_score_dry_run_state_transitions_total",
+            "Score state transitions peers would have made due to penalties in dry-run mode",
+            &["state"]
+        );
 }
 
 /// Checks if we consider the NAT open.
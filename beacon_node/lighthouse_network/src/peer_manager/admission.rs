@@ -0,0 +1,436 @@
+//! Admission control for inbound peers, based on the diversity of the IP addresses of our peers.
+//!
+//! If any peer which dials us is accepted, an attacker controlling many addresses in a single
+//! network can occupy all of our inbound slots, which makes eclipsing the node much easier. To
+//! prevent this, the number of connected peers sharing an IP prefix (e.g. a /24) or an autonomous
+//! system (ASN) is limited, so that an attacker must control addresses in many networks to
+//! occupy a significant fraction of our peers.
+//!
+//! Both limits are disabled by default, since peers sharing a hosting provider or NAT are common
+//! on mainnet and an unsuitable limit would needlessly cost such nodes their peers. Addresses which
+//! are not publicly routable (e.g., loopback or private addresses) are exempt from these limits, so
+//! that local testnets are unaffected.
+
+use libp2p::multiaddr::Protocol;
+use libp2p::Multiaddr;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+use strum::AsRefStr;
+
+/// The length of the prefix which groups IPv4 addresses, i.e. a /24.
+pub const IPV4_PREFIX_LEN: u32 = 24;
+/// The length of the prefix which groups IPv6 addresses, i.e. a /48.
+pub const IPV6_PREFIX_LEN: u32 = 48;
+
+/// Configuration of the limits on inbound peers sharing an IP prefix or ASN.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    /// The maximum number of connected peers with an address in the same /24 (IPv4) or /48 (IPv6)
+    /// prefix, beyond which inbound peers from that prefix are rejected. `None` disables the limit.
+    pub max_peers_per_ip_prefix: Option<usize>,
+    /// The maximum number of connected peers with an address in the same ASN, beyond which inbound
+    /// peers from that ASN are rejected. `None` disables the limit.
+    pub max_peers_per_asn: Option<usize>,
+    /// The path of a database mapping IP address ranges to ASNs, in the tab-separated format of
+    /// the `ip2asn` databases (https://iptoasn.com).
+    pub asn_db: Option<PathBuf>,
+}
+
+/// The reason an inbound peer was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum AdmissionRejection {
+    /// Too many connected peers share the IP prefix of the peer.
+    IpPrefix,
+    /// Too many connected peers share the ASN of the peer.
+    Asn,
+}
+
+/// The IP prefix of an address, i.e. the address with all bits after the prefix cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IpPrefix {
+    V4(Ipv4Addr),
+    V6(Ipv6Addr),
+}
+
+impl IpPrefix {
+    /// Returns the prefix of `ip`, or `None` if `ip` is not publicly routable.
+    pub fn of(ip: IpAddr) -> Option<Self> {
+        if !is_publicly_routable(&ip) {
+            return None;
+        }
+        match ip {
+            IpAddr::V4(ip) => {
+                let mask = u32::MAX << (32 - IPV4_PREFIX_LEN);
+                Some(IpPrefix::V4((u32::from(ip) & mask).into()))
+            }
+            IpAddr::V6(ip) => {
+                let mask = u128::MAX << (128 - IPV6_PREFIX_LEN);
+                Some(IpPrefix::V6((u128::from(ip) & mask).into()))
+            }
+        }
+    }
+}
+
+/// Returns `false` for loopback, private, link-local and unspecified addresses.
+fn is_publicly_routable(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_loopback() || ip.is_private() || ip.is_link_local() || ip.is_unspecified())
+        }
+        IpAddr::V6(ip) => {
+            let first_segment = ip.segments()[0];
+            let is_unique_local = first_segment & 0xfe00 == 0xfc00;
+            let is_link_local = first_segment & 0xffc0 == 0xfe80;
+            !(ip.is_loopback() || ip.is_unspecified() || is_unique_local || is_link_local)
+        }
+    }
+}
+
+/// Returns the IP address of `multiaddr`, if any.
+pub fn multiaddr_ip(multiaddr: &Multiaddr) -> Option<IpAddr> {
+    multiaddr.iter().find_map(|protocol| match protocol {
+        Protocol::Ip4(ip) => Some(ip.into()),
+        Protocol::Ip6(ip) => Some(ip.into()),
+        _ => None,
+    })
+}
+
+/// An in-memory database mapping IP address ranges to autonomous system numbers.
+#[derive(Debug, Default)]
+pub struct AsnDb {
+    /// Non-overlapping `(start, end, asn)` ranges of IPv4 addresses, sorted by `start`.
+    ipv4: Vec<(u32, u32, u32)>,
+    /// Non-overlapping `(start, end, asn)` ranges of IPv6 addresses, sorted by `start`.
+    ipv6: Vec<(u128, u128, u32)>,
+}
+
+impl AsnDb {
+    /// Reads the database at `path`. See `AsnDb::from_tsv` for the format.
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let tsv = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read ASN database {:?}: {}", path, e))?;
+        Self::from_tsv(&tsv).map_err(|e| format!("Invalid ASN database {:?}: {}", path, e))
+    }
+
+    /// Parses a database with a line per range of addresses, each with the tab-separated fields:
+    ///
+    /// `range_start  range_end  AS_number  [...]`
+    ///
+    /// Any further fields (e.g. the country code and description) are ignored, as are ranges with
+    /// an AS number of 0, which denotes addresses that are not routed.
+    pub fn from_tsv(tsv: &str) -> Result<Self, String> {
+        let mut db = Self::default();
+
+        for (i, line) in tsv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split('\t');
+            let (start, end, asn) = match (fields.next(), fields.next(), fields.next()) {
+                (Some(start), Some(end), Some(asn)) => (start, end, asn),
+                _ => return Err(format!("line {} has fewer than 3 fields", i + 1)),
+            };
+            let asn = asn
+                .trim_start_matches("AS")
+                .parse::<u32>()
+                .map_err(|e| format!("line {} has an invalid AS number: {}", i + 1, e))?;
+            if asn == 0 {
+                continue;
+            }
+
+            match (start.parse::<IpAddr>(), end.parse::<IpAddr>()) {
+                (Ok(IpAddr::V4(start)), Ok(IpAddr::V4(end))) => {
+                    db.ipv4.push((start.into(), end.into(), asn))
+                }
+                (Ok(IpAddr::V6(start)), Ok(IpAddr::V6(end))) => {
+                    db.ipv6.push((start.into(), end.into(), asn))
+                }
+                _ => return Err(format!("line {} has an invalid address range", i + 1)),
+            }
+        }
+
+        db.ipv4.sort_unstable();
+        db.ipv6.sort_unstable();
+        Ok(db)
+    }
+
+    /// Returns the ASN of `ip`, if it is known.
+    pub fn lookup(&self, ip: IpAddr) -> Option<u32> {
+        match ip {
+            IpAddr::V4(ip) => lookup_range(&self.ipv4, ip.into()),
+            IpAddr::V6(ip) => lookup_range(&self.ipv6, ip.into()),
+        }
+    }
+}
+
+/// Returns the ASN of the range in `ranges` which contains `ip`, if any.
+fn lookup_range<T: Ord + Copy>(ranges: &[(T, T, u32)], ip: T) -> Option<u32> {
+    // The index of the first range which starts after `ip`.
+    let index = ranges.partition_point(|(start, _, _)| *start <= ip);
+    ranges
+        .get(index.checked_sub(1)?)
+        .filter(|(_, end, _)| ip <= *end)
+        .map(|(_, _, asn)| *asn)
+}
+
+/// The number of distinct IP prefixes and ASNs of a set of peers.
+#[derive(Debug, Default, PartialEq)]
+pub struct PeerDiversity {
+    pub ip_prefixes: usize,
+    pub asns: usize,
+}
+
+/// Decides whether inbound peers are admitted, according to an `AdmissionConfig`.
+pub struct PeerAdmission {
+    config: AdmissionConfig,
+    asn_db: Option<AsnDb>,
+}
+
+impl PeerAdmission {
+    /// Creates a new `PeerAdmission`, reading the ASN database of `config` if any.
+    pub fn new(config: AdmissionConfig) -> Result<Self, String> {
+        let asn_db = config.asn_db.as_deref().map(AsnDb::open).transpose()?;
+        if config.max_peers_per_asn.is_some() && asn_db.is_none() {
+            return Err("An ASN database is required to limit the number of peers per ASN".into());
+        }
+        Ok(Self { config, asn_db })
+    }
+
+    fn asn(&self, ip: IpAddr) -> Option<u32> {
+        if !is_publicly_routable(&ip) {
+            return None;
+        }
+        self.asn_db.as_ref()?.lookup(ip)
+    }
+
+    /// Checks whether a peer connecting from `ip` may be admitted, given the IP addresses of each
+    /// of the currently connected peers.
+    ///
+    /// A connected peer is counted against a limit if any of its addresses shares the prefix or
+    /// ASN of `ip`.
+    pub fn check<I, J>(&self, ip: IpAddr, connected_peers: I) -> Result<(), AdmissionRejection>
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = IpAddr>,
+    {
+        let prefix_limit = self
+            .config
+            .max_peers_per_ip_prefix
+            .and_then(|max| Some((max, IpPrefix::of(ip)?)));
+        let asn_limit = self
+            .config
+            .max_peers_per_asn
+            .and_then(|max| Some((max, self.asn(ip)?)));
+
+        if prefix_limit.is_none() && asn_limit.is_none() {
+            return Ok(());
+        }
+
+        let mut prefix_count = 0;
+        let mut asn_count = 0;
+        for peer_ips in connected_peers {
+            let mut same_prefix = false;
+            let mut same_asn = false;
+            for peer_ip in peer_ips {
+                same_prefix |=
+                    prefix_limit.map_or(false, |(_, prefix)| IpPrefix::of(peer_ip) == Some(prefix));
+                same_asn |= asn_limit.map_or(false, |(_, asn)| self.asn(peer_ip) == Some(asn));
+            }
+            prefix_count += same_prefix as usize;
+            asn_count += same_asn as usize;
+        }
+
+        if prefix_limit.map_or(false, |(max, _)| prefix_count >= max) {
+            return Err(AdmissionRejection::IpPrefix);
+        }
+        if asn_limit.map_or(false, |(max, _)| asn_count >= max) {
+            return Err(AdmissionRejection::Asn);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of distinct IP prefixes and ASNs among the addresses of `peers`.
+    ///
+    /// ASNs are only counted if an ASN database is configured.
+    pub fn diversity<I, J>(&self, peers: I) -> PeerDiversity
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = IpAddr>,
+    {
+        let mut ip_prefixes = HashSet::new();
+        let mut asns = HashSet::new();
+        for ip in peers.into_iter().flatten() {
+            ip_prefixes.extend(IpPrefix::of(ip));
+            asns.extend(self.asn(ip));
+        }
+        PeerDiversity {
+            ip_prefixes: ip_prefixes.len(),
+            asns: asns.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASN_DB: &str = "1.0.0.0\t1.0.0.255\t13335\tUS\tCLOUDFLARENET\n\
+                          1.0.1.0\t1.0.3.255\t0\tNone\tNot routed\n\
+                          8.8.4.0\t8.8.8.255\t15169\tUS\tGOOGLE\n\
+                          8.8.9.0\t8.8.9.255\t13335\tUS\tCLOUDFLARENET\n\
+                          2001:4860::\t2001:4860:ffff:ffff:ffff:ffff:ffff:ffff\t15169\tUS\tGOOGLE\n";
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn admission(config: AdmissionConfig) -> PeerAdmission {
+        PeerAdmission {
+            config,
+            asn_db: Some(AsnDb::from_tsv(ASN_DB).unwrap()),
+        }
+    }
+
+    #[test]
+    fn ip_prefixes() {
+        assert_eq!(
+            IpPrefix::of(ip("93.184.216.34")),
+            Some(IpPrefix::V4("93.184.216.0".parse().unwrap()))
+        );
+        assert_eq!(
+            IpPrefix::of(ip("93.184.216.34")),
+            IpPrefix::of(ip("93.184.216.200"))
+        );
+        assert_ne!(
+            IpPrefix::of(ip("93.184.216.34")),
+            IpPrefix::of(ip("93.184.217.34"))
+        );
+        assert_eq!(
+            IpPrefix::of(ip("2001:db8:1:2::1")),
+            IpPrefix::of(ip("2001:db8:1:ffff::1"))
+        );
+        assert_ne!(
+            IpPrefix::of(ip("2001:db8:1::1")),
+            IpPrefix::of(ip("2001:db8:2::1"))
+        );
+
+        for local in &[
+            "127.0.0.1",
+            "10.1.2.3",
+            "192.168.0.1",
+            "169.254.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+        ] {
+            assert_eq!(IpPrefix::of(ip(local)), None, "{}", local);
+        }
+    }
+
+    #[test]
+    fn multiaddr_ips() {
+        let addr: Multiaddr = "/ip4/93.184.216.34/tcp/9000".parse().unwrap();
+        assert_eq!(multiaddr_ip(&addr), Some(ip("93.184.216.34")));
+        let addr: Multiaddr = "/ip6/2001:db8::1/tcp/9000".parse().unwrap();
+        assert_eq!(multiaddr_ip(&addr), Some(ip("2001:db8::1")));
+        let addr: Multiaddr = "/dns4/example.com/tcp/9000".parse().unwrap();
+        assert_eq!(multiaddr_ip(&addr), None);
+    }
+
+    #[test]
+    fn asn_db_lookup() {
+        let db = AsnDb::from_tsv(ASN_DB).unwrap();
+        assert_eq!(db.lookup(ip("1.0.0.0")), Some(13335));
+        assert_eq!(db.lookup(ip("1.0.0.255")), Some(13335));
+        assert_eq!(db.lookup(ip("1.0.1.1")), None);
+        assert_eq!(db.lookup(ip("8.8.8.8")), Some(15169));
+        assert_eq!(db.lookup(ip("8.8.9.1")), Some(13335));
+        assert_eq!(db.lookup(ip("8.8.10.1")), None);
+        assert_eq!(db.lookup(ip("0.0.0.1")), None);
+        assert_eq!(db.lookup(ip("2001:4860::8888")), Some(15169));
+        assert_eq!(db.lookup(ip("2001:4861::1")), None);
+    }
+
+    #[test]
+    fn asn_db_invalid() {
+        assert!(AsnDb::from_tsv("1.0.0.0\t1.0.0.255").is_err());
+        assert!(AsnDb::from_tsv("1.0.0.0\t1.0.0.255\tfoo").is_err());
+        assert!(AsnDb::from_tsv("1.0.0.0\t::1\t1").is_err());
+        assert!(AsnDb::from_tsv("# comment\n\n").is_ok());
+    }
+
+    #[test]
+    fn asn_limit_requires_db() {
+        assert!(PeerAdmission::new(AdmissionConfig {
+            max_peers_per_asn: Some(1),
+            ..AdmissionConfig::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn ip_prefix_limit() {
+        let admission = admission(AdmissionConfig {
+            max_peers_per_ip_prefix: Some(2),
+            ..AdmissionConfig::default()
+        });
+
+        let one = vec![vec![ip("93.184.216.1")]];
+        let two = vec![vec![ip("93.184.216.1")], vec![ip("93.184.216.2")]];
+
+        assert_eq!(admission.check(ip("93.184.216.3"), one), Ok(()));
+        assert_eq!(
+            admission.check(ip("93.184.216.3"), two.clone()),
+            Err(AdmissionRejection::IpPrefix)
+        );
+        assert_eq!(admission.check(ip("93.184.217.3"), two), Ok(()));
+
+        // Local addresses are exempt.
+        let local = vec![vec![ip("127.0.0.1")], vec![ip("127.0.0.1")]];
+        assert_eq!(admission.check(ip("127.0.0.1"), local), Ok(()));
+    }
+
+    #[test]
+    fn asn_limit() {
+        let admission = admission(AdmissionConfig {
+            max_peers_per_ip_prefix: None,
+            max_peers_per_asn: Some(2),
+            asn_db: None,
+        });
+
+        let peers = vec![vec![ip("1.0.0.1")], vec![ip("8.8.9.1")]];
+        assert_eq!(
+            admission.check(ip("1.0.0.2"), peers.clone()),
+            Err(AdmissionRejection::Asn)
+        );
+        assert_eq!(admission.check(ip("8.8.8.8"), peers.clone()), Ok(()));
+        // Addresses without a known ASN are not limited.
+        assert_eq!(admission.check(ip("9.9.9.9"), peers), Ok(()));
+    }
+
+    #[test]
+    fn diversity() {
+        let admission = admission(AdmissionConfig::default());
+        let peers = vec![
+            vec![ip("1.0.0.1")],
+            vec![ip("1.0.0.2"), ip("8.8.8.8")],
+            vec![ip("8.8.9.1")],
+            vec![ip("127.0.0.1")],
+        ];
+        assert_eq!(
+            admission.diversity(peers),
+            PeerDiversity {
+                ip_prefixes: 3,
+                asns: 2,
+            }
+        );
+    }
+}
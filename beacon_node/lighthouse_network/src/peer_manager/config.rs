@@ -1,3 +1,6 @@
+use super::admission::AdmissionConfig;
+use super::peerdb::score::PeerScoreConfig;
+
 /// The time in seconds between re-status's peers.
 pub const DEFAULT_STATUS_INTERVAL: u64 = 300;
 
@@ -30,6 +33,14 @@ pub struct Config {
     pub ping_interval_inbound: u64,
     /// Interval between PING events for peers dialed by us.
     pub ping_interval_outbound: u64,
+
+    /* Scoring related configurations */
+    /// The penalties applied to peers for each `PeerAction`.
+    pub score_config: PeerScoreConfig,
+
+    /* Admission related configurations */
+    /// The limits on inbound peers sharing an IP prefix or ASN.
+    pub admission: AdmissionConfig,
 }
 
 impl Default for Config {
@@ -41,6 +52,8 @@ impl Default for Config {
             status_interval: DEFAULT_STATUS_INTERVAL,
             ping_interval_inbound: DEFAULT_PING_INTERVAL_INBOUND,
             ping_interval_outbound: DEFAULT_PING_INTERVAL_OUTBOUND,
+            score_config: PeerScoreConfig::default(),
+            admission: AdmissionConfig::default(),
         }
     }
 }
@@ -5,6 +5,7 @@ use crate::rpc::{GoodbyeReason, MetaData, Protocol, RPCError, RPCResponseErrorCo
 use crate::{error, metrics, Gossipsub};
 use crate::{NetworkGlobals, PeerId};
 use crate::{Subnet, SubnetDiscovery};
+use admission::{multiaddr_ip, PeerAdmission};
 use discv5::Enr;
 use hashset_delay::HashSetDelay;
 use libp2p::identify::IdentifyInfo;
@@ -26,10 +27,14 @@ pub mod peerdb;
 pub use peerdb::peer_info::{
     ConnectionDirection, PeerConnectionStatus, PeerConnectionStatus::*, PeerInfo,
 };
-use peerdb::score::{PeerAction, ReportSource};
+use peerdb::score::{
+    PeerAction, ReportSource, MAX_SCORE, MIN_SCORE, MIN_SCORE_BEFORE_BAN,
+    MIN_SCORE_BEFORE_DISCONNECT,
+};
 pub use peerdb::sync_status::{SyncInfo, SyncStatus};
 use std::collections::{hash_map::Entry, HashMap};
 use std::net::IpAddr;
+pub mod admission;
 pub mod config;
 mod network_behaviour;
 
@@ -48,6 +53,37 @@ pub const MIN_OUTBOUND_ONLY_FACTOR: f32 = 0.3;
 /// limit is 55, and we are at 55 peers, the following parameter provisions a few more slots of
 /// dialing priority peers we need for validator duties.
 pub const PRIORITY_PEER_EXCESS: f32 = 0.1;
+/// The exclusive upper bound of each range of scores for which the number of connected peers is
+/// exported. The first two ranges hold the peers which are due to be banned or disconnected.
+const SCORE_BUCKET_BOUNDS: [f64; 6] = [
+    MIN_SCORE_BEFORE_BAN,
+    MIN_SCORE_BEFORE_DISCONNECT,
+    0.0,
+    20.0,
+    50.0,
+    f64::INFINITY,
+];
+
+lazy_static! {
+    /// The label of each range in `SCORE_BUCKET_BOUNDS`, e.g. `[-50,-20)`.
+    static ref SCORE_BUCKET_LABELS: Vec<String> = score_bucket_labels();
+}
+
+fn score_bucket_labels() -> Vec<String> {
+    let mut lower_bound = MIN_SCORE;
+    SCORE_BUCKET_BOUNDS
+        .iter()
+        .map(|&upper_bound| {
+            let label = if upper_bound.is_finite() {
+                format!("[{},{})", lower_bound, upper_bound)
+            } else {
+                format!("[{},{}]", lower_bound, MAX_SCORE)
+            };
+            lower_bound = upper_bound;
+            label
+        })
+        .collect()
+}
 
 /// The main struct that handles peer's reputation and connection status.
 pub struct PeerManager<TSpec: EthSpec> {
@@ -74,6 +110,8 @@ pub struct PeerManager<TSpec: EthSpec> {
     discovery_enabled: bool,
     /// Keeps track if the current instance is reporting metrics or not.
     metrics_enabled: bool,
+    /// Limits the number of inbound peers sharing an IP prefix or ASN.
+    admission: PeerAdmission,
     /// The logger associated with the `PeerManager`.
     log: slog::Logger,
 }
@@ -119,8 +157,17 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             status_interval,
             ping_interval_inbound,
             ping_interval_outbound,
+            score_config,
+            admission,
         } = cfg;
 
+        if score_config.dry_run {
+            warn!(log, "Peer scoring is in dry-run mode"; "info" => "penalties for peer actions will be logged but not applied");
+        }
+        network_globals.peers.write().set_score_config(score_config);
+
+        let admission = PeerAdmission::new(admission)?;
+
         // Set up the peer manager heartbeat interval
         let heartbeat = tokio::time::interval(tokio::time::Duration::from_secs(HEARTBEAT_INTERVAL));
 
@@ -135,6 +182,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             heartbeat,
             discovery_enabled,
             metrics_enabled,
+            admission,
             log: log.clone(),
         })
     }
@@ -164,6 +212,23 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         );
     }
 
+    /// Send a goodbye to and disconnect every connected peer, without penalising them.
+    ///
+    /// Used when shutting down.
+    pub fn disconnect_all_peers(&mut self, reason: GoodbyeReason) {
+        let peers: Vec<PeerId> = self
+            .network_globals
+            .peers
+            .read()
+            .connected_peers()
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+        for peer_id in peers {
+            debug!(self.log, "Sending goodbye to peer"; "peer_id" => %peer_id, "reason" => %reason);
+            self.disconnect_peer(peer_id, reason.clone());
+        }
+    }
+
     /// Reports a peer for some action.
     ///
     /// If the peer doesn't exist, log a warning and insert defaults.
@@ -638,7 +703,19 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         let mut outbound_connected_peers = 0;
         let mut clients_per_peer = HashMap::new();
 
-        for (_peer, peer_info) in self.network_globals.peers.read().connected_peers() {
+        let peers = self.network_globals.peers.read();
+        let diversity = self.admission.diversity(
+            peers
+                .connected_peers()
+                .map(|(_, peer_info)| peer_info.seen_ip_addresses()),
+        );
+        metrics::set_gauge(
+            &metrics::PEERS_DISTINCT_IP_PREFIXES,
+            diversity.ip_prefixes as i64,
+        );
+        metrics::set_gauge(&metrics::PEERS_DISTINCT_ASNS, diversity.asns as i64);
+
+        for (_peer, peer_info) in peers.connected_peers() {
             connected_peer_count += 1;
             if let PeerConnectionStatus::Connected { n_in, .. } = peer_info.connection_status() {
                 if *n_in > 0 {
@@ -769,6 +846,39 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
         true
     }
 
+    /// Checks whether a peer which dialed us from `address` may be admitted given the IP prefixes
+    /// and ASNs of our connected peers. Trusted peers and peers with future duties are always
+    /// admitted.
+    fn check_admission(&self, peer_id: &PeerId, address: &Multiaddr) -> bool {
+        let ip = match multiaddr_ip(address) {
+            Some(ip) => ip,
+            None => return true,
+        };
+
+        let peers = self.network_globals.peers.read();
+        if peers.is_connected(peer_id)
+            || peers
+                .peer_info(peer_id)
+                .map_or(false, |peer| peer.is_trusted() || peer.has_future_duty())
+        {
+            return true;
+        }
+
+        match self.admission.check(
+            ip,
+            peers
+                .connected_peers()
+                .map(|(_, peer_info)| peer_info.seen_ip_addresses()),
+        ) {
+            Ok(()) => true,
+            Err(reason) => {
+                debug!(self.log, "Rejecting inbound peer"; "peer_id" => %peer_id, "ip" => %ip, "reason" => reason.as_ref());
+                metrics::inc_counter_vec(&metrics::PEERS_REJECTED_BY_ADMISSION, &[reason.as_ref()]);
+                false
+            }
+        }
+    }
+
     // Gracefully disconnects a peer without banning them.
     fn disconnect_peer(&mut self, peer_id: PeerId, reason: GoodbyeReason) {
         self.events
@@ -902,6 +1012,7 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             .map(|gauge| gauge.reset());
 
         let mut avg_score_per_client: HashMap<String, (f64, usize)> = HashMap::with_capacity(5);
+        let mut peers_per_score_bucket = [0_i64; SCORE_BUCKET_BOUNDS.len()];
         {
             let peers_db_read_lock = self.network_globals.peers.read();
             let connected_peers = peers_db_read_lock.best_peers_by_status(PeerInfo::is_connected);
@@ -940,6 +1051,14 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
                     );
                 }
 
+                let score = peer_info.score().score();
+                if let Some(bucket) = SCORE_BUCKET_BOUNDS
+                    .iter()
+                    .position(|upper_bound| score < *upper_bound)
+                {
+                    peers_per_score_bucket[bucket] += 1;
+                }
+
                 let mut score_peers: &mut (f64, usize) = avg_score_per_client
                     .entry(peer_info.client().kind.to_string())
                     .or_default();
@@ -948,6 +1067,10 @@ impl<TSpec: EthSpec> PeerManager<TSpec> {
             }
         } // read lock ended
 
+        for (bucket, peers) in SCORE_BUCKET_LABELS.iter().zip(peers_per_score_bucket) {
+            metrics::set_gauge_vec(&metrics::PEERS_PER_SCORE_BUCKET, &[bucket], peers);
+        }
+
         for (client, (score, peers)) in avg_score_per_client {
             metrics::set_float_gauge_vec(
                 &metrics::PEER_SCORE_PER_CLIENT,
@@ -1004,6 +1127,21 @@ mod tests {
             .unwrap()
     }
 
+    #[test]
+    fn score_bucket_labels_match_thresholds() {
+        assert_eq!(
+            *SCORE_BUCKET_LABELS,
+            vec![
+                "[-100,-50)",
+                "[-50,-20)",
+                "[-20,0)",
+                "[0,20)",
+                "[20,50)",
+                "[50,100]",
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_peer_manager_disconnects_correctly_during_heartbeat() {
         let mut peer_manager = build_peer_manager(3).await;
@@ -1212,4 +1350,53 @@ mod tests {
         // the number of connected peers updates and we will not remove too many peers.
         assert_eq!(peer_manager.network_globals.connected_or_dialing_peers(), 3);
     }
+
+    #[tokio::test]
+    async fn test_peer_manager_admission_at_ip_prefix_limit() {
+        let config = config::Config {
+            discovery_enabled: false,
+            admission: admission::AdmissionConfig {
+                max_peers_per_ip_prefix: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let log = build_log(slog::Level::Debug, false);
+        let globals = NetworkGlobals::new_test_globals(&log);
+        let mut peer_manager = PeerManager::new(config, Arc::new(globals), &log)
+            .await
+            .unwrap();
+        let address =
+            |host: &str| -> Multiaddr { format!("/ip4/{}/tcp/9000", host).parse().unwrap() };
+
+        // Peers are admitted until the limit for their prefix is reached.
+        let peer0 = PeerId::random();
+        let peer1 = PeerId::random();
+        assert!(peer_manager.check_admission(&peer0, &address("93.184.216.1")));
+        peer_manager.inject_connect_ingoing(&peer0, address("93.184.216.1"), None);
+        assert!(peer_manager.check_admission(&peer1, &address("93.184.216.2")));
+        peer_manager.inject_connect_ingoing(&peer1, address("93.184.216.2"), None);
+
+        let peer2 = PeerId::random();
+        assert!(!peer_manager.check_admission(&peer2, &address("93.184.216.3")));
+        // Other prefixes and local addresses are unaffected.
+        assert!(peer_manager.check_admission(&peer2, &address("93.184.217.3")));
+        assert!(peer_manager.check_admission(&peer2, &address("127.0.0.1")));
+        // Further connections from connected peers are admitted.
+        assert!(peer_manager.check_admission(&peer0, &address("93.184.216.1")));
+
+        // Peers with future duties are admitted beyond the limit.
+        peer_manager
+            .network_globals
+            .peers
+            .write()
+            .update_min_ttl(&peer2, Instant::now() + Duration::from_secs(60));
+        assert!(peer_manager.check_admission(&peer2, &address("93.184.216.3")));
+
+        // Once a peer disconnects, there is room for another.
+        let peer3 = PeerId::random();
+        assert!(!peer_manager.check_admission(&peer3, &address("93.184.216.4")));
+        peer_manager.inject_disconnect(&peer1);
+        assert!(peer_manager.check_admission(&peer3, &address("93.184.216.4")));
+    }
 }
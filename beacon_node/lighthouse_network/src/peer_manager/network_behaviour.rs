@@ -153,6 +153,14 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
             return;
         }
 
+        // Limit the number of inbound peers from the same IP prefix or ASN.
+        if let ConnectedPoint::Listener { send_back_addr, .. } = endpoint {
+            if !self.check_admission(peer_id, send_back_addr) {
+                self.disconnect_peer(*peer_id, GoodbyeReason::TooManyPeers);
+                return;
+            }
+        }
+
         // NOTE: We don't register peers that we are disconnecting immediately. The network service
         // does not need to know about these peers.
         match endpoint {
@@ -160,11 +168,16 @@ impl<TSpec: EthSpec> NetworkBehaviour for PeerManager<TSpec> {
                 self.inject_connect_ingoing(peer_id, send_back_addr.clone(), None);
                 self.events
                     .push(PeerManagerEvent::PeerConnectedIncoming(*peer_id));
+                metrics::inc_counter_vec(&metrics::PEER_CONNECT_EVENTS_PER_DIRECTION, &["inbound"]);
             }
             ConnectedPoint::Dialer { address } => {
                 self.inject_connect_outgoing(peer_id, address.clone(), None);
                 self.events
                     .push(PeerManagerEvent::PeerConnectedOutgoing(*peer_id));
+                metrics::inc_counter_vec(
+                    &metrics::PEER_CONNECT_EVENTS_PER_DIRECTION,
+                    &["outbound"],
+                );
             }
         }
 
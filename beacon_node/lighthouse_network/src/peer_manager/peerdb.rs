@@ -6,7 +6,7 @@ use crate::{
 };
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
-use score::{PeerAction, ReportSource, Score, ScoreState};
+use score::{PeerAction, PeerScoreConfig, ReportSource, Score, ScoreState};
 use slog::{crit, debug, error, trace, warn};
 use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
@@ -29,6 +29,9 @@ const BANNED_PEERS_PER_IP_THRESHOLD: usize = 5;
 /// Relative factor of peers that are allowed to have a negative gossipsub score without penalizing
 /// them in lighthouse.
 const ALLOWED_NEGATIVE_GOSSIPSUB_FACTOR: f32 = 0.1;
+/// The reason recorded in the ban and unban metrics when a peer's gossipsub score changes its
+/// score state.
+const GOSSIPSUB_SCORE_REASON: &str = "gossipsub_score";
 
 /// Storage of known peers, their reputation and information
 pub struct PeerDB<TSpec: EthSpec> {
@@ -38,6 +41,8 @@ pub struct PeerDB<TSpec: EthSpec> {
     disconnected_peers: usize,
     /// Counts banned peers in total and per ip
     banned_peers_count: BannedPeersCount,
+    /// The penalties applied to peers for each `PeerAction`.
+    score_config: PeerScoreConfig,
     /// PeerDB's logger
     log: slog::Logger,
 }
@@ -53,12 +58,56 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             log: log.clone(),
             disconnected_peers: 0,
             banned_peers_count: BannedPeersCount::default(),
+            score_config: PeerScoreConfig::default(),
             peers,
         }
     }
 
+    /// Sets the penalties applied to peers for each `PeerAction`.
+    // VISIBILITY: The peer manager is configured with the penalties.
+    pub(super) fn set_score_config(&mut self, score_config: PeerScoreConfig) {
+        self.score_config = score_config;
+    }
+
     /* Getters */
 
+    /// Returns the total number of gossip messages of each kind which were first delivered by the
+    /// peers in the database, keyed by the kind of message.
+    pub fn first_message_delivery_totals(&self) -> HashMap<&'static str, u64> {
+        let mut totals = HashMap::new();
+        for info in self.peers.values() {
+            for (kind, count) in info.first_message_deliveries() {
+                *totals.entry(*kind).or_default() += count;
+            }
+        }
+        totals
+    }
+
+    /// Returns the share of the gossip messages of each kind which were first delivered by
+    /// `peer_id`, out of all those first delivered by the peers in the database.
+    ///
+    /// `totals` must be the result of `Self::first_message_delivery_totals`.
+    pub fn first_message_delivery_share(
+        &self,
+        peer_id: &PeerId,
+        totals: &HashMap<&'static str, u64>,
+    ) -> HashMap<String, f64> {
+        self.peer_info(peer_id)
+            .map(|info| {
+                info.first_message_deliveries()
+                    .iter()
+                    .filter_map(|(kind, count)| {
+                        let total = *totals.get(kind)?;
+                        if total == 0 {
+                            return None;
+                        }
+                        Some((kind.to_string(), *count as f64 / total as f64))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /// Gives the score of a peer, or default score if it is unknown.
     pub fn score(&self, peer_id: &PeerId) -> f64 {
         self.peers
@@ -369,6 +418,7 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
 
         // Update the state in the peerdb
         for unbanned_peer in peers_to_unban {
+            metrics::inc_counter_vec(&metrics::PEER_UNBAN_EVENTS, &["score_decay"]);
             self.update_connection_state(&unbanned_peer, NewConnectionState::Unbanned);
             let seen_ip_addresses = self
                 .peers
@@ -439,6 +489,10 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
             let result = match action {
                 ScoreTransitionResult::Banned => {
                     // The peer was banned as a result of this action.
+                    metrics::inc_counter_vec(
+                        &metrics::PEER_BAN_EVENTS,
+                        &["none", GOSSIPSUB_SCORE_REASON],
+                    );
                     self.update_connection_state(&peer_id, NewConnectionState::Banned)
                         .into()
                 }
@@ -454,6 +508,10 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
                 }
                 ScoreTransitionResult::NoAction => ScoreUpdateResult::NoAction,
                 ScoreTransitionResult::Unbanned => {
+                    metrics::inc_counter_vec(
+                        &metrics::PEER_UNBAN_EVENTS,
+                        &[GOSSIPSUB_SCORE_REASON],
+                    );
                     self.update_connection_state(&peer_id, NewConnectionState::Unbanned);
                     let seen_ip_addresses = self
                         .peers
@@ -493,14 +551,47 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         msg: &'static str,
     ) -> ScoreUpdateResult {
         metrics::inc_counter_vec(&metrics::REPORT_PEER_MSGS, &[msg]);
+        let source: &'static str = source.into();
 
         match self.peers.get_mut(peer_id) {
             Some(info) => {
                 let previous_state = info.score_state();
-                info.apply_peer_action_to_score(action);
+                match self.score_config.penalty(action, source) {
+                    // Fatal actions are applied regardless of dry-run mode.
+                    None => info.apply_peer_action_to_score(action),
+                    Some(penalty) if self.score_config.dry_run => {
+                        let previous_dry_run_state = info.dry_run_score().state();
+                        info.apply_penalty_to_dry_run_score(penalty);
+                        let dry_run_state = info.dry_run_score().state();
+
+                        metrics::inc_counter_vec(
+                            &metrics::PEER_SCORE_DRY_RUN_PENALTIES,
+                            &[action.as_ref(), source],
+                        );
+                        if dry_run_state != previous_dry_run_state {
+                            metrics::inc_counter_vec(
+                                &metrics::PEER_SCORE_DRY_RUN_STATE_TRANSITIONS,
+                                &[dry_run_state.as_ref()],
+                            );
+                        }
+                        debug!(
+                            self.log,
+                            "Peer score adjustment not applied (dry run)";
+                            "msg" => %msg,
+                            "peer_id" => %peer_id,
+                            "action" => %action,
+                            "source" => source,
+                            "penalty" => penalty,
+                            "dry_run_score" => %info.dry_run_score(),
+                            "dry_run_state" => %dry_run_state,
+                            "past_dry_run_state" => %previous_dry_run_state,
+                        );
+                    }
+                    Some(penalty) => info.apply_penalty_to_score(penalty),
+                }
                 metrics::inc_counter_vec(
                     &metrics::PEER_ACTION_EVENTS_PER_CLIENT,
-                    &[info.client().kind.as_ref(), action.as_ref(), source.into()],
+                    &[info.client().kind.as_ref(), action.as_ref(), source],
                 );
                 let result =
                     Self::handle_score_transition(previous_state, peer_id, info, &self.log);
@@ -516,6 +607,10 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
                 match result {
                     ScoreTransitionResult::Banned => {
                         // The peer was banned as a result of this action.
+                        metrics::inc_counter_vec(
+                            &metrics::PEER_BAN_EVENTS,
+                            &[action.as_ref(), msg],
+                        );
                         self.update_connection_state(peer_id, NewConnectionState::Banned)
                             .into()
                     }
@@ -578,6 +673,14 @@ impl<TSpec: EthSpec> PeerDB<TSpec> {
         }
     }
 
+    /// Records that a peer was the first to deliver a gossip message of the given kind.
+    // VISIBILITY: The behaviour is able to record gossip deliveries.
+    pub(crate) fn register_first_message_delivery(&mut self, peer_id: &PeerId, kind: &'static str) {
+        if let Some(info) = self.peers.get_mut(peer_id) {
+            info.register_first_message_delivery(kind);
+        }
+    }
+
     /// Removes a gossipsub subscription to a peer in the peerdb.
     // VISIBILITY: The behaviour is able to adjust subscriptions.
     pub(crate) fn remove_subscription(&mut self, peer_id: &PeerId, subnet: &Subnet) {
@@ -1468,6 +1571,37 @@ mod tests {
         assert_eq!(the_best.unwrap(), best_peers.get(0).unwrap().0);
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_first_message_delivery_share() {
+        let mut pdb = get_db();
+
+        let p0 = PeerId::random();
+        let p1 = PeerId::random();
+        pdb.connect_ingoing(&p0, "/ip4/0.0.0.0".parse().unwrap(), None);
+        pdb.connect_ingoing(&p1, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        for _ in 0..3 {
+            pdb.register_first_message_delivery(&p0, "beacon_block");
+        }
+        pdb.register_first_message_delivery(&p1, "beacon_block");
+        pdb.register_first_message_delivery(&p1, "beacon_attestation");
+        // Deliveries from unknown peers are ignored.
+        pdb.register_first_message_delivery(&PeerId::random(), "beacon_block");
+
+        let totals = pdb.first_message_delivery_totals();
+        assert_eq!(totals.get("beacon_block"), Some(&4));
+        assert_eq!(totals.get("beacon_attestation"), Some(&1));
+
+        let p0_share = pdb.first_message_delivery_share(&p0, &totals);
+        assert_eq!(p0_share.get("beacon_block"), Some(&0.75));
+        assert_eq!(p0_share.get("beacon_attestation"), None);
+
+        let p1_share = pdb.first_message_delivery_share(&p1, &totals);
+        assert_eq!(p1_share.get("beacon_block"), Some(&0.25));
+        assert_eq!(p1_share.get("beacon_attestation"), Some(&1.0));
+    }
+
     #[test]
     fn test_disconnected_consistency() {
         let mut pdb = get_db();
@@ -1976,4 +2110,62 @@ mod tests {
             Score::max_score().score()
         );
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_source_penalties() {
+        let mut pdb = get_db();
+        let mut score_config = PeerScoreConfig::default();
+        score_config
+            .set_penalties("gossipsub.low_tolerance_error=30")
+            .unwrap();
+        pdb.set_score_config(score_config);
+
+        let p0 = PeerId::random();
+        pdb.connect_ingoing(&p0, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        let _ = pdb.report_peer(&p0, PeerAction::LowToleranceError, ReportSource::RPC, "");
+        assert_eq!(pdb.score(&p0), -10.0);
+
+        let result = pdb.report_peer(
+            &p0,
+            PeerAction::LowToleranceError,
+            ReportSource::Gossipsub,
+            "",
+        );
+        assert_eq!(pdb.score(&p0), -40.0);
+        assert!(matches!(result, ScoreUpdateResult::Disconnect));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_dry_run_penalties_are_not_applied() {
+        let mut pdb = get_db();
+        pdb.set_score_config(PeerScoreConfig {
+            dry_run: true,
+            ..Default::default()
+        });
+
+        let p0 = PeerId::random();
+        pdb.connect_ingoing(&p0, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        for _ in 0..6 {
+            let result = pdb.report_peer(
+                &p0,
+                PeerAction::LowToleranceError,
+                ReportSource::PeerManager,
+                "",
+            );
+            assert!(matches!(result, ScoreUpdateResult::NoAction));
+        }
+        let info = pdb.peer_info(&p0).unwrap();
+        assert_eq!(info.score().score(), 0.0);
+        assert_eq!(info.score_state(), ScoreState::Healthy);
+        assert_eq!(info.dry_run_score().score(), -60.0);
+        assert_eq!(info.dry_run_score().state(), ScoreState::Banned);
+
+        // Fatal actions are applied regardless.
+        let _ = pdb.report_peer(&p0, PeerAction::Fatal, ReportSource::PeerManager, "");
+        assert!(pdb.peer_info(&p0).unwrap().score().score() <= -100.0);
+    }
 }
@@ -8,7 +8,7 @@ use serde::{
     ser::{SerializeStruct, Serializer},
     Serialize,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::net::{IpAddr, SocketAddr};
 use std::time::Instant;
 use strum::AsRefStr;
@@ -21,6 +21,9 @@ use PeerConnectionStatus::*;
 pub struct PeerInfo<T: EthSpec> {
     /// The peers reputation
     score: Score,
+    /// The reputation the peer would have if the penalties computed in dry-run mode were applied.
+    #[serde(skip)]
+    dry_run_score: Score,
     /// Client managing this peer
     client: Client,
     /// Connection status of this peer
@@ -50,12 +53,15 @@ pub struct PeerInfo<T: EthSpec> {
     connection_direction: Option<ConnectionDirection>,
     /// The enr of the peer, if known.
     enr: Option<Enr>,
+    /// The number of gossip messages of each kind which this peer was the first to deliver to us.
+    first_message_deliveries: HashMap<&'static str, u64>,
 }
 
 impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
     fn default() -> PeerInfo<TSpec> {
         PeerInfo {
             score: Score::default(),
+            dry_run_score: Score::default(),
             client: Client::default(),
             connection_status: Default::default(),
             listening_addresses: Vec::new(),
@@ -67,6 +73,7 @@ impl<TSpec: EthSpec> Default for PeerInfo<TSpec> {
             is_trusted: false,
             connection_direction: None,
             enr: None,
+            first_message_deliveries: HashMap::new(),
         }
     }
 }
@@ -76,6 +83,7 @@ impl<T: EthSpec> PeerInfo<T> {
     pub fn trusted_peer_info() -> Self {
         PeerInfo {
             score: Score::max_score(),
+            dry_run_score: Score::max_score(),
             is_trusted: true,
             ..Default::default()
         }
@@ -139,6 +147,12 @@ impl<T: EthSpec> PeerInfo<T> {
         self.enr.as_ref()
     }
 
+    /// Returns the number of gossip messages of each kind which this peer was the first to deliver
+    /// to us, keyed by the kind of message.
+    pub fn first_message_deliveries(&self) -> &HashMap<&'static str, u64> {
+        &self.first_message_deliveries
+    }
+
     /// Returns if the peer is subscribed to a given `Subnet` from the gossipsub subscriptions.
     pub fn on_subnet_gossipsub(&self, subnet: &Subnet) -> bool {
         self.subnets.contains(subnet)
@@ -176,6 +190,12 @@ impl<T: EthSpec> PeerInfo<T> {
         self.score.state()
     }
 
+    /// Returns the score the peer would have if the penalties computed in dry-run mode were
+    /// applied.
+    pub fn dry_run_score(&self) -> &Score {
+        &self.dry_run_score
+    }
+
     /// Returns true if the gossipsub score is sufficient.
     pub fn is_good_gossipsub_peer(&self) -> bool {
         self.score.is_good_gossipsub_peer()
@@ -290,10 +310,16 @@ impl<T: EthSpec> PeerInfo<T> {
         self.subnets.clear()
     }
 
+    /// Records that this peer was the first to deliver a gossip message of the given kind.
+    pub(super) fn register_first_message_delivery(&mut self, kind: &'static str) {
+        *self.first_message_deliveries.entry(kind).or_default() += 1;
+    }
+
     /// Applies decay rates to a non-trusted peer's score.
     pub(super) fn score_update(&mut self) {
         if !self.is_trusted {
-            self.score.update()
+            self.score.update();
+            self.dry_run_score.update();
         }
     }
 
@@ -301,13 +327,31 @@ impl<T: EthSpec> PeerInfo<T> {
     // VISIBILITY: The peer manager is able to modify the score of a peer.
     pub(in crate::peer_manager) fn apply_peer_action_to_score(&mut self, peer_action: PeerAction) {
         if !self.is_trusted {
-            self.score.apply_peer_action(peer_action)
+            self.score.apply_peer_action(peer_action);
+            self.dry_run_score.apply_peer_action(peer_action);
+        }
+    }
+
+    /// Subtract a penalty from a non-trusted peer's score.
+    pub(super) fn apply_penalty_to_score(&mut self, penalty: f64) {
+        if !self.is_trusted {
+            self.score.apply_penalty(penalty);
+            self.dry_run_score.apply_penalty(penalty);
+        }
+    }
+
+    /// Subtract a penalty from a non-trusted peer's dry-run score only, leaving its score
+    /// unchanged.
+    pub(super) fn apply_penalty_to_dry_run_score(&mut self, penalty: f64) {
+        if !self.is_trusted {
+            self.dry_run_score.apply_penalty(penalty);
         }
     }
 
     /// Updates the gossipsub score with a new score. Optionally ignore the gossipsub score.
     pub(super) fn update_gossipsub_score(&mut self, new_score: f64, ignore: bool) {
         self.score.update_gossipsub_score(new_score, ignore);
+        self.dry_run_score.update_gossipsub_score(new_score, ignore);
     }
 
     #[cfg(test)]
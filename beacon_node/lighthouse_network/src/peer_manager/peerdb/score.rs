@@ -6,9 +6,11 @@
 //!
 //! The scoring algorithms are currently experimental.
 use crate::behaviour::gossipsub_scoring_parameters::GREYLIST_THRESHOLD as GOSSIPSUB_GREYLIST_THRESHOLD;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::time::Instant;
-use strum::AsRefStr;
+use strum::{AsRefStr, EnumString};
 use tokio::time::Duration;
 
 lazy_static! {
@@ -18,16 +20,16 @@ lazy_static! {
 /// The default score for new peers.
 pub(crate) const DEFAULT_SCORE: f64 = 0.0;
 /// The minimum reputation before a peer is disconnected.
-const MIN_SCORE_BEFORE_DISCONNECT: f64 = -20.0;
+pub(crate) const MIN_SCORE_BEFORE_DISCONNECT: f64 = -20.0;
 /// The minimum reputation before a peer is banned.
-const MIN_SCORE_BEFORE_BAN: f64 = -50.0;
+pub(crate) const MIN_SCORE_BEFORE_BAN: f64 = -50.0;
 /// If a peer has a lighthouse score below this constant all other score parts will get ignored and
 /// the peer will get banned regardless of the other parts.
 const MIN_LIGHTHOUSE_SCORE_BEFORE_BAN: f64 = -60.0;
 /// The maximum score a peer can obtain.
-const MAX_SCORE: f64 = 100.0;
+pub(crate) const MAX_SCORE: f64 = 100.0;
 /// The minimum score a peer can obtain.
-const MIN_SCORE: f64 = -100.0;
+pub(crate) const MIN_SCORE: f64 = -100.0;
 /// The halflife of a peer's score. I.e the number of seconds it takes for the score to decay to half its value.
 const SCORE_HALFLIFE: f64 = 600.0;
 /// The number of seconds we ban a peer for before their score begins to decay.
@@ -43,7 +45,7 @@ const GOSSIPSUB_POSITIVE_SCORE_WEIGHT: f64 = GOSSIPSUB_NEGATIVE_SCORE_WEIGHT;
 /// Each variant has an associated score change.
 // To easily assess the behaviour of scores changes the number of variants should stay low, and
 // somewhat generic.
-#[derive(Debug, Clone, Copy, AsRefStr)]
+#[derive(Debug, Clone, Copy, AsRefStr, EnumString)]
 #[strum(serialize_all = "snake_case")]
 pub enum PeerAction {
     /// We should not communicate more with this peer.
@@ -87,6 +89,173 @@ impl From<ReportSource> for &'static str {
     }
 }
 
+/// The names of each `ReportSource`, as used in metrics and in a `PeerScoreConfig`.
+const REPORT_SOURCE_NAMES: [&str; 5] = [
+    "gossipsub",
+    "rpc_error",
+    "processor",
+    "sync",
+    "peer_manager",
+];
+
+/// The amount subtracted from a peer's score for each non-fatal `PeerAction`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PeerActionPenalties {
+    pub low_tolerance_error: f64,
+    pub mid_tolerance_error: f64,
+    pub high_tolerance_error: f64,
+}
+
+impl Default for PeerActionPenalties {
+    fn default() -> Self {
+        PeerActionPenalties {
+            low_tolerance_error: 10.0,
+            mid_tolerance_error: 5.0,
+            high_tolerance_error: 1.0,
+        }
+    }
+}
+
+impl PeerActionPenalties {
+    /// Returns the penalty for `action`, or `None` for `PeerAction::Fatal`, which always results in
+    /// the worst possible score.
+    pub fn get(&self, action: PeerAction) -> Option<f64> {
+        match action {
+            PeerAction::Fatal => None,
+            PeerAction::LowToleranceError => Some(self.low_tolerance_error),
+            PeerAction::MidToleranceError => Some(self.mid_tolerance_error),
+            PeerAction::HighToleranceError => Some(self.high_tolerance_error),
+        }
+    }
+
+    fn get_mut(&mut self, action: PeerAction) -> Option<&mut f64> {
+        match action {
+            PeerAction::Fatal => None,
+            PeerAction::LowToleranceError => Some(&mut self.low_tolerance_error),
+            PeerAction::MidToleranceError => Some(&mut self.mid_tolerance_error),
+            PeerAction::HighToleranceError => Some(&mut self.high_tolerance_error),
+        }
+    }
+}
+
+/// Penalties which take precedence over the `PeerActionPenalties` for the reports of a single
+/// `ReportSource`. Unset penalties fall back to the `PeerActionPenalties`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PeerActionPenaltyOverrides {
+    pub low_tolerance_error: Option<f64>,
+    pub mid_tolerance_error: Option<f64>,
+    pub high_tolerance_error: Option<f64>,
+}
+
+impl PeerActionPenaltyOverrides {
+    fn get_mut(&mut self, action: PeerAction) -> Option<&mut Option<f64>> {
+        match action {
+            PeerAction::Fatal => None,
+            PeerAction::LowToleranceError => Some(&mut self.low_tolerance_error),
+            PeerAction::MidToleranceError => Some(&mut self.mid_tolerance_error),
+            PeerAction::HighToleranceError => Some(&mut self.high_tolerance_error),
+        }
+    }
+
+    fn get(&self, action: PeerAction) -> Option<f64> {
+        match action {
+            PeerAction::Fatal => None,
+            PeerAction::LowToleranceError => self.low_tolerance_error,
+            PeerAction::MidToleranceError => self.mid_tolerance_error,
+            PeerAction::HighToleranceError => self.high_tolerance_error,
+        }
+    }
+}
+
+/// Configuration of the penalties applied to the score of a peer for each `PeerAction`.
+///
+/// A penalty set for the `ReportSource` of a report takes precedence over the penalty for reports
+/// from any source.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PeerScoreConfig {
+    /// Penalties for reports from any source.
+    pub penalties: PeerActionPenalties,
+    /// Penalties for reports from a single source, keyed by the name of the source.
+    pub source_penalties: HashMap<String, PeerActionPenaltyOverrides>,
+    /// Log and record metrics for the penalties of non-fatal actions without applying them.
+    pub dry_run: bool,
+}
+
+impl PeerScoreConfig {
+    /// Returns the penalty for `action` when reported by the source named `source`, or `None` for
+    /// `PeerAction::Fatal`.
+    pub fn penalty(&self, action: PeerAction, source: &str) -> Option<f64> {
+        self.source_penalties
+            .get(source)
+            .and_then(|overrides| overrides.get(action))
+            .or_else(|| self.penalties.get(action))
+    }
+
+    /// Sets penalties from a comma-separated list of `[source.]action=penalty` entries, e.g.
+    /// `low_tolerance_error=20,gossipsub.high_tolerance_error=0.5`.
+    ///
+    /// Entries without a source apply to reports from any source.
+    pub fn set_penalties(&mut self, penalties: &str) -> Result<(), String> {
+        for entry in penalties
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let (key, value) = entry.split_once('=').ok_or_else(|| {
+                format!(
+                    "Invalid penalty {:?}, expected [source.]action=penalty",
+                    entry
+                )
+            })?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let penalty = f64::from_str(value)
+                .map_err(|e| format!("Invalid penalty {:?} for {}: {}", value, key, e))?;
+            if !penalty.is_finite() || penalty < 0.0 {
+                return Err(format!(
+                    "Penalty for {} must be a non-negative number, not {}",
+                    key, value
+                ));
+            }
+
+            let (source, action_name) = match key.split_once('.') {
+                Some((source, action_name)) => (Some(source), action_name),
+                None => (None, key),
+            };
+            let action = PeerAction::from_str(action_name)
+                .map_err(|_| format!("Unknown peer action {:?}", action_name))?;
+            let fatal_error = || {
+                format!(
+                    "The penalty for {} cannot be configured, it always bans the peer",
+                    action_name
+                )
+            };
+
+            match source {
+                Some(source) => {
+                    if !REPORT_SOURCE_NAMES.contains(&source) {
+                        return Err(format!(
+                            "Unknown report source {:?}, expected one of: {}",
+                            source,
+                            REPORT_SOURCE_NAMES.join(", ")
+                        ));
+                    }
+                    *self
+                        .source_penalties
+                        .entry(source.to_string())
+                        .or_default()
+                        .get_mut(action)
+                        .ok_or_else(fatal_error)? = Some(penalty);
+                }
+                None => *self.penalties.get_mut(action).ok_or_else(fatal_error)? = penalty,
+            }
+        }
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for PeerAction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -99,7 +268,8 @@ impl std::fmt::Display for PeerAction {
 }
 
 /// The expected state of the peer given the peer's score.
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone, Copy, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
 pub(crate) enum ScoreState {
     /// We are content with the peers performance. We permit connections and messages.
     Healthy,
@@ -169,16 +339,19 @@ impl RealScore {
         self.score
     }
 
-    /// Modifies the score based on a peer's action.
+    /// Modifies the score based on a peer's action, using the default penalties.
     pub fn apply_peer_action(&mut self, peer_action: PeerAction) {
-        match peer_action {
-            PeerAction::Fatal => self.set_lighthouse_score(MIN_SCORE), // The worst possible score
-            PeerAction::LowToleranceError => self.add(-10.0),
-            PeerAction::MidToleranceError => self.add(-5.0),
-            PeerAction::HighToleranceError => self.add(-1.0),
+        match PeerActionPenalties::default().get(peer_action) {
+            Some(penalty) => self.apply_penalty(penalty),
+            None => self.set_lighthouse_score(MIN_SCORE), // The worst possible score
         }
     }
 
+    /// Subtracts a penalty from the score abiding by the limits.
+    pub fn apply_penalty(&mut self, penalty: f64) {
+        self.add(-penalty)
+    }
+
     fn set_lighthouse_score(&mut self, new_score: f64) {
         self.lighthouse_score = new_score;
         self.update_state();
@@ -294,6 +467,7 @@ macro_rules! apply {
 }
 
 apply!(apply_peer_action, peer_action: PeerAction);
+apply!(apply_penalty, penalty: f64);
 apply!(update);
 apply!(update_gossipsub_score, new_score: f64, ignore: bool);
 #[cfg(test)]
@@ -422,4 +596,55 @@ mod tests {
         assert!(!score.is_good_gossipsub_peer());
         assert_eq!(score.score(), 0.0);
     }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_source_penalties_take_precedence() {
+        let mut config = PeerScoreConfig::default();
+        config
+            .set_penalties("low_tolerance_error=20, gossipsub.low_tolerance_error=2.5,sync.high_tolerance_error=0")
+            .unwrap();
+
+        let low = PeerAction::LowToleranceError;
+        assert_eq!(config.penalty(low, "gossipsub"), Some(2.5));
+        assert_eq!(config.penalty(low, "rpc_error"), Some(20.0));
+        assert_eq!(
+            config.penalty(PeerAction::HighToleranceError, "sync"),
+            Some(0.0)
+        );
+        // Unset source penalties fall back to the default.
+        assert_eq!(
+            config.penalty(PeerAction::MidToleranceError, "gossipsub"),
+            Some(5.0)
+        );
+        assert_eq!(config.penalty(PeerAction::Fatal, "gossipsub"), None);
+    }
+
+    #[test]
+    fn test_invalid_penalties() {
+        for penalties in [
+            "low_tolerance_error",
+            "low_tolerance_error=-1",
+            "low_tolerance_error=NaN",
+            "unknown_error=1",
+            "fatal=1",
+            "network.low_tolerance_error=1",
+            "gossipsub.fatal=1",
+        ] {
+            assert!(
+                PeerScoreConfig::default().set_penalties(penalties).is_err(),
+                "{}",
+                penalties
+            );
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_apply_penalty() {
+        let mut score = Score::default();
+        score.apply_penalty(20.0);
+        assert_eq!(score.score(), DEFAULT_SCORE - 20.0);
+        assert_eq!(score.state(), ScoreState::Disconnected);
+    }
 }
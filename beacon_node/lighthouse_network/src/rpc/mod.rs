@@ -36,7 +36,7 @@ pub(crate) mod codec;
 mod handler;
 pub mod methods;
 mod outbound;
-mod protocol;
+pub(crate) mod protocol;
 mod rate_limiter;
 
 /// RPC events sent from Lighthouse.
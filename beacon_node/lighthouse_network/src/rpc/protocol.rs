@@ -191,21 +191,26 @@ impl<TSpec: EthSpec> UpgradeInfo for RPCProtocol<TSpec> {
 
     /// The list of supported RPC protocols for Lighthouse.
     fn protocol_info(&self) -> Self::InfoIter {
-        vec![
-            ProtocolId::new(Protocol::Status, Version::V1, Encoding::SSZSnappy),
-            ProtocolId::new(Protocol::Goodbye, Version::V1, Encoding::SSZSnappy),
-            // V2 variants have higher preference then V1
-            ProtocolId::new(Protocol::BlocksByRange, Version::V2, Encoding::SSZSnappy),
-            ProtocolId::new(Protocol::BlocksByRange, Version::V1, Encoding::SSZSnappy),
-            ProtocolId::new(Protocol::BlocksByRoot, Version::V2, Encoding::SSZSnappy),
-            ProtocolId::new(Protocol::BlocksByRoot, Version::V1, Encoding::SSZSnappy),
-            ProtocolId::new(Protocol::Ping, Version::V1, Encoding::SSZSnappy),
-            ProtocolId::new(Protocol::MetaData, Version::V2, Encoding::SSZSnappy),
-            ProtocolId::new(Protocol::MetaData, Version::V1, Encoding::SSZSnappy),
-        ]
+        supported_protocol_ids()
     }
 }
 
+/// Returns every protocol we support, in order of preference.
+pub(crate) fn supported_protocol_ids() -> Vec<ProtocolId> {
+    vec![
+        ProtocolId::new(Protocol::Status, Version::V1, Encoding::SSZSnappy),
+        ProtocolId::new(Protocol::Goodbye, Version::V1, Encoding::SSZSnappy),
+        // V2 variants have higher preference then V1
+        ProtocolId::new(Protocol::BlocksByRange, Version::V2, Encoding::SSZSnappy),
+        ProtocolId::new(Protocol::BlocksByRange, Version::V1, Encoding::SSZSnappy),
+        ProtocolId::new(Protocol::BlocksByRoot, Version::V2, Encoding::SSZSnappy),
+        ProtocolId::new(Protocol::BlocksByRoot, Version::V1, Encoding::SSZSnappy),
+        ProtocolId::new(Protocol::Ping, Version::V1, Encoding::SSZSnappy),
+        ProtocolId::new(Protocol::MetaData, Version::V2, Encoding::SSZSnappy),
+        ProtocolId::new(Protocol::MetaData, Version::V1, Encoding::SSZSnappy),
+    ]
+}
+
 /// Represents the ssz length bounds for RPC messages.
 #[derive(Debug, PartialEq)]
 pub struct RpcLimits {
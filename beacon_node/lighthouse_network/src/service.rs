@@ -301,6 +301,11 @@ impl<TSpec: EthSpec> Service<TSpec> {
             .goodbye_peer(peer_id, reason, source);
     }
 
+    /// Disconnect every connected peer without banning them, providing a reason.
+    pub fn disconnect_all_peers(&mut self, reason: GoodbyeReason) {
+        self.swarm.behaviour_mut().disconnect_all_peers(reason);
+    }
+
     /// Sends a response to a peer's request.
     pub fn send_response(&mut self, peer_id: PeerId, id: PeerRequestId, response: Response<TSpec>) {
         self.swarm
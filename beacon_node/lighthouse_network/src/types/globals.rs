@@ -7,7 +7,7 @@ use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
 use parking_lot::RwLock;
 use std::collections::HashSet;
-use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
 use types::EthSpec;
 
 pub struct NetworkGlobals<TSpec: EthSpec> {
@@ -31,6 +31,10 @@ pub struct NetworkGlobals<TSpec: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// Whether the backfill sync has been paused by the user. Unlike `BackFillState::Paused`,
+    /// which is left automatically once synced peers are available, this is only cleared on
+    /// request.
+    pub backfill_paused: AtomicBool,
 }
 
 impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
@@ -53,6 +57,7 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            backfill_paused: AtomicBool::new(false),
         }
     }
 
@@ -112,6 +117,18 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
         self.backfill_state.read().clone()
     }
 
+    /// Returns `true` if the backfill sync has been paused by the user.
+    pub fn is_backfill_paused(&self) -> bool {
+        self.backfill_paused.load(Ordering::Relaxed)
+    }
+
+    /// Pauses or resumes the backfill sync at the request of the user.
+    ///
+    /// The old value is returned.
+    pub fn set_backfill_paused(&self, paused: bool) -> bool {
+        self.backfill_paused.swap(paused, Ordering::Relaxed)
+    }
+
     /// Returns a `Client` type if one is known for the `PeerId`.
     pub fn client(&self, peer_id: &PeerId) -> Client {
         self.peers
@@ -1,7 +1,7 @@
 use libp2p::gossipsub::{IdentTopic as Topic, TopicHash};
 use serde_derive::{Deserialize, Serialize};
-use strum::AsRefStr;
-use types::{SubnetId, SyncSubnetId};
+use strum::{AsRefStr, IntoStaticStr};
+use types::{ForkName, SubnetId, SyncSubnetId};
 
 use crate::Subnet;
 
@@ -18,14 +18,44 @@ pub const PROPOSER_SLASHING_TOPIC: &str = "proposer_slashing";
 pub const ATTESTER_SLASHING_TOPIC: &str = "attester_slashing";
 pub const SIGNED_CONTRIBUTION_AND_PROOF_TOPIC: &str = "sync_committee_contribution_and_proof";
 pub const SYNC_COMMITTEE_PREFIX_TOPIC: &str = "sync_committee_";
+pub const LIGHT_CLIENT_FINALITY_UPDATE: &str = "light_client_finality_update";
+pub const LIGHT_CLIENT_OPTIMISTIC_UPDATE: &str = "light_client_optimistic_update";
 
-pub const CORE_TOPICS: [GossipKind; 6] = [
+pub const BASE_CORE_TOPICS: [GossipKind; 5] = [
     GossipKind::BeaconBlock,
     GossipKind::BeaconAggregateAndProof,
     GossipKind::VoluntaryExit,
     GossipKind::ProposerSlashing,
     GossipKind::AttesterSlashing,
-    GossipKind::SignedContributionAndProof,
+];
+
+pub const ALTAIR_CORE_TOPICS: [GossipKind; 1] = [GossipKind::SignedContributionAndProof];
+
+/// Returns the core topics which are introduced by `fork_name`.
+pub fn fork_core_topics(fork_name: &ForkName) -> Vec<GossipKind> {
+    match fork_name {
+        ForkName::Base => BASE_CORE_TOPICS.to_vec(),
+        ForkName::Altair => ALTAIR_CORE_TOPICS.to_vec(),
+        ForkName::Merge => vec![],
+    }
+}
+
+/// Returns all the core topics which are active at `fork_name`, including those introduced by
+/// previous forks.
+pub fn core_topics_to_subscribe(fork_name: ForkName) -> Vec<GossipKind> {
+    let mut topics = fork_core_topics(&fork_name);
+    let mut current_fork = fork_name;
+    while let Some(previous_fork) = current_fork.previous_fork() {
+        topics.extend(fork_core_topics(&previous_fork));
+        current_fork = previous_fork;
+    }
+    topics
+}
+
+/// The topics which are only subscribed to when serving light clients, from Altair onwards.
+pub const LIGHT_CLIENT_GOSSIP_TOPICS: [GossipKind; 2] = [
+    GossipKind::LightClientFinalityUpdate,
+    GossipKind::LightClientOptimisticUpdate,
 ];
 
 /// A gossipsub topic which encapsulates the type of messages that should be sent and received over
@@ -42,7 +72,7 @@ pub struct GossipTopic {
 
 /// Enum that brings these topics into the rust type system.
 // NOTE: There is intentionally no unknown type here. We only allow known gossipsub topics.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, AsRefStr)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, AsRefStr, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
 pub enum GossipKind {
     /// Topic for publishing beacon blocks.
@@ -63,6 +93,10 @@ pub enum GossipKind {
     /// Topic for publishing unaggregated sync committee signatures on a particular subnet.
     #[strum(serialize = "sync_committee")]
     SyncCommitteeMessage(SyncSubnetId),
+    /// Topic for publishing finality updates for light clients.
+    LightClientFinalityUpdate,
+    /// Topic for publishing optimistic updates for light clients.
+    LightClientOptimisticUpdate,
 }
 
 impl std::fmt::Display for GossipKind {
@@ -141,6 +175,8 @@ impl GossipTopic {
                 VOLUNTARY_EXIT_TOPIC => GossipKind::VoluntaryExit,
                 PROPOSER_SLASHING_TOPIC => GossipKind::ProposerSlashing,
                 ATTESTER_SLASHING_TOPIC => GossipKind::AttesterSlashing,
+                LIGHT_CLIENT_FINALITY_UPDATE => GossipKind::LightClientFinalityUpdate,
+                LIGHT_CLIENT_OPTIMISTIC_UPDATE => GossipKind::LightClientOptimisticUpdate,
                 topic => match committee_topic_index(topic) {
                     Some(subnet) => match subnet {
                         Subnet::Attestation(s) => GossipKind::Attestation(s),
@@ -184,6 +220,8 @@ impl From<GossipTopic> for String {
             GossipKind::SyncCommitteeMessage(index) => {
                 format!("{}{}", SYNC_COMMITTEE_PREFIX_TOPIC, *index)
             }
+            GossipKind::LightClientFinalityUpdate => LIGHT_CLIENT_FINALITY_UPDATE.into(),
+            GossipKind::LightClientOptimisticUpdate => LIGHT_CLIENT_OPTIMISTIC_UPDATE.into(),
         };
         format!(
             "/{}/{}/{}/{}",
@@ -212,6 +250,8 @@ impl std::fmt::Display for GossipTopic {
             GossipKind::SyncCommitteeMessage(index) => {
                 format!("{}{}", SYNC_COMMITTEE_PREFIX_TOPIC, *index)
             }
+            GossipKind::LightClientFinalityUpdate => LIGHT_CLIENT_FINALITY_UPDATE.into(),
+            GossipKind::LightClientOptimisticUpdate => LIGHT_CLIENT_OPTIMISTIC_UPDATE.into(),
         };
         write!(
             f,
@@ -289,6 +329,8 @@ mod tests {
                 VoluntaryExit,
                 ProposerSlashing,
                 AttesterSlashing,
+                LightClientFinalityUpdate,
+                LightClientOptimisticUpdate,
             ]
             .iter()
             {
@@ -392,4 +434,35 @@ mod tests {
         assert_eq!("proposer_slashing", ProposerSlashing.as_ref());
         assert_eq!("attester_slashing", AttesterSlashing.as_ref());
     }
+
+    #[test]
+    fn test_into_static_str() {
+        // The static strings match `as_ref`, so that both may be used as the same label.
+        for kind in [
+            BeaconBlock,
+            BeaconAggregateAndProof,
+            Attestation(SubnetId::new(42)),
+            SyncCommitteeMessage(SyncSubnetId::new(42)),
+            VoluntaryExit,
+            LightClientOptimisticUpdate,
+        ] {
+            let static_str: &'static str = (&kind).into();
+            assert_eq!(static_str, kind.as_ref());
+        }
+    }
+
+    #[test]
+    fn test_core_topics_to_subscribe() {
+        let base_topics = core_topics_to_subscribe(ForkName::Base);
+        assert_eq!(base_topics, BASE_CORE_TOPICS.to_vec());
+
+        let altair_topics = core_topics_to_subscribe(ForkName::Altair);
+        assert!(altair_topics.contains(&SignedContributionAndProof));
+        assert!(BASE_CORE_TOPICS
+            .iter()
+            .all(|kind| altair_topics.contains(kind)));
+
+        let merge_topics = core_topics_to_subscribe(ForkName::Merge);
+        assert_eq!(merge_topics.len(), altair_topics.len());
+    }
 }
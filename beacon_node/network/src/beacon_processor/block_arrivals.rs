@@ -0,0 +1,235 @@
+//! Tracks how many times the same block arrives at the beacon processor, and via which source.
+//!
+//! A block should normally arrive once, via gossip, a single block/parent lookup or a chain
+//! segment. Repeated arrivals of the same block indicate either a bug in the sync logic (e.g.,
+//! re-requesting blocks which have already been imported) or a peer which repeatedly serves the
+//! same block. The `BlockArrivals` cache counts these repeats so they can be exposed as metrics and
+//! logged.
+
+use beacon_chain::parking_lot::Mutex;
+use lighthouse_network::PeerId;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use types::Hash256;
+
+/// The duration for which the arrivals of a block are remembered.
+pub const BLOCK_ARRIVAL_WINDOW: Duration = Duration::from_secs(384);
+
+/// The number of times a single peer may deliver the same block within `BLOCK_ARRIVAL_WINDOW`
+/// before the duplication is considered excessive.
+pub const MAX_ARRIVALS_PER_PEER: usize = 2;
+
+/// The route via which a block arrived at the beacon processor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockSource {
+    Gossip,
+    Rpc,
+    ChainSegment,
+}
+
+impl BlockSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlockSource::Gossip => "gossip",
+            BlockSource::Rpc => "rpc",
+            BlockSource::ChainSegment => "chain_segment",
+        }
+    }
+}
+
+/// The result of recording the arrival of a block.
+#[derive(Debug, PartialEq)]
+pub struct Arrival {
+    /// If the block has arrived before within the window, the source of its first arrival.
+    pub first_source: Option<BlockSource>,
+    /// The number of times the block has arrived within the window, including this arrival.
+    pub arrivals: usize,
+    /// The number of times the block has arrived from the peer within the window, including this
+    /// arrival. Zero if the peer is unknown.
+    pub peer_arrivals: usize,
+}
+
+impl Arrival {
+    /// Returns `true` if the peer has just exceeded `MAX_ARRIVALS_PER_PEER` for the block.
+    ///
+    /// Only the arrival which exceeds the limit returns `true`, so that each block is reported at
+    /// most once per peer.
+    pub fn is_excessive(&self) -> bool {
+        self.peer_arrivals == MAX_ARRIVALS_PER_PEER + 1
+    }
+}
+
+struct Arrivals {
+    first_source: BlockSource,
+    count: usize,
+    per_peer: HashMap<PeerId, usize>,
+}
+
+#[derive(Default)]
+struct Inner {
+    blocks: HashMap<Hash256, Arrivals>,
+    /// The block roots in `blocks` in the order of their first arrival, used for pruning.
+    first_seen: VecDeque<(Instant, Hash256)>,
+}
+
+/// A cache of the recent arrivals of blocks, shared between workers.
+#[derive(Clone, Default)]
+pub struct BlockArrivals {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl BlockArrivals {
+    /// Records the arrival of the block with `block_root` from `source`, optionally sent by
+    /// `peer_id`.
+    pub fn record(
+        &self,
+        block_root: Hash256,
+        source: BlockSource,
+        peer_id: Option<PeerId>,
+    ) -> Arrival {
+        self.record_at(Instant::now(), block_root, source, peer_id)
+    }
+
+    fn record_at(
+        &self,
+        now: Instant,
+        block_root: Hash256,
+        source: BlockSource,
+        peer_id: Option<PeerId>,
+    ) -> Arrival {
+        let mut inner = self.inner.lock();
+
+        // Forget blocks which first arrived outside of the window.
+        while let Some((first_seen, root)) = inner.first_seen.front().copied() {
+            if now.saturating_duration_since(first_seen) < BLOCK_ARRIVAL_WINDOW {
+                break;
+            }
+            inner.first_seen.pop_front();
+            inner.blocks.remove(&root);
+        }
+
+        let Inner { blocks, first_seen } = &mut *inner;
+        let arrivals = blocks.entry(block_root).or_insert_with(|| {
+            first_seen.push_back((now, block_root));
+            Arrivals {
+                first_source: source,
+                count: 0,
+                per_peer: HashMap::new(),
+            }
+        });
+
+        let first_source = if arrivals.count > 0 {
+            Some(arrivals.first_source)
+        } else {
+            None
+        };
+        arrivals.count += 1;
+
+        let peer_arrivals = peer_id.map_or(0, |peer_id| {
+            let count = arrivals.per_peer.entry(peer_id).or_default();
+            *count += 1;
+            *count
+        });
+
+        Arrival {
+            first_source,
+            arrivals: arrivals.count,
+            peer_arrivals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl BlockArrivals {
+        fn len(&self) -> usize {
+            self.inner.lock().blocks.len()
+        }
+    }
+
+    #[test]
+    fn counts_duplicates() {
+        let arrivals = BlockArrivals::default();
+        let now = Instant::now();
+        let root = Hash256::repeat_byte(1);
+        let peer = PeerId::random();
+
+        assert_eq!(
+            arrivals.record_at(now, root, BlockSource::Gossip, Some(peer)),
+            Arrival {
+                first_source: None,
+                arrivals: 1,
+                peer_arrivals: 1,
+            }
+        );
+        assert_eq!(
+            arrivals.record_at(now, root, BlockSource::ChainSegment, None),
+            Arrival {
+                first_source: Some(BlockSource::Gossip),
+                arrivals: 2,
+                peer_arrivals: 0,
+            }
+        );
+        assert_eq!(
+            arrivals.record_at(now, root, BlockSource::Rpc, Some(PeerId::random())),
+            Arrival {
+                first_source: Some(BlockSource::Gossip),
+                arrivals: 3,
+                peer_arrivals: 1,
+            }
+        );
+
+        // Other blocks are counted separately.
+        let other = arrivals.record_at(now, Hash256::repeat_byte(2), BlockSource::Rpc, Some(peer));
+        assert_eq!(other.first_source, None);
+        assert_eq!(other.arrivals, 1);
+        assert_eq!(arrivals.len(), 2);
+    }
+
+    #[test]
+    fn excessive_arrivals_from_peer() {
+        let arrivals = BlockArrivals::default();
+        let now = Instant::now();
+        let root = Hash256::repeat_byte(1);
+        let peer = PeerId::random();
+
+        for _ in 0..MAX_ARRIVALS_PER_PEER {
+            assert!(!arrivals
+                .record_at(now, root, BlockSource::Rpc, Some(peer))
+                .is_excessive());
+        }
+        assert!(arrivals
+            .record_at(now, root, BlockSource::Rpc, Some(peer))
+            .is_excessive());
+        // Only reported once.
+        assert!(!arrivals
+            .record_at(now, root, BlockSource::Rpc, Some(peer))
+            .is_excessive());
+    }
+
+    #[test]
+    fn prunes_after_window() {
+        let arrivals = BlockArrivals::default();
+        let now = Instant::now();
+        let root = Hash256::repeat_byte(1);
+
+        arrivals.record_at(now, root, BlockSource::Gossip, None);
+        let later = now + BLOCK_ARRIVAL_WINDOW - Duration::from_secs(1);
+        assert_eq!(
+            arrivals
+                .record_at(later, root, BlockSource::Rpc, None)
+                .arrivals,
+            2
+        );
+
+        // The window is measured from the first arrival.
+        let after_window = now + BLOCK_ARRIVAL_WINDOW;
+        let arrival = arrivals.record_at(after_window, root, BlockSource::Rpc, None);
+        assert_eq!(arrival.first_source, None);
+        assert_eq!(arrival.arrivals, 1);
+        assert_eq!(arrivals.len(), 1);
+    }
+}
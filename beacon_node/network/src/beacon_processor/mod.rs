@@ -50,17 +50,18 @@ use lighthouse_network::{
 use slog::{crit, debug, error, trace, warn, Logger};
 use std::collections::VecDeque;
 use std::fmt;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::{Arc, Weak};
 use std::task::Context;
 use std::time::{Duration, Instant};
 use std::{cmp, collections::HashSet};
-use task_executor::TaskExecutor;
-use tokio::sync::{mpsc, oneshot};
+use task_executor::{ShutdownStage, TaskExecutor};
+use tokio::sync::{mpsc, oneshot, watch};
 use types::{
-    Attestation, AttesterSlashing, Hash256, ProposerSlashing, SignedAggregateAndProof,
-    SignedBeaconBlock, SignedContributionAndProof, SignedVoluntaryExit, SubnetId,
-    SyncCommitteeMessage, SyncSubnetId,
+    Attestation, AttesterSlashing, Hash256, LightClientFinalityUpdate, LightClientOptimisticUpdate,
+    ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof,
+    SignedVoluntaryExit, SubnetId, SyncCommitteeMessage, SyncSubnetId,
 };
 use work_reprocessing_queue::{
     spawn_reprocess_scheduler, QueuedAggregate, QueuedUnaggregate, ReadyWork,
@@ -68,11 +69,13 @@ use work_reprocessing_queue::{
 
 use worker::{Toolbox, Worker};
 
+mod block_arrivals;
 mod tests;
 mod work_reprocessing_queue;
 mod worker;
 
 use crate::beacon_processor::work_reprocessing_queue::QueuedBlock;
+pub use block_arrivals::BlockArrivals;
 pub use worker::{GossipAggregatePackage, GossipAttestationPackage, ProcessId};
 
 /// The maximum size of the channel for work events to the `BeaconProcessor`.
@@ -133,6 +136,14 @@ const MAX_SYNC_MESSAGE_QUEUE_LEN: usize = 2048;
 /// start dropping them.
 const MAX_SYNC_CONTRIBUTION_QUEUE_LEN: usize = 1024;
 
+/// The maximum number of queued `LightClientFinalityUpdate` objects received on gossip that will be
+/// stored before we start dropping them.
+const MAX_GOSSIP_FINALITY_UPDATE_QUEUE_LEN: usize = 1_024;
+
+/// The maximum number of queued `LightClientOptimisticUpdate` objects received on gossip that will
+/// be stored before we start dropping them.
+const MAX_GOSSIP_OPTIMISTIC_UPDATE_QUEUE_LEN: usize = 1_024;
+
 /// The maximum number of queued `SignedBeaconBlock` objects received from the network RPC that
 /// will be stored before we start dropping them.
 const MAX_RPC_BLOCK_QUEUE_LEN: usize = 1_024;
@@ -190,6 +201,8 @@ pub const GOSSIP_PROPOSER_SLASHING: &str = "gossip_proposer_slashing";
 pub const GOSSIP_ATTESTER_SLASHING: &str = "gossip_attester_slashing";
 pub const GOSSIP_SYNC_SIGNATURE: &str = "gossip_sync_signature";
 pub const GOSSIP_SYNC_CONTRIBUTION: &str = "gossip_sync_contribution";
+pub const GOSSIP_LIGHT_CLIENT_FINALITY_UPDATE: &str = "gossip_light_client_finality_update";
+pub const GOSSIP_LIGHT_CLIENT_OPTIMISTIC_UPDATE: &str = "gossip_light_client_optimistic_update";
 pub const RPC_BLOCK: &str = "rpc_block";
 pub const CHAIN_SEGMENT: &str = "chain_segment";
 pub const STATUS_PROCESSING: &str = "status_processing";
@@ -446,6 +459,42 @@ impl<T: BeaconChainTypes> WorkEvent<T> {
         }
     }
 
+    /// Create a new `Work` event for some light client finality update.
+    pub fn gossip_light_client_finality_update(
+        message_id: MessageId,
+        peer_id: PeerId,
+        finality_update: Box<LightClientFinalityUpdate<T::EthSpec>>,
+        seen_timestamp: Duration,
+    ) -> Self {
+        Self {
+            drop_during_sync: true,
+            work: Work::GossipLightClientFinalityUpdate {
+                message_id,
+                peer_id,
+                finality_update,
+                seen_timestamp,
+            },
+        }
+    }
+
+    /// Create a new `Work` event for some light client optimistic update.
+    pub fn gossip_light_client_optimistic_update(
+        message_id: MessageId,
+        peer_id: PeerId,
+        optimistic_update: Box<LightClientOptimisticUpdate<T::EthSpec>>,
+        seen_timestamp: Duration,
+    ) -> Self {
+        Self {
+            drop_during_sync: true,
+            work: Work::GossipLightClientOptimisticUpdate {
+                message_id,
+                peer_id,
+                optimistic_update,
+                seen_timestamp,
+            },
+        }
+    }
+
     /// Create a new `Work` event for some exit.
     pub fn gossip_voluntary_exit(
         message_id: MessageId,
@@ -497,12 +546,17 @@ impl<T: BeaconChainTypes> WorkEvent<T> {
     /// Create a new `Work` event for some block, where the result from computation (if any) is
     /// sent to the other side of `result_tx`.
     pub fn rpc_beacon_block(
+        peer_id: PeerId,
         block: Box<SignedBeaconBlock<T::EthSpec>>,
     ) -> (Self, BlockResultReceiver<T::EthSpec>) {
         let (result_tx, result_rx) = oneshot::channel();
         let event = Self {
             drop_during_sync: false,
-            work: Work::RpcBlock { block, result_tx },
+            work: Work::RpcBlock {
+                peer_id,
+                block,
+                result_tx,
+            },
         };
         (event, result_rx)
     }
@@ -692,7 +746,20 @@ pub enum Work<T: BeaconChainTypes> {
         sync_contribution: Box<SignedContributionAndProof<T::EthSpec>>,
         seen_timestamp: Duration,
     },
+    GossipLightClientFinalityUpdate {
+        message_id: MessageId,
+        peer_id: PeerId,
+        finality_update: Box<LightClientFinalityUpdate<T::EthSpec>>,
+        seen_timestamp: Duration,
+    },
+    GossipLightClientOptimisticUpdate {
+        message_id: MessageId,
+        peer_id: PeerId,
+        optimistic_update: Box<LightClientOptimisticUpdate<T::EthSpec>>,
+        seen_timestamp: Duration,
+    },
     RpcBlock {
+        peer_id: PeerId,
         block: Box<SignedBeaconBlock<T::EthSpec>>,
         result_tx: BlockResultSender<T::EthSpec>,
     },
@@ -731,6 +798,8 @@ impl<T: BeaconChainTypes> Work<T> {
             Work::GossipAttesterSlashing { .. } => GOSSIP_ATTESTER_SLASHING,
             Work::GossipSyncSignature { .. } => GOSSIP_SYNC_SIGNATURE,
             Work::GossipSyncContribution { .. } => GOSSIP_SYNC_CONTRIBUTION,
+            Work::GossipLightClientFinalityUpdate { .. } => GOSSIP_LIGHT_CLIENT_FINALITY_UPDATE,
+            Work::GossipLightClientOptimisticUpdate { .. } => GOSSIP_LIGHT_CLIENT_OPTIMISTIC_UPDATE,
             Work::RpcBlock { .. } => RPC_BLOCK,
             Work::ChainSegment { .. } => CHAIN_SEGMENT,
             Work::Status { .. } => STATUS_PROCESSING,
@@ -839,6 +908,8 @@ pub struct BeaconProcessor<T: BeaconChainTypes> {
     pub max_workers: usize,
     pub current_workers: usize,
     pub importing_blocks: DuplicateCache,
+    pub block_arrivals: BlockArrivals,
+    pub failed_batch_dir: Option<PathBuf>,
     pub log: Logger,
 }
 
@@ -877,6 +948,11 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
         let mut sync_message_queue = LifoQueue::new(MAX_SYNC_MESSAGE_QUEUE_LEN);
         let mut sync_contribution_queue = LifoQueue::new(MAX_SYNC_CONTRIBUTION_QUEUE_LEN);
 
+        // Using LIFO queues for light client updates since only the most recent update is
+        // forwarded.
+        let mut finality_update_queue = LifoQueue::new(MAX_GOSSIP_FINALITY_UPDATE_QUEUE_LEN);
+        let mut optimistic_update_queue = LifoQueue::new(MAX_GOSSIP_OPTIMISTIC_UPDATE_QUEUE_LEN);
+
         // Using a FIFO queue for voluntary exits since it prevents exit censoring. I don't have
         // a strong feeling about queue type for exits.
         let mut gossip_voluntary_exit_queue = FifoQueue::new(MAX_GOSSIP_EXIT_QUEUE_LEN);
@@ -918,6 +994,21 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
 
         let executor = self.executor.clone();
 
+        // During a graceful shutdown, wait for the work which has been queued to be processed.
+        let (is_idle_tx, mut is_idle_rx) = watch::channel(true);
+        executor.on_shutdown(
+            ShutdownStage::DrainQueues,
+            "drain_beacon_processor",
+            async move {
+                while !*is_idle_rx.borrow() {
+                    // The manager has stopped.
+                    if is_idle_rx.changed().await.is_err() {
+                        break;
+                    }
+                }
+            },
+        );
+
         // The manager future will run on the core executor and delegate tasks to worker
         // threads on the blocking executor.
         let manager_future = async move {
@@ -1132,6 +1223,12 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                         // Check exits last since our validators don't get rewards from them.
                         } else if let Some(item) = gossip_voluntary_exit_queue.pop() {
                             self.spawn_worker(item, toolbox);
+                        // Light client updates are only served to light clients, so they come
+                        // after everything our validators depend on.
+                        } else if let Some(item) = finality_update_queue.pop() {
+                            self.spawn_worker(item, toolbox);
+                        } else if let Some(item) = optimistic_update_queue.pop() {
+                            self.spawn_worker(item, toolbox);
                         // This statement should always be the final else statement.
                         } else {
                             // Let the journal know that a worker is freed and there's nothing else
@@ -1216,6 +1313,12 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                             Work::GossipSyncContribution { .. } => {
                                 sync_contribution_queue.push(work)
                             }
+                            Work::GossipLightClientFinalityUpdate { .. } => {
+                                finality_update_queue.push(work)
+                            }
+                            Work::GossipLightClientOptimisticUpdate { .. } => {
+                                optimistic_update_queue.push(work)
+                            }
                             Work::RpcBlock { .. } => rpc_block_queue.push(work, work_id, &self.log),
                             Work::ChainSegment { .. } => {
                                 chain_segment_queue.push(work, work_id, &self.log)
@@ -1282,6 +1385,34 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                     gossip_attester_slashing_queue.len() as i64,
                 );
 
+                let queued_work = [
+                    aggregate_queue.len(),
+                    attestation_queue.len(),
+                    unknown_block_aggregate_queue.len(),
+                    unknown_block_attestation_queue.len(),
+                    sync_message_queue.len(),
+                    sync_contribution_queue.len(),
+                    finality_update_queue.len(),
+                    optimistic_update_queue.len(),
+                    gossip_voluntary_exit_queue.len(),
+                    gossip_proposer_slashing_queue.len(),
+                    gossip_attester_slashing_queue.len(),
+                    rpc_block_queue.len(),
+                    chain_segment_queue.len(),
+                    gossip_block_queue.len(),
+                    delayed_block_queue.len(),
+                    status_queue.len(),
+                    bbrange_queue.len(),
+                    bbroots_queue.len(),
+                ]
+                .iter()
+                .sum::<usize>();
+                let is_idle = self.current_workers == 0 && queued_work == 0;
+                if *is_idle_tx.borrow() != is_idle {
+                    // There is no receiver once the shutdown hooks have run.
+                    let _ = is_idle_tx.send(is_idle);
+                }
+
                 if aggregate_queue.is_full() && aggregate_debounce.elapsed() {
                     error!(
                         self.log,
@@ -1351,11 +1482,16 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
             chain,
             network_tx: self.network_tx.clone(),
             sync_tx: self.sync_tx.clone(),
+            failed_batch_dir: self.failed_batch_dir.clone(),
+            block_arrivals: self.block_arrivals.clone(),
             log: self.log.clone(),
         };
 
         let duplicate_cache = self.importing_blocks.clone();
 
+        // Backfill batches that reach a worker whilst the backfill sync is paused are rejected.
+        let backfill_paused = self.network_globals.is_backfill_paused();
+
         trace!(
             self.log,
             "Spawning beacon processor worker";
@@ -1506,11 +1642,41 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                         *sync_contribution,
                         seen_timestamp,
                     ),
+                    /*
+                     * Light client update verification.
+                     */
+                    Work::GossipLightClientFinalityUpdate {
+                        message_id,
+                        peer_id,
+                        finality_update,
+                        seen_timestamp,
+                    } => worker.process_gossip_finality_update(
+                        message_id,
+                        peer_id,
+                        *finality_update,
+                        seen_timestamp,
+                    ),
+                    Work::GossipLightClientOptimisticUpdate {
+                        message_id,
+                        peer_id,
+                        optimistic_update,
+                        seen_timestamp,
+                    } => worker.process_gossip_optimistic_update(
+                        message_id,
+                        peer_id,
+                        *optimistic_update,
+                        seen_timestamp,
+                    ),
                     /*
                      * Verification for beacon blocks received during syncing via RPC.
                      */
-                    Work::RpcBlock { block, result_tx } => {
+                    Work::RpcBlock {
+                        peer_id,
+                        block,
+                        result_tx,
+                    } => {
                         worker.process_rpc_block(
+                            peer_id,
                             *block,
                             result_tx,
                             work_reprocessing_tx.clone(),
@@ -1520,6 +1686,10 @@ impl<T: BeaconChainTypes> BeaconProcessor<T> {
                     /*
                      * Verification for a chain segment (multiple blocks).
                      */
+                    Work::ChainSegment {
+                        process_id: ProcessId::BackSyncBatchId(epoch),
+                        ..
+                    } if backfill_paused => worker.reject_backfill_segment(epoch),
                     Work::ChainSegment { process_id, blocks } => {
                         worker.process_chain_segment(process_id, blocks)
                     }
@@ -7,7 +7,7 @@ use crate::{service::NetworkMessage, sync::SyncMessage};
 use beacon_chain::test_utils::{
     AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
 };
-use beacon_chain::{BeaconChain, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
+use beacon_chain::{events::EventKind, BeaconChain, MAXIMUM_GOSSIP_CLOCK_DISPARITY};
 use environment::{null_logger, Environment, EnvironmentBuilder};
 use lighthouse_network::{
     discv5::enr::{CombinedKey, EnrBuilder},
@@ -202,6 +202,8 @@ impl TestRig {
             max_workers: cmp::max(1, num_cpus::get()),
             current_workers: 0,
             importing_blocks: Default::default(),
+            block_arrivals: Default::default(),
+            failed_batch_dir: None,
             log: log.clone(),
         }
         .spawn_manager(beacon_processor_rx, Some(work_journal_tx));
@@ -240,7 +242,8 @@ impl TestRig {
     }
 
     pub fn enqueue_rpc_block(&self) {
-        let (event, _rx) = WorkEvent::rpc_beacon_block(Box::new(self.next_block.clone()));
+        let (event, _rx) =
+            WorkEvent::rpc_beacon_block(junk_peer_id(), Box::new(self.next_block.clone()));
         self.beacon_processor_tx.try_send(event).unwrap();
     }
 
@@ -553,6 +556,40 @@ fn import_gossip_block_at_current_slot() {
     );
 }
 
+/// Ensure a `block_gossip` event is emitted for a block which passes gossip verification.
+#[test]
+fn import_gossip_block_emits_block_gossip_event() {
+    let mut rig = TestRig::new(SMALL_CHAIN);
+
+    let mut events = rig
+        .chain
+        .event_handler
+        .as_ref()
+        .expect("harness should have an event handler")
+        .subscribe_block_gossip();
+
+    rig.enqueue_gossip_block();
+
+    rig.assert_event_journal(&[GOSSIP_BLOCK, WORKER_FREED, NOTHING_TO_DO]);
+
+    let event = events.try_recv().expect("should emit a block_gossip event");
+    match event.event {
+        EventKind::BlockGossip(event) => {
+            assert_eq!(event.slot, rig.next_block.slot());
+            assert_eq!(event.block, rig.next_block.canonical_root());
+            assert_eq!(
+                event.proposer_index,
+                rig.next_block.message().proposer_index()
+            );
+        }
+        other => panic!("unexpected event {:?}", other),
+    }
+    assert!(
+        events.try_recv().is_err(),
+        "should emit a single block_gossip event"
+    );
+}
+
 /// Ensure a valid attestation can be imported.
 #[test]
 fn import_gossip_attestation() {
@@ -0,0 +1,228 @@
+//! Writes chain segments that failed to process due to an internal error to disk, so that users
+//! can attach reproducible artifacts to bug reports.
+
+use super::Worker;
+use crate::sync::FailedBlock;
+use beacon_chain::BeaconChainTypes;
+use serde::Serialize;
+use slog::{debug, warn};
+use ssz::Encode;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use types::{Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
+
+/// The name of the file that describes the failed batch.
+const METADATA_FILENAME: &str = "metadata.json";
+
+/// Describes a failed batch.
+///
+/// Peer identities and addresses are deliberately excluded so that the artifacts can be shared
+/// publicly.
+#[derive(Serialize)]
+struct FailedBatchMetadata {
+    lighthouse_version: &'static str,
+    batch_epoch: Epoch,
+    error: String,
+    block_count: usize,
+    first_slot: Option<Slot>,
+    last_slot: Option<Slot>,
+    failed_block_slot: Option<Slot>,
+    failed_block_root: Option<Hash256>,
+    /// The parent of the first block in the batch, from which the pre-state can be loaded.
+    pre_state_block_root: Option<Hash256>,
+    /// The state root of `pre_state_block_root`, if the block is known to the database.
+    pre_state_root: Option<Hash256>,
+}
+
+impl<T: BeaconChainTypes> Worker<T> {
+    /// Writes the SSZ encoded `blocks` of a failed batch and a JSON description of the failure to
+    /// a new sub-directory of the failed batch directory.
+    ///
+    /// Does nothing if the failed batch directory is not configured.
+    pub(super) fn write_failed_batch(
+        &self,
+        batch_epoch: Epoch,
+        blocks: &[SignedBeaconBlock<T::EthSpec>],
+        failed_block: Option<FailedBlock>,
+        error: &str,
+    ) {
+        let base_dir = match self.failed_batch_dir.as_ref() {
+            Some(dir) => dir,
+            None => return,
+        };
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let dir = base_dir.join(format!("{}_epoch_{}", timestamp, batch_epoch));
+
+        let pre_state_root = blocks
+            .first()
+            .and_then(|block| self.chain.get_block(&block.parent_root()).ok().flatten())
+            .map(|block| block.state_root());
+
+        match write_failed_batch_artifacts(
+            &dir,
+            batch_epoch,
+            blocks,
+            failed_block,
+            error,
+            pre_state_root,
+        ) {
+            Ok(()) => debug!(
+                self.log,
+                "Wrote failed batch to disk";
+                "batch_epoch" => batch_epoch,
+                "path" => ?dir,
+            ),
+            Err(e) => warn!(
+                self.log,
+                "Failed to write failed batch to disk";
+                "batch_epoch" => batch_epoch,
+                "path" => ?dir,
+                "error" => e,
+            ),
+        }
+    }
+}
+
+/// Writes the SSZ encoded `blocks` of a failed batch and a JSON description of the failure to
+/// `dir`.
+///
+/// The `pre_state_root` is the state root of the parent of the first block, if it is known.
+fn write_failed_batch_artifacts<E: EthSpec>(
+    dir: &Path,
+    batch_epoch: Epoch,
+    blocks: &[SignedBeaconBlock<E>],
+    failed_block: Option<FailedBlock>,
+    error: &str,
+    pre_state_root: Option<Hash256>,
+) -> Result<(), String> {
+    let metadata = FailedBatchMetadata {
+        lighthouse_version: lighthouse_version::VERSION,
+        batch_epoch,
+        error: error.to_string(),
+        block_count: blocks.len(),
+        first_slot: blocks.first().map(|block| block.slot()),
+        last_slot: blocks.last().map(|block| block.slot()),
+        failed_block_slot: failed_block.map(|failed| failed.slot),
+        failed_block_root: failed_block.map(|failed| failed.root),
+        pre_state_block_root: blocks.first().map(|block| block.parent_root()),
+        pre_state_root,
+    };
+
+    fs::create_dir_all(dir).map_err(|e| format!("Unable to create directory: {:?}", e))?;
+
+    for block in blocks {
+        let filename = format!(
+            "block_slot_{}_root_{:?}.ssz",
+            block.slot(),
+            block.canonical_root()
+        );
+        fs::write(dir.join(filename), block.as_ssz_bytes())
+            .map_err(|e| format!("Unable to write block: {:?}", e))?;
+    }
+
+    let json = serde_json::to_vec_pretty(&metadata)
+        .map_err(|e| format!("Unable to encode metadata: {:?}", e))?;
+    fs::write(dir.join(METADATA_FILENAME), json)
+        .map_err(|e| format!("Unable to write metadata: {:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::BlockFailureKind;
+    use serde_json::Value;
+    use types::{BeaconBlock, ChainSpec, MinimalEthSpec, Signature};
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn write_and_read_back_artifacts() {
+        let spec = ChainSpec::minimal();
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().join("1650000000_epoch_1");
+
+        let blocks = (8..11)
+            .map(|slot| {
+                let mut block = BeaconBlock::<E>::empty(&spec);
+                *block.slot_mut() = Slot::new(slot);
+                *block.parent_root_mut() = Hash256::repeat_byte(slot as u8);
+                SignedBeaconBlock::from_block(block, Signature::empty())
+            })
+            .collect::<Vec<_>>();
+        let failed_block = FailedBlock {
+            slot: blocks[1].slot(),
+            root: blocks[1].canonical_root(),
+            kind: BlockFailureKind::Internal,
+        };
+        let pre_state_root = Some(Hash256::repeat_byte(42));
+
+        write_failed_batch_artifacts(
+            &dir,
+            Epoch::new(1),
+            &blocks,
+            Some(failed_block),
+            "DBError",
+            pre_state_root,
+        )
+        .unwrap();
+
+        // Each block can be decoded from its file.
+        for block in &blocks {
+            let filename = format!(
+                "block_slot_{}_root_{:?}.ssz",
+                block.slot(),
+                block.canonical_root()
+            );
+            let bytes = fs::read(dir.join(filename)).unwrap();
+            assert_eq!(
+                &SignedBeaconBlock::<E>::from_ssz_bytes(&bytes, &spec).unwrap(),
+                block
+            );
+        }
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), blocks.len() + 1);
+
+        let metadata: Value =
+            serde_json::from_slice(&fs::read(dir.join(METADATA_FILENAME)).unwrap()).unwrap();
+        assert_eq!(
+            metadata["lighthouse_version"],
+            Value::from(lighthouse_version::VERSION)
+        );
+        assert_eq!(metadata["batch_epoch"], Value::from("1"));
+        assert_eq!(metadata["error"], Value::from("DBError"));
+        assert_eq!(metadata["block_count"], Value::from(3));
+        assert_eq!(metadata["first_slot"], Value::from("8"));
+        assert_eq!(metadata["last_slot"], Value::from("10"));
+        assert_eq!(metadata["failed_block_slot"], Value::from("9"));
+        assert_eq!(
+            metadata["failed_block_root"],
+            serde_json::to_value(failed_block.root).unwrap()
+        );
+        assert_eq!(
+            metadata["pre_state_block_root"],
+            serde_json::to_value(Hash256::repeat_byte(8)).unwrap()
+        );
+        assert_eq!(
+            metadata["pre_state_root"],
+            serde_json::to_value(pre_state_root).unwrap()
+        );
+    }
+
+    #[test]
+    fn write_empty_batch() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().join("empty");
+
+        write_failed_batch_artifacts::<E>(&dir, Epoch::new(0), &[], None, "error", None).unwrap();
+
+        let metadata: Value =
+            serde_json::from_slice(&fs::read(dir.join(METADATA_FILENAME)).unwrap()).unwrap();
+        assert_eq!(metadata["block_count"], Value::from(0));
+        assert_eq!(metadata["first_slot"], Value::Null);
+        assert_eq!(metadata["pre_state_block_root"], Value::Null);
+    }
+}
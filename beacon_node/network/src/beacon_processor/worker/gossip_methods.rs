@@ -3,6 +3,8 @@ use crate::{metrics, service::NetworkMessage, sync::SyncMessage};
 use beacon_chain::store::Error;
 use beacon_chain::{
     attestation_verification::{self, Error as AttnError, VerifiedAttestation},
+    events::{EventKind, SseBlockGossip},
+    light_client_update_verification::Error as LightClientUpdateError,
     observed_operations::ObservationOutcome,
     sync_committee_verification::{self, Error as SyncCommitteeError},
     validator_monitor::get_block_delay_ms,
@@ -17,12 +19,14 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use store::hot_cold_store::HotColdDBError;
 use tokio::sync::mpsc;
 use types::{
-    Attestation, AttesterSlashing, EthSpec, Hash256, IndexedAttestation, ProposerSlashing,
-    SignedAggregateAndProof, SignedBeaconBlock, SignedContributionAndProof, SignedVoluntaryExit,
-    Slot, SubnetId, SyncCommitteeMessage, SyncSubnetId,
+    Attestation, AttesterSlashing, EthSpec, Hash256, IndexedAttestation, LightClientFinalityUpdate,
+    LightClientOptimisticUpdate, ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock,
+    SignedContributionAndProof, SignedVoluntaryExit, Slot, SubnetId, SyncCommitteeMessage,
+    SyncSubnetId,
 };
 
 use super::{
+    super::block_arrivals::BlockSource,
     super::work_reprocessing_queue::{
         QueuedAggregate, QueuedBlock, QueuedUnaggregate, ReprocessQueueMessage,
     },
@@ -110,6 +114,14 @@ impl<T: EthSpec> FailedAtt<T> {
         }
     }
 
+    /// The name of the gossip topic on which the attestation was received.
+    pub fn topic(&self) -> &'static str {
+        match self {
+            FailedAtt::Unaggregate { .. } => "beacon_attestation",
+            FailedAtt::Aggregate { .. } => "beacon_aggregate_and_proof",
+        }
+    }
+
     pub fn attestation(&self) -> &Attestation<T> {
         match self {
             FailedAtt::Unaggregate { attestation, .. } => attestation,
@@ -195,12 +207,17 @@ impl<T: BeaconChainTypes> Worker<T> {
     /// Creates a log if there is an internal error.
     /// Propagates the result of the validation for the given message to the network. If the result
     /// is valid the message gets forwarded to other peers.
+    ///
+    /// The result is counted in the metrics by the message's `topic` and the `reason` for it.
     pub(crate) fn propagate_validation_result(
         &self,
         message_id: MessageId,
         propagation_source: PeerId,
         validation_result: MessageAcceptance,
+        topic: &str,
+        reason: &str,
     ) {
+        metrics::register_gossip_verification_outcome(topic, &validation_result, reason);
         self.send_network_message(NetworkMessage::ValidationResult {
             propagation_source,
             message_id,
@@ -348,7 +365,13 @@ impl<T: BeaconChainTypes> Worker<T> {
 
                 // Indicate to the `Network` service that this message is valid and can be
                 // propagated on the gossip network.
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Accept,
+                    "beacon_attestation",
+                    "Valid",
+                );
 
                 if !should_import {
                     return;
@@ -541,7 +564,13 @@ impl<T: BeaconChainTypes> Worker<T> {
 
                 // Indicate to the `Network` service that this message is valid and can be
                 // propagated on the gossip network.
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Accept,
+                    "beacon_aggregate_and_proof",
+                    "Valid",
+                );
 
                 // Register the attestation with any monitored validators.
                 self.chain
@@ -636,6 +665,8 @@ impl<T: BeaconChainTypes> Worker<T> {
         duplicate_cache: DuplicateCache,
         seen_duration: Duration,
     ) {
+        self.record_block_arrival(block.canonical_root(), BlockSource::Gossip, Some(peer_id));
+
         if let Some(gossip_verified_block) = self.process_gossip_unverified_block(
             message_id,
             peer_id,
@@ -714,7 +745,20 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "slot" => verified_block.block.slot(),
                     "root" => ?verified_block.block_root
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept, "beacon_block", "Valid");
+
+                if let Some(event_handler) = self.chain.event_handler.as_ref() {
+                    if event_handler.has_block_gossip_subscribers() {
+                        event_handler.register(EventKind::BlockGossip(SseBlockGossip {
+                            slot: verified_block.block.slot(),
+                            block: verified_block.block_root,
+                            proposer_index: verified_block.block.message().proposer_index(),
+                            peer_id: peer_id.to_string(),
+                            peer_client: peer_client.to_string(),
+                            arrival_delay: block_delay,
+                        }));
+                    }
+                }
 
                 // Log metrics to keep track of propagation delay times.
                 if let Some(duration) = SystemTime::now()
@@ -745,7 +789,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "Gossip block beacon chain error";
                     "error" => ?e,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore, "beacon_block", e.as_ref());
                 return None;
             }
             Err(e @ BlockError::FutureSlot { .. })
@@ -757,7 +801,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                             "error" => %e);
                 // Prevent recurring behaviour by penalizing the peer slightly.
                 self.gossip_penalize_peer(peer_id, PeerAction::HighToleranceError, "gossip_block_high");
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore, "beacon_block", e.as_ref());
                 return None;
             }
             // TODO(merge): reconsider peer scoring for this event.
@@ -765,7 +809,7 @@ impl<T: BeaconChainTypes> Worker<T> {
             | Err(e @BlockError::ExecutionPayloadError(ExecutionPayloadError::NoExecutionConnection)) => {
                 debug!(self.log, "Could not verify block for gossip, ignoring the block";
                             "error" => %e);
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore, "beacon_block", e.as_ref());
                 return None;
             }
             Err(e @ BlockError::StateRootMismatch { .. })
@@ -788,7 +832,7 @@ impl<T: BeaconChainTypes> Worker<T> {
             | Err(e @ BlockError::GenesisBlock) => {
                 warn!(self.log, "Could not verify block for gossip, rejecting the block";
                             "error" => %e);
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject, "beacon_block", e.as_ref());
                 self.gossip_penalize_peer(peer_id, PeerAction::LowToleranceError, "gossip_block_low");
                 return None;
             }
@@ -965,7 +1009,13 @@ impl<T: BeaconChainTypes> Worker<T> {
         let exit = match self.chain.verify_voluntary_exit_for_gossip(voluntary_exit) {
             Ok(ObservationOutcome::New(exit)) => exit,
             Ok(ObservationOutcome::AlreadyKnown) => {
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    "voluntary_exit",
+                    "AlreadyKnown",
+                );
                 debug!(
                     self.log,
                     "Dropping exit for already exiting validator";
@@ -984,7 +1034,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                 );
                 // These errors occur due to a fault in the beacon chain. It is not necessarily
                 // the fault on the peer.
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    "voluntary_exit",
+                    e.as_ref(),
+                );
                 // We still penalize a peer slightly to prevent overuse of invalids.
                 self.gossip_penalize_peer(
                     peer_id,
@@ -997,7 +1053,13 @@ impl<T: BeaconChainTypes> Worker<T> {
 
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_EXIT_VERIFIED_TOTAL);
 
-        self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+        self.propagate_validation_result(
+            message_id,
+            peer_id,
+            MessageAcceptance::Accept,
+            "voluntary_exit",
+            "Valid",
+        );
 
         // Register the exit with any monitored validators.
         self.chain
@@ -1033,7 +1095,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "validator_index" => validator_index,
                     "peer" => %peer_id
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    "proposer_slashing",
+                    "AlreadyKnown",
+                );
                 return;
             }
             Err(e) => {
@@ -1046,7 +1114,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer" => %peer_id,
                     "error" => ?e
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    "proposer_slashing",
+                    e.as_ref(),
+                );
 
                 // Penalize peer slightly for invalids.
                 self.gossip_penalize_peer(
@@ -1060,13 +1134,22 @@ impl<T: BeaconChainTypes> Worker<T> {
 
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_PROPOSER_SLASHING_VERIFIED_TOTAL);
 
-        self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+        self.propagate_validation_result(
+            message_id,
+            peer_id,
+            MessageAcceptance::Accept,
+            "proposer_slashing",
+            "Valid",
+        );
 
         // Register the slashing with any monitored validators.
-        self.chain
+        let validator_slashings = self
+            .chain
             .validator_monitor
             .read()
             .register_gossip_proposer_slashing(slashing.as_inner());
+        self.chain
+            .emit_validator_slashing_events(validator_slashings);
 
         self.chain.import_proposer_slashing(slashing);
         debug!(self.log, "Successfully imported proposer slashing");
@@ -1092,7 +1175,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "reason" => "Slashings already known for all slashed validators",
                     "peer" => %peer_id
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    "attester_slashing",
+                    "AlreadyKnown",
+                );
                 return;
             }
             Err(e) => {
@@ -1102,7 +1191,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer" => %peer_id,
                     "error" => ?e
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    "attester_slashing",
+                    e.as_ref(),
+                );
                 // Penalize peer slightly for invalids.
                 self.gossip_penalize_peer(
                     peer_id,
@@ -1115,13 +1210,22 @@ impl<T: BeaconChainTypes> Worker<T> {
 
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_ATTESTER_SLASHING_VERIFIED_TOTAL);
 
-        self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+        self.propagate_validation_result(
+            message_id,
+            peer_id,
+            MessageAcceptance::Accept,
+            "attester_slashing",
+            "Valid",
+        );
 
         // Register the slashing with any monitored validators.
-        self.chain
+        let validator_slashings = self
+            .chain
             .validator_monitor
             .read()
             .register_gossip_attester_slashing(slashing.as_inner());
+        self.chain
+            .emit_validator_slashing_events(validator_slashings);
 
         if let Err(e) = self.chain.import_attester_slashing(slashing) {
             debug!(self.log, "Error importing attester slashing"; "error" => ?e);
@@ -1157,6 +1261,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     peer_id,
                     message_id,
                     "sync_signature",
+                    "sync_committee",
                     e,
                     message_slot,
                     seen_timestamp,
@@ -1167,7 +1272,13 @@ impl<T: BeaconChainTypes> Worker<T> {
 
         // Indicate to the `Network` service that this message is valid and can be
         // propagated on the gossip network.
-        self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+        self.propagate_validation_result(
+            message_id,
+            peer_id,
+            MessageAcceptance::Accept,
+            "sync_committee",
+            "Valid",
+        );
 
         // Register the sync signature with any monitored validators.
         self.chain
@@ -1221,6 +1332,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     peer_id,
                     message_id,
                     "sync_contribution",
+                    "sync_committee_contribution_and_proof",
                     e,
                     contribution_slot,
                     seen_timestamp,
@@ -1231,7 +1343,13 @@ impl<T: BeaconChainTypes> Worker<T> {
 
         // Indicate to the `Network` service that this message is valid and can be
         // propagated on the gossip network.
-        self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
+        self.propagate_validation_result(
+            message_id,
+            peer_id,
+            MessageAcceptance::Accept,
+            "sync_committee_contribution_and_proof",
+            "Valid",
+        );
 
         self.chain
             .validator_monitor
@@ -1258,6 +1376,156 @@ impl<T: BeaconChainTypes> Worker<T> {
         metrics::inc_counter(&metrics::BEACON_PROCESSOR_SYNC_CONTRIBUTION_IMPORTED_TOTAL);
     }
 
+    /// Process the light client finality update received from the gossip network and, if it
+    /// matches our own update, tell the network thread to forward it.
+    pub fn process_gossip_finality_update(
+        self,
+        message_id: MessageId,
+        peer_id: PeerId,
+        finality_update: LightClientFinalityUpdate<T::EthSpec>,
+        seen_timestamp: Duration,
+    ) {
+        match self
+            .chain
+            .verify_finality_update_for_gossip(finality_update, seen_timestamp)
+        {
+            Ok(_) => {
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Accept,
+                    "light_client_finality_update",
+                    "Valid",
+                );
+                metrics::inc_counter(
+                    &metrics::BEACON_PROCESSOR_LIGHT_CLIENT_FINALITY_UPDATE_VERIFIED_TOTAL,
+                );
+            }
+            Err(e) => self.handle_light_client_update_failure(
+                peer_id,
+                message_id,
+                "light_client_finality_update",
+                e,
+            ),
+        }
+    }
+
+    /// Process the light client optimistic update received from the gossip network and, if it
+    /// matches our own update, tell the network thread to forward it.
+    pub fn process_gossip_optimistic_update(
+        self,
+        message_id: MessageId,
+        peer_id: PeerId,
+        optimistic_update: LightClientOptimisticUpdate<T::EthSpec>,
+        seen_timestamp: Duration,
+    ) {
+        match self
+            .chain
+            .verify_optimistic_update_for_gossip(optimistic_update, seen_timestamp)
+        {
+            Ok(_) => {
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Accept,
+                    "light_client_optimistic_update",
+                    "Valid",
+                );
+                metrics::inc_counter(
+                    &metrics::BEACON_PROCESSOR_LIGHT_CLIENT_OPTIMISTIC_UPDATE_VERIFIED_TOTAL,
+                );
+            }
+            Err(e) => self.handle_light_client_update_failure(
+                peer_id,
+                message_id,
+                "light_client_optimistic_update",
+                e,
+            ),
+        }
+    }
+
+    /// Handle an error whilst verifying a light client update from the network.
+    fn handle_light_client_update_failure(
+        &self,
+        peer_id: PeerId,
+        message_id: MessageId,
+        topic: &'static str,
+        error: LightClientUpdateError,
+    ) {
+        match &error {
+            LightClientUpdateError::InvalidSlotOrdering { .. } => {
+                /*
+                 * The headers of the update cannot belong to a valid update.
+                 *
+                 * The peer has published an invalid consensus message.
+                 */
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    error.as_ref(),
+                );
+                self.gossip_penalize_peer(
+                    peer_id,
+                    PeerAction::LowToleranceError,
+                    "light_client_invalid_slot_ordering",
+                );
+            }
+            LightClientUpdateError::TooEarly { .. }
+            | LightClientUpdateError::UpdateMismatch
+            | LightClientUpdateError::UpdateAlreadySeen { .. }
+            | LightClientUpdateError::LightClientServerDisabled => {
+                /*
+                 * The update may be valid, but it was received before the block at its signature
+                 * slot could propagate, we have not imported the same blocks as the peer, an
+                 * update at least as recent has already been forwarded, or we are not serving
+                 * light clients.
+                 *
+                 * The spec requires these updates to be ignored, the peer is not necessarily
+                 * faulty.
+                 */
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    error.as_ref(),
+                );
+            }
+            LightClientUpdateError::BeaconChainError(e) => {
+                /*
+                 * Lighthouse hit an unexpected error whilst processing the update. It should be
+                 * impossible to trigger a `BeaconChainError` from the network, so we have a bug.
+                 *
+                 * It's not clear if the message is invalid/malicious.
+                 */
+                error!(
+                    self.log,
+                    "Unable to validate light client update";
+                    "topic" => topic,
+                    "peer_id" => %peer_id,
+                    "error" => ?e,
+                );
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    error.as_ref(),
+                );
+            }
+        }
+
+        debug!(
+            self.log,
+            "Invalid light client update from network";
+            "topic" => topic,
+            "peer_id" => %peer_id,
+            "reason" => ?error,
+        );
+    }
+
     /// Handle an error whilst verifying an `Attestation` or `SignedAggregateAndProof` from the
     /// network.
     fn handle_attestation_verification_failure(
@@ -1271,6 +1539,8 @@ impl<T: BeaconChainTypes> Worker<T> {
     ) {
         let beacon_block_root = failed_att.beacon_block_root();
         let attestation_type = failed_att.kind();
+        let topic = failed_att.topic();
+        let reason = error.as_ref();
         metrics::register_attestation_error(&error);
         match &error {
             AttnError::FutureSlot { .. } => {
@@ -1297,7 +1567,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                 );
 
                 // Do not propagate these messages.
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
             }
             AttnError::PastSlot { .. } => {
                 // Produce a slot clock frozen at the time we received the message from the
@@ -1319,7 +1595,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     );
                 }
 
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
             }
             AttnError::InvalidSelectionProof { .. } | AttnError::InvalidSignature => {
                 /*
@@ -1327,7 +1609,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1341,7 +1629,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  * This is forbidden by the p2p spec. Reject the message.
                  *
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1362,7 +1656,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1383,7 +1683,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1404,7 +1710,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "block" => ?beacon_block_root,
                     "type" => ?attestation_type,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
                 return;
             }
             AttnError::AggregatorAlreadyKnown(_) => {
@@ -1422,7 +1734,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "type" => ?attestation_type,
                 );
                 // This is an allowed behaviour.
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
 
                 return;
             }
@@ -1445,7 +1763,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "type" => ?attestation_type,
                 );
 
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
 
                 return;
             }
@@ -1463,7 +1787,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "block" => ?beacon_block_root,
                     "type" => ?attestation_type,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1539,6 +1869,8 @@ impl<T: BeaconChainTypes> Worker<T> {
                         message_id,
                         peer_id,
                         MessageAcceptance::Ignore,
+                        topic,
+                        reason,
                     );
                 }
 
@@ -1561,7 +1893,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1575,7 +1913,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1588,7 +1932,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1601,7 +1951,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1614,7 +1970,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1631,7 +1993,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "expected" => ?expected,
                     "received" => ?received,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1644,7 +2012,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1657,7 +2031,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1670,7 +2050,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1694,7 +2080,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                 );
                 // In this case we wish to penalize gossipsub peers that do this to avoid future
                 // attestations that have too many skip slots.
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::MidToleranceError,
@@ -1705,7 +2097,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                 HotColdDBError::AttestationStateIsFinalized { .. },
             ))) => {
                 debug!(self.log, "Attestation for finalized state"; "peer_id" => % peer_id);
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
             }
             AttnError::BeaconChainError(e) => {
                 /*
@@ -1721,7 +2119,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "error" => ?e,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
             }
         }
 
@@ -1742,10 +2146,12 @@ impl<T: BeaconChainTypes> Worker<T> {
         peer_id: PeerId,
         message_id: MessageId,
         message_type: &str,
+        topic: &str,
         error: SyncCommitteeError,
         sync_committee_message_slot: Slot,
         seen_timestamp: Duration,
     ) {
+        let reason = error.as_ref();
         metrics::register_sync_committee_error(&error);
 
         match &error {
@@ -1772,7 +2178,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                 );
 
                 // Do not propagate these messages.
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
             }
             SyncCommitteeError::PastSlot { .. } => {
                 /*
@@ -1820,7 +2232,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     );
                 }
 
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
             }
             SyncCommitteeError::EmptyAggregationBitfield => {
                 /*
@@ -1829,7 +2247,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  * This is forbidden by the p2p spec. Reject the message.
                  *
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1843,7 +2267,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1858,7 +2288,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                 *
                 * The peer has published an invalid consensus message.
                 */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1879,7 +2315,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "type" => ?message_type,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
                 return;
             }
             SyncCommitteeError::UnknownValidatorIndex(_) => {
@@ -1895,7 +2337,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "type" => ?message_type,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1909,7 +2357,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "type" => ?message_type,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1926,7 +2380,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "expected" => ?expected,
                     "received" => ?received,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1939,7 +2399,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                  *
                  * The peer has published an invalid consensus message.
                  */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1966,7 +2432,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "sync_prior_known",
                 );
 
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
 
                 return;
             }
@@ -1984,7 +2456,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "error" => ?e,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
             }
             SyncCommitteeError::BeaconStateError(e) => {
                 /*
@@ -2000,7 +2478,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "error" => ?e,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
                 // Penalize the peer slightly
                 self.gossip_penalize_peer(
                     peer_id,
@@ -2015,7 +2499,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "error" => ?e,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
                 // Penalize the peer slightly
                 self.gossip_penalize_peer(
                     peer_id,
@@ -2030,7 +2520,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "error" => ?e,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
                 // Penalize the peer slightly
                 self.gossip_penalize_peer(
                     peer_id,
@@ -2048,7 +2544,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                     "peer_id" => %peer_id,
                     "error" => ?e,
                 );
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Ignore);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Ignore,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -2060,7 +2562,13 @@ impl<T: BeaconChainTypes> Worker<T> {
                 The subcommittee index is higher than `SYNC_COMMITTEE_SUBNET_COUNT`. This would imply
                 an invalid message.
                 */
-                self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Reject);
+                self.propagate_validation_result(
+                    message_id,
+                    peer_id,
+                    MessageAcceptance::Reject,
+                    topic,
+                    reason,
+                );
                 self.gossip_penalize_peer(
                     peer_id,
                     PeerAction::LowToleranceError,
@@ -1,10 +1,15 @@
+use super::block_arrivals::{BlockArrivals, BlockSource};
 use super::work_reprocessing_queue::ReprocessQueueMessage;
-use crate::{service::NetworkMessage, sync::SyncMessage};
+use crate::{metrics, service::NetworkMessage, sync::SyncMessage};
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use slog::{debug, Logger};
+use lighthouse_network::PeerId;
+use slog::{debug, warn, Logger};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc;
+use types::Hash256;
 
+mod failed_batch;
 mod gossip_methods;
 mod rpc_methods;
 mod sync_methods;
@@ -19,6 +24,8 @@ pub struct Worker<T: BeaconChainTypes> {
     pub chain: Arc<BeaconChain<T>>,
     pub network_tx: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
     pub sync_tx: mpsc::UnboundedSender<SyncMessage<T::EthSpec>>,
+    pub failed_batch_dir: Option<PathBuf>,
+    pub block_arrivals: BlockArrivals,
     pub log: Logger,
 }
 
@@ -42,6 +49,49 @@ impl<T: BeaconChainTypes> Worker<T> {
                 "error" => %e)
         });
     }
+
+    /// Records the arrival of a block in `self.block_arrivals`, updating the metrics for duplicate
+    /// arrivals and logging peers which repeatedly send the same block.
+    fn record_block_arrival(
+        &self,
+        block_root: Hash256,
+        source: BlockSource,
+        peer_id: Option<PeerId>,
+    ) {
+        let arrival = self.block_arrivals.record(block_root, source, peer_id);
+
+        if let Some(first_source) = arrival.first_source {
+            metrics::inc_counter_vec(
+                &metrics::BEACON_PROCESSOR_DUPLICATE_BLOCKS_TOTAL,
+                &[source.as_str(), first_source.as_str()],
+            );
+            debug!(
+                self.log,
+                "Duplicate block arrival";
+                "block_root" => ?block_root,
+                "source" => source.as_str(),
+                "first_source" => first_source.as_str(),
+                "arrivals" => arrival.arrivals,
+            );
+        }
+
+        if arrival.is_excessive() {
+            metrics::inc_counter_vec(
+                &metrics::BEACON_PROCESSOR_EXCESSIVE_DUPLICATE_BLOCKS_TOTAL,
+                &[source.as_str()],
+            );
+            if let Some(peer_id) = peer_id {
+                warn!(
+                    self.log,
+                    "Peer repeatedly sent the same block";
+                    "peer_id" => %peer_id,
+                    "block_root" => ?block_root,
+                    "source" => source.as_str(),
+                    "peer_arrivals" => arrival.peer_arrivals,
+                );
+            }
+        }
+    }
 }
 
 /// Contains the necessary items for a worker to do their job.
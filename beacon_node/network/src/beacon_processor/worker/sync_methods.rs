@@ -1,16 +1,20 @@
-use super::{super::work_reprocessing_queue::ReprocessQueueMessage, Worker};
+use super::{
+    super::block_arrivals::BlockSource, super::work_reprocessing_queue::ReprocessQueueMessage,
+    Worker,
+};
 use crate::beacon_processor::worker::FUTURE_SLOT_TOLERANCE;
 use crate::beacon_processor::{BlockResultSender, DuplicateCache};
 use crate::metrics;
 use crate::sync::manager::{SyncMessage, SyncRequestType};
-use crate::sync::{BatchProcessResult, ChainId};
+use crate::sync::{BatchProcessResult, BlockFailureKind, ChainId, FailedBlock};
 use beacon_chain::{
     BeaconChainError, BeaconChainTypes, BlockError, ChainSegmentResult, HistoricalBlockError,
 };
 use lighthouse_network::{PeerAction, PeerId};
 use slog::{crit, debug, error, info, trace, warn};
 use tokio::sync::mpsc;
-use types::{Epoch, Hash256, SignedBeaconBlock};
+use tracing::info_span;
+use types::{Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 /// Id associated to a block processing request, either a batch or a single block.
 #[derive(Clone, Debug, PartialEq)]
@@ -29,6 +33,8 @@ struct ChainSegmentFailed {
     message: String,
     /// Used to penalize peers.
     peer_action: Option<PeerAction>,
+    /// Used by sync to re-request only the blocks following the last imported block.
+    failed_block: Option<FailedBlock>,
 }
 
 impl<T: BeaconChainTypes> Worker<T> {
@@ -38,12 +44,14 @@ impl<T: BeaconChainTypes> Worker<T> {
     /// Raises a log if there are errors publishing the result to the channel.
     pub fn process_rpc_block(
         self,
+        peer_id: PeerId,
         block: SignedBeaconBlock<T::EthSpec>,
         result_tx: BlockResultSender<T::EthSpec>,
         reprocess_tx: mpsc::Sender<ReprocessQueueMessage<T>>,
         duplicate_cache: DuplicateCache,
     ) {
         let block_root = block.canonical_root();
+        self.record_block_arrival(block_root, BlockSource::Rpc, Some(peer_id));
         // Checks if the block is already being imported through another source
         if let Some(handle) = duplicate_cache.check_and_insert(block_root) {
             let slot = block.slot();
@@ -99,6 +107,25 @@ impl<T: BeaconChainTypes> Worker<T> {
         }
     }
 
+    /// Reject a backfill chain segment without processing it because the backfill sync is
+    /// paused. The sync thread is informed so that the batch can be downloaded again once the
+    /// sync resumes.
+    pub fn reject_backfill_segment(&self, epoch: Epoch) {
+        debug!(self.log, "Backfill batch rejected";
+            "batch_epoch" => epoch,
+            "reason" => "backfill_paused",
+            "service" => "sync");
+        self.send_sync_message(SyncMessage::BatchProcessed {
+            sync_type: SyncRequestType::BackFillSync(epoch),
+            result: BatchProcessResult::Failed {
+                imported_blocks: false,
+                peer_action: None,
+                failed_block: None,
+                last_imported_slot: None,
+            },
+        });
+    }
+
     /// Attempt to import the chain segment (`blocks`) to the beacon chain, informing the sync
     /// thread if more blocks are needed to process it.
     pub fn process_chain_segment(
@@ -106,6 +133,17 @@ impl<T: BeaconChainTypes> Worker<T> {
         process_id: ProcessId,
         downloaded_blocks: Vec<SignedBeaconBlock<T::EthSpec>>,
     ) {
+        let _span = info_span!(
+            "process_chain_segment",
+            process_id = ?process_id,
+            blocks = downloaded_blocks.len(),
+        )
+        .entered();
+
+        for block in &downloaded_blocks {
+            self.record_block_arrival(block.canonical_root(), BlockSource::ChainSegment, None);
+        }
+
         match process_id {
             // this a request from the range sync
             ProcessId::RangeBatchId(chain_id, epoch) => {
@@ -134,9 +172,32 @@ impl<T: BeaconChainTypes> Worker<T> {
                             "error" => %e.message,
                             "service" => "sync");
 
+                        let last_imported_slot = if imported_blocks > 0 {
+                            self.last_imported_slot(&downloaded_blocks)
+                        } else {
+                            None
+                        };
+
+                        if matches!(
+                            e.failed_block,
+                            Some(FailedBlock {
+                                kind: BlockFailureKind::Internal,
+                                ..
+                            })
+                        ) {
+                            self.write_failed_batch(
+                                epoch,
+                                &downloaded_blocks,
+                                e.failed_block,
+                                &e.message,
+                            );
+                        }
+
                         BatchProcessResult::Failed {
                             imported_blocks: imported_blocks > 0,
                             peer_action: e.peer_action,
+                            failed_block: e.failed_block,
+                            last_imported_slot,
                         }
                     }
                 };
@@ -171,6 +232,8 @@ impl<T: BeaconChainTypes> Worker<T> {
                         BatchProcessResult::Failed {
                             imported_blocks: false,
                             peer_action: e.peer_action,
+                            failed_block: e.failed_block,
+                            last_imported_slot: None,
                         }
                     }
                 };
@@ -209,7 +272,11 @@ impl<T: BeaconChainTypes> Worker<T> {
         &self,
         downloaded_blocks: impl Iterator<Item = &'a SignedBeaconBlock<T::EthSpec>>,
     ) -> (usize, Result<(), ChainSegmentFailed>) {
-        let blocks = downloaded_blocks.cloned().collect::<Vec<_>>();
+        let downloaded_blocks = downloaded_blocks.collect::<Vec<_>>();
+        let blocks = downloaded_blocks
+            .iter()
+            .map(|&block| block.clone())
+            .collect();
         match self.chain.process_chain_segment(blocks) {
             ChainSegmentResult::Successful { imported_blocks } => {
                 metrics::inc_counter(&metrics::BEACON_PROCESSOR_CHAIN_SEGMENT_SUCCESS_TOTAL);
@@ -225,7 +292,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                 error,
             } => {
                 metrics::inc_counter(&metrics::BEACON_PROCESSOR_CHAIN_SEGMENT_FAILED_TOTAL);
-                let r = self.handle_failed_chain_segment(error);
+                let r = self.handle_failed_chain_segment(error, &downloaded_blocks);
                 if imported_blocks > 0 {
                     self.run_fork_choice();
                 }
@@ -270,6 +337,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                                 message: String::from("mismatched_block_root"),
                                 // The peer is faulty if they send blocks with bad roots.
                                 peer_action: Some(PeerAction::LowToleranceError),
+                                failed_block: None,
                             }
                         }
                         HistoricalBlockError::InvalidSignature
@@ -284,6 +352,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                                 message: "invalid_signature".into(),
                                 // The peer is faulty if they bad signatures.
                                 peer_action: Some(PeerAction::LowToleranceError),
+                                failed_block: None,
                             }
                         }
                         HistoricalBlockError::ValidatorPubkeyCacheTimeout => {
@@ -297,6 +366,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                                 message: "pubkey_cache_timeout".into(),
                                 // This is an internal error, do not penalize the peer.
                                 peer_action: None,
+                                failed_block: None,
                             }
                         }
                         HistoricalBlockError::NoAnchorInfo => {
@@ -307,6 +377,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                                 // There is no need to do a historical sync, this is not a fault of
                                 // the peer.
                                 peer_action: None,
+                                failed_block: None,
                             }
                         }
                         HistoricalBlockError::IndexOutOfBounds => {
@@ -319,6 +390,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                                 message: String::from("logic_error"),
                                 // This should never occur, don't penalize the peer.
                                 peer_action: None,
+                                failed_block: None,
                             }
                         }
                         HistoricalBlockError::BlockOutOfRange { .. } => {
@@ -331,6 +403,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                                 message: String::from("unexpected_error"),
                                 // This should never occur, don't penalize the peer.
                                 peer_action: None,
+                                failed_block: None,
                             }
                         }
                     },
@@ -340,6 +413,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                             message: format!("{:?}", other),
                             // This is an internal error, don't penalize the peer.
                             peer_action: None,
+                            failed_block: None,
                         }
                     }
                 };
@@ -370,6 +444,7 @@ impl<T: BeaconChainTypes> Worker<T> {
     fn handle_failed_chain_segment(
         &self,
         error: BlockError<T::EthSpec>,
+        blocks: &[&SignedBeaconBlock<T::EthSpec>],
     ) -> Result<(), ChainSegmentFailed> {
         match error {
             BlockError::ParentUnknown(block) => {
@@ -378,6 +453,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     message: format!("Block has an unknown parent: {}", block.parent_root()),
                     // Peers are faulty if they send non-sequential blocks.
                     peer_action: Some(PeerAction::LowToleranceError),
+                    failed_block: self.find_failed_block(blocks, BlockFailureKind::ParentUnknown),
                 })
             }
             BlockError::BlockIsAlreadyKnown => {
@@ -415,6 +491,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     ),
                     // Peers are faulty if they send blocks from the future.
                     peer_action: Some(PeerAction::LowToleranceError),
+                    failed_block: self.find_failed_block(blocks, BlockFailureKind::FutureSlot),
                 })
             }
             BlockError::WouldRevertFinalizedSlot { .. } => {
@@ -436,6 +513,7 @@ impl<T: BeaconChainTypes> Worker<T> {
                     message: format!("Internal error whilst processing block: {:?}", e),
                     // Do not penalize peers for internal errors.
                     peer_action: None,
+                    failed_block: self.find_failed_block(blocks, BlockFailureKind::Internal),
                 })
             }
             other => {
@@ -449,8 +527,51 @@ impl<T: BeaconChainTypes> Worker<T> {
                     message: format!("Peer sent invalid block. Reason: {:?}", other),
                     // Do not penalize peers for internal errors.
                     peer_action: None,
+                    failed_block: self.find_failed_block(blocks, BlockFailureKind::InvalidBlock),
                 })
             }
         }
     }
+
+    /// Returns the slot of the last block of the leading run of `blocks` that is known to fork
+    /// choice, i.e. the progress made by a partially successful chain segment import.
+    fn last_imported_slot(&self, blocks: &[SignedBeaconBlock<T::EthSpec>]) -> Option<Slot> {
+        let fork_choice = self.chain.fork_choice.read();
+        let finalized_slot = fork_choice
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+
+        blocks
+            .iter()
+            // Blocks prior to finalization are skipped during import.
+            .filter(|block| block.slot() > finalized_slot)
+            .take_while(|block| fork_choice.contains_block(&block.canonical_root()))
+            .last()
+            .map(|block| block.slot())
+    }
+
+    /// Returns the first block in `blocks` that has not been imported, tagged with the `kind` of
+    /// error that caused the segment to fail.
+    ///
+    /// Chain segments are imported in order, so this is the block that failed processing.
+    fn find_failed_block(
+        &self,
+        blocks: &[&SignedBeaconBlock<T::EthSpec>],
+        kind: BlockFailureKind,
+    ) -> Option<FailedBlock> {
+        let fork_choice = self.chain.fork_choice.read();
+        let finalized_slot = fork_choice
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+
+        blocks
+            .iter()
+            // Blocks prior to finalization are skipped during import.
+            .filter(|block| block.slot() > finalized_slot)
+            .map(|block| (block.slot(), block.canonical_root()))
+            .find(|(_, root)| !fork_choice.contains_block(root))
+            .map(|(slot, root)| FailedBlock { slot, root, kind })
+    }
 }
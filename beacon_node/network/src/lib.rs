@@ -10,6 +10,7 @@ mod beacon_processor;
 #[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
 mod metrics;
 mod nat;
+mod persisted_backfill;
 mod persisted_dht;
 mod router;
 mod status;
@@ -5,7 +5,7 @@ use beacon_chain::{
 use fnv::FnvHashMap;
 pub use lighthouse_metrics::*;
 use lighthouse_network::{
-    types::GossipKind, BandwidthSinks, GossipTopic, Gossipsub, NetworkGlobals,
+    types::GossipKind, BandwidthSinks, GossipTopic, Gossipsub, MessageAcceptance, NetworkGlobals,
 };
 use std::sync::Arc;
 use strum::AsStaticRef;
@@ -155,6 +155,17 @@ lazy_static! {
         "beacon_processor_rpc_block_imported_total",
         "Total number of gossip blocks imported to fork choice, etc."
     );
+    // Duplicate blocks.
+    pub static ref BEACON_PROCESSOR_DUPLICATE_BLOCKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_processor_duplicate_blocks_total",
+        "Count of blocks which arrived at the beacon processor more than once, by source and the source of the first arrival.",
+        &["source", "first_source"]
+    );
+    pub static ref BEACON_PROCESSOR_EXCESSIVE_DUPLICATE_BLOCKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_processor_excessive_duplicate_blocks_total",
+        "Count of times a single peer sent the same block more often than expected, by source.",
+        &["source"]
+    );
     // Chain segments.
     pub static ref BEACON_PROCESSOR_CHAIN_SEGMENT_QUEUE_TOTAL: Result<IntGauge> = try_create_int_gauge(
         "beacon_processor_chain_segment_queue_total",
@@ -237,6 +248,15 @@ lazy_static! {
         "beacon_processor_sync_contribution_imported_total",
         "Total number of sync committee contributions imported to fork choice, etc."
     );
+    // Gossip light client updates.
+    pub static ref BEACON_PROCESSOR_LIGHT_CLIENT_FINALITY_UPDATE_VERIFIED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_light_client_finality_update_verified_total",
+        "Total number of light client finality updates verified for gossip."
+    );
+    pub static ref BEACON_PROCESSOR_LIGHT_CLIENT_OPTIMISTIC_UPDATE_VERIFIED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "beacon_processor_light_client_optimistic_update_verified_total",
+        "Total number of light client optimistic updates verified for gossip."
+    );
 
     /// Errors and Debugging Stats
     pub static ref GOSSIP_ATTESTATION_ERRORS_PER_TYPE: Result<IntCounterVec> =
@@ -251,6 +271,13 @@ lazy_static! {
             "Gossipsub sync_committee errors per error type",
             &["type"]
         );
+    pub static ref GOSSIP_VERIFICATION_OUTCOMES: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "gossipsub_verification_outcomes_total",
+            "Count of gossip messages verified, per topic, outcome (accept, ignore or reject) and \
+             the reason for the outcome",
+            &["topic", "outcome", "reason"]
+        );
 }
 
 lazy_static! {
@@ -338,6 +365,19 @@ pub fn register_sync_committee_error(error: &SyncCommitteeError) {
     inc_counter_vec(&GOSSIP_SYNC_COMMITTEE_ERRORS_PER_TYPE, &[error.as_ref()]);
 }
 
+pub fn register_gossip_verification_outcome(
+    topic: &str,
+    outcome: &MessageAcceptance,
+    reason: &str,
+) {
+    let outcome = match outcome {
+        MessageAcceptance::Accept => "accept",
+        MessageAcceptance::Ignore => "ignore",
+        MessageAcceptance::Reject => "reject",
+    };
+    inc_counter_vec(&GOSSIP_VERIFICATION_OUTCOMES, &[topic, outcome, reason]);
+}
+
 pub fn update_gossip_metrics<T: EthSpec>(
     gossipsub: &Gossipsub,
     network_globals: &Arc<NetworkGlobals<T>>,
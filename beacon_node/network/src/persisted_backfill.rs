@@ -0,0 +1,66 @@
+use std::sync::Arc;
+use store::metadata::{BackfillPaused, BACKFILL_PAUSED_KEY};
+use store::{HotColdDB, ItemStore};
+use types::EthSpec;
+
+/// Returns `true` if the backfill sync was paused by the user before the node was last shut down.
+pub fn load_backfill_paused<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+) -> Result<bool, store::Error> {
+    Ok(store
+        .get_item::<BackfillPaused>(&BACKFILL_PAUSED_KEY)?
+        .map_or(false, |paused| paused.0))
+}
+
+/// Attempt to persist whether the backfill sync is paused by the user to `store`.
+pub fn persist_backfill_paused<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    store: Arc<HotColdDB<E, Hot, Cold>>,
+    paused: bool,
+) -> Result<(), store::Error> {
+    store.put_item(&BACKFILL_PAUSED_KEY, &BackfillPaused(paused))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use store::config::StoreConfig;
+    use store::LevelDB;
+    use tempfile::tempdir;
+    use types::{ChainSpec, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    fn open_store(path: &std::path::Path) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+        let log = NullLoggerBuilder.build().unwrap();
+        HotColdDB::open(
+            &path.join("hot_db"),
+            &path.join("cold_db"),
+            |_, _, _| Ok(()),
+            StoreConfig::default(),
+            ChainSpec::minimal(),
+            log,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn backfill_pause_survives_restart() {
+        let dir = tempdir().unwrap();
+
+        let store = open_store(dir.path());
+        assert!(!load_backfill_paused(store.clone()).unwrap());
+        persist_backfill_paused(store.clone(), true).unwrap();
+        drop(store);
+
+        // The pause is restored when the database is reopened.
+        let store = open_store(dir.path());
+        assert!(load_backfill_paused(store.clone()).unwrap());
+        persist_backfill_paused(store.clone(), false).unwrap();
+        drop(store);
+
+        // And so is a subsequent resume.
+        let store = open_store(dir.path());
+        assert!(!load_backfill_paused(store).unwrap());
+    }
+}
@@ -17,6 +17,7 @@ use lighthouse_network::{
 };
 use processor::Processor;
 use slog::{debug, o, trace};
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::UnboundedReceiverStream;
@@ -66,6 +67,8 @@ pub enum RouterMessage<T: EthSpec> {
     PubsubMessage(MessageId, PeerId, PubsubMessage<T>, bool),
     /// The peer manager has requested we re-status a peer.
     StatusPeer(PeerId),
+    /// Pause or resume the backfill sync.
+    SetBackfillPaused(bool),
 }
 
 impl<T: BeaconChainTypes> Router<T> {
@@ -74,6 +77,8 @@ impl<T: BeaconChainTypes> Router<T> {
         beacon_chain: Arc<BeaconChain<T>>,
         network_globals: Arc<NetworkGlobals<T::EthSpec>>,
         network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
+        disable_backfill: bool,
+        failed_batch_dir: Option<PathBuf>,
         executor: task_executor::TaskExecutor,
         log: slog::Logger,
     ) -> error::Result<mpsc::UnboundedSender<RouterMessage<T::EthSpec>>> {
@@ -88,6 +93,8 @@ impl<T: BeaconChainTypes> Router<T> {
             beacon_chain,
             network_globals.clone(),
             network_send,
+            disable_backfill,
+            failed_batch_dir,
             &log,
         );
 
@@ -124,6 +131,9 @@ impl<T: BeaconChainTypes> Router<T> {
             RouterMessage::PeerDisconnected(peer_id) => {
                 self.processor.on_disconnect(peer_id);
             }
+            RouterMessage::SetBackfillPaused(paused) => {
+                self.processor.set_backfill_paused(paused);
+            }
             RouterMessage::RPCRequestReceived {
                 peer_id,
                 id,
@@ -277,6 +287,27 @@ impl<T: BeaconChainTypes> Router<T> {
                     sync_committtee_msg.0,
                 );
             }
+            PubsubMessage::LightClientFinalityUpdate(finality_update) => {
+                trace!(
+                    self.log,
+                    "Received light client finality update";
+                    "peer_id" => %peer_id
+                );
+                self.processor
+                    .on_light_client_finality_update_gossip(id, peer_id, finality_update);
+            }
+            PubsubMessage::LightClientOptimisticUpdate(optimistic_update) => {
+                trace!(
+                    self.log,
+                    "Received light client optimistic update";
+                    "peer_id" => %peer_id
+                );
+                self.processor.on_light_client_optimistic_update_gossip(
+                    id,
+                    peer_id,
+                    optimistic_update,
+                );
+            }
         }
     }
 }
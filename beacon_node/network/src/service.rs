@@ -6,7 +6,10 @@ use crate::{
     subnet_service::{AttestationService, SubnetServiceMessage},
     NetworkConfig,
 };
-use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use beacon_chain::{
+    light_client_update_verification::light_client_update_delay, BeaconChain, BeaconChainError,
+    BeaconChainTypes,
+};
 use futures::future::OptionFuture;
 use futures::prelude::*;
 use lighthouse_network::{
@@ -18,18 +21,22 @@ use lighthouse_network::{
     Response, Subnet,
 };
 use lighthouse_network::{
-    types::{GossipEncoding, GossipTopic},
+    types::{
+        core_topics_to_subscribe, GossipEncoding, GossipKind, GossipTopic,
+        LIGHT_CLIENT_GOSSIP_TOPICS,
+    },
     BehaviourEvent, MessageId, NetworkGlobals, PeerId,
 };
 use slog::{crit, debug, error, info, o, trace, warn};
+use slot_clock::SlotClock;
 use std::{net::SocketAddr, pin::Pin, sync::Arc, time::Duration};
 use store::HotColdDB;
-use task_executor::ShutdownReason;
+use task_executor::{ShutdownReason, ShutdownStage};
 use tokio::sync::mpsc;
 use tokio::time::Sleep;
 use types::{
-    ChainSpec, EthSpec, ForkContext, RelativeEpoch, Slot, SubnetId, SyncCommitteeSubscription,
-    SyncSubnetId, Unsigned, ValidatorSubscription,
+    ChainSpec, EthSpec, ForkContext, ForkName, RelativeEpoch, Slot, SubnetId,
+    SyncCommitteeSubscription, SyncSubnetId, Unsigned, ValidatorSubscription,
 };
 
 mod tests;
@@ -40,6 +47,8 @@ const METRIC_UPDATE_INTERVAL: u64 = 5;
 const SUBSCRIBE_DELAY_SLOTS: u64 = 2;
 /// Delay after a fork where we unsubscribe from pre-fork topics.
 const UNSUBSCRIBE_DELAY_EPOCHS: u64 = 2;
+/// The maximum time to wait for peers to disconnect when shutting down.
+const SHUTDOWN_DISCONNECT_TIMEOUT: Duration = Duration::from_secs(3);
 
 /// Types of messages that the network service can receive.
 #[derive(Debug)]
@@ -104,6 +113,12 @@ pub enum NetworkMessage<T: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Pauses or resumes the backfill sync.
+    SetBackfillPaused { paused: bool },
+    /// Unsubscribes from all gossip topics and stops routing gossip messages, before shutting down.
+    StopGossip,
+    /// Sends a goodbye to and disconnects every peer, before shutting down.
+    DisconnectAllPeers,
 }
 
 /// Service that handles communication between internal services and the `lighthouse_network` network service.
@@ -137,10 +152,16 @@ pub struct NetworkService<T: BeaconChainTypes> {
     next_fork_subscriptions: Pin<Box<OptionFuture<Sleep>>>,
     /// A delay that expires when we need to unsubscribe from old fork topics.
     next_unsubscribe: Pin<Box<OptionFuture<Sleep>>>,
+    /// A delay that expires when our latest light client finality update may next be published.
+    next_light_client_publish: Pin<Box<OptionFuture<Sleep>>>,
     /// Subscribe to all the subnets once synced.
     subscribe_all_subnets: bool,
+    /// Subscribe to the light client topics once synced.
+    enable_light_client_server: bool,
     /// Shutdown beacon node after sync is complete.
     shutdown_after_sync: bool,
+    /// Set once shutting down, after which gossip messages are no longer routed.
+    gossip_stopped: bool,
     /// Whether metrics are enabled or not.
     metrics_enabled: bool,
     /// A timer for updating various network metrics.
@@ -190,6 +211,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         let next_fork_update = Box::pin(next_fork_delay(&beacon_chain).into());
         let next_fork_subscriptions = Box::pin(next_fork_subscriptions_delay(&beacon_chain).into());
         let next_unsubscribe = Box::pin(None.into());
+        let next_light_client_publish = if config.enable_light_client_server {
+            Box::pin(next_light_client_publish_delay(&beacon_chain).into())
+        } else {
+            Box::pin(None.into())
+        };
 
         let current_slot = beacon_chain
             .slot()
@@ -236,6 +262,8 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             beacon_chain.clone(),
             network_globals.clone(),
             network_send.clone(),
+            config.disable_backfill,
+            config.failed_batch_dir.clone(),
             executor.clone(),
             network_log.clone(),
         )?;
@@ -270,8 +298,11 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             next_fork_update,
             next_fork_subscriptions,
             next_unsubscribe,
+            next_light_client_publish,
             subscribe_all_subnets: config.subscribe_all_subnets,
+            enable_light_client_server: config.enable_light_client_server,
             shutdown_after_sync: config.shutdown_after_sync,
+            gossip_stopped: false,
             metrics_enabled: config.metrics_enabled,
             metrics_update,
             gossipsub_parameter_update,
@@ -279,11 +310,26 @@ impl<T: BeaconChainTypes> NetworkService<T> {
             log: network_log,
         };
 
+        register_shutdown_hooks(&executor, network_globals.clone(), network_send.clone());
         spawn_service(executor, network_service);
 
         Ok((network_globals, network_send))
     }
 
+    /// Returns the core topics to subscribe to for `fork_digest`, which depend on the fork it
+    /// belongs to.
+    fn core_topics_for_digest(&self, fork_digest: [u8; 4]) -> Vec<GossipKind> {
+        let fork_name = match self.fork_context.from_context_bytes(fork_digest) {
+            Some(fork_name) => *fork_name,
+            None => return vec![],
+        };
+        let mut topics = core_topics_to_subscribe(fork_name);
+        if self.enable_light_client_server && fork_name != ForkName::Base {
+            topics.extend(LIGHT_CLIENT_GOSSIP_TOPICS.iter().cloned());
+        }
+        topics
+    }
+
     /// Returns the required fork digests that gossipsub needs to subscribe to based on the current slot.
     ///
     /// For `current_slot < fork_slot`, this function returns both the pre-fork and post-fork
@@ -322,6 +368,34 @@ impl<T: BeaconChainTypes> NetworkService<T> {
     }
 }
 
+/// Stop gossip at the start of a graceful shutdown and disconnect peers at the end of it.
+fn register_shutdown_hooks<E: EthSpec>(
+    executor: &task_executor::TaskExecutor,
+    network_globals: Arc<NetworkGlobals<E>>,
+    network_send: mpsc::UnboundedSender<NetworkMessage<E>>,
+) {
+    let stop_gossip_send = network_send.clone();
+    executor.on_shutdown(ShutdownStage::StopGossip, "stop_gossip", async move {
+        let _ = stop_gossip_send.send(NetworkMessage::StopGossip);
+    });
+
+    executor.on_shutdown(ShutdownStage::Disconnect, "disconnect_peers", async move {
+        if network_send
+            .send(NetworkMessage::DisconnectAllPeers)
+            .is_err()
+        {
+            return;
+        }
+        // Give goodbyes a chance to be delivered before the swarm is dropped.
+        let _ = tokio::time::timeout(SHUTDOWN_DISCONNECT_TIMEOUT, async {
+            while network_globals.connected_peers() > 0 {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await;
+    });
+}
+
 fn spawn_service<T: BeaconChainTypes>(
     executor: task_executor::TaskExecutor,
     mut service: NetworkService<T>,
@@ -448,6 +522,27 @@ fn spawn_service<T: BeaconChainTypes>(
                         }
                         NetworkMessage::ReportPeer { peer_id, action, source, msg } => service.libp2p.report_peer(&peer_id, action, source, msg),
                         NetworkMessage::GoodbyePeer { peer_id, reason, source } => service.libp2p.goodbye_peer(&peer_id, reason, source),
+                        NetworkMessage::SetBackfillPaused { paused } => {
+                            let _ = service
+                                .router_send
+                                .send(RouterMessage::SetBackfillPaused(paused))
+                                .map_err(|_| {
+                                    debug!(service.log, "Failed to send backfill pause to router");
+                                });
+                        }
+                        NetworkMessage::StopGossip => {
+                            info!(service.log, "Unsubscribing from gossip topics");
+                            service.gossip_stopped = true;
+                            service.libp2p.swarm.behaviour_mut().unsubscribe_all();
+                        }
+                        NetworkMessage::DisconnectAllPeers => {
+                            info!(
+                                service.log,
+                                "Disconnecting from peers";
+                                "peers" => service.network_globals.connected_peers()
+                            );
+                            service.libp2p.disconnect_all_peers(GoodbyeReason::ClientShutdown);
+                        }
                         NetworkMessage::AttestationSubscribe { subscriptions } => {
                             if let Err(e) = service
                                 .attestation_service
@@ -476,9 +571,9 @@ fn spawn_service<T: BeaconChainTypes>(
                                 return;
                             }
                             let mut subscribed_topics: Vec<GossipTopic> = vec![];
-                            for topic_kind in lighthouse_network::types::CORE_TOPICS.iter() {
-                                for fork_digest in service.required_gossip_fork_digests() {
-                                    let topic = GossipTopic::new(topic_kind.clone(), GossipEncoding::default(), fork_digest);
+                            for fork_digest in service.required_gossip_fork_digests() {
+                                for topic_kind in service.core_topics_for_digest(fork_digest) {
+                                    let topic = GossipTopic::new(topic_kind, GossipEncoding::default(), fork_digest);
                                     if service.libp2p.swarm.behaviour_mut().subscribe(topic.clone()) {
                                         subscribed_topics.push(topic);
                                     } else {
@@ -635,6 +730,9 @@ fn spawn_service<T: BeaconChainTypes>(
                                         debug!(service.log, "Failed to send re-status  peer to router");
                                     });
                             }
+                            BehaviourEvent::PubsubMessage { .. } if service.gossip_stopped => {
+                                // Gossip received whilst shutting down is not processed.
+                            }
                             BehaviourEvent::PubsubMessage {
                                 id,
                                 source,
@@ -726,12 +824,63 @@ fn spawn_service<T: BeaconChainTypes>(
                     info!(service.log, "Unsubscribed from old fork topics");
                     service.next_unsubscribe = Box::pin(None.into());
                 }
+                Some(_) = &mut service.next_light_client_publish => {
+                    let mut messages = vec![];
+                    match service.beacon_chain.light_client_finality_update_to_publish() {
+                        Ok(Some(finality_update)) => {
+                            debug!(
+                                service.log,
+                                "Publishing light client finality update";
+                                "attested_slot" => finality_update.attested_header.beacon.slot,
+                                "finalized_slot" => finality_update.finalized_header.beacon.slot,
+                            );
+                            messages.push(PubsubMessage::LightClientFinalityUpdate(Box::new(finality_update)));
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            warn!(service.log, "Unable to read light client finality update"; "error" => ?e);
+                        }
+                    }
+                    match service.beacon_chain.light_client_optimistic_update_to_publish() {
+                        Ok(Some(optimistic_update)) => {
+                            debug!(
+                                service.log,
+                                "Publishing light client optimistic update";
+                                "attested_slot" => optimistic_update.attested_header.beacon.slot,
+                            );
+                            messages.push(PubsubMessage::LightClientOptimisticUpdate(Box::new(optimistic_update)));
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            warn!(service.log, "Unable to read light client optimistic update"; "error" => ?e);
+                        }
+                    }
+                    if !messages.is_empty() {
+                        service.libp2p.swarm.behaviour_mut().publish(messages);
+                    }
+                    service.next_light_client_publish = Box::pin(next_light_client_publish_delay(&service.beacon_chain).into());
+                }
                 Some(_) = &mut service.next_fork_subscriptions => {
                     if let Some((fork_name, _)) = service.beacon_chain.duration_to_next_fork() {
                         let fork_version = service.beacon_chain.spec.fork_version_for_name(fork_name);
                         let fork_digest = ChainSpec::compute_fork_digest(fork_version, service.beacon_chain.genesis_validators_root);
-                        info!(service.log, "Subscribing to new fork topics");
+                        info!(service.log, "Subscribing to new fork topics"; "fork" => %fork_name);
+                        // Core topics are only subscribed to once synced, so only subscribe to
+                        // those introduced by the new fork if we are already subscribed to the
+                        // current ones.
+                        let subscribed_to_core_topics = service
+                            .network_globals
+                            .gossipsub_subscriptions
+                            .read()
+                            .iter()
+                            .any(|topic| *topic.kind() == GossipKind::BeaconBlock);
                         service.libp2p.swarm.behaviour_mut().subscribe_new_fork_topics(fork_digest);
+                        if subscribed_to_core_topics {
+                            for topic_kind in service.core_topics_for_digest(fork_digest) {
+                                let topic = GossipTopic::new(topic_kind, GossipEncoding::default(), fork_digest);
+                                service.libp2p.swarm.behaviour_mut().subscribe(topic);
+                            }
+                        }
                         service.next_fork_subscriptions = Box::pin(None.into());
                     }
                     else {
@@ -754,6 +903,24 @@ fn next_fork_delay<T: BeaconChainTypes>(
         .map(|(_, until_fork)| tokio::time::sleep(until_fork))
 }
 
+/// Returns a `Sleep` that triggers a third of the way into the next slot, once the block of that
+/// slot has had time to propagate and light client updates signed in it may be published.
+/// Returns `None` if the slot clock cannot be read.
+fn next_light_client_publish_delay<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+) -> Option<tokio::time::Sleep> {
+    let slot_duration = beacon_chain.slot_clock.slot_duration();
+    let publish_offset = light_client_update_delay(beacon_chain);
+    let duration_to_next_slot = beacon_chain.slot_clock.duration_to_next_slot()?;
+    // Publish in the current slot if its publishing time has not yet passed.
+    let delay = if duration_to_next_slot + publish_offset > slot_duration {
+        duration_to_next_slot + publish_offset - slot_duration
+    } else {
+        duration_to_next_slot + publish_offset
+    };
+    Some(tokio::time::sleep(delay))
+}
+
 /// Returns a `Sleep` that triggers `SUBSCRIBE_DELAY_SLOTS` before the next fork.
 /// Returns `None` if there are no scheduled forks or we are already past `current_slot + SUBSCRIBE_DELAY_SLOTS > fork_slot`.
 fn next_fork_subscriptions_delay<T: BeaconChainTypes>(
@@ -24,6 +24,13 @@ use crate::metrics;
 /// Subnet discovery query takes atmost 30 secs, 2 slots take 24s.
 const MIN_PEER_DISCOVERY_SLOT_LOOK_AHEAD: u64 = 2;
 
+/// The number of epochs before the start of a sync committee period at which we subscribe to the
+/// subnets of its members and search for peers.
+///
+/// Validator clients may register subscriptions for the next period long before it starts, such
+/// subscriptions are held back until this many epochs before the period.
+pub const SUBSCRIPTION_LEAD_EPOCHS: u64 = 4;
+
 /// A particular subnet at a given slot.
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub struct ExactSubnet {
@@ -45,6 +52,10 @@ pub struct SyncCommitteeService<T: BeaconChainTypes> {
     /// A collection of timeouts for when to unsubscribe from a subnet.
     unsubscriptions: HashSetDelay<SyncSubnetId>,
 
+    /// Subscriptions for future sync committee periods, which are acted upon once their timeout
+    /// expires `SUBSCRIPTION_LEAD_EPOCHS` before the period starts.
+    pending_subscriptions: HashSetDelay<ExactSubnet>,
+
     /// The waker for the current thread.
     waker: Option<std::task::Waker>,
 
@@ -79,6 +90,7 @@ impl<T: BeaconChainTypes> SyncCommitteeService<T> {
             beacon_chain,
             subscriptions: HashMap::new(),
             unsubscriptions: HashSetDelay::new(Duration::from_secs(default_timeout)),
+            pending_subscriptions: HashSetDelay::new(Duration::from_secs(default_timeout)),
             waker: None,
             subscribe_all_subnets: config.subscribe_all_subnets,
             discovery_disabled: config.disable_discovery,
@@ -97,6 +109,12 @@ impl<T: BeaconChainTypes> SyncCommitteeService<T> {
         }
     }
 
+    /// Return count of the subscriptions waiting for their sync committee period to approach.
+    #[cfg(test)]
+    pub fn pending_subscription_count(&self) -> usize {
+        self.pending_subscriptions.len()
+    }
+
     /// Processes a list of sync committee subscriptions.
     ///
     /// This will:
@@ -136,11 +154,35 @@ impl<T: BeaconChainTypes> SyncCommitteeService<T> {
                 }
             };
 
+            // Hold back subscriptions for periods which are not about to start.
+            let pending_duration =
+                match self.duration_to_subscription_lead(subscription.until_epoch) {
+                    Ok(duration) => duration,
+                    Err(e) => {
+                        warn!(self.log,
+                            "Failed to schedule sync committee subscription";
+                            "error" => e,
+                            "validator_index" => subscription.validator_index
+                        );
+                        continue;
+                    }
+                };
+
             for subnet_id in subnet_ids {
                 let exact_subnet = ExactSubnet {
                     subnet_id,
                     until_epoch: subscription.until_epoch,
                 };
+                if let Some(duration) = pending_duration {
+                    debug!(self.log,
+                        "Scheduling subscription to subnet";
+                        "subnet" => *exact_subnet.subnet_id,
+                        "until_epoch" => ?exact_subnet.until_epoch,
+                        "delay" => ?duration,
+                    );
+                    self.pending_subscriptions.insert_at(exact_subnet, duration);
+                    continue;
+                }
                 subnets_to_discover.push(exact_subnet.clone());
                 if let Err(e) = self.subscribe_to_subnet(exact_subnet.clone()) {
                     warn!(self.log,
@@ -174,6 +216,47 @@ impl<T: BeaconChainTypes> SyncCommitteeService<T> {
 
     /* Internal private functions */
 
+    /// Returns the time until we should subscribe to the subnets of a sync committee whose period
+    /// ends at `until_epoch`, or `None` if we should subscribe immediately.
+    fn duration_to_subscription_lead(
+        &self,
+        until_epoch: Epoch,
+    ) -> Result<Option<Duration>, &'static str> {
+        let slot_clock = &self.beacon_chain.slot_clock;
+        let current_slot = slot_clock.now().ok_or("Could not get the current slot")?;
+
+        let period_start_epoch =
+            until_epoch.saturating_sub(self.beacon_chain.spec.epochs_per_sync_committee_period);
+        let lead_slot = period_start_epoch
+            .saturating_sub(SUBSCRIPTION_LEAD_EPOCHS)
+            .start_slot(T::EthSpec::slots_per_epoch());
+
+        if lead_slot <= current_slot {
+            Ok(None)
+        } else {
+            slot_clock
+                .duration_to_slot(lead_slot)
+                .map(Some)
+                .ok_or("Unable to determine duration to subscription slot")
+        }
+    }
+
+    /// A pending subscription is ready, subscribe to its subnet and search for peers.
+    fn handle_pending_subscription(&mut self, exact_subnet: ExactSubnet) {
+        if let Err(e) = self.subscribe_to_subnet(exact_subnet.clone()) {
+            warn!(self.log,
+                "Subscription to sync subnet error";
+                "error" => e,
+                "exact_subnet" => ?exact_subnet,
+            );
+        }
+        if !self.discovery_disabled {
+            if let Err(e) = self.discover_peers_request(std::iter::once(&exact_subnet)) {
+                warn!(self.log, "Discovery lookup request error"; "error" => e);
+            }
+        }
+    }
+
     /// Checks if there are currently queued discovery requests and the time required to make the
     /// request.
     ///
@@ -340,6 +423,15 @@ impl<T: BeaconChainTypes> Stream for SyncCommitteeService<T> {
             Poll::Ready(None) | Poll::Pending => {}
         }
 
+        // process any subscriptions for sync committee periods which are about to start
+        match self.pending_subscriptions.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(exact_subnet))) => self.handle_pending_subscription(exact_subnet),
+            Poll::Ready(Some(Err(e))) => {
+                error!(self.log, "Failed to check for pending subnet subscriptions"; "error"=> e);
+            }
+            Poll::Ready(None) | Poll::Pending => {}
+        }
+
         // process any generated events
         if let Some(event) = self.events.pop_front() {
             return Poll::Ready(Some(event));
@@ -576,4 +576,32 @@ mod sync_committee_service {
         // Should be unsubscribed at the end.
         assert_eq!(sync_committee_service.subscription_count(), 1);
     }
+
+    #[tokio::test]
+    async fn subscription_for_future_period_is_held_back() {
+        // A subscription for the members of the next sync committee, registered at the start of
+        // the current period.
+        let validator_index = 1;
+        let epochs_per_period = MainnetEthSpec::default_spec().epochs_per_sync_committee_period;
+        let until_epoch = epochs_per_period * 2;
+        let sync_committee_indices = vec![1];
+
+        let mut sync_committee_service = get_sync_committee_service();
+
+        let subscriptions = vec![SyncCommitteeSubscription {
+            validator_index,
+            sync_committee_indices,
+            until_epoch,
+        }];
+
+        sync_committee_service
+            .validator_subscriptions(subscriptions)
+            .unwrap();
+
+        // Neither a subscription nor a discovery should happen until the period approaches.
+        let events = get_events(&mut sync_committee_service, None, 1).await;
+        assert!(events.is_empty());
+        assert_eq!(sync_committee_service.subscription_count(), 0);
+        assert_eq!(sync_committee_service.pending_subscription_count(), 1);
+    }
 }
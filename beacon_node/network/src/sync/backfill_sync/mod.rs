@@ -548,6 +548,8 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
                     imported_blocks: false,
                     // The beacon processor queue is full, no need to penalize the peer.
                     peer_action: None,
+                    failed_block: None,
+                    last_imported_slot: None,
                 },
             )
         } else {
@@ -632,7 +634,9 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
             BatchProcessResult::Failed {
                 imported_blocks,
                 peer_action,
+                ..
             } => {
+                let is_paused = self.network_globals.is_backfill_paused();
                 let batch = match self.batches.get_mut(&batch_id) {
                     Some(v) => v,
                     None => {
@@ -656,6 +660,17 @@ impl<T: BeaconChainTypes> BackFillSync<T> {
                             .map(|_| ProcessResult::Successful)
                     }
                 };
+                if is_paused && !*imported_blocks {
+                    // The batch was rejected by the processor because the sync is paused. It will
+                    // be downloaded again once the sync resumes.
+                    debug!(self.log, "Batch processing aborted"; "batch_epoch" => batch_id, "reason" => "paused");
+                    return match batch.processing_aborted() {
+                        Ok(()) => Ok(ProcessResult::Successful),
+                        Err(e) => self
+                            .fail_sync(BackFillError::BatchInvalidState(batch_id, e.0))
+                            .map(|_| ProcessResult::Successful),
+                    };
+                }
                 debug!(self.log, "Batch processing failed"; "imported_blocks" => imported_blocks,
                     "batch_epoch" => batch_id, "peer" => %peer, "client" => %network.client_type(&peer));
                 match batch.processing_completed(false) {
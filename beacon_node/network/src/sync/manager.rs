@@ -39,6 +39,7 @@ use super::peer_sync_info::{remote_sync_type, PeerSyncType};
 use super::range_sync::{ChainId, RangeSync, RangeSyncType, EPOCHS_PER_BATCH};
 use super::RequestId;
 use crate::beacon_processor::{ProcessId, WorkEvent as BeaconWorkEvent};
+use crate::persisted_backfill::{load_backfill_paused, persist_backfill_paused};
 use crate::service::NetworkMessage;
 use crate::status::ToStatusMessage;
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockError};
@@ -104,6 +105,9 @@ pub enum SyncMessage<T: EthSpec> {
     /// A peer has disconnected.
     Disconnect(PeerId),
 
+    /// The backfill sync has been paused or resumed by the user.
+    SetBackfillPaused(bool),
+
     /// An RPC Error has occurred on a request.
     RPCError(PeerId, RequestId),
 
@@ -140,9 +144,34 @@ pub enum BatchProcessResult {
     Failed {
         imported_blocks: bool,
         peer_action: Option<PeerAction>,
+        /// The block at which processing stopped, if it could be determined.
+        failed_block: Option<FailedBlock>,
+        /// The slot of the last block of the batch that was imported before the failure.
+        last_imported_slot: Option<Slot>,
     },
 }
 
+/// The class of `BlockError` that caused a chain segment to fail processing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockFailureKind {
+    /// The blocks in the segment were not sequential.
+    ParentUnknown,
+    /// The block was from a slot ahead of our slot clock.
+    FutureSlot,
+    /// The block failed verification or state transition.
+    InvalidBlock,
+    /// The block could not be processed due to a local error.
+    Internal,
+}
+
+/// Identifies the first block of a chain segment that could not be imported.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FailedBlock {
+    pub slot: Slot,
+    pub root: Hash256,
+    pub kind: BlockFailureKind,
+}
+
 /// Maintains a sequential list of parents to lookup and the lookup's current state.
 struct ParentRequests<T: EthSpec> {
     /// The blocks that have currently been downloaded.
@@ -228,12 +257,24 @@ pub fn spawn<T: BeaconChainTypes>(
     network_globals: Arc<NetworkGlobals<T::EthSpec>>,
     network_send: mpsc::UnboundedSender<NetworkMessage<T::EthSpec>>,
     beacon_processor_send: mpsc::Sender<BeaconWorkEvent<T>>,
+    disable_backfill: bool,
     log: slog::Logger,
 ) -> mpsc::UnboundedSender<SyncMessage<T::EthSpec>> {
     assert!(
         MAX_REQUEST_BLOCKS >= T::EthSpec::slots_per_epoch() * EPOCHS_PER_BATCH,
         "Max blocks that can be requested in a single batch greater than max allowed blocks in a single request"
     );
+    // The backfill sync is never started or resumed whilst it is paused by the user, either with
+    // `--disable-backfill` or by a pause which was persisted before a restart.
+    let persisted_pause = load_backfill_paused(beacon_chain.store.clone()).unwrap_or_else(|e| {
+        warn!(log, "Unable to load backfill pause"; "error" => ?e);
+        false
+    });
+    if persisted_pause && !disable_backfill {
+        info!(log, "Backfill sync remains paused by user");
+    }
+    network_globals.set_backfill_paused(disable_backfill || persisted_pause);
+
     // generate the message channel
     let (sync_send, sync_recv) = mpsc::unbounded_channel::<SyncMessage<T::EthSpec>>();
 
@@ -425,9 +466,10 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
     async fn process_block_async(
         &mut self,
+        peer_id: PeerId,
         block: SignedBeaconBlock<T::EthSpec>,
     ) -> Option<Result<Hash256, BlockError<T::EthSpec>>> {
-        let (event, rx) = BeaconWorkEvent::rpc_beacon_block(Box::new(block));
+        let (event, rx) = BeaconWorkEvent::rpc_beacon_block(peer_id, Box::new(block));
         match self.beacon_processor_send.try_send(event) {
             Ok(_) => {}
             Err(e) => {
@@ -472,7 +514,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             return;
         }
 
-        let block_result = match self.process_block_async(block.clone()).await {
+        let block_result = match self.process_block_async(peer_id, block.clone()).await {
             Some(block_result) => block_result,
             None => return,
         };
@@ -646,6 +688,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
     fn peer_disconnect(&mut self, peer_id: &PeerId) {
         self.range_sync.peer_disconnect(&mut self.network, peer_id);
+        self.network.remove_segment_failures(peer_id);
         // Regardless of the outcome, we update the sync status.
         let _ = self
             .backfill_sync
@@ -695,6 +738,26 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
     }
 
+    /// Pauses or resumes the backfill sync at the request of the user.
+    ///
+    /// Batches currently being processed are allowed to complete, but no new batches are
+    /// downloaded or sent for processing until the backfill sync is resumed.
+    fn set_backfill_paused(&mut self, paused: bool) {
+        if self.network_globals.set_backfill_paused(paused) == paused {
+            return;
+        }
+        if let Err(e) = persist_backfill_paused(self.chain.store.clone(), paused) {
+            warn!(self.log, "Unable to persist backfill pause"; "paused" => paused, "error" => ?e);
+        }
+        if paused {
+            info!(self.log, "Backfill sync paused by user");
+            self.backfill_sync.pause();
+        } else {
+            info!(self.log, "Backfill sync resumed by user");
+        }
+        self.update_sync_state();
+    }
+
     /// Updates the global sync state, optionally instigating or pausing a backfill sync as well as
     /// logging any changes.
     ///
@@ -743,7 +806,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
 
                     // If we would otherwise be synced, first check if we need to perform or
                     // complete a backfill sync.
-                    if matches!(sync_state, SyncState::Synced) {
+                    if matches!(sync_state, SyncState::Synced)
+                        && !self.network_globals.is_backfill_paused()
+                    {
                         // Determine if we need to start/resume/restart a backfill sync.
                         match self.backfill_sync.start(&mut self.network) {
                             Ok(SyncStart::Syncing {
@@ -868,7 +933,10 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 .pop()
                 .expect("There is always at least one block in the queue");
 
-            let block_result = match self.process_block_async(newest_block.clone()).await {
+            let block_result = match self
+                .process_block_async(parent_request.last_submitted_peer, newest_block.clone())
+                .await
+            {
                 Some(block_result) => block_result,
                 None => return,
             };
@@ -1061,6 +1129,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     SyncMessage::Disconnect(peer_id) => {
                         self.peer_disconnect(&peer_id);
                     }
+                    SyncMessage::SetBackfillPaused(paused) => {
+                        self.set_backfill_paused(paused);
+                    }
                     SyncMessage::RPCError(peer_id, request_id) => {
                         // Redirect to a sync mechanism if the error is related to one of their
                         // requests.
@@ -7,7 +7,7 @@ mod network_context;
 mod peer_sync_info;
 mod range_sync;
 
-pub use manager::{BatchProcessResult, SyncMessage};
+pub use manager::{BatchProcessResult, BlockFailureKind, FailedBlock, SyncMessage};
 pub use range_sync::ChainId;
 
 /// Type of id of rpc requests sent by sync
@@ -1,7 +1,7 @@
 //! Provides network functionality for the Syncing thread. This fundamentally wraps a network
 //! channel and stores a global RPC ID to perform requests.
 
-use super::manager::SyncRequestType;
+use super::manager::{BlockFailureKind, SyncRequestType};
 use super::range_sync::{BatchId, ChainId};
 use super::RequestId as SyncRequestId;
 use crate::service::NetworkMessage;
@@ -16,6 +16,10 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use types::EthSpec;
 
+/// The number of times a peer can cause a chain segment failure of the same kind before the
+/// penalty applied for that kind of failure is escalated to the next severity.
+const FAILURE_REPEATS_PER_ESCALATION: usize = 2;
+
 /// Wraps a Network channel to employ various RPC related network functionality for the Sync manager. This includes management of a global RPC request Id.
 
 pub struct SyncNetworkContext<T: EthSpec> {
@@ -31,6 +35,9 @@ pub struct SyncNetworkContext<T: EthSpec> {
     /// BlocksByRange requests made by syncing algorithms.
     range_requests: FnvHashMap<SyncRequestId, SyncRequestType>,
 
+    /// The number of chain segment failures of each kind that each peer has caused.
+    segment_failures: FnvHashMap<PeerId, FnvHashMap<BlockFailureKind, usize>>,
+
     /// Logger for the `SyncNetworkContext`.
     log: slog::Logger,
 }
@@ -46,6 +53,7 @@ impl<T: EthSpec> SyncNetworkContext<T> {
             network_globals,
             request_id: 1,
             range_requests: FnvHashMap::default(),
+            segment_failures: FnvHashMap::default(),
             log,
         }
     }
@@ -169,6 +177,41 @@ impl<T: EthSpec> SyncNetworkContext<T> {
             });
     }
 
+    /// Registers a chain segment failure of the given `kind` caused by `peer_id`.
+    ///
+    /// If the peer has repeatedly caused this kind of failure, returns the base `action` escalated
+    /// by one severity level for every `FAILURE_REPEATS_PER_ESCALATION` repeats.
+    pub fn repeated_segment_failure(
+        &mut self,
+        peer_id: PeerId,
+        kind: BlockFailureKind,
+        action: PeerAction,
+    ) -> Option<PeerAction> {
+        let count = self
+            .segment_failures
+            .entry(peer_id)
+            .or_default()
+            .entry(kind)
+            .or_default();
+        *count += 1;
+
+        let escalations = (*count - 1) / FAILURE_REPEATS_PER_ESCALATION;
+        if escalations == 0 {
+            return None;
+        }
+
+        let escalated = (0..escalations).fold(action, |action, _| escalate_peer_action(action));
+        debug!(self.log, "Escalating penalty for repeated segment failure";
+            "peer_id" => %peer_id, "failure" => ?kind, "occurrences" => *count,
+            "action" => %escalated);
+        Some(escalated)
+    }
+
+    /// Forgets the segment failure history of a peer.
+    pub fn remove_segment_failures(&mut self, peer_id: &PeerId) {
+        self.segment_failures.remove(peer_id);
+    }
+
     /// Reports to the scoring algorithm the behaviour of a peer.
     pub fn report_peer(&mut self, peer_id: PeerId, action: PeerAction, msg: &'static str) {
         debug!(self.log, "Sync reporting peer"; "peer_id" => %peer_id, "action" => %action);
@@ -217,3 +260,105 @@ impl<T: EthSpec> SyncNetworkContext<T> {
         })
     }
 }
+
+/// Returns the next most severe `PeerAction`.
+fn escalate_peer_action(action: PeerAction) -> PeerAction {
+    match action {
+        PeerAction::HighToleranceError => PeerAction::MidToleranceError,
+        PeerAction::MidToleranceError => PeerAction::LowToleranceError,
+        PeerAction::LowToleranceError | PeerAction::Fatal => PeerAction::Fatal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MinimalEthSpec as E;
+
+    fn context() -> SyncNetworkContext<E> {
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        let (network_tx, _network_rx) = mpsc::unbounded_channel();
+        let globals = Arc::new(NetworkGlobals::new_test_globals(&log));
+        SyncNetworkContext::new(network_tx, globals, log)
+    }
+
+    #[test]
+    fn escalate_peer_action_increases_severity() {
+        assert!(matches!(
+            escalate_peer_action(PeerAction::HighToleranceError),
+            PeerAction::MidToleranceError
+        ));
+        assert!(matches!(
+            escalate_peer_action(PeerAction::MidToleranceError),
+            PeerAction::LowToleranceError
+        ));
+        assert!(matches!(
+            escalate_peer_action(PeerAction::LowToleranceError),
+            PeerAction::Fatal
+        ));
+        assert!(matches!(
+            escalate_peer_action(PeerAction::Fatal),
+            PeerAction::Fatal
+        ));
+    }
+
+    #[test]
+    fn repeated_segment_failure_escalates_at_thresholds() {
+        let mut cx = context();
+        let peer_id = PeerId::random();
+        let mut failure = || {
+            cx.repeated_segment_failure(
+                peer_id,
+                BlockFailureKind::InvalidBlock,
+                PeerAction::HighToleranceError,
+            )
+        };
+
+        // The first `FAILURE_REPEATS_PER_ESCALATION` failures use the base action.
+        for _ in 0..FAILURE_REPEATS_PER_ESCALATION {
+            assert!(failure().is_none());
+        }
+        for _ in 0..FAILURE_REPEATS_PER_ESCALATION {
+            assert!(matches!(failure(), Some(PeerAction::MidToleranceError)));
+        }
+        for _ in 0..FAILURE_REPEATS_PER_ESCALATION {
+            assert!(matches!(failure(), Some(PeerAction::LowToleranceError)));
+        }
+        // Escalation saturates at `Fatal`.
+        for _ in 0..FAILURE_REPEATS_PER_ESCALATION * 2 {
+            assert!(matches!(failure(), Some(PeerAction::Fatal)));
+        }
+    }
+
+    #[test]
+    fn repeated_segment_failures_are_counted_per_peer_and_kind() {
+        let mut cx = context();
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+        let action = PeerAction::MidToleranceError;
+
+        for _ in 0..FAILURE_REPEATS_PER_ESCALATION {
+            assert!(cx
+                .repeated_segment_failure(peer_id, BlockFailureKind::InvalidBlock, action)
+                .is_none());
+        }
+
+        // A different kind of failure, or a different peer, starts from the base action.
+        assert!(cx
+            .repeated_segment_failure(peer_id, BlockFailureKind::ParentUnknown, action)
+            .is_none());
+        assert!(cx
+            .repeated_segment_failure(other_peer_id, BlockFailureKind::InvalidBlock, action)
+            .is_none());
+        assert!(matches!(
+            cx.repeated_segment_failure(peer_id, BlockFailureKind::InvalidBlock, action),
+            Some(PeerAction::LowToleranceError)
+        ));
+
+        // Forgetting the peer resets its history.
+        cx.remove_segment_failures(&peer_id);
+        assert!(cx
+            .repeated_segment_failure(peer_id, BlockFailureKind::InvalidBlock, action)
+            .is_none());
+    }
+}
@@ -45,6 +45,9 @@ pub struct BatchInfo<T: EthSpec, B: BatchConfig = RangeSyncBatchConfig> {
     start_slot: Slot,
     /// End slot of the batch.
     end_slot: Slot,
+    /// The slot of the last block of this batch that was imported by a partially successful
+    /// processing attempt. Re-downloads of the batch only request the blocks after this slot.
+    last_imported_slot: Option<Slot>,
     /// The `Attempts` that have been made and failed to send us this batch.
     failed_processing_attempts: Vec<Attempt>,
     /// The number of download retries this batch has undergone due to a failed request.
@@ -108,6 +111,7 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         BatchInfo {
             start_slot,
             end_slot,
+            last_imported_slot: None,
             failed_processing_attempts: Vec::new(),
             failed_download_attempts: Vec::new(),
             state: BatchState::AwaitingDownload,
@@ -155,13 +159,33 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
 
     /// Returns a BlocksByRange request associated with the batch.
     pub fn to_blocks_by_range_request(&self) -> BlocksByRangeRequest {
+        let start_slot = self.request_start_slot();
         BlocksByRangeRequest {
-            start_slot: self.start_slot.into(),
-            count: self.end_slot.sub(self.start_slot).into(),
+            start_slot: start_slot.into(),
+            count: self.end_slot.sub(start_slot).into(),
             step: 1,
         }
     }
 
+    /// The first slot that is requested when downloading this batch.
+    fn request_start_slot(&self) -> Slot {
+        self.last_imported_slot
+            .map_or(self.start_slot, |slot| slot + 1)
+    }
+
+    /// Acknowledges that the blocks of this batch up to and including `slot` have been imported,
+    /// so that the next download attempts only request the remaining blocks.
+    pub fn register_imported_slot(&mut self, slot: Slot) {
+        if slot >= self.start_slot
+            && slot + 1 < self.end_slot
+            && self
+                .last_imported_slot
+                .map_or(true, |current| current < slot)
+        {
+            self.last_imported_slot = Some(slot);
+        }
+    }
+
     pub fn state(&self) -> &BatchState<T> {
         &self.state
     }
@@ -202,8 +226,8 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
                     // the batch is non-empty
                     let first_slot = blocks[0].slot();
 
-                    let failed_range = if first_slot < self.start_slot {
-                        Some((self.start_slot, first_slot))
+                    let failed_range = if first_slot < self.request_start_slot() {
+                        Some((self.request_start_slot(), first_slot))
                     } else if self.end_slot < last_slot {
                         Some((self.end_slot, last_slot))
                     } else {
@@ -346,11 +370,32 @@ impl<T: EthSpec, B: BatchConfig> BatchInfo<T, B> {
         }
     }
 
+    /// Returns a batch whose processing was rejected, without fault, back to awaiting download.
+    /// No processing attempt is registered.
+    pub fn processing_aborted(&mut self) -> Result<(), WrongState> {
+        match self.state.poison() {
+            BatchState::Processing(_) => {
+                self.state = BatchState::AwaitingDownload;
+                Ok(())
+            }
+            BatchState::Poisoned => unreachable!("Poisoned batch"),
+            other => {
+                self.state = other;
+                Err(WrongState(format!(
+                    "Processing aborted for batch in wrong state: {:?}",
+                    self.state
+                )))
+            }
+        }
+    }
+
     #[must_use = "Batch may have failed"]
     pub fn validation_failed(&mut self) -> Result<IsFailed, WrongState> {
         match self.state.poison() {
             BatchState::AwaitingValidation(attempt) => {
                 self.failed_processing_attempts.push(attempt);
+                // The previously imported blocks may be invalid as well, download the whole batch.
+                self.last_imported_slot = None;
 
                 // check if the batch can be downloaded again
                 self.state = if self.failed_processing_attempts.len()
@@ -453,3 +498,107 @@ impl<T: EthSpec> std::fmt::Debug for BatchState<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{BeaconBlock, MinimalEthSpec as E, Signature};
+
+    fn block(slot: u64) -> SignedBeaconBlock<E> {
+        let mut block = BeaconBlock::empty(&E::default_spec());
+        *block.slot_mut() = Slot::new(slot);
+        SignedBeaconBlock::from_block(block, Signature::empty())
+    }
+
+    /// Downloads and processes `blocks` for `batch`, with the given processing outcome.
+    fn download_and_process(
+        batch: &mut BatchInfo<E>,
+        blocks: impl Iterator<Item = u64>,
+        was_successful: bool,
+    ) {
+        batch
+            .start_downloading_from_peer(PeerId::random(), 1)
+            .ok()
+            .expect("should start download");
+        for slot in blocks {
+            batch.add_block(block(slot)).ok().expect("should add block");
+        }
+        batch
+            .download_completed()
+            .ok()
+            .expect("should complete download");
+        batch
+            .start_processing()
+            .ok()
+            .expect("should start processing");
+        batch
+            .processing_completed(was_successful)
+            .ok()
+            .expect("should complete processing");
+    }
+
+    fn request_range(batch: &BatchInfo<E>) -> (u64, u64) {
+        let request = batch.to_blocks_by_range_request();
+        (request.start_slot, request.count)
+    }
+
+    #[test]
+    fn partial_import_resumes_from_last_imported_slot() {
+        // Slots 9 to 16.
+        let mut batch = BatchInfo::<E>::new(&Epoch::new(1), 1);
+        assert_eq!(request_range(&batch), (9, 8));
+
+        // Processing fails after the blocks up to slot 12 were imported.
+        download_and_process(&mut batch, 9..=16, false);
+        batch.register_imported_slot(Slot::new(12));
+        assert!(matches!(batch.state(), BatchState::AwaitingDownload));
+        assert_eq!(request_range(&batch), (13, 4));
+
+        // A re-download which includes already imported blocks is rejected.
+        batch
+            .start_downloading_from_peer(PeerId::random(), 2)
+            .ok()
+            .expect("should start download");
+        batch.add_block(block(12)).ok().expect("should add block");
+        assert!(batch.download_completed().is_err());
+
+        // Only the remaining blocks are processed by the retry.
+        download_and_process(&mut batch, 13..=16, true);
+        assert!(matches!(batch.state(), BatchState::AwaitingValidation(_)));
+        assert_eq!(request_range(&batch), (13, 4));
+    }
+
+    #[test]
+    fn failed_validation_requests_whole_batch() {
+        let mut batch = BatchInfo::<E>::new(&Epoch::new(1), 1);
+        download_and_process(&mut batch, 9..=16, false);
+        batch.register_imported_slot(Slot::new(12));
+
+        download_and_process(&mut batch, 13..=16, true);
+        batch
+            .validation_failed()
+            .ok()
+            .expect("should fail validation");
+
+        // The imported blocks may be invalid too, so the whole batch is requested again.
+        assert_eq!(request_range(&batch), (9, 8));
+    }
+
+    #[test]
+    fn register_imported_slot_only_advances_within_batch() {
+        let mut batch = BatchInfo::<E>::new(&Epoch::new(1), 1);
+
+        // Slots outside the batch and its last slot are ignored.
+        batch.register_imported_slot(Slot::new(8));
+        batch.register_imported_slot(Slot::new(16));
+        batch.register_imported_slot(Slot::new(17));
+        assert_eq!(request_range(&batch), (9, 8));
+
+        batch.register_imported_slot(Slot::new(11));
+        assert_eq!(request_range(&batch), (12, 5));
+
+        // An earlier slot does not undo progress.
+        batch.register_imported_slot(Slot::new(10));
+        assert_eq!(request_range(&batch), (12, 5));
+    }
+}
@@ -319,6 +319,8 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                 &BatchProcessResult::Failed {
                     imported_blocks: false,
                     peer_action: None,
+                    failed_block: None,
+                    last_imported_slot: None,
                 },
             )
         } else {
@@ -498,6 +500,8 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
             BatchProcessResult::Failed {
                 imported_blocks,
                 peer_action,
+                failed_block,
+                last_imported_slot,
             } => {
                 let batch = self.batches.get_mut(&batch_id).ok_or_else(|| {
                     RemoveChain::WrongChainState(format!(
@@ -513,6 +517,16 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                 })?;
                 debug!(self.log, "Batch processing failed"; "imported_blocks" => imported_blocks,
                     "batch_epoch" => batch_id, "peer" => %peer, "client" => %network.client_type(&peer));
+
+                // Peers that repeatedly cause the same kind of failure are penalized with
+                // increasing severity.
+                let escalated_action = match (peer_action, failed_block) {
+                    (Some(peer_action), Some(failed_block)) => {
+                        network.repeated_segment_failure(peer, failed_block.kind, *peer_action)
+                    }
+                    _ => None,
+                };
+
                 if batch.processing_completed(false)? {
                     // check that we have not exceeded the re-process retry counter
                     // If a batch has exceeded the invalid batch lookup attempts limit, it means
@@ -532,18 +546,38 @@ impl<T: BeaconChainTypes> SyncingChain<T> {
                     );
 
                     if let Some(peer_action) = peer_action {
-                        for (peer, _) in self.peers.drain() {
-                            network.report_peer(peer, *peer_action, "batch_failed");
+                        for (chain_peer, _) in self.peers.drain() {
+                            let action = match escalated_action {
+                                Some(escalated_action) if chain_peer == peer => escalated_action,
+                                _ => *peer_action,
+                            };
+                            network.report_peer(chain_peer, action, "batch_failed");
                         }
                     }
                     Err(RemoveChain::ChainFailed(batch_id))
                 } else {
+                    if let Some(escalated_action) = escalated_action {
+                        network.report_peer(peer, escalated_action, "batch_failed_repeatedly");
+                    }
+
                     // chain can continue. Check if it can be moved forward
                     if *imported_blocks {
                         // At least one block was successfully verified and imported, so we can be sure all
                         // previous batches are valid and we only need to download the current failed
                         // batch.
                         self.advance_chain(network, batch_id);
+
+                        // The blocks up to `last_imported_slot` are now part of the chain, so
+                        // only the remainder of the batch needs to be re-requested.
+                        if let Some(last_imported_slot) = last_imported_slot {
+                            if let Some(batch) = self.batches.get_mut(&batch_id) {
+                                batch.register_imported_slot(*last_imported_slot);
+                                debug!(self.log, "Re-requesting remainder of partially imported batch";
+                                    "batch_epoch" => batch_id,
+                                    "last_imported_slot" => last_imported_slot,
+                                    "failed_block" => ?failed_block);
+                            }
+                        }
                     }
                     // Handle this invalid batch, that is within the re-process retries limit.
                     self.handle_invalid_batch(network, batch_id)
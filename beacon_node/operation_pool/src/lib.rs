@@ -18,8 +18,8 @@ use max_cover::{maximum_cover, MaxCover};
 use parking_lot::RwLock;
 use state_processing::per_block_processing::errors::AttestationValidationError;
 use state_processing::per_block_processing::{
-    get_slashable_indices_modular, verify_attestation_for_block_inclusion, verify_exit,
-    VerifySignatures,
+    get_slashable_indices, get_slashable_indices_modular, verify_attestation_for_block_inclusion,
+    verify_exit, VerifySignatures,
 };
 use state_processing::SigVerifiedOp;
 use std::collections::{hash_map::Entry, HashMap, HashSet};
@@ -46,6 +46,8 @@ pub struct OperationPool<T: EthSpec + Default> {
     proposer_slashings: RwLock<HashMap<u64, ProposerSlashing>>,
     /// Map from exiting validator to their exit data.
     voluntary_exits: RwLock<HashMap<u64, SignedVoluntaryExit>>,
+    /// Statistics about the operations which have been pruned from the pool.
+    prune_stats: RwLock<PruneStats>,
     _phantom: PhantomData<T>,
 }
 
@@ -64,6 +66,57 @@ pub struct AttestationStats {
     pub num_attestation_data: usize,
     /// Maximum number of aggregates for a single `AttestationData`.
     pub max_aggregates_per_data: usize,
+    /// Number of attestations for each slot.
+    pub per_slot: HashMap<Slot, SlotAttestationCounts>,
+}
+
+/// The number of attestations in the pool for a single slot.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct SlotAttestationCounts {
+    /// Attestations with a single signer.
+    pub unaggregated: usize,
+    /// Attestations with more than one signer.
+    pub aggregated: usize,
+}
+
+/// An estimate of how the attestations in the pool would be packed into a block at the next slot.
+///
+/// Fork choice is not consulted, and attestations are assumed to cover some validators which have
+/// not yet attested on chain, so `num_candidates` may be an overestimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AttestationPackingForecast {
+    /// Number of attestations which would be valid for inclusion in the block.
+    pub num_candidates: usize,
+    /// Number of those attestations which would fit in the block.
+    pub num_included: usize,
+}
+
+/// The number of operations of each kind which have been pruned from the pool since it was
+/// created.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PruneStats {
+    /// The slot of the head state at the most recent prune, if any.
+    pub last_prune_slot: Option<Slot>,
+    pub attestations: u64,
+    pub sync_contributions: u64,
+    pub attester_slashings: u64,
+    pub proposer_slashings: u64,
+    pub voluntary_exits: u64,
+}
+
+/// Whether an operation in the pool would be included in a block produced upon some state.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InclusionForecast {
+    NextBlock,
+    /// The operation would be excluded from the block, for the given reason.
+    Excluded(&'static str),
+}
+
+/// The inclusion forecast for each slashing and exit in the pool.
+pub struct OperationForecasts<T: EthSpec> {
+    pub proposer_slashings: Vec<(ProposerSlashing, InclusionForecast)>,
+    pub attester_slashings: Vec<(AttesterSlashing<T>, InclusionForecast)>,
+    pub voluntary_exits: Vec<(SignedVoluntaryExit, InclusionForecast)>,
 }
 
 impl From<SyncAggregateError> for OpPoolError {
@@ -159,14 +212,23 @@ impl<T: EthSpec> OperationPool<T> {
 
     /// Remove sync contributions which are too old to be included in a block.
     pub fn prune_sync_contributions(&self, current_slot: Slot) {
+        let _timer = metrics::start_timer_vec(&metrics::PRUNE_TIME, &["sync_contributions"]);
+        let mut pruned = 0;
+
         // Prune sync contributions that are from before the previous slot.
         self.sync_contributions.write().retain(|_, contributions| {
             // All the contributions in this bucket have the same data, so we only need to
             // check the first one.
-            contributions.first().map_or(false, |contribution| {
+            let keep = contributions.first().map_or(false, |contribution| {
                 current_slot <= contribution.slot.saturating_add(Slot::new(1))
-            })
+            });
+            if !keep {
+                pruned += contributions.len() as u64;
+            }
+            keep
         });
+
+        self.prune_stats.write().sync_contributions += pruned;
     }
 
     /// Insert an attestation into the pool, aggregating it with existing attestations if possible.
@@ -220,16 +282,70 @@ impl<T: EthSpec> OperationPool<T> {
         let mut num_attestations = 0;
         let mut num_attestation_data = 0;
         let mut max_aggregates_per_data = 0;
+        let mut per_slot = HashMap::<Slot, SlotAttestationCounts>::new();
 
         for aggregates in self.attestations.read().values() {
             num_attestations += aggregates.len();
             num_attestation_data += 1;
             max_aggregates_per_data = std::cmp::max(max_aggregates_per_data, aggregates.len());
+
+            for attestation in aggregates {
+                let counts = per_slot.entry(attestation.data.slot).or_default();
+                if attestation.aggregation_bits.num_set_bits() > 1 {
+                    counts.aggregated += 1;
+                } else {
+                    counts.unaggregated += 1;
+                }
+            }
         }
         AttestationStats {
             num_attestations,
             num_attestation_data,
             max_aggregates_per_data,
+            per_slot,
+        }
+    }
+
+    /// Estimate how many attestations would be included in a block produced at the slot after
+    /// `state`, without running the max cover optimisation used by `get_attestations`.
+    pub fn forecast_attestation_packing(
+        &self,
+        state: &BeaconState<T>,
+        spec: &ChainSpec,
+    ) -> AttestationPackingForecast {
+        let slot = state.slot() + 1;
+        let current_epoch = slot.epoch(T::slots_per_epoch());
+        let prev_epoch = current_epoch.saturating_sub(1_u64);
+        let domain_bytes = |epoch| {
+            AttestationId::compute_domain_bytes(
+                epoch,
+                &state.fork(),
+                state.genesis_validators_root(),
+                spec,
+            )
+        };
+        let curr_domain_bytes = domain_bytes(current_epoch);
+        let prev_domain_bytes = domain_bytes(prev_epoch);
+
+        let num_candidates = self
+            .attestations
+            .read()
+            .iter()
+            .flat_map(|(id, attestations)| attestations.iter().map(move |att| (id, att)))
+            .filter(|(id, att)| {
+                let target = att.data.target.epoch;
+                let domain_matches = (target == current_epoch
+                    && id.domain_bytes_match(&curr_domain_bytes))
+                    || (target == prev_epoch && id.domain_bytes_match(&prev_domain_bytes));
+                domain_matches
+                    && att.data.slot + spec.min_attestation_inclusion_delay <= slot
+                    && slot <= att.data.slot + T::slots_per_epoch()
+            })
+            .count();
+
+        AttestationPackingForecast {
+            num_candidates,
+            num_included: std::cmp::min(num_candidates, T::MaxAttestations::to_usize()),
         }
     }
 
@@ -357,14 +473,23 @@ impl<T: EthSpec> OperationPool<T> {
 
     /// Remove attestations which are too old to be included in a block.
     pub fn prune_attestations(&self, current_epoch: Epoch) {
+        let _timer = metrics::start_timer_vec(&metrics::PRUNE_TIME, &["attestations"]);
+        let mut pruned = 0;
+
         // Prune attestations that are from before the previous epoch.
         self.attestations.write().retain(|_, attestations| {
             // All the attestations in this bucket have the same data, so we only need to
             // check the first one.
-            attestations
+            let keep = attestations
                 .first()
-                .map_or(false, |att| current_epoch <= att.data.target.epoch + 1)
+                .map_or(false, |att| current_epoch <= att.data.target.epoch + 1);
+            if !keep {
+                pruned += attestations.len() as u64;
+            }
+            keep
         });
+
+        self.prune_stats.write().attestations += pruned;
     }
 
     /// Insert a proposer slashing into the pool.
@@ -454,40 +579,47 @@ impl<T: EthSpec> OperationPool<T> {
 
     /// Prune proposer slashings for validators which are exited in the finalized epoch.
     pub fn prune_proposer_slashings(&self, head_state: &BeaconState<T>) {
-        prune_validator_hash_map(
+        let _timer = metrics::start_timer_vec(&metrics::PRUNE_TIME, &["proposer_slashings"]);
+        let pruned = prune_validator_hash_map(
             &mut self.proposer_slashings.write(),
             |validator| validator.exit_epoch <= head_state.finalized_checkpoint().epoch,
             head_state,
         );
+        self.prune_stats.write().proposer_slashings += pruned as u64;
     }
 
     /// Prune attester slashings for all slashed or withdrawn validators, or attestations on another
     /// fork.
     pub fn prune_attester_slashings(&self, head_state: &BeaconState<T>) {
-        self.attester_slashings
-            .write()
-            .retain(|(slashing, fork_version)| {
-                let previous_fork_is_finalized =
-                    head_state.finalized_checkpoint().epoch >= head_state.fork().epoch;
-                // Prune any slashings which don't match the current fork version, or the previous
-                // fork version if it is not finalized yet.
-                let fork_ok = (*fork_version == head_state.fork().current_version)
-                    || (*fork_version == head_state.fork().previous_version
-                        && !previous_fork_is_finalized);
-                // Slashings that don't slash any validators can also be dropped.
-                let slashing_ok =
-                    get_slashable_indices_modular(head_state, slashing, |_, validator| {
-                        // Declare that a validator is still slashable if they have not exited prior
-                        // to the finalized epoch.
-                        //
-                        // We cannot check the `slashed` field since the `head` is not finalized and
-                        // a fork could un-slash someone.
-                        validator.exit_epoch > head_state.finalized_checkpoint().epoch
-                    })
-                    .map_or(false, |indices| !indices.is_empty());
-
-                fork_ok && slashing_ok
-            });
+        let _timer = metrics::start_timer_vec(&metrics::PRUNE_TIME, &["attester_slashings"]);
+        let mut attester_slashings = self.attester_slashings.write();
+        let num_slashings = attester_slashings.len();
+
+        attester_slashings.retain(|(slashing, fork_version)| {
+            let previous_fork_is_finalized =
+                head_state.finalized_checkpoint().epoch >= head_state.fork().epoch;
+            // Prune any slashings which don't match the current fork version, or the previous
+            // fork version if it is not finalized yet.
+            let fork_ok = (*fork_version == head_state.fork().current_version)
+                || (*fork_version == head_state.fork().previous_version
+                    && !previous_fork_is_finalized);
+            // Slashings that don't slash any validators can also be dropped.
+            let slashing_ok =
+                get_slashable_indices_modular(head_state, slashing, |_, validator| {
+                    // Declare that a validator is still slashable if they have not exited prior
+                    // to the finalized epoch.
+                    //
+                    // We cannot check the `slashed` field since the `head` is not finalized and
+                    // a fork could un-slash someone.
+                    validator.exit_epoch > head_state.finalized_checkpoint().epoch
+                })
+                .map_or(false, |indices| !indices.is_empty());
+
+            fork_ok && slashing_ok
+        });
+
+        self.prune_stats.write().attester_slashings +=
+            (num_slashings - attester_slashings.len()) as u64;
     }
 
     /// Total number of attester slashings in the pool.
@@ -527,7 +659,8 @@ impl<T: EthSpec> OperationPool<T> {
 
     /// Prune if validator has already exited at or before the finalized checkpoint of the head.
     pub fn prune_voluntary_exits(&self, head_state: &BeaconState<T>) {
-        prune_validator_hash_map(
+        let _timer = metrics::start_timer_vec(&metrics::PRUNE_TIME, &["voluntary_exits"]);
+        let pruned = prune_validator_hash_map(
             &mut self.voluntary_exits.write(),
             // This condition is slightly too loose, since there will be some finalized exits that
             // are missed here.
@@ -537,6 +670,7 @@ impl<T: EthSpec> OperationPool<T> {
             |validator| validator.exit_epoch <= head_state.finalized_checkpoint().epoch,
             head_state,
         );
+        self.prune_stats.write().voluntary_exits += pruned as u64;
     }
 
     /// Prune all types of transactions given the latest head state and head fork.
@@ -546,6 +680,108 @@ impl<T: EthSpec> OperationPool<T> {
         self.prune_proposer_slashings(head_state);
         self.prune_attester_slashings(head_state);
         self.prune_voluntary_exits(head_state);
+        self.prune_stats.write().last_prune_slot = Some(head_state.slot());
+    }
+
+    /// Statistics about the operations which have been pruned from the pool.
+    pub fn prune_stats(&self) -> PruneStats {
+        *self.prune_stats.read()
+    }
+
+    /// Forecast whether each slashing and exit in the pool would be included in a block produced
+    /// upon `state`, along with the reason for those which would not.
+    pub fn forecast_slashings_and_exits(
+        &self,
+        state: &BeaconState<T>,
+        spec: &ChainSpec,
+    ) -> OperationForecasts<T> {
+        let (next_proposer_slashings, next_attester_slashings, next_voluntary_exits) =
+            self.get_slashings_and_exits(state, spec);
+
+        // Set of validators slashed by the block, which cannot be slashed again or exit.
+        let mut to_be_slashed = next_proposer_slashings
+            .iter()
+            .map(|s| s.signed_header_1.message.proposer_index)
+            .collect::<HashSet<_>>();
+        for slashing in &next_attester_slashings {
+            if let Ok(indices) = get_slashable_indices(state, slashing) {
+                to_be_slashed.extend(indices);
+            }
+        }
+
+        let proposer_slashings = self
+            .get_all_proposer_slashings()
+            .into_iter()
+            .map(|slashing| {
+                let forecast = if next_proposer_slashings.contains(&slashing) {
+                    InclusionForecast::NextBlock
+                } else {
+                    match state
+                        .validators()
+                        .get(slashing.signed_header_1.message.proposer_index as usize)
+                    {
+                        None => InclusionForecast::Excluded("unknown proposer"),
+                        Some(validator) if validator.slashed => {
+                            InclusionForecast::Excluded("proposer is already slashed")
+                        }
+                        Some(_) => InclusionForecast::Excluded("block is full"),
+                    }
+                };
+                (slashing, forecast)
+            })
+            .collect();
+
+        let attester_slashings = self
+            .attester_slashings
+            .read()
+            .iter()
+            .map(|(slashing, fork_version)| {
+                let forecast = if next_attester_slashings.contains(slashing) {
+                    InclusionForecast::NextBlock
+                } else if *fork_version != state.fork().previous_version
+                    && *fork_version != state.fork().current_version
+                {
+                    InclusionForecast::Excluded("slashing is from another fork")
+                } else {
+                    let slashable =
+                        get_slashable_indices_modular(state, slashing, |i, validator| {
+                            validator.is_slashable_at(state.current_epoch())
+                                && !to_be_slashed.contains(&i)
+                        })
+                        .map_or(false, |indices| !indices.is_empty());
+
+                    if slashable {
+                        InclusionForecast::Excluded("block is full")
+                    } else {
+                        InclusionForecast::Excluded("no validators remain to be slashed")
+                    }
+                };
+                (slashing.clone(), forecast)
+            })
+            .collect();
+
+        let voluntary_exits = self
+            .get_all_voluntary_exits()
+            .into_iter()
+            .map(|exit| {
+                let forecast = if next_voluntary_exits.contains(&exit) {
+                    InclusionForecast::NextBlock
+                } else if to_be_slashed.contains(&exit.message.validator_index) {
+                    InclusionForecast::Excluded("validator is slashed by the block")
+                } else if verify_exit(state, &exit, VerifySignatures::False, spec).is_err() {
+                    InclusionForecast::Excluded("exit is invalid")
+                } else {
+                    InclusionForecast::Excluded("block is full")
+                };
+                (exit, forecast)
+            })
+            .collect();
+
+        OperationForecasts {
+            proposer_slashings,
+            attester_slashings,
+            voluntary_exits,
+        }
     }
 
     /// Total number of voluntary exits in the pool.
@@ -630,7 +866,8 @@ where
         .collect()
 }
 
-/// Remove all entries from the given hash map for which `prune_if` returns true.
+/// Remove all entries from the given hash map for which `prune_if` returns true, returning the
+/// number of entries removed.
 ///
 /// The keys in the map should be validator indices, which will be looked up
 /// in the state's validator registry and then passed to `prune_if`.
@@ -639,15 +876,18 @@ fn prune_validator_hash_map<T, F, E: EthSpec>(
     map: &mut HashMap<u64, T>,
     prune_if: F,
     head_state: &BeaconState<E>,
-) where
+) -> usize
+where
     F: Fn(&Validator) -> bool,
 {
+    let len = map.len();
     map.retain(|&validator_index, _| {
         head_state
             .validators()
             .get(validator_index as usize)
             .map_or(true, |validator| !prune_if(validator))
     });
+    len - map.len()
 }
 
 /// Compare two operation pools.
@@ -850,6 +1090,26 @@ mod release_tests {
         assert_eq!(op_pool.attestations.read().len(), committees.len());
         assert_eq!(op_pool.num_attestations(), committees.len());
 
+        // The attestations were aggregated as they were inserted.
+        let stats = op_pool.attestation_stats();
+        assert_eq!(stats.per_slot.len(), 1);
+        assert_eq!(
+            stats.per_slot[&slot],
+            SlotAttestationCounts {
+                unaggregated: 0,
+                aggregated: committees.len(),
+            }
+        );
+
+        // The aggregate can be included in a block at the next slot.
+        assert_eq!(
+            op_pool.forecast_attestation_packing(&state, spec),
+            AttestationPackingForecast {
+                num_candidates: committees.len(),
+                num_included: committees.len(),
+            }
+        );
+
         // Before the min attestation inclusion delay, get_attestations shouldn't return anything.
         assert_eq!(
             op_pool
@@ -877,9 +1137,18 @@ mod release_tests {
         op_pool.prune_attestations(state.current_epoch());
         assert_eq!(op_pool.num_attestations(), committees.len());
 
+        // More than an epoch after the attestation, it can no longer be included.
+        *state.slot_mut() += MainnetEthSpec::slots_per_epoch();
+        assert_eq!(
+            op_pool
+                .forecast_attestation_packing(&state, spec)
+                .num_candidates,
+            0
+        );
+
         // But once we advance to more than an epoch after the attestation, it should prune it
         // out of existence.
-        *state.slot_mut() += 2 * MainnetEthSpec::slots_per_epoch();
+        *state.slot_mut() += MainnetEthSpec::slots_per_epoch();
         op_pool.prune_attestations(state.current_epoch());
         assert_eq!(op_pool.num_attestations(), 0);
     }
@@ -1292,6 +1561,50 @@ mod release_tests {
         );
     }
 
+    // Check the reasons given for slashings being excluded from the next block
+    #[test]
+    fn forecast_slashings() {
+        let harness = get_harness(32, None);
+        let spec = &harness.spec;
+        let state = harness.get_current_state();
+        let op_pool = OperationPool::<MainnetEthSpec>::new();
+
+        let p_slashing = harness.make_proposer_slashing(1);
+        let a_slashing_1 = harness.make_attester_slashing(vec![1, 2, 3]);
+        let a_slashing_2 = harness.make_attester_slashing(vec![1, 2]);
+
+        op_pool.insert_proposer_slashing(p_slashing.clone().validate(&state, spec).unwrap());
+        op_pool.insert_attester_slashing(
+            a_slashing_1.clone().validate(&state, spec).unwrap(),
+            state.fork(),
+        );
+        op_pool.insert_attester_slashing(
+            a_slashing_2.clone().validate(&state, spec).unwrap(),
+            state.fork(),
+        );
+
+        let forecasts = op_pool.forecast_slashings_and_exits(&state, spec);
+        assert_eq!(
+            forecasts.proposer_slashings,
+            vec![(p_slashing, InclusionForecast::NextBlock)]
+        );
+
+        let mut attester_slashings = forecasts.attester_slashings;
+        attester_slashings
+            .sort_by_key(|(slashing, _)| slashing.attestation_1.attesting_indices.len());
+        assert_eq!(
+            attester_slashings,
+            vec![
+                (
+                    a_slashing_2,
+                    InclusionForecast::Excluded("no validators remain to be slashed")
+                ),
+                (a_slashing_1, InclusionForecast::NextBlock),
+            ]
+        );
+        assert!(forecasts.voluntary_exits.is_empty());
+    }
+
     // Check that we get maximum coverage for attester slashings (highest qty of validators slashed)
     #[test]
     fn simple_max_cover_attester_slashing() {
@@ -1513,6 +1826,10 @@ mod release_tests {
         // out of existence.
         op_pool.prune_sync_contributions(state.slot() + Slot::new(1));
         assert_eq!(op_pool.num_sync_contributions(), 0);
+        assert_eq!(
+            op_pool.prune_stats().sync_contributions,
+            SYNC_COMMITTEE_SUBNET_COUNT
+        );
     }
 
     /// Adding a sync contribution already in the pool should not increase the size of the pool.
@@ -19,6 +19,11 @@ lazy_static! {
         "op_pool_curr_epoch_attestations",
         "Number of valid attestations considered for packing from the current epoch"
     );
+    pub static ref PRUNE_TIME: Result<HistogramVec> = try_create_histogram_vec(
+        "op_pool_prune_time",
+        "Time to prune each type of operation from the pool",
+        &["operation"]
+    );
     pub static ref MAX_COVER_NON_ZERO_ITEMS: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "op_pool_max_cover_non_zero_items",
         "Number of non-trivial items considered in a max coverage optimisation",
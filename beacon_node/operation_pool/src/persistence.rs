@@ -121,6 +121,7 @@ impl<T: EthSpec> PersistedOperationPool<T> {
                 attester_slashings,
                 proposer_slashings,
                 voluntary_exits,
+                prune_stats: Default::default(),
                 _phantom: Default::default(),
             },
             PersistedOperationPool::Altair(_) => {
@@ -133,6 +134,7 @@ impl<T: EthSpec> PersistedOperationPool<T> {
                     attester_slashings,
                     proposer_slashings,
                     voluntary_exits,
+                    prune_stats: Default::default(),
                     _phantom: Default::default(),
                 }
             }
@@ -12,6 +12,15 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         /*
          * Configuration directory locations.
          */
+        .arg(
+            Arg::with_name("config-file")
+                .long("config-file")
+                .value_name("FILE")
+                .help("Path to a TOML or YAML file of beacon node flags, keyed by their long \
+                       names. Flags given on the command line take precedence over the file. \
+                       See `lighthouse config dump` to create one.")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("network-dir")
                 .long("network-dir")
@@ -58,6 +67,62 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        not be performed before shutdown.")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("shutdown-timeout")
+                .long("shutdown-timeout")
+                .value_name("SECONDS")
+                .help("On receiving a shutdown signal, the maximum number of seconds to allow each \
+                       stage of a graceful shutdown (stopping gossip, draining queued work, \
+                       persisting to disk and disconnecting peers). If any stage times out the \
+                       process exits with code 2.")
+                .default_value("15")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("low-disk-space-threshold")
+                .long("low-disk-space-threshold")
+                .value_name("GIGABYTES")
+                .help("Log a warning each slot whilst the disk containing the database has less \
+                       than this many gigabytes free. Set to 0 to disable the warning.")
+                .default_value("10")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("ntp-server")
+                .long("ntp-server")
+                .value_name("HOST[:PORT]")
+                .help("An NTP server (e.g. pool.ntp.org) to query every few minutes when \
+                       estimating the offset of the system clock. The offset is also estimated \
+                       from the arrival times of blocks, which only detects a clock which is \
+                       behind.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("clock-drift-threshold")
+                .long("clock-drift-threshold")
+                .value_name("MILLISECONDS")
+                .help("Log a warning whilst the system clock is estimated to be offset by more \
+                       than this many milliseconds. A skewed clock causes blocks from peers to \
+                       be rejected as from a future slot.")
+                .default_value("500")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("disable-backfill")
+                .long("disable-backfill")
+                .help("Do not backfill historical blocks after checkpoint sync. Backfill sync can \
+                       be resumed via the /lighthouse/sync/backfill/resume HTTP API endpoint.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("dump-failed-batches")
+                .long("dump-failed-batches")
+                .help("If present, the blocks of any sync batch that fails to process due to an \
+                       internal error are written to the debug/failed_batches directory of the \
+                       beacon node data directory, along with metadata describing the failure. \
+                       These files can be attached to bug reports.")
+                .takes_value(false),
+        )
         .arg(
             Arg::with_name("zero-ports")
                 .long("zero-ports")
@@ -187,6 +252,55 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("One or more comma-delimited trusted peer ids which always have the highest score according to the peer scoring system.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("peer-action-penalties")
+                .long("peer-action-penalties")
+                .value_name("PENALTIES")
+                .help("One or more comma-delimited [SOURCE.]ACTION=PENALTY entries which set the \
+                       score penalty of a peer action, optionally only for reports from a single \
+                       source. ACTION is one of low_tolerance_error, mid_tolerance_error or \
+                       high_tolerance_error. SOURCE is one of gossipsub, rpc_error, processor, \
+                       sync or peer_manager. A penalty set for a source takes precedence over the \
+                       penalty for all sources, e.g. \"low_tolerance_error=20,gossipsub.low_tolerance_error=5\".")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("peer-score-dry-run")
+                .long("peer-score-dry-run")
+                .help("Log and record metrics for the score penalties of peer actions without \
+                       applying them. Fatal actions are still applied. Intended for evaluating a \
+                       scoring policy before enforcing it.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("max-peers-per-ip-prefix")
+                .long("max-peers-per-ip-prefix")
+                .value_name("COUNT")
+                .help("The maximum number of connected peers with an address in the same /24 \
+                       (IPv4) or /48 (IPv6) prefix. Peers which dial us from a prefix at this \
+                       limit are disconnected. Loopback and private addresses are exempt. No \
+                       limit is applied by default. Must be greater than 0.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("max-peers-per-asn")
+                .long("max-peers-per-asn")
+                .value_name("COUNT")
+                .help("The maximum number of connected peers with an address in the same \
+                       autonomous system. Peers which dial us from an AS at this limit are \
+                       disconnected. Requires --asn-db.")
+                .requires("asn-db")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("asn-db")
+                .long("asn-db")
+                .value_name("PATH")
+                .help("Path to a database mapping IP address ranges to autonomous system \
+                       numbers, in the tab-separated ip2asn format (https://iptoasn.com). Used to \
+                       limit peers per AS and to report the number of distinct ASNs of our peers.")
+                .takes_value(true),
+        )
         /* REST API related arguments */
         .arg(
             Arg::with_name("http")
@@ -220,6 +334,30 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     address of this server (e.g., http://localhost:5052).")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("http-allow-methods")
+                .long("http-allow-methods")
+                .value_name("METHODS")
+                .help("Set the comma-separated list of methods in the Access-Control-Allow-Methods \
+                    response HTTP header, replacing the default of GET, POST.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-allow-headers")
+                .long("http-allow-headers")
+                .value_name("HEADERS")
+                .help("Set the comma-separated list of headers in the Access-Control-Allow-Headers \
+                    response HTTP header, replacing the default of Content-Type.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-allow-credentials")
+                .long("http-allow-credentials")
+                .help("Allow browsers to include credentials in CORS requests by setting the \
+                    Access-Control-Allow-Credentials response HTTP header. Cannot be used if any \
+                    of the allowed origins, methods or headers is *.")
+                .takes_value(false),
+        )
         .arg(
             Arg::with_name("http-disable-legacy-spec")
                 .long("http-disable-legacy-spec")
@@ -256,6 +394,78 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     stalled. This is useful for very small testnets. TESTING ONLY. DO NOT USE ON \
                     MAINNET.")
         )
+        .arg(
+            Arg::with_name("http-max-body-size")
+                .long("http-max-body-size")
+                .value_name("BYTES")
+                .help("The maximum size of a HTTP API request body, in bytes. Requests with \
+                    larger bodies are rejected.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-max-concurrent-requests")
+                .long("http-max-concurrent-requests")
+                .value_name("COUNT")
+                .help("The maximum number of HTTP API requests to serve concurrently, excluding \
+                    requests which are critical to validator duties. Excess requests wait for \
+                    earlier requests to complete. Set to 0 to disable the limit. [default: 64]")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-max-concurrent-priority-requests")
+                .long("http-max-concurrent-priority-requests")
+                .value_name("COUNT")
+                .help("The maximum number of HTTP API requests to serve concurrently for the \
+                    routes which are critical to validator duties, such as block production and \
+                    attestation data. These requests are never delayed by requests to other \
+                    routes. No limit is applied by default. Must be greater than 0.")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-max-concurrent-state-regens")
+                .long("http-max-concurrent-state-regens")
+                .value_name("COUNT")
+                .help("The maximum number of states to regenerate concurrently in order to serve \
+                    requests to the /eth/v1/debug/beacon/states endpoint. Excess regenerations \
+                    wait for earlier regenerations to complete. [default: 2]")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-state-regen-cache-size")
+                .long("http-state-regen-cache-size")
+                .value_name("COUNT")
+                .help("The number of states regenerated for the /eth/v1/debug/beacon/states \
+                    endpoint to keep in memory, so that retried requests can be served \
+                    immediately. [default: 2]")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-state-regen-timeout")
+                .long("http-state-regen-timeout")
+                .value_name("SECONDS")
+                .help("The number of seconds to wait for a state to be regenerated for the \
+                    /eth/v1/debug/beacon/states endpoint. If the state is not ready in time the \
+                    request is answered with 202 Accepted and a Retry-After header whilst the \
+                    regeneration continues in the background. [default: 12]")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-sse-replay-window")
+                .long("http-sse-replay-window")
+                .value_name("COUNT")
+                .help("The number of recent events of each topic to retain, so that clients which \
+                    reconnect to /eth/v1/events with a Last-Event-ID header are sent the events \
+                    they missed. Set to 0 to disable replay. [default: 64]")
+                .takes_value(true)
+        )
+        .arg(
+            Arg::with_name("http-ready-min-peers")
+                .long("http-ready-min-peers")
+                .value_name("COUNT")
+                .help("The number of connected peers required for /lighthouse/health/ready to \
+                    report that the node is ready. [default: 1]")
+                .takes_value(true)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::with_name("metrics")
@@ -289,6 +499,40 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     address of this server (e.g., http://localhost:5054).")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("metrics-allow-methods")
+                .long("metrics-allow-methods")
+                .value_name("METHODS")
+                .help("Set the comma-separated list of methods in the Access-Control-Allow-Methods \
+                    response HTTP header, replacing the default of GET.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metrics-allow-headers")
+                .long("metrics-allow-headers")
+                .value_name("HEADERS")
+                .help("Set the comma-separated list of headers in the Access-Control-Allow-Headers \
+                    response HTTP header, replacing the default of Content-Type.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metrics-allow-credentials")
+                .long("metrics-allow-credentials")
+                .help("Allow browsers to include credentials in CORS requests by setting the \
+                    Access-Control-Allow-Credentials response HTTP header. Cannot be used if any \
+                    of the allowed origins, methods or headers is *.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("metrics-enable-tls")
+                .long("metrics-enable-tls")
+                .help("Serves the metrics server over TLS, using the certificate and private key \
+                    provided by --http-tls-cert and --http-tls-key.")
+                .takes_value(false)
+                .requires("metrics")
+                .requires("http-tls-cert")
+                .requires("http-tls-key")
+        )
 
         /*
          * Monitoring metrics
@@ -306,6 +550,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 and never provide an untrusted URL.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("monitoring-endpoint-period")
+                .long("monitoring-endpoint-period")
+                .value_name("SECONDS")
+                .help("Defines how many seconds to wait between each message sent to \
+                       the monitoring-endpoint. Default: 60s")
+                .requires("monitoring-endpoint")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("monitoring-endpoint-skip-system")
+                .long("monitoring-endpoint-skip-system")
+                .help("Do not send system metrics (CPU, memory, disk, network and operating \
+                       system) to the monitoring-endpoint. Only the metrics of the Lighthouse \
+                       process are sent.")
+                .requires("monitoring-endpoint")
+                .takes_value(false),
+        )
 
         /*
          * Standard staking flags
@@ -337,18 +599,10 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("If present, uses an eth1 backend that generates static dummy data.\
                       Identical to the method used at the 2019 Canada interop.")
         )
-        .arg(
-            Arg::with_name("eth1-endpoint")
-                .long("eth1-endpoint")
-                .value_name("HTTP-ENDPOINT")
-                .help("Deprecated. Use --eth1-endpoints.")
-                .takes_value(true)
-        )
         .arg(
             Arg::with_name("eth1-endpoints")
                 .long("eth1-endpoints")
                 .value_name("HTTP-ENDPOINTS")
-                .conflicts_with("eth1-endpoint")
                 .help("One or more comma-delimited server endpoints for web3 connection. \
                        If multiple endpoints are given the endpoints are used as fallback in the \
                        given order. Also enables the --eth1 flag. \
@@ -362,6 +616,18 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("Purges the eth1 block and deposit caches")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("eth1-cache-follow-distance")
+                .long("eth1-cache-follow-distance")
+                .value_name("BLOCKS")
+                .help("Specifies the distance between the Eth1 chain head and the last block \
+                       which should be imported into the cache. Setting this value lower can \
+                       help compensate for irregular Proof-of-Work block times, but setting it \
+                       too low can make the node vulnerable to re-orgs. Must not exceed the \
+                       ETH1_FOLLOW_DISTANCE of the network. Defaults to 3/4 of the \
+                       ETH1_FOLLOW_DISTANCE.")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("eth1-blocks-per-log-query")
                 .long("eth1-blocks-per-log-query")
@@ -408,6 +674,19 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                        will be used. Defaults to http://127.0.0.1:8545.")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("execution-jwt")
+                .long("execution-jwt")
+                .value_name("EXECUTION-JWT")
+                .help("One or more comma-delimited paths to files containing the hex-encoded \
+                       secrets used to authenticate with the Engine API of each of the \
+                       --execution-endpoints, in the same order. If a file does not exist, a \
+                       new random secret is written to it so that it can be supplied to the \
+                       execution engine. If this flag is omitted, requests are not \
+                       authenticated.")
+                .requires("merge")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("fee-recipient")
                 .long("fee-recipient")
@@ -419,6 +698,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .requires("merge")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("builder")
+                .long("builder")
+                .value_name("URL")
+                .help("The URL of a server implementing the builder API, such as MEV-boost. \
+                       When set, validators may request blinded blocks whose execution payloads \
+                       are built by the external builder.")
+                .requires("merge")
+                .takes_value(true)
+        )
 
         /*
          * Database purging and compaction.
@@ -441,6 +730,31 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .default_value("true")
         )
+        .arg(
+            Arg::with_name("hot-state-diffs")
+                .long("hot-state-diffs")
+                .help("If present, store each intermediate state in the hot database as a diff \
+                       against its epoch boundary state, so that it can be loaded without \
+                       replaying blocks. This uses more disk space in the hot database.")
+        )
+        .arg(
+            Arg::with_name("prune-payloads")
+                .long("prune-payloads")
+                .help("If present, the execution payloads of finalized blocks are deleted from \
+                       the database once they are older than --payload-retention-epochs. The \
+                       blocks are kept without their payloads, which remain available from the \
+                       execution engine. Pruned blocks can't be served to peers or via the HTTP \
+                       API.")
+        )
+        .arg(
+            Arg::with_name("payload-retention-epochs")
+                .long("payload-retention-epochs")
+                .value_name("EPOCHS")
+                .help("Number of epochs prior to the latest finalized epoch for which execution \
+                       payloads are kept when --prune-payloads is enabled.")
+                .takes_value(true)
+                .default_value("256")
+        )
 
         /*
          * Misc.
@@ -515,8 +829,8 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name("slasher-history-length")
                 .long("slasher-history-length")
                 .help(
-                    "Configure how many epochs of history the slasher keeps. Immutable after \
-                     initialization."
+                    "Configure how many epochs of history the slasher keeps. Can only be changed \
+                     after initialization with --slasher-rechunk."
                 )
                 .value_name("EPOCHS")
                 .requires("slasher")
@@ -526,7 +840,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name("slasher-max-db-size")
                 .long("slasher-max-db-size")
                 .help(
-                    "Maximum size of the MDBX database used by the slasher."
+                    "Maximum size of the database used by the slasher."
                 )
                 .value_name("GIGABYTES")
                 .requires("slasher")
@@ -560,6 +874,30 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .requires("slasher")
                 .takes_value(true)
         )
+        .arg(
+            Arg::with_name("slasher-rechunk")
+                .long("slasher-rechunk")
+                .help("Convert an existing slasher database to a new --slasher-chunk-size, \
+                       --slasher-validator-chunk-size or --slasher-history-length, instead of \
+                       refusing to start.")
+                .requires("slasher")
+        )
+        .arg(
+            Arg::with_name("slasher-backend")
+                .long("slasher-backend")
+                .help("Set the database backend to be used by the slasher.")
+                .value_name("DATABASE")
+                .takes_value(true)
+                .possible_values(&["mdbx", "lmdb"])
+                .requires("slasher")
+        )
+        .arg(
+            Arg::with_name("slasher-migrate-backend")
+                .long("slasher-migrate-backend")
+                .help("Convert an existing slasher database created with a different backend to \
+                       the --slasher-backend, instead of refusing to start.")
+                .requires("slasher")
+        )
         .arg(
             Arg::with_name("slasher-broadcast")
                 .long("slasher-broadcast")
@@ -611,6 +949,43 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .help("After a checkpoint sync, reconstruct historic states in the database.")
                 .takes_value(false)
         )
+        .arg(
+            Arg::with_name("light-client-server")
+                .long("light-client-server")
+                .help("Compute the data required by light clients during block import and serve it \
+                    on the HTTP API and the light client gossip topics.")
+                .takes_value(false)
+        )
+        .arg(
+            Arg::with_name("light")
+                .long("light")
+                .help("Track the chain using light client updates fetched from another beacon node, \
+                    rather than by importing blocks. Only a restricted subset of the HTTP API is \
+                    served, on the address given by --http-address and --http-port. Requires \
+                    --light-client-upstream and --light-client-trusted-block-root.")
+                .takes_value(false)
+                .requires("light-client-upstream")
+                .requires("light-client-trusted-block-root")
+        )
+        .arg(
+            Arg::with_name("light-client-upstream")
+                .long("light-client-upstream")
+                .help("The HTTP endpoint of a beacon node serving light client data, to use in \
+                    --light mode. The upstream node is not trusted, all updates are verified \
+                    against the sync committee.")
+                .value_name("BEACON_NODE")
+                .takes_value(true)
+                .requires("light")
+        )
+        .arg(
+            Arg::with_name("light-client-trusted-block-root")
+                .long("light-client-trusted-block-root")
+                .help("The root of a recent finalized block which is trusted to be canonical, from \
+                    which to start syncing in --light mode.")
+                .value_name("BLOCK_ROOT")
+                .takes_value(true)
+                .requires("light")
+        )
         .arg(
             Arg::with_name("validator-monitor-auto")
                 .long("validator-monitor-auto")
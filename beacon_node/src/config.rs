@@ -14,6 +14,7 @@ use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
 use std::net::{TcpListener, UdpSocket};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use types::{Address, Checkpoint, Epoch, EthSpec, Hash256, PublicKeyBytes, GRAFFITI_BYTES_LEN};
 
 // TODO(merge): remove this default value. It's just there to make life easy during
@@ -117,29 +118,76 @@ pub fn get_config<E: EthSpec>(
         client_config.http_api.allow_origin = Some(allow_origin.to_string());
     }
 
+    if let Some(allow_methods) = cli_args.value_of("http-allow-methods") {
+        client_config.http_api.allow_methods = Some(allow_methods.to_string());
+    }
+
+    if let Some(allow_headers) = cli_args.value_of("http-allow-headers") {
+        client_config.http_api.allow_headers = Some(allow_headers.to_string());
+    }
+
+    if cli_args.is_present("http-allow-credentials") {
+        client_config.http_api.allow_credentials = true;
+    }
+
     if cli_args.is_present("http-disable-legacy-spec") {
         client_config.http_api.serve_legacy_spec = false;
     }
 
     if cli_args.is_present("http-enable-tls") {
-        client_config.http_api.tls_config = Some(TlsConfig {
-            cert: cli_args
-                .value_of("http-tls-cert")
-                .ok_or("--http-tls-cert was not provided.")?
-                .parse::<PathBuf>()
-                .map_err(|_| "http-tls-cert is not a valid path name.")?,
-            key: cli_args
-                .value_of("http-tls-key")
-                .ok_or("--http-tls-key was not provided.")?
-                .parse::<PathBuf>()
-                .map_err(|_| "http-tls-key is not a valid path name.")?,
-        });
+        client_config.http_api.tls_config = Some(parse_tls_config(cli_args)?);
     }
 
     if cli_args.is_present("http-allow-sync-stalled") {
         client_config.http_api.allow_sync_stalled = true;
     }
 
+    if let Some(max_body_size) = clap_utils::parse_optional(cli_args, "http-max-body-size")? {
+        client_config.http_api.max_body_size = max_body_size;
+    }
+
+    if let Some(max_requests) =
+        clap_utils::parse_optional::<usize>(cli_args, "http-max-concurrent-requests")?
+    {
+        client_config.http_api.max_concurrent_requests = Some(max_requests).filter(|n| *n > 0);
+    }
+
+    if let Some(max_requests) =
+        clap_utils::parse_optional::<usize>(cli_args, "http-max-concurrent-priority-requests")?
+    {
+        if max_requests == 0 {
+            return Err(
+                "--http-max-concurrent-priority-requests must be greater than 0".to_string(),
+            );
+        }
+        client_config.http_api.max_concurrent_priority_requests = Some(max_requests);
+    }
+
+    if let Some(max_regens) =
+        clap_utils::parse_optional::<usize>(cli_args, "http-max-concurrent-state-regens")?
+    {
+        if max_regens == 0 {
+            return Err("--http-max-concurrent-state-regens must be greater than 0".to_string());
+        }
+        client_config.http_api.max_concurrent_state_regens = max_regens;
+    }
+
+    if let Some(cache_size) = clap_utils::parse_optional(cli_args, "http-state-regen-cache-size")? {
+        client_config.http_api.state_regen_cache_size = cache_size;
+    }
+
+    if let Some(timeout) = clap_utils::parse_optional(cli_args, "http-state-regen-timeout")? {
+        client_config.http_api.state_regen_max_wait = Duration::from_secs(timeout);
+    }
+
+    if let Some(replay_window) = clap_utils::parse_optional(cli_args, "http-sse-replay-window")? {
+        client_config.http_api.sse_replay_window = replay_window;
+    }
+
+    if let Some(min_peers) = clap_utils::parse_optional(cli_args, "http-ready-min-peers")? {
+        client_config.http_api.ready_min_peers = min_peers;
+    }
+
     /*
      * Prometheus metrics HTTP server
      */
@@ -169,14 +217,38 @@ pub fn get_config<E: EthSpec>(
         client_config.http_metrics.allow_origin = Some(allow_origin.to_string());
     }
 
+    if let Some(allow_methods) = cli_args.value_of("metrics-allow-methods") {
+        client_config.http_metrics.allow_methods = Some(allow_methods.to_string());
+    }
+
+    if let Some(allow_headers) = cli_args.value_of("metrics-allow-headers") {
+        client_config.http_metrics.allow_headers = Some(allow_headers.to_string());
+    }
+
+    if cli_args.is_present("metrics-allow-credentials") {
+        client_config.http_metrics.allow_credentials = true;
+    }
+
+    if cli_args.is_present("metrics-enable-tls") {
+        client_config.http_metrics.tls_config = Some(parse_tls_config(cli_args)?);
+    }
+
     /*
      * Explorer metrics
      */
     if let Some(monitoring_endpoint) = cli_args.value_of("monitoring-endpoint") {
+        let update_period_secs =
+            clap_utils::parse_optional(cli_args, "monitoring-endpoint-period")?;
+        if update_period_secs == Some(0) {
+            return Err("--monitoring-endpoint-period must be greater than 0".to_string());
+        }
+
         client_config.monitoring_api = Some(monitoring_api::Config {
             db_path: None,
             freezer_db_path: None,
             monitoring_endpoint: monitoring_endpoint.to_string(),
+            update_period_secs,
+            skip_system_metrics: cli_args.is_present("monitoring-endpoint-skip-system"),
         });
     }
 
@@ -213,16 +285,7 @@ pub fn get_config<E: EthSpec>(
     }
 
     // Defines the URL to reach the eth1 node.
-    if let Some(endpoint) = cli_args.value_of("eth1-endpoint") {
-        warn!(
-            log,
-            "The --eth1-endpoint flag is deprecated";
-            "msg" => "please use --eth1-endpoints instead"
-        );
-        client_config.sync_eth1_chain = true;
-        client_config.eth1.endpoints = vec![SensitiveUrl::parse(endpoint)
-            .map_err(|e| format!("eth1-endpoint was an invalid URL: {:?}", e))?];
-    } else if let Some(endpoints) = cli_args.value_of("eth1-endpoints") {
+    if let Some(endpoints) = cli_args.value_of("eth1-endpoints") {
         client_config.sync_eth1_chain = true;
         client_config.eth1.endpoints = endpoints
             .split(',')
@@ -253,6 +316,17 @@ pub fn get_config<E: EthSpec>(
         client_config.execution_endpoints = Some(client_config.eth1.endpoints.clone());
     }
 
+    if let Some(secrets) = cli_args.value_of("execution-jwt") {
+        client_config.execution_jwt_secrets = secrets.split(',').map(PathBuf::from).collect();
+    }
+
+    if let Some(builder_url) = cli_args.value_of("builder") {
+        client_config.builder_url = Some(
+            SensitiveUrl::parse(builder_url)
+                .map_err(|e| format!("builder contains an invalid URL {:?}", e))?,
+        );
+    }
+
     client_config.suggested_fee_recipient = Some(
         clap_utils::parse_optional(cli_args, "fee-recipient")?
             // TODO(merge): remove this default value. It's just there to make life easy during
@@ -287,6 +361,10 @@ pub fn get_config<E: EthSpec>(
             .parse()
             .map_err(|_| "auto-compact-db takes a boolean".to_string())?;
     }
+    client_config.store.hot_state_diffs = cli_args.is_present("hot-state-diffs");
+    client_config.store.prune_payloads = cli_args.is_present("prune-payloads");
+    client_config.store.payload_retention_epochs =
+        clap_utils::parse_required(cli_args, "payload-retention-epochs")?;
 
     /*
      * Zero-ports
@@ -323,6 +401,17 @@ pub fn get_config<E: EthSpec>(
     client_config.eth1.lowest_cached_block_number =
         client_config.eth1.deposit_contract_deploy_block;
     client_config.eth1.follow_distance = spec.eth1_follow_distance;
+    if let Some(cache_follow_distance) =
+        clap_utils::parse_optional::<u64>(cli_args, "eth1-cache-follow-distance")?
+    {
+        if cache_follow_distance > spec.eth1_follow_distance {
+            return Err(format!(
+                "eth1-cache-follow-distance must not exceed the ETH1_FOLLOW_DISTANCE of {}",
+                spec.eth1_follow_distance
+            ));
+        }
+        client_config.eth1.cache_follow_distance = Some(cache_follow_distance);
+    }
     client_config.eth1.node_far_behind_seconds =
         max(5, spec.eth1_follow_distance / 2) * spec.seconds_per_eth1_block;
     client_config.eth1.network_id = spec.deposit_network_id.into();
@@ -404,6 +493,32 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.reconstruct_historic_states = true;
     }
 
+    if cli_args.is_present("light-client-server") {
+        client_config.chain.enable_light_client_server = true;
+    }
+
+    if cli_args.is_present("light") {
+        let upstream = cli_args
+            .value_of("light-client-upstream")
+            .ok_or("--light requires --light-client-upstream")?;
+        let upstream = SensitiveUrl::parse(upstream)
+            .map_err(|e| format!("Invalid light client upstream URL: {:?}", e))?;
+        let trusted_block_root =
+            clap_utils::parse_required::<Hash256>(cli_args, "light-client-trusted-block-root")?;
+
+        let http_api = &client_config.http_api;
+        client_config.light_client = Some(light_client::Config {
+            listen_addr: http_api.listen_addr,
+            listen_port: http_api.listen_port,
+            allow_origin: http_api.allow_origin.clone(),
+            allow_methods: http_api.allow_methods.clone(),
+            allow_headers: http_api.allow_headers.clone(),
+            allow_credentials: http_api.allow_credentials,
+            tls_config: http_api.tls_config.clone(),
+            ..light_client::Config::new(upstream, trusted_block_root)
+        });
+    }
+
     let raw_graffiti = if let Some(graffiti) = cli_args.value_of("graffiti") {
         if graffiti.len() > GRAFFITI_BYTES_LEN {
             return Err(format!(
@@ -527,6 +642,12 @@ pub fn get_config<E: EthSpec>(
         }
 
         slasher_config.broadcast = cli_args.is_present("slasher-broadcast");
+        slasher_config.rechunk = cli_args.is_present("slasher-rechunk");
+
+        if let Some(backend) = clap_utils::parse_optional(cli_args, "slasher-backend")? {
+            slasher_config.backend = backend;
+        }
+        slasher_config.migrate_backend = cli_args.is_present("slasher-migrate-backend");
 
         client_config.slasher = Some(slasher_config);
     }
@@ -568,6 +689,18 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.enable_lock_timeouts = false;
     }
 
+    client_config.shutdown_timeout =
+        Duration::from_secs(clap_utils::parse_required(cli_args, "shutdown-timeout")?);
+
+    client_config.low_disk_space_threshold_gb =
+        clap_utils::parse_required(cli_args, "low-disk-space-threshold")?;
+
+    client_config.ntp_server = clap_utils::parse_optional(cli_args, "ntp-server")?;
+    client_config.clock_drift_threshold = Duration::from_millis(clap_utils::parse_required(
+        cli_args,
+        "clock-drift-threshold",
+    )?);
+
     Ok(client_config)
 }
 
@@ -598,6 +731,18 @@ pub fn set_network_config(
         config.shutdown_after_sync = true;
     }
 
+    if cli_args.is_present("light-client-server") {
+        config.enable_light_client_server = true;
+    }
+
+    if cli_args.is_present("disable-backfill") {
+        config.disable_backfill = true;
+    }
+
+    if cli_args.is_present("dump-failed-batches") {
+        config.failed_batch_dir = Some(data_dir.join("debug").join("failed_batches"));
+    }
+
     if let Some(listen_address_str) = cli_args.value_of("listen-address") {
         let listen_address = listen_address_str
             .parse()
@@ -680,6 +825,31 @@ pub fn set_network_config(
             .collect::<Result<Vec<PeerIdSerialized>, _>>()?;
     }
 
+    if let Some(penalties) = cli_args.value_of("peer-action-penalties") {
+        config
+            .peer_score
+            .set_penalties(penalties)
+            .map_err(|e| format!("Invalid --peer-action-penalties: {}", e))?;
+    }
+
+    if cli_args.is_present("peer-score-dry-run") {
+        config.peer_score.dry_run = true;
+    }
+
+    if let Some(max_peers) =
+        clap_utils::parse_optional::<usize>(cli_args, "max-peers-per-ip-prefix")?
+    {
+        if max_peers == 0 {
+            return Err("--max-peers-per-ip-prefix must be greater than 0".to_string());
+        }
+        config.peer_admission.max_peers_per_ip_prefix = Some(max_peers);
+    }
+
+    config.peer_admission.max_peers_per_asn =
+        clap_utils::parse_optional(cli_args, "max-peers-per-asn")?;
+
+    config.peer_admission.asn_db = clap_utils::parse_optional(cli_args, "asn-db")?;
+
     if let Some(enr_udp_port_str) = cli_args.value_of("enr-udp-port") {
         config.enr_udp_port = Some(
             enr_udp_port_str
@@ -775,6 +945,23 @@ pub fn set_network_config(
     Ok(())
 }
 
+/// Reads the `--http-tls-cert` and `--http-tls-key` flags, which are shared by the HTTP API and
+/// metrics servers.
+fn parse_tls_config(cli_args: &ArgMatches) -> Result<TlsConfig, String> {
+    Ok(TlsConfig {
+        cert: cli_args
+            .value_of("http-tls-cert")
+            .ok_or("--http-tls-cert was not provided.")?
+            .parse::<PathBuf>()
+            .map_err(|_| "http-tls-cert is not a valid path name.")?,
+        key: cli_args
+            .value_of("http-tls-key")
+            .ok_or("--http-tls-key was not provided.")?
+            .parse::<PathBuf>()
+            .map_err(|_| "http-tls-key is not a valid path name.")?,
+    })
+}
+
 /// Gets the datadir which should be used.
 pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
     // Read the `--datadir` flag.
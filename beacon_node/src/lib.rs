@@ -149,7 +149,11 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             .build_beacon_chain()?
             .network(&client_config.network)
             .await?
-            .notifier()?
+            .notifier(client_config.low_disk_space_threshold_gb)?
+            .clock_drift_monitor(
+                client_config.ntp_server.clone(),
+                client_config.clock_drift_threshold,
+            )?
             .http_metrics_config(client_config.http_metrics.clone())
             .build()
             .map(Self)
@@ -160,6 +164,22 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
     }
 }
 
+/// Starts the beacon node in light mode, following the chain with light client updates from the
+/// upstream node given in `config` rather than by importing blocks.
+pub async fn start_light_client<E: EthSpec>(
+    context: RuntimeContext<E>,
+    config: light_client::Config,
+) -> Result<(), String> {
+    let spec = context.eth2_config().spec.clone();
+    info!(
+        context.log(),
+        "Starting in light mode";
+        "upstream" => %config.upstream,
+        "trusted_block_root" => ?config.trusted_block_root,
+    );
+    light_client::start::<E>(config, spec, context.executor).await
+}
+
 impl<E: EthSpec> Deref for ProductionBeaconNode<E> {
     type Target = ProductionClient<E>;
 
@@ -0,0 +1,111 @@
+//! Implementation of the index of frozen block roots, which allows canonical blocks in the freezer
+//! to be looked up by slot or by root without iterating the freezer's block roots.
+use crate::hot_cold_store::{ColdBlockRoot, ColdBlockSlot, HotColdDB, HotColdDBError};
+use crate::{Error, ItemStore, KeyValueStore, KeyValueStoreOp, StoreItem};
+use slog::info;
+use types::{EthSpec, Hash256, Slot};
+
+/// The maximum number of index entries to write to the freezer in a single batch.
+const INDEX_BATCH_SIZE: usize = 8192;
+
+impl<E, Hot, Cold> HotColdDB<E, Hot, Cold>
+where
+    E: EthSpec,
+    Hot: KeyValueStore<E> + ItemStore<E>,
+    Cold: KeyValueStore<E> + ItemStore<E>,
+{
+    /// Load the slot of a frozen canonical block, given its root.
+    ///
+    /// Returns `None` if the block is not finalized, or is not in the canonical chain.
+    pub fn get_frozen_block_slot(&self, block_root: &Hash256) -> Result<Option<Slot>, Error> {
+        Ok(self.cold_db.get(block_root)?.map(|s: ColdBlockSlot| s.slot))
+    }
+
+    /// Load the root of the frozen canonical block at `slot`.
+    ///
+    /// Returns `None` if `slot` was skipped, or does not lie in the frozen part of the chain.
+    pub fn get_frozen_block_root(&self, slot: Slot) -> Result<Option<Hash256>, Error> {
+        Ok(self
+            .cold_db
+            .get(&Self::frozen_block_slot_key(slot))?
+            .map(|r: ColdBlockRoot| r.block_root))
+    }
+
+    /// Stage the index entries for the frozen canonical block with `block_root` at `slot`.
+    pub fn store_frozen_block_root_index(
+        &self,
+        block_root: Hash256,
+        slot: Slot,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) {
+        ops.push(ColdBlockSlot { slot }.as_kv_store_op(block_root));
+        ops.push(ColdBlockRoot { block_root }.as_kv_store_op(Self::frozen_block_slot_key(slot)));
+    }
+
+    /// Convert a `slot` into a key for the slot to block root index.
+    fn frozen_block_slot_key(slot: Slot) -> Hash256 {
+        Hash256::from_low_u64_be(slot.as_u64())
+    }
+
+    /// Populate the frozen block root index from the block roots already stored in the freezer.
+    ///
+    /// This is only required for databases created before the index was introduced, as the
+    /// index is otherwise maintained by the freezer migration and by historic block backfill.
+    pub fn build_frozen_block_root_index(&self) -> Result<(), Error> {
+        let split = self.get_split_info();
+        let start_slot = self.get_oldest_block_slot();
+
+        if split.slot <= start_slot {
+            // Nothing to do, there are no frozen blocks.
+            return Ok(());
+        }
+
+        info!(
+            self.log,
+            "Building frozen block root index";
+            "start_slot" => start_slot,
+            "end_slot" => split.slot,
+        );
+
+        let split_state = self.get_state(&split.state_root, Some(split.slot))?.ok_or(
+            HotColdDBError::MissingSplitState(split.state_root, split.slot),
+        )?;
+        let split_block_root = split_state.get_latest_block_root(split.state_root);
+
+        let mut prev_block_root = None;
+        let mut ops = Vec::with_capacity(INDEX_BATCH_SIZE);
+
+        for result in self.forwards_block_roots_iterator(
+            start_slot,
+            split_state,
+            split_block_root,
+            &self.spec,
+        )? {
+            let (block_root, slot) = result?;
+
+            if slot >= split.slot {
+                break;
+            }
+
+            // Block roots are repeated at skipped slots, so a block's slot is the first slot at
+            // which its root appears.
+            if prev_block_root != Some(block_root) {
+                self.store_frozen_block_root_index(block_root, slot, &mut ops);
+                prev_block_root = Some(block_root);
+            }
+
+            if ops.len() >= INDEX_BATCH_SIZE {
+                self.cold_db.do_atomically(std::mem::take(&mut ops))?;
+            }
+        }
+        self.cold_db.do_atomically(ops)?;
+
+        info!(
+            self.log,
+            "Frozen block root index complete";
+            "end_slot" => split.slot,
+        );
+
+        Ok(())
+    }
+}
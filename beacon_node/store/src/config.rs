@@ -6,6 +6,7 @@ use types::{EthSpec, MinimalEthSpec};
 
 pub const DEFAULT_SLOTS_PER_RESTORE_POINT: u64 = 2048;
 pub const DEFAULT_BLOCK_CACHE_SIZE: usize = 5;
+pub const DEFAULT_PAYLOAD_RETENTION_EPOCHS: u64 = 256;
 
 /// Database configuration parameters.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -18,6 +19,12 @@ pub struct StoreConfig {
     pub compact_on_init: bool,
     /// Whether to compact the database during database pruning.
     pub compact_on_prune: bool,
+    /// Whether to store intermediate hot states as deltas against their epoch boundary states.
+    pub hot_state_diffs: bool,
+    /// Whether to prune the execution payloads of finalized blocks after each migration.
+    pub prune_payloads: bool,
+    /// Number of epochs prior to the split slot for which execution payloads are kept.
+    pub payload_retention_epochs: u64,
 }
 
 /// Variant of `StoreConfig` that gets written to disk. Contains immutable configuration params.
@@ -39,6 +46,9 @@ impl Default for StoreConfig {
             block_cache_size: DEFAULT_BLOCK_CACHE_SIZE,
             compact_on_init: false,
             compact_on_prune: true,
+            hot_state_diffs: false,
+            prune_payloads: false,
+            payload_retention_epochs: DEFAULT_PAYLOAD_RETENTION_EPOCHS,
         }
     }
 }
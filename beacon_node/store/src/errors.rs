@@ -19,6 +19,9 @@ pub enum Error {
     },
     RlpError(String),
     BlockNotFound(Hash256),
+    /// The block is finalized and its execution payload has been pruned, only the blinded block
+    /// is available.
+    ExecutionPayloadPruned(Hash256),
     NoContinuationData,
     SplitPointModified(Slot, Slot),
     ConfigError(StoreConfigError),
@@ -41,6 +44,16 @@ pub enum Error {
         computed: Hash256,
     },
     BlockReplayError(BlockReplayError),
+    /// A hot state diff refers to bytes beyond the end of its base state.
+    InvalidHotStateDiff {
+        base_len: usize,
+        copy_offset: u64,
+        copy_len: u64,
+    },
+    HotStateDiffLengthMismatch {
+        expected: u64,
+        computed: u64,
+    },
 }
 
 pub trait HandleUnavailable<T> {
@@ -3,8 +3,9 @@ use crate::chunked_vector::{
 };
 use crate::config::{OnDiskStoreConfig, StoreConfig};
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
-use crate::impls::beacon_state::{get_full_state, store_full_state};
-use crate::iter::{ParentRootBlockIterator, StateRootsIterator};
+use crate::hot_state_diff::HotStateDiff;
+use crate::impls::beacon_state::{get_full_state, store_full_state, StorageContainer};
+use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, StateRootsIterator};
 use crate::leveldb_store::BytesKey;
 use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
@@ -18,6 +19,7 @@ use crate::{
     get_key_for_col, DBColumn, Error, ItemStore, KeyValueStoreOp, PartialBeaconState, StoreItem,
     StoreOp,
 };
+use itertools::process_results;
 use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
@@ -57,7 +59,7 @@ pub struct HotColdDB<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> {
     /// The hot database also contains all blocks.
     pub hot_db: Hot,
     /// LRU cache of deserialized blocks. Updated whenever a block is loaded.
-    block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
+    pub(crate) block_cache: Mutex<LruCache<Hash256, SignedBeaconBlock<E>>>,
     /// Chain spec.
     pub(crate) spec: ChainSpec,
     /// Logger.
@@ -162,6 +164,14 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             _phantom: PhantomData,
         });
 
+        // Load the previous split slot from the database (if any). This ensures we can
+        // stop and restart correctly, and that schema migrations can access frozen data.
+        let split_opt = db.load_split()?;
+        if let Some(split) = split_opt {
+            *db.split.write() = split;
+            *db.anchor_info.write() = db.load_anchor_info()?;
+        }
+
         // Ensure that the schema version of the on-disk database matches the software.
         // If the version is mismatched, an automatic migration will be attempted.
         if let Some(schema_version) = db.load_schema_version()? {
@@ -182,12 +192,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
         }
         db.store_config()?;
 
-        // Load the previous split slot from the database (if any). This ensures we can
-        // stop and restart correctly.
-        if let Some(split) = db.load_split()? {
-            *db.split.write() = split;
-            *db.anchor_info.write() = db.load_anchor_info()?;
-
+        if let Some(split) = split_opt {
             info!(
                 db.log,
                 "Hot-Cold DB initialized";
@@ -299,22 +304,31 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         block_root: &Hash256,
         decoder: impl FnOnce(&[u8]) -> Result<SignedBeaconBlock<E>, ssz::DecodeError>,
     ) -> Result<Option<SignedBeaconBlock<E>>, Error> {
-        self.hot_db
+        match self
+            .hot_db
             .get_bytes(DBColumn::BeaconBlock.into(), block_root.as_bytes())?
-            .map(|block_bytes| decoder(&block_bytes))
-            .transpose()
-            .map_err(|e| e.into())
+        {
+            Some(block_bytes) => decoder(&block_bytes).map(Some).map_err(|e| e.into()),
+            None if self.payload_pruned(block_root)? => {
+                Err(Error::ExecutionPayloadPruned(*block_root))
+            }
+            None => Ok(None),
+        }
     }
 
-    /// Determine whether a block exists in the database.
+    /// Determine whether a block exists in the database, with or without its execution payload.
     pub fn block_exists(&self, block_root: &Hash256) -> Result<bool, Error> {
-        self.hot_db
-            .key_exists(DBColumn::BeaconBlock.into(), block_root.as_bytes())
+        Ok(self
+            .hot_db
+            .key_exists(DBColumn::BeaconBlock.into(), block_root.as_bytes())?
+            || self.payload_pruned(block_root)?)
     }
 
     /// Delete a block from the store and the block cache.
     pub fn delete_block(&self, block_root: &Hash256) -> Result<(), Error> {
         self.block_cache.lock().pop(block_root);
+        self.hot_db
+            .key_delete(DBColumn::BeaconBlindedBlock.into(), block_root.as_bytes())?;
         self.hot_db
             .key_delete(DBColumn::BeaconBlock.into(), block_root.as_bytes())
     }
@@ -418,10 +432,13 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.hot_db
             .key_delete(DBColumn::BeaconStateSummary.into(), state_root.as_bytes())?;
 
-        // Delete the full state if it lies on an epoch boundary.
+        // Delete the full state if it lies on an epoch boundary, or its diff otherwise.
         if slot % E::slots_per_epoch() == 0 {
             self.hot_db
                 .key_delete(DBColumn::BeaconState.into(), state_root.as_bytes())?;
+        } else {
+            self.hot_db
+                .key_delete(DBColumn::BeaconStateDiff.into(), state_root.as_bytes())?;
         }
 
         Ok(())
@@ -526,6 +543,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.hot_db.exists::<I>(key)
     }
 
+    pub fn delete_item<I: StoreItem>(&self, key: &Hash256) -> Result<(), Error> {
+        self.hot_db.delete::<I>(key)
+    }
+
     /// Convert a batch of `StoreOp` to a batch of `KeyValueStoreOp`.
     pub fn convert_to_kv_batch(&self, batch: &[StoreOp<E>]) -> Result<Vec<KeyValueStoreOp>, Error> {
         let mut key_value_batch = Vec::with_capacity(batch.len());
@@ -556,6 +577,10 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                 StoreOp::DeleteBlock(block_root) => {
                     let key = get_key_for_col(DBColumn::BeaconBlock.into(), block_root.as_bytes());
                     key_value_batch.push(KeyValueStoreOp::DeleteKey(key));
+
+                    let key =
+                        get_key_for_col(DBColumn::BeaconBlindedBlock.into(), block_root.as_bytes());
+                    key_value_batch.push(KeyValueStoreOp::DeleteKey(key));
                 }
 
                 StoreOp::DeleteState(state_root, slot) => {
@@ -568,6 +593,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                             get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes());
                         key_value_batch.push(KeyValueStoreOp::DeleteKey(state_key));
                     }
+
+                    if slot.map_or(true, |slot| slot % E::slots_per_epoch() != 0) {
+                        let diff_key = get_key_for_col(
+                            DBColumn::BeaconStateDiff.into(),
+                            state_root.as_bytes(),
+                        );
+                        key_value_batch.push(KeyValueStoreOp::DeleteKey(diff_key));
+                    }
                 }
             }
         }
@@ -607,7 +640,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
     /// Store a post-finalization state efficiently in the hot database.
     ///
     /// On an epoch boundary, store a full state. On an intermediate slot, store
-    /// just a backpointer to the nearest epoch boundary.
+    /// just a backpointer to the nearest epoch boundary, and optionally a diff against the state
+    /// at that epoch boundary.
     pub fn store_hot_state(
         &self,
         state_root: &Hash256,
@@ -632,12 +666,109 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         let op = hot_state_summary.as_kv_store_op(*state_root);
         ops.push(op);
 
+        if self.config.hot_state_diffs && state.slot() % E::slots_per_epoch() != 0 {
+            self.store_hot_state_diff(
+                state_root,
+                state,
+                &hot_state_summary.epoch_boundary_state_root,
+                ops,
+            )?;
+        }
+
         Ok(())
     }
 
+    /// Store an intermediate state as a diff against the state at its epoch boundary.
+    ///
+    /// Nothing is stored if the epoch boundary state is not yet in the database, or if the diff
+    /// would be no smaller than the state itself. Such states are reconstructed by replaying
+    /// blocks instead.
+    fn store_hot_state_diff(
+        &self,
+        state_root: &Hash256,
+        state: &BeaconState<E>,
+        epoch_boundary_state_root: &Hash256,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        let base_bytes = match self.hot_db.get_bytes(
+            DBColumn::BeaconState.into(),
+            epoch_boundary_state_root.as_bytes(),
+        )? {
+            Some(bytes) => bytes,
+            None => {
+                debug!(
+                    self.log,
+                    "Not storing hot state diff";
+                    "reason" => "epoch boundary state unavailable",
+                    "slot" => state.slot(),
+                    "state_root" => format!("{:?}", state_root),
+                );
+                return Ok(());
+            }
+        };
+
+        let diff_bytes = {
+            let _timer = metrics::start_timer(&metrics::BEACON_STATE_DIFF_COMPUTE_TIMES);
+            let target_bytes = StorageContainer::new(state).as_ssz_bytes();
+            let diff_bytes = HotStateDiff::compute(&base_bytes, &target_bytes).as_store_bytes();
+
+            if diff_bytes.len() >= target_bytes.len() {
+                debug!(
+                    self.log,
+                    "Not storing hot state diff";
+                    "reason" => "diff is larger than state",
+                    "slot" => state.slot(),
+                    "state_root" => format!("{:?}", state_root),
+                );
+                return Ok(());
+            }
+            diff_bytes
+        };
+
+        metrics::inc_counter_by(
+            &metrics::BEACON_STATE_DIFF_WRITE_BYTES,
+            diff_bytes.len() as u64,
+        );
+        metrics::inc_counter(&metrics::BEACON_STATE_DIFF_WRITE_COUNT);
+        let key = get_key_for_col(DBColumn::BeaconStateDiff.into(), state_root.as_bytes());
+        ops.push(KeyValueStoreOp::PutKeyValue(key, diff_bytes));
+
+        Ok(())
+    }
+
+    /// Load an intermediate state from its diff against the state at its epoch boundary.
+    ///
+    /// Returns `None` if no diff is stored for the state.
+    fn load_hot_state_from_diff(
+        &self,
+        state_root: &Hash256,
+        epoch_boundary_state_root: &Hash256,
+    ) -> Result<Option<BeaconState<E>>, Error> {
+        let diff = match self.hot_db.get::<HotStateDiff>(state_root)? {
+            Some(diff) => diff,
+            None => return Ok(None),
+        };
+        let base_bytes = self
+            .hot_db
+            .get_bytes(
+                DBColumn::BeaconState.into(),
+                epoch_boundary_state_root.as_bytes(),
+            )?
+            .ok_or(HotColdDBError::MissingEpochBoundaryState(
+                *epoch_boundary_state_root,
+            ))?;
+
+        let bytes = diff.apply(&base_bytes)?;
+        let container = StorageContainer::from_ssz_bytes(&bytes, &self.spec)?;
+        metrics::inc_counter(&metrics::BEACON_STATE_DIFF_READ_COUNT);
+
+        Ok(Some(container.try_into()?))
+    }
+
     /// Load a post-finalization state from the hot database.
     ///
-    /// Will replay blocks from the nearest epoch boundary.
+    /// Will load the state from its diff if one is stored, or otherwise replay blocks from the
+    /// nearest epoch boundary.
     pub fn load_hot_state(
         &self,
         state_root: &Hash256,
@@ -657,6 +788,14 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             epoch_boundary_state_root,
         }) = self.load_hot_state_summary(state_root)?
         {
+            if slot % E::slots_per_epoch() != 0 {
+                if let Some(state) =
+                    self.load_hot_state_from_diff(state_root, &epoch_boundary_state_root)?
+                {
+                    return Ok(Some(state));
+                }
+            }
+
             let boundary_state =
                 get_full_state(&self.hot_db, &epoch_boundary_state_root, &self.spec)?.ok_or(
                     HotColdDBError::MissingEpochBoundaryState(epoch_boundary_state_root),
@@ -1223,6 +1362,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         self.config.compact_on_prune
     }
 
+    /// Return the number of epochs of execution payloads to retain, if payload pruning on
+    /// finalization is enabled.
+    pub fn payload_retention_epochs(&self) -> Option<u64> {
+        if self.config.prune_payloads {
+            Some(self.config.payload_retention_epochs)
+        } else {
+            None
+        }
+    }
+
     /// Load the checkpoint to begin pruning from (the "old finalized checkpoint").
     pub fn load_pruning_checkpoint(&self) -> Result<Option<Checkpoint>, Error> {
         Ok(self
@@ -1330,6 +1479,37 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
         hot_db_ops.push(StoreOp::DeleteState(state_root, Some(slot)));
     }
 
+    // 2. Index the roots of the blocks between the split slot and the new frozen head, so that
+    // they can be looked up by slot or by root without iterating the freezer.
+    let block_roots = process_results(BlockRootsIterator::new(&store, frozen_head), |iter| {
+        iter.take_while(|(_, slot)| *slot >= current_split_slot)
+            .collect::<Vec<_>>()
+    })?;
+
+    let mut cold_db_ops: Vec<KeyValueStoreOp> = Vec::new();
+    // Roots are ordered by descending slot and are repeated at skipped slots, so a block's slot
+    // is the lowest slot at which its root appears.
+    for (i, (block_root, slot)) in block_roots.iter().enumerate() {
+        match block_roots.get(i + 1) {
+            Some((next_root, _)) if next_root == block_root => continue,
+            Some(_) => store.store_frozen_block_root_index(*block_root, *slot, &mut cold_db_ops),
+            // The lowest root may belong to a block prior to the old split slot, which will
+            // already have been indexed, or may be unknown if it precedes the oldest stored block.
+            None => {
+                if store.get_frozen_block_slot(block_root)?.is_none() {
+                    if let Some(block) = store.get_block(block_root)? {
+                        store.store_frozen_block_root_index(
+                            *block_root,
+                            block.slot(),
+                            &mut cold_db_ops,
+                        );
+                    }
+                }
+            }
+        }
+    }
+    store.cold_db.do_atomically(cold_db_ops)?;
+
     // Warning: Critical section.  We have to take care not to put any of the two databases in an
     //          inconsistent state if the OS process dies at any point during the freezeing
     //          procedure.
@@ -1483,6 +1663,46 @@ impl StoreItem for ColdStateSummary {
     }
 }
 
+/// Struct for indexing the slot of a frozen block by its root.
+#[derive(Debug, Clone, Copy, Default, Encode, Decode)]
+pub(crate) struct ColdBlockSlot {
+    pub slot: Slot,
+}
+
+impl StoreItem for ColdBlockSlot {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconBlockRootIndex
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+/// Struct for indexing the root of a frozen block by its slot.
+#[derive(Debug, Clone, Copy, Default, Encode, Decode)]
+pub(crate) struct ColdBlockRoot {
+    pub block_root: Hash256,
+}
+
+impl StoreItem for ColdBlockRoot {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconBlockSlotIndex
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
 /// Struct for storing the state root of a restore point in the database.
 #[derive(Debug, Clone, Copy, Default, Encode, Decode)]
 struct RestorePointHash {
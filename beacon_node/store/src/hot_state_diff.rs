@@ -0,0 +1,226 @@
+//! Deltas between the encodings of hot states and their epoch boundary states.
+//!
+//! Only states on epoch boundaries are stored in full in the hot database. A state at an
+//! intermediate slot may optionally be stored as a delta against the encoding of its epoch
+//! boundary state, which allows it to be reconstructed without replaying blocks.
+//!
+//! States at nearby slots share the vast majority of their bytes, but the growth of a
+//! variable-length list (e.g. `eth1_data_votes`) shifts every subsequent byte of the encoding.
+//! Hence a delta is a sequence of copies from arbitrary offsets of the base, interleaved with
+//! literal bytes, rather than a simple XOR of the two encodings.
+use crate::{DBColumn, Error, StoreItem};
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use std::collections::HashMap;
+
+/// The size of the chunks of the base which are indexed when computing a delta.
+///
+/// Smaller chunks find more matches at the cost of a larger index.
+const CHUNK_SIZE: usize = 64;
+
+/// A run of bytes copied from the base, followed by literal bytes.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct DiffRun {
+    copy_offset: u64,
+    copy_len: u64,
+    literal: Vec<u8>,
+}
+
+/// A delta from the encoding of a base state to the encoding of a target state.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct HotStateDiff {
+    target_len: u64,
+    runs: Vec<DiffRun>,
+}
+
+impl StoreItem for HotStateDiff {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconStateDiff
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+impl HotStateDiff {
+    /// Compute the delta which transforms `base` into `target`.
+    pub fn compute(base: &[u8], target: &[u8]) -> Self {
+        // Index the aligned chunks of the base, preferring the first occurrence of each chunk.
+        let mut index = HashMap::with_capacity(base.len() / CHUNK_SIZE);
+        for (i, chunk) in base.chunks_exact(CHUNK_SIZE).enumerate() {
+            index.entry(chunk).or_insert(i * CHUNK_SIZE);
+        }
+
+        let mut runs: Vec<DiffRun> = vec![];
+        let mut literal = vec![];
+        // The offset of the base which would follow on from the previous copy or literal byte.
+        let mut expected_offset = 0;
+        let mut i = 0;
+
+        while i < target.len() {
+            let chunk = target.get(i..i + CHUNK_SIZE);
+            let copy_offset = chunk.and_then(|chunk| {
+                if base.get(expected_offset..expected_offset + CHUNK_SIZE) == Some(chunk) {
+                    Some(expected_offset)
+                } else {
+                    index.get(chunk).copied()
+                }
+            });
+
+            match copy_offset {
+                Some(copy_offset) => {
+                    let copy_len = base[copy_offset..]
+                        .iter()
+                        .zip(&target[i..])
+                        .take_while(|(a, b)| a == b)
+                        .count();
+
+                    // Literal bytes are appended to the previous run, which only requires a new
+                    // run to be created for literal bytes at the start of the target.
+                    match runs.last_mut() {
+                        Some(run) => run.literal.append(&mut literal),
+                        None if !literal.is_empty() => runs.push(DiffRun {
+                            copy_offset: 0,
+                            copy_len: 0,
+                            literal: std::mem::take(&mut literal),
+                        }),
+                        None => (),
+                    }
+                    runs.push(DiffRun {
+                        copy_offset: copy_offset as u64,
+                        copy_len: copy_len as u64,
+                        literal: vec![],
+                    });
+
+                    i += copy_len;
+                    expected_offset = copy_offset + copy_len;
+                }
+                None => {
+                    literal.push(target[i]);
+                    i += 1;
+                    expected_offset += 1;
+                }
+            }
+        }
+
+        match runs.last_mut() {
+            Some(run) => run.literal.append(&mut literal),
+            None if !literal.is_empty() => runs.push(DiffRun {
+                copy_offset: 0,
+                copy_len: 0,
+                literal,
+            }),
+            None => (),
+        }
+
+        Self {
+            target_len: target.len() as u64,
+            runs,
+        }
+    }
+
+    /// Apply the delta to `base`, returning the encoding of the target.
+    pub fn apply(&self, base: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut target = Vec::with_capacity(self.target_len as usize);
+
+        for run in &self.runs {
+            let copy = run
+                .copy_offset
+                .checked_add(run.copy_len)
+                .and_then(|copy_end| base.get(run.copy_offset as usize..copy_end as usize))
+                .ok_or(Error::InvalidHotStateDiff {
+                    base_len: base.len(),
+                    copy_offset: run.copy_offset,
+                    copy_len: run.copy_len,
+                })?;
+            target.extend_from_slice(copy);
+            target.extend_from_slice(&run.literal);
+        }
+
+        if target.len() as u64 != self.target_len {
+            return Err(Error::HotStateDiffLengthMismatch {
+                expected: self.target_len,
+                computed: target.len() as u64,
+            });
+        }
+
+        Ok(target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_roundtrip(base: &[u8], target: &[u8]) -> HotStateDiff {
+        let diff = HotStateDiff::compute(base, target);
+        let decoded = HotStateDiff::from_store_bytes(&diff.as_store_bytes()).unwrap();
+        assert_eq!(decoded, diff);
+        assert_eq!(diff.apply(base).unwrap(), target);
+        diff
+    }
+
+    fn pseudorandom_bytes(len: usize) -> Vec<u8> {
+        (0..len as u64)
+            .map(|i| (i.wrapping_mul(6364136223846793005) >> 33) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn identical() {
+        let base = pseudorandom_bytes(4096);
+        let diff = check_roundtrip(&base, &base);
+        assert_eq!(diff.runs.len(), 1);
+        assert!(diff.runs[0].literal.is_empty());
+    }
+
+    #[test]
+    fn empty() {
+        check_roundtrip(&[], &[]);
+        check_roundtrip(&pseudorandom_bytes(100), &[]);
+        check_roundtrip(&[], &pseudorandom_bytes(100));
+    }
+
+    #[test]
+    fn modified_bytes() {
+        let base = pseudorandom_bytes(4096);
+        let mut target = base.clone();
+        target[0] ^= 1;
+        target[1000] ^= 1;
+        target[4095] ^= 1;
+
+        let diff = check_roundtrip(&base, &target);
+        assert!(diff.as_store_bytes().len() < base.len() / 10);
+    }
+
+    #[test]
+    fn inserted_and_removed_bytes() {
+        let base = pseudorandom_bytes(4096);
+        let mut target = base.clone();
+        target.splice(1000..1000, vec![42; 72]);
+        target.drain(3000..3100);
+        target.extend_from_slice(&[7; 10]);
+
+        let diff = check_roundtrip(&base, &target);
+        assert!(diff.as_store_bytes().len() < base.len() / 10);
+    }
+
+    #[test]
+    fn unrelated() {
+        let base = pseudorandom_bytes(4096);
+        let target = vec![3; 100];
+        check_roundtrip(&base, &target);
+    }
+
+    #[test]
+    fn invalid_base() {
+        let base = pseudorandom_bytes(4096);
+        let diff = HotStateDiff::compute(&base, &base);
+        assert!(diff.apply(&base[..100]).is_err());
+    }
+}
@@ -10,6 +10,7 @@
 #[macro_use]
 extern crate lazy_static;
 
+mod block_root_index;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
@@ -18,12 +19,14 @@ pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
 pub mod hot_cold_store;
+mod hot_state_diff;
 mod impls;
 mod leveldb_store;
 mod memory_store;
 pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
+mod payload_pruning;
 pub mod reconstruct;
 
 pub mod iter;
@@ -151,6 +154,8 @@ pub enum DBColumn {
     /// For data related to the database itself.
     BeaconMeta,
     BeaconBlock,
+    /// For finalized blocks whose execution payloads have been pruned, stored blinded.
+    BeaconBlindedBlock,
     BeaconState,
     /// For persisting in-memory state to the database.
     BeaconChain,
@@ -162,14 +167,22 @@ pub enum DBColumn {
     BeaconRestorePoint,
     /// For the mapping from state roots to their slots or summaries.
     BeaconStateSummary,
+    /// For the deltas of intermediate hot states against their epoch boundary states.
+    BeaconStateDiff,
     /// For the list of temporary states stored during block import,
     /// and then made non-temporary by the deletion of their state root from this column.
     BeaconStateTemporary,
+    /// For the mapping from frozen block roots to their slots.
+    BeaconBlockRootIndex,
+    /// For the mapping from frozen slots to the roots of the blocks at those slots.
+    BeaconBlockSlotIndex,
     BeaconBlockRoots,
     BeaconStateRoots,
     BeaconHistoricalRoots,
     BeaconRandaoMixes,
     DhtEnrs,
+    /// For the best light client update of each sync committee period, keyed by period.
+    LightClientUpdate,
 }
 
 impl Into<&'static str> for DBColumn {
@@ -178,6 +191,7 @@ impl Into<&'static str> for DBColumn {
         match self {
             DBColumn::BeaconMeta => "bma",
             DBColumn::BeaconBlock => "blk",
+            DBColumn::BeaconBlindedBlock => "bbl",
             DBColumn::BeaconState => "ste",
             DBColumn::BeaconChain => "bch",
             DBColumn::OpPool => "opo",
@@ -186,12 +200,16 @@ impl Into<&'static str> for DBColumn {
             DBColumn::PubkeyCache => "pkc",
             DBColumn::BeaconRestorePoint => "brp",
             DBColumn::BeaconStateSummary => "bss",
+            DBColumn::BeaconStateDiff => "bsd",
             DBColumn::BeaconStateTemporary => "bst",
+            DBColumn::BeaconBlockRootIndex => "bri",
+            DBColumn::BeaconBlockSlotIndex => "bsi",
             DBColumn::BeaconBlockRoots => "bbr",
             DBColumn::BeaconStateRoots => "bsr",
             DBColumn::BeaconHistoricalRoots => "bhr",
             DBColumn::BeaconRandaoMixes => "brm",
             DBColumn::DhtEnrs => "dht",
+            DBColumn::LightClientUpdate => "lcu",
         }
     }
 }
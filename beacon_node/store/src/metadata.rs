@@ -4,7 +4,7 @@ use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use types::{Checkpoint, Hash256, Slot};
 
-pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(8);
+pub const CURRENT_SCHEMA_VERSION: SchemaVersion = SchemaVersion(9);
 
 // All the keys that get stored under the `BeaconMeta` column.
 //
@@ -15,6 +15,8 @@ pub const SPLIT_KEY: Hash256 = Hash256::repeat_byte(2);
 pub const PRUNING_CHECKPOINT_KEY: Hash256 = Hash256::repeat_byte(3);
 pub const COMPACTION_TIMESTAMP_KEY: Hash256 = Hash256::repeat_byte(4);
 pub const ANCHOR_INFO_KEY: Hash256 = Hash256::repeat_byte(5);
+pub const PAYLOAD_PRUNING_CHECKPOINT_KEY: Hash256 = Hash256::repeat_byte(6);
+pub const BACKFILL_PAUSED_KEY: Hash256 = Hash256::repeat_byte(7);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SchemaVersion(pub u64);
@@ -80,6 +82,50 @@ impl StoreItem for CompactionTimestamp {
     }
 }
 
+/// The progress of execution payload pruning.
+///
+/// The payloads of all canonical blocks prior to `slot` have been pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadPruningCheckpoint {
+    pub slot: Slot,
+}
+
+impl StoreItem for PayloadPruningCheckpoint {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.slot.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(PayloadPruningCheckpoint {
+            slot: Slot::from_ssz_bytes(bytes)?,
+        })
+    }
+}
+
+/// Whether the backfill sync has been paused by the user.
+///
+/// Persisted so that a paused backfill sync remains paused after a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackfillPaused(pub bool);
+
+impl StoreItem for BackfillPaused {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconMeta
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.0.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(BackfillPaused(bool::from_ssz_bytes(bytes)?))
+    }
+}
+
 /// Database parameters relevant to weak subjectivity sync.
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Serialize, Deserialize)]
 pub struct AnchorInfo {
@@ -90,6 +90,22 @@ lazy_static! {
         "store_beacon_state_write_bytes_total",
         "Total number of beacon state bytes written to the DB"
     );
+    pub static ref BEACON_STATE_DIFF_COMPUTE_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_state_diff_compute_seconds",
+        "Time taken to compute the delta of a hot state against its epoch boundary state"
+    );
+    pub static ref BEACON_STATE_DIFF_WRITE_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_state_diff_write_total",
+        "Total number of hot state deltas written to the DB"
+    );
+    pub static ref BEACON_STATE_DIFF_WRITE_BYTES: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_state_diff_write_bytes_total",
+        "Total number of hot state delta bytes written to the DB"
+    );
+    pub static ref BEACON_STATE_DIFF_READ_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_state_diff_read_total",
+        "Total number of hot states reconstructed from deltas rather than by replaying blocks"
+    );
     /*
      * Beacon Block
      */
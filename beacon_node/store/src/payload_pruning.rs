@@ -0,0 +1,233 @@
+//! Pruning of the execution payloads of finalized blocks.
+//!
+//! Execution payloads make up the bulk of post-merge blocks, and are also stored by the execution
+//! engine. Once a canonical block is finalized and older than the retention period, it is
+//! replaced by a blinded block which keeps the payload header, so the block's root and signature
+//! can still be verified. The full block can no longer be served to peers or the HTTP API.
+use crate::hot_cold_store::HotColdDB;
+use crate::metadata::{PayloadPruningCheckpoint, PAYLOAD_PRUNING_CHECKPOINT_KEY};
+use crate::{get_key_for_col, DBColumn, Error, ItemStore, KeyValueStoreOp, StoreItem};
+use slog::{debug, info};
+use ssz::{Decode, Encode};
+use std::cmp::max;
+use types::{
+    BlindedBeaconBlock, EthSpec, ExecutionPayloadHeader, Hash256, SignedBeaconBlock,
+    SignedBlindedBeaconBlock, Slot,
+};
+
+/// The maximum number of blocks to re-write in a single batch.
+const PRUNING_BATCH_SIZE: usize = 256;
+
+impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold> {
+    /// Returns `true` if `block_root` is stored blinded, with its execution payload pruned.
+    pub fn payload_pruned(&self, block_root: &Hash256) -> Result<bool, Error> {
+        self.hot_db
+            .key_exists(DBColumn::BeaconBlindedBlock.into(), block_root.as_bytes())
+    }
+
+    /// Fetch a block whose execution payload has been pruned.
+    ///
+    /// Returns `None` if the block is unknown, or if it is stored in full.
+    pub fn get_blinded_block(
+        &self,
+        block_root: &Hash256,
+    ) -> Result<Option<SignedBlindedBeaconBlock<E>>, Error> {
+        self.hot_db
+            .get_bytes(DBColumn::BeaconBlindedBlock.into(), block_root.as_bytes())?
+            .map(|bytes| SignedBlindedBeaconBlock::from_ssz_bytes(&bytes))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Load the slot prior to which all canonical execution payloads have been pruned.
+    pub fn load_payload_pruning_checkpoint(&self) -> Result<Option<Slot>, Error> {
+        Ok(self
+            .hot_db
+            .get(&PAYLOAD_PRUNING_CHECKPOINT_KEY)?
+            .map(|c: PayloadPruningCheckpoint| c.slot))
+    }
+
+    /// Prune the execution payloads of canonical blocks more than `retention_epochs` prior to the
+    /// split slot, returning the number of blocks pruned.
+    ///
+    /// Progress is recorded as each batch is written, so pruning may be interrupted and resumed,
+    /// and repeated calls only visit the blocks finalized since the last call.
+    pub fn prune_payloads(&self, retention_epochs: u64) -> Result<usize, Error> {
+        let bellatrix_fork_slot = match self.spec.bellatrix_fork_epoch {
+            Some(epoch) => epoch.start_slot(E::slots_per_epoch()),
+            // There are no payloads to prune.
+            None => return Ok(0),
+        };
+        let start_slot = max(
+            max(bellatrix_fork_slot, self.get_oldest_block_slot()),
+            self.load_payload_pruning_checkpoint()?
+                .unwrap_or(bellatrix_fork_slot),
+        );
+        let end_slot = self
+            .get_split_slot()
+            .saturating_sub(retention_epochs.saturating_mul(E::slots_per_epoch()));
+
+        if start_slot >= end_slot {
+            return Ok(0);
+        }
+
+        debug!(
+            self.log,
+            "Pruning execution payloads";
+            "start_slot" => start_slot,
+            "end_slot" => end_slot,
+        );
+
+        let mut num_pruned = 0;
+        let mut pruned_roots = vec![];
+        let mut ops = vec![];
+
+        for slot in (start_slot.as_u64()..end_slot.as_u64()).map(Slot::new) {
+            if let Some(block_root) = self.get_frozen_block_root(slot)? {
+                if let Some(pruned_root) = self.blind_block_ops(&block_root, &mut ops)? {
+                    pruned_roots.push(pruned_root);
+                }
+            }
+
+            if pruned_roots.len() >= PRUNING_BATCH_SIZE || slot + 1 == end_slot {
+                ops.push(
+                    PayloadPruningCheckpoint { slot: slot + 1 }
+                        .as_kv_store_op(PAYLOAD_PRUNING_CHECKPOINT_KEY),
+                );
+                self.hot_db.do_atomically(std::mem::take(&mut ops))?;
+
+                let mut block_cache = self.block_cache.lock();
+                for block_root in pruned_roots.drain(..) {
+                    block_cache.pop(&block_root);
+                    num_pruned += 1;
+                }
+            }
+        }
+
+        if num_pruned > 0 {
+            info!(
+                self.log,
+                "Pruned execution payloads";
+                "blocks" => num_pruned,
+                "end_slot" => end_slot,
+            );
+        }
+
+        Ok(num_pruned)
+    }
+
+    /// Stage the ops to replace the full block at `block_root` with its blinded equivalent.
+    ///
+    /// Returns `None` if the block has no execution payload or is not stored in full.
+    fn blind_block_ops(
+        &self,
+        block_root: &Hash256,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<Option<Hash256>, Error> {
+        let bytes = match self
+            .hot_db
+            .get_bytes(DBColumn::BeaconBlock.into(), block_root.as_bytes())?
+        {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+
+        let block = match SignedBeaconBlock::from_ssz_bytes(&bytes, &self.spec)? {
+            SignedBeaconBlock::Merge(block) => block,
+            SignedBeaconBlock::Base(_) | SignedBeaconBlock::Altair(_) => return Ok(None),
+        };
+        let header = ExecutionPayloadHeader::from(&block.message.body.execution_payload);
+        let blinded_block = SignedBlindedBeaconBlock {
+            message: BlindedBeaconBlock::from_block(block.message, header),
+            signature: block.signature,
+        };
+
+        ops.push(KeyValueStoreOp::PutKeyValue(
+            get_key_for_col(DBColumn::BeaconBlindedBlock.into(), block_root.as_bytes()),
+            blinded_block.as_ssz_bytes(),
+        ));
+        ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+            DBColumn::BeaconBlock.into(),
+            block_root.as_bytes(),
+        )));
+
+        Ok(Some(*block_root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HotColdDB, MemoryStore, StoreConfig};
+    use sloggers::{null::NullLoggerBuilder, Build};
+    use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use types::{BeaconBlock, BeaconBlockMerge, Epoch, MinimalEthSpec, Signature};
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn prune_payloads() {
+        let mut spec = E::default_spec();
+        spec.altair_fork_epoch = Some(Epoch::new(0));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+        let log = NullLoggerBuilder.build().unwrap();
+        let store = HotColdDB::<E, MemoryStore<E>, MemoryStore<E>>::open_ephemeral(
+            StoreConfig::default(),
+            spec,
+            log,
+        )
+        .unwrap();
+
+        // One block per epoch, for 4 epochs.
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let blocks = (0..4)
+            .map(|epoch| {
+                let mut block = BeaconBlockMerge::<E>::random_for_test(rng);
+                block.slot = Epoch::new(epoch).start_slot(E::slots_per_epoch());
+                SignedBeaconBlock::from_block(BeaconBlock::Merge(block), Signature::empty())
+            })
+            .collect::<Vec<_>>();
+        let mut ops = vec![];
+        for block in &blocks {
+            let block_root = block.canonical_root();
+            store.put_block(&block_root, block.clone()).unwrap();
+            store.store_frozen_block_root_index(block_root, block.slot(), &mut ops);
+        }
+        store.cold_db.do_atomically(ops).unwrap();
+        store.set_split(
+            Epoch::new(4).start_slot(E::slots_per_epoch()),
+            Hash256::zero(),
+        );
+
+        // Keep the payloads of the last epoch before the split.
+        assert_eq!(store.prune_payloads(1).unwrap(), 3);
+        assert_eq!(
+            store.load_payload_pruning_checkpoint().unwrap(),
+            Some(Epoch::new(3).start_slot(E::slots_per_epoch()))
+        );
+        // Pruning again does nothing.
+        assert_eq!(store.prune_payloads(1).unwrap(), 0);
+
+        for block in &blocks[..3] {
+            let block_root = block.canonical_root();
+            assert!(matches!(
+                store.get_block(&block_root),
+                Err(Error::ExecutionPayloadPruned(root)) if root == block_root
+            ));
+            assert!(store.block_exists(&block_root).unwrap());
+
+            let blinded_block = store.get_blinded_block(&block_root).unwrap().unwrap();
+            assert_eq!(
+                blinded_block.message.block_header().canonical_root(),
+                block_root
+            );
+        }
+
+        let block_root = blocks[3].canonical_root();
+        assert_eq!(
+            store.get_block(&block_root).unwrap().as_ref(),
+            Some(&blocks[3])
+        );
+        assert_eq!(store.get_blinded_block(&block_root).unwrap(), None);
+    }
+}
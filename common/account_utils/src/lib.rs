@@ -96,6 +96,16 @@ pub fn random_password() -> PlainText {
         .into()
 }
 
+/// Generates a random alphanumeric password of length `DEFAULT_PASSWORD_LEN`.
+pub fn random_password_string() -> ZeroizeString {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(DEFAULT_PASSWORD_LEN)
+        .map(char::from)
+        .collect::<String>()
+        .into()
+}
+
 /// Remove any number of newline or carriage returns from the end of a vector of bytes.
 pub fn strip_off_newlines(mut bytes: Vec<u8>) -> Vec<u8> {
     let mut strip_off = 0;
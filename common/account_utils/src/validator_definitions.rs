@@ -13,7 +13,7 @@ use std::collections::HashSet;
 use std::fs::{self, OpenOptions};
 use std::io;
 use std::path::{Path, PathBuf};
-use types::{graffiti::GraffitiString, PublicKey};
+use types::{graffiti::GraffitiString, Address, PublicKey};
 use validator_dir::VOTING_KEYSTORE_FILE;
 
 /// The file name for the serialized `ValidatorDefinitions` struct.
@@ -75,6 +75,15 @@ pub enum SigningDefinition {
         /// The timeout is applied from when the request starts connecting until the response body has finished.
         #[serde(skip_serializing_if = "Option::is_none")]
         request_timeout_ms: Option<u64>,
+        /// Path to a PKCS12 file containing the client certificate and private key used to
+        /// authenticate to the Web3Signer server (i.e., TLS client authentication).
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        client_identity_path: Option<PathBuf>,
+        /// The password of the PKCS12 file at `client_identity_path`.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        client_identity_password: Option<String>,
     },
 }
 
@@ -89,6 +98,19 @@ pub struct ValidatorDefinition {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub graffiti: Option<GraffitiString>,
+    /// The fee recipient to suggest for blocks proposed by this validator.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_fee_recipient: Option<Address>,
+    /// The gas limit to request for blocks proposed by this validator.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    /// Whether to propose blocks with payloads from an external builder, overriding the validator
+    /// client's `--builder-proposals` flag.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_proposals: Option<bool>,
     #[serde(default)]
     pub description: String,
     #[serde(flatten)]
@@ -117,6 +139,9 @@ impl ValidatorDefinition {
             voting_public_key,
             description: keystore.description().unwrap_or("").to_string(),
             graffiti,
+            suggested_fee_recipient: None,
+            gas_limit: None,
+            builder_proposals: None,
             signing_definition: SigningDefinition::LocalKeystore {
                 voting_keystore_path,
                 voting_keystore_password_path: None,
@@ -262,6 +287,9 @@ impl ValidatorDefinitions {
                     voting_public_key,
                     description: keystore.description().unwrap_or("").to_string(),
                     graffiti: None,
+                    suggested_fee_recipient: None,
+                    gas_limit: None,
+                    builder_proposals: None,
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path,
                         voting_keystore_password_path,
@@ -298,6 +326,11 @@ impl ValidatorDefinitions {
         self.0.push(def)
     }
 
+    /// Removes all `ValidatorDefinition` in `self` for which `f` returns `false`.
+    pub fn retain<F: FnMut(&ValidatorDefinition) -> bool>(&mut self, f: F) {
+        self.0.retain(f)
+    }
+
     /// Returns a slice of all `ValidatorDefinition` in `self`.
     pub fn as_slice(&self) -> &[ValidatorDefinition] {
         self.0.as_slice()
@@ -458,4 +491,102 @@ mod tests {
             Some(GraffitiString::from_str("mrfwashere").unwrap())
         );
     }
+
+    #[test]
+    fn fee_recipient_and_gas_limit_checks() {
+        let neither = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(neither).unwrap();
+        assert!(def.suggested_fee_recipient.is_none());
+        assert!(def.gas_limit.is_none());
+
+        let both = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        suggested_fee_recipient: "0xa2e334e71511686bcfe38bb3ee1ad8f6254d79c4"
+        gas_limit: 30000000
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(both).unwrap();
+        assert_eq!(
+            def.suggested_fee_recipient,
+            Some(Address::from_str("0xa2e334e71511686bcfe38bb3ee1ad8f6254d79c4").unwrap())
+        );
+        assert_eq!(def.gas_limit, Some(30_000_000));
+        assert!(def.builder_proposals.is_none());
+
+        let builder_proposals = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        builder_proposals: false
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(builder_proposals).unwrap();
+        assert_eq!(def.builder_proposals, Some(false));
+
+        let invalid_fee_recipient = r#"---
+        description: ""
+        enabled: true
+        type: local_keystore
+        suggested_fee_recipient: "0xa2e334"
+        voting_keystore_path: ""
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: Result<ValidatorDefinition, _> = serde_yaml::from_str(invalid_fee_recipient);
+        assert!(def.is_err());
+    }
+
+    #[test]
+    fn web3signer_client_identity_checks() {
+        let without_identity = r#"---
+        description: ""
+        enabled: true
+        type: web3signer
+        url: "https://my-remote-signer.com:1234"
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(without_identity).unwrap();
+        assert!(matches!(
+            def.signing_definition,
+            SigningDefinition::Web3Signer {
+                client_identity_path: None,
+                client_identity_password: None,
+                ..
+            }
+        ));
+
+        let with_identity = r#"---
+        description: ""
+        enabled: true
+        type: web3signer
+        url: "https://my-remote-signer.com:1234"
+        client_identity_path: "/home/me/identity.p12"
+        client_identity_password: "meow"
+        voting_public_key: "0xaf3c7ddab7e293834710fca2d39d068f884455ede270e0d0293dc818e4f2f0f975355067e8437955cb29aec674e5c9e7"
+        "#;
+        let def: ValidatorDefinition = serde_yaml::from_str(with_identity).unwrap();
+        match def.signing_definition {
+            SigningDefinition::Web3Signer {
+                client_identity_path,
+                client_identity_password,
+                ..
+            } => {
+                assert_eq!(
+                    client_identity_path,
+                    Some(PathBuf::from("/home/me/identity.p12"))
+                );
+                assert_eq!(client_identity_password, Some("meow".to_string()));
+            }
+            SigningDefinition::LocalKeystore { .. } => panic!("expected a web3signer validator"),
+        }
+    }
 }
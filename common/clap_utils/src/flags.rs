@@ -1,3 +1,4 @@
 //! CLI flags used across the Lighthouse code base can be located here.
 
 pub const DISABLE_MALLOC_TUNING_FLAG: &str = "disable-malloc-tuning";
+pub const MALLOC_ARENA_MAX_FLAG: &str = "malloc-arena-max";
@@ -29,6 +29,17 @@ pub fn get_eth2_network_config(cli_args: &ArgMatches) -> Result<Eth2NetworkConfi
     let mut eth2_network_config =
         optional_network_config.ok_or_else(|| BAD_TESTNET_DIR_MESSAGE.to_string())?;
 
+    apply_terminal_overrides(&mut eth2_network_config, cli_args)?;
+
+    Ok(eth2_network_config)
+}
+
+/// Apply the `terminal-total-difficulty-override`, `terminal-block-hash-override` and
+/// `terminal-block-hash-epoch-override` flags to the config of `eth2_network_config`.
+pub fn apply_terminal_overrides(
+    eth2_network_config: &mut Eth2NetworkConfig,
+    cli_args: &ArgMatches,
+) -> Result<(), String> {
     if let Some(string) = parse_optional::<String>(cli_args, "terminal-total-difficulty-override")?
     {
         let stripped = string.replace(",", "");
@@ -52,7 +63,7 @@ pub fn get_eth2_network_config(cli_args: &ArgMatches) -> Result<Eth2NetworkConfi
             .terminal_block_hash_activation_epoch = epoch;
     }
 
-    Ok(eth2_network_config)
+    Ok(())
 }
 
 /// Attempts to load the testnet dir at the path if `name` is in `matches`, returning an error if
@@ -157,3 +168,78 @@ pub fn parse_ssz_optional<T: Decode>(
         })
         .transpose()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{App, Arg};
+
+    const HASH: &str = "0x4242424242424242424242424242424242424242424242424242424242424242";
+
+    fn matches(args: &[&'static str]) -> ArgMatches<'static> {
+        let flags = [
+            "terminal-total-difficulty-override",
+            "terminal-block-hash-override",
+            "terminal-block-hash-epoch-override",
+        ];
+        App::new("test")
+            .args(
+                &flags
+                    .iter()
+                    .map(|flag| Arg::with_name(flag).long(flag).takes_value(true))
+                    .collect::<Vec<_>>(),
+            )
+            .get_matches_from(std::iter::once("test").chain(args.iter().copied()))
+    }
+
+    fn network_config() -> Eth2NetworkConfig {
+        Eth2NetworkConfig::constant(DEFAULT_HARDCODED_NETWORK)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn terminal_overrides_absent() {
+        let mut config = network_config();
+        apply_terminal_overrides(&mut config, &matches(&[])).unwrap();
+        assert_eq!(config.config, network_config().config);
+    }
+
+    #[test]
+    fn terminal_overrides_applied() {
+        let mut config = network_config();
+        apply_terminal_overrides(
+            &mut config,
+            &matches(&[
+                "--terminal-total-difficulty-override",
+                "1,000,000",
+                "--terminal-block-hash-override",
+                HASH,
+                "--terminal-block-hash-epoch-override",
+                "42",
+            ]),
+        )
+        .unwrap();
+        assert_eq!(
+            config.config.terminal_total_difficulty,
+            Uint256::from(1_000_000)
+        );
+        assert_eq!(config.config.terminal_block_hash, HASH.parse().unwrap());
+        assert_eq!(config.config.terminal_block_hash_activation_epoch, 42);
+    }
+
+    #[test]
+    fn terminal_overrides_invalid() {
+        let mut config = network_config();
+        assert!(apply_terminal_overrides(
+            &mut config,
+            &matches(&["--terminal-total-difficulty-override", "0x10"])
+        )
+        .is_err());
+        assert!(apply_terminal_overrides(
+            &mut config,
+            &matches(&["--terminal-block-hash-override", "42"])
+        )
+        .is_err());
+    }
+}
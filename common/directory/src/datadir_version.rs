@@ -0,0 +1,178 @@
+//! Versioning of the layout of the data directory.
+//!
+//! The layout version is stored in `DATADIR_VERSION_FILENAME` at the root of the data directory
+//! (e.g. `~/.lighthouse/mainnet`). Data directories created before the file was introduced are
+//! version 0. When the layout changes, increment `CURRENT_DATADIR_VERSION` and add a `Migration`
+//! to `MIGRATIONS` which moves files from the old layout to the new one.
+//!
+//! The schema of the beacon node's database is versioned separately, and is migrated by the beacon
+//! node when it opens the database.
+use std::fs;
+use std::path::Path;
+
+pub const DATADIR_VERSION_FILENAME: &str = "datadir_version";
+
+/// The layout version written by this release.
+pub const CURRENT_DATADIR_VERSION: DatadirVersion = DatadirVersion(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DatadirVersion(pub u64);
+
+/// Changes the layout of a data directory from version `from` to version `from + 1`, and back.
+struct Migration {
+    from: DatadirVersion,
+    description: &'static str,
+    upgrade: fn(&Path) -> Result<(), String>,
+    /// `None` if the migration cannot be safely reversed.
+    downgrade: Option<fn(&Path) -> Result<(), String>>,
+}
+
+/// All migrations, in order of the version they migrate from.
+const MIGRATIONS: &[Migration] = &[Migration {
+    from: DatadirVersion(0),
+    description: "add the datadir version file",
+    upgrade: no_op,
+    downgrade: Some(no_op),
+}];
+
+fn no_op(_: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+/// Returns the layout version of the data directory at `datadir`.
+pub fn read_datadir_version(datadir: &Path) -> Result<DatadirVersion, String> {
+    let path = datadir.join(DATADIR_VERSION_FILENAME);
+    if !path.exists() {
+        return Ok(DatadirVersion(0));
+    }
+    let contents =
+        fs::read_to_string(&path).map_err(|e| format!("Unable to read {:?}: {:?}", path, e))?;
+    contents
+        .trim()
+        .parse()
+        .map(DatadirVersion)
+        .map_err(|e| format!("Invalid datadir version in {:?}: {:?}", path, e))
+}
+
+fn write_datadir_version(datadir: &Path, version: DatadirVersion) -> Result<(), String> {
+    let path = datadir.join(DATADIR_VERSION_FILENAME);
+    fs::write(&path, format!("{}\n", version.0))
+        .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+}
+
+/// Upgrade the data directory at `datadir` to `CURRENT_DATADIR_VERSION`, creating it if it doesn't
+/// exist, and returning a description of each migration applied.
+///
+/// Returns an error if the data directory has been used by a newer release, since this release
+/// doesn't know how its layout has changed.
+pub fn upgrade_datadir(datadir: &Path) -> Result<Vec<&'static str>, String> {
+    if !datadir.exists() {
+        fs::create_dir_all(datadir)
+            .map_err(|e| format!("Unable to create {:?}: {:?}", datadir, e))?;
+        write_datadir_version(datadir, CURRENT_DATADIR_VERSION)?;
+        return Ok(vec![]);
+    }
+    migrate_datadir(datadir, CURRENT_DATADIR_VERSION)
+}
+
+/// Migrate the data directory at `datadir` from its current layout version to `to`, one version
+/// at a time, returning a description of each migration applied.
+///
+/// The version file is updated after each migration, so that a failed migration can be resumed.
+pub fn migrate_datadir(datadir: &Path, to: DatadirVersion) -> Result<Vec<&'static str>, String> {
+    let from = read_datadir_version(datadir)?;
+    if from > CURRENT_DATADIR_VERSION {
+        return Err(format!(
+            "The datadir {:?} has layout version {}, which was written by a newer release of \
+             Lighthouse. This release only supports layout versions up to {}. Downgrading is \
+             unsafe, please use a newer release.",
+            datadir, from.0, CURRENT_DATADIR_VERSION.0
+        ));
+    }
+    if to > CURRENT_DATADIR_VERSION {
+        return Err(format!(
+            "Unknown datadir layout version {}, the latest is {}",
+            to.0, CURRENT_DATADIR_VERSION.0
+        ));
+    }
+
+    let migration = |version: DatadirVersion| {
+        MIGRATIONS
+            .iter()
+            .find(|migration| migration.from == version)
+            .ok_or_else(|| format!("No datadir migration from layout version {}", version.0))
+    };
+
+    let mut applied = vec![];
+    let mut version = from;
+    while version < to {
+        let migration = migration(version)?;
+        (migration.upgrade)(datadir)
+            .map_err(|e| format!("Failed to {}: {}", migration.description, e))?;
+        version = DatadirVersion(version.0 + 1);
+        write_datadir_version(datadir, version)?;
+        applied.push(migration.description);
+    }
+    while version > to {
+        let migration = migration(DatadirVersion(version.0 - 1))?;
+        let downgrade = migration.downgrade.ok_or_else(|| {
+            format!(
+                "Unable to downgrade the datadir {:?} from layout version {} to {}, since it is \
+                 unsafe to reverse the migration to {}",
+                datadir, version.0, to.0, migration.description
+            )
+        })?;
+        downgrade(datadir)
+            .map_err(|e| format!("Failed to reverse {}: {}", migration.description, e))?;
+        version = migration.from;
+        write_datadir_version(datadir, version)?;
+        applied.push(migration.description);
+    }
+
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn new_datadir_is_current() {
+        let dir = tempdir().unwrap();
+        let datadir = dir.path().join("mainnet");
+
+        assert!(upgrade_datadir(&datadir).unwrap().is_empty());
+        assert_eq!(
+            read_datadir_version(&datadir).unwrap(),
+            CURRENT_DATADIR_VERSION
+        );
+    }
+
+    #[test]
+    fn upgrade_and_downgrade() {
+        let dir = tempdir().unwrap();
+        let datadir = dir.path();
+        assert_eq!(read_datadir_version(datadir).unwrap(), DatadirVersion(0));
+
+        assert_eq!(upgrade_datadir(datadir).unwrap().len(), MIGRATIONS.len());
+        assert_eq!(
+            read_datadir_version(datadir).unwrap(),
+            CURRENT_DATADIR_VERSION
+        );
+        assert!(upgrade_datadir(datadir).unwrap().is_empty());
+
+        migrate_datadir(datadir, DatadirVersion(0)).unwrap();
+        assert_eq!(read_datadir_version(datadir).unwrap(), DatadirVersion(0));
+    }
+
+    #[test]
+    fn newer_datadir_is_rejected() {
+        let dir = tempdir().unwrap();
+        let datadir = dir.path();
+        write_datadir_version(datadir, DatadirVersion(CURRENT_DATADIR_VERSION.0 + 1)).unwrap();
+
+        assert!(upgrade_datadir(datadir).is_err());
+        assert!(migrate_datadir(datadir, DatadirVersion(0)).is_err());
+    }
+}
@@ -1,4 +1,10 @@
+mod datadir_version;
+
 use clap::ArgMatches;
+pub use datadir_version::{
+    migrate_datadir, read_datadir_version, upgrade_datadir, DatadirVersion,
+    CURRENT_DATADIR_VERSION, DATADIR_VERSION_FILENAME,
+};
 pub use eth2_network_config::DEFAULT_HARDCODED_NETWORK;
 use std::fs::{self, create_dir_all};
 use std::path::{Path, PathBuf};
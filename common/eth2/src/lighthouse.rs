@@ -2,7 +2,10 @@
 
 use crate::{
     ok_or_error,
-    types::{BeaconState, ChainSpec, Epoch, EthSpec, GenericResponse, ValidatorId},
+    types::{
+        Attestation, BeaconState, BitList, ChainSpec, Epoch, EthSpec, GenericResponse, PeerCount,
+        PublicKeyBytes, Slot, ValidatorId,
+    },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, StateId, StatusCode,
 };
 use proto_array::core::ProtoArray;
@@ -10,6 +13,8 @@ use reqwest::IntoUrl;
 use serde::{Deserialize, Serialize};
 use ssz::four_byte_option_impl;
 use ssz_derive::{Decode, Encode};
+use std::collections::HashMap;
+use std::path::Path;
 use store::{AnchorInfo, Split};
 
 pub use lighthouse_network::{types::SyncState, PeerInfo};
@@ -28,6 +33,9 @@ pub struct Peer<T: EthSpec> {
     pub peer_id: String,
     /// The PeerInfo associated with the peer.
     pub peer_info: PeerInfo<T>,
+    /// The share of the gossip messages of each kind which this peer was the first to deliver,
+    /// out of all those first delivered by known peers.
+    pub first_message_delivery_share: HashMap<String, f64>,
 }
 
 /// The results of validators voting during an epoch.
@@ -225,6 +233,8 @@ pub struct ProcessHealth {
     pub pid_mem_virtual_memory_size: u64,
     /// Number of cpu seconds consumed by this pid.
     pub pid_process_seconds_total: u64,
+    /// The number of file descriptors currently open by this pid.
+    pub pid_num_open_fds: u64,
 }
 
 impl ProcessHealth {
@@ -247,6 +257,10 @@ impl ProcessHealth {
             .cpu_times()
             .map_err(|e| format!("Unable to get process cpu times : {:?}", e))?;
 
+        let num_open_fds = std::fs::read_dir("/proc/self/fd")
+            .map_err(|e| format!("Unable to get open file descriptors: {:?}", e))?
+            .count() as u64;
+
         Ok(Self {
             pid: process.pid(),
             pid_num_threads: stat.num_threads,
@@ -255,6 +269,34 @@ impl ProcessHealth {
             pid_process_seconds_total: process_times.busy().as_secs()
                 + process_times.children_system().as_secs()
                 + process_times.children_system().as_secs(),
+            pid_num_open_fds: num_open_fds,
+        })
+    }
+}
+
+/// Disk usage of the volume containing a particular directory, such as the data directory.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiskHealth {
+    /// Total capacity of the volume.
+    pub disk_bytes_total: u64,
+    /// Space on the volume available to this process.
+    pub disk_bytes_free: u64,
+}
+
+impl DiskHealth {
+    #[cfg(not(target_os = "linux"))]
+    pub fn observe(_path: &Path) -> Result<Self, String> {
+        Err("Health is only available on Linux".into())
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn observe(path: &Path) -> Result<Self, String> {
+        let disk_usage = psutil::disk::disk_usage(path)
+            .map_err(|e| format!("Unable to get disk usage of {:?}: {:?}", path, e))?;
+
+        Ok(Self {
+            disk_bytes_total: disk_usage.total(),
+            disk_bytes_free: disk_usage.free(),
         })
     }
 }
@@ -327,6 +369,246 @@ pub struct DatabaseInfo {
     pub anchor: Option<AnchorInfo>,
 }
 
+/// The health of the node, aggregated into a single response for user interfaces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiHealth {
+    /// The health of the host and process, or `None` if it cannot be observed on this platform.
+    pub health: Option<Health>,
+    pub sync_state: SyncState,
+    pub head_slot: Slot,
+    pub sync_distance: Slot,
+    pub finalized_epoch: Epoch,
+    pub peers: PeerCount,
+    /// The number of validators registered with the validator monitor.
+    pub monitored_validators: u64,
+}
+
+/// The readiness of the node to serve requests, as used by a readiness probe.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Readiness {
+    /// `true` if each of the other fields is satisfied.
+    pub ready: bool,
+    pub synced: bool,
+    /// Whether any execution engine is reachable, or `None` if no execution engine is configured.
+    pub execution_layer_online: Option<bool>,
+    pub connected_peers: usize,
+    /// The number of connected peers required to be ready.
+    pub min_peers: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMetricsRequestData {
+    #[serde(with = "eth2_serde_utils::quoted_u64_vec")]
+    pub indices: Vec<u64>,
+}
+
+/// The level at which a module and its submodules are logged, in place of the configured level.
+///
+/// The empty module applies to all modules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogLevelOverride {
+    pub module: String,
+    pub level: String,
+}
+
+/// A request to override the log level of a module and its submodules, or of all modules if
+/// `module` is empty. A `level` of `None` restores the configured level.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogLevelRequest {
+    #[serde(default)]
+    pub module: String,
+    pub level: Option<String>,
+}
+
+/// The on-chain attestation performance of a validator since the validator monitor began to
+/// monitor it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMetrics {
+    pub attestation_hits: u64,
+    pub attestation_misses: u64,
+    pub attestation_hit_percentage: f64,
+    pub attestation_head_hits: u64,
+    pub attestation_head_misses: u64,
+    pub attestation_head_hit_percentage: f64,
+    pub attestation_target_hits: u64,
+    pub attestation_target_misses: u64,
+    pub attestation_target_hit_percentage: f64,
+    pub latest_attestation_inclusion_distance: Option<u64>,
+}
+
+/// The metrics of each requested validator which is known to the validator monitor, keyed by
+/// validator index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorMetricsResponseData {
+    pub validators: HashMap<String, ValidatorMetrics>,
+}
+
+/// The contents of the operation pool, and whether the slashings and exits within it would be
+/// included in a block produced upon the head.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationPoolSummary {
+    pub sizes: OperationPoolSizes,
+    pub proposer_slashings: Vec<OperationForecast>,
+    pub attester_slashings: Vec<OperationForecast>,
+    pub voluntary_exits: Vec<OperationForecast>,
+    pub pruned: OperationPoolPruneStats,
+}
+
+/// The number of operations of each kind in the operation pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationPoolSizes {
+    pub attestations: usize,
+    pub sync_contributions: usize,
+    pub attester_slashings: usize,
+    pub proposer_slashings: usize,
+    pub voluntary_exits: usize,
+}
+
+/// Whether an operation in the pool would be included in a block produced upon the head.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationForecast {
+    /// The validators slashed or exited by the operation.
+    #[serde(with = "eth2_serde_utils::quoted_u64_vec")]
+    pub validator_indices: Vec<u64>,
+    pub included_in_next_block: bool,
+    /// The reason that the operation would not be included, if it would not.
+    pub exclusion_reason: Option<String>,
+}
+
+/// The number of operations of each kind pruned from the operation pool since the node started.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationPoolPruneStats {
+    /// The slot of the head at the most recent prune, if any.
+    pub last_prune_slot: Option<Slot>,
+    pub attestations: u64,
+    pub sync_contributions: u64,
+    pub attester_slashings: u64,
+    pub proposer_slashings: u64,
+    pub voluntary_exits: u64,
+}
+
+/// The status of the slasher, used to check that it is keeping up with the network.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlasherStatus {
+    /// The current epoch as of the most recent batch update, if any.
+    pub current_epoch: Option<Epoch>,
+    /// The oldest epoch within the slasher's history as of the most recent batch update, if any.
+    pub oldest_epoch: Option<Epoch>,
+    pub history_length: usize,
+    /// The number of attestations and blocks waiting for the next batch update.
+    pub queued_attestations: usize,
+    pub queued_blocks: usize,
+    /// The size of the slasher database on disk, in bytes.
+    pub database_size: u64,
+    pub last_batch: Option<SlasherBatchStatus>,
+    /// The number of slashings found by the slasher since the node started.
+    pub attester_slashings_found: u64,
+    pub proposer_slashings_found: u64,
+    /// The number of slashings held in the operation pool which are yet to be included in a
+    /// block. These include slashings received from the network and the HTTP API, as well as those
+    /// found by the slasher.
+    pub op_pool_attester_slashings: usize,
+    pub op_pool_proposer_slashings: usize,
+}
+
+/// A summary of the slasher's most recent batch update.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlasherBatchStatus {
+    pub epoch: Epoch,
+    /// The time taken to process the batch and update the database, in milliseconds.
+    pub time_taken_ms: u64,
+    pub attestations: usize,
+    pub blocks: usize,
+}
+
+/// The aggregate attestations held by the node for a single committee.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+pub struct CommitteeAttestations<T: EthSpec> {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub committee_index: u64,
+    /// The union of the aggregation bits of `aggregates`.
+    pub coverage: BitList<T::MaxValidatorsPerCommittee>,
+    /// The number of committee members attesting in at least one of `aggregates`.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub covered_validators: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub committee_size: u64,
+    pub aggregates: Vec<Attestation<T>>,
+}
+
+/// The packing efficiency of a canonical block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockPackingEfficiency {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub proposer_index: u64,
+    /// The number of attestations from the inclusion range of the block which had not been
+    /// included by an earlier block, but were eventually included.
+    pub available_attestations: u64,
+    /// The number of the available attestations included by the block.
+    pub included_attestations: u64,
+    /// The percentage of the available attestations included by the block.
+    pub efficiency: f64,
+}
+
+/// The packing efficiency of all blocks by a proposer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposerPackingEfficiency {
+    pub proposer_index: u64,
+    pub blocks: u64,
+    pub available_attestations: u64,
+    pub included_attestations: u64,
+    pub efficiency: f64,
+}
+
+/// The packing efficiency of the canonical blocks in a range of epochs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockPackingEfficiencyReport {
+    pub blocks: Vec<BlockPackingEfficiency>,
+    pub proposers: Vec<ProposerPackingEfficiency>,
+}
+
+/// The progress of a validator towards activation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivationStatus {
+    /// Neither a deposit nor a validator is known for the public key.
+    Unknown,
+    /// A deposit has been seen on the Eth1 chain, but has not yet been processed by the beacon
+    /// chain.
+    DepositPending,
+    /// The validator is known to the beacon chain but is not yet eligible for the activation
+    /// queue, e.g. because its balance is insufficient.
+    PendingEligibility,
+    /// The validator is in the activation queue.
+    Queued,
+    /// The validator has left the activation queue and will be activated at a future epoch.
+    Scheduled,
+    /// The validator has been activated.
+    Active,
+}
+
+/// An estimate of when a validator will be activated, as of the head.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivationEta {
+    pub pubkey: PublicKeyBytes,
+    pub status: ActivationStatus,
+    /// Whether a valid deposit for the public key has been seen in the deposit cache, or `None`
+    /// if the Eth1 service is not running.
+    pub deposit_seen: Option<bool>,
+    pub validator_index: Option<u64>,
+    /// The number of validators ahead of this validator in the activation queue.
+    pub queue_position: Option<u64>,
+    pub queue_length: u64,
+    /// The number of validators which may be activated per epoch.
+    pub churn_limit: u64,
+    /// The epoch at which the validator is, or is expected to be, activated.
+    pub eta_epoch: Option<Epoch>,
+    /// The start of `eta_epoch`, in seconds since the UNIX epoch.
+    pub eta_timestamp: Option<u64>,
+}
+
 impl BeaconNodeHttpClient {
     /// Perform a HTTP GET request, returning `None` on a 404 error.
     async fn get_bytes_opt<U: IntoUrl>(&self, url: U) -> Result<Option<Vec<u8>>, Error> {
@@ -361,6 +643,188 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/health/live`
+    ///
+    /// Returns an error unless the server is responsive.
+    pub async fn get_lighthouse_health_live(&self) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("health")
+            .push("live");
+
+        let status = self.client.get(path).send().await?.status();
+        if status == StatusCode::OK {
+            Ok(())
+        } else {
+            Err(Error::StatusCode(status))
+        }
+    }
+
+    /// `GET lighthouse/health/ready`
+    ///
+    /// The readiness is returned whether or not the node is ready.
+    pub async fn get_lighthouse_health_ready(&self) -> Result<GenericResponse<Readiness>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("health")
+            .push("ready");
+
+        let response = self.client.get(path).send().await?;
+        let status = response.status();
+        if status == StatusCode::OK || status == StatusCode::SERVICE_UNAVAILABLE {
+            Ok(response.json().await?)
+        } else {
+            Err(Error::StatusCode(status))
+        }
+    }
+
+    /// `GET lighthouse/operation_pool`
+    pub async fn get_lighthouse_operation_pool(
+        &self,
+    ) -> Result<GenericResponse<OperationPoolSummary>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("operation_pool");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/slasher/status`
+    pub async fn get_lighthouse_slasher_status(
+        &self,
+    ) -> Result<GenericResponse<SlasherStatus>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("slasher")
+            .push("status");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/operation_pool/attestations/{slot}`
+    pub async fn get_lighthouse_operation_pool_attestations<T: EthSpec>(
+        &self,
+        slot: Slot,
+    ) -> Result<GenericResponse<Vec<CommitteeAttestations<T>>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("operation_pool")
+            .push("attestations")
+            .push(&slot.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/analysis/block_packing/{start_epoch}/{end_epoch}`
+    pub async fn get_lighthouse_analysis_block_packing(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<BlockPackingEfficiencyReport>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("block_packing")
+            .push(&start_epoch.to_string())
+            .push(&end_epoch.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/validators/{validator_id}/activation_eta`
+    pub async fn get_lighthouse_validator_activation_eta(
+        &self,
+        validator_id: &ValidatorId,
+    ) -> Result<GenericResponse<ActivationEta>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("validators")
+            .push(&validator_id.to_string())
+            .push("activation_eta");
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/ui/health`
+    pub async fn get_lighthouse_ui_health(&self) -> Result<GenericResponse<UiHealth>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("ui")
+            .push("health");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/ui/validator_metrics`
+    pub async fn post_lighthouse_ui_validator_metrics(
+        &self,
+        indices: Vec<u64>,
+    ) -> Result<GenericResponse<ValidatorMetricsResponseData>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("ui")
+            .push("validator_metrics");
+
+        self.post_with_response(path, &ValidatorMetricsRequestData { indices })
+            .await
+    }
+
+    /// `GET lighthouse/logging`
+    pub async fn get_lighthouse_logging(
+        &self,
+    ) -> Result<GenericResponse<Vec<LogLevelOverride>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.get(path).await
+    }
+
+    /// `POST lighthouse/logging`
+    pub async fn post_lighthouse_logging(
+        &self,
+        request: &LogLevelRequest,
+    ) -> Result<GenericResponse<Vec<LogLevelOverride>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("logging");
+
+        self.post_with_response(path, request).await
+    }
+
     /// `GET lighthouse/syncing`
     pub async fn get_lighthouse_syncing(&self) -> Result<GenericResponse<SyncState>, Error> {
         let mut path = self.server.full.clone();
@@ -532,4 +996,32 @@ impl BeaconNodeHttpClient {
 
         self.post_with_response(path, &()).await
     }
+
+    /// `POST lighthouse/sync/backfill/pause`
+    pub async fn post_lighthouse_sync_backfill_pause(&self) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("backfill")
+            .push("pause");
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `POST lighthouse/sync/backfill/resume`
+    pub async fn post_lighthouse_sync_backfill_resume(&self) -> Result<String, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("sync")
+            .push("backfill")
+            .push("resume");
+
+        self.post_with_response(path, &()).await
+    }
 }
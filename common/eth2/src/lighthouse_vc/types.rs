@@ -1,11 +1,13 @@
+use super::std_types::{DeleteKeystoreStatus, KeystoreJsonStr, Status};
 use account_utils::ZeroizeString;
 use eth2_keystore::Keystore;
 use graffiti::GraffitiString;
 use serde::{Deserialize, Serialize};
+use slashing_protection::interchange::Interchange;
 use std::path::PathBuf;
 
 pub use crate::lighthouse::Health;
-pub use crate::types::{GenericResponse, VersionData};
+pub use crate::types::{GenericResponse, ValidatorStatus, VersionData};
 pub use types::*;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -15,6 +17,16 @@ pub struct ValidatorData {
     pub voting_pubkey: PublicKeyBytes,
 }
 
+/// The exit status of a validator, as observed on-chain by the beacon node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorExitData {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub index: u64,
+    pub status: ValidatorStatus,
+    pub exit_epoch: Epoch,
+    pub withdrawable_epoch: Epoch,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ValidatorRequest {
     pub enable: bool,
@@ -66,6 +78,24 @@ pub struct KeystoreValidatorsPostRequest {
     pub graffiti: Option<GraffitiString>,
 }
 
+/// The response to `DELETE lighthouse/keystores`, which deletes keystores as
+/// `DELETE eth/v1/keystores` does, but also returns each deleted keystore with its password.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportKeystoresResponse {
+    pub data: Vec<SingleExportKeystoresResponse>,
+    #[serde(with = "eth2_serde_utils::json_str")]
+    pub slashing_protection: Interchange,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SingleExportKeystoresResponse {
+    pub status: Status<DeleteKeystoreStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validating_keystore: Option<KeystoreJsonStr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validating_keystore_password: Option<ZeroizeString>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Web3SignerValidatorRequest {
     pub enable: bool,
@@ -81,4 +111,10 @@ pub struct Web3SignerValidatorRequest {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_timeout_ms: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_identity_path: Option<PathBuf>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_identity_password: Option<String>,
 }
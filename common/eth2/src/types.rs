@@ -237,11 +237,26 @@ pub struct FinalityCheckpointsData {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub enum ValidatorId {
     PublicKey(PublicKeyBytes),
     Index(u64),
 }
 
+impl From<ValidatorId> for String {
+    fn from(id: ValidatorId) -> String {
+        id.to_string()
+    }
+}
+
+impl TryFrom<String> for ValidatorId {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
 impl FromStr for ValidatorId {
     type Err = String;
 
@@ -422,6 +437,12 @@ pub struct SyncCommitteesQuery {
     pub epoch: Option<Epoch>,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdatesQuery {
+    pub start_period: u64,
+    pub count: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AttestationPoolQuery {
     pub slot: Option<Slot>,
@@ -434,6 +455,15 @@ pub struct ValidatorsQuery {
     pub status: Option<QueryVec<ValidatorStatus>>,
 }
 
+/// The request body of `POST beacon/states/{state_id}/validators`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ValidatorsRequestBody {
+    #[serde(default)]
+    pub ids: Option<Vec<ValidatorId>>,
+    #[serde(default)]
+    pub statuses: Option<Vec<ValidatorStatus>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommitteeData {
     #[serde(with = "eth2_serde_utils::quoted_u64")]
@@ -489,6 +519,39 @@ pub struct ChainHeadData {
     pub root: Hash256,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoice {
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub fork_choice_nodes: Vec<ForkChoiceNode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkChoiceNode {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub parent_root: Option<Hash256>,
+    pub justified_epoch: Option<Epoch>,
+    pub finalized_epoch: Option<Epoch>,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub weight: u64,
+    pub execution_status: ForkChoiceNodeExecutionStatus,
+    pub execution_block_hash: Option<Hash256>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkChoiceNodeExecutionStatus {
+    /// The execution payload has been verified as valid by an execution engine.
+    Valid,
+    /// The execution payload has been verified as invalid by an execution engine.
+    Invalid,
+    /// The execution payload has not yet been verified by an execution engine.
+    Optimistic,
+    /// The block does not have an execution payload.
+    Irrelevant,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct IdentityData {
     pub peer_id: String,
@@ -514,6 +577,9 @@ pub struct VersionData {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SyncingData {
     pub is_syncing: bool,
+    /// Whether the beacon node's execution engines are all unreachable, if it has any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub el_offline: Option<bool>,
     pub head_slot: Slot,
     pub sync_distance: Slot,
 }
@@ -581,6 +647,10 @@ pub struct ValidatorBlocksQuery {
 pub struct ValidatorAttestationDataQuery {
     pub slot: Slot,
     pub committee_index: CommitteeIndex,
+    /// Attest to the head even if its execution payload has not been verified by an EL, rather
+    /// than to its most recent verified ancestor. Lighthouse-specific.
+    #[serde(default)]
+    pub allow_optimistic: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -589,6 +659,28 @@ pub struct ValidatorAggregateAttestationQuery {
     pub slot: Slot,
 }
 
+/// A selection proof for an attestation aggregation duty, exchanged with distributed validator
+/// middleware. The validator client sends its partial proof and receives the combined proof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconCommitteeSelection {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub slot: Slot,
+    pub selection_proof: Signature,
+}
+
+/// A selection proof for a sync committee aggregation duty, exchanged with distributed validator
+/// middleware. The validator client sends its partial proof and receives the combined proof.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncCommitteeSelection {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub slot: Slot,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub subcommittee_index: u64,
+    pub selection_proof: Signature,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BeaconCommitteeSubscription {
     #[serde(with = "eth2_serde_utils::quoted_u64")]
@@ -736,6 +828,12 @@ pub struct SseFinalizedCheckpoint {
     pub block: Hash256,
     pub state: Hash256,
     pub epoch: Epoch,
+    /// Lighthouse-specific: the slot of the head block which finalized the checkpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head_slot: Option<Slot>,
+    /// Lighthouse-specific: the justified epoch of the head state which finalized the checkpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub justified_epoch: Option<Epoch>,
 }
 
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
@@ -774,6 +872,40 @@ pub struct SseLateHead {
     pub set_as_head_delay: Option<Duration>,
 }
 
+/// Lighthouse-specific event emitted when a block is received on gossip and passes gossip
+/// verification.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseBlockGossip {
+    pub slot: Slot,
+    pub block: Hash256,
+    pub proposer_index: u64,
+    pub peer_id: String,
+    pub peer_client: String,
+    /// The delay between the start of the slot and the block being seen on gossip.
+    pub arrival_delay: Duration,
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidatorSlashingKind {
+    Proposer,
+    Attester,
+}
+
+/// Lighthouse-specific event emitted when a slashing of a validator monitored by the beacon node
+/// is observed.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseValidatorSlashing {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub pubkey: PublicKeyBytes,
+    pub kind: ValidatorSlashingKind,
+    /// The slot of the slashable block or attestation.
+    pub slot: Slot,
+    /// Where the slashing was observed, e.g. `gossip`, `api` or `block`.
+    pub source: String,
+}
+
 #[derive(PartialEq, Debug, Serialize, Clone)]
 #[serde(bound = "T: EthSpec", untagged)]
 pub enum EventKind<T: EthSpec> {
@@ -785,9 +917,30 @@ pub enum EventKind<T: EthSpec> {
     ChainReorg(SseChainReorg),
     ContributionAndProof(Box<SignedContributionAndProof<T>>),
     LateHead(SseLateHead),
+    BlockGossip(SseBlockGossip),
+    ValidatorSlashing(SseValidatorSlashing),
+    AttesterSlashing(Box<AttesterSlashing<T>>),
+    ProposerSlashing(Box<ProposerSlashing>),
 }
 
 impl<T: EthSpec> EventKind<T> {
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            EventKind::Head(_) => EventTopic::Head,
+            EventKind::Block(_) => EventTopic::Block,
+            EventKind::Attestation(_) => EventTopic::Attestation,
+            EventKind::VoluntaryExit(_) => EventTopic::VoluntaryExit,
+            EventKind::FinalizedCheckpoint(_) => EventTopic::FinalizedCheckpoint,
+            EventKind::ChainReorg(_) => EventTopic::ChainReorg,
+            EventKind::ContributionAndProof(_) => EventTopic::ContributionAndProof,
+            EventKind::LateHead(_) => EventTopic::LateHead,
+            EventKind::BlockGossip(_) => EventTopic::BlockGossip,
+            EventKind::ValidatorSlashing(_) => EventTopic::ValidatorSlashing,
+            EventKind::AttesterSlashing(_) => EventTopic::AttesterSlashing,
+            EventKind::ProposerSlashing(_) => EventTopic::ProposerSlashing,
+        }
+    }
+
     pub fn topic_name(&self) -> &str {
         match self {
             EventKind::Head(_) => "head",
@@ -798,6 +951,10 @@ impl<T: EthSpec> EventKind<T> {
             EventKind::ChainReorg(_) => "chain_reorg",
             EventKind::ContributionAndProof(_) => "contribution_and_proof",
             EventKind::LateHead(_) => "late_head",
+            EventKind::BlockGossip(_) => "block_gossip",
+            EventKind::ValidatorSlashing(_) => "validator_slashing",
+            EventKind::AttesterSlashing(_) => "attester_slashing",
+            EventKind::ProposerSlashing(_) => "proposer_slashing",
         }
     }
 
@@ -840,6 +997,24 @@ impl<T: EthSpec> EventKind<T> {
             "late_head" => Ok(EventKind::LateHead(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Late Head: {:?}", e)),
             )?)),
+            "block_gossip" => Ok(EventKind::BlockGossip(serde_json::from_str(data).map_err(
+                |e| ServerError::InvalidServerSentEvent(format!("Block Gossip: {:?}", e)),
+            )?)),
+            "validator_slashing" => Ok(EventKind::ValidatorSlashing(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Validator Slashing: {:?}", e))
+                })?,
+            )),
+            "attester_slashing" => Ok(EventKind::AttesterSlashing(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Attester Slashing: {:?}", e))
+                })?,
+            ))),
+            "proposer_slashing" => Ok(EventKind::ProposerSlashing(Box::new(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Proposer Slashing: {:?}", e))
+                })?,
+            ))),
             "voluntary_exit" => Ok(EventKind::VoluntaryExit(
                 serde_json::from_str(data).map_err(|e| {
                     ServerError::InvalidServerSentEvent(format!("Voluntary Exit: {:?}", e))
@@ -857,12 +1032,43 @@ impl<T: EthSpec> EventKind<T> {
     }
 }
 
+/// Lighthouse-specific wrapper of an event with the ID assigned to it by the beacon node.
+///
+/// IDs increase with each event emitted by the beacon node. Providing the ID of the last event
+/// received as the `Last-Event-ID` header of a request to `/eth/v1/events` replays the events
+/// which followed it, as far as they are retained by the beacon node.
+#[derive(PartialEq, Debug, Clone)]
+pub struct IdentifiedEvent<T: EthSpec> {
+    pub id: u64,
+    pub event: EventKind<T>,
+}
+
+impl<T: EthSpec> IdentifiedEvent<T> {
+    pub fn from_sse_bytes(message: &[u8]) -> Result<Self, ServerError> {
+        let event = EventKind::from_sse_bytes(message)?;
+
+        let s = from_utf8(message)
+            .map_err(|e| ServerError::InvalidServerSentEvent(format!("{:?}", e)))?;
+        let id = s
+            .split('\n')
+            .find_map(|line| line.strip_prefix("id:"))
+            .ok_or_else(|| {
+                ServerError::InvalidServerSentEvent("Could not parse id tag".to_string())
+            })?
+            .trim()
+            .parse()
+            .map_err(|e| ServerError::InvalidServerSentEvent(format!("Id: {:?}", e)))?;
+
+        Ok(Self { id, event })
+    }
+}
+
 #[derive(Clone, Deserialize)]
 pub struct EventQuery {
     pub topics: QueryVec<EventTopic>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventTopic {
     Head,
@@ -873,6 +1079,10 @@ pub enum EventTopic {
     ChainReorg,
     ContributionAndProof,
     LateHead,
+    BlockGossip,
+    ValidatorSlashing,
+    AttesterSlashing,
+    ProposerSlashing,
 }
 
 impl FromStr for EventTopic {
@@ -888,6 +1098,10 @@ impl FromStr for EventTopic {
             "chain_reorg" => Ok(EventTopic::ChainReorg),
             "contribution_and_proof" => Ok(EventTopic::ContributionAndProof),
             "late_head" => Ok(EventTopic::LateHead),
+            "block_gossip" => Ok(EventTopic::BlockGossip),
+            "validator_slashing" => Ok(EventTopic::ValidatorSlashing),
+            "attester_slashing" => Ok(EventTopic::AttesterSlashing),
+            "proposer_slashing" => Ok(EventTopic::ProposerSlashing),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -904,6 +1118,10 @@ impl fmt::Display for EventTopic {
             EventTopic::ChainReorg => write!(f, "chain_reorg"),
             EventTopic::ContributionAndProof => write!(f, "contribution_and_proof"),
             EventTopic::LateHead => write!(f, "late_head"),
+            EventTopic::BlockGossip => write!(f, "block_gossip"),
+            EventTopic::ValidatorSlashing => write!(f, "validator_slashing"),
+            EventTopic::AttesterSlashing => write!(f, "attester_slashing"),
+            EventTopic::ProposerSlashing => write!(f, "proposer_slashing"),
         }
     }
 }
@@ -953,6 +1171,75 @@ pub struct LivenessResponseData {
     pub is_live: bool,
 }
 
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub struct StandardLivenessResponseData {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub index: u64,
+    pub is_live: bool,
+}
+
+/// The rewards earned by the proposer of a block, in Gwei.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardBlockReward {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub total: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub attestations: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub sync_aggregate: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_slashings: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub attester_slashings: u64,
+}
+
+/// The reward (or penalty, if negative) of a sync committee member for a single block, in Gwei.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncCommitteeReward {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub reward: i64,
+}
+
+/// The rewards a validator with the given `effective_balance` would earn for perfect attestation
+/// performance in an epoch, in Gwei.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IdealAttestationRewards {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub effective_balance: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub head: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub target: u64,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub source: u64,
+}
+
+/// The rewards (or penalties, if negative) a validator earned for its attestations in an epoch,
+/// in Gwei.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TotalAttestationRewards {
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub head: i64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub target: i64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub source: i64,
+    #[serde(with = "eth2_serde_utils::quoted_i64")]
+    pub inactivity: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StandardAttestationRewards {
+    pub ideal_rewards: Vec<IdealAttestationRewards>,
+    pub total_rewards: Vec<TotalAttestationRewards>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -964,4 +1251,23 @@ mod tests {
             QueryVec(vec![0_u64, 1, 2])
         );
     }
+
+    #[test]
+    fn finalized_checkpoint_event_without_lighthouse_fields() {
+        let event: SseFinalizedCheckpoint = serde_json::from_str(
+            r#"{
+                "block": "0x9a2fefd2fdb57f74993c7780ea5b9030d2897b615b89f808011ca5aebed54eaf",
+                "state": "0x600e852a08c1200654ddf11025f1ceacb3c2e74bdd5c630cde0838b2591b69f9",
+                "epoch": "2"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(event.epoch, Epoch::new(2));
+        assert_eq!(event.head_slot, None);
+        assert_eq!(event.justified_epoch, None);
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert!(json.get("head_slot").is_none());
+        assert!(json.get("justified_epoch").is_none());
+    }
 }
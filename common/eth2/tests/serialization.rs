@@ -0,0 +1,113 @@
+//! Snapshot tests of the JSON and SSZ encodings of the types served by the HTTP API.
+//!
+//! Each fixture in `tests/fixtures` is a response body in the format given by the standard beacon
+//! node API specification, modelled on the examples therein. A fixture must survive a round trip
+//! through its type unchanged, so renaming, removing or re-encoding a field (e.g., an integer
+//! which is no longer quoted) fails these tests rather than silently breaking API consumers.
+//!
+//! Where the response data is a consensus type, the matching `.ssz` fixture is the SSZ encoding
+//! of the same data and must agree with the JSON fixture.
+//!
+//! To test a new type, add a fixture named after the test and a line to the list below.
+
+use eth2::types::*;
+use serde::{de::DeserializeOwned, Serialize};
+use ssz::{Decode, Encode};
+use std::fmt::Debug;
+
+/// Asserts that the JSON `fixture` decodes as a `T` and re-encodes to the same JSON.
+fn check_json<T: Serialize + DeserializeOwned>(fixture: &str) -> T {
+    let expected: serde_json::Value =
+        serde_json::from_str(fixture).expect("fixture should be valid JSON");
+    let decoded: T =
+        serde_json::from_value(expected.clone()).expect("fixture should decode to type");
+    let encoded = serde_json::to_value(&decoded).expect("type should encode to JSON");
+    assert_eq!(encoded, expected, "JSON encoding does not match fixture");
+    decoded
+}
+
+/// Asserts that the SSZ `fixture` decodes to `expected` and re-encodes to the same bytes.
+fn check_ssz<T: Encode + Decode + PartialEq + Debug>(fixture: &[u8], expected: &T) {
+    let decoded = T::from_ssz_bytes(fixture).expect("fixture should decode to type");
+    assert_eq!(
+        &decoded, expected,
+        "SSZ fixture does not match JSON fixture"
+    );
+    assert_eq!(
+        decoded.as_ssz_bytes(),
+        fixture,
+        "SSZ encoding does not match fixture"
+    );
+}
+
+macro_rules! json_snapshot_test {
+    ($name: ident, $type: ty) => {
+        #[test]
+        fn $name() {
+            check_json::<$type>(include_str!(concat!(
+                "fixtures/",
+                stringify!($name),
+                ".json"
+            )));
+        }
+    };
+}
+
+macro_rules! json_and_ssz_snapshot_test {
+    ($name: ident, $type: ty) => {
+        #[test]
+        fn $name() {
+            let response = check_json::<GenericResponse<$type>>(include_str!(concat!(
+                "fixtures/",
+                stringify!($name),
+                ".json"
+            )));
+            check_ssz::<$type>(
+                include_bytes!(concat!("fixtures/", stringify!($name), ".ssz")),
+                &response.data,
+            );
+        }
+    };
+}
+
+/*
+ * beacon
+ */
+json_snapshot_test!(genesis, GenericResponse<GenesisData>);
+json_snapshot_test!(state_root, GenericResponse<RootData>);
+json_and_ssz_snapshot_test!(state_fork, Fork);
+json_snapshot_test!(
+    state_finality_checkpoints,
+    GenericResponse<FinalityCheckpointsData>
+);
+json_snapshot_test!(state_validator, GenericResponse<ValidatorData>);
+json_snapshot_test!(
+    state_validator_balances,
+    GenericResponse<Vec<ValidatorBalanceData>>
+);
+json_snapshot_test!(state_committees, GenericResponse<Vec<CommitteeData>>);
+json_snapshot_test!(
+    state_sync_committees,
+    GenericResponse<SyncCommitteeByValidatorIndices>
+);
+json_snapshot_test!(block_header, GenericResponse<BlockHeaderData>);
+
+/*
+ * config
+ */
+json_snapshot_test!(deposit_contract, GenericResponse<DepositContractData>);
+
+/*
+ * node
+ */
+json_snapshot_test!(node_version, GenericResponse<VersionData>);
+json_snapshot_test!(node_syncing, GenericResponse<SyncingData>);
+json_snapshot_test!(node_peer_count, GenericResponse<PeerCount>);
+
+/*
+ * validator
+ */
+json_snapshot_test!(proposer_duties, DutiesResponse<Vec<ProposerData>>);
+json_snapshot_test!(attester_duties, DutiesResponse<Vec<AttesterData>>);
+json_snapshot_test!(sync_duties, GenericResponse<Vec<SyncDuty>>);
+json_and_ssz_snapshot_test!(attestation_data, AttestationData);
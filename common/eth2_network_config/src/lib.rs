@@ -86,13 +86,18 @@ impl Eth2NetworkConfig {
     }
 
     /// Construct a consolidated `ChainSpec` from the YAML config.
+    ///
+    /// Returns an error if the config schedules a fork before the fork which precedes it.
     pub fn chain_spec<E: EthSpec>(&self) -> Result<ChainSpec, String> {
-        ChainSpec::from_config::<E>(&self.config).ok_or_else(|| {
+        let spec = ChainSpec::from_config::<E>(&self.config).ok_or_else(|| {
             format!(
                 "YAML configuration incompatible with spec constants for {}",
                 E::spec_name()
             )
-        })
+        })?;
+        spec.verify_fork_schedule()
+            .map_err(|e| format!("Invalid fork schedule in YAML configuration: {}", e))?;
+        Ok(spec)
     }
 
     /// Attempts to deserialize `self.beacon_state`, returning an error if it's missing or invalid.
@@ -37,6 +37,32 @@ impl<T> Fallback<T> {
         Err(FallbackError::AllErrored(errors))
     }
 
+    /// As `first_success`, except that the servers are tried in the order of the indices in
+    /// `order`, which must be a permutation of the indices of `self.servers`.
+    ///
+    /// Any errors are returned in the order of `self.servers`, as for `first_success`.
+    pub async fn first_success_in_order<'a, F, O, E, R>(
+        &'a self,
+        order: &[usize],
+        func: F,
+    ) -> Result<(O, usize), FallbackError<E>>
+    where
+        F: Fn(&'a T) -> R,
+        R: Future<Output = Result<O, E>>,
+    {
+        let mut errors = vec![];
+        for &index in order {
+            match func(&self.servers[index]).await {
+                Ok(val) => return Ok((val, errors.len())),
+                Err(e) => errors.push((index, e)),
+            }
+        }
+        errors.sort_by_key(|(index, _)| *index);
+        Err(FallbackError::AllErrored(
+            errors.into_iter().map(|(_, e)| e).collect(),
+        ))
+    }
+
     pub fn map_format_error<'a, E, F, S>(&'a self, f: F, error: &FallbackError<E>) -> String
     where
         F: FnMut(&'a T) -> &'a S,
@@ -8,6 +8,12 @@ use slog::Logger;
 use slog_term::Decorator;
 use std::io::{Result, Write};
 
+pub use log_levels::{level_name, parse_level, LogLevels, LogLevelsFilter};
+pub use rotating_file::{RotatingFile, RotatingFileConfig};
+
+mod log_levels;
+mod rotating_file;
+
 pub const MAX_MESSAGE_WIDTH: usize = 40;
 
 lazy_static! {
@@ -0,0 +1,162 @@
+//! Log levels which can be changed per module whilst the process is running.
+use parking_lot::RwLock;
+use slog::{Drain, Level, OwnedKVList, Record};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// The names of levels, as given to `--debug-level`.
+const LEVEL_NAMES: &[(&str, Level)] = &[
+    ("crit", Level::Critical),
+    ("error", Level::Error),
+    ("warn", Level::Warning),
+    ("info", Level::Info),
+    ("debug", Level::Debug),
+    ("trace", Level::Trace),
+];
+
+/// Parse a level named as for `--debug-level`.
+pub fn parse_level(name: &str) -> Result<Level, String> {
+    LEVEL_NAMES
+        .iter()
+        .find(|(level_name, _)| level_name.eq_ignore_ascii_case(name))
+        .map(|(_, level)| *level)
+        .ok_or_else(|| format!("Unknown log level: {}", name))
+}
+
+/// The name of `level`, as for `--debug-level`.
+pub fn level_name(level: Level) -> &'static str {
+    LEVEL_NAMES
+        .iter()
+        .find(|(_, l)| *l == level)
+        .map_or("trace", |(name, _)| *name)
+}
+
+/// A shared set of log levels which override those of `LogLevelsFilter` drains.
+///
+/// An override applies to a module and all of its submodules, e.g. an override for
+/// `network::sync` applies to `network::sync::manager`. The empty module applies to every module.
+#[derive(Clone, Default)]
+pub struct LogLevels {
+    overrides: Arc<RwLock<BTreeMap<String, Level>>>,
+}
+
+impl LogLevels {
+    /// Log `module` and its submodules at `level`.
+    pub fn set(&self, module: String, level: Level) {
+        self.overrides.write().insert(module, level);
+    }
+
+    /// Restore the configured level of `module`, returning `false` if it had not been overridden.
+    pub fn remove(&self, module: &str) -> bool {
+        self.overrides.write().remove(module).is_some()
+    }
+
+    /// Restore the configured levels of all modules.
+    pub fn clear(&self) {
+        self.overrides.write().clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.read().is_empty()
+    }
+
+    /// Returns all overrides, ordered by module.
+    pub fn overrides(&self) -> Vec<(String, Level)> {
+        self.overrides
+            .read()
+            .iter()
+            .map(|(module, level)| (module.clone(), *level))
+            .collect()
+    }
+
+    /// Returns the level of the most specific override which applies to `module`.
+    fn level_of(&self, module: &str) -> Option<Level> {
+        self.overrides
+            .read()
+            .iter()
+            .filter(|(prefix, _)| is_within(module, prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level)
+    }
+}
+
+/// Returns `true` if `module` is `parent` or one of its submodules.
+fn is_within(module: &str, parent: &str) -> bool {
+    parent.is_empty()
+        || module
+            .strip_prefix(parent)
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+/// Filters out records below `level`, or below the level of any override in `levels` which
+/// applies to the record's module.
+pub struct LogLevelsFilter<D> {
+    drain: D,
+    level: Level,
+    levels: LogLevels,
+}
+
+impl<D> LogLevelsFilter<D> {
+    pub fn new(drain: D, level: Level, levels: LogLevels) -> Self {
+        Self {
+            drain,
+            level,
+            levels,
+        }
+    }
+}
+
+impl<D: Drain> Drain for LogLevelsFilter<D> {
+    type Ok = Option<D::Ok>;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<Self::Ok, Self::Err> {
+        let level = self.levels.level_of(record.module()).unwrap_or(self.level);
+        if record.level().is_at_least(level) {
+            self.drain.log(record, values).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn most_specific_override_applies() {
+        let levels = LogLevels::default();
+        assert_eq!(levels.level_of("network::sync::manager"), None);
+
+        levels.set("network".to_string(), Level::Debug);
+        levels.set("network::sync".to_string(), Level::Trace);
+        assert_eq!(
+            levels.level_of("network::sync::manager"),
+            Some(Level::Trace)
+        );
+        assert_eq!(levels.level_of("network::sync"), Some(Level::Trace));
+        assert_eq!(levels.level_of("network::syncing"), Some(Level::Debug));
+        assert_eq!(levels.level_of("network_utils"), None);
+
+        levels.set(String::new(), Level::Error);
+        assert_eq!(levels.level_of("network_utils"), Some(Level::Error));
+
+        assert!(levels.remove("network::sync"));
+        assert!(!levels.remove("network::sync"));
+        assert_eq!(levels.level_of("network::sync"), Some(Level::Debug));
+
+        levels.clear();
+        assert!(levels.is_empty());
+    }
+
+    #[test]
+    fn level_names() {
+        for (name, level) in LEVEL_NAMES {
+            assert_eq!(parse_level(name), Ok(*level));
+            assert_eq!(level_name(*level), *name);
+        }
+        assert_eq!(parse_level("DEBUG"), Ok(Level::Debug));
+        assert!(parse_level("verbose").is_err());
+    }
+}
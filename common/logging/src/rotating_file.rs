@@ -0,0 +1,236 @@
+//! A log file which is rotated once it reaches a maximum size or age.
+//!
+//! Rotated files are named by appending their age to the path, e.g. `beacon.log.1` is the most
+//! recently rotated file. When compression is enabled they are gzipped to `beacon.log.1.gz`.
+use flate2::{write::GzEncoder, Compression};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Configuration for a `RotatingFile`.
+#[derive(Debug, Clone)]
+pub struct RotatingFileConfig {
+    pub path: PathBuf,
+    /// Rotate once the file reaches this many bytes, if non-zero.
+    pub max_size: u64,
+    /// Rotate once the file has been written to for this long, if set.
+    pub max_age: Option<Duration>,
+    /// The number of rotated files to keep, after which the oldest is deleted.
+    pub max_files: usize,
+    /// Gzip rotated files.
+    pub compress: bool,
+}
+
+/// A writer to the log file at `config.path`, which rotates the file as it is flushed.
+///
+/// Rotation only happens on `flush`, which log drains call once per record, so that records are
+/// never split across files.
+pub struct RotatingFile {
+    config: RotatingFileConfig,
+    file: File,
+    size: u64,
+    opened_at: Instant,
+}
+
+impl RotatingFile {
+    /// Open the log file for appending, creating it if necessary.
+    pub fn open(config: RotatingFileConfig) -> io::Result<Self> {
+        let file = open_file(&config.path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            config,
+            file,
+            size,
+            opened_at: Instant::now(),
+        })
+    }
+
+    fn should_rotate(&self) -> bool {
+        let size_exceeded = self.config.max_size > 0 && self.size >= self.config.max_size;
+        let age_exceeded = self
+            .config
+            .max_age
+            .map_or(false, |max_age| self.opened_at.elapsed() >= max_age);
+        self.size > 0 && (size_exceeded || age_exceeded)
+    }
+
+    /// Move the current file to `path.1`, shifting older files along and deleting the oldest.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        let max_files = self.config.max_files;
+        if max_files > 0 {
+            remove_if_exists(&self.rotated_path(max_files, false))?;
+            remove_if_exists(&self.rotated_path(max_files, true))?;
+            for i in (1..max_files).rev() {
+                for &compressed in &[false, true] {
+                    let from = self.rotated_path(i, compressed);
+                    if from.exists() {
+                        fs::rename(&from, self.rotated_path(i + 1, compressed))?;
+                    }
+                }
+            }
+
+            let rotated = self.rotated_path(1, false);
+            fs::rename(&self.config.path, &rotated)?;
+            if self.config.compress {
+                compress(&rotated, &self.rotated_path(1, true))?;
+            }
+        } else {
+            fs::remove_file(&self.config.path)?;
+        }
+
+        self.file = open_file(&self.config.path)?;
+        self.size = 0;
+        self.opened_at = Instant::now();
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize, compressed: bool) -> PathBuf {
+        let mut path = self.config.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        if compressed {
+            path.push(".gz");
+        }
+        path.into()
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.should_rotate() {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Open `path` for appending, readable only by the current user.
+fn open_file(path: &Path) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Gzip `from` to `to`, then delete `from`.
+fn compress(from: &Path, to: &Path) -> io::Result<()> {
+    let mut encoder = GzEncoder::new(open_file(to)?, Compression::default());
+    io::copy(&mut File::open(from)?, &mut encoder)?;
+    encoder.finish()?;
+    fs::remove_file(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use tempfile::tempdir;
+
+    fn config(path: PathBuf) -> RotatingFileConfig {
+        RotatingFileConfig {
+            path,
+            max_size: 10,
+            max_age: None,
+            max_files: 2,
+            compress: false,
+        }
+    }
+
+    fn write_record(file: &mut RotatingFile, record: &str) {
+        file.write_all(record.as_bytes()).unwrap();
+        file.flush().unwrap();
+    }
+
+    #[test]
+    fn rotates_by_size() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("beacon.log");
+        let mut file = RotatingFile::open(config(path.clone())).unwrap();
+
+        write_record(&mut file, "first\n");
+        assert!(!dir.path().join("beacon.log.1").exists());
+
+        // Records are not split, even though this one exceeds the maximum size.
+        write_record(&mut file, "second record\n");
+        write_record(&mut file, "third record\n");
+        write_record(&mut file, "fourth record\n");
+        write_record(&mut file, "fifth\n");
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "fifth\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("beacon.log.1")).unwrap(),
+            "fourth record\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("beacon.log.2")).unwrap(),
+            "third record\n"
+        );
+        assert!(!dir.path().join("beacon.log.3").exists());
+    }
+
+    #[test]
+    fn rotates_by_age() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("beacon.log");
+        let mut file = RotatingFile::open(RotatingFileConfig {
+            max_size: 0,
+            max_age: Some(Duration::from_secs(0)),
+            ..config(path.clone())
+        })
+        .unwrap();
+
+        write_record(&mut file, "first\n");
+        write_record(&mut file, "second\n");
+
+        assert!(fs::read_to_string(&path).unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("beacon.log.1")).unwrap(),
+            "second\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("beacon.log.2")).unwrap(),
+            "first\n"
+        );
+    }
+
+    #[test]
+    fn compresses_rotated_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("beacon.log");
+        let mut file = RotatingFile::open(RotatingFileConfig {
+            compress: true,
+            ..config(path)
+        })
+        .unwrap();
+
+        write_record(&mut file, "a long record\n");
+
+        assert!(!dir.path().join("beacon.log.1").exists());
+        let mut contents = String::new();
+        GzDecoder::new(File::open(dir.path().join("beacon.log.1.gz")).unwrap())
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "a long record\n");
+    }
+}
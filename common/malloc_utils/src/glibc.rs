@@ -7,6 +7,7 @@
 use lazy_static::lazy_static;
 use lighthouse_metrics::*;
 use parking_lot::Mutex;
+use std::convert::TryFrom;
 use std::env;
 use std::os::raw::c_int;
 use std::result::Result;
@@ -27,6 +28,7 @@ const OPTIMAL_MMAP_THRESHOLD: c_int = 2 * 1_024 * 1_024;
 ///
 /// https://github.com/lattera/glibc/blob/895ef79e04a953cac1493863bcae29ad85657ee1/malloc/malloc.h#L115-L123
 const M_MMAP_THRESHOLD: c_int = -4;
+const M_ARENA_MAX: c_int = -8;
 
 /// Environment variables used to configure malloc.
 ///
@@ -80,6 +82,26 @@ lazy_static! {
     );
 }
 
+// Allocator statistics derived from `mallinfo`, named independently of the allocator.
+lazy_static! {
+    pub static ref ALLOCATOR_ACTIVE_BYTES: lighthouse_metrics::Result<IntGauge> =
+        try_create_int_gauge(
+            "allocator_active_bytes",
+            "The number of bytes in allocations which are in use.",
+        );
+    pub static ref ALLOCATOR_RESIDENT_BYTES: lighthouse_metrics::Result<IntGauge> =
+        try_create_int_gauge(
+            "allocator_resident_bytes",
+            "The number of bytes obtained from the system by the allocator, both in use and free.",
+        );
+    pub static ref ALLOCATOR_FRAGMENTATION: lighthouse_metrics::Result<Gauge> =
+        try_create_float_gauge(
+            "allocator_fragmentation_ratio",
+            "The proportion of the bytes obtained from the system by the allocator which are free \
+            but have not been returned to the system.",
+        );
+}
+
 /// Calls `mallinfo` and updates Prometheus metrics with the results.
 pub fn scrape_mallinfo_metrics() {
     let mallinfo = mallinfo();
@@ -93,6 +115,19 @@ pub fn scrape_mallinfo_metrics() {
     set_gauge(&MALLINFO_UORDBLKS, unsigned_i64(mallinfo.uordblks));
     set_gauge(&MALLINFO_FORDBLKS, unsigned_i64(mallinfo.fordblks));
     set_gauge(&MALLINFO_KEEPCOST, unsigned_i64(mallinfo.keepcost));
+
+    // Allocations made via `mmap` are returned to the system when freed, so are always active.
+    let mmapped = unsigned_i64(mallinfo.hblkhd);
+    let active = unsigned_i64(mallinfo.uordblks) + mmapped;
+    let resident = unsigned_i64(mallinfo.arena) + mmapped;
+    set_gauge(&ALLOCATOR_ACTIVE_BYTES, active);
+    set_gauge(&ALLOCATOR_RESIDENT_BYTES, resident);
+    if resident > 0 {
+        set_float_gauge(
+            &ALLOCATOR_FRAGMENTATION,
+            resident.saturating_sub(active) as f64 / resident as f64,
+        );
+    }
 }
 
 /// Cast a C integer as returned by `mallinfo` to an unsigned i64.
@@ -113,13 +148,25 @@ fn unsigned_i64(x: usize) -> i64 {
 }
 
 /// Perform all configuration routines.
-pub fn configure_glibc_malloc() -> Result<(), String> {
+///
+/// If `arena_max` is provided, it limits the number of arenas in place of the `MALLOC_ARENA_MAX`
+/// environment variable. Fewer arenas reduce fragmentation at the cost of more contention between
+/// threads.
+pub fn configure_glibc_malloc(arena_max: Option<usize>) -> Result<(), String> {
     if !env_var_present(ENV_VAR_MMAP_THRESHOLD) {
         if let Err(e) = malloc_mmap_threshold(OPTIMAL_MMAP_THRESHOLD) {
             return Err(format!("failed (code {}) to set malloc mmap threshold", e));
         }
     }
 
+    if let Some(arena_max) = arena_max {
+        let arena_max = c_int::try_from(arena_max)
+            .map_err(|_| format!("malloc arena max of {} is too large", arena_max))?;
+        if let Err(e) = malloc_arena_max(arena_max) {
+            return Err(format!("failed (code {}) to set malloc arena max", e));
+        }
+    }
+
     Ok(())
 }
 
@@ -138,6 +185,16 @@ fn malloc_mmap_threshold(num_arenas: c_int) -> Result<(), c_int> {
     into_result(mallopt(M_MMAP_THRESHOLD, num_arenas))
 }
 
+/// Uses `mallopt` to set the `M_ARENA_MAX` value, specifying the maximum number of arenas which may
+/// be created.
+///
+/// ## Resources
+///
+/// - https://man7.org/linux/man-pages/man3/mallopt.3.html
+fn malloc_arena_max(num_arenas: c_int) -> Result<(), c_int> {
+    into_result(mallopt(M_ARENA_MAX, num_arenas))
+}
+
 fn mallopt(param: c_int, val: c_int) -> c_int {
     // Prevent this function from being called in parallel with any other non-thread-safe function.
     let _lock = GLOBAL_LOCK.lock();
@@ -178,4 +235,14 @@ mod tests {
     fn malloc_mmap_threshold_does_not_panic() {
         malloc_mmap_threshold(OPTIMAL_MMAP_THRESHOLD).unwrap();
     }
+
+    #[test]
+    fn malloc_arena_max_does_not_panic() {
+        malloc_arena_max(4).unwrap();
+    }
+
+    #[test]
+    fn scrape_mallinfo_metrics_does_not_panic() {
+        scrape_mallinfo_metrics();
+    }
 }
@@ -2,8 +2,8 @@
 //!
 //! ## Conditional Compilation
 //!
-//! Presently, only configuration for "The GNU Allocator" from `glibc` is supported. All other
-//! allocators are ignored.
+//! Presently, only configuration and statistics for "The GNU Allocator" from `glibc` are
+//! supported. All other allocators are ignored.
 //!
 //! It is assumed that if the following two statements are correct then we should expect to
 //! configure `glibc`:
@@ -38,7 +38,7 @@ mod interface {
 #[cfg(any(not(target_os = "linux"), target_env = "musl"))]
 mod interface {
     #[allow(dead_code, clippy::unnecessary_wraps)]
-    pub fn configure_memory_allocator() -> Result<(), String> {
+    pub fn configure_memory_allocator(_arena_max: Option<usize>) -> Result<(), String> {
         Ok(())
     }
 
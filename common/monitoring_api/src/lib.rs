@@ -55,6 +55,10 @@ pub struct Config {
     /// Path for the cold database required for fetching beacon db size metrics.
     /// Note: not relevant for validator and system metrics.
     pub freezer_db_path: Option<PathBuf>,
+    /// User-defined update period in seconds. Defaults to `UPDATE_DURATION` if not set.
+    pub update_period_secs: Option<u64>,
+    /// Do not send system metrics (CPU, memory, disk, network and OS) to the remote endpoint.
+    pub skip_system_metrics: bool,
 }
 
 #[derive(Clone)]
@@ -65,6 +69,8 @@ pub struct MonitoringHttpClient {
     /// Path to the freezer database.
     freezer_db_path: Option<PathBuf>,
     monitoring_endpoint: SensitiveUrl,
+    update_period: Duration,
+    skip_system_metrics: bool,
     log: slog::Logger,
 }
 
@@ -76,6 +82,10 @@ impl MonitoringHttpClient {
             freezer_db_path: config.freezer_db_path.clone(),
             monitoring_endpoint: SensitiveUrl::parse(&config.monitoring_endpoint)
                 .map_err(|e| format!("Invalid monitoring endpoint: {:?}", e))?,
+            update_period: Duration::from_secs(
+                config.update_period_secs.unwrap_or(UPDATE_DURATION),
+            ),
+            skip_system_metrics: config.skip_system_metrics,
             log,
         })
     }
@@ -96,14 +106,26 @@ impl MonitoringHttpClient {
 
     /// Creates a task which periodically sends the provided process metrics
     /// to the configured remote endpoint.
-    pub fn auto_update(self, executor: TaskExecutor, processes: Vec<ProcessType>) {
+    ///
+    /// System metrics are omitted if the client was configured to skip them.
+    pub fn auto_update(self, executor: TaskExecutor, mut processes: Vec<ProcessType>) {
+        if self.skip_system_metrics {
+            processes.retain(|process| *process != ProcessType::System);
+        }
+
         let mut interval = interval_at(
             // Have some initial delay for the metrics to get initialized
             Instant::now() + Duration::from_secs(25),
-            Duration::from_secs(UPDATE_DURATION),
+            self.update_period,
         );
 
-        info!(self.log, "Starting monitoring api"; "endpoint" => %self.monitoring_endpoint);
+        info!(
+            self.log,
+            "Starting monitoring api";
+            "endpoint" => %self.monitoring_endpoint,
+            "update_period" => ?self.update_period,
+            "processes" => ?processes,
+        );
 
         let update_future = async move {
             loop {
@@ -1,4 +1,7 @@
 mod metrics;
+mod shutdown_hooks;
+
+pub use shutdown_hooks::{ShutdownHooks, ShutdownStage};
 
 use futures::channel::mpsc::Sender;
 use futures::prelude::*;
@@ -36,6 +39,8 @@ pub struct TaskExecutor {
     ///
     /// The task must provide a reason for shutting down.
     signal_tx: Sender<ShutdownReason>,
+    /// Futures which run in stages when a graceful shutdown is requested.
+    shutdown_hooks: ShutdownHooks,
 
     log: slog::Logger,
 }
@@ -55,16 +60,24 @@ impl TaskExecutor {
             runtime,
             exit,
             signal_tx,
+            shutdown_hooks: ShutdownHooks::default(),
             log,
         }
     }
 
+    /// Returns a task executor which registers its shutdown hooks with `shutdown_hooks`.
+    pub fn with_shutdown_hooks(mut self, shutdown_hooks: ShutdownHooks) -> Self {
+        self.shutdown_hooks = shutdown_hooks;
+        self
+    }
+
     /// Clones the task executor adding a service name.
     pub fn clone_with_name(&self, service_name: String) -> Self {
         TaskExecutor {
             runtime: self.runtime.clone(),
             exit: self.exit.clone(),
             signal_tx: self.signal_tx.clone(),
+            shutdown_hooks: self.shutdown_hooks.clone(),
             log: self.log.new(o!("service" => service_name)),
         }
     }
@@ -277,6 +290,18 @@ impl TaskExecutor {
         self.exit.clone()
     }
 
+    /// Run `hook` during `stage` of a graceful shutdown, before tasks are cancelled.
+    ///
+    /// The hook is not run if the process exits without a graceful shutdown, e.g. due to a failure.
+    pub fn on_shutdown(
+        &self,
+        stage: ShutdownStage,
+        name: &'static str,
+        hook: impl Future<Output = ()> + Send + 'static,
+    ) {
+        self.shutdown_hooks.register(stage, name, hook)
+    }
+
     /// Get a channel to request shutting down.
     pub fn shutdown_sender(&self) -> Sender<ShutdownReason> {
         self.signal_tx.clone()
@@ -0,0 +1,90 @@
+use futures::future::{join_all, BoxFuture, FutureExt};
+use slog::{debug, info, warn, Logger};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The stages of a graceful shutdown, in the order they run.
+///
+/// Every hook of a stage completes before the hooks of the next stage begin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShutdownStage {
+    /// Stop accepting new work from the network.
+    StopGossip,
+    /// Complete work which has already been accepted.
+    DrainQueues,
+    /// Write in-memory state to disk.
+    Persist,
+    /// Say goodbye to peers and close connections.
+    Disconnect,
+}
+
+struct ShutdownHook {
+    stage: ShutdownStage,
+    name: &'static str,
+    future: BoxFuture<'static, ()>,
+}
+
+/// Futures which run when a shutdown is requested, before tasks are cancelled.
+#[derive(Clone, Default)]
+pub struct ShutdownHooks {
+    hooks: Arc<Mutex<Vec<ShutdownHook>>>,
+}
+
+impl ShutdownHooks {
+    /// Run `hook` during `stage` of a graceful shutdown.
+    pub fn register(
+        &self,
+        stage: ShutdownStage,
+        name: &'static str,
+        hook: impl Future<Output = ()> + Send + 'static,
+    ) {
+        if let Ok(mut hooks) = self.hooks.lock() {
+            hooks.push(ShutdownHook {
+                stage,
+                name,
+                future: hook.boxed(),
+            });
+        }
+    }
+
+    /// Run each stage in turn, allowing each at most `stage_timeout` to complete.
+    ///
+    /// Returns `false` if any stage timed out. Hooks are run at most once.
+    pub async fn run(&self, stage_timeout: Duration, log: &Logger) -> bool {
+        let mut hooks = match self.hooks.lock() {
+            Ok(mut hooks) => std::mem::take(&mut *hooks),
+            Err(_) => return false,
+        };
+        // The sort is stable, so hooks of a stage keep their order of registration.
+        hooks.sort_by_key(|hook| hook.stage);
+
+        let mut completed = true;
+        while let Some(stage) = hooks.first().map(|hook| hook.stage) {
+            let count = hooks.iter().take_while(|hook| hook.stage == stage).count();
+            let (names, futures): (Vec<_>, Vec<_>) = hooks
+                .drain(..count)
+                .map(|hook| (hook.name, hook.future))
+                .unzip();
+
+            debug!(log, "Starting shutdown stage"; "stage" => ?stage, "hooks" => ?names);
+            if tokio::time::timeout(stage_timeout, join_all(futures))
+                .await
+                .is_err()
+            {
+                warn!(
+                    log,
+                    "Shutdown stage timed out";
+                    "stage" => ?stage,
+                    "timeout_secs" => stage_timeout.as_secs(),
+                );
+                completed = false;
+            }
+        }
+
+        if completed {
+            info!(log, "Graceful shutdown complete");
+        }
+        completed
+    }
+}
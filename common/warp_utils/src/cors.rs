@@ -1,5 +1,76 @@
 use std::net::Ipv4Addr;
 use warp::filters::cors::Builder;
+use warp::http::{header::HeaderName, Method};
+
+/// The CORS settings used by a server when none are configured.
+pub struct CorsDefaults {
+    /// The listen address and port of the server, which is the only allowed origin by default.
+    pub origin: (Ipv4Addr, u16),
+    pub methods: &'static [&'static str],
+    pub headers: &'static [&'static str],
+}
+
+/// Build a `cors::Builder` from the CORS settings of a server.
+///
+/// `allow_origin`, `allow_methods` and `allow_headers` are comma-separated lists which replace the
+/// corresponding default when present.
+///
+/// Browsers refuse to send credentials when any of the allowed origins, methods or headers is the
+/// `*` wildcard, so such settings are rejected rather than silently failing in the browser.
+pub fn build_cors(
+    allow_origin: Option<&str>,
+    allow_methods: Option<&str>,
+    allow_headers: Option<&str>,
+    allow_credentials: bool,
+    defaults: CorsDefaults,
+) -> Result<Builder, String> {
+    let methods = parse_list(allow_methods, defaults.methods);
+    let headers = parse_list(allow_headers, defaults.headers);
+
+    if allow_credentials {
+        let origins = parse_list(allow_origin, &[]);
+        if origins
+            .iter()
+            .chain(&methods)
+            .chain(&headers)
+            .any(|s| *s == "*")
+        {
+            return Err(
+                "The * wildcard cannot be used for the allowed origins, methods or \
+                headers of a server which allows credentials"
+                    .to_string(),
+            );
+        }
+    }
+
+    for method in &methods {
+        Method::from_bytes(method.as_bytes())
+            .map_err(|_| format!("{} is not a valid HTTP method", method))?;
+    }
+    for header in &headers {
+        HeaderName::from_bytes(header.as_bytes())
+            .map_err(|_| format!("{} is not a valid HTTP header name", header))?;
+    }
+
+    let builder = warp::cors()
+        .allow_methods(methods)
+        .allow_headers(headers)
+        .allow_credentials(allow_credentials);
+
+    set_builder_origins(builder, allow_origin, defaults.origin)
+}
+
+/// Split a comma-separated list, or return `default` if there is no list.
+fn parse_list<'a>(list: Option<&'a str>, default: &[&'a str]) -> Vec<&'a str> {
+    match list {
+        Some(list) => list
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect(),
+        None => default.to_vec(),
+    }
+}
 
 /// Configure a `cors::Builder`.
 ///
@@ -73,4 +144,48 @@ mod test {
         verify_cors_origin_str("127.0.0.1").unwrap_err();
         verify_cors_origin_str("localhost").unwrap_err();
     }
+
+    fn defaults() -> CorsDefaults {
+        CorsDefaults {
+            origin: (Ipv4Addr::new(127, 0, 0, 1), 5052),
+            methods: &["GET", "POST"],
+            headers: &["Content-Type"],
+        }
+    }
+
+    #[test]
+    fn custom_methods_and_headers() {
+        assert!(build_cors(None, None, None, false, defaults()).is_ok());
+        assert!(build_cors(
+            Some("http://localhost:8000,http://localhost:8001"),
+            Some("GET, POST, PATCH"),
+            Some("Content-Type, Authorization, X-Requested-With"),
+            true,
+            defaults(),
+        )
+        .is_ok());
+        assert!(build_cors(None, Some("GET,"), Some("*"), false, defaults()).is_ok());
+    }
+
+    #[test]
+    fn invalid_methods_and_headers() {
+        assert!(build_cors(None, Some("GET,NOT A METHOD"), None, false, defaults()).is_err());
+        assert!(build_cors(None, None, Some("Content Type"), false, defaults()).is_err());
+    }
+
+    #[test]
+    fn wildcard_with_credentials() {
+        assert!(build_cors(Some("*"), None, None, false, defaults()).is_ok());
+        assert!(build_cors(Some("*"), None, None, true, defaults()).is_err());
+        assert!(build_cors(
+            Some("http://localhost:8000,*"),
+            None,
+            None,
+            true,
+            defaults()
+        )
+        .is_err());
+        assert!(build_cors(None, Some("*"), None, true, defaults()).is_err());
+        assert!(build_cors(None, None, Some("*"), true, defaults()).is_err());
+    }
 }
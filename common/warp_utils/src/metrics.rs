@@ -1,5 +1,6 @@
-use eth2::lighthouse::{ProcessHealth, SystemHealth};
+use eth2::lighthouse::{DiskHealth, ProcessHealth, SystemHealth};
 use lighthouse_metrics::*;
+use std::path::Path;
 
 lazy_static::lazy_static! {
     pub static ref PROCESS_NUM_THREADS: Result<IntGauge> = try_create_int_gauge(
@@ -18,6 +19,10 @@ lazy_static::lazy_static! {
         "process_cpu_seconds_total",
         "Total cpu time taken by the current process"
     );
+    pub static ref PROCESS_OPEN_FDS: Result<IntGauge> = try_create_int_gauge(
+        "process_open_fds",
+        "Number of file descriptors open by the current process"
+    );
     pub static ref SYSTEM_VIRT_MEM_TOTAL: Result<IntGauge> =
         try_create_int_gauge("system_virt_mem_total_bytes", "Total system virtual memory");
     pub static ref SYSTEM_VIRT_MEM_AVAILABLE: Result<IntGauge> = try_create_int_gauge(
@@ -69,6 +74,15 @@ lazy_static::lazy_static! {
     pub static ref DISK_WRITES: Result<IntGauge> =
         try_create_int_gauge("disk_node_writes_total", "Number of disk writes");
 
+    pub static ref DATADIR_DISK_BYTES_TOTAL: Result<IntGauge> = try_create_int_gauge(
+        "datadir_disk_bytes_total",
+        "Total capacity of the disk containing the data directory"
+    );
+    pub static ref DATADIR_DISK_BYTES_FREE: Result<IntGauge> = try_create_int_gauge(
+        "datadir_disk_bytes_free",
+        "Free space in the disk containing the data directory"
+    );
+
     pub static ref NETWORK_BYTES_RECEIVED: Result<IntGauge> =
         try_create_int_gauge("network_node_bytes_total_received", "Total bytes received over all network interfaces");
     pub static ref NETWORK_BYTES_SENT: Result<IntGauge> =
@@ -91,6 +105,18 @@ pub fn scrape_process_health_metrics() {
         set_gauge(&PROCESS_RES_MEM, health.pid_mem_resident_set_size as i64);
         set_gauge(&PROCESS_VIRT_MEM, health.pid_mem_virtual_memory_size as i64);
         set_gauge(&PROCESS_SECONDS, health.pid_process_seconds_total as i64);
+        set_gauge(&PROCESS_OPEN_FDS, health.pid_num_open_fds as i64);
+    }
+}
+
+/// Scrape the disk usage of the volume containing the data directory at `path`, which may differ
+/// from the root volume reported by `scrape_system_health_metrics`.
+pub fn scrape_datadir_metrics(path: &Path) {
+    // This will silently fail if we are unable to observe the health. This is desired behaviour
+    // since we don't support `Health` for all platforms.
+    if let Ok(health) = DiskHealth::observe(path) {
+        set_gauge(&DATADIR_DISK_BYTES_TOTAL, health.disk_bytes_total as i64);
+        set_gauge(&DATADIR_DISK_BYTES_FREE, health.disk_bytes_free as i64);
     }
 }
 
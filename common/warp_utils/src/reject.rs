@@ -118,6 +118,15 @@ pub fn not_synced(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(NotSynced(msg))
 }
 
+#[derive(Debug)]
+pub struct ExecutionLayerOffline(pub String);
+
+impl Reject for ExecutionLayerOffline {}
+
+pub fn execution_layer_offline(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(ExecutionLayerOffline(msg))
+}
+
 #[derive(Debug)]
 pub struct InvalidAuthorization(pub String);
 
@@ -127,6 +136,24 @@ pub fn invalid_auth(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(InvalidAuthorization(msg))
 }
 
+#[derive(Debug)]
+pub struct PayloadTooLarge(pub String);
+
+impl Reject for PayloadTooLarge {}
+
+pub fn payload_too_large(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(PayloadTooLarge(msg))
+}
+
+#[derive(Debug)]
+pub struct LengthRequired(pub String);
+
+impl Reject for LengthRequired {}
+
+pub fn length_required(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(LengthRequired(msg))
+}
+
 #[derive(Debug)]
 pub struct IndexedBadRequestErrors {
     pub message: String,
@@ -201,9 +228,18 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::NotSynced>() {
         code = StatusCode::SERVICE_UNAVAILABLE;
         message = format!("SERVICE_UNAVAILABLE: beacon node is syncing: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::ExecutionLayerOffline>() {
+        code = StatusCode::SERVICE_UNAVAILABLE;
+        message = format!("SERVICE_UNAVAILABLE: execution engine is offline: {}", e.0);
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::PayloadTooLarge>() {
+        code = StatusCode::PAYLOAD_TOO_LARGE;
+        message = format!("PAYLOAD_TOO_LARGE: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::LengthRequired>() {
+        code = StatusCode::LENGTH_REQUIRED;
+        message = format!("LENGTH_REQUIRED: {}", e.0);
     } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
         code = StatusCode::BAD_REQUEST;
         message = format!("BAD_REQUEST: missing {} header", e.name());
@@ -0,0 +1,11 @@
+//! Configuration shared by the HTTP servers which may be served over TLS.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Configuration used when serving a HTTP server over TLS.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+}
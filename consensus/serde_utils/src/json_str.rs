@@ -0,0 +1,61 @@
+//! Serialize a datatype as a JSON blob within a single string.
+use serde::{
+    de::{DeserializeOwned, Error as _},
+    ser::Error as _,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Serialize as a JSON object within a string.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    serializer.serialize_str(&serde_json::to_string(value).map_err(S::Error::custom)?)
+}
+
+/// Deserialize a JSON object embedded in a string.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let json_str = String::deserialize(deserializer)?;
+    serde_json::from_str(&json_str).map_err(D::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Inner {
+        a: u64,
+        b: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(transparent)]
+    struct Wrapper {
+        #[serde(with = "super")]
+        inner: Inner,
+    }
+
+    #[test]
+    fn roundtrip() {
+        let wrapper = Wrapper {
+            inner: Inner {
+                a: 42,
+                b: "hello".to_string(),
+            },
+        };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(json, r#""{\"a\":42,\"b\":\"hello\"}""#);
+        assert_eq!(serde_json::from_str::<Wrapper>(&json).unwrap(), wrapper);
+    }
+
+    #[test]
+    fn not_a_string() {
+        serde_json::from_str::<Wrapper>(r#"{"a":42,"b":"hello"}"#).unwrap_err();
+    }
+}
@@ -155,6 +155,12 @@ pub mod quoted_u64 {
     define_mod!(u64, visit_u64);
 }
 
+pub mod quoted_i64 {
+    use super::*;
+
+    define_mod!(i64, visit_i64);
+}
+
 pub mod quoted_u256 {
     use super::*;
 
@@ -0,0 +1,194 @@
+#![allow(deprecated)]
+
+//! Benchmarks for the state transition, run with `cargo bench -p state_processing`.
+//!
+//! All states are produced by a chain with full participation. To evaluate a change, save a
+//! baseline on the target branch with `make bench-baseline`, then run `make bench-check` on the
+//! branch with the change. `bench-check` fails if any benchmark is more than 10% slower than the
+//! baseline, and is run by CI on every pull request.
+
+use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
+use criterion::Criterion;
+use criterion::{black_box, criterion_group, criterion_main, Benchmark};
+use state_processing::{
+    per_block_processing, per_epoch_processing, BlockReplayer, BlockSignatureStrategy,
+    VerifyBlockRoot,
+};
+use types::{
+    BeaconState, ChainSpec, Epoch, EthSpec, ForkName, MainnetEthSpec, SignedBeaconBlock, Unsigned,
+};
+
+type E = MainnetEthSpec;
+
+const VALIDATOR_COUNT: usize = 16_384;
+
+/// The number of epochs to produce before the benchmarked segment, so that the states have full
+/// participation in both the previous and current epochs.
+const WARMUP_EPOCHS: u64 = 2;
+
+/// The states and blocks used by the benchmarks.
+struct Inputs {
+    /// A state at the last slot of an epoch, prior to epoch processing.
+    epoch_state: BeaconState<E>,
+    /// A block containing the maximum number of attestations, each from a different committee
+    /// member so that none can be aggregated.
+    block: SignedBeaconBlock<E>,
+    /// The pre-state of `block`, advanced to its slot.
+    block_pre_state: BeaconState<E>,
+    /// An epoch of blocks with full participation.
+    segment: Vec<SignedBeaconBlock<E>>,
+    /// The pre-state of `segment`.
+    segment_pre_state: BeaconState<E>,
+}
+
+fn get_spec() -> ChainSpec {
+    ForkName::Altair.make_genesis_spec(E::default_spec())
+}
+
+fn get_harness() -> BeaconChainHarness<EphemeralHarnessType<E>> {
+    let harness = BeaconChainHarness::builder(E::default())
+        .spec(get_spec())
+        .deterministic_keypairs(VALIDATOR_COUNT)
+        .fresh_ephemeral_store()
+        .build();
+    harness.advance_slot();
+    harness
+}
+
+fn get_inputs() -> Inputs {
+    let harness = get_harness();
+
+    let warmup_end_slot = (Epoch::new(WARMUP_EPOCHS) - 1).end_slot(E::slots_per_epoch());
+    harness.extend_to_slot(warmup_end_slot);
+    let segment_pre_state = harness.get_current_state();
+
+    harness.extend_slots(E::slots_per_epoch() as usize);
+    let segment = harness
+        .chain
+        .chain_dump()
+        .expect("should dump chain")
+        .into_iter()
+        .map(|snapshot| snapshot.beacon_block)
+        .filter(|block| block.slot() > segment_pre_state.slot())
+        .collect::<Vec<_>>();
+
+    let (mut epoch_state, state_root) = harness.get_current_state_and_root();
+    let head_block_root = harness
+        .chain
+        .head_info()
+        .expect("should get head")
+        .block_root;
+    let head_slot = epoch_state.slot();
+    epoch_state
+        .build_all_caches(&harness.spec)
+        .expect("should build caches");
+
+    let attestations = harness
+        .make_unaggregated_attestations(
+            &harness.get_all_validators(),
+            &epoch_state,
+            state_root,
+            head_block_root.into(),
+            head_slot,
+        )
+        .into_iter()
+        .flatten()
+        .map(|(attestation, _)| attestation)
+        .take(<E as EthSpec>::MaxAttestations::to_usize())
+        .collect::<Vec<_>>();
+    let (block, block_pre_state) =
+        harness.make_block_with_modifier(epoch_state.clone(), head_slot + 1, |block| {
+            *block.body_mut().attestations_mut() = attestations.into();
+        });
+
+    Inputs {
+        epoch_state,
+        block,
+        block_pre_state,
+        segment,
+        segment_pre_state,
+    }
+}
+
+fn all_benches(c: &mut Criterion) {
+    let spec = get_spec();
+    let inputs = get_inputs();
+    let group = format!("{}_validators", VALIDATOR_COUNT);
+
+    let inner_state = inputs.epoch_state.clone();
+    let inner_spec = spec.clone();
+    c.bench(
+        &group,
+        Benchmark::new("per_epoch_processing", move |b| {
+            b.iter_batched_ref(
+                || inner_state.clone(),
+                |state| {
+                    black_box(
+                        per_epoch_processing(state, &inner_spec).expect("should process epoch"),
+                    )
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        })
+        .sample_size(10),
+    );
+
+    for (name, strategy) in [
+        ("verify_bulk", BlockSignatureStrategy::VerifyBulk),
+        ("no_verification", BlockSignatureStrategy::NoVerification),
+    ] {
+        let inner_state = inputs.block_pre_state.clone();
+        let inner_block = inputs.block.clone();
+        let inner_spec = spec.clone();
+        c.bench(
+            &group,
+            Benchmark::new(
+                format!("per_block_processing/max_attestations/{}", name),
+                move |b| {
+                    b.iter_batched_ref(
+                        || inner_state.clone(),
+                        |state| {
+                            per_block_processing(
+                                state,
+                                &inner_block,
+                                None,
+                                strategy,
+                                VerifyBlockRoot::True,
+                                &inner_spec,
+                            )
+                            .expect("should process block")
+                        },
+                        criterion::BatchSize::SmallInput,
+                    )
+                },
+            )
+            .sample_size(10),
+        );
+    }
+
+    let inner_state = inputs.segment_pre_state;
+    let inner_segment = inputs.segment;
+    let inner_spec = spec;
+    c.bench(
+        &group,
+        Benchmark::new("chain_segment/one_epoch", move |b| {
+            b.iter_batched(
+                || (inner_state.clone(), inner_segment.clone()),
+                |(state, segment)| {
+                    black_box(
+                        BlockReplayer::<E>::new(state, &inner_spec)
+                            .block_signature_strategy(BlockSignatureStrategy::VerifyBulk)
+                            .apply_blocks(segment, None)
+                            .expect("should apply segment")
+                            .into_state(),
+                    )
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        })
+        .sample_size(10),
+    );
+}
+
+criterion_group!(benches, all_benches);
+criterion_main!(benches);
@@ -1,5 +1,6 @@
 #![cfg(feature = "metrics")]
 
+use crate::per_epoch_processing;
 use lazy_static::lazy_static;
 pub use lighthouse_metrics::*;
 
@@ -23,4 +24,13 @@ lazy_static! {
         "beacon_participation_prev_epoch_active_gwei_total",
         "Total effective balance (gwei) of validators active in the previous epoch"
     );
+
+    /*
+     * Epoch Processing
+     */
+    pub static ref EPOCH_PROCESSING_STAGE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        per_epoch_processing::EPOCH_PROCESSING_STAGE_TIMES,
+        "Time taken by each stage of per-epoch processing",
+        &["stage"]
+    );
 }
@@ -20,6 +20,10 @@ pub mod slashings;
 pub mod tests;
 pub mod weigh_justification_and_finalization;
 
+/// The name of the histogram which records the duration of each stage of epoch processing. Each
+/// observation is labelled with the name of its `stage`.
+pub const EPOCH_PROCESSING_STAGE_TIMES: &str = "beacon_epoch_processing_stage_seconds";
+
 /// Performs per-epoch processing on some BeaconState.
 ///
 /// Mutates the given `BeaconState`, returning early if an error is encountered. If an error is
@@ -65,3 +69,15 @@ impl Delta {
         self.penalize(other.penalties)
     }
 }
+
+/// Runs `f`, which performs the `stage` of epoch processing, recording its duration in the
+/// `EPOCH_PROCESSING_STAGE_TIMES` histogram if the `metrics` feature is enabled.
+pub(crate) fn time_stage<R>(stage: &str, f: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "metrics")]
+    let _timer =
+        crate::metrics::start_timer_vec(&crate::metrics::EPOCH_PROCESSING_STAGE_TIMES, &[stage]);
+    #[cfg(not(feature = "metrics"))]
+    let _ = stage;
+
+    f()
+}
@@ -1,4 +1,6 @@
-use super::{process_registry_updates, process_slashings, EpochProcessingSummary, Error};
+use super::{
+    process_registry_updates, process_slashings, time_stage, EpochProcessingSummary, Error,
+};
 use crate::per_epoch_processing::{
     effective_balance_updates::process_effective_balance_updates,
     historical_roots_update::process_historical_roots_update,
@@ -24,37 +26,52 @@ pub fn process_epoch<T: EthSpec>(
     spec: &ChainSpec,
 ) -> Result<EpochProcessingSummary<T>, Error> {
     // Ensure the committee caches are built.
-    state.build_committee_cache(RelativeEpoch::Previous, spec)?;
-    state.build_committee_cache(RelativeEpoch::Current, spec)?;
-    state.build_committee_cache(RelativeEpoch::Next, spec)?;
+    time_stage("committee_caches", || -> Result<(), Error> {
+        state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, spec)?;
+        state.build_committee_cache(RelativeEpoch::Next, spec)?;
+        Ok(())
+    })?;
 
     // Pre-compute participating indices and total balances.
-    let participation_cache = ParticipationCache::new(state, spec)?;
+    let participation_cache = time_stage("participation_cache", || {
+        ParticipationCache::new(state, spec)
+    })?;
     let sync_committee = state.current_sync_committee()?.clone();
 
     // Justification and finalization.
-    process_justification_and_finalization(state, &participation_cache)?;
+    time_stage("justification_and_finalization", || {
+        process_justification_and_finalization(state, &participation_cache)
+    })?;
 
-    process_inactivity_updates(state, &participation_cache, spec)?;
+    time_stage("inactivity_updates", || {
+        process_inactivity_updates(state, &participation_cache, spec)
+    })?;
 
     // Rewards and Penalties.
-    process_rewards_and_penalties(state, &participation_cache, spec)?;
+    time_stage("rewards_and_penalties", || {
+        process_rewards_and_penalties(state, &participation_cache, spec)
+    })?;
 
     // Registry Updates.
-    process_registry_updates(state, spec)?;
+    time_stage("registry_updates", || process_registry_updates(state, spec))?;
 
     // Slashings.
-    process_slashings(
-        state,
-        participation_cache.current_epoch_total_active_balance(),
-        spec,
-    )?;
+    time_stage("slashings", || {
+        process_slashings(
+            state,
+            participation_cache.current_epoch_total_active_balance(),
+            spec,
+        )
+    })?;
 
     // Reset eth1 data votes.
     process_eth1_data_reset(state)?;
 
     // Update effective balances with hysteresis (lag).
-    process_effective_balance_updates(state, spec)?;
+    time_stage("effective_balance_updates", || {
+        process_effective_balance_updates(state, spec)
+    })?;
 
     // Reset slashings
     process_slashings_reset(state)?;
@@ -63,15 +80,21 @@ pub fn process_epoch<T: EthSpec>(
     process_randao_mixes_reset(state)?;
 
     // Set historical root accumulator
-    process_historical_roots_update(state)?;
+    time_stage("historical_roots_update", || {
+        process_historical_roots_update(state)
+    })?;
 
     // Rotate current/previous epoch participation
-    process_participation_flag_updates(state)?;
+    time_stage("participation_flag_updates", || {
+        process_participation_flag_updates(state)
+    })?;
 
-    process_sync_committee_updates(state, spec)?;
+    time_stage("sync_committee_updates", || {
+        process_sync_committee_updates(state, spec)
+    })?;
 
     // Rotate the epoch caches to suit the epoch transition.
-    state.advance_caches(spec)?;
+    time_stage("advance_caches", || state.advance_caches(spec))?;
 
     Ok(EpochProcessingSummary::Altair {
         participation_cache,
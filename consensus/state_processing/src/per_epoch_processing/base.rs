@@ -1,4 +1,6 @@
-use super::{process_registry_updates, process_slashings, EpochProcessingSummary, Error};
+use super::{
+    process_registry_updates, process_slashings, time_stage, EpochProcessingSummary, Error,
+};
 use crate::per_epoch_processing::{
     effective_balance_updates::process_effective_balance_updates,
     historical_roots_update::process_historical_roots_update,
@@ -20,37 +22,51 @@ pub fn process_epoch<T: EthSpec>(
     spec: &ChainSpec,
 ) -> Result<EpochProcessingSummary<T>, Error> {
     // Ensure the committee caches are built.
-    state.build_committee_cache(RelativeEpoch::Previous, spec)?;
-    state.build_committee_cache(RelativeEpoch::Current, spec)?;
-    state.build_committee_cache(RelativeEpoch::Next, spec)?;
+    time_stage("committee_caches", || -> Result<(), Error> {
+        state.build_committee_cache(RelativeEpoch::Previous, spec)?;
+        state.build_committee_cache(RelativeEpoch::Current, spec)?;
+        state.build_committee_cache(RelativeEpoch::Next, spec)?;
+        Ok(())
+    })?;
 
     // Load the struct we use to assign validators into sets based on their participation.
     //
     // E.g., attestation in the previous epoch, attested to the head, etc.
-    let mut validator_statuses = ValidatorStatuses::new(state, spec)?;
-    validator_statuses.process_attestations(state)?;
+    let mut validator_statuses = time_stage("validator_statuses", || -> Result<_, Error> {
+        let mut validator_statuses = ValidatorStatuses::new(state, spec)?;
+        validator_statuses.process_attestations(state)?;
+        Ok(validator_statuses)
+    })?;
 
     // Justification and finalization.
-    process_justification_and_finalization(state, &validator_statuses.total_balances, spec)?;
+    time_stage("justification_and_finalization", || {
+        process_justification_and_finalization(state, &validator_statuses.total_balances, spec)
+    })?;
 
     // Rewards and Penalties.
-    process_rewards_and_penalties(state, &mut validator_statuses, spec)?;
+    time_stage("rewards_and_penalties", || {
+        process_rewards_and_penalties(state, &mut validator_statuses, spec)
+    })?;
 
     // Registry Updates.
-    process_registry_updates(state, spec)?;
+    time_stage("registry_updates", || process_registry_updates(state, spec))?;
 
     // Slashings.
-    process_slashings(
-        state,
-        validator_statuses.total_balances.current_epoch(),
-        spec,
-    )?;
+    time_stage("slashings", || {
+        process_slashings(
+            state,
+            validator_statuses.total_balances.current_epoch(),
+            spec,
+        )
+    })?;
 
     // Reset eth1 data votes.
     process_eth1_data_reset(state)?;
 
     // Update effective balances with hysteresis (lag).
-    process_effective_balance_updates(state, spec)?;
+    time_stage("effective_balance_updates", || {
+        process_effective_balance_updates(state, spec)
+    })?;
 
     // Reset slashings
     process_slashings_reset(state)?;
@@ -59,13 +75,17 @@ pub fn process_epoch<T: EthSpec>(
     process_randao_mixes_reset(state)?;
 
     // Set historical root accumulator
-    process_historical_roots_update(state)?;
+    time_stage("historical_roots_update", || {
+        process_historical_roots_update(state)
+    })?;
 
     // Rotate current/previous epoch attestations
-    process_participation_record_updates(state)?;
+    time_stage("participation_record_updates", || {
+        process_participation_record_updates(state)
+    })?;
 
     // Rotate the epoch caches to suit the epoch transition.
-    state.advance_caches(spec)?;
+    time_stage("advance_caches", || state.advance_caches(spec))?;
 
     Ok(EpochProcessingSummary::Base {
         total_balances: validator_statuses.total_balances,
@@ -99,6 +99,8 @@ pub enum Error {
     SszTypesError(ssz_types::Error),
     TreeHashCacheNotInitialized,
     NonLinearTreeHashCacheHistory,
+    /// A Merkle proof was requested for a generalized index which is not supported.
+    IndexNotSupported(usize),
     TreeHashCacheSkippedSlot {
         cache: Slot,
         state: Slot,
@@ -1570,6 +1572,50 @@ impl<T: EthSpec> BeaconState<T> {
         }
     }
 
+    /// Computes a Merkle proof of the node at `generalized_index` against the tree hash root of
+    /// the state, in bottom-up order.
+    ///
+    /// Only the indices required by light clients are supported, see the constants in
+    /// `light_client_update`. These indices are only valid for Altair and later states.
+    pub fn compute_merkle_proof(
+        &mut self,
+        generalized_index: usize,
+    ) -> Result<Vec<Hash256>, Error> {
+        if let BeaconState::Base(_) = self {
+            return Err(Error::IncorrectStateVariant);
+        }
+
+        // The depth of the tree formed by the fields of the state.
+        let depth = light_client_update::CURRENT_SYNC_COMMITTEE_PROOF_LEN;
+        let num_fields = 1 << depth;
+
+        let field_index = match generalized_index {
+            light_client_update::CURRENT_SYNC_COMMITTEE_INDEX
+            | light_client_update::NEXT_SYNC_COMMITTEE_INDEX => generalized_index - num_fields,
+            // The finalized root is the right child of the `finalized_checkpoint` field.
+            light_client_update::FINALIZED_ROOT_INDEX => generalized_index / 2 - num_fields,
+            _ => return Err(Error::IndexNotSupported(generalized_index)),
+        };
+
+        self.initialize_tree_hash_cache();
+        let mut cache = self
+            .tree_hash_cache_mut()
+            .take()
+            .ok_or(Error::TreeHashCacheNotInitialized)?;
+        let leaves = cache.recalculate_tree_hash_leaves(self)?;
+        self.tree_hash_cache_mut().restore(cache);
+
+        let tree = merkle_proof::MerkleTree::create(&leaves, depth);
+        let (_, mut proof) = tree.generate_proof(field_index, depth);
+
+        if generalized_index == light_client_update::FINALIZED_ROOT_INDEX {
+            // The sibling of the finalized root is the finalized epoch.
+            proof.insert(0, self.finalized_checkpoint().epoch.tree_hash_root());
+        }
+
+        Ok(proof)
+    }
+
     /// Completely drops the tree hash cache, replacing it with a new, empty cache.
     pub fn drop_tree_hash_cache(&mut self) {
         self.tree_hash_cache_mut().uninitialize();
@@ -610,3 +610,49 @@ fn tree_hash_cache_linear_history_long_skip() {
         target_slot
     );
 }
+
+#[test]
+fn light_client_merkle_proofs() {
+    use beacon_chain::types::light_client_update::*;
+
+    let mut rng = XorShiftRng::from_seed([42; 16]);
+
+    let mut state: BeaconState<MainnetEthSpec> =
+        BeaconState::Altair(BeaconStateAltair::random_for_test(&mut rng));
+    let state_root = state.update_tree_hash_cache().unwrap();
+
+    let cases = [
+        (
+            CURRENT_SYNC_COMMITTEE_INDEX,
+            CURRENT_SYNC_COMMITTEE_PROOF_LEN,
+            state.current_sync_committee().unwrap().tree_hash_root(),
+        ),
+        (
+            NEXT_SYNC_COMMITTEE_INDEX,
+            NEXT_SYNC_COMMITTEE_PROOF_LEN,
+            state.next_sync_committee().unwrap().tree_hash_root(),
+        ),
+        (
+            FINALIZED_ROOT_INDEX,
+            FINALIZED_ROOT_PROOF_LEN,
+            state.finalized_checkpoint().root,
+        ),
+    ];
+
+    for (generalized_index, depth, leaf) in cases {
+        let proof = state.compute_merkle_proof(generalized_index).unwrap();
+        assert_eq!(proof.len(), depth);
+        assert!(merkle_proof::verify_merkle_proof(
+            leaf,
+            &proof,
+            depth,
+            generalized_index - (1 << depth),
+            state_root
+        ));
+    }
+
+    assert_eq!(
+        state.compute_merkle_proof(1),
+        Err(BeaconStateError::IndexNotSupported(1))
+    );
+}
@@ -210,12 +210,16 @@ impl<T: EthSpec> BeaconTreeHashCacheInner<T> {
         }
     }
 
-    /// Updates the cache and returns the tree hash root for the given `state`.
+    /// Updates the cache and returns the tree hash roots of each of the fields of the given
+    /// `state`, in order. These are the leaves of the `BeaconState` Merkle tree, without padding.
     ///
     /// The provided `state` should be a descendant of the last `state` given to this function, or
     /// the `Self::new` function. If the state is more than `SLOTS_PER_HISTORICAL_ROOT` slots
     /// after `self.previous_state` then the whole cache will be re-initialized.
-    pub fn recalculate_tree_hash_root(&mut self, state: &BeaconState<T>) -> Result<Hash256, Error> {
+    pub fn recalculate_tree_hash_leaves(
+        &mut self,
+        state: &BeaconState<T>,
+    ) -> Result<Vec<Hash256>, Error> {
         // If this cache has previously produced a root, ensure that it is in the state root
         // history of this state.
         //
@@ -244,123 +248,107 @@ impl<T: EthSpec> BeaconTreeHashCacheInner<T> {
             }
         }
 
-        let mut hasher = MerkleHasher::with_leaves(NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES);
+        let mut leaves = Vec::with_capacity(NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES);
 
-        hasher.write(state.genesis_time().tree_hash_root().as_bytes())?;
-        hasher.write(state.genesis_validators_root().tree_hash_root().as_bytes())?;
-        hasher.write(state.slot().tree_hash_root().as_bytes())?;
-        hasher.write(state.fork().tree_hash_root().as_bytes())?;
-        hasher.write(state.latest_block_header().tree_hash_root().as_bytes())?;
-        hasher.write(
+        leaves.push(state.genesis_time().tree_hash_root());
+        leaves.push(state.genesis_validators_root().tree_hash_root());
+        leaves.push(state.slot().tree_hash_root());
+        leaves.push(state.fork().tree_hash_root());
+        leaves.push(state.latest_block_header().tree_hash_root());
+        leaves.push(
             state
                 .block_roots()
-                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.block_roots)?
-                .as_bytes(),
-        )?;
-        hasher.write(
+                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.block_roots)?,
+        );
+        leaves.push(
             state
                 .state_roots()
-                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.state_roots)?
-                .as_bytes(),
-        )?;
-        hasher.write(
+                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.state_roots)?,
+        );
+        leaves.push(
             state
                 .historical_roots()
-                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.historical_roots)?
-                .as_bytes(),
-        )?;
-        hasher.write(state.eth1_data().tree_hash_root().as_bytes())?;
-        hasher.write(
-            self.eth1_data_votes
-                .recalculate_tree_hash_root(state)?
-                .as_bytes(),
-        )?;
-        hasher.write(state.eth1_deposit_index().tree_hash_root().as_bytes())?;
-        hasher.write(
+                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.historical_roots)?,
+        );
+        leaves.push(state.eth1_data().tree_hash_root());
+        leaves.push(self.eth1_data_votes.recalculate_tree_hash_root(state)?);
+        leaves.push(state.eth1_deposit_index().tree_hash_root());
+        leaves.push(
             self.validators
-                .recalculate_tree_hash_root(state.validators())?
-                .as_bytes(),
-        )?;
-        hasher.write(
+                .recalculate_tree_hash_root(state.validators())?,
+        );
+        leaves.push(
             state
                 .balances()
-                .recalculate_tree_hash_root(&mut self.balances_arena, &mut self.balances)?
-                .as_bytes(),
-        )?;
-        hasher.write(
+                .recalculate_tree_hash_root(&mut self.balances_arena, &mut self.balances)?,
+        );
+        leaves.push(
             state
                 .randao_mixes()
-                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.randao_mixes)?
-                .as_bytes(),
-        )?;
-        hasher.write(
+                .recalculate_tree_hash_root(&mut self.fixed_arena, &mut self.randao_mixes)?,
+        );
+        leaves.push(
             state
                 .slashings()
-                .recalculate_tree_hash_root(&mut self.slashings_arena, &mut self.slashings)?
-                .as_bytes(),
-        )?;
+                .recalculate_tree_hash_root(&mut self.slashings_arena, &mut self.slashings)?,
+        );
 
         // Participation
         if let BeaconState::Base(state) = state {
-            hasher.write(
-                state
-                    .previous_epoch_attestations
-                    .tree_hash_root()
-                    .as_bytes(),
-            )?;
-            hasher.write(state.current_epoch_attestations.tree_hash_root().as_bytes())?;
+            leaves.push(state.previous_epoch_attestations.tree_hash_root());
+            leaves.push(state.current_epoch_attestations.tree_hash_root());
         } else {
-            hasher.write(
+            leaves.push(
                 self.previous_epoch_participation
                     .recalculate_tree_hash_root(&ParticipationList::new(
                         state.previous_epoch_participation()?,
-                    ))?
-                    .as_bytes(),
-            )?;
-            hasher.write(
+                    ))?,
+            );
+            leaves.push(
                 self.current_epoch_participation
                     .recalculate_tree_hash_root(&ParticipationList::new(
                         state.current_epoch_participation()?,
-                    ))?
-                    .as_bytes(),
-            )?;
+                    ))?,
+            );
         }
 
-        hasher.write(state.justification_bits().tree_hash_root().as_bytes())?;
-        hasher.write(
-            state
-                .previous_justified_checkpoint()
-                .tree_hash_root()
-                .as_bytes(),
-        )?;
-        hasher.write(
-            state
-                .current_justified_checkpoint()
-                .tree_hash_root()
-                .as_bytes(),
-        )?;
-        hasher.write(state.finalized_checkpoint().tree_hash_root().as_bytes())?;
+        leaves.push(state.justification_bits().tree_hash_root());
+        leaves.push(state.previous_justified_checkpoint().tree_hash_root());
+        leaves.push(state.current_justified_checkpoint().tree_hash_root());
+        leaves.push(state.finalized_checkpoint().tree_hash_root());
 
         // Inactivity & light-client sync committees (Altair and later).
         if let Ok(inactivity_scores) = state.inactivity_scores() {
-            hasher.write(
+            leaves.push(
                 self.inactivity_scores
-                    .recalculate_tree_hash_root(inactivity_scores)?
-                    .as_bytes(),
-            )?;
+                    .recalculate_tree_hash_root(inactivity_scores)?,
+            );
         }
 
         if let Ok(current_sync_committee) = state.current_sync_committee() {
-            hasher.write(current_sync_committee.tree_hash_root().as_bytes())?;
+            leaves.push(current_sync_committee.tree_hash_root());
         }
 
         if let Ok(next_sync_committee) = state.next_sync_committee() {
-            hasher.write(next_sync_committee.tree_hash_root().as_bytes())?;
+            leaves.push(next_sync_committee.tree_hash_root());
         }
 
         // Execution payload (merge and later).
         if let Ok(payload_header) = state.latest_execution_payload_header() {
-            hasher.write(payload_header.tree_hash_root().as_bytes())?;
+            leaves.push(payload_header.tree_hash_root());
+        }
+
+        Ok(leaves)
+    }
+
+    /// Updates the cache and returns the tree hash root for the given `state`.
+    ///
+    /// See `Self::recalculate_tree_hash_leaves` for the requirements on `state`.
+    pub fn recalculate_tree_hash_root(&mut self, state: &BeaconState<T>) -> Result<Hash256, Error> {
+        let mut hasher = MerkleHasher::with_leaves(NUM_BEACON_STATE_HASH_TREE_ROOT_LEAVES);
+
+        for leaf in self.recalculate_tree_hash_leaves(state)? {
+            hasher.write(leaf.as_bytes())?;
         }
 
         let root = hasher.finish()?;
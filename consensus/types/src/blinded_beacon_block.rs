@@ -0,0 +1,183 @@
+use crate::test_utils::TestRandom;
+use crate::*;
+use bls::Signature;
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use ssz_types::VariableList;
+use test_random_derive::TestRandom;
+use tree_hash::TreeHash;
+use tree_hash_derive::TreeHash;
+
+/// The body of a `BlindedBeaconBlock`, which is identical to a `BeaconBlockBodyMerge` except that
+/// the execution payload is replaced by its header.
+///
+/// Since the tree hash root of an `ExecutionPayloadHeader` is equal to that of the
+/// `ExecutionPayload` it summarises, the root of a blinded body is equal to the root of the full
+/// body.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+#[serde(bound = "T: EthSpec", deny_unknown_fields)]
+pub struct BlindedBeaconBlockBody<T: EthSpec> {
+    pub randao_reveal: Signature,
+    pub eth1_data: Eth1Data,
+    pub graffiti: Graffiti,
+    pub proposer_slashings: VariableList<ProposerSlashing, T::MaxProposerSlashings>,
+    pub attester_slashings: VariableList<AttesterSlashing<T>, T::MaxAttesterSlashings>,
+    pub attestations: VariableList<Attestation<T>, T::MaxAttestations>,
+    pub deposits: VariableList<Deposit, T::MaxDeposits>,
+    pub voluntary_exits: VariableList<SignedVoluntaryExit, T::MaxVoluntaryExits>,
+    pub sync_aggregate: SyncAggregate<T>,
+    pub execution_payload_header: ExecutionPayloadHeader<T>,
+}
+
+/// A post-merge block in which the execution payload has been replaced by its header, as produced
+/// when the payload is built by an external builder.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+#[serde(bound = "T: EthSpec", deny_unknown_fields)]
+pub struct BlindedBeaconBlock<T: EthSpec> {
+    pub slot: Slot,
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub proposer_index: u64,
+    pub parent_root: Hash256,
+    pub state_root: Hash256,
+    pub body: BlindedBeaconBlockBody<T>,
+}
+
+impl<T: EthSpec> SignedRoot for BlindedBeaconBlock<T> {}
+
+impl<T: EthSpec> BlindedBeaconBlock<T> {
+    /// Creates a blinded block from `block`, replacing its execution payload with
+    /// `execution_payload_header`.
+    ///
+    /// The execution payload of `block` is discarded, it need not correspond to the header.
+    pub fn from_block(
+        block: BeaconBlockMerge<T>,
+        execution_payload_header: ExecutionPayloadHeader<T>,
+    ) -> Self {
+        let BeaconBlockMerge {
+            slot,
+            proposer_index,
+            parent_root,
+            state_root,
+            body,
+        } = block;
+
+        BlindedBeaconBlock {
+            slot,
+            proposer_index,
+            parent_root,
+            state_root,
+            body: BlindedBeaconBlockBody {
+                randao_reveal: body.randao_reveal,
+                eth1_data: body.eth1_data,
+                graffiti: body.graffiti,
+                proposer_slashings: body.proposer_slashings,
+                attester_slashings: body.attester_slashings,
+                attestations: body.attestations,
+                deposits: body.deposits,
+                voluntary_exits: body.voluntary_exits,
+                sync_aggregate: body.sync_aggregate,
+                execution_payload_header,
+            },
+        }
+    }
+
+    /// Returns the full block, using `execution_payload` in place of the payload header.
+    ///
+    /// It is the responsibility of the caller to ensure that `execution_payload` matches the
+    /// header, otherwise the returned block will have a different root to `self`.
+    pub fn into_full_block(self, execution_payload: ExecutionPayload<T>) -> BeaconBlock<T> {
+        let BlindedBeaconBlock {
+            slot,
+            proposer_index,
+            parent_root,
+            state_root,
+            body,
+        } = self;
+
+        BeaconBlock::Merge(BeaconBlockMerge {
+            slot,
+            proposer_index,
+            parent_root,
+            state_root,
+            body: BeaconBlockBodyMerge {
+                randao_reveal: body.randao_reveal,
+                eth1_data: body.eth1_data,
+                graffiti: body.graffiti,
+                proposer_slashings: body.proposer_slashings,
+                attester_slashings: body.attester_slashings,
+                attestations: body.attestations,
+                deposits: body.deposits,
+                voluntary_exits: body.voluntary_exits,
+                sync_aggregate: body.sync_aggregate,
+                execution_payload,
+            },
+        })
+    }
+
+    /// Returns the epoch corresponding to `self.slot`.
+    pub fn epoch(&self) -> Epoch {
+        self.slot.epoch(T::slots_per_epoch())
+    }
+
+    /// Returns a full `BeaconBlockHeader` of this block.
+    ///
+    /// The header is identical to that of the full block.
+    pub fn block_header(&self) -> BeaconBlockHeader {
+        BeaconBlockHeader {
+            slot: self.slot,
+            proposer_index: self.proposer_index,
+            parent_root: self.parent_root,
+            state_root: self.state_root,
+            body_root: self.body.tree_hash_root(),
+        }
+    }
+}
+
+/// A `BlindedBeaconBlock` and a signature from its proposer.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+#[serde(bound = "T: EthSpec")]
+pub struct SignedBlindedBeaconBlock<T: EthSpec> {
+    pub message: BlindedBeaconBlock<T>,
+    pub signature: Signature,
+}
+
+impl<T: EthSpec> SignedBlindedBeaconBlock<T> {
+    /// Returns the full signed block, using `execution_payload` in place of the payload header.
+    ///
+    /// Since the full block has the same root as the blinded block, the signature remains valid
+    /// as long as `execution_payload` matches the header.
+    pub fn into_full_block(self, execution_payload: ExecutionPayload<T>) -> SignedBeaconBlock<T> {
+        SignedBeaconBlock::from_block(
+            self.message.into_full_block(execution_payload),
+            self.signature,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+
+    ssz_and_tree_hash_tests!(BlindedBeaconBlock<MainnetEthSpec>);
+
+    #[test]
+    fn blinded_root_matches_full_root() {
+        let rng = &mut XorShiftRng::from_seed([42; 16]);
+        let block = BeaconBlockMerge::<MainnetEthSpec>::random_for_test(rng);
+        let header = ExecutionPayloadHeader::from(&block.body.execution_payload);
+
+        let blinded = BlindedBeaconBlock::from_block(block.clone(), header);
+        assert_eq!(blinded.tree_hash_root(), block.tree_hash_root());
+        assert_eq!(
+            blinded.block_header(),
+            BeaconBlock::Merge(block.clone()).block_header()
+        );
+
+        let full = blinded.into_full_block(block.body.execution_payload.clone());
+        assert_eq!(full, BeaconBlock::Merge(block));
+    }
+}
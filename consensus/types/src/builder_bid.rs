@@ -0,0 +1,41 @@
+use crate::*;
+use bls::Signature;
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use tree_hash_derive::TreeHash;
+
+/// A bid from an external builder, offering the execution payload summarised by `header` in
+/// return for `value` wei.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode, TreeHash)]
+#[serde(bound = "T: EthSpec")]
+pub struct BuilderBid<T: EthSpec> {
+    pub header: ExecutionPayloadHeader<T>,
+    #[serde(with = "eth2_serde_utils::quoted_u256")]
+    pub value: Uint256,
+    pub pubkey: PublicKeyBytes,
+}
+
+impl<T: EthSpec> SignedRoot for BuilderBid<T> {}
+
+/// A `BuilderBid` and a signature from the builder.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+#[serde(bound = "T: EthSpec")]
+pub struct SignedBuilderBid<T: EthSpec> {
+    pub message: BuilderBid<T>,
+    pub signature: Signature,
+}
+
+impl<T: EthSpec> SignedBuilderBid<T> {
+    /// Returns `true` if the bid is signed by the builder identified by `message.pubkey`.
+    pub fn verify_signature(&self, spec: &ChainSpec) -> bool {
+        self.message
+            .pubkey
+            .decompress()
+            .map(|pubkey| {
+                let domain = spec.get_builder_domain();
+                let message = self.message.signing_root(domain);
+                self.signature.verify(&pubkey, message)
+            })
+            .unwrap_or(false)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::*;
 use eth2_serde_utils::quoted_u64::MaybeQuoted;
 use int_to_bytes::int_to_bytes4;
+use itertools::Itertools;
 use serde::{Deserializer, Serialize, Serializer};
 use serde_derive::Deserialize;
 use std::fs::File;
@@ -20,6 +21,7 @@ pub enum Domain {
     SyncCommittee,
     ContributionAndProof,
     SyncCommitteeSelectionProof,
+    ApplicationBuilder,
 }
 
 /// Lighthouse's internal configuration struct.
@@ -96,6 +98,7 @@ pub struct ChainSpec {
     pub(crate) domain_voluntary_exit: u32,
     pub(crate) domain_selection_proof: u32,
     pub(crate) domain_aggregate_and_proof: u32,
+    pub(crate) domain_application_builder: u32,
 
     /*
      * Fork choice
@@ -200,14 +203,45 @@ impl ChainSpec {
         }
     }
 
-    /// Returns the epoch of the next scheduled fork along with its corresponding `ForkName`.
+    /// Returns the epoch of the next scheduled fork along with the `ForkName` which becomes active
+    /// at that epoch.
     ///
-    /// If no future forks are scheduled, this function returns `None`.
+    /// Forks which are scheduled for the same epoch activate together, in which case the latest of
+    /// them is returned. If no future forks are scheduled, this function returns `None`.
     pub fn next_fork_epoch<T: EthSpec>(&self, slot: Slot) -> Option<(ForkName, Epoch)> {
-        let current_fork_name = self.fork_name_at_slot::<T>(slot);
-        let next_fork_name = current_fork_name.next_fork()?;
-        let fork_epoch = self.fork_epoch(next_fork_name)?;
-        Some((next_fork_name, fork_epoch))
+        let current_epoch = slot.epoch(T::slots_per_epoch());
+        let fork_epoch = ForkName::list_all()
+            .into_iter()
+            .filter_map(|fork_name| self.fork_epoch(fork_name))
+            .filter(|fork_epoch| *fork_epoch > current_epoch)
+            .min()?;
+        Some((self.fork_name_at_epoch(fork_epoch), fork_epoch))
+    }
+
+    /// Returns an error if a fork is scheduled before, or without, the fork which precedes it.
+    ///
+    /// Forks may be scheduled for the same epoch as their predecessor.
+    pub fn verify_fork_schedule(&self) -> Result<(), String> {
+        for (previous_fork, fork) in ForkName::list_all().into_iter().tuple_windows() {
+            if let Some(fork_epoch) = self.fork_epoch(fork) {
+                match self.fork_epoch(previous_fork) {
+                    Some(previous_fork_epoch) if previous_fork_epoch <= fork_epoch => (),
+                    Some(previous_fork_epoch) => {
+                        return Err(format!(
+                            "{} is scheduled at epoch {}, before {} at epoch {}",
+                            fork, fork_epoch, previous_fork, previous_fork_epoch
+                        ))
+                    }
+                    None => {
+                        return Err(format!(
+                            "{} is scheduled at epoch {} but {} is not scheduled",
+                            fork, fork_epoch, previous_fork
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 
     /// Returns the name of the fork which is active at `slot`.
@@ -320,6 +354,7 @@ impl ChainSpec {
             Domain::SyncCommittee => self.domain_sync_committee,
             Domain::ContributionAndProof => self.domain_contribution_and_proof,
             Domain::SyncCommitteeSelectionProof => self.domain_sync_committee_selection_proof,
+            Domain::ApplicationBuilder => self.domain_application_builder,
         }
     }
 
@@ -347,6 +382,18 @@ impl ChainSpec {
         self.compute_domain(Domain::Deposit, self.genesis_fork_version, Hash256::zero())
     }
 
+    /// Get the domain for a builder API signature, such as a validator registration.
+    ///
+    /// Like deposits, builder API messages are valid across forks and so the domain is computed
+    /// with the genesis fork version.
+    pub fn get_builder_domain(&self) -> Hash256 {
+        self.compute_domain(
+            Domain::ApplicationBuilder,
+            self.genesis_fork_version,
+            Hash256::zero(),
+        )
+    }
+
     /// Return the 32-byte fork data root for the `current_version` and `genesis_validators_root`.
     ///
     /// This is used primarily in signature domains to avoid collisions across forks/chains.
@@ -485,6 +532,8 @@ impl ChainSpec {
             domain_voluntary_exit: 4,
             domain_selection_proof: 5,
             domain_aggregate_and_proof: 6,
+            // `DomainType('0x00000001')` from the builder specs, as a little-endian integer.
+            domain_application_builder: 0x0100_0000,
 
             /*
              * Fork choice
@@ -611,9 +660,12 @@ pub struct Config {
     #[serde(default)]
     pub preset_base: String,
 
+    #[serde(default = "default_terminal_total_difficulty")]
     #[serde(with = "eth2_serde_utils::quoted_u256")]
     pub terminal_total_difficulty: Uint256,
+    #[serde(default)]
     pub terminal_block_hash: Hash256,
+    #[serde(default = "default_terminal_block_hash_activation_epoch")]
     pub terminal_block_hash_activation_epoch: Epoch,
 
     #[serde(with = "eth2_serde_utils::quoted_u64")]
@@ -625,14 +677,18 @@ pub struct Config {
     #[serde(with = "eth2_serde_utils::quoted_u64")]
     genesis_delay: u64,
 
+    #[serde(default = "default_fork_version")]
     #[serde(with = "eth2_serde_utils::bytes_4_hex")]
     altair_fork_version: [u8; 4],
+    #[serde(default)]
     #[serde(serialize_with = "serialize_fork_epoch")]
     #[serde(deserialize_with = "deserialize_fork_epoch")]
     pub altair_fork_epoch: Option<MaybeQuoted<Epoch>>,
 
+    #[serde(default = "default_fork_version")]
     #[serde(with = "eth2_serde_utils::bytes_4_hex")]
     bellatrix_fork_version: [u8; 4],
+    #[serde(default)]
     #[serde(serialize_with = "serialize_fork_epoch")]
     #[serde(deserialize_with = "deserialize_fork_epoch")]
     pub bellatrix_fork_epoch: Option<MaybeQuoted<Epoch>>,
@@ -676,6 +732,21 @@ impl Default for Config {
     }
 }
 
+/// The fork version used for forks which are missing from a runtime config.
+///
+/// Such forks are never scheduled, so this version should never be used.
+fn default_fork_version() -> [u8; 4] {
+    [0xff, 0xff, 0xff, 0xff]
+}
+
+fn default_terminal_total_difficulty() -> Uint256 {
+    ChainSpec::mainnet().terminal_total_difficulty
+}
+
+fn default_terminal_block_hash_activation_epoch() -> Epoch {
+    ChainSpec::mainnet().terminal_block_hash_activation_epoch
+}
+
 /// Util function to serialize a `None` fork epoch value
 /// as `Epoch::max_value()`.
 fn serialize_fork_epoch<S>(val: &Option<MaybeQuoted<Epoch>>, s: S) -> Result<S::Ok, S::Error>
@@ -892,6 +963,11 @@ mod tests {
             &spec,
         );
         test_domain(Domain::SyncCommittee, spec.domain_sync_committee, &spec);
+        test_domain(
+            Domain::ApplicationBuilder,
+            spec.domain_application_builder,
+            &spec,
+        );
     }
 
     // Test that `fork_name_at_epoch` and `fork_epoch` are consistent.
@@ -932,6 +1008,40 @@ mod tests {
             }
         }
     }
+
+    // Test that forks scheduled for the same epoch are transitioned to together.
+    #[test]
+    fn next_fork_epoch_coincident_forks() {
+        type E = MainnetEthSpec;
+        let mut spec = ChainSpec::mainnet();
+        spec.altair_fork_epoch = Some(Epoch::new(10));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(10));
+
+        let prior_slot = Epoch::new(9).start_slot(E::slots_per_epoch());
+        assert_eq!(
+            spec.next_fork_epoch::<E>(prior_slot),
+            Some((ForkName::Merge, Epoch::new(10)))
+        );
+        let fork_slot = Epoch::new(10).start_slot(E::slots_per_epoch());
+        assert_eq!(spec.next_fork_epoch::<E>(fork_slot), None);
+    }
+
+    #[test]
+    fn verify_fork_schedule() {
+        let mut spec = ChainSpec::mainnet();
+        spec.altair_fork_epoch = Some(Epoch::new(10));
+        spec.bellatrix_fork_epoch = Some(Epoch::new(10));
+        assert!(spec.verify_fork_schedule().is_ok());
+
+        spec.bellatrix_fork_epoch = Some(Epoch::new(9));
+        assert!(spec.verify_fork_schedule().is_err());
+
+        spec.altair_fork_epoch = None;
+        assert!(spec.verify_fork_schedule().is_err());
+
+        spec.bellatrix_fork_epoch = None;
+        assert!(spec.verify_fork_schedule().is_ok());
+    }
 }
 
 #[cfg(test)]
@@ -1005,4 +1115,42 @@ mod yaml_tests {
             .expect("should have applied spec");
         assert_eq!(new_spec, ChainSpec::minimal());
     }
+
+    #[test]
+    fn missing_and_unknown_forks() {
+        let spec = ChainSpec::mainnet();
+        let yamlconfig = Config::from_chain_spec::<MainnetEthSpec>(&spec);
+        let mut mapping = match serde_yaml::to_value(&yamlconfig).unwrap() {
+            serde_yaml::Value::Mapping(mapping) => mapping,
+            other => panic!("config should serialize to a mapping, not {:?}", other),
+        };
+
+        // A config written before the merge, for a network which also schedules a fork which is
+        // unknown to this version of Lighthouse.
+        for key in [
+            "BELLATRIX_FORK_VERSION",
+            "BELLATRIX_FORK_EPOCH",
+            "TERMINAL_TOTAL_DIFFICULTY",
+            "TERMINAL_BLOCK_HASH",
+            "TERMINAL_BLOCK_HASH_ACTIVATION_EPOCH",
+        ] {
+            assert!(mapping.remove(&key.into()).is_some());
+        }
+        mapping.insert("CAPELLA_FORK_VERSION".into(), "0x03000000".into());
+        mapping.insert("CAPELLA_FORK_EPOCH".into(), "18446744073709551615".into());
+
+        let from: Config = serde_yaml::from_value(serde_yaml::Value::Mapping(mapping))
+            .expect("error while deserializing");
+        assert_eq!(from.bellatrix_fork_epoch, None);
+        assert_eq!(
+            from.terminal_total_difficulty,
+            spec.terminal_total_difficulty
+        );
+
+        let new_spec = from
+            .apply_to_chain_spec::<MainnetEthSpec>(&spec)
+            .expect("should have applied spec");
+        assert_eq!(new_spec.bellatrix_fork_epoch, None);
+        assert_eq!(new_spec.altair_fork_epoch, spec.altair_fork_epoch);
+    }
 }
@@ -27,6 +27,8 @@ pub mod beacon_block_body;
 pub mod beacon_block_header;
 pub mod beacon_committee;
 pub mod beacon_state;
+pub mod blinded_beacon_block;
+pub mod builder_bid;
 pub mod chain_spec;
 pub mod checkpoint;
 pub mod consts;
@@ -46,7 +48,13 @@ pub mod free_attestation;
 pub mod graffiti;
 pub mod historical_batch;
 pub mod indexed_attestation;
+pub mod light_client_bootstrap;
+pub mod light_client_finality_update;
+pub mod light_client_header;
+pub mod light_client_optimistic_update;
+pub mod light_client_update;
 pub mod pending_attestation;
+pub mod proposer_preparation_data;
 pub mod proposer_slashing;
 pub mod relative_epoch;
 pub mod selection_proof;
@@ -60,6 +68,7 @@ pub mod signing_data;
 pub mod sync_committee_subscription;
 pub mod sync_duty;
 pub mod validator;
+pub mod validator_registration_data;
 pub mod validator_subscription;
 pub mod voluntary_exit;
 #[macro_use]
@@ -102,6 +111,10 @@ pub use crate::beacon_block_body::{
 pub use crate::beacon_block_header::BeaconBlockHeader;
 pub use crate::beacon_committee::{BeaconCommittee, OwnedBeaconCommittee};
 pub use crate::beacon_state::{BeaconTreeHashCache, Error as BeaconStateError, *};
+pub use crate::blinded_beacon_block::{
+    BlindedBeaconBlock, BlindedBeaconBlockBody, SignedBlindedBeaconBlock,
+};
+pub use crate::builder_bid::{BuilderBid, SignedBuilderBid};
 pub use crate::chain_spec::{ChainSpec, Config, Domain};
 pub use crate::checkpoint::Checkpoint;
 pub use crate::config_and_preset::ConfigAndPreset;
@@ -122,10 +135,16 @@ pub use crate::free_attestation::FreeAttestation;
 pub use crate::graffiti::{Graffiti, GRAFFITI_BYTES_LEN};
 pub use crate::historical_batch::HistoricalBatch;
 pub use crate::indexed_attestation::IndexedAttestation;
+pub use crate::light_client_bootstrap::LightClientBootstrap;
+pub use crate::light_client_finality_update::LightClientFinalityUpdate;
+pub use crate::light_client_header::LightClientHeader;
+pub use crate::light_client_optimistic_update::LightClientOptimisticUpdate;
+pub use crate::light_client_update::LightClientUpdate;
 pub use crate::participation_flags::ParticipationFlags;
 pub use crate::participation_list::ParticipationList;
 pub use crate::pending_attestation::PendingAttestation;
 pub use crate::preset::{AltairPreset, BasePreset, BellatrixPreset};
+pub use crate::proposer_preparation_data::ProposerPreparationData;
 pub use crate::proposer_slashing::ProposerSlashing;
 pub use crate::relative_epoch::{Error as RelativeEpochError, RelativeEpoch};
 pub use crate::selection_proof::SelectionProof;
@@ -151,6 +170,9 @@ pub use crate::sync_duty::SyncDuty;
 pub use crate::sync_selection_proof::SyncSelectionProof;
 pub use crate::sync_subnet_id::SyncSubnetId;
 pub use crate::validator::Validator;
+pub use crate::validator_registration_data::{
+    SignedValidatorRegistrationData, ValidatorRegistrationData,
+};
 pub use crate::validator_subscription::ValidatorSubscription;
 pub use crate::voluntary_exit::VoluntaryExit;
 
@@ -0,0 +1,30 @@
+use crate::light_client_update::CurrentSyncCommitteeProofLen;
+use crate::test_utils::TestRandom;
+use crate::{EthSpec, FixedVector, Hash256, LightClientHeader, SyncCommittee};
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use std::sync::Arc;
+use test_random_derive::TestRandom;
+use tree_hash_derive::TreeHash;
+
+/// The data with which a light client initializes its view of the sync committee, starting from
+/// a trusted block root.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+#[serde(bound = "T: EthSpec")]
+pub struct LightClientBootstrap<T: EthSpec> {
+    /// The header of the trusted block.
+    pub header: LightClientHeader,
+    /// The sync committee of the period of `header`.
+    pub current_sync_committee: Arc<SyncCommittee<T>>,
+    /// A proof of `current_sync_committee` against `header.beacon.state_root`.
+    pub current_sync_committee_branch: FixedVector<Hash256, CurrentSyncCommitteeProofLen>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    ssz_and_tree_hash_tests!(LightClientBootstrap<MainnetEthSpec>);
+}
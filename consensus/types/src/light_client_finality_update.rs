@@ -0,0 +1,30 @@
+use crate::light_client_update::FinalizedRootProofLen;
+use crate::test_utils::TestRandom;
+use crate::{EthSpec, FixedVector, Hash256, LightClientHeader, Slot, SyncAggregate};
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash_derive::TreeHash;
+
+/// The latest finalized header known to the server, attested by the sync committee.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+#[serde(bound = "T: EthSpec")]
+pub struct LightClientFinalityUpdate<T: EthSpec> {
+    /// The header signed by the sync committee.
+    pub attested_header: LightClientHeader,
+    /// The header of the finalized checkpoint of the state of `attested_header`.
+    pub finalized_header: LightClientHeader,
+    pub finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
+    pub sync_aggregate: SyncAggregate<T>,
+    /// The slot of the block which contains `sync_aggregate`.
+    pub signature_slot: Slot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    ssz_and_tree_hash_tests!(LightClientFinalityUpdate<MainnetEthSpec>);
+}
@@ -0,0 +1,26 @@
+use crate::test_utils::TestRandom;
+use crate::BeaconBlockHeader;
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash_derive::TreeHash;
+
+/// The header of a beacon block, as served to light clients.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+pub struct LightClientHeader {
+    pub beacon: BeaconBlockHeader,
+}
+
+impl From<BeaconBlockHeader> for LightClientHeader {
+    fn from(beacon: BeaconBlockHeader) -> Self {
+        Self { beacon }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ssz_and_tree_hash_tests!(LightClientHeader);
+}
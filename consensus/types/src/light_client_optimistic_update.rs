@@ -0,0 +1,26 @@
+use crate::test_utils::TestRandom;
+use crate::{EthSpec, LightClientHeader, Slot, SyncAggregate};
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use test_random_derive::TestRandom;
+use tree_hash_derive::TreeHash;
+
+/// The latest header known to the server, attested by the sync committee.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+#[serde(bound = "T: EthSpec")]
+pub struct LightClientOptimisticUpdate<T: EthSpec> {
+    /// The header signed by the sync committee.
+    pub attested_header: LightClientHeader,
+    pub sync_aggregate: SyncAggregate<T>,
+    /// The slot of the block which contains `sync_aggregate`.
+    pub signature_slot: Slot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    ssz_and_tree_hash_tests!(LightClientOptimisticUpdate<MainnetEthSpec>);
+}
@@ -0,0 +1,54 @@
+use crate::test_utils::TestRandom;
+use crate::{
+    typenum::{U5, U6},
+    EthSpec, FixedVector, Hash256, LightClientHeader, Slot, SyncAggregate, SyncCommittee,
+};
+use serde_derive::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use std::sync::Arc;
+use test_random_derive::TestRandom;
+use tree_hash_derive::TreeHash;
+
+/// The generalized index of `state.finalized_checkpoint.root` in the `BeaconState` Merkle tree.
+pub const FINALIZED_ROOT_INDEX: usize = 105;
+/// The generalized index of `state.current_sync_committee` in the `BeaconState` Merkle tree.
+pub const CURRENT_SYNC_COMMITTEE_INDEX: usize = 54;
+/// The generalized index of `state.next_sync_committee` in the `BeaconState` Merkle tree.
+pub const NEXT_SYNC_COMMITTEE_INDEX: usize = 55;
+
+pub type FinalizedRootProofLen = U6;
+pub type CurrentSyncCommitteeProofLen = U5;
+pub type NextSyncCommitteeProofLen = U5;
+
+pub const FINALIZED_ROOT_PROOF_LEN: usize = 6;
+pub const CURRENT_SYNC_COMMITTEE_PROOF_LEN: usize = 5;
+pub const NEXT_SYNC_COMMITTEE_PROOF_LEN: usize = 5;
+
+/// An update which allows a light client to advance its view of the finalized header and the sync
+/// committee.
+///
+/// Light clients request the best update for each sync committee period.
+#[cfg_attr(feature = "arbitrary-fuzz", derive(arbitrary::Arbitrary))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Encode, Decode, TreeHash, TestRandom)]
+#[serde(bound = "T: EthSpec")]
+pub struct LightClientUpdate<T: EthSpec> {
+    /// The header signed by the sync committee.
+    pub attested_header: LightClientHeader,
+    /// The next sync committee, from the state of `attested_header`.
+    pub next_sync_committee: Arc<SyncCommittee<T>>,
+    pub next_sync_committee_branch: FixedVector<Hash256, NextSyncCommitteeProofLen>,
+    /// The header of the finalized checkpoint of the state of `attested_header`.
+    pub finalized_header: LightClientHeader,
+    pub finality_branch: FixedVector<Hash256, FinalizedRootProofLen>,
+    pub sync_aggregate: SyncAggregate<T>,
+    /// The slot of the block which contains `sync_aggregate`.
+    pub signature_slot: Slot,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    ssz_and_tree_hash_tests!(LightClientUpdate<MainnetEthSpec>);
+}
@@ -0,0 +1,13 @@
+use crate::*;
+use serde_derive::{Deserialize, Serialize};
+
+/// Information supplied by a validator client ahead of a block proposal, so that the beacon node
+/// can prepare an execution payload for the proposer.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct ProposerPreparationData {
+    /// The index of the validator which will propose.
+    #[serde(with = "eth2_serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// The address to which transaction fees should be paid.
+    pub fee_recipient: Address,
+}
@@ -0,0 +1,157 @@
+//! Maintenance of the beacon node's database while the beacon node is stopped.
+use beacon_node::{get_data_dir, ClientConfig};
+use clap::{App, Arg, ArgMatches};
+use environment::RuntimeContext;
+use slog::info;
+use std::path::PathBuf;
+use std::sync::Arc;
+use store::{config::DEFAULT_SLOTS_PER_RESTORE_POINT, Error, HotColdDB, LevelDB, StoreConfig};
+use task_executor::ShutdownReason;
+use types::EthSpec;
+
+pub const CMD: &str = "db";
+pub const PRUNE_PAYLOADS_CMD: &str = "prune-payloads";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Manage the beacon node's database. The beacon node must not be running.")
+        .arg(
+            Arg::with_name("freezer-dir")
+                .long("freezer-dir")
+                .value_name("DIR")
+                .help("Data directory for the freezer database.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slots-per-restore-point")
+                .long("slots-per-restore-point")
+                .value_name("SLOT_COUNT")
+                .help(
+                    "The slots per restore point that the database was created with. \
+                     [default: 2048 (mainnet) or 64 (minimal)]",
+                )
+                .takes_value(true),
+        )
+        .subcommand(
+            App::new(PRUNE_PAYLOADS_CMD)
+                .about(
+                    "Delete the execution payloads of finalized blocks which are older than the \
+                     retention period, as the beacon node does with --prune-payloads.",
+                )
+                .arg(
+                    Arg::with_name("payload-retention-epochs")
+                        .long("payload-retention-epochs")
+                        .value_name("EPOCHS")
+                        .help(
+                            "Number of epochs prior to the latest finalized epoch for which \
+                             execution payloads are kept.",
+                        )
+                        .takes_value(true)
+                        .default_value("256"),
+                ),
+        )
+}
+
+/// Build the client config, so that the databases are found where the beacon node keeps them.
+fn parse_client_config<E: EthSpec>(cli_args: &ArgMatches) -> Result<ClientConfig, String> {
+    let mut client_config = ClientConfig {
+        data_dir: get_data_dir(cli_args),
+        ..Default::default()
+    };
+
+    client_config.freezer_db_path = clap_utils::parse_optional::<PathBuf>(cli_args, "freezer-dir")?;
+    client_config.store.slots_per_restore_point =
+        clap_utils::parse_optional(cli_args, "slots-per-restore-point")?.unwrap_or_else(|| {
+            std::cmp::min(
+                E::slots_per_historical_root() as u64,
+                DEFAULT_SLOTS_PER_RESTORE_POINT,
+            )
+        });
+
+    Ok(client_config)
+}
+
+/// Open the database without migrating it, since that is the beacon node's responsibility.
+fn open_store<E: EthSpec>(
+    client_config: &ClientConfig,
+    store_config: StoreConfig,
+    context: &RuntimeContext<E>,
+) -> Result<Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>, String> {
+    let hot_path = client_config.get_db_path();
+    let cold_path = client_config.get_freezer_db_path();
+    if !hot_path.exists() {
+        return Err(format!("No database found at {:?}", hot_path));
+    }
+
+    HotColdDB::open(
+        &hot_path,
+        &cold_path,
+        |_, from, to| {
+            if from == to {
+                Ok(())
+            } else {
+                Err(Error::SchemaMigrationError(format!(
+                    "database schema is {:?} but {:?} is required, run the beacon node to \
+                     migrate it",
+                    from, to
+                )))
+            }
+        },
+        store_config,
+        context.eth2_config.spec.clone(),
+        context.log().clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {:?}", e))
+}
+
+fn prune_payloads<E: EthSpec>(
+    client_config: ClientConfig,
+    cli_args: &ArgMatches,
+    context: &RuntimeContext<E>,
+) -> Result<(), String> {
+    let retention_epochs = clap_utils::parse_required(cli_args, "payload-retention-epochs")?;
+    let store = open_store(&client_config, client_config.store.clone(), context)?;
+
+    info!(
+        context.log(),
+        "Pruning execution payloads";
+        "retention_epochs" => retention_epochs,
+        "split_slot" => store.get_split_slot(),
+    );
+    let num_pruned = store
+        .prune_payloads(retention_epochs)
+        .map_err(|e| format!("Unable to prune payloads: {:?}", e))?;
+    info!(context.log(), "Payload pruning complete"; "blocks" => num_pruned);
+
+    if num_pruned > 0 {
+        info!(context.log(), "Compacting database");
+        store
+            .compact()
+            .map_err(|e| format!("Unable to compact database: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Run the `db` command to completion, then request that the process shuts down.
+pub fn run<E: EthSpec>(cli_args: &ArgMatches, context: RuntimeContext<E>) -> Result<(), String> {
+    let client_config = parse_client_config::<E>(cli_args)?;
+
+    match cli_args.subcommand() {
+        (PRUNE_PAYLOADS_CMD, Some(matches)) => prune_payloads(client_config, matches, &context)?,
+        (unknown, _) => {
+            return Err(format!(
+                "{} is not a valid {} command. See --help.",
+                unknown, CMD
+            ))
+        }
+    }
+
+    // Ignore the error, the shutdown may already be under way.
+    let _ = context
+        .executor
+        .shutdown_sender()
+        .try_send(ShutdownReason::Success("Database command complete"));
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use clap::ArgMatches;
+use clap_utils::{apply_terminal_overrides, parse_optional, parse_required};
+use environment::Environment;
+use eth2_network_config::Eth2NetworkConfig;
+use execution_layer::ExecutionLayer;
+use sensitive_url::SensitiveUrl;
+use std::path::PathBuf;
+use types::{EthSpec, Hash256};
+
+/// Simulates the merge transition checks which a beacon node would perform against an execution
+/// engine, without requiring a beacon node or a merge fork.
+pub fn run<T: EthSpec>(
+    mut env: Environment<T>,
+    testnet_dir: PathBuf,
+    matches: &ArgMatches<'_>,
+) -> Result<(), String> {
+    let mut network_config = Eth2NetworkConfig::load(testnet_dir)?;
+    apply_terminal_overrides(&mut network_config, matches)?;
+    let spec = network_config.chain_spec::<T>()?;
+
+    let execution_endpoint =
+        parse_required::<String>(matches, "execution-endpoint").and_then(|url| {
+            SensitiveUrl::parse(&url)
+                .map_err(|e| format!("Unable to parse execution endpoint: {:?}", e))
+        })?;
+    let jwt_secrets = parse_optional::<PathBuf>(matches, "jwt-secret")?
+        .into_iter()
+        .collect();
+    let block_hash = parse_optional::<Hash256>(matches, "block-hash")?;
+
+    let context = env.core_context();
+    let execution_layer = ExecutionLayer::from_urls(
+        vec![execution_endpoint],
+        jwt_secrets,
+        None,
+        None,
+        context.executor.clone(),
+        context.log().clone(),
+    )
+    .map_err(|e| format!("Unable to create execution layer: {:?}", e))?;
+
+    println!(
+        "Terminal total difficulty: {}",
+        spec.terminal_total_difficulty
+    );
+    if spec.terminal_block_hash != Hash256::zero() {
+        println!(
+            "Terminal block hash override: {:?} (activation epoch {})",
+            spec.terminal_block_hash, spec.terminal_block_hash_activation_epoch
+        );
+    }
+
+    env.runtime().block_on(async {
+        let terminal_block_hash = execution_layer
+            .get_terminal_pow_block_hash(&spec)
+            .await
+            .map_err(|e| format!("Unable to find terminal block: {:?}", e))?;
+
+        match terminal_block_hash {
+            Some(hash) => println!("Terminal block found: {:?}", hash),
+            None => println!("Terminal block not yet reached by the execution engine"),
+        }
+
+        // Validate the given block as the parent of a transition block, or else the terminal
+        // block which the beacon node would build upon.
+        let candidate = match block_hash.or(terminal_block_hash) {
+            Some(candidate) => candidate,
+            None => return Ok(()),
+        };
+
+        // Mirrors the checks of `validate_merge_block` in the beacon chain.
+        let is_valid = if spec.terminal_block_hash != Hash256::zero() {
+            Some(candidate == spec.terminal_block_hash)
+        } else {
+            execution_layer
+                .is_valid_terminal_pow_block_hash(candidate, &spec)
+                .await
+                .map_err(|e| format!("Unable to verify terminal block: {:?}", e))?
+        };
+
+        match is_valid {
+            Some(true) => {
+                println!("Block {:?} is a valid terminal block", candidate);
+                Ok(())
+            }
+            Some(false) => Err(format!(
+                "block {:?} is not a valid terminal block, a transition block built upon it \
+                 would be rejected",
+                candidate
+            )),
+            None => Err(format!(
+                "block {:?} or its parent is unknown to the execution engine, a transition \
+                 block built upon it would be optimistically imported",
+                candidate
+            )),
+        }
+    })
+}
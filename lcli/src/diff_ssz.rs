@@ -0,0 +1,279 @@
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use eth2_network_config::Eth2NetworkConfig;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use types::{BeaconState, EthSpec, SignedBeaconBlock};
+
+/// The fields of a `BeaconState` which are lists indexed by validator index. Their differences are
+/// grouped by validator, rather than by field.
+const VALIDATOR_FIELDS: &[&str] = &[
+    "validators",
+    "balances",
+    "previous_epoch_participation",
+    "current_epoch_participation",
+    "inactivity_scores",
+];
+
+pub fn run_diff_states<T: EthSpec>(
+    testnet_dir: PathBuf,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let (path_a, path_b) = parse_paths(matches)?;
+
+    info!("Using {} spec", T::spec_name());
+
+    let eth2_network_config = Eth2NetworkConfig::load(testnet_dir)?;
+    let spec = &eth2_network_config.chain_spec::<T>()?;
+
+    let state_a: BeaconState<T> = load_from_ssz_with(&path_a, spec, BeaconState::from_ssz_bytes)?;
+    let state_b: BeaconState<T> = load_from_ssz_with(&path_b, spec, BeaconState::from_ssz_bytes)?;
+
+    let mut json_a = to_json(&state_a)?;
+    let mut json_b = to_json(&state_b)?;
+    let validator_diffs = diff_validators(&mut json_a, &mut json_b);
+
+    let mut diffs = vec![];
+    diff_values("", &json_a, &json_b, &mut diffs);
+    diffs.extend(validator_diffs);
+    print_diffs(&diffs);
+
+    Ok(())
+}
+
+pub fn run_diff_blocks<T: EthSpec>(
+    testnet_dir: PathBuf,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let (path_a, path_b) = parse_paths(matches)?;
+
+    info!("Using {} spec", T::spec_name());
+
+    let eth2_network_config = Eth2NetworkConfig::load(testnet_dir)?;
+    let spec = &eth2_network_config.chain_spec::<T>()?;
+
+    let block_a: SignedBeaconBlock<T> =
+        load_from_ssz_with(&path_a, spec, SignedBeaconBlock::from_ssz_bytes)?;
+    let block_b: SignedBeaconBlock<T> =
+        load_from_ssz_with(&path_b, spec, SignedBeaconBlock::from_ssz_bytes)?;
+
+    let mut diffs = vec![];
+    diff_values("", &to_json(&block_a)?, &to_json(&block_b)?, &mut diffs);
+    print_diffs(&diffs);
+
+    Ok(())
+}
+
+fn parse_paths(matches: &ArgMatches) -> Result<(PathBuf, PathBuf), String> {
+    let parse = |name: &str| -> Result<PathBuf, String> {
+        matches
+            .value_of(name)
+            .ok_or_else(|| format!("No {} supplied", name))?
+            .parse::<PathBuf>()
+            .map_err(|e| format!("Failed to parse {}: {}", name, e))
+    };
+    Ok((parse("ssz-a")?, parse("ssz-b")?))
+}
+
+fn to_json<T: Serialize>(item: &T) -> Result<Value, String> {
+    serde_json::to_value(item).map_err(|e| format!("Unable to convert object to JSON: {:?}", e))
+}
+
+fn print_diffs(diffs: &[String]) {
+    if diffs.is_empty() {
+        println!("No differences");
+    }
+    for diff in diffs {
+        println!("{}", diff);
+    }
+}
+
+fn join_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{}.{}", path, field)
+    }
+}
+
+/// Returns `value` as an integer, if it is a number or a quoted number.
+fn as_integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(n) => n.as_u64().map(i128::from),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Appends a line to `diffs` for each leaf value which differs between `a` and `b`.
+fn diff_values(path: &str, a: &Value, b: &Value, diffs: &mut Vec<String>) {
+    match (a, b) {
+        (Value::Object(fields_a), Value::Object(fields_b)) => {
+            for (field, value_a) in fields_a {
+                let field_path = join_path(path, field);
+                match fields_b.get(field) {
+                    Some(value_b) => diff_values(&field_path, value_a, value_b, diffs),
+                    None => diffs.push(format!("{}: only in first", field_path)),
+                }
+            }
+            for field in fields_b.keys() {
+                if !fields_a.contains_key(field) {
+                    diffs.push(format!("{}: only in second", join_path(path, field)));
+                }
+            }
+        }
+        (Value::Array(items_a), Value::Array(items_b)) => {
+            for i in 0..std::cmp::max(items_a.len(), items_b.len()) {
+                let item_path = format!("{}[{}]", path, i);
+                match (items_a.get(i), items_b.get(i)) {
+                    (Some(item_a), Some(item_b)) => diff_values(&item_path, item_a, item_b, diffs),
+                    (Some(_), None) => diffs.push(format!("{}: only in first", item_path)),
+                    (None, Some(_)) => diffs.push(format!("{}: only in second", item_path)),
+                    (None, None) => (),
+                }
+            }
+        }
+        (a, b) if a != b => match (as_integer(a), as_integer(b)) {
+            (Some(int_a), Some(int_b)) => {
+                diffs.push(format!("{}: {} -> {} ({:+})", path, a, b, int_b - int_a))
+            }
+            _ => diffs.push(format!("{}: {} -> {}", path, a, b)),
+        },
+        _ => (),
+    }
+}
+
+/// Removes the `VALIDATOR_FIELDS` from the states `a` and `b`, returning their differences grouped
+/// by validator index.
+fn diff_validators(a: &mut Value, b: &mut Value) -> Vec<String> {
+    let take_list = |state: &mut Value, field: &str| match state
+        .as_object_mut()
+        .and_then(|fields| fields.remove(field))
+    {
+        Some(Value::Array(items)) => items,
+        _ => vec![],
+    };
+    let lists = VALIDATOR_FIELDS
+        .iter()
+        .map(|field| (*field, take_list(a, field), take_list(b, field)))
+        .collect::<Vec<_>>();
+    let validator_count = lists
+        .iter()
+        .map(|(_, list_a, list_b)| std::cmp::max(list_a.len(), list_b.len()))
+        .max()
+        .unwrap_or(0);
+
+    let mut diffs = vec![];
+    for i in 0..validator_count {
+        let mut validator_diffs = vec![];
+        for (field, list_a, list_b) in &lists {
+            match (list_a.get(i), list_b.get(i)) {
+                (Some(item_a), Some(item_b)) => {
+                    diff_values(field, item_a, item_b, &mut validator_diffs)
+                }
+                (Some(_), None) => validator_diffs.push(format!("{}: only in first", field)),
+                (None, Some(_)) => validator_diffs.push(format!("{}: only in second", field)),
+                (None, None) => (),
+            }
+        }
+        if !validator_diffs.is_empty() {
+            diffs.push(format!("validator {}:", i));
+            diffs.extend(
+                validator_diffs
+                    .into_iter()
+                    .map(|diff| format!("  {}", diff)),
+            );
+        }
+    }
+    diffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn diffs(a: &Value, b: &Value) -> Vec<String> {
+        let mut diffs = vec![];
+        diff_values("", a, b, &mut diffs);
+        diffs
+    }
+
+    #[test]
+    fn diff_nested_fields() {
+        let a = json!({
+            "fork": { "epoch": "0" },
+            "latest_block_header": { "parent_root": "0x00", "slot": "1" },
+            "slot": "1",
+        });
+        let b = json!({
+            "slot": "3",
+            "latest_block_header": { "parent_root": "0x01", "slot": "1" },
+            "eth1_data": { "deposit_count": "0" },
+        });
+
+        assert_eq!(
+            diffs(&a, &b),
+            vec![
+                "fork: only in first",
+                "latest_block_header.parent_root: \"0x00\" -> \"0x01\"",
+                "slot: \"1\" -> \"3\" (+2)",
+                "eth1_data: only in second",
+            ]
+        );
+        assert!(diffs(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn diff_list_lengths() {
+        let a = json!({ "historical_roots": ["0x00", "0x01"], "slashings": [] });
+        let b = json!({ "historical_roots": ["0x00", "0x02", "0x03"], "slashings": ["5"] });
+
+        assert_eq!(
+            diffs(&a, &b),
+            vec![
+                "historical_roots[1]: \"0x01\" -> \"0x02\"",
+                "historical_roots[2]: only in second",
+                "slashings[0]: only in second",
+            ]
+        );
+        assert_eq!(
+            diffs(&b, &a),
+            vec![
+                "historical_roots[1]: \"0x02\" -> \"0x01\"",
+                "historical_roots[2]: only in first",
+                "slashings[0]: only in first",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_validators_grouped_by_index() {
+        let mut a = json!({
+            "slot": "1",
+            "validators": [{ "slashed": false }, { "slashed": false }],
+            "balances": ["32", "32"],
+        });
+        let mut b = json!({
+            "slot": "1",
+            "validators": [{ "slashed": false }, { "slashed": true }, { "slashed": false }],
+            "balances": ["32", "31", "32"],
+        });
+
+        assert_eq!(
+            diff_validators(&mut a, &mut b),
+            vec![
+                "validator 1:",
+                "  validators.slashed: false -> true",
+                "  balances: \"32\" -> \"31\" (-1)",
+                "validator 2:",
+                "  validators: only in second",
+                "  balances: only in second",
+            ]
+        );
+        // The validator fields are removed, leaving the rest of the states to be compared.
+        assert_eq!(a, json!({ "slot": "1" }));
+        assert!(diffs(&a, &b).is_empty());
+    }
+}
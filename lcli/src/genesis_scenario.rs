@@ -0,0 +1,292 @@
+//! Declarative modifications to an interop genesis state, read from a YAML scenario file.
+//!
+//! Example scenario:
+//!
+//! ```yaml
+//! validator_count: 64
+//! # Balances (in gwei) which replace the default of `MAX_EFFECTIVE_BALANCE`, by validator index.
+//! # As at genesis, validators with an effective balance below `MAX_EFFECTIVE_BALANCE` are not
+//! # activated.
+//! balances:
+//!   3: 16000000000
+//!   4: 64000000000
+//! # Validators which have initiated an exit at genesis.
+//! exits: [5, 6]
+//! # Validators which have been slashed at genesis.
+//! slashings: [7]
+//! # The members of the current and next sync committees. Repeated to fill the committee.
+//! sync_committee: [0, 1, 2, 3]
+//! ```
+//!
+//! Every modification is applied in the order above, so scenarios always produce the same state.
+//! Without a `sync_committee`, the sync committees are recomputed from the validators which remain
+//! active after the balance overrides. The `genesis_validators_root` is recomputed last.
+use serde::Deserialize;
+use state_processing::common::{initiate_validator_exit, slash_validator};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use types::{AggregatePublicKey, BeaconState, ChainSpec, EthSpec, FixedVector, SyncCommittee};
+
+#[derive(Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Overrides the validator count given on the command line.
+    pub validator_count: Option<usize>,
+    #[serde(default)]
+    pub balances: BTreeMap<usize, u64>,
+    #[serde(default)]
+    pub exits: Vec<usize>,
+    #[serde(default)]
+    pub slashings: Vec<usize>,
+    pub sync_committee: Option<Vec<usize>>,
+}
+
+impl Scenario {
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|e| format!("Unable to open scenario {:?}: {:?}", path, e))?;
+        serde_yaml::from_reader(file)
+            .map_err(|e| format!("Unable to parse scenario {:?}: {:?}", path, e))
+    }
+
+    /// Applies the scenario to the genesis `state`.
+    pub fn apply<T: EthSpec>(
+        &self,
+        state: &mut BeaconState<T>,
+        spec: &ChainSpec,
+    ) -> Result<(), String> {
+        for (&index, &balance) in &self.balances {
+            *state
+                .get_balance_mut(index)
+                .map_err(|e| format!("Invalid balance override for {}: {:?}", index, e))? = balance;
+            state
+                .get_validator_mut(index)
+                .map_err(|e| format!("Invalid balance override for {}: {:?}", index, e))?
+                .effective_balance = std::cmp::min(
+                balance - balance % spec.effective_balance_increment,
+                spec.max_effective_balance,
+            );
+        }
+
+        // Re-run the genesis activation of the overridden validators, which only activates
+        // validators with the maximum effective balance.
+        for &index in self.balances.keys() {
+            let validator = state
+                .get_validator_mut(index)
+                .map_err(|e| format!("Invalid balance override for {}: {:?}", index, e))?;
+            if validator.effective_balance < spec.max_effective_balance {
+                validator.activation_eligibility_epoch = spec.far_future_epoch;
+                validator.activation_epoch = spec.far_future_epoch;
+            }
+        }
+
+        // The genesis sync committees are chosen from the active validators, weighted by their
+        // effective balances.
+        if !self.balances.is_empty() && self.sync_committee.is_none() {
+            if state.current_sync_committee().is_ok() {
+                let sync_committee = Arc::new(
+                    state
+                        .get_next_sync_committee(spec)
+                        .map_err(|e| format!("Unable to compute sync committee: {:?}", e))?,
+                );
+                *state
+                    .current_sync_committee_mut()
+                    .map_err(|e| format!("Unable to set sync committee: {:?}", e))? =
+                    sync_committee.clone();
+                *state
+                    .next_sync_committee_mut()
+                    .map_err(|e| format!("Unable to set sync committee: {:?}", e))? =
+                    sync_committee;
+            }
+        }
+
+        state
+            .build_all_caches(spec)
+            .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+
+        for &index in &self.exits {
+            initiate_validator_exit(state, index, spec)
+                .map_err(|e| format!("Unable to exit validator {}: {:?}", index, e))?;
+        }
+
+        for &index in &self.slashings {
+            slash_validator(state, index, None, spec)
+                .map_err(|e| format!("Unable to slash validator {}: {:?}", index, e))?;
+        }
+
+        if let Some(indices) = &self.sync_committee {
+            let sync_committee = Arc::new(sync_committee(state, indices)?);
+            *state
+                .current_sync_committee_mut()
+                .map_err(|_| "Sync committees require an Altair genesis state".to_string())? =
+                sync_committee.clone();
+            *state
+                .next_sync_committee_mut()
+                .map_err(|_| "Sync committees require an Altair genesis state".to_string())? =
+                sync_committee;
+        }
+
+        *state.genesis_validators_root_mut() = state
+            .update_validators_tree_hash_cache()
+            .map_err(|e| format!("Unable to compute genesis validators root: {:?}", e))?;
+
+        // Invalidate all the caches after all the manual state surgery.
+        state
+            .drop_all_caches()
+            .map_err(|e| format!("Unable to drop caches: {:?}", e))
+    }
+}
+
+/// Returns a sync committee made up of the validators at `indices`, repeated as many times as
+/// required to fill the committee.
+fn sync_committee<T: EthSpec>(
+    state: &BeaconState<T>,
+    indices: &[usize],
+) -> Result<SyncCommittee<T>, String> {
+    if indices.is_empty() {
+        return Err("The sync committee must have at least one member".to_string());
+    }
+
+    let pubkeys = indices
+        .iter()
+        .cycle()
+        .take(T::sync_committee_size())
+        .map(|&index| {
+            state
+                .get_validator(index)
+                .map(|validator| validator.pubkey)
+                .map_err(|e| format!("Invalid sync committee member {}: {:?}", index, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let decompressed_pubkeys = pubkeys
+        .iter()
+        .map(|pubkey| pubkey.decompress())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid sync committee pubkey: {:?}", e))?;
+    let aggregate_pubkey = AggregatePublicKey::aggregate(&decompressed_pubkeys)
+        .map_err(|e| format!("Unable to aggregate sync committee pubkeys: {:?}", e))?;
+
+    Ok(SyncCommittee {
+        pubkeys: FixedVector::new(pubkeys)
+            .map_err(|e| format!("Invalid sync committee size: {:?}", e))?,
+        aggregate_pubkey: aggregate_pubkey.to_public_key().compress(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use genesis::{interop_genesis_state, DEFAULT_ETH1_BLOCK_HASH};
+    use tree_hash::TreeHash;
+    use types::{
+        test_utils::generate_deterministic_keypairs, Epoch, ForkName, Hash256, MinimalEthSpec,
+        PublicKeyBytes,
+    };
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 16;
+
+    fn genesis_state(spec: &ChainSpec) -> BeaconState<E> {
+        interop_genesis_state(
+            &generate_deterministic_keypairs(VALIDATOR_COUNT),
+            42,
+            Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+            None,
+            spec,
+        )
+        .unwrap()
+    }
+
+    fn sync_committee_pubkeys(state: &BeaconState<E>) -> Vec<PublicKeyBytes> {
+        state
+            .current_sync_committee()
+            .unwrap()
+            .pubkeys
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn apply_scenario() {
+        let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+        let mut state = genesis_state(&spec);
+        let scenario: Scenario = serde_yaml::from_str(
+            "
+            balances:
+              3: 16000000000
+              4: 64000000000
+            exits: [5]
+            slashings: [6]
+            sync_committee: [0, 1]
+            ",
+        )
+        .unwrap();
+
+        scenario.apply(&mut state, &spec).unwrap();
+
+        let validator = state.get_validator(3).unwrap();
+        assert_eq!(state.balances()[3], 16_000_000_000);
+        assert_eq!(validator.effective_balance, 16_000_000_000);
+        assert_eq!(validator.activation_epoch, spec.far_future_epoch);
+
+        let validator = state.get_validator(4).unwrap();
+        assert_eq!(state.balances()[4], 64_000_000_000);
+        assert_eq!(validator.effective_balance, spec.max_effective_balance);
+        assert_eq!(validator.activation_epoch, Epoch::new(0));
+
+        assert_ne!(
+            state.get_validator(5).unwrap().exit_epoch,
+            spec.far_future_epoch
+        );
+        assert!(!state.get_validator(5).unwrap().slashed);
+        assert!(state.get_validator(6).unwrap().slashed);
+
+        let members = [0, 1]
+            .iter()
+            .map(|&index| state.get_validator(index).unwrap().pubkey)
+            .collect::<Vec<_>>();
+        let pubkeys = sync_committee_pubkeys(&state);
+        assert_eq!(pubkeys.len(), E::sync_committee_size());
+        for (i, pubkey) in pubkeys.iter().enumerate() {
+            assert_eq!(*pubkey, members[i % members.len()]);
+        }
+        assert_eq!(
+            state.next_sync_committee().unwrap(),
+            state.current_sync_committee().unwrap()
+        );
+
+        assert_eq!(
+            state.genesis_validators_root(),
+            state.validators().tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn inactive_validators_excluded_from_sync_committee() {
+        let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+        let mut state = genesis_state(&spec);
+        let scenario: Scenario = serde_yaml::from_str("balances: { 0: 1000000000 }").unwrap();
+
+        scenario.apply(&mut state, &spec).unwrap();
+
+        let pubkey = state.get_validator(0).unwrap().pubkey;
+        assert!(!sync_committee_pubkeys(&state).contains(&pubkey));
+        assert_eq!(
+            state.genesis_validators_root(),
+            state.validators().tree_hash_root()
+        );
+    }
+
+    #[test]
+    fn empty_sync_committee_rejected() {
+        let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+        let mut state = genesis_state(&spec);
+        let scenario: Scenario = serde_yaml::from_str("sync_committee: []").unwrap();
+
+        assert!(scenario.apply(&mut state, &spec).is_err());
+    }
+}
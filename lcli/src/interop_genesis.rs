@@ -1,5 +1,6 @@
+use crate::genesis_scenario::Scenario;
 use clap::ArgMatches;
-use clap_utils::parse_ssz_optional;
+use clap_utils::{parse_optional, parse_ssz_optional};
 use eth2_network_config::Eth2NetworkConfig;
 use genesis::{interop_genesis_state, DEFAULT_ETH1_BLOCK_HASH};
 use ssz::Encode;
@@ -8,11 +9,17 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use types::{test_utils::generate_deterministic_keypairs, EthSpec, Hash256};
 
 pub fn run<T: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(), String> {
+    let scenario = parse_optional(matches, "scenario")?
+        .map(|path: PathBuf| Scenario::from_file(&path))
+        .transpose()?
+        .unwrap_or_default();
+
     let validator_count = matches
         .value_of("validator-count")
         .ok_or("validator-count not specified")?
         .parse::<usize>()
         .map_err(|e| format!("Unable to parse validator-count: {}", e))?;
+    let validator_count = scenario.validator_count.unwrap_or(validator_count);
 
     let genesis_time = if let Some(genesis_time) = matches.value_of("genesis-time") {
         genesis_time
@@ -34,13 +41,14 @@ pub fn run<T: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(),
     }
 
     let keypairs = generate_deterministic_keypairs(validator_count);
-    let genesis_state = interop_genesis_state::<T>(
+    let mut genesis_state = interop_genesis_state::<T>(
         &keypairs,
         genesis_time,
         Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
         None,
         &spec,
     )?;
+    scenario.apply(&mut genesis_state, &spec)?;
 
     eth2_network_config.genesis_state_bytes = Some(genesis_state.as_ssz_bytes());
     eth2_network_config.force_write_to_file(testnet_dir)?;
@@ -2,11 +2,14 @@
 extern crate log;
 mod change_genesis_time;
 mod check_deposit_data;
+mod check_merge_transition;
 mod create_payload_header;
 mod deploy_deposit_contract;
+mod diff_ssz;
 mod eth1_genesis;
 mod etl;
 mod generate_bootnode_enr;
+mod genesis_scenario;
 mod insecure_validators;
 mod interop_genesis;
 mod new_testnet;
@@ -77,6 +80,36 @@ fn main() {
                         .required(true)
                         .default_value("./output.ssz")
                         .help("Path to output a SSZ file."),
+                )
+                .arg(
+                    Arg::with_name("checkpoint-interval")
+                        .long("checkpoint-interval")
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .requires("checkpoint-dir")
+                        .help(
+                            "Write the state to --checkpoint-dir at each slot which is a \
+                            multiple of this value.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("checkpoint-dir")
+                        .long("checkpoint-dir")
+                        .value_name("DIR")
+                        .takes_value(true)
+                        .help(
+                            "Directory in which to write intermediate states, as \
+                            state_<slot>.ssz files.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("partial-state-cache")
+                        .long("partial-state-cache")
+                        .takes_value(false)
+                        .help(
+                            "Only build the committee caches before skipping slots, rather than \
+                            every cache. Reduces the memory used for states with many validators.",
+                        ),
                 ),
         )
         .subcommand(
@@ -103,6 +136,24 @@ fn main() {
                         .required(true)
                         .default_value("./output.ssz")
                         .help("Path to output a SSZ file."),
+                )
+                .arg(
+                    Arg::with_name("no-signature-verification")
+                        .long("no-signature-verification")
+                        .takes_value(false)
+                        .help("Do not verify the signatures in the block."),
+                )
+                .arg(
+                    Arg::with_name("runs")
+                        .long("runs")
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .default_value("1")
+                        .help(
+                            "Number of times to repeat the state transition, starting from the \
+                            pre-state each time. The time taken by each run is logged, broken \
+                            down by per-slot, epoch and block processing stages and tree hashing.",
+                        ),
                 ),
         )
         .subcommand(
@@ -134,6 +185,45 @@ fn main() {
                         .help("Path to SSZ bytes"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("diff-states")
+                .about(
+                    "Prints the differences between two SSZ-encoded beacon states, with the \
+                    differences in validator fields grouped by validator.",
+                )
+                .arg(
+                    Arg::with_name("ssz-a")
+                        .value_name("BEACON_STATE")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a SSZ file of the first state."),
+                )
+                .arg(
+                    Arg::with_name("ssz-b")
+                        .value_name("BEACON_STATE")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a SSZ file of the second state."),
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("diff-blocks")
+                .about("Prints the differences between two SSZ-encoded signed beacon blocks.")
+                .arg(
+                    Arg::with_name("ssz-a")
+                        .value_name("BEACON_BLOCK")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a SSZ file of the first block."),
+                )
+                .arg(
+                    Arg::with_name("ssz-b")
+                        .value_name("BEACON_BLOCK")
+                        .takes_value(true)
+                        .required(true)
+                        .help("Path to a SSZ file of the second block."),
+                )
+        )
         .subcommand(
             SubCommand::with_name("deploy-deposit-contract")
                 .about(
@@ -219,6 +309,17 @@ fn main() {
                             "Used to avoid reply attacks between testnets. Recommended to set to
                               non-default.",
                         ),
+                )
+                .arg(
+                    Arg::with_name("scenario")
+                        .long("scenario")
+                        .value_name("YAML_FILE")
+                        .takes_value(true)
+                        .help(
+                            "Path to a YAML file of balance overrides, exits, slashings and sync \
+                            committee members to apply to the genesis state. A validator_count in \
+                            the file overrides --validator-count.",
+                        ),
                 ),
         )
         .subcommand(
@@ -494,6 +595,17 @@ fn main() {
                         .takes_value(true)
                         .help("The number of validators when generating a genesis state."),
                 )
+                .arg(
+                    Arg::with_name("scenario")
+                        .long("scenario")
+                        .value_name("YAML_FILE")
+                        .takes_value(true)
+                        .help(
+                            "Path to a YAML file of balance overrides, exits, slashings and sync \
+                            committee members to apply to the interop genesis state. A \
+                            validator_count in the file overrides --validator-count.",
+                        ),
+                )
                 .arg(
                     Arg::with_name("genesis-time")
                         .long("genesis-time")
@@ -574,6 +686,63 @@ fn main() {
                         ),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("check-merge-transition")
+                .about(
+                    "Performs the terminal block checks which a beacon node would perform during \
+                    the merge transition, against an execution engine.",
+                )
+                .arg(
+                    Arg::with_name("execution-endpoint")
+                        .long("execution-endpoint")
+                        .value_name("HTTP-ENDPOINT")
+                        .takes_value(true)
+                        .required(true)
+                        .help("The URL of the execution engine's Engine API."),
+                )
+                .arg(
+                    Arg::with_name("jwt-secret")
+                        .long("jwt-secret")
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Path to the secret shared with the execution engine."),
+                )
+                .arg(
+                    Arg::with_name("block-hash")
+                        .long("block-hash")
+                        .value_name("HASH")
+                        .takes_value(true)
+                        .help(
+                            "The execution block to check as the terminal block. Defaults to the \
+                            terminal block found by the execution engine.",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("terminal-total-difficulty-override")
+                        .long("terminal-total-difficulty-override")
+                        .value_name("INTEGER")
+                        .takes_value(true)
+                        .help("Overrides the terminal total difficulty of the testnet dir."),
+                )
+                .arg(
+                    Arg::with_name("terminal-block-hash-override")
+                        .long("terminal-block-hash-override")
+                        .value_name("HASH")
+                        .takes_value(true)
+                        .help("Overrides the terminal block hash of the testnet dir."),
+                )
+                .arg(
+                    Arg::with_name("terminal-block-hash-epoch-override")
+                        .long("terminal-block-hash-epoch-override")
+                        .value_name("EPOCH")
+                        .takes_value(true)
+                        .requires("terminal-block-hash-override")
+                        .help(
+                            "Overrides the terminal block hash activation epoch of the testnet \
+                            dir.",
+                        ),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("insecure-validators")
                 .about("Produces validator directories with INSECURE, deterministic keypairs.")
@@ -688,8 +857,10 @@ fn run<T: EthSpec>(
             debug_level: "trace",
             logfile_debug_level: "trace",
             log_format: None,
+            logfile_format: None,
             max_log_size: 0,
             max_log_number: 0,
+            max_log_age: None,
             compression: false,
         })
         .map_err(|e| format!("should start logger: {:?}", e))?
@@ -710,6 +881,10 @@ fn run<T: EthSpec>(
         ("pretty-ssz", Some(matches)) => {
             run_parse_ssz::<T>(matches).map_err(|e| format!("Failed to pretty print hex: {}", e))
         }
+        ("diff-states", Some(matches)) => diff_ssz::run_diff_states::<T>(testnet_dir, matches)
+            .map_err(|e| format!("Failed to diff states: {}", e)),
+        ("diff-blocks", Some(matches)) => diff_ssz::run_diff_blocks::<T>(testnet_dir, matches)
+            .map_err(|e| format!("Failed to diff blocks: {}", e)),
         ("deploy-deposit-contract", Some(matches)) => {
             deploy_deposit_contract::run::<T>(env, matches)
                 .map_err(|e| format!("Failed to run deploy-deposit-contract command: {}", e))
@@ -734,6 +909,10 @@ fn run<T: EthSpec>(
             .map_err(|e| format!("Failed to run check-deposit-data command: {}", e)),
         ("generate-bootnode-enr", Some(matches)) => generate_bootnode_enr::run::<T>(matches)
             .map_err(|e| format!("Failed to run generate-bootnode-enr command: {}", e)),
+        ("check-merge-transition", Some(matches)) => {
+            check_merge_transition::run::<T>(env, testnet_dir, matches)
+                .map_err(|e| format!("Failed to run check-merge-transition command: {}", e))
+        }
         ("insecure-validators", Some(matches)) => insecure_validators::run(matches)
             .map_err(|e| format!("Failed to run insecure-validators command: {}", e)),
         ("etl-block-efficiency", Some(matches)) => env
@@ -1,3 +1,4 @@
+use crate::genesis_scenario::Scenario;
 use clap::ArgMatches;
 use clap_utils::{parse_optional, parse_required, parse_ssz_optional};
 use eth2_network_config::Eth2NetworkConfig;
@@ -101,17 +102,26 @@ pub fn run<T: EthSpec>(testnet_dir_path: PathBuf, matches: &ArgMatches) -> Resul
             (eth1_block_hash, genesis_time)
         };
 
-        let validator_count = parse_required(matches, "validator-count")?;
+        let scenario = parse_optional(matches, "scenario")?
+            .map(|path: PathBuf| Scenario::from_file(&path))
+            .transpose()?
+            .unwrap_or_default();
+
+        let validator_count = match scenario.validator_count {
+            Some(validator_count) => validator_count,
+            None => parse_required(matches, "validator-count")?,
+        };
 
         let keypairs = generate_deterministic_keypairs(validator_count);
 
-        let genesis_state = interop_genesis_state::<T>(
+        let mut genesis_state = interop_genesis_state::<T>(
             &keypairs,
             genesis_time,
             eth1_block_hash,
             execution_payload_header,
             &spec,
         )?;
+        scenario.apply(&mut genesis_state, &spec)?;
 
         Some(genesis_state.as_ssz_bytes())
     } else {
@@ -1,11 +1,12 @@
 use crate::transition_blocks::load_from_ssz_with;
 use clap::ArgMatches;
+use clap_utils::parse_optional;
 use eth2_network_config::Eth2NetworkConfig;
 use ssz::Encode;
 use state_processing::per_slot_processing;
 use std::fs::File;
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use types::{BeaconState, EthSpec};
 
 pub fn run<T: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(), String> {
@@ -27,6 +28,22 @@ pub fn run<T: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(),
         .parse::<PathBuf>()
         .map_err(|e| format!("Failed to parse output path: {}", e))?;
 
+    let checkpoint_interval: Option<u64> = parse_optional(matches, "checkpoint-interval")?;
+    let checkpoint_dir: Option<PathBuf> = parse_optional(matches, "checkpoint-dir")?;
+    let checkpoints = match (checkpoint_interval, checkpoint_dir) {
+        (Some(0), _) => return Err("--checkpoint-interval must be at least 1".to_string()),
+        (Some(interval), Some(dir)) => {
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Unable to create checkpoint dir {:?}: {:?}", dir, e))?;
+            Some((interval, dir))
+        }
+        (Some(_), None) => {
+            return Err("--checkpoint-interval requires --checkpoint-dir".to_string())
+        }
+        (None, _) => None,
+    };
+    let partial_state_cache = matches.is_present("partial-state-cache");
+
     info!("Using {} spec", T::spec_name());
     info!("Pre-state path: {:?}", pre_state_path);
     info!("Slots: {:?}", slots);
@@ -37,22 +54,48 @@ pub fn run<T: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(),
     let mut state: BeaconState<T> =
         load_from_ssz_with(&pre_state_path, spec, BeaconState::from_ssz_bytes)?;
 
-    state
-        .build_all_caches(spec)
-        .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+    if partial_state_cache {
+        // Slot processing only requires the committee caches up-front. Skipping the pubkey cache
+        // saves time and memory for states with large validator sets.
+        state
+            .build_all_committee_caches(spec)
+            .map_err(|e| format!("Unable to build committee caches: {:?}", e))?;
+    } else {
+        state
+            .build_all_caches(spec)
+            .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+    }
 
     // Transition the parent state to the block slot.
     for i in 0..slots {
         per_slot_processing(&mut state, None, spec)
             .map_err(|e| format!("Failed to advance slot on iteration {}: {:?}", i, e))?;
+
+        if let Some((interval, dir)) = &checkpoints {
+            if state.slot().as_u64() % interval == 0 {
+                let state_root = state
+                    .update_tree_hash_cache()
+                    .map_err(|e| format!("Unable to compute state root: {:?}", e))?;
+                let path = dir.join(format!("state_{}.ssz", state.slot()));
+                write_state(&path, &state)?;
+                info!(
+                    "Wrote state at slot {} with root {:?} to {:?}",
+                    state.slot(),
+                    state_root,
+                    path
+                );
+            }
+        }
     }
 
-    let mut output_file =
-        File::create(output_path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
+    write_state(&output_path, &state)
+}
+
+fn write_state<T: EthSpec>(path: &Path, state: &BeaconState<T>) -> Result<(), String> {
+    let mut output_file = File::create(path)
+        .map_err(|e| format!("Unable to create output file {:?}: {:?}", path, e))?;
 
     output_file
         .write_all(&state.as_ssz_bytes())
-        .map_err(|e| format!("Unable to write to output file: {:?}", e))?;
-
-    Ok(())
+        .map_err(|e| format!("Unable to write to output file {:?}: {:?}", path, e))
 }
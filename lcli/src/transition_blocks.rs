@@ -1,14 +1,29 @@
 use clap::ArgMatches;
+use clap_utils::parse_required;
 use eth2_network_config::Eth2NetworkConfig;
 use ssz::Encode;
 use state_processing::{
-    per_block_processing, per_slot_processing, BlockSignatureStrategy, VerifyBlockRoot,
+    per_block_processing, per_epoch_processing::EPOCH_PROCESSING_STAGE_TIMES, per_slot_processing,
+    BlockSignatureStrategy, VerifyBlockRoot,
 };
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use types::{BeaconState, ChainSpec, EthSpec, SignedBeaconBlock};
 
+/// The time taken by each part of a single state transition.
+#[derive(Default)]
+struct Timings {
+    build_caches: Duration,
+    tree_hash: Duration,
+    per_slot_processing: Duration,
+    per_block_processing: Duration,
+    /// The time taken by each stage of epoch processing, which is part of `per_slot_processing`.
+    epoch_stages: Vec<(String, Duration)>,
+}
+
 pub fn run_transition_blocks<T: EthSpec>(
     testnet_dir: PathBuf,
     matches: &ArgMatches,
@@ -31,9 +46,21 @@ pub fn run_transition_blocks<T: EthSpec>(
         .parse::<PathBuf>()
         .map_err(|e| format!("Failed to parse output path: {}", e))?;
 
+    let runs: usize = parse_required(matches, "runs")?;
+    if runs == 0 {
+        return Err("--runs must be at least 1".to_string());
+    }
+    let verify_signatures = !matches.is_present("no-signature-verification");
+    let signature_strategy = if verify_signatures {
+        BlockSignatureStrategy::VerifyIndividual
+    } else {
+        BlockSignatureStrategy::NoVerification
+    };
+
     info!("Using {} spec", T::spec_name());
     info!("Pre-state path: {:?}", pre_state_path);
     info!("Block path: {:?}", block_path);
+    info!("Verify signatures: {}", verify_signatures);
 
     let eth2_network_config = Eth2NetworkConfig::load(testnet_dir)?;
     let spec = &eth2_network_config.chain_spec::<T>()?;
@@ -43,7 +70,22 @@ pub fn run_transition_blocks<T: EthSpec>(
     let block: SignedBeaconBlock<T> =
         load_from_ssz_with(&block_path, spec, SignedBeaconBlock::from_ssz_bytes)?;
 
-    let post_state = do_transition(pre_state, block, spec)?;
+    let mut post_state = None;
+    let mut total_time = Duration::default();
+    for run in 1..=runs {
+        let (state, timings) = do_transition(pre_state.clone(), &block, signature_strategy, spec)?;
+        total_time += timings.total();
+        log_timings(run, &timings);
+        post_state = Some(state);
+    }
+    if runs > 1 {
+        info!(
+            "Mean transition time over {} runs: {:?}",
+            runs,
+            total_time / runs as u32
+        );
+    }
+    let post_state = post_state.ok_or("No state transition was run")?;
 
     let mut output_file =
         File::create(output_path).map_err(|e| format!("Unable to create output file: {:?}", e))?;
@@ -57,34 +99,106 @@ pub fn run_transition_blocks<T: EthSpec>(
 
 fn do_transition<T: EthSpec>(
     mut pre_state: BeaconState<T>,
-    block: SignedBeaconBlock<T>,
+    block: &SignedBeaconBlock<T>,
+    signature_strategy: BlockSignatureStrategy,
     spec: &ChainSpec,
-) -> Result<BeaconState<T>, String> {
+) -> Result<(BeaconState<T>, Timings), String> {
+    let mut timings = Timings::default();
+    let epoch_stage_times_before = epoch_stage_times();
+
+    let t = Instant::now();
     pre_state
         .build_all_caches(spec)
         .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+    timings.build_caches += t.elapsed();
 
     // Transition the parent state to the block slot.
     for i in pre_state.slot().as_u64()..block.slot().as_u64() {
-        per_slot_processing(&mut pre_state, None, spec)
+        // Compute the state root separately so that tree hashing is timed on its own.
+        let t = Instant::now();
+        let state_root = pre_state
+            .update_tree_hash_cache()
+            .map_err(|e| format!("Unable to compute state root on iteration {}: {:?}", i, e))?;
+        timings.tree_hash += t.elapsed();
+
+        let t = Instant::now();
+        per_slot_processing(&mut pre_state, Some(state_root), spec)
             .map_err(|e| format!("Failed to advance slot on iteration {}: {:?}", i, e))?;
+        timings.per_slot_processing += t.elapsed();
     }
 
+    let t = Instant::now();
     pre_state
         .build_all_caches(spec)
         .map_err(|e| format!("Unable to build caches: {:?}", e))?;
+    timings.build_caches += t.elapsed();
 
+    let t = Instant::now();
     per_block_processing(
         &mut pre_state,
-        &block,
+        block,
         None,
-        BlockSignatureStrategy::VerifyIndividual,
+        signature_strategy,
         VerifyBlockRoot::True,
         spec,
     )
     .map_err(|e| format!("State transition failed: {:?}", e))?;
+    timings.per_block_processing += t.elapsed();
+
+    let t = Instant::now();
+    pre_state
+        .update_tree_hash_cache()
+        .map_err(|e| format!("Unable to compute post-state root: {:?}", e))?;
+    timings.tree_hash += t.elapsed();
+
+    let mut epoch_stages = epoch_stage_times()
+        .into_iter()
+        .map(|(stage, seconds)| {
+            let before = epoch_stage_times_before.get(&stage).copied().unwrap_or(0.0);
+            (stage, Duration::from_secs_f64((seconds - before).max(0.0)))
+        })
+        .filter(|(_, duration)| *duration > Duration::default())
+        .collect::<Vec<_>>();
+    epoch_stages.sort_by(|(_, a), (_, b)| b.cmp(a));
+    timings.epoch_stages = epoch_stages;
+
+    Ok((pre_state, timings))
+}
+
+impl Timings {
+    fn total(&self) -> Duration {
+        self.build_caches + self.tree_hash + self.per_slot_processing + self.per_block_processing
+    }
+}
+
+fn log_timings(run: usize, timings: &Timings) {
+    info!("Run {}: total {:?}", run, timings.total());
+    info!("  build caches: {:?}", timings.build_caches);
+    info!("  tree hashing: {:?}", timings.tree_hash);
+    info!("  per-slot processing: {:?}", timings.per_slot_processing);
+    for (stage, duration) in &timings.epoch_stages {
+        info!("    epoch processing {}: {:?}", stage, duration);
+    }
+    info!("  per-block processing: {:?}", timings.per_block_processing);
+}
 
-    Ok(pre_state)
+/// Returns the total number of seconds spent in each stage of epoch processing by this process,
+/// as recorded by the `state_processing` metrics.
+fn epoch_stage_times() -> HashMap<String, f64> {
+    lighthouse_metrics::gather()
+        .iter()
+        .filter(|family| family.get_name() == EPOCH_PROCESSING_STAGE_TIMES)
+        .flat_map(|family| family.get_metric())
+        .filter_map(|metric| {
+            let stage = metric
+                .get_label()
+                .iter()
+                .find(|label| label.get_name() == "stage")?
+                .get_value()
+                .to_string();
+            Some((stage, metric.get_histogram().get_sample_sum()))
+        })
+        .collect()
 }
 
 pub fn load_from_ssz_with<T>(
@@ -12,14 +12,14 @@ use eth2_network_config::Eth2NetworkConfig;
 use futures::channel::mpsc::{channel, Receiver, Sender};
 use futures::{future, StreamExt};
 
-use slog::{error, info, o, warn, Drain, Duplicate, Level, Logger};
-use sloggers::{
-    file::FileLoggerBuilder, null::NullLoggerBuilder, types::Format, types::Severity, Build,
-};
+use logging::{LogLevels, LogLevelsFilter, RotatingFile, RotatingFileConfig};
+use slog::{error, info, o, warn, Drain, Duplicate, FnValue, Level, Logger, Record};
+use sloggers::{null::NullLoggerBuilder, Build};
 use std::fs::create_dir_all;
 use std::path::PathBuf;
 use std::sync::Arc;
-use task_executor::{ShutdownReason, TaskExecutor};
+use std::time::Duration;
+use task_executor::{ShutdownHooks, ShutdownReason, TaskExecutor};
 use tokio::runtime::{Builder as RuntimeBuilder, Runtime};
 use types::{EthSpec, MainnetEthSpec, MinimalEthSpec};
 
@@ -46,16 +46,50 @@ pub struct LoggerConfig<'a> {
     pub path: Option<PathBuf>,
     pub debug_level: &'a str,
     pub logfile_debug_level: &'a str,
+    /// The format of logs to stdout, either `None` for the terminal format or `Some("JSON")`.
     pub log_format: Option<&'a str>,
+    /// The format of logs to `path`, as for `log_format`.
+    pub logfile_format: Option<&'a str>,
     pub max_log_size: u64,
+    /// The number of rotated log files to keep.
     pub max_log_number: usize,
+    /// Rotate the log file after this long, as well as when it reaches `max_log_size` bytes.
+    pub max_log_age: Option<Duration>,
     pub compression: bool,
 }
 
+/// Returns `true` if `format` selects JSON logs, or `false` if it selects the terminal format.
+fn is_json_format(format: Option<&str>) -> Result<bool, String> {
+    match format.map(str::to_uppercase).as_deref() {
+        None => Ok(false),
+        Some("JSON") => Ok(true),
+        Some(_) => Err("Logging format provided is not supported".to_string()),
+    }
+}
+
+/// Log all modules at trace level, or restore the configured log levels if any have been
+/// overridden.
+#[cfg(target_family = "unix")]
+fn toggle_trace_logging(log_levels: &LogLevels, log: &Logger) {
+    if log_levels.is_empty() {
+        log_levels.set(String::new(), Level::Trace);
+        info!(log, "Logging all modules at trace level"; "reason" => "Received SIGUSR1");
+    } else {
+        info!(log, "Restoring configured log levels"; "reason" => "Received SIGUSR1");
+        log_levels.clear();
+    }
+}
+
+/// The source location of a log, under the `module` key.
+fn record_module(record: &Record) -> String {
+    format!("{}:{}", record.module(), record.line())
+}
+
 /// Builds an `Environment`.
 pub struct EnvironmentBuilder<E: EthSpec> {
     runtime: Option<Arc<Runtime>>,
     log: Option<Logger>,
+    log_levels: Option<LogLevels>,
     eth_spec_instance: E,
     eth2_config: Eth2Config,
     eth2_network_config: Option<Eth2NetworkConfig>,
@@ -67,6 +101,7 @@ impl EnvironmentBuilder<MinimalEthSpec> {
         Self {
             runtime: None,
             log: None,
+            log_levels: None,
             eth_spec_instance: MinimalEthSpec,
             eth2_config: Eth2Config::minimal(),
             eth2_network_config: None,
@@ -80,6 +115,7 @@ impl EnvironmentBuilder<MainnetEthSpec> {
         Self {
             runtime: None,
             log: None,
+            log_levels: None,
             eth_spec_instance: MainnetEthSpec,
             eth2_config: Eth2Config::mainnet(),
             eth2_network_config: None,
@@ -115,16 +151,16 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
     /// Note that background file logging will spawn a new thread.
     pub fn initialize_logger(mut self, config: LoggerConfig) -> Result<Self, String> {
         // Setting up the initial logger format and build it.
-        let stdout_drain = if let Some(format) = config.log_format {
-            match format.to_uppercase().as_str() {
-                "JSON" => {
-                    let stdout_drain = slog_json::Json::default(std::io::stdout()).fuse();
-                    slog_async::Async::new(stdout_drain)
-                        .chan_size(LOG_CHANNEL_SIZE)
-                        .build()
-                }
-                _ => return Err("Logging format provided is not supported".to_string()),
-            }
+        let stdout_drain = if is_json_format(config.log_format)? {
+            // Use the same keys as the JSON log file, so that both can be ingested alike.
+            let stdout_drain = slog_json::Json::new(std::io::stdout())
+                .add_default_keys()
+                .add_key_value(o!("module" => FnValue(record_module)))
+                .build()
+                .fuse();
+            slog_async::Async::new(stdout_drain)
+                .chan_size(LOG_CHANNEL_SIZE)
+                .build()
         } else {
             let stdout_decorator = slog_term::TermDecorator::new().build();
             let stdout_decorator =
@@ -135,17 +171,24 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
                 .build()
         };
 
-        let stdout_drain = match config.debug_level {
-            "info" => stdout_drain.filter_level(Level::Info),
-            "debug" => stdout_drain.filter_level(Level::Debug),
-            "trace" => stdout_drain.filter_level(Level::Trace),
-            "warn" => stdout_drain.filter_level(Level::Warning),
-            "error" => stdout_drain.filter_level(Level::Error),
-            "crit" => stdout_drain.filter_level(Level::Critical),
+        let stdout_level = match config.debug_level {
+            "info" => Level::Info,
+            "debug" => Level::Debug,
+            "trace" => Level::Trace,
+            "warn" => Level::Warning,
+            "error" => Level::Error,
+            "crit" => Level::Critical,
             unknown => return Err(format!("Unknown debug-level: {}", unknown)),
         };
 
-        let stdout_logger = Logger::root(stdout_drain.fuse(), o!());
+        // The levels of both the stdout and file drains may be overridden at runtime.
+        let log_levels = LogLevels::default();
+        self.log_levels = Some(log_levels.clone());
+
+        let stdout_logger = Logger::root(
+            LogLevelsFilter::new(stdout_drain, stdout_level, log_levels.clone()).fuse(),
+            o!(),
+        );
 
         // Disable file logging if values set to 0.
         if config.max_log_size == 0 || config.max_log_number == 0 {
@@ -174,28 +217,46 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
         }
 
         let logfile_level = match config.logfile_debug_level {
-            "info" => Severity::Info,
-            "debug" => Severity::Debug,
-            "trace" => Severity::Trace,
-            "warn" => Severity::Warning,
-            "error" => Severity::Error,
-            "crit" => Severity::Critical,
+            "info" => Level::Info,
+            "debug" => Level::Debug,
+            "trace" => Level::Trace,
+            "warn" => Level::Warning,
+            "error" => Level::Error,
+            "crit" => Level::Critical,
             unknown => return Err(format!("Unknown loglevel-debug-level: {}", unknown)),
         };
 
-        let file_logger = FileLoggerBuilder::new(&path)
-            .level(logfile_level)
-            .channel_size(LOG_CHANNEL_SIZE)
-            .format(match config.log_format {
-                Some("JSON") => Format::Json,
-                _ => Format::default(),
-            })
-            .rotate_size(config.max_log_size)
-            .rotate_keep(config.max_log_number)
-            .rotate_compress(config.compression)
-            .restrict_permissions(true)
-            .build()
-            .map_err(|e| format!("Unable to build file logger: {}", e))?;
+        let file = RotatingFile::open(RotatingFileConfig {
+            path: path.clone(),
+            max_size: config.max_log_size,
+            max_age: config.max_log_age,
+            max_files: config.max_log_number,
+            compress: config.compression,
+        })
+        .map_err(|e| format!("Unable to open log file {:?}: {:?}", path, e))?;
+
+        // Both formats flush once per record, which is when the file may be rotated.
+        let file_drain = if is_json_format(config.logfile_format)? {
+            let file_drain = slog_json::Json::new(file)
+                .set_flush(true)
+                .add_default_keys()
+                .build()
+                .fuse();
+            slog_async::Async::new(file_drain)
+                .chan_size(LOG_CHANNEL_SIZE)
+                .build()
+        } else {
+            let file_decorator = slog_term::PlainDecorator::new(file);
+            let file_drain = slog_term::FullFormat::new(file_decorator).build().fuse();
+            slog_async::Async::new(file_drain)
+                .chan_size(LOG_CHANNEL_SIZE)
+                .build()
+        };
+
+        let file_logger = Logger::root(
+            LogLevelsFilter::new(file_drain, logfile_level, log_levels).fuse(),
+            o!("module" => FnValue(record_module)),
+        );
 
         let log = Logger::root(Duplicate::new(stdout_logger, file_logger).fuse(), o!());
 
@@ -247,6 +308,8 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
             signal: Some(signal),
             exit,
             log: self.log.ok_or("Cannot build environment without log")?,
+            log_levels: self.log_levels,
+            shutdown_hooks: ShutdownHooks::default(),
             eth_spec_instance: self.eth_spec_instance,
             eth2_config: self.eth2_config,
             eth2_network_config: self.eth2_network_config.map(Arc::new),
@@ -264,6 +327,9 @@ pub struct RuntimeContext<E: EthSpec> {
     pub eth_spec_instance: E,
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
+    /// Overrides of the log levels, which are `None` unless the logger was initialized with a
+    /// `LoggerConfig`.
+    pub log_levels: Option<LogLevels>,
 }
 
 impl<E: EthSpec> RuntimeContext<E> {
@@ -276,6 +342,7 @@ impl<E: EthSpec> RuntimeContext<E> {
             eth_spec_instance: self.eth_spec_instance.clone(),
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
+            log_levels: self.log_levels.clone(),
         }
     }
 
@@ -301,6 +368,9 @@ pub struct Environment<E: EthSpec> {
     signal: Option<exit_future::Signal>,
     exit: exit_future::Exit,
     log: Logger,
+    log_levels: Option<LogLevels>,
+    /// Futures registered by services to run during a graceful shutdown.
+    shutdown_hooks: ShutdownHooks,
     eth_spec_instance: E,
     pub eth2_config: Eth2Config,
     pub eth2_network_config: Option<Arc<Eth2NetworkConfig>>,
@@ -323,10 +393,12 @@ impl<E: EthSpec> Environment<E> {
                 self.exit.clone(),
                 self.log.clone(),
                 self.signal_tx.clone(),
-            ),
+            )
+            .with_shutdown_hooks(self.shutdown_hooks.clone()),
             eth_spec_instance: self.eth_spec_instance.clone(),
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
+            log_levels: self.log_levels.clone(),
         }
     }
 
@@ -338,10 +410,12 @@ impl<E: EthSpec> Environment<E> {
                 self.exit.clone(),
                 self.log.new(o!("service" => service_name)),
                 self.signal_tx.clone(),
-            ),
+            )
+            .with_shutdown_hooks(self.shutdown_hooks.clone()),
             eth_spec_instance: self.eth_spec_instance.clone(),
             eth2_config: self.eth2_config.clone(),
             eth2_network_config: self.eth2_network_config.clone(),
+            log_levels: self.log_levels.clone(),
         }
     }
 
@@ -389,6 +463,21 @@ impl<E: EthSpec> Environment<E> {
                 Err(e) => error!(self.log, "Could not register SIGHUP handler"; "error" => e),
             }
 
+            // setup for toggling trace logging with SIGUSR1, which doesn't shut down
+            if let Some(log_levels) = self.log_levels.clone() {
+                match signal(SignalKind::user_defined1()) {
+                    Ok(mut usr1_stream) => {
+                        let log = self.log.clone();
+                        tokio::spawn(async move {
+                            while usr1_stream.recv().await.is_some() {
+                                toggle_trace_logging(&log_levels, &log);
+                            }
+                        });
+                    }
+                    Err(e) => error!(self.log, "Could not register SIGUSR1 handler"; "error" => e),
+                }
+            }
+
             future::select(inner_shutdown, future::select_all(handles.into_iter())).await
         }) {
             future::Either::Left((Ok(reason), _)) => {
@@ -449,6 +538,20 @@ impl<E: EthSpec> Environment<E> {
         }
     }
 
+    /// Run the hooks registered by services to shut down gracefully, allowing each stage of the
+    /// shutdown at most `stage_timeout`.
+    ///
+    /// Returns `false` if any stage timed out.
+    pub fn run_shutdown_hooks(&self, stage_timeout: Duration) -> bool {
+        info!(
+            self.log,
+            "Shutting down gracefully";
+            "stage_timeout_secs" => stage_timeout.as_secs(),
+        );
+        self.runtime()
+            .block_on(self.shutdown_hooks.run(stage_timeout, &self.log))
+    }
+
     /// Shutdown the `tokio` runtime when all tasks are idle.
     pub fn shutdown_on_idle(self) {
         match Arc::try_unwrap(self.runtime) {
@@ -0,0 +1,394 @@
+//! Supplies beacon node flags from a TOML or YAML file, via `--config-file`.
+//!
+//! Each key of the file is the long name of a flag, without the leading dashes. Flags which don't
+//! take a value are enabled with `true`, and flags which may be given multiple times accept a
+//! list. Flags given on the command line take precedence over the file.
+use clap::{App, AppSettings, Arg, ArgMatches, ErrorKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CMD: &str = "config";
+pub const DUMP_CMD: &str = "dump";
+pub const CONFIG_FILE_FLAG: &str = "config-file";
+
+/// The name and aliases of the beacon node subcommand, which is the only one to read config files.
+pub const BEACON_NODE_CMDS: &[&str] = &["beacon_node", "b", "bn", "beacon"];
+
+/// The value of a single key in a config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<ConfigValue>),
+}
+
+impl ConfigValue {
+    /// Parse a value given on the command line, preferring an integer where it is unambiguous.
+    fn from_arg(value: &str) -> Self {
+        match value.parse::<i64>() {
+            Ok(integer) if integer.to_string() == value => ConfigValue::Integer(integer),
+            _ => ConfigValue::String(value.to_string()),
+        }
+    }
+
+    /// Returns the value as it would be given on the command line, or `None` if it is not a
+    /// scalar.
+    fn to_arg(&self) -> Option<String> {
+        match self {
+            ConfigValue::Integer(integer) => Some(integer.to_string()),
+            ConfigValue::Float(float) => Some(float.to_string()),
+            ConfigValue::String(string) => Some(string.clone()),
+            ConfigValue::Bool(_) | ConfigValue::List(_) => None,
+        }
+    }
+}
+
+pub type ConfigFile = BTreeMap<String, ConfigValue>;
+
+/// Read a config file, in a format determined by its extension.
+pub fn load(path: &Path) -> Result<ConfigFile, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read config file {:?}: {:?}", path, e))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::from_str(&contents)
+            .map_err(|e| format!("Unable to parse config file {:?}: {}", path, e)),
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+            .map_err(|e| format!("Unable to parse config file {:?}: {}", path, e)),
+        _ => Err(format!(
+            "Config file {:?} must have a .toml, .yaml or .yml extension",
+            path
+        )),
+    }
+}
+
+/// Write a config file, in a format determined by its extension.
+pub fn save(path: &Path, config_file: &ConfigFile) -> Result<(), String> {
+    let contents = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::to_string(config_file)
+            .map_err(|e| format!("Unable to serialize config file: {}", e))?,
+        Some("yaml") | Some("yml") => serde_yaml::to_string(config_file)
+            .map_err(|e| format!("Unable to serialize config file: {}", e))?,
+        _ => {
+            return Err(format!(
+                "Config file {:?} must have a .toml, .yaml or .yml extension",
+                path
+            ))
+        }
+    };
+    fs::write(path, contents).map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+}
+
+/// Command line arguments, extended with those read from a config file.
+pub struct Args {
+    pub args: Vec<OsString>,
+    /// The path of the config file and the keys which were read from it, if any.
+    config_file: Option<(PathBuf, Vec<String>)>,
+}
+
+impl Args {
+    /// Returns a message naming the config file if `error` is due to one of its keys not being a
+    /// flag, since the key won't appear on the command line.
+    pub fn unknown_key_error(&self, error: &clap::Error) -> Option<String> {
+        let (path, keys) = self.config_file.as_ref()?;
+        if error.kind != ErrorKind::UnknownArgument {
+            return None;
+        }
+        let arg = error.info.as_ref()?.first()?;
+        let key = arg.trim_start_matches('-');
+        keys.iter().find(|k| *k == key).map(|key| {
+            format!(
+                "Unknown key `{}` in config file {:?}, keys must be the long names of beacon \
+                 node flags",
+                key, path
+            )
+        })
+    }
+}
+
+/// If `args` includes `--config-file` for the beacon node, append the flags from the file which
+/// aren't also given in `args`.
+pub fn args_with_config_file(args: Vec<OsString>) -> Result<Args, String> {
+    let unchanged = |args| {
+        Ok(Args {
+            args,
+            config_file: None,
+        })
+    };
+
+    let is_beacon_node = args.iter().skip(1).any(|arg| {
+        arg.to_str()
+            .map_or(false, |arg| BEACON_NODE_CMDS.contains(&arg))
+    });
+    let path = args.iter().enumerate().find_map(|(i, arg)| {
+        let arg = arg.to_str()?.strip_prefix("--")?;
+        if arg == CONFIG_FILE_FLAG {
+            args.get(i + 1).map(PathBuf::from)
+        } else {
+            arg.strip_prefix(CONFIG_FILE_FLAG)?
+                .strip_prefix('=')
+                .map(PathBuf::from)
+        }
+    });
+    let path = match path {
+        // Leave clap to reject any misuse of the flag.
+        Some(path) if is_beacon_node => path,
+        _ => return unchanged(args),
+    };
+
+    let config_file = load(&path)?;
+
+    let cli_flags: HashSet<String> = args
+        .iter()
+        .filter_map(|arg| arg.to_str()?.strip_prefix("--"))
+        .map(|arg| arg.split('=').next().unwrap_or(arg).to_string())
+        .collect();
+
+    let mut file_args = vec![];
+    let mut keys = vec![];
+    for (key, value) in config_file {
+        if key == CONFIG_FILE_FLAG {
+            return Err(format!(
+                "Config file {:?} cannot contain `{}`",
+                path, CONFIG_FILE_FLAG
+            ));
+        }
+        if cli_flags.contains(&key) {
+            continue;
+        }
+
+        let invalid_value = || {
+            format!(
+                "Invalid value for `{}` in config file {:?}, expected a boolean, number, \
+                 string or list of numbers and strings",
+                key, path
+            )
+        };
+        match &value {
+            ConfigValue::Bool(true) => file_args.push(format!("--{}", key)),
+            ConfigValue::Bool(false) => (),
+            ConfigValue::List(values) => {
+                for value in values {
+                    let value = value.to_arg().ok_or_else(invalid_value)?;
+                    file_args.push(format!("--{}={}", key, value));
+                }
+            }
+            value => {
+                let value = value.to_arg().ok_or_else(invalid_value)?;
+                file_args.push(format!("--{}={}", key, value));
+            }
+        }
+        keys.push(key);
+    }
+
+    let mut args = args;
+    args.extend(file_args.into_iter().map(OsString::from));
+    Ok(Args {
+        args,
+        config_file: Some((path, keys)),
+    })
+}
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .about("Manage the config files read by the beacon node's --config-file flag.")
+        .subcommand(
+            App::new(DUMP_CMD)
+                .about(
+                    "Write the given beacon node flags to a config file, e.g. \
+                     `lighthouse config dump --output beacon.toml -- --network mainnet --http`.",
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Path of the config file, ending in .toml, .yaml or .yml.")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("flags")
+                        .value_name("FLAGS")
+                        .help("The beacon node flags, following `--`.")
+                        .multiple(true)
+                        .last(true),
+                ),
+        )
+}
+
+/// Run the `config` command, using `app` to parse beacon node flags.
+pub fn run(cli_args: &ArgMatches, app: App) -> Result<(), String> {
+    match cli_args.subcommand() {
+        (DUMP_CMD, Some(matches)) => dump(matches, app),
+        (unknown, _) => Err(format!(
+            "{} is not a valid {} command. See --help.",
+            unknown, CMD
+        )),
+    }
+}
+
+fn dump(cli_args: &ArgMatches, app: App) -> Result<(), String> {
+    let output = clap_utils::parse_required::<PathBuf>(cli_args, "output")?;
+    let flags: Vec<&str> = cli_args
+        .values_of("flags")
+        .map(Iterator::collect)
+        .unwrap_or_default();
+
+    // Check the flags are valid, and learn which of them take values.
+    let matches = app
+        .get_matches_from_safe(
+            ["lighthouse", BEACON_NODE_CMDS[0]]
+                .iter()
+                .chain(flags.iter()),
+        )
+        .map_err(|e| e.message)?;
+    let matches = matches
+        .subcommand_matches(BEACON_NODE_CMDS[0])
+        .ok_or("Unable to parse beacon node flags")?;
+
+    let mut config_file = ConfigFile::new();
+    for flag in &flags {
+        let name = match flag.strip_prefix("--") {
+            Some(flag) => flag.split('=').next().unwrap_or(flag),
+            None if flag.starts_with('-') && flag.parse::<f64>().is_err() => {
+                return Err(format!("Use the long form of {}", flag));
+            }
+            // The value of the preceding flag.
+            None => continue,
+        };
+        if name == CONFIG_FILE_FLAG {
+            return Err(format!("--{} cannot be written to a config file", name));
+        }
+
+        let value = match matches.values_of(name) {
+            Some(values) => {
+                let mut values: Vec<ConfigValue> = values.map(ConfigValue::from_arg).collect();
+                if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    ConfigValue::List(values)
+                }
+            }
+            None => ConfigValue::Bool(true),
+        };
+        config_file.insert(name.to_string(), value);
+    }
+
+    save(&output, &config_file)?;
+    println!("Wrote {} flags to {:?}", config_file.len(), output);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn appends_flags_not_on_command_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("beacon.toml");
+        fs::write(
+            &path,
+            "http = true\n\
+             staking = false\n\
+             http-port = 5053\n\
+             target-peers = 50\n\
+             boot-nodes = [\"a\", \"b\"]\n",
+        )
+        .unwrap();
+        let path_str = path.to_str().unwrap();
+
+        let result = args_with_config_file(args(&[
+            "lighthouse",
+            "bn",
+            "--config-file",
+            path_str,
+            "--target-peers=80",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            result.args,
+            args(&[
+                "lighthouse",
+                "bn",
+                "--config-file",
+                path_str,
+                "--target-peers=80",
+                "--boot-nodes=a",
+                "--boot-nodes=b",
+                "--http",
+                "--http-port=5053",
+            ])
+        );
+    }
+
+    #[test]
+    fn yaml_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("beacon.yaml");
+        fs::write(&path, "http: true\nhttp-port: 5053\n").unwrap();
+
+        let result = args_with_config_file(args(&[
+            "lighthouse",
+            "bn",
+            &format!("--config-file={}", path.to_str().unwrap()),
+        ]))
+        .unwrap();
+
+        assert_eq!(result.args[3..], args(&["--http", "--http-port=5053"])[..]);
+    }
+
+    #[test]
+    fn ignored_without_beacon_node() {
+        let original = args(&["lighthouse", "vc", "--config-file", "missing.toml"]);
+        let result = args_with_config_file(original.clone()).unwrap();
+        assert_eq!(result.args, original);
+    }
+
+    #[test]
+    fn invalid_config_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let check = |name: &str, contents: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, contents).unwrap();
+            args_with_config_file(args(&[
+                "lighthouse",
+                "bn",
+                "--config-file",
+                path.to_str().unwrap(),
+            ]))
+            .map(|_| ())
+        };
+
+        assert!(check("beacon.toml", "http = true\n").is_ok());
+        assert!(check("beacon.json", "{}").is_err());
+        assert!(check("beacon.toml", "[http]\nport = 5052\n").is_err());
+        assert!(check("beacon.toml", "boot-nodes = [true]\n").is_err());
+        assert!(check("beacon.toml", "config-file = \"other.toml\"\n").is_err());
+    }
+
+    #[test]
+    fn config_value_from_arg() {
+        assert_eq!(ConfigValue::from_arg("42"), ConfigValue::Integer(42));
+        assert_eq!(
+            ConfigValue::from_arg("042"),
+            ConfigValue::String("042".to_string())
+        );
+        assert_eq!(
+            ConfigValue::from_arg("0.5"),
+            ConfigValue::String("0.5".to_string())
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Keeps renamed flags working for a number of releases after they have been renamed.
+//!
+//! To rename a flag, give the `Arg` its new name and add an entry to `DEPRECATED_FLAGS`. Before
+//! the arguments are parsed, each use of the old name is replaced by the new one and a warning is
+//! logged once the logger is running. Only the new name is known to clap, so only it appears in
+//! `--help`. The entry should be removed in the release named by `removed_in`.
+use crate::config_file::BEACON_NODE_CMDS;
+use slog::{warn, Logger};
+use std::ffi::OsString;
+
+/// The name and aliases of the validator client subcommand.
+const VALIDATOR_CLIENT_CMDS: &[&str] = &["validator_client", "v", "vc", "validator"];
+
+/// A flag which has been renamed, but which may still be given by its old name.
+#[derive(Debug, PartialEq)]
+pub struct DeprecatedFlag {
+    /// The name and aliases of the subcommand to which the flag belongs.
+    pub subcommands: &'static [&'static str],
+    /// The long name by which the flag is deprecated.
+    pub name: &'static str,
+    /// The long name which replaces `name`.
+    pub replacement: &'static str,
+    /// The release in which `name` will stop being accepted.
+    pub removed_in: &'static str,
+}
+
+pub const DEPRECATED_FLAGS: &[DeprecatedFlag] = &[
+    DeprecatedFlag {
+        subcommands: BEACON_NODE_CMDS,
+        name: "eth1-endpoint",
+        replacement: "eth1-endpoints",
+        removed_in: "v3.0.0",
+    },
+    DeprecatedFlag {
+        subcommands: VALIDATOR_CLIENT_CMDS,
+        name: "beacon-node",
+        replacement: "beacon-nodes",
+        removed_in: "v3.0.0",
+    },
+    DeprecatedFlag {
+        subcommands: VALIDATOR_CLIENT_CMDS,
+        name: "server",
+        replacement: "beacon-nodes",
+        removed_in: "v3.0.0",
+    },
+];
+
+/// Replace each deprecated flag in `args` with its replacement, returning the new arguments along
+/// with the deprecated flags which were given.
+///
+/// Flags are only replaced after the name of the subcommand they belong to, so that a flag of the
+/// same name belonging to another subcommand is left for clap to handle.
+pub fn replace_deprecated_flags(
+    args: Vec<OsString>,
+) -> (Vec<OsString>, Vec<&'static DeprecatedFlag>) {
+    let mut subcommand = None;
+    let mut used = vec![];
+
+    let args = args
+        .into_iter()
+        .enumerate()
+        .map(|(i, arg)| {
+            let arg_str = match arg.to_str() {
+                Some(arg_str) if i > 0 => arg_str,
+                _ => return arg,
+            };
+            let current = match subcommand {
+                Some(current) => current,
+                None => {
+                    if !arg_str.starts_with('-') {
+                        subcommand = DEPRECATED_FLAGS
+                            .iter()
+                            .flat_map(|flag| flag.subcommands.iter())
+                            .find(|name| **name == arg_str)
+                            .copied();
+                    }
+                    return arg;
+                }
+            };
+
+            let (name, value) = match arg_str.strip_prefix("--") {
+                Some(flag) => match flag.find('=') {
+                    Some(i) => (&flag[..i], Some(&flag[i..])),
+                    None => (flag, None),
+                },
+                None => return arg,
+            };
+            match DEPRECATED_FLAGS
+                .iter()
+                .find(|flag| flag.name == name && flag.subcommands.contains(&current))
+            {
+                Some(flag) => {
+                    if !used.contains(&flag) {
+                        used.push(flag);
+                    }
+                    OsString::from(format!("--{}{}", flag.replacement, value.unwrap_or("")))
+                }
+                None => arg,
+            }
+        })
+        .collect();
+
+    (args, used)
+}
+
+/// Log a warning for each of the deprecated flags in `used`.
+pub fn warn_deprecated_flags(used: &[&DeprecatedFlag], log: &Logger) {
+    for flag in used {
+        warn!(
+            log,
+            "Deprecated flag used";
+            "flag" => format!("--{}", flag.name),
+            "msg" => format!("please use --{} instead", flag.replacement),
+            "removed_in" => flag.removed_in,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn replaces_deprecated_flags() {
+        let (result, used) = replace_deprecated_flags(args(&[
+            "lighthouse",
+            "--network",
+            "mainnet",
+            "vc",
+            "--server",
+            "http://localhost:5052",
+            "--beacon-node=http://localhost:5052",
+            "--server",
+            "http://localhost:5052",
+        ]));
+
+        assert_eq!(
+            result,
+            args(&[
+                "lighthouse",
+                "--network",
+                "mainnet",
+                "vc",
+                "--beacon-nodes",
+                "http://localhost:5052",
+                "--beacon-nodes=http://localhost:5052",
+                "--beacon-nodes",
+                "http://localhost:5052",
+            ])
+        );
+        assert_eq!(used, vec![&DEPRECATED_FLAGS[2], &DEPRECATED_FLAGS[1]]);
+    }
+
+    #[test]
+    fn ignores_flags_of_other_subcommands() {
+        let original = args(&["lighthouse", "bn", "--server", "a", "--eth1-endpoints", "b"]);
+        let (result, used) = replace_deprecated_flags(original.clone());
+        assert_eq!(result, original);
+        assert!(used.is_empty());
+
+        let original = args(&["lighthouse", "--eth1-endpoint", "a", "bn"]);
+        let (result, used) = replace_deprecated_flags(original.clone());
+        assert_eq!(result, original);
+        assert!(used.is_empty());
+    }
+}
@@ -1,11 +1,20 @@
 #![recursion_limit = "256"]
 
+mod config_file;
+mod deprecated_flags;
 mod metrics;
+mod otlp;
 
 use beacon_node::ProductionBeaconNode;
 use clap::{App, Arg, ArgMatches};
-use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, get_eth2_network_config};
-use directory::{parse_path_or_default, DEFAULT_BEACON_NODE_DIR, DEFAULT_VALIDATOR_DIR};
+use clap_utils::{
+    flags::{DISABLE_MALLOC_TUNING_FLAG, MALLOC_ARENA_MAX_FLAG},
+    get_eth2_network_config,
+};
+use deprecated_flags::{replace_deprecated_flags, warn_deprecated_flags, DeprecatedFlag};
+use directory::{
+    parse_path_or_default, upgrade_datadir, DEFAULT_BEACON_NODE_DIR, DEFAULT_VALIDATOR_DIR,
+};
 use env_logger::{Builder, Env};
 use environment::{EnvironmentBuilder, LoggerConfig};
 use eth2_hashing::have_sha_extensions;
@@ -16,10 +25,14 @@ use slog::{crit, info, warn};
 use std::fs::File;
 use std::path::PathBuf;
 use std::process::exit;
+use std::time::Duration;
 use task_executor::ShutdownReason;
 use types::{EthSpec, EthSpecId};
 use validator_client::ProductionValidatorClient;
 
+/// The exit code used when a graceful shutdown did not complete within its timeout.
+const INCOMPLETE_SHUTDOWN_EXIT_CODE: i32 = 2;
+
 fn bls_library_name() -> &'static str {
     if cfg!(feature = "portable") {
         "blst-portable"
@@ -32,33 +45,16 @@ fn bls_library_name() -> &'static str {
     }
 }
 
-fn main() {
-    // Enable backtraces unless a RUST_BACKTRACE value has already been explicitly provided.
-    if std::env::var("RUST_BACKTRACE").is_err() {
-        std::env::set_var("RUST_BACKTRACE", "1");
-    }
-
-    // Parse the CLI parameters.
-    let matches = App::new("Lighthouse")
-        .version(VERSION.replace("Lighthouse/", "").as_str())
+fn cli_app<'a>(version: &'a str, long_version: &'a str) -> App<'a, 'a> {
+    App::new("Lighthouse")
+        .version(version)
         .author("Sigma Prime <contact@sigmaprime.io>")
         .setting(clap::AppSettings::ColoredHelp)
         .about(
             "Ethereum 2.0 client by Sigma Prime. Provides a full-featured beacon \
              node, a validator client and utilities for managing validator accounts.",
         )
-        .long_version(
-            format!(
-                "{}\n\
-                 BLS library: {}\n\
-                 SHA256 hardware acceleration: {}\n\
-                 Specs: mainnet (true), minimal ({})",
-                 VERSION.replace("Lighthouse/", ""),
-                 bls_library_name(),
-                 have_sha_extensions(),
-                 cfg!(feature = "spec-minimal"),
-            ).as_str()
-        )
+        .long_version(long_version)
         .arg(
             Arg::with_name("spec")
                 .short("s")
@@ -121,6 +117,16 @@ fn main() {
                 .default_value("5")
                 .global(true),
         )
+        .arg(
+            Arg::with_name("logfile-max-age")
+                .long("logfile-max-age")
+                .value_name("HOURS")
+                .help(
+                    "The maximum time (in hours) each log file is written to before rotating, \
+                    regardless of its size. By default log files are only rotated by size.")
+                .takes_value(true)
+                .global(true),
+        )
         .arg(
             Arg::with_name("logfile-compress")
                 .long("logfile-compress")
@@ -133,8 +139,21 @@ fn main() {
             Arg::with_name("log-format")
                 .long("log-format")
                 .value_name("FORMAT")
-                .help("Specifies the log format used when emitting logs to the terminal.")
+                .help("Specifies the log format used when emitting logs to the terminal. JSON logs \
+                    have `ts`, `level`, `msg` and `module` keys alongside the fields of each log.")
+                .possible_values(&["JSON"])
+                .case_insensitive(true)
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("logfile-format")
+                .long("logfile-format")
+                .value_name("FORMAT")
+                .help("Specifies the log format used when emitting logs to the logfile. \
+                    Defaults to the value of --log-format.")
                 .possible_values(&["JSON"])
+                .case_insensitive(true)
                 .takes_value(true)
                 .global(true),
         )
@@ -211,6 +230,19 @@ fn main() {
                 )
                 .global(true),
         )
+        .arg(
+            Arg::with_name(MALLOC_ARENA_MAX_FLAG)
+                .long(MALLOC_ARENA_MAX_FLAG)
+                .value_name("COUNT")
+                .help(
+                    "The maximum number of glibc malloc arenas, in place of the MALLOC_ARENA_MAX \
+                    environment variable. Fewer arenas reduce memory fragmentation at the cost of \
+                    contention between threads. Only applies to the beacon node."
+                )
+                .conflicts_with(DISABLE_MALLOC_TUNING_FLAG)
+                .takes_value(true)
+                .global(true),
+        )
         .arg(
             Arg::with_name("terminal-total-difficulty-override")
                 .long("terminal-total-difficulty-override")
@@ -250,11 +282,75 @@ fn main() {
                 .takes_value(true)
                 .global(true)
         )
+        .arg(
+            Arg::with_name(otlp::OTLP_ENDPOINT_FLAG)
+                .long(otlp::OTLP_ENDPOINT_FLAG)
+                .value_name("URL")
+                .help("The OTLP/gRPC endpoint of an OpenTelemetry collector (e.g., Jaeger or \
+                       Tempo) to which spans for block processing, sync batch processing and \
+                       HTTP requests are exported. Requires Lighthouse to be compiled with the \
+                       `otlp` feature.")
+                .takes_value(true)
+                .global(true)
+        )
         .subcommand(beacon_node::cli_app())
         .subcommand(boot_node::cli_app())
         .subcommand(validator_client::cli_app())
         .subcommand(account_manager::cli_app())
-        .get_matches();
+        .subcommand(validator_manager::cli_app())
+        .subcommand(slasher_ingest::cli_app())
+        .subcommand(database_manager::cli_app())
+        .subcommand(config_file::cli_app())
+}
+
+fn main() {
+    // Enable backtraces unless a RUST_BACKTRACE value has already been explicitly provided.
+    if std::env::var("RUST_BACKTRACE").is_err() {
+        std::env::set_var("RUST_BACKTRACE", "1");
+    }
+
+    let version = VERSION.replace("Lighthouse/", "");
+    let long_version = format!(
+        "{}\n\
+         BLS library: {}\n\
+         SHA256 hardware acceleration: {}\n\
+         Specs: mainnet (true), minimal ({})",
+        version,
+        bls_library_name(),
+        have_sha_extensions(),
+        cfg!(feature = "spec-minimal"),
+    );
+
+    // Parse the CLI parameters, along with any supplied by a config file.
+    let mut args = match config_file::args_with_config_file(std::env::args_os().collect()) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1)
+        }
+    };
+    // Replace renamed flags, which clap doesn't know about, with their new names.
+    let (replaced_args, deprecated_flags) = replace_deprecated_flags(args.args);
+    args.args = replaced_args;
+    let matches = match cli_app(&version, &long_version).get_matches_from_safe(&args.args) {
+        Ok(matches) => matches,
+        Err(e) => match args.unknown_key_error(&e) {
+            Some(message) => {
+                eprintln!("{}", message);
+                exit(1)
+            }
+            None => e.exit(),
+        },
+    };
+
+    // Generating a config file doesn't require a network or environment.
+    if let Some(config_matches) = matches.subcommand_matches(config_file::CMD) {
+        if let Err(e) = config_file::run(config_matches, cli_app(&version, &long_version)) {
+            eprintln!("{}", e);
+            exit(1)
+        }
+        exit(0)
+    }
 
     // Configure the allocator early in the process, before it has the chance to use the default values for
     // anything important.
@@ -263,7 +359,14 @@ fn main() {
     // memory footprint.
     let is_beacon_node = matches.subcommand_name() == Some("beacon_node");
     if is_beacon_node && !matches.is_present(DISABLE_MALLOC_TUNING_FLAG) {
-        if let Err(e) = configure_memory_allocator() {
+        let arena_max = match clap_utils::parse_optional(&matches, MALLOC_ARENA_MAX_FLAG) {
+            Ok(arena_max) => arena_max,
+            Err(e) => {
+                eprintln!("{}", e);
+                exit(1)
+            }
+        };
+        if let Err(e) = configure_memory_allocator(arena_max) {
             eprintln!(
                 "Unable to configure the memory allocator: {} \n\
                 Try providing the --{} flag",
@@ -297,13 +400,23 @@ fn main() {
                 debug_info,
             );
 
-            return Ok(());
+            return Ok(0);
         }
 
         match eth_spec_id {
-            EthSpecId::Mainnet => run(EnvironmentBuilder::mainnet(), &matches, eth2_network_config),
+            EthSpecId::Mainnet => run(
+                EnvironmentBuilder::mainnet(),
+                &matches,
+                eth2_network_config,
+                &deprecated_flags,
+            ),
             #[cfg(feature = "spec-minimal")]
-            EthSpecId::Minimal => run(EnvironmentBuilder::minimal(), &matches, eth2_network_config),
+            EthSpecId::Minimal => run(
+                EnvironmentBuilder::minimal(),
+                &matches,
+                eth2_network_config,
+                &deprecated_flags,
+            ),
             #[cfg(not(feature = "spec-minimal"))]
             other => {
                 eprintln!(
@@ -321,7 +434,7 @@ fn main() {
 
     // Return the appropriate error code.
     match result {
-        Ok(()) => exit(0),
+        Ok(code) => exit(code),
         Err(e) => {
             eprintln!("{}", e);
             drop(e);
@@ -334,7 +447,8 @@ fn run<E: EthSpec>(
     environment_builder: EnvironmentBuilder<E>,
     matches: &ArgMatches,
     eth2_network_config: Eth2NetworkConfig,
-) -> Result<(), String> {
+    deprecated_flags: &[&DeprecatedFlag],
+) -> Result<i32, String> {
     if std::mem::size_of::<usize>() != 8 {
         return Err(format!(
             "{}-bit architecture is not supported (64-bit only).",
@@ -347,6 +461,7 @@ fn run<E: EthSpec>(
         .ok_or("Expected --debug-level flag")?;
 
     let log_format = matches.value_of("log-format");
+    let logfile_format = matches.value_of("logfile-format").or(log_format);
 
     let logfile_debug_level = matches
         .value_of("logfile-debug-level")
@@ -364,6 +479,9 @@ fn run<E: EthSpec>(
         .parse()
         .map_err(|e| format!("Failed to parse `logfile-max-number`: {:?}", e))?;
 
+    let logfile_max_age = clap_utils::parse_optional::<u64>(matches, "logfile-max-age")?
+        .map(|hours| Duration::from_secs(hours * 60 * 60));
+
     let logfile_compress = matches.is_present("logfile-compress");
 
     // Construct the path to the log file.
@@ -384,6 +502,12 @@ fn run<E: EthSpec>(
                     .join("validator")
                     .with_extension("log"),
             ),
+            Some(slasher_ingest::CMD) => Some(
+                parse_path_or_default(matches, "datadir")?
+                    .join("logs")
+                    .join("slasher")
+                    .with_extension("log"),
+            ),
             _ => None,
         };
     }
@@ -393,8 +517,10 @@ fn run<E: EthSpec>(
         debug_level,
         logfile_debug_level,
         log_format,
+        logfile_format,
         max_log_size: logfile_max_size * 1_024 * 1_024,
         max_log_number: logfile_max_number,
+        max_log_age: logfile_max_age,
         compression: logfile_compress,
     };
 
@@ -420,6 +546,13 @@ fn run<E: EthSpec>(
         );
     }
 
+    warn_deprecated_flags(deprecated_flags, &log);
+
+    if let Some(endpoint) = matches.value_of(otlp::OTLP_ENDPOINT_FLAG) {
+        otlp::init(endpoint, environment.runtime())?;
+        info!(log, "Exporting tracing spans"; "endpoint" => endpoint);
+    }
+
     #[cfg(all(feature = "modern", target_arch = "x86_64"))]
     if !std::is_x86_feature_detected!("adx") {
         warn!(
@@ -454,9 +587,17 @@ fn run<E: EthSpec>(
         account_manager::run(sub_matches, environment)?;
 
         // Exit as soon as account manager returns control.
-        return Ok(());
+        return Ok(0);
     };
 
+    if let Some(sub_matches) = matches.subcommand_matches(validator_manager::CMD) {
+        eprintln!("Running validator manager for {} network", network_name);
+        validator_manager::run(sub_matches, environment)?;
+
+        // Exit as soon as validator manager returns control.
+        return Ok(0);
+    }
+
     info!(log, "Lighthouse started"; "version" => VERSION);
     info!(
         log,
@@ -464,12 +605,35 @@ fn run<E: EthSpec>(
         "name" => &network_name
     );
 
+    // Upgrade the layout of the datadir before anything reads from it. A validator client given
+    // `--validators-dir` doesn't use the datadir.
+    let uses_datadir = match matches.subcommand() {
+        ("beacon_node", Some(_)) => true,
+        ("validator_client", Some(matches)) => !matches.is_present("validators-dir"),
+        _ => false,
+    };
+    if uses_datadir {
+        let datadir = parse_path_or_default(matches, "datadir")?;
+        for migration in upgrade_datadir(&datadir)? {
+            info!(
+                log,
+                "Upgraded datadir layout";
+                "migration" => migration,
+                "datadir" => ?datadir,
+            );
+        }
+    }
+
+    // Only the beacon node shuts down in stages.
+    let mut shutdown_timeout = None;
+
     match matches.subcommand() {
         ("beacon_node", Some(matches)) => {
             let context = environment.core_context();
             let log = context.log().clone();
             let executor = context.executor.clone();
             let config = beacon_node::get_config::<E>(matches, &context)?;
+            shutdown_timeout = Some(config.shutdown_timeout);
             let shutdown_flag = matches.is_present("immediate-shutdown");
             if let Some(dump_path) = clap_utils::parse_optional::<PathBuf>(matches, "dump-config")?
             {
@@ -481,7 +645,21 @@ fn run<E: EthSpec>(
 
             executor.clone().spawn(
                 async move {
-                    if let Err(e) = ProductionBeaconNode::new(context.clone(), config).await {
+                    let result = if let Some(light_client_config) = config.light_client.clone() {
+                        // Like the validator client, light mode has no database to initialize so
+                        // there is nothing to do before an immediate shutdown.
+                        if shutdown_flag {
+                            Ok(())
+                        } else {
+                            beacon_node::start_light_client(context.clone(), light_client_config)
+                                .await
+                        }
+                    } else {
+                        ProductionBeaconNode::new(context.clone(), config)
+                            .await
+                            .map(|_| ())
+                    };
+                    if let Err(e) = result {
                         crit!(log, "Failed to start beacon node"; "reason" => e);
                         // Ignore the error since it always occurs during normal operation when
                         // shutting down.
@@ -534,6 +712,12 @@ fn run<E: EthSpec>(
                 ));
             }
         }
+        (slasher_ingest::CMD, Some(matches)) => {
+            slasher_ingest::run(matches, environment.core_context())?;
+        }
+        (database_manager::CMD, Some(matches)) => {
+            database_manager::run(matches, environment.core_context())?;
+        }
         _ => {
             crit!(log, "No subcommand supplied. See --help .");
             return Err("No subcommand supplied.".into());
@@ -544,13 +728,20 @@ fn run<E: EthSpec>(
     let shutdown_reason = environment.block_until_shutdown_requested()?;
     info!(log, "Shutting down.."; "reason" => ?shutdown_reason);
 
+    // Stop gossip, drain queued work and persist to disk before cancelling tasks.
+    let shutdown_complete =
+        shutdown_timeout.map_or(true, |timeout| environment.run_shutdown_hooks(timeout));
+
+    otlp::shutdown();
+
     environment.fire_signal();
 
     // Shutdown the environment once all tasks have completed.
     environment.shutdown_on_idle();
 
     match shutdown_reason {
-        ShutdownReason::Success(_) => Ok(()),
+        ShutdownReason::Success(_) if shutdown_complete => Ok(0),
+        ShutdownReason::Success(_) => Ok(INCOMPLETE_SHUTDOWN_EXIT_CODE),
         ShutdownReason::Failure(msg) => Err(msg.to_string()),
     }
 }
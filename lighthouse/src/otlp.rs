@@ -0,0 +1,58 @@
+//! Exports the `tracing` spans emitted by Lighthouse (e.g., for block processing, sync batch
+//! processing and HTTP requests) to an OpenTelemetry collector, such as Jaeger or Tempo, using
+//! OTLP.
+//!
+//! The spans are always emitted, but are discarded unless Lighthouse is compiled with the `otlp`
+//! feature and run with `--otlp-endpoint`.
+use tokio::runtime::Runtime;
+
+pub const OTLP_ENDPOINT_FLAG: &str = "otlp-endpoint";
+
+/// The name Lighthouse reports to the collector.
+#[cfg(feature = "otlp")]
+const SERVICE_NAME: &str = "lighthouse";
+
+/// Start exporting spans to the OTLP/gRPC collector at `endpoint`, using `runtime` to send them.
+#[cfg(feature = "otlp")]
+pub fn init(endpoint: &str, runtime: &Runtime) -> Result<(), String> {
+    use opentelemetry::{sdk, KeyValue};
+    use opentelemetry_otlp::WithExportConfig;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    // The batch exporter is spawned onto the runtime.
+    let _guard = runtime.enter();
+
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(sdk::trace::config().with_resource(sdk::Resource::new(vec![
+            KeyValue::new("service.name", SERVICE_NAME),
+            KeyValue::new("service.version", lighthouse_version::VERSION),
+        ])))
+        .install_batch(opentelemetry::runtime::Tokio)
+        .map_err(|e| format!("Unable to start the OTLP exporter: {:?}", e))?;
+
+    tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .map_err(|e| format!("Unable to register the OTLP exporter: {:?}", e))
+}
+
+#[cfg(not(feature = "otlp"))]
+pub fn init(_endpoint: &str, _runtime: &Runtime) -> Result<(), String> {
+    Err(format!(
+        "--{} requires Lighthouse to be compiled with the `otlp` feature",
+        OTLP_ENDPOINT_FLAG
+    ))
+}
+
+/// Export any spans which have not yet been sent to the collector.
+pub fn shutdown() {
+    #[cfg(feature = "otlp")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
@@ -3,6 +3,7 @@ use account_manager::{
         create::*,
         import::{self, CMD as IMPORT_CMD},
         modify::{ALL, CMD as MODIFY_CMD, DISABLE, ENABLE, PUBKEY_FLAG},
+        recover::{CMD as RECOVER_CMD, FIRST_INDEX_FLAG, MNEMONIC_FLAG as RECOVER_MNEMONIC_FLAG},
         CMD as VALIDATOR_CMD,
     },
     wallet::{
@@ -403,6 +404,107 @@ fn validator_create() {
     assert_eq!(dir_validator_count(validator_dir.path()), 6);
 }
 
+#[test]
+fn validator_recover_without_beacon_node() {
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon about";
+
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let mnemonic_dir = tempdir().unwrap();
+    let mnemonic_path = mnemonic_dir.path().join("mnemonic.txt");
+    fs::write(&mnemonic_path, MNEMONIC).unwrap();
+
+    let output = output_result(
+        validator_cmd()
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(RECOVER_CMD)
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str())
+            .arg(format!("--{}", RECOVER_MNEMONIC_FLAG))
+            .arg(mnemonic_path.as_os_str())
+            .arg(format!("--{}", FIRST_INDEX_FLAG))
+            .arg("1")
+            .arg(format!("--{}", COUNT_FLAG))
+            .arg("2"),
+    )
+    .unwrap();
+
+    // Without `--beacon-node`, exactly `--count` indices are recovered from `--first-index`.
+    let stdout = from_utf8(&output.stdout).expect("stdout is not utf8");
+    let indices = stdout
+        .lines()
+        .map(|line| line.split('\t').nth(1).expect("line must have an index"))
+        .collect::<Vec<_>>();
+    assert_eq!(indices, vec!["Index: 1", "Index: 2"]);
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+}
+
+#[test]
+fn validator_create_from_mnemonic_with_deposit_data() {
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon \
+        abandon abandon abandon about";
+    // The voting pubkey at index 0 of `MNEMONIC`.
+    const FIRST_PUBKEY: &str = "b3e445d43871965d890a398f719348a1405ac72e35b92727cc570026f54471af7ea7b2040622a8fd0b5bfb2a209b5911";
+
+    let validator_dir = tempdir().unwrap();
+    let secrets_dir = tempdir().unwrap();
+    let deposit_dir = tempdir().unwrap();
+    let deposit_data_path = deposit_dir.path().join("deposit_data.json");
+
+    let create = || {
+        let mut child = validator_cmd()
+            .arg(format!("--{}", VALIDATOR_DIR_FLAG))
+            .arg(validator_dir.path().as_os_str())
+            .arg(CREATE_CMD)
+            .arg(format!("--{}", SECRETS_DIR_FLAG))
+            .arg(secrets_dir.path().as_os_str())
+            .arg(format!("--{}", MNEMONIC_FROM_STDIN_FLAG))
+            .arg(format!("--{}", COUNT_FLAG))
+            .arg("2")
+            .arg(format!("--{}", DEPOSIT_DATA_FLAG))
+            .arg(deposit_data_path.as_os_str())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(format!("{}\n", MNEMONIC).as_bytes())
+            .unwrap();
+        child.wait_with_output().unwrap()
+    };
+
+    let output = create();
+    assert!(
+        output.status.success(),
+        "{}",
+        from_utf8(&output.stderr).unwrap()
+    );
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+
+    // The deposit data file has a deposit for each of the new validators, in order.
+    let deposits: Vec<serde_json::Value> =
+        serde_json::from_slice(&fs::read(&deposit_data_path).unwrap()).unwrap();
+    assert_eq!(deposits.len(), 2);
+    assert_eq!(deposits[0]["pubkey"], FIRST_PUBKEY);
+    for deposit in &deposits {
+        let pubkey = deposit["pubkey"].as_str().unwrap();
+        assert!(validator_dir.path().join(format!("0x{}", pubkey)).exists());
+        assert_eq!(deposit["amount"], 32_000_000_000u64);
+    }
+
+    // An existing deposit data file is not overwritten, and no keys are created.
+    let contents = fs::read(&deposit_data_path).unwrap();
+    assert!(!create().status.success());
+    assert_eq!(fs::read(&deposit_data_path).unwrap(), contents);
+    assert_eq!(dir_validator_count(validator_dir.path()), 2);
+}
+
 #[test]
 fn validator_import_launchpad() {
     const PASSWORD: &str = "cats";
@@ -493,6 +595,9 @@ fn validator_import_launchpad() {
         enabled: false,
         description: "".into(),
         graffiti: None,
+        suggested_fee_recipient: None,
+        gas_limit: None,
+        builder_proposals: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -612,6 +717,9 @@ fn validator_import_launchpad_no_password_then_add_password() {
         enabled: true,
         description: "".into(),
         graffiti: None,
+        suggested_fee_recipient: None,
+        gas_limit: None,
+        builder_proposals: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
@@ -635,6 +743,9 @@ fn validator_import_launchpad_no_password_then_add_password() {
         enabled: true,
         description: "".into(),
         graffiti: None,
+        suggested_fee_recipient: None,
+        gas_limit: None,
+        builder_proposals: None,
         voting_public_key: keystore.public_key().unwrap(),
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path: dst_keystore_dir.join(KEYSTORE_NAME),
@@ -734,6 +845,9 @@ fn validator_import_launchpad_password_file() {
         description: "".into(),
         voting_public_key: keystore.public_key().unwrap(),
         graffiti: None,
+        suggested_fee_recipient: None,
+        gas_limit: None,
+        builder_proposals: None,
         signing_definition: SigningDefinition::LocalKeystore {
             voting_keystore_path,
             voting_keystore_password_path: None,
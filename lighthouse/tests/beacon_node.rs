@@ -1,7 +1,7 @@
 use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
-use lighthouse_network::PeerId;
+use lighthouse_network::{PeerAction, PeerId};
 use std::fs::File;
 use std::io::Write;
 use std::net::{IpAddr, Ipv4Addr};
@@ -10,6 +10,7 @@ use std::path::PathBuf;
 use std::process::Command;
 use std::str::FromStr;
 use std::string::ToString;
+use std::time::Duration;
 use tempfile::TempDir;
 use types::{Address, Checkpoint, Epoch, Hash256};
 
@@ -108,6 +109,58 @@ fn disable_lock_timeouts_flag() {
         .with_config(|config| assert!(!config.chain.enable_lock_timeouts));
 }
 
+#[test]
+fn shutdown_timeout_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.shutdown_timeout, Duration::from_secs(15)));
+}
+
+#[test]
+fn shutdown_timeout_flag() {
+    CommandLineTest::new()
+        .flag("shutdown-timeout", Some("30"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.shutdown_timeout, Duration::from_secs(30)));
+}
+
+#[test]
+fn low_disk_space_threshold_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.low_disk_space_threshold_gb, 10));
+}
+
+#[test]
+fn low_disk_space_threshold_flag() {
+    CommandLineTest::new()
+        .flag("low-disk-space-threshold", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.low_disk_space_threshold_gb, 0));
+}
+
+#[test]
+fn clock_drift_defaults() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.ntp_server, None);
+            assert_eq!(config.clock_drift_threshold, Duration::from_millis(500));
+        });
+}
+
+#[test]
+fn clock_drift_flags() {
+    CommandLineTest::new()
+        .flag("ntp-server", Some("pool.ntp.org"))
+        .flag("clock-drift-threshold", Some("250"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.ntp_server, Some("pool.ntp.org".to_string()));
+            assert_eq!(config.clock_drift_threshold, Duration::from_millis(250));
+        });
+}
+
 #[test]
 fn freezer_dir_flag() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
@@ -150,6 +203,91 @@ fn trusted_peers_flag() {
             );
         });
 }
+#[test]
+fn peer_action_penalties_flag() {
+    CommandLineTest::new()
+        .flag(
+            "peer-action-penalties",
+            Some("low_tolerance_error=20,sync.low_tolerance_error=2.5"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let peer_score = &config.network.peer_score;
+            assert_eq!(
+                peer_score.penalty(PeerAction::LowToleranceError, "gossipsub"),
+                Some(20.0)
+            );
+            assert_eq!(
+                peer_score.penalty(PeerAction::LowToleranceError, "sync"),
+                Some(2.5)
+            );
+            assert!(!peer_score.dry_run);
+        });
+}
+#[test]
+#[should_panic]
+fn peer_action_penalties_fatal_flag() {
+    CommandLineTest::new()
+        .flag("peer-action-penalties", Some("fatal=1"))
+        .run_with_zero_port();
+}
+#[test]
+fn peer_score_dry_run_flag() {
+    CommandLineTest::new()
+        .flag("peer-score-dry-run", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.peer_score.dry_run));
+}
+#[test]
+fn max_peers_per_ip_prefix_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.peer_admission.max_peers_per_ip_prefix, None);
+            assert_eq!(config.network.peer_admission.max_peers_per_asn, None);
+            assert_eq!(config.network.peer_admission.asn_db, None);
+        });
+}
+#[test]
+fn max_peers_per_ip_prefix_flag() {
+    CommandLineTest::new()
+        .flag("max-peers-per-ip-prefix", Some("3"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.peer_admission.max_peers_per_ip_prefix,
+                Some(3)
+            )
+        });
+}
+#[test]
+#[should_panic]
+fn max_peers_per_ip_prefix_zero_flag() {
+    CommandLineTest::new()
+        .flag("max-peers-per-ip-prefix", Some("0"))
+        .run_with_zero_port();
+}
+#[test]
+fn max_peers_per_asn_flag() {
+    CommandLineTest::new()
+        .flag("max-peers-per-asn", Some("10"))
+        .flag("asn-db", Some("/tmp/ip2asn-combined.tsv"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.network.peer_admission.max_peers_per_asn, Some(10));
+            assert_eq!(
+                config.network.peer_admission.asn_db,
+                Some(PathBuf::from("/tmp/ip2asn-combined.tsv"))
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn max_peers_per_asn_without_db_flag() {
+    CommandLineTest::new()
+        .flag("max-peers-per-asn", Some("10"))
+        .run_with_zero_port();
+}
 
 // Tests for Eth1 flags.
 #[test]
@@ -167,6 +305,19 @@ fn eth1_flag() {
         .with_config(|config| assert!(config.sync_eth1_chain));
 }
 #[test]
+fn eth1_endpoint_deprecated_flag() {
+    CommandLineTest::new()
+        .flag("eth1-endpoint", Some("http://localhost:9545"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.eth1.endpoints[0].full.to_string(),
+                "http://localhost:9545/"
+            );
+            assert!(config.sync_eth1_chain);
+        });
+}
+#[test]
 fn eth1_endpoints_flag() {
     CommandLineTest::new()
         .flag(
@@ -205,6 +356,19 @@ fn eth1_purge_cache_flag() {
         .run_with_zero_port()
         .with_config(|config| assert!(config.eth1.purge_cache));
 }
+#[test]
+fn eth1_cache_follow_distance_flag() {
+    CommandLineTest::new()
+        .flag("eth1-cache-follow-distance", Some("128"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.eth1.cache_follow_distance, Some(128)));
+}
+#[test]
+fn eth1_cache_follow_distance_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.eth1.cache_follow_distance, None));
+}
 
 // Tests for Merge flags.
 #[test]
@@ -223,6 +387,53 @@ fn merge_fee_recipient_flag() {
             )
         });
 }
+#[test]
+fn merge_builder_flag() {
+    CommandLineTest::new()
+        .flag("merge", None)
+        .flag("builder", Some("http://localhost:18550"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.builder_url.as_ref().map(|url| url.full.to_string()),
+                Some("http://localhost:18550/".to_string())
+            )
+        });
+}
+#[test]
+fn merge_builder_flag_default() {
+    CommandLineTest::new()
+        .flag("merge", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.builder_url.is_none()));
+}
+#[test]
+fn merge_execution_jwt_flag() {
+    CommandLineTest::new()
+        .flag("merge", None)
+        .flag(
+            "execution-endpoints",
+            Some("http://localhost:8551,http://localhost:8552"),
+        )
+        .flag("execution-jwt", Some("/tmp/jwt-a.hex,/tmp/jwt-b.hex"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.execution_jwt_secrets,
+                vec![
+                    PathBuf::from("/tmp/jwt-a.hex"),
+                    PathBuf::from("/tmp/jwt-b.hex")
+                ]
+            )
+        });
+}
+#[test]
+fn merge_execution_jwt_flag_default() {
+    CommandLineTest::new()
+        .flag("merge", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.execution_jwt_secrets.is_empty()));
+}
 
 // Tests for Network flags.
 #[test]
@@ -270,6 +481,44 @@ fn network_shutdown_after_sync_disabled_flag() {
         .with_config(|config| assert!(!config.network.shutdown_after_sync));
 }
 #[test]
+fn network_disable_backfill_flag() {
+    CommandLineTest::new()
+        .flag("disable-backfill", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.network.disable_backfill));
+}
+#[test]
+fn network_disable_backfill_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.network.disable_backfill));
+}
+#[test]
+fn network_dump_failed_batches_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("datadir", dir.path().as_os_str().to_str())
+        .flag("dump-failed-batches", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.failed_batch_dir,
+                Some(
+                    dir.path()
+                        .join("beacon")
+                        .join("debug")
+                        .join("failed_batches")
+                )
+            )
+        });
+}
+#[test]
+fn network_dump_failed_batches_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.failed_batch_dir, None));
+}
+#[test]
 fn network_listen_address_flag() {
     let addr = "127.0.0.2".parse::<Ipv4Addr>().unwrap();
     CommandLineTest::new()
@@ -527,6 +776,36 @@ fn http_allow_origin_all_flag() {
         .with_config(|config| assert_eq!(config.http_api.allow_origin, Some("*".to_string())));
 }
 #[test]
+fn http_allow_methods_and_headers_flags() {
+    CommandLineTest::new()
+        .flag("http-allow-methods", Some("GET,POST,OPTIONS"))
+        .flag("http-allow-headers", Some("Content-Type,X-Requested-With"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.allow_methods,
+                Some("GET,POST,OPTIONS".to_string())
+            );
+            assert_eq!(
+                config.http_api.allow_headers,
+                Some("Content-Type,X-Requested-With".to_string())
+            );
+        });
+}
+#[test]
+fn http_allow_credentials_flag() {
+    CommandLineTest::new()
+        .flag("http-allow-credentials", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_api.allow_credentials));
+}
+#[test]
+fn http_allow_credentials_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_api.allow_credentials));
+}
+#[test]
 fn http_tls_flags() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
     CommandLineTest::new()
@@ -548,6 +827,156 @@ fn http_tls_flags() {
                 .expect("tls_config was empty.");
             assert_eq!(tls_config.cert, dir.path().join("certificate.crt"));
             assert_eq!(tls_config.key, dir.path().join("private.key"));
+            assert!(config.http_metrics.tls_config.is_none());
+        });
+}
+#[test]
+fn metrics_tls_flags() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-enable-tls", None)
+        .flag(
+            "http-tls-cert",
+            dir.path().join("certificate.crt").as_os_str().to_str(),
+        )
+        .flag(
+            "http-tls-key",
+            dir.path().join("private.key").as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let tls_config = config
+                .http_metrics
+                .tls_config
+                .as_ref()
+                .expect("tls_config was empty.");
+            assert_eq!(tls_config.cert, dir.path().join("certificate.crt"));
+            assert_eq!(tls_config.key, dir.path().join("private.key"));
+            assert!(config.http_api.tls_config.is_none());
+        });
+}
+#[test]
+fn http_max_body_size_flag() {
+    CommandLineTest::new()
+        .flag("http-max-body-size", Some("1024"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.max_body_size, 1024));
+}
+#[test]
+fn http_max_concurrent_requests_flag() {
+    CommandLineTest::new()
+        .flag("http-max-concurrent-requests", Some("8"))
+        .flag("http-max-concurrent-priority-requests", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.max_concurrent_requests, Some(8));
+            assert_eq!(config.http_api.max_concurrent_priority_requests, Some(4));
+        });
+}
+#[test]
+fn http_max_concurrent_requests_zero_flag() {
+    CommandLineTest::new()
+        .flag("http-max-concurrent-requests", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.max_concurrent_requests, None));
+}
+#[test]
+#[should_panic]
+fn http_max_concurrent_priority_requests_zero_flag() {
+    CommandLineTest::new()
+        .flag("http-max-concurrent-priority-requests", Some("0"))
+        .run_with_zero_port();
+}
+#[test]
+fn http_max_concurrent_requests_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.max_concurrent_requests, Some(64));
+            assert_eq!(config.http_api.max_concurrent_priority_requests, None);
+        });
+}
+#[test]
+fn http_state_regen_flags() {
+    CommandLineTest::new()
+        .flag("http-max-concurrent-state-regens", Some("4"))
+        .flag("http-state-regen-cache-size", Some("8"))
+        .flag("http-state-regen-timeout", Some("30"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.max_concurrent_state_regens, 4);
+            assert_eq!(config.http_api.state_regen_cache_size, 8);
+            assert_eq!(
+                config.http_api.state_regen_max_wait,
+                Duration::from_secs(30)
+            );
+        });
+}
+#[test]
+fn http_state_regen_defaults() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.max_concurrent_state_regens, 2);
+            assert_eq!(config.http_api.state_regen_cache_size, 2);
+            assert_eq!(
+                config.http_api.state_regen_max_wait,
+                Duration::from_secs(12)
+            );
+        });
+}
+#[test]
+fn http_sse_replay_window_flag() {
+    CommandLineTest::new()
+        .flag("http-sse-replay-window", Some("16"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.sse_replay_window, 16));
+}
+#[test]
+fn http_sse_replay_window_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.sse_replay_window, 64));
+}
+#[test]
+fn http_ready_min_peers_flag() {
+    CommandLineTest::new()
+        .flag("http-ready-min-peers", Some("8"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.ready_min_peers, 8));
+}
+#[test]
+fn http_ready_min_peers_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.ready_min_peers, 1));
+}
+
+// Tests for monitoring endpoint flags.
+#[test]
+fn monitoring_endpoint_flag() {
+    CommandLineTest::new()
+        .flag("monitoring-endpoint", Some("http://example:8000"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let api_conf = config.monitoring_api.as_ref().unwrap();
+            assert_eq!(api_conf.monitoring_endpoint.as_str(), "http://example:8000");
+            assert_eq!(api_conf.update_period_secs, None);
+            assert!(!api_conf.skip_system_metrics);
+        });
+}
+#[test]
+fn monitoring_endpoint_period_and_skip_system_flags() {
+    CommandLineTest::new()
+        .flag("monitoring-endpoint", Some("http://example:8000"))
+        .flag("monitoring-endpoint-period", Some("30"))
+        .flag("monitoring-endpoint-skip-system", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            let api_conf = config.monitoring_api.as_ref().unwrap();
+            assert_eq!(api_conf.update_period_secs, Some(30));
+            assert!(api_conf.skip_system_metrics);
         });
 }
 
@@ -603,6 +1032,41 @@ fn metrics_allow_origin_all_flag() {
         .run_with_zero_port()
         .with_config(|config| assert_eq!(config.http_metrics.allow_origin, Some("*".to_string())));
 }
+#[test]
+fn metrics_allow_methods_and_headers_flags() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-allow-methods", Some("GET,POST,OPTIONS"))
+        .flag(
+            "metrics-allow-headers",
+            Some("Content-Type,X-Requested-With"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_metrics.allow_methods,
+                Some("GET,POST,OPTIONS".to_string())
+            );
+            assert_eq!(
+                config.http_metrics.allow_headers,
+                Some("Content-Type,X-Requested-With".to_string())
+            );
+        });
+}
+#[test]
+fn metrics_allow_credentials_flag() {
+    CommandLineTest::new()
+        .flag("metrics", None)
+        .flag("metrics-allow-credentials", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_metrics.allow_credentials));
+}
+#[test]
+fn metrics_allow_credentials_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_metrics.allow_credentials));
+}
 
 // Tests for Validator Monitor flags.
 #[test]
@@ -670,6 +1134,39 @@ fn compact_db_flag() {
         .with_config(|config| assert!(config.store.compact_on_init));
 }
 #[test]
+fn hot_state_diffs_flag() {
+    CommandLineTest::new()
+        .flag("hot-state-diffs", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.store.hot_state_diffs));
+}
+#[test]
+fn hot_state_diffs_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.store.hot_state_diffs));
+}
+#[test]
+fn prune_payloads_flag() {
+    CommandLineTest::new()
+        .flag("prune-payloads", None)
+        .flag("payload-retention-epochs", Some("1024"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.store.prune_payloads);
+            assert_eq!(config.store.payload_retention_epochs, 1024);
+        });
+}
+#[test]
+fn prune_payloads_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(!config.store.prune_payloads);
+            assert_eq!(config.store.payload_retention_epochs, 256);
+        });
+}
+#[test]
 fn reconstruct_historic_states_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
@@ -682,6 +1179,63 @@ fn no_reconstruct_historic_states_flag() {
         .run_with_zero_port()
         .with_config(|config| assert!(!config.chain.reconstruct_historic_states));
 }
+#[test]
+fn light_client_server_flag() {
+    CommandLineTest::new()
+        .flag("light-client-server", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.chain.enable_light_client_server);
+            assert!(config.network.enable_light_client_server);
+        });
+}
+#[test]
+fn no_light_client_server_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(!config.chain.enable_light_client_server);
+            assert!(!config.network.enable_light_client_server);
+        });
+}
+#[test]
+fn light_flag() {
+    let root = "0x7d37d9e1aa1a8fc4a0bbfc3b0c0eb0c5e9e72bc8dc5c4a8e4dd08c0c7f9ec0d4";
+    CommandLineTest::new()
+        .flag("light", None)
+        .flag("light-client-upstream", Some("http://localhost:9052"))
+        .flag("light-client-trusted-block-root", Some(root))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let light_client = config.light_client.as_ref().unwrap();
+            assert_eq!(
+                light_client.upstream.full.to_string(),
+                "http://localhost:9052/"
+            );
+            assert_eq!(
+                light_client.trusted_block_root,
+                Hash256::from_str(&root[2..]).unwrap()
+            );
+            assert_eq!(light_client.listen_port, config.http_api.listen_port);
+        });
+}
+#[test]
+fn no_light_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.light_client.is_none()));
+}
+#[test]
+#[should_panic]
+fn light_flag_without_upstream() {
+    CommandLineTest::new()
+        .flag("light", None)
+        .flag(
+            "light-client-trusted-block-root",
+            Some("0x7d37d9e1aa1a8fc4a0bbfc3b0c0eb0c5e9e72bc8dc5c4a8e4dd08c0c7f9ec0d4"),
+        )
+        .run_with_zero_port();
+}
 
 // Tests for Slasher flags.
 #[test]
@@ -833,6 +1387,44 @@ fn slasher_broadcast_flag() {
         });
 }
 #[test]
+fn slasher_rechunk_flag() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-rechunk", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            let slasher_config = config
+                .slasher
+                .as_ref()
+                .expect("Unable to parse Slasher config");
+            assert!(slasher_config.rechunk);
+        });
+}
+#[test]
+fn slasher_backend_default() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            let slasher_config = config.slasher.as_ref().unwrap();
+            assert_eq!(slasher_config.backend, slasher::DatabaseBackend::Mdbx);
+            assert!(!slasher_config.migrate_backend);
+        });
+}
+#[test]
+fn slasher_backend_override_to_lmdb() {
+    CommandLineTest::new()
+        .flag("slasher", None)
+        .flag("slasher-backend", Some("lmdb"))
+        .flag("slasher-migrate-backend", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            let slasher_config = config.slasher.as_ref().unwrap();
+            assert_eq!(slasher_config.backend, slasher::DatabaseBackend::Lmdb);
+            assert!(slasher_config.migrate_backend);
+        });
+}
+#[test]
 pub fn malloc_tuning_flag() {
     CommandLineTest::new()
         .flag("disable-malloc-tuning", None)
@@ -842,6 +1434,15 @@ pub fn malloc_tuning_flag() {
         });
 }
 #[test]
+pub fn malloc_arena_max_flag() {
+    CommandLineTest::new()
+        .flag("malloc-arena-max", Some("4"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.http_metrics.allocator_metrics_enabled);
+        });
+}
+#[test]
 #[should_panic]
 fn ensure_panic_on_failed_launch() {
     CommandLineTest::new()
@@ -857,6 +1458,78 @@ fn ensure_panic_on_failed_launch() {
         });
 }
 
+// Tests for the config file.
+fn write_config_file(dir: &TempDir, name: &str, contents: &str) -> PathBuf {
+    let path = dir.path().join(name);
+    let mut file = File::create(&path).expect("Unable to create config file");
+    file.write_all(contents.as_bytes())
+        .expect("Unable to write to config file");
+    path
+}
+#[test]
+fn config_file_toml_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = write_config_file(&dir, "beacon.toml", "http = true\ntarget-peers = 30\n");
+    CommandLineTest::new()
+        .flag("config-file", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.http_api.enabled);
+            assert_eq!(config.network.target_peers, 30);
+        });
+}
+#[test]
+fn config_file_yaml_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = write_config_file(&dir, "beacon.yaml", "http: true\ntarget-peers: 30\n");
+    CommandLineTest::new()
+        .flag("config-file", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.http_api.enabled);
+            assert_eq!(config.network.target_peers, 30);
+        });
+}
+#[test]
+fn config_file_overridden_by_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = write_config_file(&dir, "beacon.toml", "target-peers = 30\n");
+    CommandLineTest::new()
+        .flag("config-file", path.as_os_str().to_str())
+        .flag("target-peers", Some("55"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.target_peers, 55));
+}
+#[test]
+#[should_panic]
+fn config_file_unknown_key() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = write_config_file(&dir, "beacon.toml", "not-a-flag = true\n");
+    CommandLineTest::new()
+        .flag("config-file", path.as_os_str().to_str())
+        .run_with_zero_port();
+}
+#[test]
+fn config_dump_round_trip() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("beacon.toml");
+    let output = Command::new(env!("CARGO_BIN_EXE_lighthouse"))
+        .args(&["config", "dump", "--output"])
+        .arg(&path)
+        .args(&["--", "--http", "--target-peers", "30"])
+        .output()
+        .expect("should run lighthouse config dump");
+    assert!(output.status.success(), "{:?}", output);
+
+    CommandLineTest::new()
+        .flag("config-file", path.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config.http_api.enabled);
+            assert_eq!(config.network.target_peers, 30);
+        });
+}
+
 /// A bit of hack to find an unused port.
 ///
 /// Does not guarantee that the given port is unused after the function exits, just that it was
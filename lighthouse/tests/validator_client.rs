@@ -1,4 +1,4 @@
-use validator_client::Config;
+use validator_client::{Config, SlashingHalt};
 
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
@@ -7,8 +7,10 @@ use std::io::Write;
 use std::net::Ipv4Addr;
 use std::path::PathBuf;
 use std::process::Command;
+use std::str::FromStr;
 use std::string::ToString;
 use tempfile::TempDir;
+use types::Address;
 
 /// Returns the `lighthouse validator_client` command.
 fn base_cmd() -> Command {
@@ -86,6 +88,21 @@ fn beacon_nodes_flag() {
         });
 }
 
+#[test]
+fn beacon_node_deprecated_flags() {
+    for flag in &["beacon-node", "server"] {
+        CommandLineTest::new()
+            .flag(flag, Some("http://localhost:1001"))
+            .run()
+            .with_config(|config| {
+                assert_eq!(
+                    config.beacon_nodes[0].full.to_string(),
+                    "http://localhost:1001/"
+                );
+            });
+    }
+}
+
 #[test]
 fn allow_unsynced_flag() {
     CommandLineTest::new()
@@ -102,6 +119,21 @@ fn disable_auto_discover_flag() {
         .with_config(|config| assert!(config.disable_auto_discover));
 }
 
+#[test]
+fn watch_validators_flag() {
+    CommandLineTest::new()
+        .flag("watch-validators", None)
+        .run()
+        .with_config(|config| assert!(config.watch_validators));
+}
+
+#[test]
+fn no_watch_validators_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.watch_validators));
+}
+
 #[test]
 fn init_slashing_protections_flag() {
     CommandLineTest::new()
@@ -144,6 +176,42 @@ fn beacon_nodes_tls_certs_flag() {
             )
         });
 }
+#[test]
+fn beacon_node_header_flag() {
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes",
+            Some("http://localhost:1001,https://provider.example/"),
+        )
+        .flag("beacon-node-header", Some("1:Authorization=Bearer secret"))
+        .flag("beacon-node-header", Some("1:X-Api-Key=key"))
+        .run()
+        .with_config(|config| {
+            assert!(config.beacon_node_headers.get(&0).is_none());
+            assert_eq!(
+                config.beacon_node_headers.get(&1),
+                Some(&vec![
+                    ("Authorization".to_string(), "Bearer secret".to_string()),
+                    ("X-Api-Key".to_string(), "key".to_string()),
+                ])
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn beacon_node_header_flag_invalid_index() {
+    CommandLineTest::new()
+        .flag("beacon-nodes", Some("http://localhost:1001"))
+        .flag("beacon-node-header", Some("1:Authorization=Bearer secret"))
+        .run();
+}
+#[test]
+#[should_panic]
+fn beacon_node_header_flag_invalid_format() {
+    CommandLineTest::new()
+        .flag("beacon-node-header", Some("Authorization: Bearer secret"))
+        .run();
+}
 
 // Tests for Graffiti flags.
 #[test]
@@ -218,6 +286,68 @@ fn graffiti_file_with_pk_flag() {
         });
 }
 
+// Tests for suggested-fee-recipient flags.
+#[test]
+fn fee_recipient_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut file =
+        File::create(dir.path().join("fee_recipient.txt")).expect("Unable to create file");
+    let new_key = Keypair::random();
+    let pubkeybytes = PublicKeyBytes::from(new_key.pk);
+    let contents = "default:0x00000000219ab540356cbb839cbe05303d7705fa";
+    file.write_all(contents.as_bytes())
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag(
+            "suggested-fee-recipient-file",
+            dir.path().join("fee_recipient.txt").as_os_str().to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            // Public key not present so load default.
+            assert_eq!(
+                config
+                    .fee_recipient_file
+                    .clone()
+                    .unwrap()
+                    .load_fee_recipient(&pubkeybytes)
+                    .unwrap(),
+                Some(Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap())
+            )
+        });
+}
+#[test]
+fn fee_recipient_file_with_pk_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut file =
+        File::create(dir.path().join("fee_recipient.txt")).expect("Unable to create file");
+    let new_key = Keypair::random();
+    let pubkeybytes = PublicKeyBytes::from(new_key.pk);
+    let contents = format!(
+        "{}:0x00000000219ab540356cbb839cbe05303d7705fa",
+        pubkeybytes.to_string()
+    );
+    file.write_all(contents.as_bytes())
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag(
+            "suggested-fee-recipient-file",
+            dir.path().join("fee_recipient.txt").as_os_str().to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config
+                    .fee_recipient_file
+                    .clone()
+                    .unwrap()
+                    .load_fee_recipient(&pubkeybytes)
+                    .unwrap(),
+                Some(Address::from_str("0x00000000219ab540356cbb839cbe05303d7705fa").unwrap())
+            )
+        });
+}
+
 // Tests for HTTP flags.
 #[test]
 fn http_flag() {
@@ -270,6 +400,55 @@ fn http_allow_origin_all_flag() {
         .run()
         .with_config(|config| assert_eq!(config.http_api.allow_origin, Some("*".to_string())));
 }
+#[test]
+fn http_allow_methods_and_headers_flags() {
+    CommandLineTest::new()
+        .flag("http-allow-methods", Some("GET,POST,OPTIONS"))
+        .flag(
+            "http-allow-headers",
+            Some("Content-Type,Authorization,X-Requested-With"),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.allow_methods,
+                Some("GET,POST,OPTIONS".to_string())
+            );
+            assert_eq!(
+                config.http_api.allow_headers,
+                Some("Content-Type,Authorization,X-Requested-With".to_string())
+            );
+        });
+}
+#[test]
+fn http_allow_credentials_flag() {
+    CommandLineTest::new()
+        .flag("http-allow-credentials", None)
+        .run()
+        .with_config(|config| assert!(config.http_api.allow_credentials));
+}
+#[test]
+fn http_allow_credentials_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.http_api.allow_credentials));
+}
+
+// Tests for monitoring endpoint flags.
+#[test]
+fn monitoring_endpoint_period_and_skip_system_flags() {
+    CommandLineTest::new()
+        .flag("monitoring-endpoint", Some("http://example:8000"))
+        .flag("monitoring-endpoint-period", Some("30"))
+        .flag("monitoring-endpoint-skip-system", None)
+        .run()
+        .with_config(|config| {
+            let api_conf = config.monitoring_api.as_ref().unwrap();
+            assert_eq!(api_conf.monitoring_endpoint.as_str(), "http://example:8000");
+            assert_eq!(api_conf.update_period_secs, Some(30));
+            assert!(api_conf.skip_system_metrics);
+        });
+}
 
 // Tests for Metrics flags.
 #[test]
@@ -314,6 +493,39 @@ fn metrics_allow_origin_all_flag() {
         .with_config(|config| assert_eq!(config.http_metrics.allow_origin, Some("*".to_string())));
 }
 #[test]
+fn metrics_allow_methods_and_headers_flags() {
+    CommandLineTest::new()
+        .flag("metrics-allow-methods", Some("GET,POST,OPTIONS"))
+        .flag(
+            "metrics-allow-headers",
+            Some("Content-Type,X-Requested-With"),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_metrics.allow_methods,
+                Some("GET,POST,OPTIONS".to_string())
+            );
+            assert_eq!(
+                config.http_metrics.allow_headers,
+                Some("Content-Type,X-Requested-With".to_string())
+            );
+        });
+}
+#[test]
+fn metrics_allow_credentials_flag() {
+    CommandLineTest::new()
+        .flag("metrics-allow-credentials", None)
+        .run()
+        .with_config(|config| assert!(config.http_metrics.allow_credentials));
+}
+#[test]
+fn metrics_allow_credentials_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.http_metrics.allow_credentials));
+}
+#[test]
 pub fn malloc_tuning_flag() {
     CommandLineTest::new()
         .flag("disable-malloc-tuning", None)
@@ -334,3 +546,215 @@ fn no_doppelganger_protection_flag() {
         .run()
         .with_config(|config| assert!(!config.enable_doppelganger_protection));
 }
+#[test]
+fn attest_to_optimistic_head_flag() {
+    CommandLineTest::new()
+        .flag("attest-to-optimistic-head", None)
+        .run()
+        .with_config(|config| assert!(config.attest_to_optimistic_head));
+}
+#[test]
+fn no_attest_to_optimistic_head_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.attest_to_optimistic_head));
+}
+#[test]
+fn attest_early_when_block_seen_flag() {
+    CommandLineTest::new()
+        .flag("attest-early-when-block-seen", None)
+        .run()
+        .with_config(|config| assert!(config.attest_early_when_block_seen));
+}
+#[test]
+fn no_attest_early_when_block_seen_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.attest_early_when_block_seen));
+}
+#[test]
+fn distributed_flag() {
+    CommandLineTest::new()
+        .flag("distributed", None)
+        .run()
+        .with_config(|config| assert!(config.distributed));
+}
+#[test]
+fn no_distributed_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.distributed));
+}
+#[test]
+fn disable_publication_broadcast_flag() {
+    CommandLineTest::new()
+        .flag("disable-publication-broadcast", None)
+        .run()
+        .with_config(|config| assert!(!config.broadcast_publications));
+}
+#[test]
+fn no_disable_publication_broadcast_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(config.broadcast_publications));
+}
+#[test]
+fn halt_on_slashing_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.halt_on_slashing, None));
+}
+#[test]
+fn halt_on_slashing_key() {
+    CommandLineTest::new()
+        .flag("halt-on-slashing", Some("key"))
+        .run()
+        .with_config(|config| assert_eq!(config.halt_on_slashing, Some(SlashingHalt::Key)));
+}
+#[test]
+fn halt_on_slashing_all() {
+    CommandLineTest::new()
+        .flag("halt-on-slashing", Some("all"))
+        .run()
+        .with_config(|config| assert_eq!(config.halt_on_slashing, Some(SlashingHalt::All)));
+}
+#[test]
+#[should_panic]
+fn halt_on_slashing_invalid() {
+    CommandLineTest::new()
+        .flag("halt-on-slashing", Some("some"))
+        .run();
+}
+#[test]
+fn slashing_webhook_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(config.slashing_webhook.is_none()));
+}
+#[test]
+fn slashing_webhook_flag() {
+    CommandLineTest::new()
+        .flag(
+            "slashing-webhook",
+            Some("https://alerts.example.com/secret"),
+        )
+        .run()
+        .with_config(|config| {
+            let webhook = config.slashing_webhook.as_ref().unwrap();
+            assert_eq!(
+                webhook.full.to_string(),
+                "https://alerts.example.com/secret"
+            );
+            assert_eq!(webhook.to_string(), "https://alerts.example.com/");
+        });
+}
+#[test]
+#[should_panic]
+fn slashing_webhook_invalid() {
+    CommandLineTest::new()
+        .flag("slashing-webhook", Some("not a url"))
+        .run();
+}
+#[test]
+fn builder_proposals_flag() {
+    CommandLineTest::new()
+        .flag("builder-proposals", None)
+        .run()
+        .with_config(|config| assert!(config.builder_proposals));
+}
+#[test]
+fn no_builder_proposals_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.builder_proposals));
+}
+#[test]
+fn keystore_decryption_threads_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.keystore_decryption_threads, None));
+}
+#[test]
+fn keystore_decryption_threads_flag() {
+    CommandLineTest::new()
+        .flag("keystore-decryption-threads", Some("4"))
+        .run()
+        .with_config(|config| assert_eq!(config.keystore_decryption_threads, Some(4)));
+}
+#[test]
+#[should_panic]
+fn keystore_decryption_threads_zero() {
+    CommandLineTest::new()
+        .flag("keystore-decryption-threads", Some("0"))
+        .run();
+}
+#[test]
+fn slashing_protection_history_epochs_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert_eq!(config.slashing_protection_history_epochs, 512));
+}
+#[test]
+fn slashing_protection_history_epochs_flag() {
+    CommandLineTest::new()
+        .flag("slashing-protection-history-epochs", Some("64"))
+        .run()
+        .with_config(|config| assert_eq!(config.slashing_protection_history_epochs, 64));
+}
+#[test]
+#[should_panic]
+fn slashing_protection_history_epochs_zero() {
+    CommandLineTest::new()
+        .flag("slashing-protection-history-epochs", Some("0"))
+        .run();
+}
+
+/// Runs the validator client with `flags` and a log file, returning each line of the log file.
+fn logfile_lines(flags: &[(&str, Option<&str>)]) -> Vec<String> {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let logfile = dir.path().join("vc.log");
+    let mut test = CommandLineTest::new();
+    test.flag("logfile", logfile.to_str());
+    for (flag, value) in flags {
+        test.flag(flag, *value);
+    }
+    test.run();
+    std::fs::read_to_string(logfile)
+        .expect("should read log file")
+        .lines()
+        .map(ToString::to_string)
+        .collect()
+}
+/// Returns `true` if `line` is a JSON log with the keys shared by all JSON logs.
+fn is_json_log(line: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line).map_or(false, |log| {
+        ["ts", "level", "msg", "module"]
+            .iter()
+            .all(|key| log.get(key).is_some())
+    })
+}
+#[test]
+fn logfile_format_default() {
+    let lines = logfile_lines(&[]);
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| !is_json_log(line)));
+}
+#[test]
+fn logfile_format_defaults_to_log_format() {
+    let lines = logfile_lines(&[("log-format", Some("JSON"))]);
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| is_json_log(line)));
+}
+#[test]
+fn logfile_format_flag() {
+    let lines = logfile_lines(&[("logfile-format", Some("json"))]);
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| is_json_log(line)));
+}
+#[test]
+#[should_panic]
+fn logfile_format_invalid() {
+    CommandLineTest::new()
+        .flag("logfile-format", Some("yaml"))
+        .run();
+}
@@ -0,0 +1,78 @@
+#!/usr/bin/env python3
+
+# The purpose of this script is to compare the results of a criterion benchmark run against a saved
+# baseline and return an error if any benchmark has become significantly slower.
+#
+# Criterion stores the estimates of each benchmark in `<criterion_dir>/<group>/<bench>/<name>/`,
+# where `<name>` is either the name of a baseline saved with `--save-baseline <name>` or `new` for
+# the most recent run. Run the benchmarks with `--baseline <name>` so that the baseline is not
+# overwritten by the most recent run.
+
+import argparse
+import json
+import os
+import sys
+
+parser = argparse.ArgumentParser(
+    description="Fail if any criterion benchmark regressed against a saved baseline."
+)
+parser.add_argument(
+    "criterion_dir",
+    nargs="?",
+    default="target/criterion",
+    help="the directory containing the criterion results",
+)
+parser.add_argument(
+    "--baseline", default="base", help="the name of the saved baseline to compare against"
+)
+parser.add_argument(
+    "--threshold",
+    type=float,
+    default=0.1,
+    help="the maximum allowed increase in mean time, as a fraction of the baseline",
+)
+args = parser.parse_args()
+
+
+def mean_estimate(path):
+    with open(path) as f:
+        return json.load(f)["mean"]["point_estimate"]
+
+
+results = []
+missing = []
+for root, dirs, files in os.walk(args.criterion_dir):
+    if os.path.basename(root) != args.baseline or "estimates.json" not in files:
+        continue
+    bench_dir = os.path.dirname(root)
+    bench = os.path.relpath(bench_dir, args.criterion_dir)
+    new_estimates = os.path.join(bench_dir, "new", "estimates.json")
+    if not os.path.exists(new_estimates):
+        missing.append(bench)
+        continue
+    base = mean_estimate(os.path.join(root, "estimates.json"))
+    new = mean_estimate(new_estimates)
+    results.append((bench, base, new, new / base - 1))
+
+if not results:
+    print("No benchmarks found with baseline '{}' in {}".format(args.baseline, args.criterion_dir))
+    sys.exit(1)
+
+regressions = []
+for bench, base, new, change in sorted(results):
+    regressed = change > args.threshold
+    if regressed:
+        regressions.append(bench)
+    print(
+        "{:<70} {:>12.3f} ms {:>12.3f} ms {:>+8.1%}{}".format(
+            bench, base / 1e6, new / 1e6, change, "  REGRESSED" if regressed else ""
+        )
+    )
+
+# Benchmarks which were removed or renamed by the change have no new results to compare.
+for bench in missing:
+    print("{} has a baseline but was not run, skipping".format(bench))
+
+if regressions:
+    print("{} benchmark(s) regressed by more than {:.0%}".format(len(regressions), args.threshold))
+    sys.exit(1)
@@ -0,0 +1,94 @@
+use clap::{App, Arg};
+
+pub const CMD: &str = "slasher";
+pub const INGEST_CMD: &str = "ingest";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about("Utilities for running the Lighthouse slasher outside of the beacon node.")
+        .subcommand(ingest_cli_app())
+}
+
+fn ingest_cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(INGEST_CMD)
+        .about(
+            "Run a standalone slasher which ingests attestations and blocks from the event \
+             stream of a beacon node's HTTP API, rather than running inside the beacon node. \
+             The beacon node should be run with --subscribe-all-subnets so that it sees all \
+             unaggregated attestations.",
+        )
+        .arg(
+            Arg::with_name("beacon-node")
+                .long("beacon-node")
+                .value_name("NETWORK_ADDRESS")
+                .help("HTTP API endpoint of the beacon node to ingest events from.")
+                .default_value("http://localhost:5052/")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slasher-dir")
+                .long("slasher-dir")
+                .value_name("PATH")
+                .help(
+                    "Set the slasher's database directory. Defaults to the slasher_db \
+                     directory within the data directory.",
+                )
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("submit-slashings")
+                .long("submit-slashings")
+                .help(
+                    "Submit slashings found to the beacon node's operation pool, from which they \
+                     are published to the network [disabled by default].",
+                ),
+        )
+        .arg(
+            Arg::with_name("slasher-update-period")
+                .long("slasher-update-period")
+                .help("Configure how often the slasher runs batch processing.")
+                .value_name("SECONDS")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slasher-slot-offset")
+                .long("slasher-slot-offset")
+                .help(
+                    "Set the delay from the start of the slot at which the slasher should ingest \
+                     attestations. Only effective if the slasher-update-period is a multiple of \
+                     the slot duration.",
+                )
+                .value_name("SECONDS")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slasher-history-length")
+                .long("slasher-history-length")
+                .help("Configure how many epochs of history the slasher keeps.")
+                .value_name("EPOCHS")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slasher-max-db-size")
+                .long("slasher-max-db-size")
+                .help("Maximum size of the database used by the slasher.")
+                .value_name("GIGABYTES")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slasher-backend")
+                .long("slasher-backend")
+                .help("Set the database backend to be used by the slasher.")
+                .value_name("DATABASE")
+                .takes_value(true)
+                .possible_values(&["mdbx", "lmdb"]),
+        )
+        .arg(
+            Arg::with_name("slasher-migrate-backend")
+                .long("slasher-migrate-backend")
+                .help(
+                    "Convert an existing slasher database created with a different backend to \
+                     the --slasher-backend, instead of refusing to start.",
+                ),
+        )
+}
@@ -0,0 +1,70 @@
+use clap::ArgMatches;
+use sensitive_url::SensitiveUrl;
+use std::path::PathBuf;
+
+/// Configuration for the standalone slasher.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The beacon node to ingest events from.
+    pub beacon_node: SensitiveUrl,
+    /// Whether to submit slashings found to the beacon node.
+    pub submit_slashings: bool,
+    pub slasher: slasher::Config,
+}
+
+impl Config {
+    pub fn from_cli(cli_args: &ArgMatches) -> Result<Self, String> {
+        let beacon_node =
+            clap_utils::parse_required::<String>(cli_args, "beacon-node").and_then(|url| {
+                SensitiveUrl::parse(&url)
+                    .map_err(|e| format!("Unable to parse beacon node URL: {:?}", e))
+            })?;
+
+        let slasher_dir = match clap_utils::parse_optional::<PathBuf>(cli_args, "slasher-dir")? {
+            Some(slasher_dir) => slasher_dir,
+            None => directory::parse_path_or_default(cli_args, "datadir")?.join("slasher_db"),
+        };
+        let mut slasher = slasher::Config::new(slasher_dir);
+
+        if let Some(update_period) = clap_utils::parse_optional(cli_args, "slasher-update-period")?
+        {
+            slasher.update_period = update_period;
+        }
+
+        if let Some(slot_offset) =
+            clap_utils::parse_optional::<f64>(cli_args, "slasher-slot-offset")?
+        {
+            if slot_offset.is_finite() {
+                slasher.slot_offset = slot_offset;
+            } else {
+                return Err(format!(
+                    "invalid float for slasher-slot-offset: {}",
+                    slot_offset
+                ));
+            }
+        }
+
+        if let Some(history_length) =
+            clap_utils::parse_optional(cli_args, "slasher-history-length")?
+        {
+            slasher.history_length = history_length;
+        }
+
+        if let Some(max_db_size_gbs) =
+            clap_utils::parse_optional::<usize>(cli_args, "slasher-max-db-size")?
+        {
+            slasher.max_db_size_mbs = max_db_size_gbs * 1024;
+        }
+
+        if let Some(backend) = clap_utils::parse_optional(cli_args, "slasher-backend")? {
+            slasher.backend = backend;
+        }
+        slasher.migrate_backend = cli_args.is_present("slasher-migrate-backend");
+
+        Ok(Self {
+            beacon_node,
+            submit_slashings: cli_args.is_present("submit-slashings"),
+            slasher,
+        })
+    }
+}
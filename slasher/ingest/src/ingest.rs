@@ -0,0 +1,475 @@
+//! Ingestion of attestations and blocks from the event stream of a beacon node.
+//!
+//! Messages are queued in the slasher as they arrive, and processed in batches every
+//! `update_period` seconds, as when the slasher runs inside the beacon node.
+use crate::config::Config;
+use environment::RuntimeContext;
+use eth2::{
+    types::{BlockId, EventKind, EventTopic, StateId},
+    BeaconNodeHttpClient, Timeouts,
+};
+use futures::StreamExt;
+use slasher::{
+    scheduler::{run_notifier, run_processor},
+    Slasher,
+};
+use slog::{debug, info, o, warn, Logger};
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use state_processing::common::get_indexed_attestation;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::mpsc::sync_channel;
+use std::sync::Arc;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::time::{sleep, Duration, Instant};
+use types::{
+    Attestation, AttesterSlashing, Epoch, EthSpec, Hash256, IndexedAttestation, ProposerSlashing,
+    SignedBeaconBlock, Slot,
+};
+
+/// Timeout for requests to the beacon node.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(12);
+/// Delay before reconnecting to the event stream after it fails.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+/// Number of epochs of committees to keep in the `CommitteeCache`.
+const COMMITTEE_CACHE_EPOCHS: u64 = 4;
+/// Delay before requesting the committees of an epoch again after failing to fetch them.
+const COMMITTEE_RETRY_DELAY: Duration = Duration::from_secs(12);
+
+/// Start the standalone slasher, ingesting events from the beacon node in `config`.
+pub async fn run<E: EthSpec>(config: Config, context: RuntimeContext<E>) -> Result<(), String> {
+    let log = context.log().clone();
+    let spec = &context.eth2_config.spec;
+
+    let beacon_node =
+        BeaconNodeHttpClient::new(config.beacon_node.clone(), Timeouts::set_all(HTTP_TIMEOUT));
+    let genesis = beacon_node
+        .get_beacon_genesis()
+        .await
+        .map_err(|e| format!("Unable to get genesis from beacon node: {:?}", e))?
+        .data;
+    let slot_clock = SystemTimeSlotClock::new(
+        spec.genesis_slot,
+        Duration::from_secs(genesis.genesis_time),
+        Duration::from_secs(spec.seconds_per_slot),
+    );
+
+    let slasher = Arc::new(
+        Slasher::open(config.slasher.clone(), log.new(o!("service" => "slasher")))
+            .map_err(|e| format!("Unable to open slasher: {:?}", e))?,
+    );
+
+    info!(
+        log,
+        "Starting standalone slasher";
+        "beacon_node" => %config.beacon_node,
+        "submit_slashings" => config.submit_slashings,
+    );
+
+    // Buffer just a single message in the channel, as in the slasher service of the beacon node.
+    let (notif_sender, notif_receiver) = sync_channel(1);
+    let (slashings_sender, slashings_receiver) = unbounded_channel();
+
+    context.executor.spawn(
+        run_notifier::<E, _>(
+            slot_clock,
+            slasher.config().update_period,
+            slasher.config().slot_offset,
+            notif_sender,
+            log.clone(),
+        ),
+        "slasher_ingest_notifier",
+    );
+    let processor_slasher = slasher.clone();
+    context.executor.spawn_blocking(
+        move || {
+            run_processor(&processor_slasher, notif_receiver, |slasher| {
+                // The receiver only stops when the runtime is shutting down.
+                let _ = slashings_sender.send(Slashings {
+                    attester: slasher.get_attester_slashings(),
+                    proposer: slasher.get_proposer_slashings(),
+                });
+            })
+        },
+        "slasher_ingest_processor",
+    );
+    context.executor.spawn(
+        submit_or_log_slashings(
+            slashings_receiver,
+            beacon_node.clone(),
+            config.submit_slashings,
+            log.clone(),
+        ),
+        "slasher_ingest_submitter",
+    );
+    context.executor.spawn(
+        ingest_events(slasher, beacon_node, log),
+        "slasher_ingest_events",
+    );
+
+    Ok(())
+}
+
+/// The slashings found by a single slasher update.
+struct Slashings<E: EthSpec> {
+    attester: HashSet<AttesterSlashing<E>>,
+    proposer: HashSet<ProposerSlashing>,
+}
+
+/// Committees of a single epoch, keyed by slot and committee index.
+type Committees = HashMap<(Slot, u64), Vec<usize>>;
+
+enum CommitteeCacheEntry {
+    Committees(Committees),
+    /// The committees could not be fetched, and shouldn't be requested again until `retry_at`.
+    Unavailable {
+        error: String,
+        retry_at: Instant,
+    },
+}
+
+/// Committees of recent epochs, used to convert attestations to indexed attestations.
+#[derive(Default)]
+struct CommitteeCache {
+    entries: BTreeMap<Epoch, CommitteeCacheEntry>,
+}
+
+impl CommitteeCache {
+    async fn get_indexed_attestation<E: EthSpec>(
+        &mut self,
+        beacon_node: &BeaconNodeHttpClient,
+        attestation: &Attestation<E>,
+    ) -> Result<IndexedAttestation<E>, String> {
+        let epoch = attestation.data.target.epoch;
+        if self.needs_fetch(epoch, Instant::now()) {
+            let committees = get_committees::<E>(beacon_node, epoch).await;
+            self.insert(epoch, committees, Instant::now());
+        }
+        self.index_attestation(attestation)
+    }
+
+    /// Return `true` if the committees of `epoch` are neither cached nor recently failed.
+    fn needs_fetch(&self, epoch: Epoch, now: Instant) -> bool {
+        match self.entries.get(&epoch) {
+            Some(CommitteeCacheEntry::Committees(_)) => false,
+            Some(CommitteeCacheEntry::Unavailable { retry_at, .. }) => now >= *retry_at,
+            None => true,
+        }
+    }
+
+    /// Cache the result of fetching the committees of `epoch`, keeping only the most recent
+    /// epochs.
+    fn insert(&mut self, epoch: Epoch, committees: Result<Committees, String>, now: Instant) {
+        let entry = match committees {
+            Ok(committees) => CommitteeCacheEntry::Committees(committees),
+            Err(error) => CommitteeCacheEntry::Unavailable {
+                error,
+                retry_at: now + COMMITTEE_RETRY_DELAY,
+            },
+        };
+        self.entries.insert(epoch, entry);
+
+        while self.entries.len() > COMMITTEE_CACHE_EPOCHS as usize {
+            let oldest_epoch = *self.entries.keys().next().expect("cache is not empty");
+            self.entries.remove(&oldest_epoch);
+        }
+    }
+
+    fn index_attestation<E: EthSpec>(
+        &self,
+        attestation: &Attestation<E>,
+    ) -> Result<IndexedAttestation<E>, String> {
+        let committees = match self.entries.get(&attestation.data.target.epoch) {
+            Some(CommitteeCacheEntry::Committees(committees)) => committees,
+            Some(CommitteeCacheEntry::Unavailable { error, .. }) => {
+                return Err(format!("Committees unavailable: {}", error))
+            }
+            None => return Err("Committees not cached".to_string()),
+        };
+        let committee = committees
+            .get(&(attestation.data.slot, attestation.data.index))
+            .ok_or_else(|| "Unknown committee".to_string())?;
+        get_indexed_attestation(committee, attestation).map_err(|e| format!("{:?}", e))
+    }
+}
+
+/// Fetch the committees of `epoch` from the state at the start of the epoch, so that the
+/// committees of past epochs can be fetched as well as those of the head.
+async fn get_committees<E: EthSpec>(
+    beacon_node: &BeaconNodeHttpClient,
+    epoch: Epoch,
+) -> Result<Committees, String> {
+    let state_id = StateId::Slot(epoch.start_slot(E::slots_per_epoch()));
+    let committees = beacon_node
+        .get_beacon_states_committees(state_id, None, None, Some(epoch))
+        .await
+        .map_err(|e| format!("Unable to get committees: {:?}", e))?
+        .ok_or_else(|| format!("State at start of epoch {} not found", epoch))?
+        .data
+        .into_iter()
+        .map(|committee| {
+            let validators = committee.validators.into_iter().map(|i| i as usize);
+            ((committee.slot, committee.index), validators.collect())
+        })
+        .collect();
+    Ok(committees)
+}
+
+/// Follow the event stream of the beacon node, pushing attestations and blocks into the slasher's
+/// queues. Reconnects if the stream fails.
+///
+/// The attestations included in blocks are ingested as well as those seen on gossip, as the beacon
+/// node may not have seen the latter.
+async fn ingest_events<E: EthSpec>(
+    slasher: Arc<Slasher<E>>,
+    beacon_node: BeaconNodeHttpClient,
+    log: Logger,
+) {
+    let mut committee_cache = CommitteeCache::default();
+
+    loop {
+        let mut events = match beacon_node
+            .get_events::<E>(&[EventTopic::Attestation, EventTopic::Block])
+            .await
+        {
+            Ok(events) => Box::pin(events),
+            Err(e) => {
+                warn!(
+                    log,
+                    "Unable to subscribe to beacon node events";
+                    "error" => %e,
+                );
+                sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+        info!(log, "Subscribed to beacon node events");
+
+        while let Some(event) = events.next().await {
+            match event {
+                Ok(EventKind::Attestation(attestation)) => {
+                    ingest_attestation(
+                        &slasher,
+                        &beacon_node,
+                        &mut committee_cache,
+                        &attestation,
+                        &log,
+                    )
+                    .await
+                }
+                Ok(EventKind::Block(block)) => {
+                    match get_block::<E>(&beacon_node, block.block).await {
+                        Ok(signed_block) => {
+                            slasher.accept_block_header(signed_block.signed_block_header());
+                            for attestation in signed_block.message().body().attestations() {
+                                ingest_attestation(
+                                    &slasher,
+                                    &beacon_node,
+                                    &mut committee_cache,
+                                    attestation,
+                                    &log,
+                                )
+                                .await
+                            }
+                        }
+                        Err(e) => debug!(
+                            log,
+                            "Unable to get block";
+                            "error" => e,
+                            "block_root" => ?block.block,
+                        ),
+                    }
+                }
+                Ok(_) => (),
+                Err(e) => {
+                    warn!(log, "Error from beacon node event stream"; "error" => %e);
+                    break;
+                }
+            }
+        }
+
+        warn!(log, "Beacon node event stream ended");
+        sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn ingest_attestation<E: EthSpec>(
+    slasher: &Slasher<E>,
+    beacon_node: &BeaconNodeHttpClient,
+    committee_cache: &mut CommitteeCache,
+    attestation: &Attestation<E>,
+    log: &Logger,
+) {
+    match committee_cache
+        .get_indexed_attestation(beacon_node, attestation)
+        .await
+    {
+        Ok(indexed_attestation) => slasher.accept_attestation(indexed_attestation),
+        Err(e) => debug!(
+            log,
+            "Unable to index attestation";
+            "error" => e,
+            "slot" => attestation.data.slot,
+            "index" => attestation.data.index,
+        ),
+    }
+}
+
+async fn get_block<E: EthSpec>(
+    beacon_node: &BeaconNodeHttpClient,
+    block_root: Hash256,
+) -> Result<SignedBeaconBlock<E>, String> {
+    Ok(beacon_node
+        .get_beacon_blocks::<E>(BlockId::Root(block_root))
+        .await
+        .map_err(|e| format!("{:?}", e))?
+        .ok_or_else(|| "Unknown block".to_string())?
+        .data)
+}
+
+/// Submit the slashings found to the beacon node, or just log them if `submit_slashings` is false.
+async fn submit_or_log_slashings<E: EthSpec>(
+    mut slashings_receiver: UnboundedReceiver<Slashings<E>>,
+    beacon_node: BeaconNodeHttpClient,
+    submit_slashings: bool,
+    log: Logger,
+) {
+    while let Some(slashings) = slashings_receiver.recv().await {
+        for slashing in slashings.attester {
+            info!(
+                log,
+                "Found attester slashing";
+                "attestation_1" => ?slashing.attestation_1.data,
+                "attestation_2" => ?slashing.attestation_2.data,
+            );
+            if submit_slashings {
+                if let Err(e) = beacon_node
+                    .post_beacon_pool_attester_slashings(&slashing)
+                    .await
+                {
+                    warn!(log, "Unable to submit attester slashing"; "error" => %e);
+                }
+            }
+        }
+
+        for slashing in slashings.proposer {
+            info!(
+                log,
+                "Found proposer slashing";
+                "proposer_index" => slashing.signed_header_1.message.proposer_index,
+                "slot" => slashing.signed_header_1.message.slot,
+            );
+            if submit_slashings {
+                if let Err(e) = beacon_node
+                    .post_beacon_pool_proposer_slashings(&slashing)
+                    .await
+                {
+                    warn!(log, "Unable to submit proposer slashing"; "error" => %e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{AggregateSignature, AttestationData, BitList, Checkpoint, MainnetEthSpec as E};
+
+    const COMMITTEE: &[usize] = &[7, 3, 12, 5];
+
+    fn attestation(epoch: Epoch, slot: Slot, index: u64, bits: &[usize]) -> Attestation<E> {
+        let mut aggregation_bits = BitList::with_capacity(COMMITTEE.len()).unwrap();
+        for &bit in bits {
+            aggregation_bits.set(bit, true).unwrap();
+        }
+        let checkpoint = Checkpoint {
+            epoch,
+            root: Hash256::zero(),
+        };
+        Attestation {
+            aggregation_bits,
+            data: AttestationData {
+                slot,
+                index,
+                beacon_block_root: Hash256::zero(),
+                source: checkpoint,
+                target: checkpoint,
+            },
+            signature: AggregateSignature::empty(),
+        }
+    }
+
+    fn committees(slot: Slot, index: u64) -> Committees {
+        vec![((slot, index), COMMITTEE.to_vec())]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn index_cached_attestation() {
+        let epoch = Epoch::new(3);
+        let slot = epoch.start_slot(E::slots_per_epoch()) + 1;
+        let mut cache = CommitteeCache::default();
+        assert!(cache.needs_fetch(epoch, Instant::now()));
+
+        cache.insert(epoch, Ok(committees(slot, 1)), Instant::now());
+        assert!(!cache.needs_fetch(epoch, Instant::now()));
+
+        let indexed = cache
+            .index_attestation(&attestation(epoch, slot, 1, &[1, 2]))
+            .unwrap();
+        assert_eq!(indexed.attesting_indices.to_vec(), vec![3, 12]);
+
+        // Attestations for committees which don't exist can't be indexed.
+        assert!(cache
+            .index_attestation(&attestation(epoch, slot, 0, &[1]))
+            .is_err());
+        assert!(cache
+            .index_attestation(&attestation(epoch + 1, slot, 1, &[1]))
+            .is_err());
+    }
+
+    #[test]
+    fn failed_fetch_is_cached_until_retry() {
+        let epoch = Epoch::new(3);
+        let slot = epoch.start_slot(E::slots_per_epoch());
+        let now = Instant::now();
+        let mut cache = CommitteeCache::default();
+
+        cache.insert(epoch, Err("state not found".to_string()), now);
+        assert!(!cache.needs_fetch(epoch, now));
+        assert!(!cache.needs_fetch(epoch, now + COMMITTEE_RETRY_DELAY / 2));
+        assert!(cache.needs_fetch(epoch, now + COMMITTEE_RETRY_DELAY));
+        assert!(cache
+            .index_attestation(&attestation(epoch, slot, 0, &[0]))
+            .unwrap_err()
+            .contains("state not found"));
+
+        // A successful retry replaces the failure.
+        cache.insert(epoch, Ok(committees(slot, 0)), now + COMMITTEE_RETRY_DELAY);
+        assert!(!cache.needs_fetch(epoch, now + 2 * COMMITTEE_RETRY_DELAY));
+        assert!(cache
+            .index_attestation(&attestation(epoch, slot, 0, &[0]))
+            .is_ok());
+    }
+
+    #[test]
+    fn oldest_epochs_are_pruned() {
+        let now = Instant::now();
+        let mut cache = CommitteeCache::default();
+        let num_epochs = 2 * COMMITTEE_CACHE_EPOCHS;
+
+        for epoch in (0..num_epochs).map(Epoch::new) {
+            let slot = epoch.start_slot(E::slots_per_epoch());
+            cache.insert(epoch, Ok(committees(slot, 0)), now);
+        }
+
+        assert_eq!(
+            cache.entries.keys().copied().collect::<Vec<_>>(),
+            (num_epochs - COMMITTEE_CACHE_EPOCHS..num_epochs)
+                .map(Epoch::new)
+                .collect::<Vec<_>>()
+        );
+        assert!(cache.needs_fetch(Epoch::new(0), now));
+    }
+}
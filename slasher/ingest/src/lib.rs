@@ -0,0 +1,42 @@
+//! A standalone slasher which ingests attestations and blocks from a beacon node's HTTP API.
+mod cli;
+mod config;
+mod ingest;
+
+pub use cli::{cli_app, CMD, INGEST_CMD};
+pub use config::Config;
+
+use clap::ArgMatches;
+use environment::RuntimeContext;
+use slog::crit;
+use task_executor::ShutdownReason;
+use types::EthSpec;
+
+/// Run the `slasher` command, spawning any long-running services onto the `context` executor.
+pub fn run<E: EthSpec>(matches: &ArgMatches, context: RuntimeContext<E>) -> Result<(), String> {
+    match matches.subcommand() {
+        (INGEST_CMD, Some(matches)) => {
+            let config = Config::from_cli(matches)?;
+            let log = context.log().clone();
+            let executor = context.executor.clone();
+            executor.clone().spawn(
+                async move {
+                    if let Err(e) = ingest::run(config, context).await {
+                        crit!(log, "Failed to start standalone slasher"; "reason" => e);
+                        // Ignore the error since it always occurs during normal operation when
+                        // shutting down.
+                        let _ = executor.shutdown_sender().try_send(ShutdownReason::Failure(
+                            "Failed to start standalone slasher",
+                        ));
+                    }
+                },
+                "slasher_ingest",
+            );
+            Ok(())
+        }
+        (unknown, _) => Err(format!(
+            "{} is not a valid {} command. See --help.",
+            unknown, CMD
+        )),
+    }
+}
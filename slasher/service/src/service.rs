@@ -1,27 +1,24 @@
 use beacon_chain::{
     observed_operations::ObservationOutcome, BeaconChain, BeaconChainError, BeaconChainTypes,
 };
-use directory::size_of_dir;
 use lighthouse_network::PubsubMessage;
 use network::NetworkMessage;
 use slasher::{
-    metrics::{self, SLASHER_DATABASE_SIZE, SLASHER_RUN_TIME},
+    scheduler::{run_notifier, run_processor},
     Slasher,
 };
-use slog::{debug, error, info, trace, warn, Logger};
-use slot_clock::SlotClock;
+use slog::{debug, error, info, warn};
 use state_processing::{
     per_block_processing::errors::{
         AttesterSlashingInvalid, BlockOperationError, ProposerSlashingInvalid,
     },
     VerifyOperation,
 };
-use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::mpsc::sync_channel;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc::UnboundedSender;
-use tokio::time::{interval_at, Duration, Instant};
-use types::{AttesterSlashing, Epoch, EthSpec, ProposerSlashing};
+use types::{AttesterSlashing, ProposerSlashing};
 
 pub struct SlasherService<T: BeaconChainTypes> {
     beacon_chain: Arc<BeaconChain<T>>,
@@ -60,8 +57,8 @@ impl<T: BeaconChainTypes> SlasherService<T> {
         let network_sender = self.network_sender.clone();
 
         executor.spawn(
-            Self::run_notifier(
-                beacon_chain.clone(),
+            run_notifier::<T::EthSpec, _>(
+                beacon_chain.slot_clock.clone(),
                 update_period,
                 slot_offset,
                 notif_sender,
@@ -71,101 +68,18 @@ impl<T: BeaconChainTypes> SlasherService<T> {
         );
 
         executor.spawn_blocking(
-            || Self::run_processor(beacon_chain, slasher, notif_receiver, network_sender),
+            move || {
+                run_processor(&slasher, notif_receiver, |slasher| {
+                    // Provide slashings to the beacon chain, and optionally publish them.
+                    Self::process_slashings(&beacon_chain, slasher, &network_sender)
+                })
+            },
             "slasher_server_processor",
         );
 
         Ok(())
     }
 
-    /// Run the async notifier which periodically prompts the processor to run.
-    async fn run_notifier(
-        beacon_chain: Arc<BeaconChain<T>>,
-        update_period: u64,
-        slot_offset: f64,
-        notif_sender: SyncSender<Epoch>,
-        log: Logger,
-    ) {
-        let slot_offset = Duration::from_secs_f64(slot_offset);
-        let start_instant =
-            if let Some(duration_to_next_slot) = beacon_chain.slot_clock.duration_to_next_slot() {
-                Instant::now() + duration_to_next_slot + slot_offset
-            } else {
-                error!(log, "Error aligning slasher to slot clock");
-                Instant::now()
-            };
-        let mut interval = interval_at(start_instant, Duration::from_secs(update_period));
-
-        loop {
-            interval.tick().await;
-            if let Some(current_slot) = beacon_chain.slot_clock.now() {
-                let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
-                if let Err(TrySendError::Disconnected(_)) = notif_sender.try_send(current_epoch) {
-                    break;
-                }
-            } else {
-                trace!(log, "Slasher has nothing to do: we are pre-genesis");
-            }
-        }
-    }
-
-    /// Run the blocking task that performs work.
-    fn run_processor(
-        beacon_chain: Arc<BeaconChain<T>>,
-        slasher: Arc<Slasher<T::EthSpec>>,
-        notif_receiver: Receiver<Epoch>,
-        network_sender: UnboundedSender<NetworkMessage<T::EthSpec>>,
-    ) {
-        let log = slasher.log();
-        while let Ok(current_epoch) = notif_receiver.recv() {
-            let t = Instant::now();
-
-            let batch_timer = metrics::start_timer(&SLASHER_RUN_TIME);
-            let stats = match slasher.process_queued(current_epoch) {
-                Ok(stats) => Some(stats),
-                Err(e) => {
-                    error!(
-                        log,
-                        "Error during scheduled slasher processing";
-                        "epoch" => current_epoch,
-                        "error" => ?e,
-                    );
-                    None
-                }
-            };
-            drop(batch_timer);
-
-            // Prune the database, even in the case where batch processing failed.
-            // If the database is full then pruning could help to free it up.
-            if let Err(e) = slasher.prune_database(current_epoch) {
-                error!(
-                    log,
-                    "Error during slasher database pruning";
-                    "epoch" => current_epoch,
-                    "error" => ?e,
-                );
-                continue;
-            };
-
-            // Provide slashings to the beacon chain, and optionally publish them.
-            Self::process_slashings(&beacon_chain, &slasher, &network_sender);
-
-            let database_size = size_of_dir(&slasher.config().database_path);
-            metrics::set_gauge(&SLASHER_DATABASE_SIZE, database_size as i64);
-
-            if let Some(stats) = stats {
-                debug!(
-                    log,
-                    "Completed slasher update";
-                    "epoch" => current_epoch,
-                    "time_taken" => format!("{}ms", t.elapsed().as_millis()),
-                    "num_attestations" => stats.attestation_stats.num_processed,
-                    "num_blocks" => stats.block_stats.num_processed,
-                );
-            }
-        }
-    }
-
     /// Push any slashings found to the beacon chain, optionally publishing them on the network.
     fn process_slashings(
         beacon_chain: &BeaconChain<T>,
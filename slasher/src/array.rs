@@ -1,3 +1,4 @@
+use crate::database::interface::Database;
 use crate::metrics::{self, SLASHER_COMPRESSION_RATIO, SLASHER_NUM_CHUNKS_UPDATED};
 use crate::RwTransaction;
 use crate::{AttesterSlashingStatus, Config, Error, IndexedAttesterRecord, SlasherDB};
@@ -51,6 +52,21 @@ impl Chunk {
             .ok_or(Error::ChunkIndexOutOfBounds(cell_index))
     }
 
+    pub fn get_raw_distance(
+        &self,
+        validator_index: u64,
+        epoch: Epoch,
+        config: &Config,
+    ) -> Result<u16, Error> {
+        let validator_offset = config.validator_offset(validator_index);
+        let chunk_offset = config.chunk_offset(epoch);
+        let cell_index = config.cell_index(validator_offset, chunk_offset);
+        self.data
+            .get(cell_index)
+            .copied()
+            .ok_or(Error::ChunkIndexOutOfBounds(cell_index))
+    }
+
     pub fn set_target(
         &mut self,
         validator_index: u64,
@@ -147,10 +163,7 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
 
     fn next_start_epoch(start_epoch: Epoch, config: &Config) -> Epoch;
 
-    fn select_db<'txn, E: EthSpec>(
-        db: &SlasherDB<E>,
-        txn: &'txn RwTransaction<'txn>,
-    ) -> Result<mdbx::Database<'txn>, Error>;
+    fn select_db<E: EthSpec>(db: &SlasherDB<E>) -> Database;
 
     fn load<E: EthSpec>(
         db: &SlasherDB<E>,
@@ -160,11 +173,10 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
         config: &Config,
     ) -> Result<Option<Self>, Error> {
         let disk_key = config.disk_key(validator_chunk_index, chunk_index);
-        let chunk_bytes: Cow<[u8]> =
-            match txn.get(&Self::select_db(db, txn)?, &disk_key.to_be_bytes())? {
-                Some(chunk_bytes) => chunk_bytes,
-                None => return Ok(None),
-            };
+        let chunk_bytes: Cow<[u8]> = match txn.get(Self::select_db(db), &disk_key.to_be_bytes())? {
+            Some(chunk_bytes) => chunk_bytes,
+            None => return Ok(None),
+        };
 
         let chunk = bincode::deserialize_from(ZlibDecoder::new(chunk_bytes.borrow()))?;
 
@@ -180,6 +192,18 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
         config: &Config,
     ) -> Result<(), Error> {
         let disk_key = config.disk_key(validator_chunk_index, chunk_index);
+        let compressed_value = self.compress()?;
+
+        txn.put(
+            Self::select_db(db),
+            &disk_key.to_be_bytes(),
+            &compressed_value,
+        )?;
+        Ok(())
+    }
+
+    /// Serialize and compress the chunk into the format stored on disk.
+    fn compress(&self) -> Result<Vec<u8>, Error> {
         let value = bincode::serialize(self)?;
         let mut encoder = ZlibEncoder::new(&value[..], flate2::Compression::default());
         let mut compressed_value = vec![];
@@ -188,13 +212,7 @@ pub trait TargetArrayChunk: Sized + serde::Serialize + serde::de::DeserializeOwn
         let compression_ratio = value.len() as f64 / compressed_value.len() as f64;
         metrics::set_float_gauge(&SLASHER_COMPRESSION_RATIO, compression_ratio);
 
-        txn.put(
-            &Self::select_db(db, txn)?,
-            &disk_key.to_be_bytes(),
-            &compressed_value,
-            SlasherDB::<E>::write_flags(),
-        )?;
-        Ok(())
+        Ok(compressed_value)
     }
 }
 
@@ -296,11 +314,8 @@ impl TargetArrayChunk for MinTargetChunk {
         start_epoch / chunk_size * chunk_size - 1
     }
 
-    fn select_db<'txn, E: EthSpec>(
-        db: &SlasherDB<E>,
-        txn: &'txn RwTransaction<'txn>,
-    ) -> Result<mdbx::Database<'txn>, Error> {
-        db.min_targets_db(txn)
+    fn select_db<E: EthSpec>(db: &SlasherDB<E>) -> Database {
+        db.min_targets_db()
     }
 }
 
@@ -398,11 +413,8 @@ impl TargetArrayChunk for MaxTargetChunk {
         (start_epoch / chunk_size + 1) * chunk_size
     }
 
-    fn select_db<'txn, E: EthSpec>(
-        db: &SlasherDB<E>,
-        txn: &'txn RwTransaction<'txn>,
-    ) -> Result<mdbx::Database<'txn>, Error> {
-        db.max_targets_db(txn)
+    fn select_db<E: EthSpec>(db: &SlasherDB<E>) -> Database {
+        db.max_targets_db()
     }
 }
 
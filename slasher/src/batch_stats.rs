@@ -1,3 +1,6 @@
+use std::time::Duration;
+use types::Epoch;
+
 #[derive(Debug)]
 pub struct BatchStats {
     pub block_stats: BlockStats,
@@ -14,3 +17,12 @@ pub struct BlockStats {
 pub struct AttestationStats {
     pub num_processed: usize,
 }
+
+/// Summary of a completed batch update.
+#[derive(Debug, Clone, Copy)]
+pub struct BatchSummary {
+    pub current_epoch: Epoch,
+    pub time_taken: Duration,
+    pub num_attestations: usize,
+    pub num_blocks: usize,
+}
@@ -1,6 +1,7 @@
 use crate::Error;
 use serde_derive::{Deserialize, Serialize};
 use std::path::PathBuf;
+use strum::{Display, EnumString};
 use types::{Epoch, EthSpec, IndexedAttestation};
 
 pub const DEFAULT_CHUNK_SIZE: usize = 16;
@@ -11,6 +12,9 @@ pub const DEFAULT_SLOT_OFFSET: f64 = 10.5;
 pub const DEFAULT_MAX_DB_SIZE: usize = 256 * 1024; // 256 GiB
 pub const DEFAULT_ATTESTATION_ROOT_CACHE_SIZE: usize = 100_000;
 pub const DEFAULT_BROADCAST: bool = false;
+pub const DEFAULT_RECHUNK: bool = false;
+pub const DEFAULT_BACKEND: DatabaseBackend = DatabaseBackend::Mdbx;
+pub const DEFAULT_MIGRATE_BACKEND: bool = false;
 
 pub const MAX_HISTORY_LENGTH: usize = 1 << 16;
 pub const MDBX_GROWTH_STEP: isize = 256 * (1 << 20); // 256 MiB
@@ -32,6 +36,19 @@ pub struct Config {
     pub attestation_root_cache_size: usize,
     /// Whether to broadcast slashings found to the network.
     pub broadcast: bool,
+    /// Whether to re-chunk an existing database whose chunk sizes or history length differ.
+    pub rechunk: bool,
+    /// Database backend to use.
+    pub backend: DatabaseBackend,
+    /// Whether to migrate an existing database created with another backend to `backend`.
+    pub migrate_backend: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum DatabaseBackend {
+    Mdbx,
+    Lmdb,
 }
 
 /// Immutable configuration parameters which are stored on disk and checked for consistency.
@@ -54,6 +71,9 @@ impl Config {
             max_db_size_mbs: DEFAULT_MAX_DB_SIZE,
             attestation_root_cache_size: DEFAULT_ATTESTATION_ROOT_CACHE_SIZE,
             broadcast: DEFAULT_BROADCAST,
+            rechunk: DEFAULT_RECHUNK,
+            backend: DEFAULT_BACKEND,
+            migrate_backend: DEFAULT_MIGRATE_BACKEND,
         }
     }
 
@@ -1,19 +1,23 @@
-use crate::config::MDBX_GROWTH_STEP;
+pub mod interface;
+mod lmdb_impl;
+mod mdbx_impl;
+
+use crate::config::{DatabaseBackend, DiskConfig};
+use crate::rechunk::{RECHUNK_ATTESTERS_DB, RECHUNK_MAX_TARGETS_DB, RECHUNK_MIN_TARGETS_DB};
 use crate::{
     metrics, utils::TxnMapFull, AttesterRecord, AttesterSlashingStatus, CompactAttesterRecord,
     Config, Environment, Error, ProposerSlashingStatus, RwTransaction,
 };
 use byteorder::{BigEndian, ByteOrder};
+use interface::{database_exists, database_files, Database};
 use lru::LruCache;
-use mdbx::{Database, DatabaseFlags, Geometry, WriteFlags};
 use parking_lot::Mutex;
 use serde::de::DeserializeOwned;
 use slog::{info, Logger};
 use ssz::{Decode, Encode};
 use std::borrow::{Borrow, Cow};
 use std::marker::PhantomData;
-use std::ops::Range;
-use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tree_hash::TreeHash;
 use types::{
@@ -24,35 +28,50 @@ use types::{
 pub const CURRENT_SCHEMA_VERSION: u64 = 3;
 
 /// Metadata about the slashing database itself.
-const METADATA_DB: &str = "metadata";
+const METADATA_DB: Database = Database::new("metadata");
 /// Map from `(target_epoch, validator_index)` to `CompactAttesterRecord`.
-const ATTESTERS_DB: &str = "attesters";
+const ATTESTERS_DB: Database = Database::new("attesters");
 /// Companion database for the attesters DB mapping `validator_index` to largest `target_epoch`
 /// stored for that validator in the attesters DB.
 ///
 /// Used to implement wrap-around semantics for target epochs modulo the history length.
-const ATTESTERS_MAX_TARGETS_DB: &str = "attesters_max_targets";
+const ATTESTERS_MAX_TARGETS_DB: Database = Database::new("attesters_max_targets");
 /// Map from `indexed_attestation_id` to `IndexedAttestation`.
-const INDEXED_ATTESTATION_DB: &str = "indexed_attestations";
+const INDEXED_ATTESTATION_DB: Database = Database::new("indexed_attestations");
 /// Map from `(target_epoch, indexed_attestation_hash)` to `indexed_attestation_id`.
-const INDEXED_ATTESTATION_ID_DB: &str = "indexed_attestation_ids";
+const INDEXED_ATTESTATION_ID_DB: Database = Database::new("indexed_attestation_ids");
 /// Table of minimum targets for every source epoch within range.
-const MIN_TARGETS_DB: &str = "min_targets";
+const MIN_TARGETS_DB: Database = Database::new("min_targets");
 /// Table of maximum targets for every source epoch within range.
-const MAX_TARGETS_DB: &str = "max_targets";
+const MAX_TARGETS_DB: Database = Database::new("max_targets");
 /// Map from `validator_index` to the `current_epoch` for that validator.
 ///
 /// Used to implement wrap-around semantics for the min and max target arrays.
-const CURRENT_EPOCHS_DB: &str = "current_epochs";
+const CURRENT_EPOCHS_DB: Database = Database::new("current_epochs");
 /// Map from `(slot, validator_index)` to `SignedBeaconBlockHeader`.
-const PROPOSERS_DB: &str = "proposers";
-
-/// The number of DBs for MDBX to use (equal to the number of DBs defined above).
-const MAX_NUM_DBS: usize = 9;
-
-/// Filename for the legacy (LMDB) database file, so that it may be deleted.
-const LEGACY_DB_FILENAME: &str = "data.mdb";
-const LEGACY_DB_LOCK_FILENAME: &str = "lock.mdb";
+const PROPOSERS_DB: Database = Database::new("proposers");
+
+/// All of the DBs defined above, plus the temporary DBs used while re-chunking.
+const DATABASES: &[Database] = &[
+    METADATA_DB,
+    ATTESTERS_DB,
+    ATTESTERS_MAX_TARGETS_DB,
+    INDEXED_ATTESTATION_DB,
+    INDEXED_ATTESTATION_ID_DB,
+    MIN_TARGETS_DB,
+    MAX_TARGETS_DB,
+    CURRENT_EPOCHS_DB,
+    PROPOSERS_DB,
+    RECHUNK_MIN_TARGETS_DB,
+    RECHUNK_MAX_TARGETS_DB,
+    RECHUNK_ATTESTERS_DB,
+];
+
+/// The schema version at which the slasher database was switched from LMDB to MDBX.
+///
+/// LMDB databases with an earlier schema version are left over from before the switch, and are
+/// deleted when the MDBX backend is used.
+const MDBX_SCHEMA_VERSION: u64 = 3;
 
 /// Constant key under which the schema version is stored in the `metadata_db`.
 const METADATA_VERSION_KEY: &[u8] = &[0];
@@ -154,6 +173,14 @@ impl CurrentEpochKey {
             validator_index: validator_index.to_be_bytes(),
         }
     }
+
+    pub fn parse(data: Cow<[u8]>) -> Result<u64, Error> {
+        if data.len() == CURRENT_EPOCH_KEY_SIZE {
+            Ok(BigEndian::read_u64(&data))
+        } else {
+            Err(Error::CurrentEpochKeyCorrupt { length: data.len() })
+        }
+    }
 }
 
 impl AsRef<[u8]> for CurrentEpochKey {
@@ -249,36 +276,18 @@ fn ssz_decode<T: Decode>(bytes: Cow<[u8]>) -> Result<T, Error> {
 
 impl<E: EthSpec> SlasherDB<E> {
     pub fn open(config: Arc<Config>, log: Logger) -> Result<Self, Error> {
-        // Delete any legacy LMDB database.
-        Self::delete_legacy_file(&config.database_path, LEGACY_DB_FILENAME, &log)?;
-        Self::delete_legacy_file(&config.database_path, LEGACY_DB_LOCK_FILENAME, &log)?;
-
         std::fs::create_dir_all(&config.database_path)?;
 
-        let env = Environment::new()
-            .set_max_dbs(MAX_NUM_DBS)
-            .set_geometry(Self::geometry(&config))
-            .open_with_permissions(&config.database_path, 0o600)?;
-
-        let txn = env.begin_rw_txn()?;
-        txn.create_db(Some(INDEXED_ATTESTATION_DB), Self::db_flags())?;
-        txn.create_db(Some(INDEXED_ATTESTATION_ID_DB), Self::db_flags())?;
-        txn.create_db(Some(ATTESTERS_DB), Self::db_flags())?;
-        txn.create_db(Some(ATTESTERS_MAX_TARGETS_DB), Self::db_flags())?;
-        txn.create_db(Some(MIN_TARGETS_DB), Self::db_flags())?;
-        txn.create_db(Some(MAX_TARGETS_DB), Self::db_flags())?;
-        txn.create_db(Some(CURRENT_EPOCHS_DB), Self::db_flags())?;
-        txn.create_db(Some(PROPOSERS_DB), Self::db_flags())?;
-        txn.create_db(Some(METADATA_DB), Self::db_flags())?;
-        txn.commit()?;
+        Self::check_backend(&config, &log)?;
+
+        let env = Environment::new(&config, DATABASES)?;
 
         #[cfg(windows)]
         {
             use filesystem::restrict_file_permissions;
-            let data = config.database_path.join("mdbx.dat");
-            let lock = config.database_path.join("mdbx.lck");
-            restrict_file_permissions(data).map_err(Error::DatabasePermissionsError)?;
-            restrict_file_permissions(lock).map_err(Error::DatabasePermissionsError)?;
+            for file in database_files(config.backend, &config.database_path) {
+                restrict_file_permissions(file).map_err(Error::DatabasePermissionsError)?;
+            }
         }
 
         let attestation_root_cache = Mutex::new(LruCache::new(config.attestation_root_cache_size));
@@ -293,116 +302,175 @@ impl<E: EthSpec> SlasherDB<E> {
         db = db.migrate()?;
 
         let mut txn = db.begin_rw_txn()?;
-        if let Some(on_disk_config) = db.load_config(&mut txn)? {
+        if let Some(on_disk_config) = db.load_config::<DiskConfig>(&mut txn)? {
             let current_disk_config = db.config.disk_config();
             if current_disk_config != on_disk_config {
-                return Err(Error::ConfigIncompatible {
-                    on_disk_config,
-                    config: current_disk_config,
-                });
+                if !db.config.rechunk {
+                    return Err(Error::ConfigIncompatible {
+                        on_disk_config,
+                        config: current_disk_config,
+                    });
+                }
+                info!(
+                    log,
+                    "Re-chunking slasher database";
+                    "on_disk_config" => ?on_disk_config,
+                    "config" => ?current_disk_config,
+                );
+                db.rechunk(&on_disk_config, &mut txn)?;
             }
         }
+        db.store_schema_version(&mut txn)?;
+        db.store_config(&db.config, &mut txn)?;
         txn.commit()?;
 
         Ok(db)
     }
 
-    fn delete_legacy_file(slasher_dir: &Path, filename: &str, log: &Logger) -> Result<(), Error> {
-        let path = slasher_dir.join(filename);
+    /// Check that any existing database was created with the configured backend.
+    ///
+    /// A database created with the other backend is migrated to the configured backend if
+    /// `config.migrate_backend` is set, and is an error otherwise. Legacy LMDB databases from
+    /// before the switch to MDBX are deleted.
+    fn check_backend(config: &Config, log: &Logger) -> Result<(), Error> {
+        let path = &config.database_path;
+        let on_disk_backend = match config.backend {
+            DatabaseBackend::Mdbx => DatabaseBackend::Lmdb,
+            DatabaseBackend::Lmdb => DatabaseBackend::Mdbx,
+        };
+        if database_exists(config.backend, path) || !database_exists(on_disk_backend, path) {
+            return Ok(());
+        }
 
-        if path.is_file() {
-            info!(
-                log,
-                "Deleting legacy slasher DB";
-                "file" => ?path.display(),
-            );
-            std::fs::remove_file(&path)?;
+        let on_disk_config = Config {
+            backend: on_disk_backend,
+            ..config.clone()
+        };
+        let on_disk_env = Environment::new(&on_disk_config, DATABASES)?;
+        let mut on_disk_txn = on_disk_env.begin_rw_txn()?;
+
+        let schema_version: Option<u64> = on_disk_txn
+            .get(METADATA_DB, METADATA_VERSION_KEY)?
+            .map(bincode_deserialize)
+            .transpose()?;
+        if on_disk_backend == DatabaseBackend::Lmdb
+            && schema_version.map_or(true, |version| version < MDBX_SCHEMA_VERSION)
+        {
+            drop(on_disk_txn);
+            drop(on_disk_env);
+            return Self::delete_files(database_files(on_disk_backend, path), log);
         }
-        Ok(())
-    }
 
-    fn open_db<'a>(&self, txn: &'a RwTransaction<'a>, name: &str) -> Result<Database<'a>, Error> {
-        Ok(txn.open_db(Some(name))?)
-    }
+        if !config.migrate_backend {
+            return Err(Error::DatabaseBackendMismatch {
+                on_disk_backend,
+                backend: config.backend,
+            });
+        }
 
-    pub fn indexed_attestation_db<'a>(
-        &self,
-        txn: &'a RwTransaction<'a>,
-    ) -> Result<Database<'a>, Error> {
-        self.open_db(txn, INDEXED_ATTESTATION_DB)
+        info!(
+            log,
+            "Migrating slasher database";
+            "from" => %on_disk_backend,
+            "to" => %config.backend,
+        );
+        let result = Self::copy_databases(&mut on_disk_txn, config);
+        drop(on_disk_txn);
+        drop(on_disk_env);
+
+        // Only delete the original database once it has been copied, and don't leave behind an
+        // incomplete copy which would be opened in its place next time.
+        let obsolete_backend = if result.is_ok() {
+            on_disk_backend
+        } else {
+            config.backend
+        };
+        Self::delete_files(database_files(obsolete_backend, path), log)?;
+        result
     }
 
-    pub fn indexed_attestation_id_db<'a>(
-        &self,
-        txn: &'a RwTransaction<'a>,
-    ) -> Result<Database<'a>, Error> {
-        self.open_db(txn, INDEXED_ATTESTATION_ID_DB)
+    /// Copy the contents of every DB read by `from_txn` to a new database using `config.backend`.
+    fn copy_databases(from_txn: &mut RwTransaction<'_>, config: &Config) -> Result<(), Error> {
+        let env = Environment::new(config, DATABASES)?;
+        let mut txn = env.begin_rw_txn()?;
+
+        for &db in DATABASES {
+            let mut cursor = from_txn.cursor(db)?;
+            let mut entry = cursor.first_entry()?;
+            while let Some((key, value)) = entry {
+                txn.put(db, key, value)?;
+                entry = cursor.next_entry()?;
+            }
+        }
+
+        txn.commit()
     }
 
-    pub fn attesters_db<'a>(&self, txn: &'a RwTransaction<'a>) -> Result<Database<'a>, Error> {
-        self.open_db(txn, ATTESTERS_DB)
+    fn delete_files(files: Vec<PathBuf>, log: &Logger) -> Result<(), Error> {
+        for path in files {
+            if path.is_file() {
+                info!(
+                    log,
+                    "Deleting slasher DB file";
+                    "file" => ?path.display(),
+                );
+                std::fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
     }
 
-    pub fn attesters_max_targets_db<'a>(
-        &self,
-        txn: &'a RwTransaction<'a>,
-    ) -> Result<Database<'a>, Error> {
-        self.open_db(txn, ATTESTERS_MAX_TARGETS_DB)
+    pub fn indexed_attestation_db(&self) -> Database {
+        INDEXED_ATTESTATION_DB
     }
 
-    pub fn min_targets_db<'a>(&self, txn: &'a RwTransaction<'a>) -> Result<Database<'a>, Error> {
-        self.open_db(txn, MIN_TARGETS_DB)
+    pub fn indexed_attestation_id_db(&self) -> Database {
+        INDEXED_ATTESTATION_ID_DB
     }
 
-    pub fn max_targets_db<'a>(&self, txn: &'a RwTransaction<'a>) -> Result<Database<'a>, Error> {
-        self.open_db(txn, MAX_TARGETS_DB)
+    pub fn attesters_db(&self) -> Database {
+        ATTESTERS_DB
     }
 
-    pub fn current_epochs_db<'a>(&self, txn: &'a RwTransaction<'a>) -> Result<Database<'a>, Error> {
-        self.open_db(txn, CURRENT_EPOCHS_DB)
+    pub fn attesters_max_targets_db(&self) -> Database {
+        ATTESTERS_MAX_TARGETS_DB
     }
 
-    pub fn proposers_db<'a>(&self, txn: &'a RwTransaction<'a>) -> Result<Database<'a>, Error> {
-        self.open_db(txn, PROPOSERS_DB)
+    pub fn min_targets_db(&self) -> Database {
+        MIN_TARGETS_DB
     }
 
-    pub fn metadata_db<'a>(&self, txn: &'a RwTransaction<'a>) -> Result<Database<'a>, Error> {
-        self.open_db(txn, METADATA_DB)
+    pub fn max_targets_db(&self) -> Database {
+        MAX_TARGETS_DB
     }
 
-    pub fn db_flags() -> DatabaseFlags {
-        DatabaseFlags::default()
+    pub fn current_epochs_db(&self) -> Database {
+        CURRENT_EPOCHS_DB
     }
 
-    pub fn write_flags() -> WriteFlags {
-        WriteFlags::default()
+    pub fn proposers_db(&self) -> Database {
+        PROPOSERS_DB
     }
 
-    pub fn begin_rw_txn(&self) -> Result<RwTransaction<'_>, Error> {
-        Ok(self.env.begin_rw_txn()?)
+    pub fn metadata_db(&self) -> Database {
+        METADATA_DB
     }
 
-    pub fn geometry(config: &Config) -> Geometry<Range<usize>> {
-        Geometry {
-            size: Some(0..config.max_db_size_mbs * MEGABYTE),
-            growth_step: Some(MDBX_GROWTH_STEP),
-            shrink_threshold: None,
-            page_size: None,
-        }
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction<'_>, Error> {
+        self.env.begin_rw_txn()
     }
 
     pub fn load_schema_version(&self, txn: &mut RwTransaction<'_>) -> Result<Option<u64>, Error> {
-        txn.get(&self.metadata_db(txn)?, METADATA_VERSION_KEY)?
+        txn.get(self.metadata_db(), METADATA_VERSION_KEY)?
             .map(bincode_deserialize)
             .transpose()
     }
 
     pub fn store_schema_version(&self, txn: &mut RwTransaction<'_>) -> Result<(), Error> {
         txn.put(
-            &self.metadata_db(txn)?,
+            self.metadata_db(),
             &METADATA_VERSION_KEY,
             &bincode::serialize(&CURRENT_SCHEMA_VERSION)?,
-            Self::write_flags(),
         )?;
         Ok(())
     }
@@ -415,17 +483,16 @@ impl<E: EthSpec> SlasherDB<E> {
         &self,
         txn: &mut RwTransaction<'_>,
     ) -> Result<Option<T>, Error> {
-        txn.get(&self.metadata_db(txn)?, METADATA_CONFIG_KEY)?
+        txn.get(self.metadata_db(), METADATA_CONFIG_KEY)?
             .map(bincode_deserialize)
             .transpose()
     }
 
     pub fn store_config(&self, config: &Config, txn: &mut RwTransaction<'_>) -> Result<(), Error> {
         txn.put(
-            &self.metadata_db(txn)?,
+            self.metadata_db(),
             &METADATA_CONFIG_KEY,
-            &bincode::serialize(config)?,
-            Self::write_flags(),
+            &bincode::serialize(&config.disk_config())?,
         )?;
         Ok(())
     }
@@ -436,7 +503,7 @@ impl<E: EthSpec> SlasherDB<E> {
         txn: &mut RwTransaction<'_>,
     ) -> Result<Option<Epoch>, Error> {
         txn.get(
-            &self.attesters_max_targets_db(txn)?,
+            self.attesters_max_targets_db(),
             CurrentEpochKey::new(validator_index).as_ref(),
         )?
         .map(ssz_decode)
@@ -466,19 +533,17 @@ impl<E: EthSpec> SlasherDB<E> {
             );
             for target_epoch in (start_epoch..max_target.as_u64()).map(Epoch::new) {
                 txn.put(
-                    &self.attesters_db(txn)?,
+                    self.attesters_db(),
                     &AttesterKey::new(validator_index, target_epoch, &self.config),
                     &CompactAttesterRecord::null().as_bytes(),
-                    Self::write_flags(),
                 )?;
             }
         }
 
         txn.put(
-            &self.attesters_max_targets_db(txn)?,
+            self.attesters_max_targets_db(),
             &CurrentEpochKey::new(validator_index),
             &max_target.as_ssz_bytes(),
-            Self::write_flags(),
         )?;
         Ok(())
     }
@@ -489,7 +554,7 @@ impl<E: EthSpec> SlasherDB<E> {
         txn: &mut RwTransaction<'_>,
     ) -> Result<Option<Epoch>, Error> {
         txn.get(
-            &self.current_epochs_db(txn)?,
+            self.current_epochs_db(),
             CurrentEpochKey::new(validator_index).as_ref(),
         )?
         .map(ssz_decode)
@@ -503,10 +568,9 @@ impl<E: EthSpec> SlasherDB<E> {
         txn: &mut RwTransaction<'_>,
     ) -> Result<(), Error> {
         txn.put(
-            &self.current_epochs_db(txn)?,
+            self.current_epochs_db(),
             &CurrentEpochKey::new(validator_index),
             &current_epoch.as_ssz_bytes(),
-            Self::write_flags(),
         )?;
         Ok(())
     }
@@ -516,7 +580,7 @@ impl<E: EthSpec> SlasherDB<E> {
         txn: &mut RwTransaction<'_>,
         key: &IndexedAttestationIdKey,
     ) -> Result<Option<u64>, Error> {
-        txn.get(&self.indexed_attestation_id_db(txn)?, key.as_ref())?
+        txn.get(self.indexed_attestation_id_db(), key.as_ref())?
             .map(IndexedAttestationId::parse)
             .transpose()
     }
@@ -527,12 +591,7 @@ impl<E: EthSpec> SlasherDB<E> {
         key: &IndexedAttestationIdKey,
         value: IndexedAttestationId,
     ) -> Result<(), Error> {
-        txn.put(
-            &self.indexed_attestation_id_db(txn)?,
-            key,
-            &value,
-            Self::write_flags(),
-        )?;
+        txn.put(self.indexed_attestation_id_db(), key, &value)?;
         Ok(())
     }
 
@@ -556,9 +615,9 @@ impl<E: EthSpec> SlasherDB<E> {
         }
 
         // Store the new indexed attestation at the end of the current table.
-        let mut cursor = txn.cursor(&self.indexed_attestation_db(txn)?)?;
+        let mut cursor = txn.cursor(self.indexed_attestation_db())?;
 
-        let indexed_att_id = match cursor.last::<_, ()>()? {
+        let indexed_att_id = match cursor.last_entry()? {
             // First ID is 1 so that 0 can be used to represent `null` in `CompactAttesterRecord`.
             None => 1,
             Some((key_bytes, _)) => IndexedAttestationId::parse(key_bytes)? + 1,
@@ -567,7 +626,7 @@ impl<E: EthSpec> SlasherDB<E> {
         let attestation_key = IndexedAttestationId::new(indexed_att_id);
         let data = indexed_attestation.as_ssz_bytes();
 
-        cursor.put(attestation_key.as_ref(), &data, Self::write_flags())?;
+        cursor.put(attestation_key, &data)?;
         drop(cursor);
 
         // Update the (epoch, hash) to ID mapping.
@@ -583,7 +642,7 @@ impl<E: EthSpec> SlasherDB<E> {
     ) -> Result<IndexedAttestation<E>, Error> {
         let bytes = txn
             .get(
-                &self.indexed_attestation_db(txn)?,
+                self.indexed_attestation_db(),
                 indexed_attestation_id.as_ref(),
             )?
             .ok_or(Error::MissingIndexedAttestation {
@@ -685,10 +744,9 @@ impl<E: EthSpec> SlasherDB<E> {
             self.update_attester_max_target(validator_index, prev_max_target, target_epoch, txn)?;
 
             txn.put(
-                &self.attesters_db(txn)?,
+                self.attesters_db(),
                 &AttesterKey::new(validator_index, target_epoch, &self.config),
                 &indexed_attestation_id,
-                Self::write_flags(),
             )?;
 
             Ok(AttesterSlashingStatus::NotSlashable)
@@ -725,7 +783,7 @@ impl<E: EthSpec> SlasherDB<E> {
 
         let attester_key = AttesterKey::new(validator_index, target, &self.config);
         Ok(txn
-            .get(&self.attesters_db(txn)?, attester_key.as_ref())?
+            .get(self.attesters_db(), attester_key.as_ref())?
             .map(CompactAttesterRecord::parse)
             .transpose()?
             .filter(|record| !record.is_null()))
@@ -738,7 +796,7 @@ impl<E: EthSpec> SlasherDB<E> {
         slot: Slot,
     ) -> Result<Option<SignedBeaconBlockHeader>, Error> {
         let proposer_key = ProposerKey::new(proposer_index, slot);
-        txn.get(&self.proposers_db(txn)?, proposer_key.as_ref())?
+        txn.get(self.proposers_db(), proposer_key.as_ref())?
             .map(ssz_decode)
             .transpose()
     }
@@ -764,10 +822,9 @@ impl<E: EthSpec> SlasherDB<E> {
             }
         } else {
             txn.put(
-                &self.proposers_db(txn)?,
+                self.proposers_db(),
                 &ProposerKey::new(proposer_index, slot),
                 &block_header.as_ssz_bytes(),
-                Self::write_flags(),
             )?;
             Ok(ProposerSlashingStatus::NotSlashable)
         }
@@ -804,22 +861,22 @@ impl<E: EthSpec> SlasherDB<E> {
             .saturating_sub(self.config.history_length)
             .start_slot(E::slots_per_epoch());
 
-        let mut cursor = txn.cursor(&self.proposers_db(txn)?)?;
+        let mut cursor = txn.cursor(self.proposers_db())?;
 
         // Position cursor at first key, bailing out if the database is empty.
-        if cursor.first::<(), ()>()?.is_none() {
+        if cursor.first_entry()?.is_none() {
             return Ok(());
         }
 
         loop {
-            let (key_bytes, ()) = cursor.get_current()?.ok_or(Error::MissingProposerKey)?;
+            let (key_bytes, _) = cursor.current_entry()?.ok_or(Error::MissingProposerKey)?;
 
             let (slot, _) = ProposerKey::parse(key_bytes)?;
             if slot < min_slot {
-                cursor.del(Self::write_flags())?;
+                cursor.delete_current()?;
 
                 // End the loop if there is no next entry.
-                if cursor.next::<(), ()>()?.is_none() {
+                if cursor.next_entry()?.is_none() {
                     break;
                 }
             } else {
@@ -842,16 +899,16 @@ impl<E: EthSpec> SlasherDB<E> {
         // Collect indexed attestation IDs to delete.
         let mut indexed_attestation_ids = vec![];
 
-        let mut cursor = txn.cursor(&self.indexed_attestation_id_db(txn)?)?;
+        let mut cursor = txn.cursor(self.indexed_attestation_id_db())?;
 
         // Position cursor at first key, bailing out if the database is empty.
-        if cursor.first::<(), ()>()?.is_none() {
+        if cursor.first_entry()?.is_none() {
             return Ok(());
         }
 
         loop {
             let (key_bytes, value) = cursor
-                .get_current()?
+                .current_entry()?
                 .ok_or(Error::MissingIndexedAttestationIdKey)?;
 
             let (target_epoch, _) = IndexedAttestationIdKey::parse(key_bytes)?;
@@ -861,9 +918,9 @@ impl<E: EthSpec> SlasherDB<E> {
                     IndexedAttestationId::parse(value)?,
                 ));
 
-                cursor.del(Self::write_flags())?;
+                cursor.delete_current()?;
 
-                if cursor.next::<(), ()>()?.is_none() {
+                if cursor.next_entry()?.is_none() {
                     break;
                 }
             } else {
@@ -874,9 +931,9 @@ impl<E: EthSpec> SlasherDB<E> {
 
         // Delete the indexed attestations.
         // Optimisation potential: use a cursor here.
-        let indexed_attestation_db = self.indexed_attestation_db(txn)?;
+        let indexed_attestation_db = self.indexed_attestation_db();
         for indexed_attestation_id in &indexed_attestation_ids {
-            txn.del(&indexed_attestation_db, indexed_attestation_id, None)?;
+            txn.del(indexed_attestation_db, indexed_attestation_id)?;
         }
         self.delete_attestation_data_roots(indexed_attestation_ids);
 
@@ -0,0 +1,183 @@
+//! Common interface to the database backends supported by the slasher.
+//!
+//! Each type dispatches to the backend selected by `Config::backend`, so that the rest of the
+//! slasher doesn't depend on the backend in use.
+use crate::config::{Config, DatabaseBackend};
+use crate::database::{lmdb_impl, mdbx_impl};
+use crate::Error;
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+pub type Key<'a> = Cow<'a, [u8]>;
+pub type Value<'a> = Cow<'a, [u8]>;
+
+/// A named table within the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Database {
+    name: &'static str,
+}
+
+impl Database {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[derive(Debug)]
+pub enum Environment {
+    Mdbx(mdbx_impl::Environment),
+    Lmdb(lmdb_impl::Environment),
+}
+
+#[derive(Debug)]
+pub enum RwTransaction<'env> {
+    Mdbx(mdbx_impl::RwTransaction<'env>),
+    Lmdb(lmdb_impl::RwTransaction<'env>),
+}
+
+#[derive(Debug)]
+pub enum Cursor<'txn> {
+    Mdbx(mdbx_impl::Cursor<'txn>),
+    Lmdb(lmdb_impl::Cursor<'txn>),
+}
+
+/// Return the paths of the files which hold a database created with `backend` in `path`.
+pub fn database_files(backend: DatabaseBackend, path: &Path) -> Vec<PathBuf> {
+    let filenames = match backend {
+        DatabaseBackend::Mdbx => mdbx_impl::FILENAMES,
+        DatabaseBackend::Lmdb => lmdb_impl::FILENAMES,
+    };
+    filenames
+        .iter()
+        .map(|filename| path.join(filename))
+        .collect()
+}
+
+/// Return `true` if a database created with `backend` exists in `path`.
+///
+/// The data file is checked, as the lock file may be left behind on its own.
+pub fn database_exists(backend: DatabaseBackend, path: &Path) -> bool {
+    database_files(backend, path)
+        .first()
+        .map_or(false, |data_file| data_file.is_file())
+}
+
+impl Environment {
+    /// Open the database in `config.database_path` using `config.backend`, creating it and any of
+    /// the `databases` which don't exist yet.
+    pub fn new(config: &Config, databases: &[Database]) -> Result<Self, Error> {
+        match config.backend {
+            DatabaseBackend::Mdbx => {
+                mdbx_impl::Environment::new(config, databases).map(Environment::Mdbx)
+            }
+            DatabaseBackend::Lmdb => {
+                lmdb_impl::Environment::new(config, databases).map(Environment::Lmdb)
+            }
+        }
+    }
+
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction<'_>, Error> {
+        match self {
+            Self::Mdbx(env) => env.begin_rw_txn().map(RwTransaction::Mdbx),
+            Self::Lmdb(env) => env.begin_rw_txn().map(RwTransaction::Lmdb),
+        }
+    }
+}
+
+impl<'env> RwTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &self,
+        db: Database,
+        key: &K,
+    ) -> Result<Option<Value<'_>>, Error> {
+        match self {
+            Self::Mdbx(txn) => txn.get(db, key),
+            Self::Lmdb(txn) => txn.get(db, key),
+        }
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        db: Database,
+        key: K,
+        value: V,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Mdbx(txn) => txn.put(db, key, value),
+            Self::Lmdb(txn) => txn.put(db, key, value),
+        }
+    }
+
+    /// Delete `key` from `db`, if it exists.
+    pub fn del<K: AsRef<[u8]>>(&mut self, db: Database, key: K) -> Result<(), Error> {
+        match self {
+            Self::Mdbx(txn) => txn.del(db, key),
+            Self::Lmdb(txn) => txn.del(db, key),
+        }
+    }
+
+    pub fn cursor(&mut self, db: Database) -> Result<Cursor<'_>, Error> {
+        match self {
+            Self::Mdbx(txn) => txn.cursor(db).map(Cursor::Mdbx),
+            Self::Lmdb(txn) => txn.cursor(db).map(Cursor::Lmdb),
+        }
+    }
+
+    pub fn commit(self) -> Result<(), Error> {
+        match self {
+            Self::Mdbx(txn) => txn.commit(),
+            Self::Lmdb(txn) => txn.commit(),
+        }
+    }
+}
+
+impl<'txn> Cursor<'txn> {
+    /// Move to the first entry, returning `None` if the database is empty.
+    pub fn first_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        match self {
+            Self::Mdbx(cursor) => cursor.first_entry(),
+            Self::Lmdb(cursor) => cursor.first_entry(),
+        }
+    }
+
+    /// Move to the last entry, returning `None` if the database is empty.
+    pub fn last_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        match self {
+            Self::Mdbx(cursor) => cursor.last_entry(),
+            Self::Lmdb(cursor) => cursor.last_entry(),
+        }
+    }
+
+    /// Move to the next entry, returning `None` if the cursor is at the last entry.
+    pub fn next_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        match self {
+            Self::Mdbx(cursor) => cursor.next_entry(),
+            Self::Lmdb(cursor) => cursor.next_entry(),
+        }
+    }
+
+    pub fn current_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        match self {
+            Self::Mdbx(cursor) => cursor.current_entry(),
+            Self::Lmdb(cursor) => cursor.current_entry(),
+        }
+    }
+
+    pub fn delete_current(&mut self) -> Result<(), Error> {
+        match self {
+            Self::Mdbx(cursor) => cursor.delete_current(),
+            Self::Lmdb(cursor) => cursor.delete_current(),
+        }
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), Error> {
+        match self {
+            Self::Mdbx(cursor) => cursor.put(key, value),
+            Self::Lmdb(cursor) => cursor.put(key, value),
+        }
+    }
+}
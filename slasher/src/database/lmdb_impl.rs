@@ -0,0 +1,147 @@
+use crate::database::interface::{Database, Key, Value};
+use crate::database::MEGABYTE;
+use crate::{Config, Error};
+use lmdb::{Cursor as _, DatabaseFlags, Transaction, WriteFlags};
+use lmdb_sys::{MDB_FIRST, MDB_GET_CURRENT, MDB_LAST, MDB_NEXT};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::os::raw::c_uint;
+
+/// The data file, followed by the lock file.
+pub const FILENAMES: &[&str] = &["data.mdb", "lock.mdb"];
+
+/// Named databases, which are opened once when the environment is opened as LMDB doesn't allow
+/// opening them within a write transaction.
+type Databases = HashMap<&'static str, lmdb::Database>;
+
+#[derive(Debug)]
+pub struct Environment {
+    env: lmdb::Environment,
+    databases: Databases,
+}
+
+#[derive(Debug)]
+pub struct RwTransaction<'env> {
+    txn: lmdb::RwTransaction<'env>,
+    databases: &'env Databases,
+}
+
+#[derive(Debug)]
+pub struct Cursor<'txn> {
+    cursor: lmdb::RwCursor<'txn>,
+}
+
+/// Map the `NotFound` error returned by LMDB for missing keys to `None`.
+fn optional<T>(result: Result<T, lmdb::Error>) -> Result<Option<T>, Error> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(lmdb::Error::NotFound) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl Environment {
+    pub fn new(config: &Config, databases: &[Database]) -> Result<Self, Error> {
+        let env = lmdb::Environment::new()
+            .set_max_dbs(databases.len() as u32)
+            .set_map_size(config.max_db_size_mbs * MEGABYTE)
+            .open_with_permissions(&config.database_path, 0o600)?;
+
+        let databases = databases
+            .iter()
+            .map(|db| {
+                let handle = env.create_db(Some(db.name()), DatabaseFlags::empty())?;
+                Ok((db.name(), handle))
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(Self { env, databases })
+    }
+
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction<'_>, Error> {
+        Ok(RwTransaction {
+            txn: self.env.begin_rw_txn()?,
+            databases: &self.databases,
+        })
+    }
+}
+
+impl<'env> RwTransaction<'env> {
+    fn db(&self, db: Database) -> Result<lmdb::Database, Error> {
+        self.databases
+            .get(db.name())
+            .copied()
+            .ok_or(Error::UnknownDatabase { name: db.name() })
+    }
+
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &self,
+        db: Database,
+        key: &K,
+    ) -> Result<Option<Value<'_>>, Error> {
+        let db = self.db(db)?;
+        Ok(optional(self.txn.get(db, &key.as_ref()))?.map(Cow::Borrowed))
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        db: Database,
+        key: K,
+        value: V,
+    ) -> Result<(), Error> {
+        let db = self.db(db)?;
+        self.txn.put(db, &key, &value, WriteFlags::empty())?;
+        Ok(())
+    }
+
+    pub fn del<K: AsRef<[u8]>>(&mut self, db: Database, key: K) -> Result<(), Error> {
+        let db = self.db(db)?;
+        optional(self.txn.del(db, &key, None))?;
+        Ok(())
+    }
+
+    pub fn cursor(&mut self, db: Database) -> Result<Cursor<'_>, Error> {
+        let db = self.db(db)?;
+        Ok(Cursor {
+            cursor: self.txn.open_rw_cursor(db)?,
+        })
+    }
+
+    pub fn commit(self) -> Result<(), Error> {
+        self.txn.commit()?;
+        Ok(())
+    }
+}
+
+impl<'txn> Cursor<'txn> {
+    fn get(&mut self, op: c_uint) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        Ok(optional(self.cursor.get(None, None, op))?
+            .and_then(|(key, value)| Some((Cow::Borrowed(key?), Cow::Borrowed(value)))))
+    }
+
+    pub fn first_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        self.get(MDB_FIRST)
+    }
+
+    pub fn last_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        self.get(MDB_LAST)
+    }
+
+    pub fn next_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        self.get(MDB_NEXT)
+    }
+
+    pub fn current_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        self.get(MDB_GET_CURRENT)
+    }
+
+    pub fn delete_current(&mut self) -> Result<(), Error> {
+        self.cursor.del(WriteFlags::empty())?;
+        Ok(())
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), Error> {
+        self.cursor.put(&key, &value, WriteFlags::empty())?;
+        Ok(())
+    }
+}
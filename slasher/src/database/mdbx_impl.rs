@@ -0,0 +1,130 @@
+use crate::config::MDBX_GROWTH_STEP;
+use crate::database::interface::{Database, Key, Value};
+use crate::database::MEGABYTE;
+use crate::{Config, Error};
+use mdbx::{DatabaseFlags, Geometry, NoWriteMap, WriteFlags, RW};
+use std::ops::Range;
+
+/// The data file, followed by the lock file.
+pub const FILENAMES: &[&str] = &["mdbx.dat", "mdbx.lck"];
+
+#[derive(Debug)]
+pub struct Environment {
+    env: mdbx::Environment<NoWriteMap>,
+}
+
+#[derive(Debug)]
+pub struct RwTransaction<'env> {
+    txn: mdbx::Transaction<'env, RW, NoWriteMap>,
+}
+
+#[derive(Debug)]
+pub struct Cursor<'txn> {
+    cursor: mdbx::Cursor<'txn, RW>,
+}
+
+impl Environment {
+    pub fn new(config: &Config, databases: &[Database]) -> Result<Self, Error> {
+        let env = mdbx::Environment::<NoWriteMap>::new()
+            .set_max_dbs(databases.len())
+            .set_geometry(Self::geometry(config))
+            .open_with_permissions(&config.database_path, 0o600)?;
+
+        let txn = env.begin_rw_txn()?;
+        for db in databases {
+            txn.create_db(Some(db.name()), DatabaseFlags::default())?;
+        }
+        txn.commit()?;
+
+        Ok(Self { env })
+    }
+
+    pub fn geometry(config: &Config) -> Geometry<Range<usize>> {
+        Geometry {
+            size: Some(0..config.max_db_size_mbs * MEGABYTE),
+            growth_step: Some(MDBX_GROWTH_STEP),
+            shrink_threshold: None,
+            page_size: None,
+        }
+    }
+
+    pub fn begin_rw_txn(&self) -> Result<RwTransaction<'_>, Error> {
+        Ok(RwTransaction {
+            txn: self.env.begin_rw_txn()?,
+        })
+    }
+}
+
+impl<'env> RwTransaction<'env> {
+    pub fn get<K: AsRef<[u8]> + ?Sized>(
+        &self,
+        db: Database,
+        key: &K,
+    ) -> Result<Option<Value<'_>>, Error> {
+        Ok(self
+            .txn
+            .get(&self.txn.open_db(Some(db.name()))?, key.as_ref())?)
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(
+        &mut self,
+        db: Database,
+        key: K,
+        value: V,
+    ) -> Result<(), Error> {
+        self.txn.put(
+            &self.txn.open_db(Some(db.name()))?,
+            key,
+            value,
+            WriteFlags::default(),
+        )?;
+        Ok(())
+    }
+
+    pub fn del<K: AsRef<[u8]>>(&mut self, db: Database, key: K) -> Result<(), Error> {
+        self.txn
+            .del(&self.txn.open_db(Some(db.name()))?, key, None)?;
+        Ok(())
+    }
+
+    pub fn cursor(&mut self, db: Database) -> Result<Cursor<'_>, Error> {
+        let db = self.txn.open_db(Some(db.name()))?;
+        Ok(Cursor {
+            cursor: self.txn.cursor(&db)?,
+        })
+    }
+
+    pub fn commit(self) -> Result<(), Error> {
+        self.txn.commit()?;
+        Ok(())
+    }
+}
+
+impl<'txn> Cursor<'txn> {
+    pub fn first_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        Ok(self.cursor.first()?)
+    }
+
+    pub fn last_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        Ok(self.cursor.last()?)
+    }
+
+    pub fn next_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        Ok(self.cursor.next()?)
+    }
+
+    pub fn current_entry(&mut self) -> Result<Option<(Key<'txn>, Value<'txn>)>, Error> {
+        Ok(self.cursor.get_current()?)
+    }
+
+    pub fn delete_current(&mut self) -> Result<(), Error> {
+        self.cursor.del(WriteFlags::default())?;
+        Ok(())
+    }
+
+    pub fn put<K: AsRef<[u8]>, V: AsRef<[u8]>>(&mut self, key: K, value: V) -> Result<(), Error> {
+        self.cursor
+            .put(key.as_ref(), value.as_ref(), WriteFlags::default())?;
+        Ok(())
+    }
+}
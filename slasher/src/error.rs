@@ -1,10 +1,11 @@
-use crate::config::{Config, DiskConfig};
+use crate::config::{Config, DatabaseBackend, DiskConfig};
 use std::io;
 use types::Epoch;
 
 #[derive(Debug)]
 pub enum Error {
-    DatabaseError(mdbx::Error),
+    DatabaseMdbxError(mdbx::Error),
+    DatabaseLmdbError(lmdb::Error),
     DatabaseIOError(io::Error),
     DatabasePermissionsError(filesystem::Error),
     SszDecodeError(ssz::DecodeError),
@@ -31,6 +32,13 @@ pub enum Error {
         config: DiskConfig,
     },
     ConfigMissing,
+    DatabaseBackendMismatch {
+        on_disk_backend: DatabaseBackend,
+        backend: DatabaseBackend,
+    },
+    UnknownDatabase {
+        name: &'static str,
+    },
     DistanceTooLarge,
     DistanceCalculationOverflow,
     /// Missing an attester record that we expected to exist.
@@ -47,6 +55,9 @@ pub enum Error {
     ProposerKeyCorrupt {
         length: usize,
     },
+    CurrentEpochKeyCorrupt {
+        length: usize,
+    },
     IndexedAttestationIdKeyCorrupt {
         length: usize,
     },
@@ -67,7 +78,16 @@ impl From<mdbx::Error> for Error {
     fn from(e: mdbx::Error) -> Self {
         match e {
             mdbx::Error::Other(os_error) => Error::from(io::Error::from_raw_os_error(os_error)),
-            _ => Error::DatabaseError(e),
+            _ => Error::DatabaseMdbxError(e),
+        }
+    }
+}
+
+impl From<lmdb::Error> for Error {
+    fn from(e: lmdb::Error) -> Self {
+        match e {
+            lmdb::Error::Other(os_error) => Error::from(io::Error::from_raw_os_error(os_error)),
+            _ => Error::DatabaseLmdbError(e),
         }
     }
 }
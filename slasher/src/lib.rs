@@ -10,6 +10,8 @@ mod database;
 mod error;
 pub mod metrics;
 mod migrate;
+mod rechunk;
+pub mod scheduler;
 mod slasher;
 pub mod test_utils;
 mod utils;
@@ -17,17 +19,17 @@ mod utils;
 pub use crate::slasher::Slasher;
 pub use attestation_queue::{AttestationBatch, AttestationQueue, SimpleBatch};
 pub use attester_record::{AttesterRecord, CompactAttesterRecord, IndexedAttesterRecord};
+pub use batch_stats::BatchSummary;
 pub use block_queue::BlockQueue;
-pub use config::Config;
-pub use database::{IndexedAttestationId, SlasherDB};
+pub use config::{Config, DatabaseBackend};
+pub use database::{
+    interface::{Environment, RwTransaction},
+    IndexedAttestationId, SlasherDB,
+};
 pub use error::Error;
 
 use types::{AttesterSlashing, EthSpec, IndexedAttestation, ProposerSlashing};
 
-/// LMDB-to-MDBX compatibility shims.
-pub type Environment = mdbx::Environment<mdbx::NoWriteMap>;
-pub type RwTransaction<'env> = mdbx::Transaction<'env, mdbx::RW, mdbx::NoWriteMap>;
-
 #[derive(Debug, PartialEq)]
 pub enum AttesterSlashingStatus<E: EthSpec> {
     NotSlashable,
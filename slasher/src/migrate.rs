@@ -10,8 +10,8 @@ impl<E: EthSpec> SlasherDB<E> {
 
         if let Some(schema_version) = schema_version {
             match (schema_version, CURRENT_SCHEMA_VERSION) {
-                // Schema v3 changed the underlying database from LMDB to MDBX. Unless the user did
-                // some manual hacking it should be impossible to read an MDBX schema version < 3.
+                // Schema v3 changed the underlying database from LMDB to MDBX. Older LMDB databases
+                // are deleted when using MDBX, but can't be read by the LMDB backend either.
                 (from, _) if from < 3 => Err(Error::IncompatibleSchemaVersion {
                     database_schema_version: schema_version,
                     software_schema_version: CURRENT_SCHEMA_VERSION,
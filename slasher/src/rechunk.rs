@@ -0,0 +1,218 @@
+//! Conversion of the slasher database to a new chunk size, validator chunk size or history length.
+use crate::array::{MaxTargetChunk, MinTargetChunk, TargetArrayChunk};
+use crate::config::{Config, DiskConfig};
+use crate::database::{interface::Database, AttesterKey, CurrentEpochKey};
+use crate::{Error, RwTransaction, SlasherDB};
+use ssz::Decode;
+use std::borrow::Cow;
+use std::collections::{btree_map::Entry, BTreeMap};
+use types::{Epoch, EthSpec};
+
+/// Temporary tables which hold the re-chunked data until it replaces the original tables.
+pub const RECHUNK_MIN_TARGETS_DB: Database = Database::new("rechunk_min_targets");
+pub const RECHUNK_MAX_TARGETS_DB: Database = Database::new("rechunk_max_targets");
+pub const RECHUNK_ATTESTERS_DB: Database = Database::new("rechunk_attesters");
+
+impl<E: EthSpec> SlasherDB<E> {
+    /// Convert data stored with `on_disk_config` to the chunk sizes and history length of the
+    /// current config.
+    ///
+    /// The min and max target arrays are re-chunked and the attester records re-keyed. History
+    /// older than the shorter of the two history lengths is dropped. The other tables don't
+    /// depend on these parameters and are left as they are.
+    pub fn rechunk(
+        &self,
+        on_disk_config: &DiskConfig,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        let old_config = Config {
+            chunk_size: on_disk_config.chunk_size,
+            validator_chunk_size: on_disk_config.validator_chunk_size,
+            history_length: on_disk_config.history_length,
+            ..(*self.config).clone()
+        };
+
+        let current_epochs = Self::load_validator_epochs(txn, self.current_epochs_db())?;
+        self.rechunk_array::<MinTargetChunk>(
+            &current_epochs,
+            &old_config,
+            RECHUNK_MIN_TARGETS_DB,
+            txn,
+        )?;
+        self.rechunk_array::<MaxTargetChunk>(
+            &current_epochs,
+            &old_config,
+            RECHUNK_MAX_TARGETS_DB,
+            txn,
+        )?;
+        self.rekey_attesters(&old_config, txn)
+    }
+
+    /// Number of epochs of history which are valid under both `old_config` and the current config.
+    fn rechunk_history_length(&self, old_config: &Config) -> u64 {
+        std::cmp::min(old_config.history_length, self.config.history_length) as u64
+    }
+
+    /// Load a table mapping `validator_index` to an epoch, in ascending order of validator index.
+    fn load_validator_epochs(
+        txn: &mut RwTransaction<'_>,
+        db: Database,
+    ) -> Result<Vec<(u64, Epoch)>, Error> {
+        let mut cursor = txn.cursor(db)?;
+        let mut validator_epochs = vec![];
+
+        let mut entry = cursor.first_entry()?;
+        while let Some((key_bytes, value_bytes)) = entry {
+            let validator_index = CurrentEpochKey::parse(key_bytes)?;
+            let epoch = Epoch::from_ssz_bytes(&value_bytes)?;
+            validator_epochs.push((validator_index, epoch));
+            entry = cursor.next_entry()?;
+        }
+
+        Ok(validator_epochs)
+    }
+
+    fn rechunk_array<T: TargetArrayChunk>(
+        &self,
+        current_epochs: &[(u64, Epoch)],
+        old_config: &Config,
+        temp_db: Database,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        let history_length = self.rechunk_history_length(old_config);
+
+        // Chunks in the old layout, keyed by `(validator_chunk_index, chunk_index)`. Chunks which
+        // don't exist on disk are cached as `None`.
+        let mut old_chunks: BTreeMap<(usize, usize), Option<T>> = BTreeMap::new();
+        // Chunks in the new layout for the current validator chunk, keyed by `chunk_index`.
+        let mut new_chunks: BTreeMap<usize, T> = BTreeMap::new();
+        let mut new_validator_chunk_index = None;
+
+        for &(validator_index, current_epoch) in current_epochs {
+            let validator_chunk_index = self.config.validator_chunk_index(validator_index);
+            if new_validator_chunk_index != Some(validator_chunk_index) {
+                if let Some(prev_validator_chunk_index) = new_validator_chunk_index {
+                    self.store_rechunked(
+                        std::mem::take(&mut new_chunks),
+                        prev_validator_chunk_index,
+                        temp_db,
+                        txn,
+                    )?;
+                }
+                new_validator_chunk_index = Some(validator_chunk_index);
+            }
+
+            // Validators are visited in ascending order, so old chunks for lower validator chunk
+            // indices are no longer required.
+            let old_validator_chunk_index = old_config.validator_chunk_index(validator_index);
+            old_chunks = old_chunks.split_off(&(old_validator_chunk_index, 0));
+
+            let start_epoch = (current_epoch.as_u64() + 1).saturating_sub(history_length);
+            for epoch in (start_epoch..=current_epoch.as_u64()).map(Epoch::new) {
+                let old_chunk_index = old_config.chunk_index(epoch);
+                let old_chunk = match old_chunks.entry((old_validator_chunk_index, old_chunk_index))
+                {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(T::load(
+                        self,
+                        txn,
+                        old_validator_chunk_index,
+                        old_chunk_index,
+                        old_config,
+                    )?),
+                };
+                let distance = match old_chunk {
+                    Some(chunk) => {
+                        chunk
+                            .chunk()
+                            .get_raw_distance(validator_index, epoch, old_config)?
+                    }
+                    None => continue,
+                };
+
+                if distance != T::neutral_element() {
+                    new_chunks
+                        .entry(self.config.chunk_index(epoch))
+                        .or_insert_with(|| T::empty(&self.config))
+                        .chunk()
+                        .set_raw_distance(validator_index, epoch, distance, &self.config)?;
+                }
+            }
+        }
+
+        if let Some(validator_chunk_index) = new_validator_chunk_index {
+            self.store_rechunked(new_chunks, validator_chunk_index, temp_db, txn)?;
+        }
+
+        Self::replace_db(txn, T::select_db(self), temp_db)
+    }
+
+    fn store_rechunked<T: TargetArrayChunk>(
+        &self,
+        chunks: BTreeMap<usize, T>,
+        validator_chunk_index: usize,
+        temp_db: Database,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        for (chunk_index, chunk) in chunks {
+            let disk_key = self.config.disk_key(validator_chunk_index, chunk_index);
+            txn.put(temp_db, &disk_key.to_be_bytes(), &chunk.compress()?)?;
+        }
+        Ok(())
+    }
+
+    /// Move the attester records within the history length to their keys under the new history
+    /// length.
+    fn rekey_attesters(
+        &self,
+        old_config: &Config,
+        txn: &mut RwTransaction<'_>,
+    ) -> Result<(), Error> {
+        let history_length = self.rechunk_history_length(old_config);
+        let max_targets = Self::load_validator_epochs(txn, self.attesters_max_targets_db())?;
+
+        for (validator_index, max_target) in max_targets {
+            let start_epoch = (max_target.as_u64() + 1).saturating_sub(history_length);
+            for target_epoch in (start_epoch..=max_target.as_u64()).map(Epoch::new) {
+                let record: Option<Cow<[u8]>> = txn.get(
+                    self.attesters_db(),
+                    AttesterKey::new(validator_index, target_epoch, old_config).as_ref(),
+                )?;
+                if let Some(record) = record.map(Cow::into_owned) {
+                    txn.put(
+                        RECHUNK_ATTESTERS_DB,
+                        &AttesterKey::new(validator_index, target_epoch, &self.config),
+                        &record,
+                    )?;
+                }
+            }
+        }
+
+        Self::replace_db(txn, self.attesters_db(), RECHUNK_ATTESTERS_DB)
+    }
+
+    /// Replace the contents of `db` with the contents of `temp_db`, leaving `temp_db` empty.
+    fn replace_db(
+        txn: &mut RwTransaction<'_>,
+        db: Database,
+        temp_db: Database,
+    ) -> Result<(), Error> {
+        let mut cursor = txn.cursor(db)?;
+        while cursor.first_entry()?.is_some() {
+            cursor.delete_current()?;
+        }
+        drop(cursor);
+
+        loop {
+            let mut cursor = txn.cursor(temp_db)?;
+            let (key, value) = match cursor.first_entry()? {
+                Some((key, value)) => (key.into_owned(), value.into_owned()),
+                None => break,
+            };
+            cursor.delete_current()?;
+            drop(cursor);
+            txn.put(db, key, value)?;
+        }
+        Ok(())
+    }
+}
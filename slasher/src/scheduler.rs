@@ -0,0 +1,113 @@
+//! Periodic batch processing, shared by the slasher service in the beacon node and the standalone
+//! slasher.
+//!
+//! The notifier runs on the async runtime and prompts the processor at a fixed offset into the
+//! slot, while the processor runs on a blocking thread and does the actual work.
+use crate::metrics::{self, SLASHER_DATABASE_SIZE, SLASHER_RUN_TIME};
+use crate::Slasher;
+use directory::size_of_dir;
+use slog::{debug, error, trace, Logger};
+use slot_clock::SlotClock;
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError};
+use tokio::time::{interval_at, Duration, Instant};
+use types::{Epoch, EthSpec};
+
+/// Run the async notifier which periodically prompts the processor to run.
+///
+/// The notifier stops once the receiving half of `notif_sender` is dropped.
+pub async fn run_notifier<E: EthSpec, S: SlotClock>(
+    slot_clock: S,
+    update_period: u64,
+    slot_offset: f64,
+    notif_sender: SyncSender<Epoch>,
+    log: Logger,
+) {
+    let slot_offset = Duration::from_secs_f64(slot_offset);
+    let start_instant = if let Some(duration_to_next_slot) = slot_clock.duration_to_next_slot() {
+        Instant::now() + duration_to_next_slot + slot_offset
+    } else {
+        error!(log, "Error aligning slasher to slot clock");
+        Instant::now()
+    };
+    let mut interval = interval_at(start_instant, Duration::from_secs(update_period));
+
+    loop {
+        interval.tick().await;
+        if let Some(current_slot) = slot_clock.now() {
+            let current_epoch = current_slot.epoch(E::slots_per_epoch());
+            if let Err(TrySendError::Disconnected(_)) = notif_sender.try_send(current_epoch) {
+                break;
+            }
+        } else {
+            trace!(log, "Slasher has nothing to do: we are pre-genesis");
+        }
+    }
+}
+
+/// Run the blocking task that performs work, until the notifier is dropped.
+///
+/// After each successful update `process_slashings` is called so that the caller can harvest the
+/// slashings found, using `Slasher::get_attester_slashings` and `Slasher::get_proposer_slashings`.
+pub fn run_processor<E: EthSpec, F: FnMut(&Slasher<E>)>(
+    slasher: &Slasher<E>,
+    notif_receiver: Receiver<Epoch>,
+    mut process_slashings: F,
+) {
+    while let Ok(current_epoch) = notif_receiver.recv() {
+        if process_batch(slasher, current_epoch) {
+            process_slashings(slasher);
+        }
+    }
+}
+
+/// Process the queued attestations and blocks, and prune the database.
+///
+/// Return `false` if the database could not be pruned, in which case no slashings should be
+/// harvested until the next update.
+pub fn process_batch<E: EthSpec>(slasher: &Slasher<E>, current_epoch: Epoch) -> bool {
+    let log = slasher.log();
+    let t = Instant::now();
+
+    let batch_timer = metrics::start_timer(&SLASHER_RUN_TIME);
+    let stats = match slasher.process_queued(current_epoch) {
+        Ok(stats) => Some(stats),
+        Err(e) => {
+            error!(
+                log,
+                "Error during scheduled slasher processing";
+                "epoch" => current_epoch,
+                "error" => ?e,
+            );
+            None
+        }
+    };
+    drop(batch_timer);
+
+    // Prune the database, even in the case where batch processing failed.
+    // If the database is full then pruning could help to free it up.
+    if let Err(e) = slasher.prune_database(current_epoch) {
+        error!(
+            log,
+            "Error during slasher database pruning";
+            "epoch" => current_epoch,
+            "error" => ?e,
+        );
+        return false;
+    };
+
+    let database_size = size_of_dir(&slasher.config().database_path);
+    metrics::set_gauge(&SLASHER_DATABASE_SIZE, database_size as i64);
+
+    if let Some(stats) = stats {
+        debug!(
+            log,
+            "Completed slasher update";
+            "epoch" => current_epoch,
+            "time_taken" => format!("{}ms", t.elapsed().as_millis()),
+            "num_attestations" => stats.attestation_stats.num_processed,
+            "num_blocks" => stats.block_stats.num_processed,
+        );
+    }
+
+    true
+}
@@ -1,4 +1,4 @@
-use crate::batch_stats::{AttestationStats, BatchStats, BlockStats};
+use crate::batch_stats::{AttestationStats, BatchStats, BatchSummary, BlockStats};
 use crate::metrics::{
     self, SLASHER_NUM_ATTESTATIONS_DEFERRED, SLASHER_NUM_ATTESTATIONS_DROPPED,
     SLASHER_NUM_ATTESTATIONS_STORED_PER_BATCH, SLASHER_NUM_ATTESTATIONS_VALID,
@@ -11,7 +11,9 @@ use crate::{
 use parking_lot::Mutex;
 use slog::{debug, error, info, Logger};
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 use types::{
     AttesterSlashing, Epoch, EthSpec, IndexedAttestation, ProposerSlashing, SignedBeaconBlockHeader,
 };
@@ -23,6 +25,11 @@ pub struct Slasher<E: EthSpec> {
     block_queue: BlockQueue,
     attester_slashings: Mutex<HashSet<AttesterSlashing<E>>>,
     proposer_slashings: Mutex<HashSet<ProposerSlashing>>,
+    /// Summary of the most recent successful batch update.
+    last_batch: Mutex<Option<BatchSummary>>,
+    /// Number of slashings found since the slasher started.
+    num_attester_slashings_found: AtomicU64,
+    num_proposer_slashings_found: AtomicU64,
     config: Arc<Config>,
     log: Logger,
 }
@@ -42,6 +49,9 @@ impl<E: EthSpec> Slasher<E> {
             block_queue,
             attester_slashings,
             proposer_slashings,
+            last_batch: Mutex::new(None),
+            num_attester_slashings_found: AtomicU64::new(0),
+            num_proposer_slashings_found: AtomicU64::new(0),
             config,
             log,
         })
@@ -65,6 +75,31 @@ impl<E: EthSpec> Slasher<E> {
         &self.log
     }
 
+    /// Summary of the most recent successful batch update, if any.
+    pub fn last_batch(&self) -> Option<BatchSummary> {
+        *self.last_batch.lock()
+    }
+
+    /// Number of attestations waiting for the next batch update.
+    pub fn num_queued_attestations(&self) -> usize {
+        self.attestation_queue.len()
+    }
+
+    /// Number of block headers waiting for the next batch update.
+    pub fn num_queued_blocks(&self) -> usize {
+        self.block_queue.len()
+    }
+
+    /// Number of attester slashings found since the slasher started.
+    pub fn num_attester_slashings_found(&self) -> u64 {
+        self.num_attester_slashings_found.load(Ordering::Relaxed)
+    }
+
+    /// Number of proposer slashings found since the slasher started.
+    pub fn num_proposer_slashings_found(&self) -> u64 {
+        self.num_proposer_slashings_found.load(Ordering::Relaxed)
+    }
+
     /// Accept an attestation from the network and queue it for processing.
     pub fn accept_attestation(&self, attestation: IndexedAttestation<E>) {
         self.attestation_queue.queue(attestation);
@@ -77,10 +112,17 @@ impl<E: EthSpec> Slasher<E> {
 
     /// Apply queued blocks and attestations to the on-disk database, and detect slashings!
     pub fn process_queued(&self, current_epoch: Epoch) -> Result<BatchStats, Error> {
+        let t = Instant::now();
         let mut txn = self.db.begin_rw_txn()?;
         let block_stats = self.process_blocks(&mut txn)?;
         let attestation_stats = self.process_attestations(current_epoch, &mut txn)?;
         txn.commit()?;
+        *self.last_batch.lock() = Some(BatchSummary {
+            current_epoch,
+            time_taken: t.elapsed(),
+            num_attestations: attestation_stats.num_processed,
+            num_blocks: block_stats.num_processed,
+        });
         Ok(BatchStats {
             block_stats,
             attestation_stats,
@@ -112,6 +154,8 @@ impl<E: EthSpec> Slasher<E> {
                 "Found {} new proposer slashings!",
                 slashings.len(),
             );
+            self.num_proposer_slashings_found
+                .fetch_add(num_slashings as u64, Ordering::Relaxed);
             self.proposer_slashings.lock().extend(slashings);
         }
 
@@ -226,6 +270,8 @@ impl<E: EthSpec> Slasher<E> {
                             slashings.len()
                         );
                     }
+                    self.num_attester_slashings_found
+                        .fetch_add(slashings.len() as u64, Ordering::Relaxed);
                     self.attester_slashings.lock().extend(slashings);
                 }
                 Err(e) => {
@@ -256,6 +302,8 @@ impl<E: EthSpec> Slasher<E> {
                         slashings.len()
                     );
                 }
+                self.num_attester_slashings_found
+                    .fetch_add(slashings.len() as u64, Ordering::Relaxed);
                 self.attester_slashings.lock().extend(slashings);
             }
             Err(e) => {
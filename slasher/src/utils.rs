@@ -9,7 +9,8 @@ impl<T> TxnMapFull<T, Error> for Result<T, Error> {
     fn allow_map_full(self) -> Result<Option<T>, Error> {
         match self {
             Ok(x) => Ok(Some(x)),
-            Err(Error::DatabaseError(mdbx::Error::MapFull)) => Ok(None),
+            Err(Error::DatabaseMdbxError(mdbx::Error::MapFull))
+            | Err(Error::DatabaseLmdbError(lmdb::Error::MapFull)) => Ok(None),
             Err(e) => Err(e),
         }
     }
@@ -0,0 +1,67 @@
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    test_utils::{att_slashing, indexed_att, E},
+    Config, DatabaseBackend, Error, Slasher,
+};
+use tempfile::tempdir;
+use types::Epoch;
+
+/// Check that a surround vote against an attestation stored before migrating the database from
+/// `from` to `to` is detected.
+fn migrate_backend_test(from: DatabaseBackend, to: DatabaseBackend) {
+    let tempdir = tempdir().unwrap();
+    let config = Config {
+        backend: from,
+        ..Config::new(tempdir.path().into())
+    };
+    let validators = vec![0, 3, 5, 100];
+    let current_epoch = Epoch::new(40);
+
+    let att1 = indexed_att(&validators, 30, 31, 0);
+    let slasher = Slasher::<E>::open(config.clone(), test_logger()).unwrap();
+    slasher.accept_attestation(att1.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    assert!(slasher.get_attester_slashings().is_empty());
+    drop(slasher);
+
+    let mut new_config = Config {
+        backend: to,
+        ..config.clone()
+    };
+    assert!(matches!(
+        Slasher::<E>::open(new_config.clone(), test_logger()),
+        Err(Error::DatabaseBackendMismatch { on_disk_backend, backend })
+            if on_disk_backend == from && backend == to
+    ));
+
+    new_config.migrate_backend = true;
+    let slasher = Slasher::<E>::open(new_config.clone(), test_logger()).unwrap();
+    let att2 = indexed_att(&validators, 29, 32, 0);
+    slasher.accept_attestation(att2.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&att2, &att1)]
+    );
+    drop(slasher);
+
+    // Once migrated, the database opens without the flag, and the original database is gone.
+    new_config.migrate_backend = false;
+    Slasher::<E>::open(new_config, test_logger()).unwrap();
+    assert!(matches!(
+        Slasher::<E>::open(config, test_logger()),
+        Err(Error::DatabaseBackendMismatch { on_disk_backend, backend })
+            if on_disk_backend == to && backend == from
+    ));
+}
+
+#[test]
+fn migrate_mdbx_to_lmdb() {
+    migrate_backend_test(DatabaseBackend::Mdbx, DatabaseBackend::Lmdb);
+}
+
+#[test]
+fn migrate_lmdb_to_mdbx() {
+    migrate_backend_test(DatabaseBackend::Lmdb, DatabaseBackend::Mdbx);
+}
@@ -0,0 +1,83 @@
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    test_utils::{att_slashing, indexed_att, E},
+    Config, Error, Slasher,
+};
+use tempfile::tempdir;
+use types::Epoch;
+
+fn config_with(
+    config: &Config,
+    chunk_size: usize,
+    validator_chunk_size: usize,
+    history_length: usize,
+) -> Config {
+    Config {
+        chunk_size,
+        validator_chunk_size,
+        history_length,
+        ..config.clone()
+    }
+}
+
+/// Check that a surround vote against an attestation from before the re-chunk is detected.
+fn rechunk_test(new_chunk_size: usize, new_validator_chunk_size: usize, new_history_length: usize) {
+    let tempdir = tempdir().unwrap();
+    let config = config_with(&Config::new(tempdir.path().into()), 4, 2, 64);
+    let validators = vec![0, 3, 5, 100];
+    let current_epoch = Epoch::new(40);
+
+    let att1 = indexed_att(&validators, 30, 31, 0);
+    let slasher = Slasher::<E>::open(config.clone(), test_logger()).unwrap();
+    slasher.accept_attestation(att1.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    assert!(slasher.get_attester_slashings().is_empty());
+    drop(slasher);
+
+    let mut new_config = config_with(
+        &config,
+        new_chunk_size,
+        new_validator_chunk_size,
+        new_history_length,
+    );
+    assert!(matches!(
+        Slasher::<E>::open(new_config.clone(), test_logger()),
+        Err(Error::ConfigIncompatible { .. })
+    ));
+
+    new_config.rechunk = true;
+    let slasher = Slasher::<E>::open(new_config.clone(), test_logger()).unwrap();
+    let att2 = indexed_att(&validators, 29, 32, 0);
+    slasher.accept_attestation(att2.clone());
+    slasher.process_queued(current_epoch).unwrap();
+    assert_eq!(
+        slasher.get_attester_slashings(),
+        hashset![att_slashing(&att2, &att1)]
+    );
+    drop(slasher);
+
+    // Once re-chunked, the database opens without the flag.
+    new_config.rechunk = false;
+    Slasher::<E>::open(new_config, test_logger()).unwrap();
+}
+
+#[test]
+fn rechunk_chunk_size() {
+    rechunk_test(8, 2, 64);
+}
+
+#[test]
+fn rechunk_validator_chunk_size() {
+    rechunk_test(4, 3, 64);
+}
+
+#[test]
+fn rechunk_increase_history_length() {
+    rechunk_test(4, 2, 128);
+}
+
+#[test]
+fn rechunk_decrease_history_length() {
+    rechunk_test(16, 16, 32);
+}
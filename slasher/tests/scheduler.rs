@@ -0,0 +1,62 @@
+use logging::test_logger;
+use maplit::hashset;
+use slasher::{
+    scheduler::run_processor,
+    test_utils::{att_slashing, block, indexed_att, E},
+    Config, Slasher,
+};
+use std::sync::mpsc::sync_channel;
+use std::thread;
+use tempfile::tempdir;
+use types::{Epoch, ProposerSlashing};
+
+/// Check that each update of the processor harvests the slashings found by that update.
+#[test]
+fn processor_harvests_slashings() {
+    let tempdir = tempdir().unwrap();
+    let config = Config::new(tempdir.path().into());
+    let slasher = Slasher::<E>::open(config, test_logger()).unwrap();
+    let current_epoch = Epoch::new(4);
+
+    let att1 = indexed_att(&[1, 2], 2, 3, 0);
+    let att2 = indexed_att(&[1, 2], 2, 3, 1);
+    let block1 = block(32, 9, 0);
+    let block2 = block(32, 9, 1);
+    for attestation in [&att1, &att2] {
+        slasher.accept_attestation(attestation.clone());
+    }
+    for header in [&block1, &block2] {
+        slasher.accept_block_header(header.clone());
+    }
+
+    let (notif_sender, notif_receiver) = sync_channel(1);
+    let notifier = thread::spawn(move || {
+        // Two updates, the second of which finds nothing new. Dropping the sender stops the
+        // processor.
+        notif_sender.send(current_epoch).unwrap();
+        notif_sender.send(current_epoch).unwrap();
+    });
+
+    let mut updates = vec![];
+    run_processor(&slasher, notif_receiver, |slasher| {
+        updates.push((
+            slasher.get_attester_slashings(),
+            slasher.get_proposer_slashings(),
+        ))
+    });
+    notifier.join().unwrap();
+
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates[0].0, hashset![att_slashing(&att1, &att2)]);
+    assert_eq!(updates[0].1.len(), 1);
+    let ProposerSlashing {
+        signed_header_1,
+        signed_header_2,
+    } = updates[0].1.iter().next().unwrap();
+    assert_eq!(
+        hashset![signed_header_1.clone(), signed_header_2.clone()],
+        hashset![block1, block2]
+    );
+    assert!(updates[1].0.is_empty());
+    assert!(updates[1].1.is_empty());
+}
@@ -50,8 +50,10 @@ pub fn run_no_eth1_sim(matches: &ArgMatches) -> Result<(), String> {
             debug_level: log_level,
             logfile_debug_level: "debug",
             log_format,
+            logfile_format: log_format,
             max_log_size: 0,
             max_log_number: 0,
+            max_log_age: None,
             compression: false,
         })?
         .multi_threaded_tokio_runtime()?
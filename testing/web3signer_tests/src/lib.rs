@@ -36,8 +36,10 @@ mod tests {
     use types::*;
     use url::Url;
     use validator_client::{
-        initialized_validators::{load_pem_certificate, InitializedValidators},
-        validator_store::ValidatorStore,
+        initialized_validators::{
+            load_pem_certificate, load_pkcs12_identity, InitializedValidators,
+        },
+        validator_store::{ValidatorStore, DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS},
         SlashingDatabase, SLASHING_PROTECTION_FILENAME,
     };
 
@@ -111,6 +113,18 @@ mod tests {
         tls_dir().join("cert.pem")
     }
 
+    /// The PKCS12 file used by Lighthouse to authenticate to Web3Signer.
+    fn client_identity_path() -> PathBuf {
+        tls_dir().join("client_key.p12")
+    }
+
+    fn client_identity_password() -> String {
+        fs::read_to_string(tls_dir().join("password.txt"))
+            .unwrap()
+            .trim()
+            .to_string()
+    }
+
     /// A testing rig which holds a live Web3Signer process.
     struct Web3SignerRig {
         keypair: Keypair,
@@ -157,6 +171,7 @@ mod tests {
 
             let tls_keystore_file = tls_dir().join("key.p12");
             let tls_keystore_password_file = tls_dir().join("password.txt");
+            let tls_known_clients_file = tls_dir().join("known_clients.txt");
 
             let stdio = || {
                 if SUPPRESS_WEB3SIGNER_LOGS {
@@ -173,7 +188,10 @@ mod tests {
                 ))
                 .arg(format!("--http-listen-host={}", listen_address))
                 .arg(format!("--http-listen-port={}", listen_port))
-                .arg("--tls-allow-any-client=true")
+                .arg(format!(
+                    "--tls-known-clients-file={}",
+                    tls_known_clients_file.to_str().unwrap()
+                ))
                 .arg(format!(
                     "--tls-keystore-file={}",
                     tls_keystore_file.to_str().unwrap()
@@ -193,8 +211,11 @@ mod tests {
             let url = Url::parse(&format!("https://{}:{}", listen_address, listen_port)).unwrap();
 
             let certificate = load_pem_certificate(root_certificate_path()).unwrap();
+            let identity =
+                load_pkcs12_identity(client_identity_path(), &client_identity_password()).unwrap();
             let http_client = Client::builder()
                 .add_root_certificate(certificate)
+                .identity(identity)
                 .build()
                 .unwrap();
 
@@ -255,6 +276,7 @@ mod tests {
             let initialized_validators = InitializedValidators::from_definitions(
                 validator_definitions,
                 validator_dir.path().into(),
+                None,
                 log.clone(),
             )
             .await
@@ -285,6 +307,7 @@ mod tests {
             let validator_store = ValidatorStore::<_, E>::new(
                 initialized_validators,
                 slashing_protection,
+                DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS,
                 Hash256::repeat_byte(42),
                 spec,
                 None,
@@ -336,6 +359,9 @@ mod tests {
                     enabled: true,
                     voting_public_key: validator_pubkey.clone(),
                     graffiti: None,
+                    suggested_fee_recipient: None,
+                    gas_limit: None,
+                    builder_proposals: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::LocalKeystore {
                         voting_keystore_path: signer_rig.keystore_path.clone(),
@@ -351,11 +377,16 @@ mod tests {
                     enabled: true,
                     voting_public_key: validator_pubkey.clone(),
                     graffiti: None,
+                    suggested_fee_recipient: None,
+                    gas_limit: None,
+                    builder_proposals: None,
                     description: String::default(),
                     signing_definition: SigningDefinition::Web3Signer {
                         url: signer_rig.url.to_string(),
                         root_certificate_path: Some(root_certificate_path()),
                         request_timeout_ms: None,
+                        client_identity_path: Some(client_identity_path()),
+                        client_identity_password: Some(client_identity_password()),
                     },
                 };
                 ValidatorStoreRig::new(vec![validator_definition], spec).await
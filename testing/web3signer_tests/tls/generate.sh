@@ -1,4 +1,6 @@
 #!/bin/bash
 openssl req -x509 -sha256 -nodes -days 36500 -newkey rsa:4096 -keyout key.key -out cert.pem -config config &&
-openssl pkcs12 -export -out key.p12 -inkey key.key -in cert.pem -password pass:$(cat password.txt)
-
+openssl pkcs12 -export -out key.p12 -inkey key.key -in cert.pem -password pass:$(cat password.txt) &&
+openssl req -x509 -sha256 -nodes -days 36500 -newkey rsa:4096 -keyout client_key.key -out client_cert.pem -config client_config &&
+openssl pkcs12 -export -keypbe PBE-SHA1-3DES -certpbe PBE-SHA1-3DES -macalg sha1 -out client_key.p12 -inkey client_key.key -in client_cert.pem -password pass:$(cat password.txt) &&
+echo "lighthouse $(openssl x509 -in client_cert.pem -noout -fingerprint -sha256 | cut -d "=" -f2)" > known_clients.txt
@@ -3,6 +3,7 @@
 use crate::test_utils::pubkey;
 use crate::*;
 use tempfile::tempdir;
+use types::{Epoch, Slot};
 
 #[test]
 fn export_non_existent_key() {
@@ -41,6 +42,29 @@ fn export_non_existent_key() {
     assert_eq!(interchange.data[0].pubkey, key1);
 }
 
+#[test]
+fn export_registered_skips_non_existent_key() {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+
+    let key1 = pubkey(1);
+    let key2 = pubkey(2);
+
+    let interchange = slashing_db
+        .export_registered_interchange_info(Hash256::zero(), &[key1, key2])
+        .unwrap();
+    assert!(interchange.data.is_empty());
+
+    slashing_db.register_validator(key2).unwrap();
+
+    let interchange = slashing_db
+        .export_registered_interchange_info(Hash256::zero(), &[key1, key2])
+        .unwrap();
+    assert_eq!(interchange.data.len(), 1);
+    assert_eq!(interchange.data[0].pubkey, key2);
+}
+
 #[test]
 fn export_same_key_twice() {
     let dir = tempdir().unwrap();
@@ -73,3 +97,67 @@ fn export_same_key_twice() {
         export_double.minify().unwrap()
     );
 }
+
+#[test]
+fn interchange_conflicts() {
+    use crate::interchange::{
+        InterchangeConflict, InterchangeData, InterchangeMetadata,
+        SignedAttestation as InterchangeAttestation, SignedBlock as InterchangeBlock,
+    };
+
+    let key1 = pubkey(1);
+    let key2 = pubkey(2);
+    let block = |slot, root: Option<u64>| InterchangeBlock {
+        slot: Slot::new(slot),
+        signing_root: root.map(Hash256::from_low_u64_be),
+    };
+    let att = |source, target, root: Option<u64>| InterchangeAttestation {
+        source_epoch: Epoch::new(source),
+        target_epoch: Epoch::new(target),
+        signing_root: root.map(Hash256::from_low_u64_be),
+    };
+
+    let interchange = Interchange {
+        metadata: InterchangeMetadata {
+            interchange_format_version: SUPPORTED_INTERCHANGE_FORMAT_VERSION,
+            genesis_validators_root: Hash256::zero(),
+        },
+        data: vec![
+            InterchangeData {
+                pubkey: key1,
+                signed_blocks: vec![block(1, Some(1)), block(2, Some(2)), block(3, None)],
+                signed_attestations: vec![att(1, 2, Some(1)), att(2, 5, Some(2))],
+            },
+            // Conflicts are detected across duplicate records for the same key.
+            InterchangeData {
+                pubkey: key1,
+                signed_blocks: vec![block(1, Some(3)), block(2, Some(2)), block(3, Some(4))],
+                signed_attestations: vec![att(0, 2, Some(3)), att(3, 4, Some(4))],
+            },
+            // Null signing roots and identical messages are not conflicts.
+            InterchangeData {
+                pubkey: key2,
+                signed_blocks: vec![block(1, None), block(1, None), block(2, Some(1))],
+                signed_attestations: vec![att(1, 2, None), att(1, 2, None), att(2, 3, Some(1))],
+            },
+        ],
+    };
+
+    let conflicts = interchange.conflicts();
+    assert_eq!(conflicts.len(), 3, "{:?}", conflicts);
+    assert!(
+        conflicts.contains(&InterchangeConflict::DoubleBlockProposal {
+            pubkey: key1,
+            slot: Slot::new(1)
+        })
+    );
+    assert!(conflicts.contains(&InterchangeConflict::DoubleVote {
+        pubkey: key1,
+        target_epoch: Epoch::new(2)
+    }));
+    assert!(conflicts.contains(&InterchangeConflict::SurroundVote {
+        pubkey: key1,
+        surrounding: (Epoch::new(2), Epoch::new(5)),
+        surrounded: (Epoch::new(3), Epoch::new(4)),
+    }));
+}
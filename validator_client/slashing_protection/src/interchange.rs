@@ -45,6 +45,27 @@ pub struct SignedAttestation {
     pub signing_root: Option<Hash256>,
 }
 
+/// Slashable data found within a single interchange file.
+///
+/// Such data indicates that a validator has been run by more than one client at the same time.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterchangeConflict {
+    /// Blocks with different signing roots were signed at the same slot.
+    DoubleBlockProposal { pubkey: PublicKeyBytes, slot: Slot },
+    /// Attestations with different signing roots were signed for the same target epoch.
+    DoubleVote {
+        pubkey: PublicKeyBytes,
+        target_epoch: Epoch,
+    },
+    /// The `surrounding` attestation surrounds the `surrounded` attestation. Each attestation is
+    /// represented as `(source_epoch, target_epoch)`.
+    SurroundVote {
+        pubkey: PublicKeyBytes,
+        surrounding: (Epoch, Epoch),
+        surrounded: (Epoch, Epoch),
+    },
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Interchange {
@@ -156,4 +177,94 @@ impl Interchange {
             data,
         })
     }
+
+    /// Find any slashable data within the interchange, merging the records of validators which
+    /// appear more than once.
+    ///
+    /// Only blocks and attestations with known signing roots can be identified as double
+    /// proposals or double votes, since null signing roots may refer to the same message.
+    pub fn conflicts(&self) -> Vec<InterchangeConflict> {
+        let mut validator_data =
+            HashMap::<PublicKeyBytes, (Vec<&SignedBlock>, Vec<&SignedAttestation>)>::new();
+        for data in self.data.iter() {
+            let (blocks, attestations) = validator_data.entry(data.pubkey).or_default();
+            blocks.extend(&data.signed_blocks);
+            attestations.extend(&data.signed_attestations);
+        }
+
+        let mut conflicts = vec![];
+        for (pubkey, (blocks, mut attestations)) in validator_data {
+            // Distinct known signing roots by slot and by target epoch.
+            let mut block_roots = HashMap::<Slot, HashSet<Hash256>>::new();
+            for block in blocks {
+                if let Some(signing_root) = block.signing_root {
+                    block_roots
+                        .entry(block.slot)
+                        .or_default()
+                        .insert(signing_root);
+                }
+            }
+            let mut attestation_roots = HashMap::<Epoch, HashSet<Hash256>>::new();
+            for attestation in &attestations {
+                if let Some(signing_root) = attestation.signing_root {
+                    attestation_roots
+                        .entry(attestation.target_epoch)
+                        .or_default()
+                        .insert(signing_root);
+                }
+            }
+
+            conflicts.extend(
+                block_roots
+                    .into_iter()
+                    .filter(|(_, roots)| roots.len() > 1)
+                    .map(|(slot, _)| InterchangeConflict::DoubleBlockProposal { pubkey, slot }),
+            );
+            conflicts.extend(
+                attestation_roots
+                    .into_iter()
+                    .filter(|(_, roots)| roots.len() > 1)
+                    .map(|(target_epoch, _)| InterchangeConflict::DoubleVote {
+                        pubkey,
+                        target_epoch,
+                    }),
+            );
+
+            // Sweep the attestations in order of source epoch, tracking the attestation with the
+            // greatest target among those with a strictly lower source. Any attestation with a
+            // lower target than that one is surrounded by it.
+            attestations.sort_by_key(|att| (att.source_epoch, att.target_epoch));
+            let mut surrounding: Option<&SignedAttestation> = None;
+            let mut remaining = attestations.as_slice();
+            while let Some(first) = remaining.first() {
+                let group_len = remaining
+                    .iter()
+                    .take_while(|att| att.source_epoch == first.source_epoch)
+                    .count();
+                let (group, rest) = remaining.split_at(group_len);
+                remaining = rest;
+
+                if let Some(prev) = surrounding {
+                    for att in group
+                        .iter()
+                        .filter(|att| att.target_epoch < prev.target_epoch)
+                    {
+                        conflicts.push(InterchangeConflict::SurroundVote {
+                            pubkey,
+                            surrounding: (prev.source_epoch, prev.target_epoch),
+                            surrounded: (att.source_epoch, att.target_epoch),
+                        });
+                    }
+                }
+                // Groups are sorted by target, so the last attestation has the greatest target.
+                if let Some(last) = group.last() {
+                    if surrounding.map_or(true, |prev| last.target_epoch > prev.target_epoch) {
+                        surrounding = Some(*last);
+                    }
+                }
+            }
+        }
+
+        conflicts
+    }
 }
@@ -736,6 +736,37 @@ impl SlashingDatabase {
             self.list_all_registered_validators(txn)?
         };
 
+        self.export_interchange_info_for_ids(genesis_validators_root, to_export, txn)
+    }
+
+    /// Export slashing protection data for those of `pubkeys` which are registered.
+    ///
+    /// Unlike `export_interchange_info`, validators which are not registered are omitted from the
+    /// interchange rather than causing an error.
+    pub fn export_registered_interchange_info(
+        &self,
+        genesis_validators_root: Hash256,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = &conn.transaction()?;
+
+        let mut to_export = vec![];
+        for pubkey in pubkeys {
+            if let Some(id) = self.get_validator_id_opt(txn, pubkey)? {
+                to_export.push((id, *pubkey));
+            }
+        }
+
+        self.export_interchange_info_for_ids(genesis_validators_root, to_export, txn)
+    }
+
+    fn export_interchange_info_for_ids(
+        &self,
+        genesis_validators_root: Hash256,
+        to_export: Vec<(i64, PublicKeyBytes)>,
+        txn: &Transaction,
+    ) -> Result<Interchange, InterchangeError> {
         let data = to_export
             .into_iter()
             .map(|(validator_id, pubkey)| {
@@ -920,6 +951,42 @@ impl SlashingDatabase {
         Ok(())
     }
 
+    /// Remove all blocks and attestations except the latest block and attestation of each
+    /// validator.
+    ///
+    /// This is the minimal data required to prevent slashable signing, as in a minified
+    /// interchange file. The attestation with the maximum target also has the maximum source,
+    /// because we never store mutually slashable attestations.
+    pub fn prune_all_to_latest(&self) -> Result<(), NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        txn.execute(
+            "DELETE FROM signed_blocks
+             WHERE slot < (SELECT MAX(slot)
+                           FROM signed_blocks AS latest
+                           WHERE latest.validator_id = signed_blocks.validator_id)",
+            params![],
+        )?;
+        txn.execute(
+            "DELETE FROM signed_attestations
+             WHERE target_epoch < (SELECT MAX(target_epoch)
+                                   FROM signed_attestations AS latest
+                                   WHERE latest.validator_id = signed_attestations.validator_id)",
+            params![],
+        )?;
+        txn.commit()?;
+        Ok(())
+    }
+
+    /// Rebuild the database file, returning the space freed by pruning to the file system.
+    ///
+    /// SQLite does not shrink the database file when rows are deleted.
+    pub fn vacuum(&self) -> Result<(), NotSafe> {
+        let conn = self.conn_pool.get()?;
+        conn.execute("VACUUM", params![])?;
+        Ok(())
+    }
+
     pub fn num_validator_rows(&self) -> Result<u32, NotSafe> {
         let mut conn = self.conn_pool.get()?;
         let txn = conn.transaction()?;
@@ -1148,6 +1215,65 @@ mod tests {
         check(&db2);
     }
 
+    #[test]
+    fn prune_all_to_latest() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+
+        let pubkeys = [pubkey(0), pubkey(1)];
+        db.register_validators(pubkeys.iter()).unwrap();
+        for (i, pubkey) in pubkeys.iter().enumerate() {
+            let offset = i as u64 * 10;
+            for slot in 1..=5 {
+                db.check_and_insert_block_signing_root(
+                    pubkey,
+                    Slot::new(offset + slot),
+                    Hash256::from_low_u64_be(slot).into(),
+                )
+                .unwrap();
+            }
+            for epoch in 1..=5 {
+                db.check_and_insert_attestation_signing_root(
+                    pubkey,
+                    Epoch::new(offset + epoch - 1),
+                    Epoch::new(offset + epoch),
+                    Hash256::from_low_u64_be(epoch).into(),
+                )
+                .unwrap();
+            }
+        }
+
+        db.prune_all_to_latest().unwrap();
+        db.vacuum().unwrap();
+
+        for (i, pubkey) in pubkeys.iter().enumerate() {
+            let offset = i as u64 * 10;
+            let summary = db
+                .with_transaction(|txn| db.validator_summary(pubkey, txn))
+                .unwrap();
+            assert_eq!(summary.min_block_slot, Some(Slot::new(offset + 5)));
+            assert_eq!(summary.max_block_slot, Some(Slot::new(offset + 5)));
+            assert_eq!(summary.min_attestation_target, Some(Epoch::new(offset + 5)));
+            assert_eq!(summary.max_attestation_source, Some(Epoch::new(offset + 4)));
+
+            // Signing at or below the latest block and attestation is still prevented.
+            db.check_and_insert_block_signing_root(
+                pubkey,
+                Slot::new(offset + 3),
+                Hash256::from_low_u64_be(100).into(),
+            )
+            .unwrap_err();
+            db.check_and_insert_attestation_signing_root(
+                pubkey,
+                Epoch::new(offset + 2),
+                Epoch::new(offset + 3),
+                Hash256::from_low_u64_be(100).into(),
+            )
+            .unwrap_err();
+        }
+    }
+
     #[test]
     fn test_transaction_failure() {
         let dir = tempdir().unwrap();
@@ -5,13 +5,16 @@ use crate::{
     validator_store::ValidatorStore,
 };
 use environment::RuntimeContext;
+use eth2::types::{EventKind, EventTopic};
 use futures::future::join_all;
-use slog::{crit, error, info, trace};
+use futures::StreamExt;
+use slog::{crit, debug, error, info, trace};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::Arc;
-use tokio::time::{sleep, sleep_until, Duration, Instant};
+use tokio::sync::watch;
+use tokio::time::{sleep, sleep_until, timeout, Duration, Instant};
 use tree_hash::TreeHash;
 use types::{
     AggregateSignature, Attestation, AttestationData, BitList, ChainSpec, CommitteeIndex, EthSpec,
@@ -25,6 +28,8 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
+    attest_to_optimistic_head: bool,
+    attest_early_when_block_seen: bool,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
@@ -35,6 +40,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             context: None,
+            attest_to_optimistic_head: false,
+            attest_early_when_block_seen: false,
         }
     }
 
@@ -63,6 +70,16 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    pub fn attest_to_optimistic_head(mut self, attest_to_optimistic_head: bool) -> Self {
+        self.attest_to_optimistic_head = attest_to_optimistic_head;
+        self
+    }
+
+    pub fn attest_early_when_block_seen(mut self, attest_early_when_block_seen: bool) -> Self {
+        self.attest_early_when_block_seen = attest_early_when_block_seen;
+        self
+    }
+
     pub fn build(self) -> Result<AttestationService<T, E>, String> {
         Ok(AttestationService {
             inner: Arc::new(Inner {
@@ -81,6 +98,8 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
+                attest_to_optimistic_head: self.attest_to_optimistic_head,
+                attest_early_when_block_seen: self.attest_early_when_block_seen,
             }),
         })
     }
@@ -93,9 +112,16 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    /// Permit the beacon node to provide attestation data for a head block which it has not yet
+    /// verified with an execution engine.
+    attest_to_optimistic_head: bool,
+    /// Produce attestations as soon as the beacon node's head is a block from the current slot,
+    /// rather than always waiting until 1/3rd of the way through the slot.
+    attest_early_when_block_seen: bool,
 }
 
-/// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
+/// Attempts to produce attestations for all known validators 1/3rd of the way through each slot,
+/// or earlier if `attest_early_when_block_seen` is set and the block for the slot has been seen.
 ///
 /// If any validators are on the same committee, a single attestation will be downloaded and
 /// returned to the beacon node. This attestation will have a signature from each of the
@@ -139,10 +165,33 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
 
         let executor = self.context.executor.clone();
 
+        let mut head_slot_rx = if self.attest_early_when_block_seen {
+            Some(self.spawn_head_event_listener(slot_duration))
+        } else {
+            None
+        };
+
         let interval_fut = async move {
             loop {
                 if let Some(duration_to_next_slot) = self.slot_clock.duration_to_next_slot() {
-                    sleep(duration_to_next_slot + slot_duration / 3).await;
+                    sleep(duration_to_next_slot).await;
+                    match (head_slot_rx.as_mut(), self.slot_clock.now()) {
+                        // Never attest later than 1/3rd of the way through the slot, even if the
+                        // block has not been seen.
+                        (Some(head_slot_rx), Some(slot)) => {
+                            if timeout(slot_duration / 3, wait_for_head_slot(head_slot_rx, slot))
+                                .await
+                                .is_ok()
+                            {
+                                debug!(
+                                    self.context.log(),
+                                    "Attesting early to block";
+                                    "slot" => slot,
+                                );
+                            }
+                        }
+                        _ => sleep(slot_duration / 3).await,
+                    }
                     let log = self.context.log();
 
                     if let Err(e) = self.spawn_attestation_tasks(slot_duration) {
@@ -170,6 +219,70 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(())
     }
 
+    /// Spawns a task which follows the head of the beacon node via its event stream, returning a
+    /// receiver for the slot of the latest head block.
+    ///
+    /// If the event stream is unavailable no slot is received, so attestations are produced at the
+    /// usual time.
+    fn spawn_head_event_listener(
+        &self,
+        reconnect_delay: Duration,
+    ) -> watch::Receiver<Option<Slot>> {
+        let (head_slot_tx, head_slot_rx) = watch::channel(None);
+        let beacon_nodes = self.beacon_nodes.clone();
+        let log = self.context.log().clone();
+
+        let future = async move {
+            loop {
+                let result = beacon_nodes
+                    .first_success(RequireSynced::Yes, |beacon_node| async move {
+                        beacon_node.get_events::<E>(&[EventTopic::Head]).await
+                    })
+                    .await;
+
+                match result {
+                    Ok(stream) => {
+                        futures::pin_mut!(stream);
+                        while let Some(event) = stream.next().await {
+                            match event {
+                                Ok(EventKind::Head(head)) => {
+                                    // The receiver is only dropped on shutdown.
+                                    if head_slot_tx.send(Some(head.slot)).is_err() {
+                                        return;
+                                    }
+                                }
+                                Ok(_) => (),
+                                Err(e) => {
+                                    debug!(
+                                        log,
+                                        "Head event stream failed";
+                                        "error" => ?e,
+                                    );
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        debug!(
+                            log,
+                            "Unable to subscribe to head events";
+                            "error" => %e,
+                        );
+                    }
+                }
+
+                sleep(reconnect_delay).await;
+            }
+        };
+
+        self.context
+            .executor
+            .spawn(future, "attestation_head_listener");
+
+        head_slot_rx
+    }
+
     /// For each each required attestation, spawn a new task that downloads, signs and uploads the
     /// attestation to the beacon node.
     fn spawn_attestation_tasks(&self, slot_duration: Duration) -> Result<(), String> {
@@ -335,6 +448,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .ok_or("Unable to determine current slot from clock")?
             .epoch(E::slots_per_epoch());
 
+        let allow_optimistic = self.attest_to_optimistic_head;
         let attestation_data = self
             .beacon_nodes
             .first_success(RequireSynced::No, |beacon_node| async move {
@@ -343,7 +457,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                     &[metrics::ATTESTATIONS_HTTP_GET],
                 );
                 beacon_node
-                    .get_validator_attestation_data(slot, committee_index)
+                    .get_validator_attestation_data(slot, committee_index, allow_optimistic)
                     .await
                     .map_err(|e| format!("Failed to produce attestation data: {:?}", e))
                     .map(|result| result.data)
@@ -413,7 +527,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         // Post the attestations to the BN.
         match self
             .beacon_nodes
-            .first_success(RequireSynced::No, |beacon_node| async move {
+            .broadcast(RequireSynced::No, |beacon_node| async move {
                 let _timer = metrics::start_timer_vec(
                     &metrics::ATTESTATION_SERVICE_TIMES,
                     &[metrics::ATTESTATIONS_HTTP_POST],
@@ -533,7 +647,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             let signed_aggregate_and_proofs_slice = signed_aggregate_and_proofs.as_slice();
             match self
                 .beacon_nodes
-                .first_success(RequireSynced::No, |beacon_node| async move {
+                .broadcast(RequireSynced::No, |beacon_node| async move {
                     let _timer = metrics::start_timer_vec(
                         &metrics::ATTESTATION_SERVICE_TIMES,
                         &[metrics::AGGREGATES_HTTP_POST],
@@ -605,6 +719,19 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
     }
 }
 
+/// Waits until `head_slot_rx` reports a head block from `slot`.
+///
+/// Head blocks from other slots are ignored, so a beacon node with a clock ahead of ours cannot
+/// cause an attestation to be produced too early.
+async fn wait_for_head_slot(head_slot_rx: &mut watch::Receiver<Option<Slot>>, slot: Slot) {
+    while *head_slot_rx.borrow() != Some(slot) {
+        if head_slot_rx.changed().await.is_err() {
+            // The listener has stopped, so wait for the usual attestation time.
+            futures::future::pending::<()>().await;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -628,4 +755,48 @@ mod tests {
             "state should have been updated"
         );
     }
+
+    #[tokio::test]
+    async fn wait_for_head_slot_ignores_other_slots() {
+        let slot = Slot::new(10);
+        let wait_duration = Duration::from_millis(50);
+        let (head_slot_tx, mut head_slot_rx) = watch::channel(None);
+
+        assert!(
+            timeout(wait_duration, wait_for_head_slot(&mut head_slot_rx, slot))
+                .await
+                .is_err(),
+            "should wait without a head"
+        );
+
+        for other_slot in [slot - 1, slot + 1] {
+            head_slot_tx.send(Some(other_slot)).unwrap();
+            assert!(
+                timeout(wait_duration, wait_for_head_slot(&mut head_slot_rx, slot))
+                    .await
+                    .is_err(),
+                "should wait for a head from another slot"
+            );
+        }
+
+        head_slot_tx.send(Some(slot)).unwrap();
+        assert!(
+            timeout(wait_duration, wait_for_head_slot(&mut head_slot_rx, slot))
+                .await
+                .is_ok(),
+            "should not wait for a head from the slot"
+        );
+
+        // The usual attestation time is used once the listener stops.
+        drop(head_slot_tx);
+        assert!(
+            timeout(
+                wait_duration,
+                wait_for_head_slot(&mut head_slot_rx, slot + 2)
+            )
+            .await
+            .is_err(),
+            "should wait after the listener stops"
+        );
+    }
 }
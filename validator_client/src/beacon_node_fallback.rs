@@ -1,9 +1,17 @@
 //! Allows for a list of `BeaconNodeHttpClient` to appear as a single entity which will exhibits
 //! "fallback" behaviour; it will try a request on all of the nodes until one or none of them
 //! succeed.
-
-use crate::check_synced::check_synced;
-use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
+//!
+//! Candidates are tried in order of their health, which is determined by their sync distance and
+//! latency. Publications (e.g., blocks and attestations) may instead be broadcast to all healthy
+//! candidates.
+
+use crate::check_synced::{check_synced, SYNC_TOLERANCE};
+use crate::clock_offset::ClockOffset;
+use crate::http_metrics::metrics::{
+    inc_counter_vec, set_gauge_vec, ENDPOINT_ERRORS, ENDPOINT_LATENCY, ENDPOINT_REQUESTS,
+    ENDPOINT_SYNC_DISTANCE,
+};
 use environment::RuntimeContext;
 use eth2::BeaconNodeHttpClient;
 use futures::future;
@@ -14,9 +22,9 @@ use std::fmt::Debug;
 use std::future::Future;
 use std::marker::PhantomData;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::{sync::RwLock, time::sleep};
-use types::{ChainSpec, EthSpec};
+use types::{ChainSpec, EthSpec, Hash256};
 
 /// The number of seconds *prior* to slot start that we will try and update the state of fallback
 /// nodes.
@@ -27,6 +35,14 @@ use types::{ChainSpec, EthSpec};
 /// having the correct nodes up and running prior to the start of the slot.
 const SLOT_LOOKAHEAD: Duration = Duration::from_secs(1);
 
+/// If the estimated offset between our clock and a beacon node's clock exceeds this many
+/// milliseconds then a warning will be logged.
+const CLOCK_OFFSET_WARN_THRESHOLD_MILLIS: i64 = 500;
+
+/// Latencies are compared in buckets of this many milliseconds when ordering candidates, so that
+/// small fluctuations in latency do not cause the preferred candidate to change.
+const LATENCY_BUCKET_MILLIS: u128 = 250;
+
 /// Starts a service that will routinely try and update the status of the provided `beacon_nodes`.
 ///
 /// See `SLOT_LOOKAHEAD` for information about when this should run.
@@ -42,6 +58,8 @@ pub fn start_fallback_updater_service<T: SlotClock + 'static, E: EthSpec>(
     let future = async move {
         loop {
             beacon_nodes.update_unready_candidates().await;
+            beacon_nodes.update_clock_offsets().await;
+            beacon_nodes.update_health().await;
 
             let sleep_time = beacon_nodes
                 .slot_clock
@@ -121,11 +139,47 @@ pub enum CandidateError {
     NotSynced,
 }
 
+/// The result of the most recent health check of a candidate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CandidateHealth {
+    /// The sync distance reported by the node.
+    pub sync_distance: u64,
+    /// The time taken for the node to respond to the health check.
+    pub latency: Duration,
+    /// Whether the node reported that all of its execution engines are offline.
+    pub el_offline: bool,
+}
+
+impl CandidateHealth {
+    /// Returns a key by which candidates are ordered, where lower is healthier.
+    ///
+    /// Nodes without an online execution engine are least healthy, since they are unable to
+    /// produce blocks or follow the head after the merge.
+    ///
+    /// Sync distances within `SYNC_TOLERANCE` and latencies within the same bucket are considered
+    /// equal, so that the configured order of candidates is preserved when they are similarly
+    /// healthy.
+    fn order_key(&self) -> (bool, u64, u128) {
+        let sync_distance = if self.sync_distance < SYNC_TOLERANCE {
+            0
+        } else {
+            self.sync_distance
+        };
+        (
+            self.el_offline,
+            sync_distance,
+            self.latency.as_millis() / LATENCY_BUCKET_MILLIS,
+        )
+    }
+}
+
 /// Represents a `BeaconNodeHttpClient` inside a `BeaconNodeFallback` that may or may not be used
 /// for a query.
 pub struct CandidateBeaconNode<E> {
     beacon_node: BeaconNodeHttpClient,
     status: RwLock<Result<(), CandidateError>>,
+    health: RwLock<Option<CandidateHealth>>,
+    clock_offset: RwLock<ClockOffset>,
     _phantom: PhantomData<E>,
 }
 
@@ -135,6 +189,8 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         Self {
             beacon_node,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            health: RwLock::new(None),
+            clock_offset: RwLock::new(ClockOffset::default()),
             _phantom: PhantomData,
         }
     }
@@ -149,16 +205,78 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         }
     }
 
+    /// Returns the result of the most recent health check of `self`, if it succeeded.
+    pub async fn health(&self) -> Option<CandidateHealth> {
+        *self.health.read().await
+    }
+
     /// Indicate that `self` is offline.
     pub async fn set_offline(&self) {
         *self.status.write().await = Err(CandidateError::Offline)
     }
 
+    /// Measure the sync distance and latency of the node, updating `self.health`.
+    ///
+    /// This does not affect `self.status`, which is only updated by `Self::refresh_status`.
+    pub async fn refresh_health(&self, log: &Logger) -> Option<CandidateHealth> {
+        let request_start = Instant::now();
+        let result = self.beacon_node.get_node_syncing().await;
+        let latency = request_start.elapsed();
+
+        let health = match result {
+            Ok(resp) => {
+                let health = CandidateHealth {
+                    sync_distance: resp.data.sync_distance.as_u64(),
+                    latency,
+                    el_offline: resp.data.el_offline.unwrap_or(false),
+                };
+                let endpoint = self.beacon_node.as_ref();
+                set_gauge_vec(&ENDPOINT_LATENCY, &[endpoint], latency.as_millis() as i64);
+                set_gauge_vec(
+                    &ENDPOINT_SYNC_DISTANCE,
+                    &[endpoint],
+                    health.sync_distance as i64,
+                );
+                Some(health)
+            }
+            Err(e) => {
+                debug!(
+                    log,
+                    "Unable to check beacon node health";
+                    "error" => %e,
+                    "endpoint" => %self.beacon_node,
+                );
+                None
+            }
+        };
+
+        let mut health_lock = self.health.write().await;
+        let was_el_offline = health_lock.map_or(false, |health| health.el_offline);
+        match health.map(|health| health.el_offline) {
+            Some(true) if !was_el_offline => warn!(
+                log,
+                "Beacon node's execution engine is offline";
+                "msg" => "block proposals are paused unless another beacon node is available",
+                "endpoint" => %self.beacon_node,
+            ),
+            Some(false) if was_el_offline => info!(
+                log,
+                "Beacon node's execution engine is back online";
+                "endpoint" => %self.beacon_node,
+            ),
+            _ => (),
+        }
+
+        *health_lock = health;
+        health
+    }
+
     /// Perform some queries against the node to determine if it is a good candidate, updating
     /// `self.status` and returning that result.
     pub async fn refresh_status<T: SlotClock>(
         &self,
         slot_clock: Option<&T>,
+        genesis_validators_root: Option<Hash256>,
         spec: &ChainSpec,
         log: &Logger,
     ) -> Result<(), CandidateError> {
@@ -166,7 +284,10 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
         if let Err(e) = self.is_online(log).await {
             *status = Err(e);
-        } else if let Err(e) = self.is_compatible(spec, log).await {
+        } else if let Err(e) = self
+            .is_compatible(slot_clock, genesis_validators_root, spec, log)
+            .await
+        {
             *status = Err(e);
         } else if let Err(e) = self.is_synced(slot_clock, log).await {
             *status = Err(e);
@@ -187,6 +308,16 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 
         match result {
             Ok(version) => {
+                if !is_compatible_lighthouse_version(&version) {
+                    warn!(
+                        log,
+                        "Beacon node version differs from validator client";
+                        "advice" => "run the same version of Lighthouse on the beacon node and validator client",
+                        "bn_version" => &version,
+                        "vc_version" => lighthouse_version::VERSION,
+                        "endpoint" => %self.beacon_node,
+                    );
+                }
                 info!(
                     log,
                     "Connected to beacon node";
@@ -207,8 +338,19 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         }
     }
 
-    /// Checks if the node has the correct specification.
-    async fn is_compatible(&self, spec: &ChainSpec, log: &Logger) -> Result<(), CandidateError> {
+    /// Checks if the node has the correct specification and, if a slot clock is supplied, the
+    /// same genesis time and current fork.
+    ///
+    /// If `genesis_validators_root` is supplied, the node must also have the same genesis
+    /// validators root. Signing with a node on a different network would otherwise only fail once
+    /// the signatures are rejected for having an unexpected domain.
+    async fn is_compatible<T: SlotClock>(
+        &self,
+        slot_clock: Option<&T>,
+        genesis_validators_root: Option<Hash256>,
+        spec: &ChainSpec,
+        log: &Logger,
+    ) -> Result<(), CandidateError> {
         let config_and_preset = self
             .beacon_node
             .get_config_spec()
@@ -272,9 +414,131 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
             );
         }
 
+        if slot_clock.is_none() && genesis_validators_root.is_none() {
+            return Ok(());
+        }
+
+        let genesis = self
+            .beacon_node
+            .get_beacon_genesis()
+            .await
+            .map_err(|e| {
+                error!(
+                    log,
+                    "Unable to read genesis from beacon node";
+                    "error" => %e,
+                    "endpoint" => %self.beacon_node,
+                );
+                CandidateError::Offline
+            })?
+            .data;
+
+        if let Some(genesis_validators_root) = genesis_validators_root {
+            if genesis.genesis_validators_root != genesis_validators_root {
+                error!(
+                    log,
+                    "Beacon node is on a different network";
+                    "endpoint" => %self.beacon_node,
+                    "bn_genesis_validators_root" => ?genesis.genesis_validators_root,
+                    "our_genesis_validators_root" => ?genesis_validators_root,
+                );
+                return Err(CandidateError::Incompatible);
+            }
+        }
+
+        if let Some(slot_clock) = slot_clock {
+            if genesis.genesis_time != slot_clock.genesis_duration().as_secs() {
+                error!(
+                    log,
+                    "Beacon node has a different genesis time";
+                    "endpoint" => %self.beacon_node,
+                    "bn_genesis_time" => genesis.genesis_time,
+                    "our_genesis_time" => slot_clock.genesis_duration().as_secs(),
+                );
+                return Err(CandidateError::Incompatible);
+            }
+
+            // A difference in the schedule of a future fork is tolerated (see the warning above),
+            // but a difference in the current fork would cause our signatures to be invalid.
+            let current_epoch = slot_clock
+                .now_or_genesis()
+                .unwrap_or(spec.genesis_slot)
+                .epoch(E::slots_per_epoch());
+            let bn_fork = beacon_node_spec.fork_at_epoch(current_epoch);
+            let our_fork = spec.fork_at_epoch(current_epoch);
+
+            if bn_fork != our_fork {
+                error!(
+                    log,
+                    "Beacon node is on a different fork";
+                    "advice" => "check that the BN and VC are updated and configured for the same forks",
+                    "endpoint" => %self.beacon_node,
+                    "epoch" => current_epoch,
+                    "bn_fork" => ?bn_fork,
+                    "our_fork" => ?our_fork,
+                );
+                return Err(CandidateError::Incompatible);
+            }
+        }
+
         Ok(())
     }
 
+    /// Samples the time reported by the beacon node in order to estimate the offset between its
+    /// clock and `slot_clock`, logging if the two disagree.
+    pub async fn refresh_clock_offset<T: SlotClock>(&self, slot_clock: &T, log: &Logger) {
+        let request_start = slot_clock.now_duration();
+        let result = self.beacon_node.get_node_version_date().await;
+        let request_end = slot_clock.now_duration();
+
+        let server_time = match result {
+            Ok(Some(server_time)) => server_time,
+            Ok(None) => return,
+            Err(e) => {
+                debug!(
+                    log,
+                    "Unable to sample beacon node clock";
+                    "error" => %e,
+                    "endpoint" => %self.beacon_node,
+                );
+                return;
+            }
+        };
+
+        let (request_start, request_end) = match (request_start, request_end) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return,
+        };
+
+        let mut clock_offset = self.clock_offset.write().await;
+        if !clock_offset.add_sample(request_start, request_end, server_time) {
+            return;
+        }
+
+        if let Some(offset_ms) = clock_offset.offset_millis() {
+            let disagrees = offset_ms.abs() > CLOCK_OFFSET_WARN_THRESHOLD_MILLIS;
+
+            if disagrees && !clock_offset.disagrees {
+                warn!(
+                    log,
+                    "Beacon node clock disagrees with local clock";
+                    "msg" => "check the system time on this host and the beacon node",
+                    "offset_ms" => offset_ms,
+                    "endpoint" => %self.beacon_node,
+                );
+            } else if !disagrees && clock_offset.disagrees {
+                info!(
+                    log,
+                    "Beacon node clock agrees with local clock";
+                    "offset_ms" => offset_ms,
+                    "endpoint" => %self.beacon_node,
+                );
+            }
+
+            clock_offset.disagrees = disagrees;
+        }
+    }
+
     /// Checks if the beacon node is synced.
     async fn is_synced<T: SlotClock>(
         &self,
@@ -295,16 +559,27 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
 /// identical query.
 pub struct BeaconNodeFallback<T, E> {
     candidates: Vec<CandidateBeaconNode<E>>,
+    broadcast: bool,
     slot_clock: Option<T>,
+    genesis_validators_root: Option<Hash256>,
     spec: ChainSpec,
     log: Logger,
 }
 
 impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
-    pub fn new(candidates: Vec<CandidateBeaconNode<E>>, spec: ChainSpec, log: Logger) -> Self {
+    /// If `broadcast` is `true`, calls to `Self::broadcast` are sent to all ready candidates rather
+    /// than only the first to succeed.
+    pub fn new(
+        candidates: Vec<CandidateBeaconNode<E>>,
+        broadcast: bool,
+        spec: ChainSpec,
+        log: Logger,
+    ) -> Self {
         Self {
             candidates,
+            broadcast,
             slot_clock: None,
+            genesis_validators_root: None,
             spec,
             log,
         }
@@ -319,6 +594,14 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         self.slot_clock = Some(slot_clock);
     }
 
+    /// Used to set the genesis validators root post-instantiation, for the same reason as
+    /// `Self::set_slot_clock`.
+    ///
+    /// Once set, any candidate with a different genesis validators root is deemed incompatible.
+    pub fn set_genesis_validators_root(&mut self, genesis_validators_root: Hash256) {
+        self.genesis_validators_root = Some(genesis_validators_root);
+    }
+
     /// The count of candidates, regardless of their state.
     pub fn num_total(&self) -> usize {
         self.candidates.len()
@@ -357,6 +640,23 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         n
     }
 
+    /// Returns `true` if every candidate which passed its most recent health check reported that
+    /// its execution engines are offline, and at least one did.
+    ///
+    /// Candidates which have not passed a health check are ignored, since their execution engines
+    /// are unknown.
+    pub async fn all_execution_layers_offline(&self) -> bool {
+        let mut any_offline = false;
+        for candidate in &self.candidates {
+            match candidate.health().await {
+                Some(health) if health.el_offline => any_offline = true,
+                Some(_) => return false,
+                None => (),
+            }
+        }
+        any_offline
+    }
+
     /// Loop through any `self.candidates` that we don't think are online, compatible or synced and
     /// poll them to see if their status has changed.
     ///
@@ -377,6 +677,7 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 // acceptable inefficiency.
                 futures.push(candidate.refresh_status(
                     self.slot_clock.as_ref(),
+                    self.genesis_validators_root,
                     &self.spec,
                     &self.log,
                 ));
@@ -387,6 +688,55 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         let _ = future::join_all(futures).await;
     }
 
+    /// Estimate the offset between our clock and the clock of each candidate, logging any
+    /// significant disagreement.
+    ///
+    /// Our clock is used to schedule duties regardless of the outcome, so that duties are not
+    /// stalled whilst beacon nodes are unavailable.
+    pub async fn update_clock_offsets(&self) {
+        if let Some(slot_clock) = self.slot_clock.as_ref() {
+            let futures = self
+                .candidates
+                .iter()
+                .map(|candidate| candidate.refresh_clock_offset(slot_clock, &self.log));
+
+            future::join_all(futures).await;
+        }
+    }
+
+    /// Measure the sync distance and latency of each candidate, which determines the order in
+    /// which they are tried.
+    pub async fn update_health(&self) {
+        let futures = self
+            .candidates
+            .iter()
+            .map(|candidate| candidate.refresh_health(&self.log));
+
+        future::join_all(futures).await;
+    }
+
+    /// Returns the candidates ordered from healthiest to least healthy.
+    ///
+    /// Candidates which have not passed a health check are placed last. Candidates with equal
+    /// health retain their configured order.
+    async fn ordered_candidates(&self) -> Vec<&CandidateBeaconNode<E>> {
+        let mut candidates = Vec::with_capacity(self.candidates.len());
+        for candidate in &self.candidates {
+            candidates.push((candidate.health().await, candidate));
+        }
+
+        // `sort_by_key` is stable, so ties are resolved by the configured order.
+        candidates.sort_by_key(|(health, _)| match health {
+            Some(health) => (false, health.order_key()),
+            None => (true, (0, 0)),
+        });
+
+        candidates
+            .into_iter()
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
     /// Run `func` against each candidate in `self`, returning immediately if a result is found.
     /// Otherwise, return all the errors encountered along the way.
     ///
@@ -432,10 +782,10 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
             }};
         }
 
-        // First pass: try `func` on all synced and ready candidates.
+        // First pass: try `func` on all synced and ready candidates, healthiest first.
         //
         // This ensures that we always choose a synced node if it is available.
-        for candidate in &self.candidates {
+        for candidate in self.ordered_candidates().await {
             match candidate.status(RequireSynced::Yes).await {
                 Err(e @ CandidateError::NotSynced) if require_synced == false => {
                     // This client is unsynced we will try it after trying all synced clients
@@ -470,7 +820,12 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
                 Ok(()) => Ok(()),
                 Err(_) => {
                     candidate
-                        .refresh_status(self.slot_clock.as_ref(), &self.spec, &self.log)
+                        .refresh_status(
+                            self.slot_clock.as_ref(),
+                            self.genesis_validators_root,
+                            &self.spec,
+                            &self.log,
+                        )
                         .await
                 }
             };
@@ -487,4 +842,143 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         // There were no candidates already ready and we were unable to make any of them ready.
         Err(AllErrored(errors))
     }
+
+    /// Run `func` concurrently against all ready candidates in `self`, succeeding if at least one
+    /// of them succeeds. This is intended for publishing messages, where sending the message to
+    /// several beacon nodes improves the chance that it propagates in time.
+    ///
+    /// If broadcasting is disabled or no candidates are ready, this behaves like
+    /// `Self::first_success`.
+    pub async fn broadcast<'a, F, O, Err, R>(
+        &'a self,
+        require_synced: RequireSynced,
+        func: F,
+    ) -> Result<(), AllErrored<Err>>
+    where
+        F: Fn(&'a BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+    {
+        if !self.broadcast {
+            return self.first_success(require_synced, func).await.map(|_| ());
+        }
+
+        let mut ready = vec![];
+        for candidate in self.ordered_candidates().await {
+            if candidate.status(require_synced).await.is_ok() {
+                ready.push(candidate);
+            }
+        }
+
+        if ready.is_empty() {
+            return self.first_success(require_synced, func).await.map(|_| ());
+        }
+
+        let func = &func;
+        let futures = ready.into_iter().map(|candidate| async move {
+            inc_counter_vec(&ENDPOINT_REQUESTS, &[candidate.beacon_node.as_ref()]);
+            (candidate, func(&candidate.beacon_node).await)
+        });
+
+        let mut errors = vec![];
+        let mut succeeded = false;
+        for (candidate, result) in future::join_all(futures).await {
+            match result {
+                Ok(_) => succeeded = true,
+                Err(e) => {
+                    // As in `Self::first_success`, a failed request marks the candidate as
+                    // not-ready.
+                    candidate.set_offline().await;
+                    errors.push((candidate.beacon_node.to_string(), Error::RequestFailed(e)));
+                    inc_counter_vec(&ENDPOINT_ERRORS, &[candidate.beacon_node.as_ref()]);
+                }
+            }
+        }
+
+        if succeeded {
+            if !errors.is_empty() {
+                debug!(
+                    self.log,
+                    "Broadcast failed on some beacon nodes";
+                    "failed" => errors.len(),
+                );
+            }
+            Ok(())
+        } else {
+            Err(AllErrored(errors))
+        }
+    }
+}
+
+/// Returns the `major.minor` component of a Lighthouse version string such as
+/// `Lighthouse/v2.1.0-rc.1/x86_64-linux`, or `None` if `version` is not from Lighthouse.
+fn lighthouse_major_minor(version: &str) -> Option<(&str, &str)> {
+    let semver = version.strip_prefix("Lighthouse/v")?;
+    let mut parts = semver.split(|c: char| c == '.' || c == '-' || c == '/');
+    Some((parts.next()?, parts.next()?))
+}
+
+/// Returns `false` if `bn_version` is a Lighthouse version with a different major or minor
+/// version to this validator client.
+///
+/// Non-Lighthouse beacon nodes are assumed to be compatible, since the standard API is versioned
+/// independently of the software.
+fn is_compatible_lighthouse_version(bn_version: &str) -> bool {
+    match (
+        lighthouse_major_minor(bn_version),
+        lighthouse_major_minor(lighthouse_version::VERSION),
+    ) {
+        (Some(bn), Some(vc)) => bn == vc,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lighthouse_version_parsing() {
+        assert_eq!(
+            lighthouse_major_minor("Lighthouse/v2.1.0-rc.1-5ec3b2f/x86_64-linux"),
+            Some(("2", "1"))
+        );
+        assert_eq!(
+            lighthouse_major_minor("Lighthouse/v3.0.0"),
+            Some(("3", "0"))
+        );
+        assert_eq!(lighthouse_major_minor("teku/v22.1.0"), None);
+        assert_eq!(lighthouse_major_minor("Lighthouse/v3"), None);
+    }
+
+    #[test]
+    fn non_lighthouse_versions_are_compatible() {
+        assert!(is_compatible_lighthouse_version("Prysm/v2.0.6"));
+        assert!(is_compatible_lighthouse_version(
+            lighthouse_version::VERSION
+        ));
+        assert!(!is_compatible_lighthouse_version("Lighthouse/v0.0.1"));
+    }
+
+    #[test]
+    fn candidate_health_ordering() {
+        let health = |sync_distance, latency_ms| CandidateHealth {
+            sync_distance,
+            latency: Duration::from_millis(latency_ms),
+            el_offline: false,
+        };
+
+        // Small differences in sync distance and latency are ignored.
+        assert_eq!(health(0, 10).order_key(), health(3, 200).order_key());
+        // Lower latency is preferred.
+        assert!(health(0, 10).order_key() < health(0, 300).order_key());
+        // A node which is behind is less healthy than a slow node.
+        assert!(health(0, 2_000).order_key() < health(SYNC_TOLERANCE, 10).order_key());
+        assert!(health(SYNC_TOLERANCE, 10).order_key() < health(32, 10).order_key());
+        // A node without an execution engine is less healthy than a node which is behind.
+        let el_offline = CandidateHealth {
+            el_offline: true,
+            ..health(0, 10)
+        };
+        assert!(health(32, 2_000).order_key() < el_offline.order_key());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
     beacon_node_fallback::{BeaconNodeFallback, RequireSynced},
+    fee_recipient_file::FeeRecipientFile,
     graffiti_file::GraffitiFile,
 };
 use crate::{http_metrics::metrics, validator_store::ValidatorStore};
@@ -9,8 +10,11 @@ use slog::{crit, debug, error, info, trace, warn};
 use slot_clock::SlotClock;
 use std::ops::Deref;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::mpsc;
-use types::{EthSpec, PublicKeyBytes, Slot};
+use types::{
+    BlindedBeaconBlock, EthSpec, ProposerPreparationData, PublicKeyBytes, SignatureBytes, Slot,
+};
 
 /// Builds a `BlockService`.
 pub struct BlockServiceBuilder<T, E: EthSpec> {
@@ -20,6 +24,8 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     context: Option<RuntimeContext<E>>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
+    fee_recipient_file: Option<FeeRecipientFile>,
+    builder_proposals: bool,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
@@ -31,6 +37,8 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             context: None,
             graffiti: None,
             graffiti_file: None,
+            fee_recipient_file: None,
+            builder_proposals: false,
         }
     }
 
@@ -64,6 +72,16 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
+    pub fn fee_recipient_file(mut self, fee_recipient_file: Option<FeeRecipientFile>) -> Self {
+        self.fee_recipient_file = fee_recipient_file;
+        self
+    }
+
+    pub fn builder_proposals(mut self, builder_proposals: bool) -> Self {
+        self.builder_proposals = builder_proposals;
+        self
+    }
+
     pub fn build(self) -> Result<BlockService<T, E>, String> {
         Ok(BlockService {
             inner: Arc::new(Inner {
@@ -81,6 +99,8 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                     .ok_or("Cannot build BlockService without runtime_context")?,
                 graffiti: self.graffiti,
                 graffiti_file: self.graffiti_file,
+                fee_recipient_file: self.fee_recipient_file,
+                builder_proposals: self.builder_proposals,
             }),
         })
     }
@@ -94,6 +114,8 @@ pub struct Inner<T, E: EthSpec> {
     context: RuntimeContext<E>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
+    fee_recipient_file: Option<FeeRecipientFile>,
+    builder_proposals: bool,
 }
 
 /// Attempts to produce attestations for any block producer(s) at the start of the epoch.
@@ -237,6 +259,26 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             .now()
             .ok_or("Unable to determine current slot from clock")?;
 
+        // Blocks after the merge fork can't be produced without an execution engine, so don't sign
+        // anything until one of the beacon nodes has an execution engine again.
+        let requires_payload = self
+            .context
+            .eth2_config
+            .spec
+            .bellatrix_fork_epoch
+            .map_or(false, |fork_epoch| {
+                slot.epoch(E::slots_per_epoch()) >= fork_epoch
+            });
+        if requires_payload && self.beacon_nodes.all_execution_layers_offline().await {
+            error!(
+                log,
+                "Skipping block proposal";
+                "msg" => "the execution engines of all beacon nodes are offline",
+                "slot" => slot.as_u64(),
+            );
+            return Ok(());
+        }
+
         let randao_reveal = self
             .validator_store
             .randao_reveal(validator_pubkey, slot.epoch(E::slots_per_epoch()))
@@ -257,23 +299,92 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             .or_else(|| self.validator_store.graffiti(&validator_pubkey))
             .or(self.graffiti);
 
+        let proposer_index = self.validator_store.validator_index(&validator_pubkey);
+
+        if self
+            .validator_store
+            .builder_proposals(&validator_pubkey, self.builder_proposals)
+        {
+            // Nothing has been signed if the blinded block could not be produced, so it is safe
+            // to fall back to a locally built block.
+            match self
+                .produce_blinded_block(slot, &randao_reveal, graffiti, proposer_index)
+                .await
+            {
+                Ok(block) => {
+                    return self
+                        .publish_blinded_block(block, validator_pubkey, current_slot, graffiti)
+                        .await
+                }
+                Err(e) => warn!(
+                    log,
+                    "Unable to produce block with builder payload";
+                    "msg" => "falling back to a locally built payload",
+                    "error" => e,
+                    "slot" => slot.as_u64(),
+                ),
+            }
+        }
+
+        // A fee recipient from the validator definitions takes precedence over the fee recipient
+        // file, which is re-read for each proposal so that it may be changed without a restart.
+        let preparation_data = self
+            .validator_store
+            .suggested_fee_recipient(&validator_pubkey)
+            .or_else(|| {
+                self.fee_recipient_file.clone().and_then(|mut f| {
+                    match f.load_fee_recipient(&validator_pubkey) {
+                        Ok(f) => f,
+                        Err(e) => {
+                            warn!(log, "Failed to read fee recipient file"; "error" => ?e);
+                            None
+                        }
+                    }
+                })
+            })
+            .zip(proposer_index)
+            .map(|(fee_recipient, validator_index)| ProposerPreparationData {
+                validator_index,
+                fee_recipient,
+            });
+
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
-        let proposer_index = self.validator_store.validator_index(&validator_pubkey);
         let validator_pubkey_ref = &validator_pubkey;
-        let signed_block = self
+        let preparation_data_ref = &preparation_data;
+        let (signed_block, get_time, sign_time) = self
             .beacon_nodes
             .first_success(RequireSynced::No, |beacon_node| async move {
-                let get_timer = metrics::start_timer_vec(
-                    &metrics::BLOCK_SERVICE_TIMES,
-                    &[metrics::BEACON_BLOCK_HTTP_GET],
-                );
+                // Inform the beacon node of the fee recipient immediately before requesting the
+                // block, so that it is used for the execution payload.
+                if let Some(preparation_data) = preparation_data_ref {
+                    if let Err(e) = beacon_node
+                        .post_validator_prepare_beacon_proposer(std::slice::from_ref(
+                            preparation_data,
+                        ))
+                        .await
+                    {
+                        warn!(
+                            log,
+                            "Unable to send fee recipient to beacon node";
+                            "error" => ?e,
+                            "fee_recipient" => ?preparation_data.fee_recipient,
+                        );
+                    }
+                }
+
+                let get_start = Instant::now();
                 let block = beacon_node
                     .get_validator_blocks(slot, randao_reveal_ref, graffiti.as_ref())
                     .await
                     .map_err(|e| format!("Error from beacon node when producing block: {:?}", e))?
                     .data;
-                drop(get_timer);
+                let get_time = get_start.elapsed();
+                metrics::observe_timer_vec(
+                    &metrics::BLOCK_SERVICE_TIMES,
+                    &[metrics::BEACON_BLOCK_HTTP_GET],
+                    get_time,
+                );
 
                 if proposer_index != Some(block.proposer_index()) {
                     return Err(
@@ -282,24 +393,37 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                     );
                 }
 
+                let sign_start = Instant::now();
                 let signed_block = self_ref
                     .validator_store
                     .sign_block(*validator_pubkey_ref, block, current_slot)
                     .await
                     .map_err(|e| format!("Unable to sign block: {:?}", e))?;
+                let sign_time = sign_start.elapsed();
+                metrics::observe_timer_vec(
+                    &metrics::BLOCK_SERVICE_TIMES,
+                    &[metrics::BEACON_BLOCK_SIGN],
+                    sign_time,
+                );
 
+                Ok::<_, String>((signed_block, get_time, sign_time))
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Publish the block separately from its production so that it may be broadcast to all
+        // healthy beacon nodes, not just the one which produced it.
+        let signed_block_ref = &signed_block;
+        self.beacon_nodes
+            .broadcast(RequireSynced::No, |beacon_node| async move {
                 let _post_timer = metrics::start_timer_vec(
                     &metrics::BLOCK_SERVICE_TIMES,
                     &[metrics::BEACON_BLOCK_HTTP_POST],
                 );
                 beacon_node
-                    .post_beacon_blocks(&signed_block)
+                    .post_beacon_blocks(signed_block_ref)
                     .await
-                    .map_err(|e| {
-                        format!("Error from beacon node when publishing block: {:?}", e)
-                    })?;
-
-                Ok::<_, String>(signed_block)
+                    .map_err(|e| format!("Error from beacon node when publishing block: {:?}", e))
             })
             .await
             .map_err(|e| e.to_string())?;
@@ -311,6 +435,104 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
             "attestations" => signed_block.message().body().attestations().len(),
             "graffiti" => ?graffiti.map(|g| g.as_utf8_lossy()),
             "slot" => signed_block.slot().as_u64(),
+            "production_time" => ?get_time,
+            "signing_time" => ?sign_time,
+        );
+
+        Ok(())
+    }
+
+    /// Request a block with a payload from the builder, without signing it.
+    async fn produce_blinded_block(
+        &self,
+        slot: Slot,
+        randao_reveal: &SignatureBytes,
+        graffiti: Option<Graffiti>,
+        proposer_index: Option<u64>,
+    ) -> Result<BlindedBeaconBlock<E>, String> {
+        self.beacon_nodes
+            .first_success(RequireSynced::No, |beacon_node| async move {
+                let _get_timer = metrics::start_timer_vec(
+                    &metrics::BLOCK_SERVICE_TIMES,
+                    &[metrics::BLINDED_BEACON_BLOCK_HTTP_GET],
+                );
+                let block = beacon_node
+                    .get_validator_blinded_blocks::<E>(slot, randao_reveal, graffiti.as_ref())
+                    .await
+                    .map_err(|e| {
+                        format!(
+                            "Error from beacon node when producing blinded block: {:?}",
+                            e
+                        )
+                    })?
+                    .data;
+
+                if proposer_index != Some(block.proposer_index) {
+                    return Err(
+                        "Proposer index does not match block proposer. Beacon chain re-orged"
+                            .to_string(),
+                    );
+                }
+
+                Ok::<_, String>(block)
+            })
+            .await
+            .map_err(|e| e.to_string())
+    }
+
+    /// Sign a block with a payload from the builder and publish it, which reveals the payload.
+    async fn publish_blinded_block(
+        &self,
+        block: BlindedBeaconBlock<E>,
+        validator_pubkey: PublicKeyBytes,
+        current_slot: Slot,
+        graffiti: Option<Graffiti>,
+    ) -> Result<(), String> {
+        let log = self.context.log();
+
+        let sign_start = Instant::now();
+        let signed_block = self
+            .validator_store
+            .sign_blinded_block(validator_pubkey, block, current_slot)
+            .await
+            .map_err(|e| format!("Unable to sign blinded block: {:?}", e))?;
+        let sign_time = sign_start.elapsed();
+        metrics::observe_timer_vec(
+            &metrics::BLOCK_SERVICE_TIMES,
+            &[metrics::BLINDED_BEACON_BLOCK_SIGN],
+            sign_time,
+        );
+
+        let signed_block_ref = &signed_block;
+        self.beacon_nodes
+            .broadcast(RequireSynced::No, |beacon_node| async move {
+                let _post_timer = metrics::start_timer_vec(
+                    &metrics::BLOCK_SERVICE_TIMES,
+                    &[metrics::BLINDED_BEACON_BLOCK_HTTP_POST],
+                );
+                beacon_node
+                    .post_beacon_blinded_blocks(signed_block_ref)
+                    .await
+                    .map_err(|e| {
+                        format!(
+                            "Error from beacon node when publishing blinded block: {:?}",
+                            e
+                        )
+                    })
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let body = &signed_block.message.body;
+        info!(
+            log,
+            "Successfully published block";
+            "deposits" => body.deposits.len(),
+            "attestations" => body.attestations.len(),
+            "graffiti" => ?graffiti.map(|g| g.as_utf8_lossy()),
+            "slot" => signed_block.message.slot.as_u64(),
+            "payload" => "builder",
+            "signing_time" => ?sign_time,
         );
 
         Ok(())
@@ -0,0 +1,190 @@
+//! Registers local validators with the builder connected to the beacon node, so that the builder
+//! knows the fee recipient and gas limit to use when building payloads on their behalf.
+//!
+//! Registrations are re-sent each epoch, since the builder may have restarted or the beacon node
+//! may have changed. A registration is only re-signed when its fee recipient or gas limit changes.
+
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::doppelganger_service::DoppelgangerStatus;
+use crate::fee_recipient_file::FeeRecipientFile;
+use crate::validator_store::ValidatorStore;
+use environment::RuntimeContext;
+use slog::{debug, error, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+use types::{
+    Address, EthSpec, PublicKeyBytes, SignedValidatorRegistrationData, ValidatorRegistrationData,
+};
+
+/// The preferences contained in a registration, used to detect when it must be re-signed.
+#[derive(PartialEq)]
+struct RegistrationKey {
+    fee_recipient: Address,
+    gas_limit: u64,
+}
+
+/// Starts a service which registers the validators in `validator_store` with the builder each
+/// epoch. Validators are only registered if they propose with the builder, according to
+/// `builder_proposals` and their validator definitions.
+pub fn start_builder_registration_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    slot_clock: T,
+    fee_recipient_file: Option<FeeRecipientFile>,
+    builder_proposals: bool,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        let mut registrations: HashMap<
+            PublicKeyBytes,
+            (RegistrationKey, SignedValidatorRegistrationData),
+        > = HashMap::new();
+
+        loop {
+            register_validators(
+                &mut registrations,
+                &validator_store,
+                &beacon_nodes,
+                fee_recipient_file.clone(),
+                builder_proposals,
+                &log,
+            )
+            .await;
+
+            match slot_clock.duration_to_next_epoch(E::slots_per_epoch()) {
+                Some(duration) => sleep(duration).await,
+                None => {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another slot.
+                    sleep(slot_clock.slot_duration()).await;
+                }
+            }
+        }
+    };
+
+    context
+        .executor
+        .spawn(future, "builder_registration_service");
+}
+
+/// Sign any new or changed registrations and send all registrations to the beacon nodes.
+async fn register_validators<T: SlotClock + 'static, E: EthSpec>(
+    registrations: &mut HashMap<PublicKeyBytes, (RegistrationKey, SignedValidatorRegistrationData)>,
+    validator_store: &ValidatorStore<T, E>,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    fee_recipient_file: Option<FeeRecipientFile>,
+    builder_proposals: bool,
+    log: &Logger,
+) {
+    let pubkeys: Vec<PublicKeyBytes> = validator_store.voting_pubkeys(DoppelgangerStatus::ignored);
+
+    // Forget validators which have been removed or no longer propose with the builder.
+    registrations.retain(|pubkey, _| {
+        pubkeys.contains(pubkey) && validator_store.builder_proposals(pubkey, builder_proposals)
+    });
+
+    for pubkey in pubkeys {
+        if !validator_store.builder_proposals(&pubkey, builder_proposals) {
+            continue;
+        }
+
+        // A fee recipient from the validator definitions takes precedence over the fee recipient
+        // file, as for block proposals.
+        let fee_recipient = match validator_store
+            .suggested_fee_recipient(&pubkey)
+            .or_else(|| {
+                fee_recipient_file
+                    .clone()
+                    .and_then(|mut f| match f.load_fee_recipient(&pubkey) {
+                        Ok(f) => f,
+                        Err(e) => {
+                            warn!(log, "Failed to read fee recipient file"; "error" => ?e);
+                            None
+                        }
+                    })
+            }) {
+            Some(fee_recipient) => fee_recipient,
+            None => {
+                warn!(
+                    log,
+                    "Not registering validator without a fee recipient";
+                    "pubkey" => ?pubkey,
+                );
+                continue;
+            }
+        };
+
+        let key = RegistrationKey {
+            fee_recipient,
+            gas_limit: validator_store.gas_limit(&pubkey),
+        };
+
+        if registrations
+            .get(&pubkey)
+            .map_or(false, |(existing, _)| *existing == key)
+        {
+            continue;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_else(|_| Duration::from_secs(0))
+            .as_secs();
+        let registration_data = ValidatorRegistrationData {
+            fee_recipient: key.fee_recipient,
+            gas_limit: key.gas_limit,
+            timestamp,
+            pubkey,
+        };
+
+        match validator_store
+            .sign_validator_registration_data(registration_data)
+            .await
+        {
+            Ok(signed) => {
+                registrations.insert(pubkey, (key, signed));
+            }
+            Err(e) => error!(
+                log,
+                "Unable to sign validator registration";
+                "error" => ?e,
+                "pubkey" => ?pubkey,
+            ),
+        }
+    }
+
+    if registrations.is_empty() {
+        return;
+    }
+
+    let signed: Vec<SignedValidatorRegistrationData> = registrations
+        .values()
+        .map(|(_, signed)| signed.clone())
+        .collect();
+    let signed_ref = &signed;
+    match beacon_nodes
+        .broadcast(RequireSynced::No, |beacon_node| async move {
+            beacon_node
+                .post_validator_register_validator(signed_ref)
+                .await
+        })
+        .await
+    {
+        Ok(()) => debug!(
+            log,
+            "Registered validators with builder";
+            "count" => signed.len(),
+        ),
+        Err(e) => warn!(
+            log,
+            "Unable to register validators with builder";
+            "error" => %e,
+            "count" => signed.len(),
+        ),
+    }
+}
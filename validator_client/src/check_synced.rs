@@ -4,7 +4,7 @@ use slog::{debug, error, warn, Logger};
 use slot_clock::SlotClock;
 
 /// A distance in slots.
-const SYNC_TOLERANCE: u64 = 4;
+pub const SYNC_TOLERANCE: u64 = 4;
 
 /// Returns
 ///
@@ -8,15 +8,6 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             "When connected to a beacon node, performs the duties of a staked \
                 validator (e.g., proposing blocks and attestations).",
         )
-        // This argument is deprecated, use `--beacon-nodes` instead.
-        .arg(
-            Arg::with_name("beacon-node")
-                .long("beacon-node")
-                .value_name("NETWORK_ADDRESS")
-                .help("Deprecated. Use --beacon-nodes.")
-                .takes_value(true)
-                .conflicts_with("beacon-nodes"),
-        )
         .arg(
             Arg::with_name("beacon-nodes")
                 .long("beacon-nodes")
@@ -26,15 +17,6 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 )
                 .takes_value(true),
         )
-        // This argument is deprecated, use `--beacon-nodes` instead.
-        .arg(
-            Arg::with_name("server")
-                .long("server")
-                .value_name("NETWORK_ADDRESS")
-                .help("Deprecated. Use --beacon-nodes.")
-                .takes_value(true)
-                .conflicts_with_all(&["beacon-node", "beacon-nodes"]),
-        )
         .arg(
             Arg::with_name("validators-dir")
                 .long("validators-dir")
@@ -78,6 +60,18 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                      misplace your database and then run with this flag you risk being slashed."
                 )
         )
+        .arg(
+            Arg::with_name("slashing-protection-history-epochs")
+                .long("slashing-protection-history-epochs")
+                .value_name("EPOCHS")
+                .help(
+                    "The number of epochs of signing history to keep in the slashing protection \
+                     database. Older blocks and attestations are pruned periodically, always \
+                     keeping the latest block and attestation of each validator."
+                )
+                .default_value("512")
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("disable-auto-discover")
             .long("disable-auto-discover")
@@ -86,6 +80,27 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 will need to be manually added to the validator_definitions.yml file."
             )
         )
+        .arg(
+            Arg::with_name("watch-validators")
+                .long("watch-validators")
+                .help(
+                    "If present, periodically check the validator_definitions.yml file and \
+                    (unless --disable-auto-discover is set) the validators-dir for new \
+                    validators, loading them without a restart. New validators are registered \
+                    with the slashing protection database."
+                )
+                .takes_value(false)
+        )
+        .arg(
+            Arg::with_name("keystore-decryption-threads")
+                .long("keystore-decryption-threads")
+                .value_name("THREADS")
+                .help(
+                    "The maximum number of validator keystores to decrypt in parallel when \
+                    loading validators. Defaults to one per CPU."
+                )
+                .takes_value(true)
+        )
         .arg(
             Arg::with_name("allow-unsynced")
                 .long("allow-unsynced")
@@ -111,6 +126,20 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                         in addition to the OS trust store. Commas must only be used as a \
                         delimiter, and must not be part of the certificate path.")
         )
+        .arg(
+            Arg::with_name("beacon-node-header")
+                .long("beacon-node-header")
+                .value_name("INDEX:NAME=VALUE")
+                .help("A custom HTTP header to send with every request to a beacon node, such as \
+                        the credentials of a beacon API provider. INDEX is the position of the \
+                        beacon node in --beacon-nodes, starting from 0. For example, \
+                        `--beacon-node-header \"1:Authorization=Bearer <TOKEN>\"`. May be \
+                        provided multiple times. Basic authentication credentials may instead \
+                        be given in the beacon node URL.")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+        )
         // This overwrites the graffiti configured in the beacon node.
         .arg(
             Arg::with_name("graffiti")
@@ -127,6 +156,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .conflicts_with("graffiti")
         )
+        .arg(
+            Arg::with_name("suggested-fee-recipient-file")
+                .long("suggested-fee-recipient-file")
+                .help("Specify a file to load validator fee recipients from. The file is re-read \
+                       before each block proposal, so changes take effect without a restart. \
+                       A fee recipient set for a validator in validator_definitions.yml takes \
+                       precedence over this file.")
+                .value_name("FEE-RECIPIENT-FILE")
+                .takes_value(true)
+        )
         /* REST API related arguments */
         .arg(
             Arg::with_name("http")
@@ -178,6 +217,30 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     address of this server (e.g., http://localhost:5062).")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("http-allow-methods")
+                .long("http-allow-methods")
+                .value_name("METHODS")
+                .help("Set the comma-separated list of methods in the Access-Control-Allow-Methods \
+                    response HTTP header, replacing the default of GET, POST, PATCH, DELETE.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-allow-headers")
+                .long("http-allow-headers")
+                .value_name("HEADERS")
+                .help("Set the comma-separated list of headers in the Access-Control-Allow-Headers \
+                    response HTTP header, replacing the default of Content-Type, Authorization.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-allow-credentials")
+                .long("http-allow-credentials")
+                .help("Allow browsers to include credentials in CORS requests by setting the \
+                    Access-Control-Allow-Credentials response HTTP header. Cannot be used if any \
+                    of the allowed origins, methods or headers is *.")
+                .takes_value(false),
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::with_name("metrics")
@@ -211,6 +274,30 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     address of this server (e.g., http://localhost:5064).")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("metrics-allow-methods")
+                .long("metrics-allow-methods")
+                .value_name("METHODS")
+                .help("Set the comma-separated list of methods in the Access-Control-Allow-Methods \
+                    response HTTP header, replacing the default of GET.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metrics-allow-headers")
+                .long("metrics-allow-headers")
+                .value_name("HEADERS")
+                .help("Set the comma-separated list of headers in the Access-Control-Allow-Headers \
+                    response HTTP header, replacing the default of Content-Type.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("metrics-allow-credentials")
+                .long("metrics-allow-credentials")
+                .help("Allow browsers to include credentials in CORS requests by setting the \
+                    Access-Control-Allow-Credentials response HTTP header. Cannot be used if any \
+                    of the allowed origins, methods or headers is *.")
+                .takes_value(false),
+        )
         /*
          * Explorer metrics
          */
@@ -226,6 +313,24 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 and never provide an untrusted URL.")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("monitoring-endpoint-period")
+                .long("monitoring-endpoint-period")
+                .value_name("SECONDS")
+                .help("Defines how many seconds to wait between each message sent to \
+                       the monitoring-endpoint. Default: 60s")
+                .requires("monitoring-endpoint")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("monitoring-endpoint-skip-system")
+                .long("monitoring-endpoint-skip-system")
+                .help("Do not send system metrics (CPU, memory, disk, network and operating \
+                       system) to the monitoring-endpoint. Only the metrics of the Lighthouse \
+                       process are sent.")
+                .requires("monitoring-endpoint")
+                .takes_value(false),
+        )
         .arg(
             Arg::with_name("enable-doppelganger-protection")
                 .long("enable-doppelganger-protection")
@@ -241,4 +346,77 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                     immediately.")
                 .takes_value(false),
         )
+        .arg(
+            Arg::with_name("attest-to-optimistic-head")
+                .long("attest-to-optimistic-head")
+                .help("By default, if the head of the beacon node has an execution payload which \
+                    has not been verified by an execution engine (i.e., it was imported \
+                    optimistically), Lighthouse will attest to the most recent verified ancestor \
+                    of the head instead. If this flag is set, Lighthouse will attest to the \
+                    unverified head. This risks attesting to an invalid chain and is only \
+                    intended for advanced users.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("attest-early-when-block-seen")
+                .long("attest-early-when-block-seen")
+                .help("By default, attestations are produced 1/3rd of the way through each slot. \
+                    If this flag is set, attestations are produced as soon as the block for the \
+                    slot becomes the head of the beacon node, which improves the chance of voting \
+                    for the correct head. Attestations are never produced later than usual, and \
+                    are produced at the usual time if the block is not seen or the beacon node's \
+                    event stream is unavailable.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("distributed")
+                .long("distributed")
+                .help("Enables the functionality required to run the validators in a distributed \
+                    validator cluster, with --beacon-nodes pointing to the distributed validator \
+                    middleware. Selection proofs for aggregation duties are sent to the \
+                    middleware, and aggregators are selected using the proofs it combines from \
+                    all operators in the cluster.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("disable-publication-broadcast")
+                .long("disable-publication-broadcast")
+                .help("By default, when multiple beacon nodes are provided with --beacon-nodes, \
+                    blocks, attestations and sync committee messages are published to all \
+                    healthy beacon nodes. If this flag is set, they will only be published to the \
+                    first beacon node which accepts them.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("halt-on-slashing")
+                .long("halt-on-slashing")
+                .value_name("MODE")
+                .help("The action to take if the beacon node reports that a validator managed by \
+                    this client has been slashed. A slashing is always logged as critical. If set \
+                    to \"key\", the slashed validator is disabled. If set to \"all\", the \
+                    validator client shuts down. The beacon node only reports slashings of \
+                    validators it monitors, see --validator-monitor-auto.")
+                .possible_values(&["key", "all"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("slashing-webhook")
+                .long("slashing-webhook")
+                .value_name("URL")
+                .help("A URL to which an alert is sent, as a JSON POST request, if the beacon \
+                    node reports that a validator managed by this client has been slashed. The \
+                    request body is the beacon node's validator_slashing event.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("builder-proposals")
+                .long("builder-proposals")
+                .help("If this flag is set, Lighthouse will register its validators with the \
+                    builder connected to the beacon node (see the beacon node's --builder flag) \
+                    and will propose blocks with payloads from that builder. If the builder fails \
+                    to provide a block, Lighthouse falls back to a locally built block. This may \
+                    be overridden for each validator with `builder_proposals` in \
+                    validator_definitions.yml.")
+                .takes_value(false),
+        )
 }
@@ -0,0 +1,130 @@
+//! Estimates the offset between the clock of the validator client and the clock of a beacon node.
+//!
+//! The validator client schedules its duties using its own slot clock, so it does not rely upon a
+//! beacon node being available to know the current slot. However, if the clocks of the two hosts
+//! disagree then objects may be produced too early or too late to be useful. This module tracks
+//! the disagreement so that it can be reported to the user.
+//!
+//! Samples are taken from the `Date` header of beacon node responses. The header only has a
+//! resolution of one second, however the error introduced by this truncation is uniformly
+//! distributed and is averaged away by smoothing many samples.
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// Samples whose round-trip time exceeds this value are discarded as being too imprecise.
+const MAX_ROUND_TRIP_TIME: Duration = Duration::from_secs(1);
+
+/// The number of samples required before an estimate is reported.
+const MIN_SAMPLES: usize = 8;
+
+/// Each sample is weighted `1 / SMOOTHING_FACTOR` in the moving average.
+const SMOOTHING_FACTOR: i64 = 8;
+
+/// An estimate of the offset of a beacon node's clock from our own.
+#[derive(Debug, Default)]
+pub struct ClockOffset {
+    /// The smoothed offset, in milliseconds. Positive values indicate that the beacon node's
+    /// clock is ahead of ours.
+    offset_millis: i64,
+    num_samples: usize,
+    /// Set to `true` when the estimate exceeds the user-facing threshold, to avoid repeatedly
+    /// logging the same disagreement.
+    pub disagrees: bool,
+}
+
+impl ClockOffset {
+    /// Returns the estimated offset in milliseconds, if enough samples have been collected.
+    pub fn offset_millis(&self) -> Option<i64> {
+        if self.num_samples >= MIN_SAMPLES {
+            Some(self.offset_millis)
+        } else {
+            None
+        }
+    }
+
+    /// Adds a sample for a request sent at `request_start` and answered at `request_end`, where the
+    /// beacon node reported its time as `server_time`. All times are durations since the UNIX epoch
+    /// and `server_time` is assumed to be truncated to the second.
+    ///
+    /// Returns `false` if the sample was discarded.
+    pub fn add_sample(
+        &mut self,
+        request_start: Duration,
+        request_end: Duration,
+        server_time: Duration,
+    ) -> bool {
+        let round_trip_time = match request_end.checked_sub(request_start) {
+            Some(rtt) if rtt <= MAX_ROUND_TRIP_TIME => rtt,
+            _ => return false,
+        };
+
+        // Assume that the server time was read half-way through the request and that, on average,
+        // it was truncated by half a second.
+        let local_millis = millis(request_start) + millis(round_trip_time) / 2;
+        let server_millis = millis(server_time) + 500;
+        let sample = server_millis - local_millis;
+
+        if self.num_samples == 0 {
+            self.offset_millis = sample;
+        } else {
+            self.offset_millis += (sample - self.offset_millis) / SMOOTHING_FACTOR;
+        }
+        self.num_samples = self.num_samples.saturating_add(1);
+
+        true
+    }
+}
+
+fn millis(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_estimate_without_enough_samples() {
+        let mut offset = ClockOffset::default();
+        let start = Duration::from_secs(100);
+        for _ in 0..MIN_SAMPLES - 1 {
+            assert!(offset.add_sample(start, start, start));
+        }
+        assert_eq!(offset.offset_millis(), None);
+        assert!(offset.add_sample(start, start, start));
+        assert!(offset.offset_millis().is_some());
+    }
+
+    #[test]
+    fn slow_samples_are_discarded() {
+        let mut offset = ClockOffset::default();
+        let start = Duration::from_secs(100);
+        let end = start + MAX_ROUND_TRIP_TIME + Duration::from_millis(1);
+        assert!(!offset.add_sample(start, end, start));
+        assert!(!offset.add_sample(end, start, start));
+    }
+
+    #[test]
+    fn truncation_is_averaged_away() {
+        for true_offset in &[-3_000_i64, -250, 0, 250, 3_000] {
+            let mut offset = ClockOffset::default();
+
+            // Sample at many different sub-second phases, as happens in practice.
+            for i in 0..256_u64 {
+                let local = Duration::from_millis(1_000_000 + i * 12_618);
+                let server = millis(local) + true_offset;
+                let truncated = Duration::from_secs(server as u64 / 1_000);
+                assert!(offset.add_sample(local, local, truncated));
+            }
+
+            let estimate = offset.offset_millis().unwrap();
+            assert!(
+                (estimate - true_offset).abs() < 250,
+                "estimate {} too far from {}",
+                estimate,
+                true_offset
+            );
+        }
+    }
+}
@@ -1,4 +1,7 @@
+use crate::fee_recipient_file::FeeRecipientFile;
 use crate::graffiti_file::GraffitiFile;
+use crate::slashing_alarm::SlashingHalt;
+use crate::validator_store::DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS;
 use crate::{http_api, http_metrics};
 use clap::ArgMatches;
 use clap_utils::{parse_optional, parse_required};
@@ -6,10 +9,12 @@ use directory::{
     get_network_dir, DEFAULT_HARDCODED_NETWORK, DEFAULT_ROOT_DIR, DEFAULT_SECRET_DIR,
     DEFAULT_VALIDATOR_DIR,
 };
+use eth2::reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use eth2::types::Graffiti;
 use sensitive_url::SensitiveUrl;
 use serde_derive::{Deserialize, Serialize};
 use slog::{info, warn, Logger};
+use std::collections::HashMap;
 use std::fs;
 use std::net::Ipv4Addr;
 use std::path::PathBuf;
@@ -41,6 +46,8 @@ pub struct Config {
     pub graffiti: Option<Graffiti>,
     /// Graffiti file to load per validator graffitis.
     pub graffiti_file: Option<GraffitiFile>,
+    /// Fee recipient file to load per validator fee recipients.
+    pub fee_recipient_file: Option<FeeRecipientFile>,
     /// Configuration for the HTTP REST API.
     pub http_api: http_api::Config,
     /// Configuration for the HTTP REST API.
@@ -53,6 +60,35 @@ pub struct Config {
     /// A list of custom certificates that the validator client will additionally use when
     /// connecting to a beacon node over SSL/TLS.
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
+    /// Custom HTTP headers, such as `Authorization`, to send with every request to the beacon
+    /// node at the given index of `beacon_nodes`.
+    pub beacon_node_headers: HashMap<usize, Vec<(String, String)>>,
+    /// If true, attest to the head of the beacon node even if its execution payload has only been
+    /// optimistically imported, rather than to its most recent verified ancestor.
+    pub attest_to_optimistic_head: bool,
+    /// If true, publish blocks, attestations and sync committee messages to all healthy beacon
+    /// nodes rather than only the first to accept them.
+    pub broadcast_publications: bool,
+    /// The action to take if a local validator is reported as slashed by the beacon node, if any.
+    pub halt_on_slashing: Option<SlashingHalt>,
+    /// A URL to notify if a local validator is reported as slashed by the beacon node, if any.
+    pub slashing_webhook: Option<SensitiveUrl>,
+    /// If true, propose blocks with payloads from an external builder, unless overridden in the
+    /// validator definitions.
+    pub builder_proposals: bool,
+    /// If true, produce attestations as soon as the block for the slot becomes the head of the
+    /// beacon node, rather than always waiting 1/3rd of the way through the slot.
+    pub attest_early_when_block_seen: bool,
+    /// If true, the validators are part of a distributed validator cluster and the beacon node
+    /// URLs point to the distributed validator middleware.
+    pub distributed: bool,
+    /// If true, load validators which are added to the validators directory whilst running.
+    pub watch_validators: bool,
+    /// The maximum number of keystores to decrypt in parallel at startup, or `None` for one per
+    /// CPU.
+    pub keystore_decryption_threads: Option<usize>,
+    /// The number of epochs of signing history to keep in the slashing protection database.
+    pub slashing_protection_history_epochs: u64,
 }
 
 impl Default for Config {
@@ -79,11 +115,23 @@ impl Default for Config {
             use_long_timeouts: false,
             graffiti: None,
             graffiti_file: None,
+            fee_recipient_file: None,
             http_api: <_>::default(),
             http_metrics: <_>::default(),
             monitoring_api: None,
             enable_doppelganger_protection: false,
             beacon_nodes_tls_certs: None,
+            beacon_node_headers: HashMap::new(),
+            attest_to_optimistic_head: false,
+            broadcast_publications: true,
+            halt_on_slashing: None,
+            slashing_webhook: None,
+            builder_proposals: false,
+            attest_early_when_block_seen: false,
+            distributed: false,
+            watch_validators: false,
+            keystore_decryption_threads: None,
+            slashing_protection_history_epochs: DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS,
         }
     }
 }
@@ -135,26 +183,6 @@ impl Config {
                 .collect::<Result<_, _>>()
                 .map_err(|e| format!("Unable to parse beacon node URL: {:?}", e))?;
         }
-        // To be deprecated.
-        else if let Some(beacon_node) = parse_optional::<String>(cli_args, "beacon-node")? {
-            warn!(
-                log,
-                "The --beacon-node flag is deprecated";
-                "msg" => "please use --beacon-nodes instead"
-            );
-            config.beacon_nodes = vec![SensitiveUrl::parse(&beacon_node)
-                .map_err(|e| format!("Unable to parse beacon node URL: {:?}", e))?];
-        }
-        // To be deprecated.
-        else if let Some(server) = parse_optional::<String>(cli_args, "server")? {
-            warn!(
-                log,
-                "The --server flag is deprecated";
-                "msg" => "please use --beacon-nodes instead"
-            );
-            config.beacon_nodes = vec![SensitiveUrl::parse(&server)
-                .map_err(|e| format!("Unable to parse beacon node URL: {:?}", e))?];
-        }
 
         if cli_args.is_present("delete-lockfiles") {
             warn!(
@@ -166,7 +194,22 @@ impl Config {
 
         config.allow_unsynced_beacon_node = cli_args.is_present("allow-unsynced");
         config.disable_auto_discover = cli_args.is_present("disable-auto-discover");
+        config.watch_validators = cli_args.is_present("watch-validators");
+
+        if let Some(threads) = parse_optional::<usize>(cli_args, "keystore-decryption-threads")? {
+            if threads == 0 {
+                return Err("--keystore-decryption-threads must be at least 1".to_string());
+            }
+            config.keystore_decryption_threads = Some(threads);
+        }
         config.init_slashing_protection = cli_args.is_present("init-slashing-protection");
+        if let Some(epochs) = parse_optional::<u64>(cli_args, "slashing-protection-history-epochs")?
+        {
+            if epochs == 0 {
+                return Err("--slashing-protection-history-epochs must be at least 1".to_string());
+            }
+            config.slashing_protection_history_epochs = epochs;
+        }
         config.use_long_timeouts = cli_args.is_present("use-long-timeouts");
 
         if let Some(graffiti_file_path) = cli_args.value_of("graffiti-file") {
@@ -178,6 +221,19 @@ impl Config {
             info!(log, "Successfully loaded graffiti file"; "path" => graffiti_file_path);
         }
 
+        if let Some(fee_recipient_file_path) = cli_args.value_of("suggested-fee-recipient-file") {
+            let mut fee_recipient_file = FeeRecipientFile::new(fee_recipient_file_path.into());
+            fee_recipient_file
+                .read_fee_recipient_file()
+                .map_err(|e| format!("Error reading fee recipient file: {:?}", e))?;
+            config.fee_recipient_file = Some(fee_recipient_file);
+            info!(
+                log,
+                "Successfully loaded fee recipient file";
+                "path" => fee_recipient_file_path
+            );
+        }
+
         if let Some(input_graffiti) = cli_args.value_of("graffiti") {
             let graffiti_bytes = input_graffiti.as_bytes();
             if graffiti_bytes.len() > GRAFFITI_BYTES_LEN {
@@ -201,6 +257,26 @@ impl Config {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
 
+        if let Some(headers) = cli_args.values_of("beacon-node-header") {
+            for header in headers {
+                let (index, name, value) = parse_beacon_node_header(header)?;
+                if index >= config.beacon_nodes.len() {
+                    return Err(format!(
+                        "--beacon-node-header index {} does not match any beacon node",
+                        index
+                    ));
+                }
+                config
+                    .beacon_node_headers
+                    .entry(index)
+                    .or_default()
+                    .push((name, value));
+            }
+            for index in config.beacon_node_headers.keys() {
+                config.beacon_node_header_map(*index)?;
+            }
+        }
+
         /*
          * Http API server
          */
@@ -237,6 +313,18 @@ impl Config {
             config.http_api.allow_origin = Some(allow_origin.to_string());
         }
 
+        if let Some(allow_methods) = cli_args.value_of("http-allow-methods") {
+            config.http_api.allow_methods = Some(allow_methods.to_string());
+        }
+
+        if let Some(allow_headers) = cli_args.value_of("http-allow-headers") {
+            config.http_api.allow_headers = Some(allow_headers.to_string());
+        }
+
+        if cli_args.is_present("http-allow-credentials") {
+            config.http_api.allow_credentials = true;
+        }
+
         /*
          * Prometheus metrics HTTP server
          */
@@ -265,14 +353,33 @@ impl Config {
 
             config.http_metrics.allow_origin = Some(allow_origin.to_string());
         }
+
+        if let Some(allow_methods) = cli_args.value_of("metrics-allow-methods") {
+            config.http_metrics.allow_methods = Some(allow_methods.to_string());
+        }
+
+        if let Some(allow_headers) = cli_args.value_of("metrics-allow-headers") {
+            config.http_metrics.allow_headers = Some(allow_headers.to_string());
+        }
+
+        if cli_args.is_present("metrics-allow-credentials") {
+            config.http_metrics.allow_credentials = true;
+        }
         /*
          * Explorer metrics
          */
         if let Some(monitoring_endpoint) = cli_args.value_of("monitoring-endpoint") {
+            let update_period_secs = parse_optional(cli_args, "monitoring-endpoint-period")?;
+            if update_period_secs == Some(0) {
+                return Err("--monitoring-endpoint-period must be greater than 0".to_string());
+            }
+
             config.monitoring_api = Some(monitoring_api::Config {
                 db_path: None,
                 freezer_db_path: None,
                 monitoring_endpoint: monitoring_endpoint.to_string(),
+                update_period_secs,
+                skip_system_metrics: cli_args.is_present("monitoring-endpoint-skip-system"),
             });
         }
 
@@ -280,8 +387,66 @@ impl Config {
             config.enable_doppelganger_protection = true;
         }
 
+        if cli_args.is_present("attest-to-optimistic-head") {
+            config.attest_to_optimistic_head = true;
+        }
+
+        if cli_args.is_present("disable-publication-broadcast") {
+            config.broadcast_publications = false;
+        }
+
+        config.halt_on_slashing = parse_optional(cli_args, "halt-on-slashing")?;
+
+        if let Some(webhook) = cli_args.value_of("slashing-webhook") {
+            config.slashing_webhook = Some(
+                SensitiveUrl::parse(webhook)
+                    .map_err(|e| format!("Unable to parse slashing webhook URL: {:?}", e))?,
+            );
+        }
+
+        if cli_args.is_present("builder-proposals") {
+            config.builder_proposals = true;
+        }
+
+        if cli_args.is_present("attest-early-when-block-seen") {
+            config.attest_early_when_block_seen = true;
+        }
+
+        if cli_args.is_present("distributed") {
+            config.distributed = true;
+        }
+
         Ok(config)
     }
+
+    /// Returns the custom headers to send to the beacon node at `index` of `beacon_nodes`.
+    ///
+    /// Header values are marked as sensitive so that they are never logged.
+    pub fn beacon_node_header_map(&self, index: usize) -> Result<HeaderMap, String> {
+        let mut header_map = HeaderMap::new();
+        for (name, value) in self.beacon_node_headers.get(&index).into_iter().flatten() {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| format!("Invalid beacon node header name {}: {:?}", name, e))?;
+            let mut value = HeaderValue::from_str(value)
+                .map_err(|e| format!("Invalid value for beacon node header {}: {:?}", name, e))?;
+            value.set_sensitive(true);
+            header_map.append(name, value);
+        }
+        Ok(header_map)
+    }
+}
+
+/// Parses a `--beacon-node-header` value of the form `INDEX:NAME=VALUE`.
+fn parse_beacon_node_header(header: &str) -> Result<(usize, String, String), String> {
+    let invalid = || {
+        "--beacon-node-header must be of the form INDEX:NAME=VALUE, e.g. \
+         0:Authorization=Bearer <TOKEN>"
+            .to_string()
+    };
+    let (index, header) = header.split_once(':').ok_or_else(invalid)?;
+    let (name, value) = header.split_once('=').ok_or_else(invalid)?;
+    let index = index.trim().parse().map_err(|_| invalid())?;
+    Ok((index, name.trim().to_string(), value.trim().to_string()))
 }
 
 #[cfg(test)]
@@ -6,6 +6,7 @@
 //! The `DutiesService` is also responsible for sending events to the `BlockService` which trigger
 //! block production.
 
+mod persist;
 mod sync;
 
 use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
@@ -15,13 +16,19 @@ use crate::{
     validator_store::{DoppelgangerStatus, Error as ValidatorStoreError, ValidatorStore},
 };
 use environment::RuntimeContext;
-use eth2::types::{AttesterData, BeaconCommitteeSubscription, ProposerData, StateId, ValidatorId};
+use eth2::types::{
+    AttesterData, BeaconCommitteeSelection, BeaconCommitteeSubscription, ProposerData, StateId,
+    ValidatorId,
+};
 use futures::future::join_all;
 use parking_lot::RwLock;
+use persist::persist_duties;
+pub use persist::{duties_cache_path, load_duties};
 use safe_arith::ArithError;
 use slog::{debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 use sync::poll_sync_committee_duties;
 use sync::SyncDutiesMap;
@@ -44,6 +51,7 @@ pub enum Error {
     UnableToReadSlotClock,
     FailedToDownloadAttesters(String),
     FailedToProduceSelectionProof(ValidatorStoreError),
+    FailedToCombineSelectionProof(String),
     InvalidModulo(ArithError),
     Arith(ArithError),
     SyncDutiesNotFound(u64),
@@ -75,6 +83,15 @@ impl DutyAndProof {
             .await
             .map_err(Error::FailedToProduceSelectionProof)?;
 
+        Self::new_with_selection_proof(duty, selection_proof, spec)
+    }
+
+    /// Instantiate `Self` from a selection proof which has already been computed.
+    pub fn new_with_selection_proof(
+        duty: AttesterData,
+        selection_proof: SelectionProof,
+        spec: &ChainSpec,
+    ) -> Result<Self, Error> {
         let selection_proof = selection_proof
             .is_aggregator(duty.committee_length as usize, spec)
             .map_err(Error::InvalidModulo)
@@ -121,6 +138,14 @@ pub struct DutiesService<T, E: EthSpec> {
     /// This functionality is a little redundant since most BNs will likely reject duties when they
     /// aren't synced, but we keep it around for an emergency.
     pub require_synced: RequireSynced,
+    /// If true, the validators are part of a distributed validator cluster, so selection proofs
+    /// are combined with those of the other operators by the middleware before use.
+    pub distributed: bool,
+    /// The file to which upcoming attester and proposer duties are persisted, so they are
+    /// available immediately after a restart.
+    pub duties_cache_path: PathBuf,
+    /// Serializes writes to `duties_cache_path`.
+    pub persist_lock: tokio::sync::Mutex<()>,
     pub context: RuntimeContext<E>,
     pub spec: ChainSpec,
 }
@@ -669,14 +694,19 @@ async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
     );
 
     // Produce the `DutyAndProof` messages in parallel.
-    let duty_and_proof_results = join_all(new_duties.into_iter().map(|duty| {
-        DutyAndProof::new(duty, &duties_service.validator_store, &duties_service.spec)
-    }))
-    .await;
+    let duty_and_proof_results = if duties_service.distributed {
+        produce_distributed_duty_and_proofs(duties_service, new_duties).await
+    } else {
+        join_all(new_duties.into_iter().map(|duty| {
+            DutyAndProof::new(duty, &duties_service.validator_store, &duties_service.spec)
+        }))
+        .await
+    };
 
     // Update the duties service with the new `DutyAndProof` messages.
     let mut attesters = duties_service.attesters.write();
     let mut already_warned = Some(());
+    let mut num_updated = 0;
     for result in duty_and_proof_results {
         let duty_and_proof = match result {
             Ok(duty_and_proof) => duty_and_proof,
@@ -693,6 +723,7 @@ async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
         };
 
         let attester_map = attesters.entry(duty_and_proof.duty.pubkey).or_default();
+        num_updated += 1;
 
         if let Some((prior_dependent_root, _)) =
             attester_map.insert(epoch, (dependent_root, duty_and_proof))
@@ -711,6 +742,10 @@ async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
     }
     drop(attesters);
 
+    if num_updated > 0 {
+        persist_duties(duties_service).await;
+    }
+
     Ok(())
 }
 
@@ -806,11 +841,21 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
                     "num_relevant_duties" => relevant_duties.len(),
                 );
 
-                if let Some((prior_dependent_root, _)) = duties_service
-                    .proposers
-                    .write()
-                    .insert(current_epoch, (dependent_root, relevant_duties))
-                {
+                let mut proposers = duties_service.proposers.write();
+                let changed = proposers.get(&current_epoch).map_or(
+                    true,
+                    |(prior_dependent_root, prior_duties)| {
+                        *prior_dependent_root != dependent_root || *prior_duties != relevant_duties
+                    },
+                );
+                let prior = proposers.insert(current_epoch, (dependent_root, relevant_duties));
+                drop(proposers);
+
+                if changed {
+                    persist_duties(duties_service).await;
+                }
+
+                if let Some((prior_dependent_root, _)) = prior {
                     if dependent_root != prior_dependent_root {
                         warn!(
                             log,
@@ -872,6 +917,96 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     Ok(())
 }
 
+/// Produce `DutyAndProof` messages for a distributed validator.
+///
+/// The selection proof signed by this validator client is only a partial signature. All of the
+/// partial proofs are sent to the middleware in a single request, which returns the combined
+/// proofs once the other operators in the cluster have sent theirs. Aggregators are selected using
+/// the combined proofs.
+async fn produce_distributed_duty_and_proofs<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    duties: Vec<AttesterData>,
+) -> Vec<Result<DutyAndProof, Error>> {
+    if duties.is_empty() {
+        return vec![];
+    }
+
+    let partial_proofs = join_all(duties.iter().map(|duty| {
+        duties_service
+            .validator_store
+            .produce_selection_proof(duty.pubkey, duty.slot)
+    }))
+    .await;
+
+    let mut results = Vec::with_capacity(duties.len());
+    let mut selections = vec![];
+    let mut pending_duties = vec![];
+    for (duty, partial_proof) in duties.into_iter().zip(partial_proofs) {
+        match partial_proof {
+            Ok(proof) => {
+                selections.push(BeaconCommitteeSelection {
+                    validator_index: duty.validator_index,
+                    slot: duty.slot,
+                    selection_proof: proof.into(),
+                });
+                pending_duties.push(duty);
+            }
+            Err(e) => results.push(Err(Error::FailedToProduceSelectionProof(e))),
+        }
+    }
+
+    if pending_duties.is_empty() {
+        return results;
+    }
+
+    let selections = &selections;
+    let combined_proofs = match duties_service
+        .beacon_nodes
+        .first_success(duties_service.require_synced, |beacon_node| async move {
+            beacon_node
+                .post_validator_beacon_committee_selections(selections)
+                .await
+        })
+        .await
+    {
+        Ok(response) => response
+            .data
+            .into_iter()
+            .map(|selection| {
+                (
+                    (selection.validator_index, selection.slot),
+                    selection.selection_proof,
+                )
+            })
+            .collect::<HashMap<_, _>>(),
+        Err(e) => {
+            let error = e.to_string();
+            results.extend(
+                pending_duties
+                    .into_iter()
+                    .map(|_| Err(Error::FailedToCombineSelectionProof(error.clone()))),
+            );
+            return results;
+        }
+    };
+
+    results.extend(pending_duties.into_iter().map(|duty| {
+        match combined_proofs.get(&(duty.validator_index, duty.slot)) {
+            Some(proof) => DutyAndProof::new_with_selection_proof(
+                duty,
+                proof.clone().into(),
+                &duties_service.spec,
+            ),
+            None => Err(Error::FailedToCombineSelectionProof(format!(
+                "no combined selection proof for validator {} at slot {}",
+                duty.validator_index, duty.slot
+            ))),
+        }
+    }));
+
+    results
+}
+
 /// Notify the block service if it should produce a block.
 async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
     current_slot: Slot,
@@ -0,0 +1,458 @@
+//! Persistence of upcoming attester and proposer duties to disk.
+//!
+//! After a restart, the persisted duties are loaded so that attestations and blocks can be
+//! produced immediately, rather than only once the duties have been downloaded from the beacon
+//! node. Loaded duties are replaced as usual by the next poll if their dependent root has changed.
+
+use crate::doppelganger_service::DoppelgangerStatus;
+use crate::duties_service::{AttesterMap, DutiesService, DutyAndProof, ProposerMap};
+use account_utils::write_file_via_temporary;
+use eth2::types::{AttesterData, ProposerData};
+use serde_derive::{Deserialize, Serialize};
+use slog::{debug, info, warn};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use types::{Epoch, EthSpec, Hash256, PublicKeyBytes, Signature};
+
+/// The file name for the persisted duties, in the validators directory.
+pub const DUTIES_CACHE_FILENAME: &str = "duties_cache.json";
+
+/// The file name for the temporary persisted duties.
+pub const TEMP_DUTIES_CACHE_FILENAME: &str = ".duties_cache.json.tmp";
+
+#[derive(Serialize, Deserialize)]
+struct PersistedDuties {
+    genesis_validators_root: Hash256,
+    attesters: Vec<PersistedAttesterDuty>,
+    proposers: Vec<PersistedProposerDuties>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedAttesterDuty {
+    epoch: Epoch,
+    dependent_root: Hash256,
+    duty: AttesterData,
+    /// Only present if the validator is an aggregator, as in `DutyAndProof`.
+    selection_proof: Option<Signature>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedProposerDuties {
+    epoch: Epoch,
+    dependent_root: Hash256,
+    duties: Vec<ProposerData>,
+}
+
+/// Returns the path of the persisted duties in `validators_dir`.
+pub fn duties_cache_path<P: AsRef<Path>>(validators_dir: P) -> PathBuf {
+    validators_dir.as_ref().join(DUTIES_CACHE_FILENAME)
+}
+
+impl PersistedDuties {
+    /// Returns the duties in `attesters` and `proposers` for `current_epoch` and later epochs.
+    fn new(
+        attesters: &AttesterMap,
+        proposers: &ProposerMap,
+        genesis_validators_root: Hash256,
+        current_epoch: Epoch,
+    ) -> Self {
+        let attesters = attesters
+            .values()
+            .flat_map(|map| map.iter())
+            .filter(|(epoch, _)| **epoch >= current_epoch)
+            .map(
+                |(epoch, (dependent_root, duty_and_proof))| PersistedAttesterDuty {
+                    epoch: *epoch,
+                    dependent_root: *dependent_root,
+                    duty: duty_and_proof.duty.clone(),
+                    selection_proof: duty_and_proof.selection_proof.clone().map(Into::into),
+                },
+            )
+            .collect();
+        let proposers = proposers
+            .iter()
+            .filter(|(epoch, _)| **epoch >= current_epoch)
+            .map(
+                |(epoch, (dependent_root, duties))| PersistedProposerDuties {
+                    epoch: *epoch,
+                    dependent_root: *dependent_root,
+                    duties: duties.clone(),
+                },
+            )
+            .collect();
+
+        Self {
+            genesis_validators_root,
+            attesters,
+            proposers,
+        }
+    }
+
+    /// Writes `self` to `path`, replacing any existing file atomically.
+    fn write(&self, path: &Path) -> Result<(), String> {
+        let bytes = serde_json::to_vec(self).map_err(|e| format!("{:?}", e))?;
+        let temp_path = path.with_file_name(TEMP_DUTIES_CACHE_FILENAME);
+        write_file_via_temporary(path, &temp_path, &bytes).map_err(|e| format!("{:?}", e))
+    }
+
+    /// Reads the duties at `path`, which must have been persisted for the network with
+    /// `genesis_validators_root`.
+    fn read(path: &Path, genesis_validators_root: Hash256) -> Result<Self, String> {
+        let persisted: Self = File::open(path)
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|file| serde_json::from_reader(file).map_err(|e| format!("{:?}", e)))?;
+
+        if persisted.genesis_validators_root != genesis_validators_root {
+            return Err(format!(
+                "duties were persisted for another network with genesis validators root {:?}",
+                persisted.genesis_validators_root
+            ));
+        }
+
+        Ok(persisted)
+    }
+
+    /// Inserts the duties of the validators in `local_pubkeys` for `current_epoch` and later
+    /// epochs into `attesters` and `proposers`, returning the number of attester and proposer
+    /// duties inserted.
+    fn insert_into(
+        self,
+        attesters: &mut AttesterMap,
+        proposers: &mut ProposerMap,
+        local_pubkeys: &HashSet<PublicKeyBytes>,
+        current_epoch: Epoch,
+    ) -> (usize, usize) {
+        let mut num_attesters = 0;
+        for persisted_duty in self.attesters {
+            if persisted_duty.epoch < current_epoch
+                || !local_pubkeys.contains(&persisted_duty.duty.pubkey)
+            {
+                continue;
+            }
+            let duty_and_proof = DutyAndProof {
+                duty: persisted_duty.duty,
+                selection_proof: persisted_duty.selection_proof.map(Into::into),
+            };
+            attesters
+                .entry(duty_and_proof.duty.pubkey)
+                .or_default()
+                .insert(
+                    persisted_duty.epoch,
+                    (persisted_duty.dependent_root, duty_and_proof),
+                );
+            num_attesters += 1;
+        }
+
+        let mut num_proposers = 0;
+        for persisted_duties in self.proposers {
+            if persisted_duties.epoch < current_epoch {
+                continue;
+            }
+            let duties: Vec<ProposerData> = persisted_duties
+                .duties
+                .into_iter()
+                .filter(|duty| local_pubkeys.contains(&duty.pubkey))
+                .collect();
+            num_proposers += duties.len();
+            proposers.insert(
+                persisted_duties.epoch,
+                (persisted_duties.dependent_root, duties),
+            );
+        }
+
+        (num_attesters, num_proposers)
+    }
+}
+
+/// Write the attester and proposer duties for the current and later epochs to disk.
+///
+/// The file is written on a blocking thread, so that the fsync does not stall the runtime. Errors
+/// are logged rather than returned, since the duties are still held in memory.
+pub async fn persist_duties<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+) {
+    let log = duties_service.context.log().clone();
+    let current_epoch = match duties_service.slot_clock.now() {
+        Some(slot) => slot.epoch(E::slots_per_epoch()),
+        None => return,
+    };
+
+    // Hold the lock until the file is written, so that an older snapshot of the duties cannot
+    // replace a newer one.
+    let _persist_guard = duties_service.persist_lock.lock().await;
+    let persisted = PersistedDuties::new(
+        &duties_service.attesters.read(),
+        &duties_service.proposers.read(),
+        duties_service.validator_store.genesis_validators_root(),
+        current_epoch,
+    );
+    let path = duties_service.duties_cache_path.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        persisted
+            .write(&path)
+            .map(|()| (persisted.attesters.len(), persisted.proposers.len()))
+    })
+    .await
+    .map_err(|e| format!("{:?}", e))
+    .and_then(|result| result);
+    match result {
+        Ok((attesters, proposal_epochs)) => debug!(
+            log,
+            "Persisted duties";
+            "attesters" => attesters,
+            "proposal_epochs" => proposal_epochs,
+        ),
+        Err(e) => warn!(
+            log,
+            "Unable to persist duties";
+            "error" => e,
+            "path" => ?duties_service.duties_cache_path,
+        ),
+    }
+}
+
+/// Load any persisted attester and proposer duties for the current and later epochs into
+/// `duties_service`.
+///
+/// Duties persisted for another network or for validators which are no longer local are ignored.
+pub fn load_duties<T: SlotClock + 'static, E: EthSpec>(duties_service: &DutiesService<T, E>) {
+    let log = duties_service.context.log();
+    let path = &duties_service.duties_cache_path;
+    if !path.exists() {
+        return;
+    }
+
+    let current_epoch = match duties_service.slot_clock.now() {
+        Some(slot) => slot.epoch(E::slots_per_epoch()),
+        None => return,
+    };
+
+    let persisted = match PersistedDuties::read(
+        path,
+        duties_service.validator_store.genesis_validators_root(),
+    ) {
+        Ok(persisted) => persisted,
+        Err(e) => {
+            warn!(
+                log,
+                "Unable to load persisted duties";
+                "error" => e,
+                "path" => ?path,
+            );
+            return;
+        }
+    };
+
+    // Load the duties of all validators, even those undergoing doppelganger protection, as when
+    // polling the beacon node.
+    let local_pubkeys: HashSet<PublicKeyBytes> = duties_service
+        .validator_store
+        .voting_pubkeys(DoppelgangerStatus::ignored);
+
+    let (num_attesters, num_proposers) = persisted.insert_into(
+        &mut duties_service.attesters.write(),
+        &mut duties_service.proposers.write(),
+        &local_pubkeys,
+        current_epoch,
+    );
+
+    info!(
+        log,
+        "Loaded persisted duties";
+        "attesters" => num_attesters,
+        "proposers" => num_proposers,
+        "current_epoch" => current_epoch,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+    use types::{Keypair, SelectionProof};
+
+    fn pubkey(i: u8) -> PublicKeyBytes {
+        PublicKeyBytes::deserialize(&[i; 48]).unwrap()
+    }
+
+    fn attester_duty(i: u8, epoch: u64) -> AttesterData {
+        AttesterData {
+            pubkey: pubkey(i),
+            validator_index: i as u64,
+            committees_at_slot: 1,
+            committee_index: 0,
+            committee_length: 8,
+            validator_committee_index: i as u64,
+            slot: Epoch::new(epoch).start_slot(32) + i as u64,
+        }
+    }
+
+    fn proposer_duty(i: u8, epoch: u64) -> ProposerData {
+        ProposerData {
+            pubkey: pubkey(i),
+            validator_index: i as u64,
+            slot: Epoch::new(epoch).start_slot(32) + i as u64,
+        }
+    }
+
+    /// Returns duties for validators 1 and 2 in epochs 4 and 5.
+    fn duties() -> (AttesterMap, ProposerMap) {
+        let selection_proof = Keypair::random().sk.sign(Hash256::repeat_byte(1));
+        let mut attesters = AttesterMap::new();
+        for &i in &[1, 2] {
+            for &epoch in &[4, 5] {
+                let duty_and_proof = DutyAndProof {
+                    duty: attester_duty(i, epoch),
+                    selection_proof: Some(SelectionProof::from(selection_proof.clone()))
+                        .filter(|_| i == 1),
+                };
+                attesters.entry(pubkey(i)).or_default().insert(
+                    Epoch::new(epoch),
+                    (Hash256::repeat_byte(epoch as u8), duty_and_proof),
+                );
+            }
+        }
+
+        let mut proposers = ProposerMap::new();
+        for &epoch in &[4, 5] {
+            proposers.insert(
+                Epoch::new(epoch),
+                (
+                    Hash256::repeat_byte(epoch as u8),
+                    vec![proposer_duty(1, epoch), proposer_duty(2, epoch)],
+                ),
+            );
+        }
+
+        (attesters, proposers)
+    }
+
+    fn assert_attesters_eq(a: &AttesterMap, b: &AttesterMap) {
+        assert_eq!(a.len(), b.len());
+        for (pubkey, a_duties) in a {
+            let b_duties = &b[pubkey];
+            assert_eq!(a_duties.len(), b_duties.len());
+            for (epoch, (a_root, a_duty)) in a_duties {
+                let (b_root, b_duty) = &b_duties[epoch];
+                assert_eq!(a_root, b_root);
+                assert_eq!(a_duty.duty, b_duty.duty);
+                assert_eq!(a_duty.selection_proof, b_duty.selection_proof);
+            }
+        }
+    }
+
+    #[test]
+    fn persist_and_load_roundtrip() {
+        let dir = tempdir().unwrap();
+        let path = duties_cache_path(dir.path());
+        let genesis_validators_root = Hash256::repeat_byte(42);
+        let (attesters, proposers) = duties();
+
+        PersistedDuties::new(
+            &attesters,
+            &proposers,
+            genesis_validators_root,
+            Epoch::new(4),
+        )
+        .write(&path)
+        .unwrap();
+        // Persisting again replaces the file.
+        PersistedDuties::new(
+            &attesters,
+            &proposers,
+            genesis_validators_root,
+            Epoch::new(4),
+        )
+        .write(&path)
+        .unwrap();
+
+        let mut loaded_attesters = AttesterMap::new();
+        let mut loaded_proposers = ProposerMap::new();
+        let counts = PersistedDuties::read(&path, genesis_validators_root)
+            .unwrap()
+            .insert_into(
+                &mut loaded_attesters,
+                &mut loaded_proposers,
+                &[pubkey(1), pubkey(2)].iter().copied().collect(),
+                Epoch::new(4),
+            );
+
+        assert_eq!(counts, (4, 4));
+        assert_attesters_eq(&loaded_attesters, &attesters);
+        assert_eq!(loaded_proposers, proposers);
+    }
+
+    #[test]
+    fn load_skips_stale_and_foreign_duties() {
+        let dir = tempdir().unwrap();
+        let path = duties_cache_path(dir.path());
+        let genesis_validators_root = Hash256::repeat_byte(42);
+        let (attesters, proposers) = duties();
+
+        PersistedDuties::new(
+            &attesters,
+            &proposers,
+            genesis_validators_root,
+            Epoch::new(4),
+        )
+        .write(&path)
+        .unwrap();
+
+        // By the time the duties are loaded, epoch 4 has passed and validator 2 has been removed.
+        let mut loaded_attesters = AttesterMap::new();
+        let mut loaded_proposers = ProposerMap::new();
+        let counts = PersistedDuties::read(&path, genesis_validators_root)
+            .unwrap()
+            .insert_into(
+                &mut loaded_attesters,
+                &mut loaded_proposers,
+                &[pubkey(1)].iter().copied().collect(),
+                Epoch::new(5),
+            );
+
+        assert_eq!(counts, (1, 1));
+        assert_eq!(loaded_attesters.len(), 1);
+        assert_eq!(
+            loaded_attesters[&pubkey(1)].keys().collect::<Vec<_>>(),
+            vec![&Epoch::new(5)]
+        );
+        assert_eq!(loaded_proposers.len(), 1);
+        assert_eq!(
+            loaded_proposers[&Epoch::new(5)].1,
+            vec![proposer_duty(1, 5)]
+        );
+
+        // Duties from another network are rejected.
+        assert!(PersistedDuties::read(&path, Hash256::repeat_byte(43)).is_err());
+    }
+
+    #[test]
+    fn persist_skips_past_epochs() {
+        let (attesters, proposers) = duties();
+        let persisted =
+            PersistedDuties::new(&attesters, &proposers, Hash256::zero(), Epoch::new(5));
+
+        assert_eq!(persisted.attesters.len(), 2);
+        assert!(persisted.attesters.iter().all(|duty| duty.epoch == 5));
+        assert_eq!(persisted.proposers.len(), 1);
+        assert_eq!(persisted.proposers[0].epoch, 5);
+    }
+
+    #[test]
+    fn load_corrupt_file() {
+        let dir = tempdir().unwrap();
+        let path = duties_cache_path(dir.path());
+
+        fs::write(&path, b"{\"genesis_validators_root\": ").unwrap();
+        assert!(PersistedDuties::read(&path, Hash256::zero()).is_err());
+
+        fs::write(&path, b"").unwrap();
+        assert!(PersistedDuties::read(&path, Hash256::zero()).is_err());
+
+        assert!(PersistedDuties::read(&dir.path().join("missing.json"), Hash256::zero()).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::{
     doppelganger_service::DoppelgangerStatus,
     duties_service::{DutiesService, Error},
 };
+use eth2::types::SyncCommitteeSelection;
 use futures::future::join_all;
 use itertools::Itertools;
 use parking_lot::{MappedRwLockReadGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
@@ -10,12 +11,19 @@ use slot_clock::SlotClock;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use types::{
-    ChainSpec, Epoch, EthSpec, PublicKeyBytes, Slot, SyncDuty, SyncSelectionProof, SyncSubnetId,
+    ChainSpec, Epoch, EthSpec, PublicKeyBytes, Signature, Slot, SyncDuty, SyncSelectionProof,
+    SyncSubnetId,
 };
 
 /// Number of epochs in advance to compute selection proofs.
 pub const AGGREGATION_PRE_COMPUTE_EPOCHS: u64 = 2;
 
+/// Number of epochs in advance of the start of a sync committee period to fetch its duties.
+///
+/// The next sync committee is known from the start of the current period, so on mainnet (256
+/// epochs per period) this fetches the duties for the next period as soon as they are available.
+pub const SYNC_DUTIES_LOOKAHEAD_EPOCHS: u64 = 256;
+
 /// Top-level data-structure containing sync duty information.
 ///
 /// This data is structured as a series of nested `HashMap`s wrapped in `RwLock`s. Fine-grained
@@ -256,11 +264,6 @@ impl ValidatorDuties {
     }
 }
 
-/// Number of epochs to wait from the start of the period before actually fetching duties.
-fn epoch_offset(spec: &ChainSpec) -> u64 {
-    spec.epochs_per_sync_committee_period.as_u64() / 2
-}
-
 fn first_epoch_of_period(sync_committee_period: u64, spec: &ChainSpec) -> Epoch {
     spec.epochs_per_sync_committee_period * sync_committee_period
 }
@@ -346,9 +349,9 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
         );
     }
 
-    // If we're past the point in the current period where we should determine duties for the next
-    // period and they are not yet known, then poll.
-    if current_epoch.as_u64() % spec.epochs_per_sync_committee_period.as_u64() >= epoch_offset(spec)
+    // If the next period starts within the lookahead and its duties are not yet known, then poll.
+    if current_epoch + SYNC_DUTIES_LOOKAHEAD_EPOCHS
+        >= first_epoch_of_period(next_sync_committee_period, spec)
         && !sync_duties.all_duties_known(next_sync_committee_period, &local_indices)
     {
         poll_sync_committee_duties_for_period(
@@ -518,12 +521,12 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
                     // Construct proof for prior slot.
                     let slot = duty_slot - 1;
 
-                    let proof = match duties_service_ref
+                    match duties_service_ref
                         .validator_store
                         .produce_sync_selection_proof(&duty.pubkey, slot, *subnet_id)
                         .await
                     {
-                        Ok(proof) => proof,
+                        Ok(proof) => Some((slot, *subnet_id, proof)),
                         Err(e) => {
                             warn!(
                                 log,
@@ -532,30 +535,6 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
                                 "pubkey" => ?duty.pubkey,
                                 "slot" => slot,
                             );
-                            return None;
-                        }
-                    };
-
-                    match proof.is_aggregator::<E>() {
-                        Ok(true) => {
-                            debug!(
-                                log,
-                                "Validator is sync aggregator";
-                                "validator_index" => duty.validator_index,
-                                "slot" => slot,
-                                "subnet_id" => %subnet_id,
-                            );
-                            Some(((slot, *subnet_id), proof))
-                        }
-                        Ok(false) => None,
-                        Err(e) => {
-                            warn!(
-                                log,
-                                "Error determining is_aggregator";
-                                "pubkey" => ?duty.pubkey,
-                                "slot" => slot,
-                                "error" => ?e,
-                            );
                             None
                         }
                     }
@@ -568,9 +547,60 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
                 .flatten()
                 .collect::<Vec<_>>();
 
-            validator_proofs.push((duty.validator_index, proofs));
+            validator_proofs.push((duty, proofs));
+        }
+
+        // In a distributed validator cluster, aggregators are selected with the combined proofs.
+        if duties_service.distributed {
+            if let Err(e) =
+                combine_sync_selection_proofs(&duties_service, &mut validator_proofs).await
+            {
+                warn!(
+                    log,
+                    "Unable to combine sync selection proofs";
+                    "error" => e,
+                    "epoch" => epoch,
+                );
+                continue;
+            }
         }
 
+        // Only keep the proofs which select the validator as an aggregator.
+        let validator_proofs = validator_proofs
+            .into_iter()
+            .map(|(duty, proofs)| {
+                let proofs = proofs
+                    .into_iter()
+                    .filter_map(
+                        |(slot, subnet_id, proof)| match proof.is_aggregator::<E>() {
+                            Ok(true) => {
+                                debug!(
+                                    log,
+                                    "Validator is sync aggregator";
+                                    "validator_index" => duty.validator_index,
+                                    "slot" => slot,
+                                    "subnet_id" => %subnet_id,
+                                );
+                                Some(((slot, subnet_id), proof))
+                            }
+                            Ok(false) => None,
+                            Err(e) => {
+                                warn!(
+                                    log,
+                                    "Error determining is_aggregator";
+                                    "pubkey" => ?duty.pubkey,
+                                    "slot" => slot,
+                                    "error" => ?e,
+                                );
+                                None
+                            }
+                        },
+                    )
+                    .collect::<Vec<_>>();
+                (duty.validator_index, proofs)
+            })
+            .collect::<Vec<_>>();
+
         // Add to global storage (we add regularly so the proofs can be used ASAP).
         let sync_map = duties_service.sync_duties.committees.read();
         let committee_duties = if let Some(duties) = sync_map.get(&sync_committee_period) {
@@ -609,3 +639,67 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
         }
     }
 }
+
+/// Replace the partial selection proofs signed by this validator client with the proofs combined
+/// by the distributed validator middleware from the partial proofs of all operators.
+///
+/// Proofs which the middleware does not return are dropped.
+async fn combine_sync_selection_proofs<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    validator_proofs: &mut [(&SyncDuty, Vec<(Slot, SyncSubnetId, SyncSelectionProof)>)],
+) -> Result<(), String> {
+    let selections = validator_proofs
+        .iter()
+        .flat_map(|(duty, proofs)| {
+            proofs
+                .iter()
+                .map(move |(slot, subnet_id, proof)| SyncCommitteeSelection {
+                    validator_index: duty.validator_index,
+                    slot: *slot,
+                    subcommittee_index: **subnet_id,
+                    selection_proof: proof.clone().into(),
+                })
+        })
+        .collect::<Vec<_>>();
+
+    if selections.is_empty() {
+        return Ok(());
+    }
+
+    let selections = &selections;
+    let combined_proofs: HashMap<(u64, Slot, u64), Signature> = duties_service
+        .beacon_nodes
+        .first_success(duties_service.require_synced, |beacon_node| async move {
+            beacon_node
+                .post_validator_sync_committee_selections(selections)
+                .await
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .data
+        .into_iter()
+        .map(|selection| {
+            (
+                (
+                    selection.validator_index,
+                    selection.slot,
+                    selection.subcommittee_index,
+                ),
+                selection.selection_proof,
+            )
+        })
+        .collect();
+
+    for (duty, proofs) in validator_proofs.iter_mut() {
+        *proofs = std::mem::take(proofs)
+            .into_iter()
+            .filter_map(|(slot, subnet_id, _)| {
+                combined_proofs
+                    .get(&(duty.validator_index, slot, *subnet_id))
+                    .map(|proof| (slot, subnet_id, proof.clone().into()))
+            })
+            .collect();
+    }
+
+    Ok(())
+}
@@ -63,6 +63,9 @@ impl GraffitiFile {
 
         let lines = reader.lines();
 
+        self.graffitis.clear();
+        self.default = None;
+
         for line in lines {
             let line = line.map_err(|e| Error::InvalidLine(e.to_string()))?;
             let (pk_opt, graffiti) = read_line(&line)?;
@@ -166,21 +166,28 @@ impl ApiSecret {
         &self.pk_path
     }
 
-    /// Returns the value of the `Authorization` header which is used for verifying incoming HTTP
+    /// Returns the values of the `Authorization` header which are accepted for incoming HTTP
     /// requests.
-    fn auth_header_value(&self) -> String {
-        format!("Basic {}", self.api_token())
+    ///
+    /// The `Basic` scheme is used by Lighthouse clients, whilst the `Bearer` scheme is required by
+    /// the standard keymanager API.
+    fn auth_header_values(&self) -> [String; 2] {
+        let api_token = self.api_token();
+        [
+            format!("Basic {}", api_token),
+            format!("Bearer {}", api_token),
+        ]
     }
 
     /// Returns a `warp` header which filters out request that have a missing or inaccurate
     /// `Authorization` header.
     pub fn authorization_header_filter(&self) -> warp::filters::BoxedFilter<()> {
-        let expected = self.auth_header_value();
+        let expected = self.auth_header_values();
         warp::any()
             .map(move || expected.clone())
             .and(warp::filters::header::header("Authorization"))
-            .and_then(move |expected: String, header: String| async move {
-                if header == expected {
+            .and_then(move |expected: [String; 2], header: String| async move {
+                if expected.contains(&header) {
                     Ok(())
                 } else {
                     Err(warp_utils::reject::invalid_auth(header))
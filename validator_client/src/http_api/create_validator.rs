@@ -170,11 +170,16 @@ pub async fn create_validators_web3signer<T: 'static + SlotClock, E: EthSpec>(
             enabled: request.enable,
             voting_public_key: request.voting_public_key.clone(),
             graffiti: request.graffiti.clone(),
+            suggested_fee_recipient: None,
+            gas_limit: None,
+            builder_proposals: None,
             description: request.description.clone(),
             signing_definition: SigningDefinition::Web3Signer {
                 url: request.url.clone(),
                 root_certificate_path: request.root_certificate_path.clone(),
                 request_timeout_ms: request.request_timeout_ms,
+                client_identity_path: request.client_identity_path.clone(),
+                client_identity_password: request.client_identity_password.clone(),
             },
         };
         validator_store
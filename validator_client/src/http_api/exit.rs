@@ -0,0 +1,151 @@
+//! Publishing of voluntary exits for the validators managed by this validator client.
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::ValidatorStore;
+use eth2::lighthouse_vc::types::ValidatorExitData;
+use eth2::types::{StateId, ValidatorData, ValidatorId, ValidatorStatus};
+use safe_arith::SafeArith;
+use slog::{info, Logger};
+use slot_clock::SlotClock;
+use types::{ChainSpec, EthSpec, PublicKey, SignedVoluntaryExit, VoluntaryExit};
+use warp::Rejection;
+
+/// Handles `POST lighthouse/validators/{validator_pubkey}/exit`.
+///
+/// Signs a voluntary exit for the current epoch and publishes it to the beacon nodes, returning
+/// the signed exit. Returns an error if the validator is not yet eligible to exit.
+pub async fn publish_voluntary_exit<T: SlotClock + 'static, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    validator_store: &ValidatorStore<T, E>,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    slot_clock: &T,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> Result<SignedVoluntaryExit, Rejection> {
+    let enabled = validator_store
+        .initialized_validators()
+        .read()
+        .is_enabled(&validator_pubkey);
+    if enabled != Some(true) {
+        return Err(warp_utils::reject::custom_not_found(format!(
+            "no enabled validator for {:?}",
+            validator_pubkey
+        )));
+    }
+
+    let current_epoch = slot_clock
+        .now()
+        .ok_or_else(|| {
+            warp_utils::reject::custom_server_error("unable to read slot clock".to_string())
+        })?
+        .epoch(E::slots_per_epoch());
+
+    let validator_data = get_validator_data(&validator_pubkey, beacon_nodes).await?;
+    match validator_data.status {
+        ValidatorStatus::ActiveOngoing => {
+            let eligible_epoch = validator_data
+                .validator
+                .activation_epoch
+                .safe_add(spec.shard_committee_period)
+                .map_err(|e| {
+                    warp_utils::reject::custom_server_error(format!(
+                        "unable to calculate eligible epoch: {:?}",
+                        e
+                    ))
+                })?;
+            if current_epoch < eligible_epoch {
+                return Err(warp_utils::reject::custom_bad_request(format!(
+                    "validator is not eligible to exit until epoch {}",
+                    eligible_epoch
+                )));
+            }
+        }
+        status => {
+            return Err(warp_utils::reject::custom_bad_request(format!(
+                "validator is not eligible to exit, status: {:?}",
+                status
+            )))
+        }
+    }
+
+    let voluntary_exit = VoluntaryExit {
+        epoch: current_epoch,
+        validator_index: validator_data.index,
+    };
+    let signed_voluntary_exit = validator_store
+        .sign_voluntary_exit(validator_pubkey.compress(), voluntary_exit)
+        .await
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "unable to sign voluntary exit: {:?}",
+                e
+            ))
+        })?;
+
+    let signed_voluntary_exit_ref = &signed_voluntary_exit;
+    beacon_nodes
+        .broadcast(RequireSynced::Yes, |beacon_node| async move {
+            beacon_node
+                .post_beacon_pool_voluntary_exits(signed_voluntary_exit_ref)
+                .await
+        })
+        .await
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "unable to publish voluntary exit: {}",
+                e
+            ))
+        })?;
+
+    info!(
+        log,
+        "Published voluntary exit";
+        "validator_index" => validator_data.index,
+        "epoch" => current_epoch,
+        "pubkey" => ?validator_pubkey,
+    );
+
+    Ok(signed_voluntary_exit)
+}
+
+/// Handles `GET lighthouse/validators/{validator_pubkey}/exit`, reporting the exit status of the
+/// validator as observed on-chain by the beacon nodes.
+pub async fn exit_status<T: SlotClock + 'static, E: EthSpec>(
+    validator_pubkey: PublicKey,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+) -> Result<ValidatorExitData, Rejection> {
+    let validator_data = get_validator_data(&validator_pubkey, beacon_nodes).await?;
+    Ok(ValidatorExitData {
+        index: validator_data.index,
+        status: validator_data.status,
+        exit_epoch: validator_data.validator.exit_epoch,
+        withdrawable_epoch: validator_data.validator.withdrawable_epoch,
+    })
+}
+
+/// Returns the validator's entry in the head state of the beacon nodes.
+async fn get_validator_data<T: SlotClock + 'static, E: EthSpec>(
+    validator_pubkey: &PublicKey,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+) -> Result<ValidatorData, Rejection> {
+    let validator_id = &ValidatorId::PublicKey(validator_pubkey.compress());
+    beacon_nodes
+        .first_success(RequireSynced::Yes, |beacon_node| async move {
+            beacon_node
+                .get_beacon_states_validator_id(StateId::Head, validator_id)
+                .await
+        })
+        .await
+        .map_err(|e| {
+            warp_utils::reject::custom_server_error(format!(
+                "unable to get validator from beacon node: {}",
+                e
+            ))
+        })?
+        .map(|response| response.data)
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(format!(
+                "validator {:?} is not in the beacon state",
+                validator_pubkey
+            ))
+        })
+}
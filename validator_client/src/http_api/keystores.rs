@@ -0,0 +1,344 @@
+//! Implementation of the standard keystore management API.
+use crate::{
+    initialized_validators::{Error, InitializedValidators},
+    signing_method::SigningMethod,
+    ValidatorStore,
+};
+use account_utils::{validator_definitions::SigningDefinition, ZeroizeString};
+use eth2::lighthouse_vc::{
+    std_types::{
+        DeleteKeystoreStatus, DeleteKeystoresRequest, DeleteKeystoresResponse,
+        ImportKeystoreStatus, ImportKeystoresRequest, ImportKeystoresResponse, InterchangeJsonStr,
+        KeystoreJsonStr, ListKeystoresResponse, SingleKeystoreResponse, Status,
+    },
+    types::{ExportKeystoresResponse, SingleExportKeystoresResponse},
+};
+use eth2_keystore::Keystore;
+use slashing_protection::interchange::Interchange;
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::path::PathBuf;
+use std::sync::Weak;
+use tokio::runtime::Runtime;
+use types::{EthSpec, PublicKeyBytes};
+use validator_dir::Builder as ValidatorDirBuilder;
+use warp::Rejection;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+/// Handles `GET eth/v1/keystores`, listing all validators with local keystores.
+pub fn list<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+) -> ListKeystoresResponse {
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators_rwlock.read();
+
+    let keystores = initialized_validators
+        .validator_definitions()
+        .iter()
+        .filter(|def| {
+            matches!(
+                def.signing_definition,
+                SigningDefinition::LocalKeystore { .. }
+            )
+        })
+        .map(|def| {
+            let validating_pubkey = def.voting_public_key.compress();
+
+            // The derivation path is only known for validators which have been initialized.
+            let derivation_path = initialized_validators
+                .signing_method(&validating_pubkey)
+                .and_then(|signing_method| match *signing_method {
+                    SigningMethod::LocalKeystore {
+                        ref voting_keystore,
+                        ..
+                    } => voting_keystore.path(),
+                    SigningMethod::Web3Signer { .. } => None,
+                });
+
+            SingleKeystoreResponse {
+                validating_pubkey,
+                derivation_path,
+                readonly: None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    ListKeystoresResponse { data: keystores }
+}
+
+/// Handles `POST eth/v1/keystores`.
+///
+/// The slashing protection data (if any) is imported before any keystores, and the whole request
+/// fails if it cannot be imported. Each keystore is then imported independently, with its outcome
+/// reported in the response.
+pub fn import<T: SlotClock + 'static, E: EthSpec>(
+    request: ImportKeystoresRequest,
+    validator_dir: PathBuf,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+    log: Logger,
+) -> Result<ImportKeystoresResponse, Rejection> {
+    if request.keystores.len() != request.passwords.len() {
+        return Err(custom_bad_request(format!(
+            "mismatched numbers of keystores ({}) and passwords ({})",
+            request.keystores.len(),
+            request.passwords.len(),
+        )));
+    }
+
+    info!(
+        log,
+        "Importing keystores via standard HTTP API";
+        "count" => request.keystores.len(),
+    );
+
+    if let Some(InterchangeJsonStr(interchange)) = request.slashing_protection {
+        let outcomes = validator_store
+            .import_slashing_protection(interchange)
+            .map_err(|e| {
+                custom_bad_request(format!("error importing slashing protection: {:?}", e))
+            })?;
+
+        for outcome in outcomes.iter().filter(|outcome| outcome.failed()) {
+            warn!(
+                log,
+                "Slashing protection import failed for validator";
+                "outcome" => ?outcome,
+            );
+        }
+    }
+
+    let mut statuses = Vec::with_capacity(request.keystores.len());
+
+    for (KeystoreJsonStr(keystore), password) in request
+        .keystores
+        .into_iter()
+        .zip(request.passwords.into_iter())
+    {
+        let pubkey_str = keystore.pubkey().to_string();
+
+        let status = match import_single_keystore(
+            keystore,
+            password,
+            validator_dir.clone(),
+            validator_store,
+            runtime.clone(),
+        ) {
+            Ok(status) => Status::ok(status),
+            Err(e) => {
+                warn!(
+                    log,
+                    "Error importing keystore, skipped";
+                    "pubkey" => pubkey_str,
+                    "error" => ?e,
+                );
+                Status::error(ImportKeystoreStatus::Error, e)
+            }
+        };
+        statuses.push(status);
+    }
+
+    Ok(ImportKeystoresResponse { data: statuses })
+}
+
+fn import_single_keystore<T: SlotClock + 'static, E: EthSpec>(
+    keystore: Keystore,
+    password: ZeroizeString,
+    validator_dir_path: PathBuf,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+) -> Result<ImportKeystoreStatus, String> {
+    let pubkey = keystore
+        .public_key()
+        .ok_or_else(|| format!("invalid pubkey: {}", keystore.pubkey()))?;
+
+    if validator_store
+        .initialized_validators()
+        .read()
+        .is_enabled(&pubkey)
+        .is_some()
+    {
+        return Ok(ImportKeystoreStatus::Duplicate);
+    }
+
+    // Check to ensure the password is correct.
+    keystore
+        .decrypt_keypair(password.as_ref())
+        .map_err(|e| format!("incorrect password: {:?}", e))?;
+
+    let validator_dir = ValidatorDirBuilder::new(validator_dir_path)
+        .voting_keystore(keystore, password.as_ref())
+        .store_withdrawal_keystore(false)
+        .build()
+        .map_err(|e| format!("failed to build validator directory: {:?}", e))?;
+
+    // Drop validator dir so that `add_validator_keystore` can re-lock the keystore.
+    let voting_keystore_path = validator_dir.voting_keystore_path();
+    drop(validator_dir);
+
+    let runtime = runtime
+        .upgrade()
+        .ok_or_else(|| "Runtime shutdown".to_string())?;
+    runtime
+        .block_on(validator_store.add_validator_keystore(
+            voting_keystore_path,
+            password,
+            true,
+            None,
+        ))
+        .map_err(|e| format!("failed to initialize validator: {:?}", e))?;
+
+    Ok(ImportKeystoreStatus::Imported)
+}
+
+/// Handles `DELETE eth/v1/keystores`.
+pub fn delete<T: SlotClock + 'static, E: EthSpec>(
+    request: DeleteKeystoresRequest,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+    log: Logger,
+) -> Result<DeleteKeystoresResponse, Rejection> {
+    let (results, slashing_protection) =
+        delete_impl(request, validator_store, runtime, false, log)?;
+
+    Ok(DeleteKeystoresResponse {
+        data: results.into_iter().map(|(status, _)| status).collect(),
+        slashing_protection,
+    })
+}
+
+/// Handles `DELETE lighthouse/keystores`.
+///
+/// Deletes keystores as `DELETE eth/v1/keystores` does, but also returns each keystore along with
+/// its password so that the validator can be moved to another validator client. A keystore whose
+/// password is not known is not deleted.
+pub fn export<T: SlotClock + 'static, E: EthSpec>(
+    request: DeleteKeystoresRequest,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+    log: Logger,
+) -> Result<ExportKeystoresResponse, Rejection> {
+    let (results, slashing_protection) = delete_impl(request, validator_store, runtime, true, log)?;
+
+    let data = results
+        .into_iter()
+        .map(|(status, exported)| {
+            let (keystore, password) = exported.map_or((None, None), |(keystore, password)| {
+                (Some(KeystoreJsonStr(keystore)), Some(password))
+            });
+            SingleExportKeystoresResponse {
+                status,
+                validating_keystore: keystore,
+                validating_keystore_password: password,
+            }
+        })
+        .collect();
+
+    Ok(ExportKeystoresResponse {
+        data,
+        slashing_protection,
+    })
+}
+
+/// The outcome of deleting a single keystore, along with the keystore and its password if they
+/// were exported.
+type DeleteResult = (
+    Status<DeleteKeystoreStatus>,
+    Option<(Keystore, ZeroizeString)>,
+);
+
+/// Deletes the requested keystores, exporting them first if `export` is set.
+///
+/// The lock on the initialized validators is held until the slashing protection data has been
+/// exported, so that a deleted validator cannot sign any further messages which would be absent
+/// from the export.
+fn delete_impl<T: SlotClock + 'static, E: EthSpec>(
+    request: DeleteKeystoresRequest,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+    export: bool,
+    log: Logger,
+) -> Result<(Vec<DeleteResult>, Interchange), Rejection> {
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rwlock.write();
+
+    let mut results = request
+        .pubkeys
+        .iter()
+        .map(|pubkey_bytes| {
+            match delete_single_keystore(
+                pubkey_bytes,
+                &mut initialized_validators,
+                runtime.clone(),
+                export,
+            ) {
+                Ok((status, exported)) => (Status::ok(status), exported),
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Error deleting keystore";
+                        "pubkey" => ?pubkey_bytes,
+                        "error" => ?e,
+                    );
+                    (Status::error(DeleteKeystoreStatus::Error, e), None)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let slashing_protection = validator_store
+        .export_slashing_protection_for_keys(&request.pubkeys)
+        .map_err(|e| {
+            custom_server_error(format!("error exporting slashing protection: {:?}", e))
+        })?;
+
+    drop(initialized_validators);
+
+    // Keys which are unknown to the validator client but which have slashing protection data are
+    // reported as inactive rather than absent.
+    for (pubkey, (status, _)) in request.pubkeys.iter().zip(results.iter_mut()) {
+        if status.status == DeleteKeystoreStatus::NotFound
+            && slashing_protection
+                .data
+                .iter()
+                .any(|data| data.pubkey == *pubkey)
+        {
+            status.status = DeleteKeystoreStatus::NotActive;
+        }
+    }
+
+    Ok((results, slashing_protection))
+}
+
+fn delete_single_keystore(
+    pubkey_bytes: &PublicKeyBytes,
+    initialized_validators: &mut InitializedValidators,
+    runtime: Weak<Runtime>,
+    export: bool,
+) -> Result<(DeleteKeystoreStatus, Option<(Keystore, ZeroizeString)>), String> {
+    let pubkey = pubkey_bytes
+        .decompress()
+        .map_err(|e| format!("invalid pubkey, {:?}: {:?}", pubkey_bytes, e))?;
+
+    // Read the keystore before deleting it, so that it is never lost.
+    let exported = if export {
+        match initialized_validators.export_keystore(&pubkey) {
+            Ok(exported) => Some(exported),
+            Err(Error::ValidatorNotInitialized(_)) => {
+                return Ok((DeleteKeystoreStatus::NotFound, None))
+            }
+            Err(e) => return Err(format!("unable to export keystore: {:?}", e)),
+        }
+    } else {
+        None
+    };
+
+    let runtime = runtime
+        .upgrade()
+        .ok_or_else(|| "Runtime shutdown".to_string())?;
+    match runtime.block_on(initialized_validators.delete_definition_and_keystore(&pubkey, true)) {
+        Ok(()) => Ok((DeleteKeystoreStatus::Deleted, exported)),
+        Err(Error::ValidatorNotInitialized(_)) => Ok((DeleteKeystoreStatus::NotFound, None)),
+        Err(e) => Err(format!("unable to delete keystore: {:?}", e)),
+    }
+}
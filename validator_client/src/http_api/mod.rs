@@ -1,11 +1,25 @@
 mod api_secret;
 mod create_validator;
+mod exit;
+mod keystores;
+mod remotekeys;
 mod tests;
 
-use crate::ValidatorStore;
+use crate::{
+    beacon_node_fallback::BeaconNodeFallback,
+    initialized_validators::Error as InitializedValidatorsError,
+    validator_store::DEFAULT_GAS_LIMIT, ValidatorStore,
+};
 use account_utils::mnemonic_from_phrase;
 use create_validator::{create_validators_mnemonic, create_validators_web3signer};
-use eth2::lighthouse_vc::types::{self as api_types, PublicKey, PublicKeyBytes};
+use eth2::lighthouse_vc::{
+    std_types::{
+        DeleteKeystoresRequest, DeleteRemotekeysRequest, GetFeeRecipientResponse,
+        GetGasLimitResponse, ImportKeystoresRequest, ImportRemotekeysRequest,
+        UpdateFeeRecipientRequest, UpdateGasLimitRequest,
+    },
+    types::{self as api_types, PublicKey, PublicKeyBytes},
+};
 use lighthouse_version::version_with_platform;
 use serde::{Deserialize, Serialize};
 use slog::{crit, info, Logger};
@@ -26,6 +40,7 @@ use warp::{
     },
     Filter,
 };
+use warp_utils::cors::CorsDefaults;
 
 pub use api_secret::ApiSecret;
 
@@ -55,6 +70,8 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
     pub validator_dir: Option<PathBuf>,
+    pub beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+    pub slot_clock: T,
     pub spec: ChainSpec,
     pub config: Config,
     pub log: Logger,
@@ -68,6 +85,12 @@ pub struct Config {
     pub listen_addr: Ipv4Addr,
     pub listen_port: u16,
     pub allow_origin: Option<String>,
+    /// A comma-separated list of the methods allowed by CORS, replacing the defaults.
+    pub allow_methods: Option<String>,
+    /// A comma-separated list of the headers allowed by CORS, replacing the defaults.
+    pub allow_headers: Option<String>,
+    /// Whether CORS requests may include credentials.
+    pub allow_credentials: bool,
 }
 
 impl Default for Config {
@@ -77,6 +100,9 @@ impl Default for Config {
             listen_addr: Ipv4Addr::new(127, 0, 0, 1),
             listen_port: 5062,
             allow_origin: None,
+            allow_methods: None,
+            allow_headers: None,
+            allow_credentials: false,
         }
     }
 }
@@ -104,17 +130,17 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     let log = ctx.log.clone();
 
     // Configure CORS.
-    let cors_builder = {
-        let builder = warp::cors()
-            .allow_methods(vec!["GET", "POST", "PATCH"])
-            .allow_headers(vec!["Content-Type", "Authorization"]);
-
-        warp_utils::cors::set_builder_origins(
-            builder,
-            config.allow_origin.as_deref(),
-            (config.listen_addr, config.listen_port),
-        )?
-    };
+    let cors_builder = warp_utils::cors::build_cors(
+        config.allow_origin.as_deref(),
+        config.allow_methods.as_deref(),
+        config.allow_headers.as_deref(),
+        config.allow_credentials,
+        CorsDefaults {
+            origin: (config.listen_addr, config.listen_port),
+            methods: &["GET", "POST", "PATCH", "DELETE"],
+            headers: &["Content-Type", "Authorization"],
+        },
+    )?;
 
     // Sanity check.
     if !config.enabled {
@@ -154,9 +180,26 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    let inner_beacon_nodes = ctx.beacon_nodes.clone();
+    let beacon_nodes_filter = warp::any()
+        .map(move || inner_beacon_nodes.clone())
+        .and_then(|beacon_nodes: Option<_>| async move {
+            beacon_nodes.ok_or_else(|| {
+                warp_utils::reject::custom_not_found(
+                    "beacon nodes are not initialized.".to_string(),
+                )
+            })
+        });
+
+    let inner_slot_clock = ctx.slot_clock.clone();
+    let slot_clock_filter = warp::any().map(move || inner_slot_clock.clone());
+
     let inner_spec = Arc::new(ctx.spec.clone());
     let spec_filter = warp::any().map(move || inner_spec.clone());
 
+    let inner_log = ctx.log.clone();
+    let log_filter = warp::any().map(move || inner_log.clone());
+
     // GET lighthouse/version
     let get_node_version = warp::path("lighthouse")
         .and(warp::path("version"))
@@ -348,7 +391,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(warp::path("keystore"))
         .and(warp::path::end())
         .and(warp::body::json())
-        .and(validator_dir_filter)
+        .and(validator_dir_filter.clone())
         .and(validator_store_filter.clone())
         .and(signer.clone())
         .and(runtime_filter.clone())
@@ -451,9 +494,9 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
         .and(warp::path::param::<PublicKey>())
         .and(warp::path::end())
         .and(warp::body::json())
-        .and(validator_store_filter)
-        .and(signer)
-        .and(runtime_filter)
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and(runtime_filter.clone())
         .and_then(
             |validator_pubkey: PublicKey,
              body: api_types::ValidatorPatchRequest,
@@ -495,6 +538,375 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST lighthouse/validators/{validator_pubkey}/exit
+    let post_validators_exit = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("exit"))
+        .and(warp::path::end())
+        .and(validator_store_filter.clone())
+        .and(beacon_nodes_filter.clone())
+        .and(slot_clock_filter)
+        .and(spec_filter.clone())
+        .and(signer.clone())
+        .and(runtime_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+             slot_clock: T,
+             spec: Arc<ChainSpec>,
+             signer,
+             runtime: Weak<Runtime>,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    if let Some(runtime) = runtime.upgrade() {
+                        let signed_voluntary_exit =
+                            runtime.block_on(exit::publish_voluntary_exit(
+                                validator_pubkey,
+                                &validator_store,
+                                &beacon_nodes,
+                                &slot_clock,
+                                &spec,
+                                &log,
+                            ))?;
+                        Ok(api_types::GenericResponse::from(signed_voluntary_exit))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Runtime shutdown".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // GET lighthouse/validators/{validator_pubkey}/exit
+    let get_validators_exit = warp::path("lighthouse")
+        .and(warp::path("validators"))
+        .and(warp::path::param::<PublicKey>())
+        .and(warp::path("exit"))
+        .and(warp::path::end())
+        .and(beacon_nodes_filter)
+        .and(signer.clone())
+        .and(runtime_filter.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+             signer,
+             runtime: Weak<Runtime>| {
+                blocking_signed_json_task(signer, move || {
+                    if let Some(runtime) = runtime.upgrade() {
+                        let exit_data =
+                            runtime.block_on(exit::exit_status(validator_pubkey, &beacon_nodes))?;
+                        Ok(api_types::GenericResponse::from(exit_data))
+                    } else {
+                        Err(warp_utils::reject::custom_server_error(
+                            "Runtime shutdown".into(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // Standard key-manager endpoints.
+    let eth_v1 = warp::path("eth").and(warp::path("v1"));
+    let std_keystores = eth_v1.and(warp::path("keystores")).and(warp::path::end());
+    let std_remotekeys = eth_v1.and(warp::path("remotekeys")).and(warp::path::end());
+    let std_validator = eth_v1
+        .and(warp::path("validator"))
+        .and(warp::path::param::<PublicKey>());
+    let std_fee_recipient = std_validator
+        .and(warp::path("feerecipient"))
+        .and(warp::path::end());
+    let std_gas_limit = std_validator
+        .and(warp::path("gas_limit"))
+        .and(warp::path::end());
+
+    // DELETE /lighthouse/keystores
+    let delete_lighthouse_keystores = warp::path("lighthouse")
+        .and(warp::path("keystores"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and(runtime_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |request: DeleteKeystoresRequest,
+             signer,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             runtime: Weak<Runtime>,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    keystores::export(
+                        request.clone(),
+                        &validator_store,
+                        runtime.clone(),
+                        log.clone(),
+                    )
+                })
+            },
+        );
+
+    // GET /eth/v1/keystores
+    let get_std_keystores = std_keystores
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and_then(|signer, validator_store: Arc<ValidatorStore<T, E>>| {
+            blocking_signed_json_task(signer, move || Ok(keystores::list(&validator_store)))
+        });
+
+    // POST /eth/v1/keystores
+    let post_std_keystores = std_keystores
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_dir_filter)
+        .and(validator_store_filter.clone())
+        .and(runtime_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |request: ImportKeystoresRequest,
+             signer,
+             validator_dir: PathBuf,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             runtime: Weak<Runtime>,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    keystores::import(
+                        request.clone(),
+                        validator_dir.clone(),
+                        &validator_store,
+                        runtime.clone(),
+                        log.clone(),
+                    )
+                })
+            },
+        );
+
+    // DELETE /eth/v1/keystores
+    let delete_std_keystores = std_keystores
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and(runtime_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |request: DeleteKeystoresRequest,
+             signer,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             runtime: Weak<Runtime>,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    keystores::delete(
+                        request.clone(),
+                        &validator_store,
+                        runtime.clone(),
+                        log.clone(),
+                    )
+                })
+            },
+        );
+
+    // GET /eth/v1/remotekeys
+    let get_std_remotekeys = std_remotekeys
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and_then(|signer, validator_store: Arc<ValidatorStore<T, E>>| {
+            blocking_signed_json_task(signer, move || Ok(remotekeys::list(&validator_store)))
+        });
+
+    // POST /eth/v1/remotekeys
+    let post_std_remotekeys = std_remotekeys
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and(runtime_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            |request: ImportRemotekeysRequest,
+             signer,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             runtime: Weak<Runtime>,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    remotekeys::import(
+                        request.clone(),
+                        &validator_store,
+                        runtime.clone(),
+                        log.clone(),
+                    )
+                })
+            },
+        );
+
+    // DELETE /eth/v1/remotekeys
+    let delete_std_remotekeys = std_remotekeys
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and(runtime_filter)
+        .and(log_filter)
+        .and_then(
+            |request: DeleteRemotekeysRequest,
+             signer,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             runtime: Weak<Runtime>,
+             log: Logger| {
+                blocking_signed_json_task(signer, move || {
+                    remotekeys::delete(
+                        request.clone(),
+                        &validator_store,
+                        runtime.clone(),
+                        log.clone(),
+                    )
+                })
+            },
+        );
+
+    // GET /eth/v1/validator/{pubkey}/feerecipient
+    let get_fee_recipient = std_fee_recipient
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    let initialized_validators_rw_lock = validator_store.initialized_validators();
+                    let initialized_validators = initialized_validators_rw_lock.read();
+
+                    if initialized_validators
+                        .is_enabled(&validator_pubkey)
+                        .is_none()
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator for {:?}",
+                            validator_pubkey
+                        )));
+                    }
+
+                    let ethaddress = initialized_validators
+                        .suggested_fee_recipient(&validator_pubkey)
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(format!(
+                                "no fee recipient set for {:?}",
+                                validator_pubkey
+                            ))
+                        })?;
+
+                    Ok(api_types::GenericResponse::from(GetFeeRecipientResponse {
+                        pubkey: PublicKeyBytes::from(&validator_pubkey),
+                        ethaddress,
+                    }))
+                })
+            },
+        );
+
+    // POST /eth/v1/validator/{pubkey}/feerecipient
+    let post_fee_recipient = std_fee_recipient
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             request: UpdateFeeRecipientRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    validator_store
+                        .initialized_validators()
+                        .write()
+                        .set_validator_fee_recipient(&validator_pubkey, Some(request.ethaddress))
+                        .map_err(update_definition_rejection)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, StatusCode::ACCEPTED));
+
+    // DELETE /eth/v1/validator/{pubkey}/feerecipient
+    let delete_fee_recipient = std_fee_recipient
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    validator_store
+                        .initialized_validators()
+                        .write()
+                        .set_validator_fee_recipient(&validator_pubkey, None)
+                        .map_err(update_definition_rejection)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, StatusCode::NO_CONTENT));
+
+    // GET /eth/v1/validator/{pubkey}/gas_limit
+    let get_gas_limit = std_gas_limit
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    let initialized_validators_rw_lock = validator_store.initialized_validators();
+                    let initialized_validators = initialized_validators_rw_lock.read();
+
+                    if initialized_validators
+                        .is_enabled(&validator_pubkey)
+                        .is_none()
+                    {
+                        return Err(warp_utils::reject::custom_not_found(format!(
+                            "no validator for {:?}",
+                            validator_pubkey
+                        )));
+                    }
+
+                    Ok(api_types::GenericResponse::from(GetGasLimitResponse {
+                        pubkey: PublicKeyBytes::from(&validator_pubkey),
+                        gas_limit: initialized_validators
+                            .gas_limit(&validator_pubkey)
+                            .unwrap_or(DEFAULT_GAS_LIMIT),
+                    }))
+                })
+            },
+        );
+
+    // POST /eth/v1/validator/{pubkey}/gas_limit
+    let post_gas_limit = std_gas_limit
+        .and(warp::body::json())
+        .and(validator_store_filter.clone())
+        .and(signer.clone())
+        .and_then(
+            |validator_pubkey: PublicKey,
+             request: UpdateGasLimitRequest,
+             validator_store: Arc<ValidatorStore<T, E>>,
+             signer| {
+                blocking_signed_json_task(signer, move || {
+                    validator_store
+                        .initialized_validators()
+                        .write()
+                        .set_validator_gas_limit(&validator_pubkey, Some(request.gas_limit))
+                        .map_err(update_definition_rejection)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, StatusCode::ACCEPTED));
+
+    // DELETE /eth/v1/validator/{pubkey}/gas_limit
+    let delete_gas_limit = std_gas_limit
+        .and(validator_store_filter)
+        .and(signer)
+        .and_then(
+            |validator_pubkey: PublicKey, validator_store: Arc<ValidatorStore<T, E>>, signer| {
+                blocking_signed_json_task(signer, move || {
+                    validator_store
+                        .initialized_validators()
+                        .write()
+                        .set_validator_gas_limit(&validator_pubkey, None)
+                        .map_err(update_definition_rejection)
+                })
+            },
+        )
+        .map(|reply| warp::reply::with_status(reply, StatusCode::NO_CONTENT));
+
     let routes = warp::any()
         .and(authorization_header_filter)
         // Note: it is critical that the `authorization_header_filter` is applied to all routes.
@@ -508,15 +920,32 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(get_lighthouse_health)
                         .or(get_lighthouse_spec)
                         .or(get_lighthouse_validators)
-                        .or(get_lighthouse_validators_pubkey),
+                        .or(get_lighthouse_validators_pubkey)
+                        .or(get_validators_exit)
+                        .or(get_std_keystores)
+                        .or(get_std_remotekeys)
+                        .or(get_fee_recipient)
+                        .or(get_gas_limit),
                 )
                 .or(warp::post().and(
                     post_validators
                         .or(post_validators_keystore)
                         .or(post_validators_mnemonic)
-                        .or(post_validators_web3signer),
+                        .or(post_validators_web3signer)
+                        .or(post_validators_exit)
+                        .or(post_std_keystores)
+                        .or(post_std_remotekeys)
+                        .or(post_fee_recipient)
+                        .or(post_gas_limit),
                 ))
-                .or(warp::patch().and(patch_validators)),
+                .or(warp::patch().and(patch_validators))
+                .or(warp::delete().and(
+                    delete_lighthouse_keystores
+                        .or(delete_std_keystores)
+                        .or(delete_std_remotekeys)
+                        .or(delete_fee_recipient)
+                        .or(delete_gas_limit),
+                )),
         )
         // Maps errors into HTTP responses.
         .recover(warp_utils::reject::handle_rejection)
@@ -541,6 +970,20 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     Ok((listening_socket, server))
 }
 
+/// Maps an error from updating a validator definition into a rejection, which is a 404 if the
+/// validator is unknown.
+fn update_definition_rejection(e: InitializedValidatorsError) -> warp::Rejection {
+    match e {
+        InitializedValidatorsError::ValidatorNotInitialized(pubkey) => {
+            warp_utils::reject::custom_not_found(format!("no validator for {:?}", pubkey))
+        }
+        e => warp_utils::reject::custom_server_error(format!(
+            "unable to update validator definition: {:?}",
+            e
+        )),
+    }
+}
+
 /// Executes `func` in blocking tokio task (i.e., where long-running tasks are permitted).
 /// JSON-encodes the return value of `func`, using the `signer` function to produce a signature of
 /// those bytes.
@@ -0,0 +1,183 @@
+//! Implementation of the standard remote key management API.
+use crate::{
+    initialized_validators::{Error, InitializedValidators},
+    ValidatorStore,
+};
+use account_utils::validator_definitions::{SigningDefinition, ValidatorDefinition};
+use eth2::lighthouse_vc::std_types::{
+    DeleteRemotekeyStatus, DeleteRemotekeysRequest, DeleteRemotekeysResponse,
+    ImportRemotekeyStatus, ImportRemotekeysRequest, ImportRemotekeysResponse,
+    ListRemotekeysResponse, SingleImportRemotekeysRequest, SingleListRemotekeysResponse, Status,
+};
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Weak;
+use tokio::runtime::Runtime;
+use types::{EthSpec, PublicKeyBytes};
+use url::Url;
+use warp::Rejection;
+
+/// Handles `GET eth/v1/remotekeys`, listing all validators with a remote signer.
+pub fn list<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+) -> ListRemotekeysResponse {
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let initialized_validators = initialized_validators_rwlock.read();
+
+    let keystores = initialized_validators
+        .validator_definitions()
+        .iter()
+        .filter_map(|def| match &def.signing_definition {
+            SigningDefinition::Web3Signer { url, .. } => Some(SingleListRemotekeysResponse {
+                pubkey: def.voting_public_key.compress(),
+                url: url.clone(),
+                readonly: false,
+            }),
+            SigningDefinition::LocalKeystore { .. } => None,
+        })
+        .collect::<Vec<_>>();
+
+    ListRemotekeysResponse { data: keystores }
+}
+
+/// Handles `POST eth/v1/remotekeys`, adding an enabled Web3Signer validator for each key.
+pub fn import<T: SlotClock + 'static, E: EthSpec>(
+    request: ImportRemotekeysRequest,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+    log: Logger,
+) -> Result<ImportRemotekeysResponse, Rejection> {
+    info!(
+        log,
+        "Importing remotekeys via standard HTTP API";
+        "count" => request.remote_keys.len(),
+    );
+
+    let statuses = request
+        .remote_keys
+        .into_iter()
+        .map(|remote_key| {
+            let pubkey = remote_key.pubkey;
+            match import_single_remotekey(remote_key, validator_store, runtime.clone()) {
+                Ok(status) => Status::ok(status),
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Error importing remotekey, skipped";
+                        "pubkey" => ?pubkey,
+                        "error" => ?e,
+                    );
+                    Status::error(ImportRemotekeyStatus::Error, e)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(ImportRemotekeysResponse { data: statuses })
+}
+
+fn import_single_remotekey<T: SlotClock + 'static, E: EthSpec>(
+    remote_key: SingleImportRemotekeysRequest,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+) -> Result<ImportRemotekeyStatus, String> {
+    Url::parse(&remote_key.url).map_err(|e| format!("invalid url {}: {:?}", remote_key.url, e))?;
+
+    let pubkey = remote_key
+        .pubkey
+        .decompress()
+        .map_err(|e| format!("invalid pubkey, {:?}: {:?}", remote_key.pubkey, e))?;
+
+    if validator_store
+        .initialized_validators()
+        .read()
+        .is_enabled(&pubkey)
+        .is_some()
+    {
+        return Ok(ImportRemotekeyStatus::Duplicate);
+    }
+
+    let validator_definition = ValidatorDefinition {
+        enabled: true,
+        voting_public_key: pubkey,
+        graffiti: None,
+        suggested_fee_recipient: None,
+        gas_limit: None,
+        builder_proposals: None,
+        description: String::from("Added by remotekey API"),
+        signing_definition: SigningDefinition::Web3Signer {
+            url: remote_key.url,
+            root_certificate_path: None,
+            request_timeout_ms: None,
+            client_identity_path: None,
+            client_identity_password: None,
+        },
+    };
+
+    let runtime = runtime
+        .upgrade()
+        .ok_or_else(|| "Runtime shutdown".to_string())?;
+    runtime
+        .block_on(validator_store.add_validator(validator_definition))
+        .map_err(|e| format!("failed to initialize validator: {:?}", e))?;
+
+    Ok(ImportRemotekeyStatus::Imported)
+}
+
+/// Handles `DELETE eth/v1/remotekeys`.
+///
+/// Unlike keystores, no slashing protection data is returned since the remote signer is expected
+/// to maintain its own slashing protection.
+pub fn delete<T: SlotClock + 'static, E: EthSpec>(
+    request: DeleteRemotekeysRequest,
+    validator_store: &ValidatorStore<T, E>,
+    runtime: Weak<Runtime>,
+    log: Logger,
+) -> Result<DeleteRemotekeysResponse, Rejection> {
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rwlock.write();
+
+    let statuses = request
+        .pubkeys
+        .iter()
+        .map(|pubkey_bytes| {
+            match delete_single_remotekey(
+                pubkey_bytes,
+                &mut initialized_validators,
+                runtime.clone(),
+            ) {
+                Ok(status) => Status::ok(status),
+                Err(e) => {
+                    warn!(
+                        log,
+                        "Error deleting remotekey";
+                        "pubkey" => ?pubkey_bytes,
+                        "error" => ?e,
+                    );
+                    Status::error(DeleteRemotekeyStatus::Error, e)
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(DeleteRemotekeysResponse { data: statuses })
+}
+
+fn delete_single_remotekey(
+    pubkey_bytes: &PublicKeyBytes,
+    initialized_validators: &mut InitializedValidators,
+    runtime: Weak<Runtime>,
+) -> Result<DeleteRemotekeyStatus, String> {
+    let pubkey = pubkey_bytes
+        .decompress()
+        .map_err(|e| format!("invalid pubkey, {:?}: {:?}", pubkey_bytes, e))?;
+
+    let runtime = runtime
+        .upgrade()
+        .ok_or_else(|| "Runtime shutdown".to_string())?;
+    match runtime.block_on(initialized_validators.delete_definition_and_keystore(&pubkey, false)) {
+        Ok(()) => Ok(DeleteRemotekeyStatus::Deleted),
+        Err(Error::ValidatorNotInitialized(_)) => Ok(DeleteRemotekeyStatus::NotFound),
+        Err(e) => Err(format!("unable to delete remotekey: {:?}", e)),
+    }
+}
@@ -1,10 +1,12 @@
 #![cfg(test)]
 #![cfg(not(debug_assertions))]
 
+use crate::beacon_node_fallback::{BeaconNodeFallback, CandidateBeaconNode};
 use crate::doppelganger_service::DoppelgangerService;
 use crate::{
     http_api::{ApiSecret, Config as HttpConfig, Context},
     initialized_validators::InitializedValidators,
+    validator_store::DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS,
     Config, ValidatorDefinitions, ValidatorStore,
 };
 use account_utils::{
@@ -14,9 +16,9 @@ use account_utils::{
 use deposit_contract::decode_eth1_tx_data;
 use environment::null_logger;
 use eth2::{
-    lighthouse_vc::{http_client::ValidatorClientHttpClient, types::*},
-    types::ErrorMessage as ApiErrorMessage,
-    Error as ApiError,
+    lighthouse_vc::{http_client::ValidatorClientHttpClient, std_types::*, types::*},
+    types::{ErrorMessage as ApiErrorMessage, ValidatorData as BeaconValidatorData, ValidatorId},
+    BeaconNodeHttpClient, Error as ApiError, Timeouts,
 };
 use eth2_keystore::KeystoreBuilder;
 use parking_lot::RwLock;
@@ -34,13 +36,16 @@ use tokio::runtime::Runtime;
 use tokio::sync::oneshot;
 
 const PASSWORD_BYTES: &[u8] = &[42, 50, 37];
+const MOCK_VALIDATOR_INDEX: u64 = 42;
 
 type E = MainnetEthSpec;
 
 struct ApiTester {
     client: ValidatorClientHttpClient,
     initialized_validators: Arc<RwLock<InitializedValidators>>,
+    slot_clock: TestingSlotClock,
     url: SensitiveUrl,
+    api_token: String,
     _server_shutdown: oneshot::Sender<()>,
     _validator_dir: TempDir,
     _runtime_shutdown: exit_future::Signal,
@@ -58,6 +63,16 @@ fn build_runtime() -> Arc<Runtime> {
 
 impl ApiTester {
     pub async fn new(runtime: std::sync::Weak<Runtime>) -> Self {
+        Self::new_with_beacon_node(runtime, None, Slot::new(0)).await
+    }
+
+    /// Create a tester which uses the beacon node at `beacon_node_url`, if any, and whose slot
+    /// clock reads `current_slot`.
+    pub async fn new_with_beacon_node(
+        runtime: std::sync::Weak<Runtime>,
+        beacon_node_url: Option<SensitiveUrl>,
+        current_slot: Slot,
+    ) -> Self {
         let log = null_logger().unwrap();
 
         let validator_dir = tempdir().unwrap();
@@ -68,6 +83,7 @@ impl ApiTester {
         let initialized_validators = InitializedValidators::from_definitions(
             validator_defs,
             validator_dir.path().into(),
+            None,
             log.clone(),
         )
         .await
@@ -87,6 +103,18 @@ impl ApiTester {
 
         let slot_clock =
             TestingSlotClock::new(Slot::new(0), Duration::from_secs(0), Duration::from_secs(1));
+        slot_clock.set_slot(current_slot.as_u64());
+
+        let beacon_nodes = beacon_node_url.map(|url| {
+            let beacon_node =
+                BeaconNodeHttpClient::new(url, Timeouts::set_all(Duration::from_secs(1)));
+            Arc::new(BeaconNodeFallback::new(
+                vec![CandidateBeaconNode::new(beacon_node)],
+                false,
+                spec.clone(),
+                log.clone(),
+            ))
+        });
 
         let (runtime_shutdown, exit) = exit_future::signal();
         let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
@@ -95,10 +123,11 @@ impl ApiTester {
         let validator_store = ValidatorStore::<_, E>::new(
             initialized_validators,
             slashing_protection,
+            DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS,
             Hash256::repeat_byte(42),
             spec,
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
-            slot_clock,
+            slot_clock.clone(),
             executor,
             log.clone(),
         );
@@ -114,12 +143,17 @@ impl ApiTester {
             api_secret,
             validator_dir: Some(validator_dir.path().into()),
             validator_store: Some(Arc::new(validator_store)),
+            beacon_nodes,
+            slot_clock: slot_clock.clone(),
             spec: E::default_spec(),
             config: HttpConfig {
                 enabled: true,
                 listen_addr: Ipv4Addr::new(127, 0, 0, 1),
                 listen_port: 0,
                 allow_origin: None,
+                allow_methods: None,
+                allow_headers: None,
+                allow_credentials: false,
             },
             log,
             _phantom: PhantomData,
@@ -141,13 +175,15 @@ impl ApiTester {
         ))
         .unwrap();
 
-        let client = ValidatorClientHttpClient::new(url.clone(), api_pubkey).unwrap();
+        let client = ValidatorClientHttpClient::new(url.clone(), api_pubkey.clone()).unwrap();
 
         Self {
             initialized_validators,
+            slot_clock,
             _validator_dir: validator_dir,
             client,
             url,
+            api_token: api_pubkey,
             _server_shutdown: shutdown_tx,
             _runtime_shutdown: runtime_shutdown,
         }
@@ -449,6 +485,8 @@ impl ApiTester {
                     url: format!("http://signer_{}.com/", i),
                     root_certificate_path: None,
                     request_timeout_ms: None,
+                    client_identity_path: None,
+                    client_identity_password: None,
                 }
             })
             .collect();
@@ -468,6 +506,387 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_std_keystores(self) -> Self {
+        let initial_vals = self.vals_total();
+
+        let password = random_password_string();
+        let keystores = (0..3)
+            .map(|_| {
+                KeystoreBuilder::new(&Keypair::random(), password.as_ref(), String::new())
+                    .unwrap()
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let pubkeys = keystores
+            .iter()
+            .map(|keystore| keystore.public_key().unwrap().compress())
+            .collect::<Vec<_>>();
+
+        // The last keystore has an incorrect password.
+        let request = ImportKeystoresRequest {
+            keystores: keystores.iter().cloned().map(KeystoreJsonStr).collect(),
+            passwords: vec![password.clone(), password.clone(), random_password_string()],
+            slashing_protection: None,
+        };
+        let response = self.client.post_keystores(&request).await.unwrap();
+        let statuses = response
+            .data
+            .iter()
+            .map(|status| status.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                ImportKeystoreStatus::Imported,
+                ImportKeystoreStatus::Imported,
+                ImportKeystoreStatus::Error
+            ]
+        );
+        assert!(response.data[2].message.is_some());
+        assert_eq!(self.vals_total(), initial_vals + 2);
+
+        // Importing the same keystores again reports them as duplicates.
+        let request = ImportKeystoresRequest {
+            keystores: keystores[..2]
+                .iter()
+                .cloned()
+                .map(KeystoreJsonStr)
+                .collect(),
+            passwords: vec![password.clone(), password.clone()],
+            slashing_protection: None,
+        };
+        let response = self.client.post_keystores(&request).await.unwrap();
+        assert!(response
+            .data
+            .iter()
+            .all(|status| status.status == ImportKeystoreStatus::Duplicate));
+        assert_eq!(self.vals_total(), initial_vals + 2);
+
+        // Mismatched keystores and passwords are rejected outright.
+        let request = ImportKeystoresRequest {
+            keystores: keystores[..2]
+                .iter()
+                .cloned()
+                .map(KeystoreJsonStr)
+                .collect(),
+            passwords: vec![password.clone()],
+            slashing_protection: None,
+        };
+        self.client.post_keystores(&request).await.unwrap_err();
+
+        let listed = self.client.get_keystores().await.unwrap().data;
+        for pubkey in &pubkeys[..2] {
+            assert!(listed
+                .iter()
+                .any(|keystore| keystore.validating_pubkey == *pubkey));
+        }
+        assert!(!listed
+            .iter()
+            .any(|keystore| keystore.validating_pubkey == pubkeys[2]));
+
+        // Delete the first keystore, along with the keystore which was never imported.
+        let request = DeleteKeystoresRequest {
+            pubkeys: vec![pubkeys[0], pubkeys[2]],
+        };
+        let response = self.client.delete_keystores(&request).await.unwrap();
+        assert_eq!(response.data[0].status, DeleteKeystoreStatus::Deleted);
+        assert_eq!(response.data[1].status, DeleteKeystoreStatus::NotFound);
+        assert_eq!(response.slashing_protection.data.len(), 1);
+        assert_eq!(response.slashing_protection.data[0].pubkey, pubkeys[0]);
+        assert_eq!(self.vals_total(), initial_vals + 1);
+
+        // A deleted keystore still has slashing protection data, so it is reported as inactive.
+        let request = DeleteKeystoresRequest {
+            pubkeys: vec![pubkeys[0]],
+        };
+        let response = self.client.delete_keystores(&request).await.unwrap();
+        assert_eq!(response.data[0].status, DeleteKeystoreStatus::NotActive);
+        assert_eq!(response.slashing_protection.data.len(), 1);
+
+        // A deleted keystore can be re-imported along with its slashing protection data.
+        let request = ImportKeystoresRequest {
+            keystores: vec![KeystoreJsonStr(keystores[0].clone())],
+            passwords: vec![password],
+            slashing_protection: Some(InterchangeJsonStr(response.slashing_protection)),
+        };
+        let response = self.client.post_keystores(&request).await.unwrap();
+        assert_eq!(response.data[0].status, ImportKeystoreStatus::Imported);
+        assert_eq!(self.vals_total(), initial_vals + 2);
+
+        self
+    }
+
+    pub async fn test_lighthouse_keystores_export(self) -> Self {
+        let initial_vals = self.vals_total();
+
+        let password = random_password_string();
+        let keystore = KeystoreBuilder::new(&Keypair::random(), password.as_ref(), String::new())
+            .unwrap()
+            .build()
+            .unwrap();
+        let pubkey = keystore.public_key().unwrap().compress();
+        let unknown_pubkey = Keypair::random().pk.compress();
+
+        let request = ImportKeystoresRequest {
+            keystores: vec![KeystoreJsonStr(keystore.clone())],
+            passwords: vec![password.clone()],
+            slashing_protection: None,
+        };
+        let response = self.client.post_keystores(&request).await.unwrap();
+        assert_eq!(response.data[0].status, ImportKeystoreStatus::Imported);
+        assert_eq!(self.vals_total(), initial_vals + 1);
+
+        // The keystore is returned with its password, and an unknown keystore is not found.
+        let request = DeleteKeystoresRequest {
+            pubkeys: vec![pubkey, unknown_pubkey],
+        };
+        let response = self
+            .client
+            .delete_lighthouse_keystores(&request)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.data[0].status.status,
+            DeleteKeystoreStatus::Deleted
+        );
+        assert_eq!(
+            response.data[0]
+                .validating_keystore
+                .as_ref()
+                .unwrap()
+                .public_key(),
+            keystore.public_key()
+        );
+        assert!(response.data[0].validating_keystore_password.as_ref() == Some(&password));
+        assert_eq!(
+            response.data[1].status.status,
+            DeleteKeystoreStatus::NotFound
+        );
+        assert!(response.data[1].validating_keystore.is_none());
+        assert_eq!(response.slashing_protection.data.len(), 1);
+        assert_eq!(self.vals_total(), initial_vals);
+
+        // The exported keystore can be imported again.
+        let exported = response.data.into_iter().next().unwrap();
+        let request = ImportKeystoresRequest {
+            keystores: vec![exported.validating_keystore.unwrap()],
+            passwords: vec![exported.validating_keystore_password.unwrap()],
+            slashing_protection: Some(InterchangeJsonStr(response.slashing_protection)),
+        };
+        let response = self.client.post_keystores(&request).await.unwrap();
+        assert_eq!(response.data[0].status, ImportKeystoreStatus::Imported);
+        assert_eq!(self.vals_total(), initial_vals + 1);
+
+        self
+    }
+
+    pub async fn test_std_remotekeys(self) -> Self {
+        let initial_vals = self.vals_total();
+
+        let pubkeys = (0..2)
+            .map(|_| Keypair::random().pk.compress())
+            .collect::<Vec<_>>();
+        let request = ImportRemotekeysRequest {
+            remote_keys: pubkeys
+                .iter()
+                .map(|pubkey| SingleImportRemotekeysRequest {
+                    pubkey: *pubkey,
+                    url: "http://signer.com/".to_string(),
+                })
+                .collect(),
+        };
+        let response = self.client.post_remotekeys(&request).await.unwrap();
+        assert!(response
+            .data
+            .iter()
+            .all(|status| status.status == ImportRemotekeyStatus::Imported));
+        assert_eq!(self.vals_total(), initial_vals + 2);
+
+        let response = self.client.post_remotekeys(&request).await.unwrap();
+        assert!(response
+            .data
+            .iter()
+            .all(|status| status.status == ImportRemotekeyStatus::Duplicate));
+
+        let listed = self.client.get_remotekeys().await.unwrap().data;
+        for pubkey in &pubkeys {
+            assert!(listed.iter().any(|remotekey| remotekey.pubkey == *pubkey));
+        }
+
+        // Remote keys are not listed or deleted by the keystores endpoints.
+        let keystores = self.client.get_keystores().await.unwrap().data;
+        assert!(!keystores
+            .iter()
+            .any(|keystore| keystore.validating_pubkey == pubkeys[0]));
+        let response = self
+            .client
+            .delete_keystores(&DeleteKeystoresRequest {
+                pubkeys: vec![pubkeys[0]],
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.data[0].status, DeleteKeystoreStatus::Error);
+        assert_eq!(self.vals_total(), initial_vals + 2);
+
+        let response = self
+            .client
+            .delete_remotekeys(&DeleteRemotekeysRequest {
+                pubkeys: vec![pubkeys[0], Keypair::random().pk.compress()],
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.data[0].status, DeleteRemotekeyStatus::Deleted);
+        assert_eq!(response.data[1].status, DeleteRemotekeyStatus::NotFound);
+        assert_eq!(self.vals_total(), initial_vals + 1);
+
+        self
+    }
+
+    pub async fn test_fee_recipient_and_gas_limit(self) -> Self {
+        let pubkey = self.client.get_lighthouse_validators().await.unwrap().data[0].voting_pubkey;
+
+        // No fee recipient is set by default.
+        assert!(matches!(
+            self.client.get_fee_recipient(&pubkey).await,
+            Err(ApiError::ServerMessage(ApiErrorMessage { code: 404, .. }))
+        ));
+        assert_eq!(
+            self.client.get_gas_limit(&pubkey).await.unwrap().data,
+            GetGasLimitResponse {
+                pubkey,
+                gas_limit: crate::validator_store::DEFAULT_GAS_LIMIT,
+            }
+        );
+
+        let ethaddress = Address::repeat_byte(42);
+        self.client
+            .post_fee_recipient(&pubkey, &UpdateFeeRecipientRequest { ethaddress })
+            .await
+            .unwrap();
+        self.client
+            .post_gas_limit(&pubkey, &UpdateGasLimitRequest { gas_limit: 1_000 })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            self.client.get_fee_recipient(&pubkey).await.unwrap().data,
+            GetFeeRecipientResponse { pubkey, ethaddress }
+        );
+        assert_eq!(
+            self.client
+                .get_gas_limit(&pubkey)
+                .await
+                .unwrap()
+                .data
+                .gas_limit,
+            1_000
+        );
+
+        self.client.delete_fee_recipient(&pubkey).await.unwrap();
+        self.client.delete_gas_limit(&pubkey).await.unwrap();
+
+        self.client.get_fee_recipient(&pubkey).await.unwrap_err();
+        assert_eq!(
+            self.client
+                .get_gas_limit(&pubkey)
+                .await
+                .unwrap()
+                .data
+                .gas_limit,
+            crate::validator_store::DEFAULT_GAS_LIMIT
+        );
+
+        // Unknown validators are not found.
+        let unknown = Keypair::random().pk.compress();
+        assert!(matches!(
+            self.client.get_gas_limit(&unknown).await,
+            Err(ApiError::ServerMessage(ApiErrorMessage { code: 404, .. }))
+        ));
+        assert!(matches!(
+            self.client
+                .post_fee_recipient(&unknown, &UpdateFeeRecipientRequest { ethaddress })
+                .await,
+            Err(ApiError::ServerMessage(ApiErrorMessage { code: 404, .. }))
+        ));
+
+        self
+    }
+
+    pub async fn test_bearer_auth(self) -> Self {
+        let url = format!("{}eth/v1/keystores", self.url.full);
+        let response = reqwest::Client::new()
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", self.api_token))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        let response = reqwest::Client::new()
+            .get(&url)
+            .header(
+                "Authorization",
+                format!("Bearer {}", self.api_token.to_uppercase()),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+
+        self
+    }
+
+    pub async fn test_post_validators_exit(self, beacon_node: &MockBeaconNode) -> Self {
+        let pubkey = self.client.get_lighthouse_validators().await.unwrap().data[0].voting_pubkey;
+
+        let signed_exit = self
+            .client
+            .post_lighthouse_validators_exit(&pubkey)
+            .await
+            .unwrap()
+            .data;
+
+        let spec = E::default_spec();
+        let epoch = self.slot_clock.now().unwrap().epoch(E::slots_per_epoch());
+        assert_eq!(
+            signed_exit.message,
+            VoluntaryExit {
+                epoch,
+                validator_index: MOCK_VALIDATOR_INDEX,
+            }
+        );
+        let domain = spec.get_domain(
+            epoch,
+            Domain::VoluntaryExit,
+            &spec.fork_at_epoch(epoch),
+            Hash256::repeat_byte(42),
+        );
+        assert!(signed_exit.signature.verify(
+            &pubkey.decompress().unwrap(),
+            signed_exit.message.signing_root(domain)
+        ));
+
+        // The exit must have been published to the beacon node.
+        assert_eq!(*beacon_node.voluntary_exits.read(), vec![signed_exit]);
+
+        self
+    }
+
+    pub async fn test_post_validators_exit_ineligible(self, beacon_node: &MockBeaconNode) -> Self {
+        let pubkey = self.client.get_lighthouse_validators().await.unwrap().data[0].voting_pubkey;
+
+        match self.client.post_lighthouse_validators_exit(&pubkey).await {
+            Err(ApiError::ServerMessage(ApiErrorMessage {
+                code: 400, message, ..
+            })) if message.contains("not eligible to exit") => (),
+            other => panic!("expected ineligible exit error, got {:?}", other),
+        }
+        assert!(beacon_node.voluntary_exits.read().is_empty());
+
+        self
+    }
+
     pub async fn set_validator_enabled(self, index: usize, enabled: bool) -> Self {
         let validator = &self.client.get_lighthouse_validators().await.unwrap().data[index];
 
@@ -511,6 +930,12 @@ impl ApiTester {
     }
 }
 
+fn random_password_string() -> ZeroizeString {
+    String::from_utf8(random_password().as_ref().to_vec())
+        .unwrap()
+        .into()
+}
+
 struct HdValidatorScenario {
     count: usize,
     specify_mnemonic: bool,
@@ -528,6 +953,95 @@ struct Web3SignerValidatorScenario {
     enabled: bool,
 }
 
+/// A beacon node which reports every validator as active since `activation_epoch`, with the index
+/// `MOCK_VALIDATOR_INDEX`, and records the voluntary exits published to it.
+struct MockBeaconNode {
+    url: SensitiveUrl,
+    voluntary_exits: Arc<RwLock<Vec<SignedVoluntaryExit>>>,
+    _server_shutdown: oneshot::Sender<()>,
+}
+
+impl MockBeaconNode {
+    pub fn new(activation_epoch: Epoch) -> Self {
+        let spec = E::default_spec();
+        let voluntary_exits = Arc::new(RwLock::new(vec![]));
+
+        let get_node_version = warp::path!("eth" / "v1" / "node" / "version")
+            .and(warp::get())
+            .map(|| {
+                warp::reply::json(&GenericResponse::from(VersionData {
+                    version: lighthouse_version::version_with_platform(),
+                }))
+            });
+
+        let config_and_preset = ConfigAndPreset::from_chain_spec::<E>(&spec);
+        let get_config_spec = warp::path!("eth" / "v1" / "config" / "spec")
+            .and(warp::get())
+            .map(move || warp::reply::json(&GenericResponse::from(config_and_preset.clone())));
+
+        let get_validator =
+            warp::path!("eth" / "v1" / "beacon" / "states" / "head" / "validators" / ValidatorId)
+                .and(warp::get())
+                .map(move |validator_id: ValidatorId| {
+                    let pubkey = match validator_id {
+                        ValidatorId::PublicKey(pubkey) => pubkey,
+                        ValidatorId::Index(_) => PublicKeyBytes::empty(),
+                    };
+                    warp::reply::json(&GenericResponse::from(BeaconValidatorData {
+                        index: MOCK_VALIDATOR_INDEX,
+                        balance: spec.max_effective_balance,
+                        status: ValidatorStatus::ActiveOngoing,
+                        validator: Validator {
+                            pubkey,
+                            withdrawal_credentials: Hash256::zero(),
+                            effective_balance: spec.max_effective_balance,
+                            slashed: false,
+                            activation_eligibility_epoch: Epoch::new(0),
+                            activation_epoch,
+                            exit_epoch: spec.far_future_epoch,
+                            withdrawable_epoch: spec.far_future_epoch,
+                        },
+                    }))
+                });
+
+        let inner_voluntary_exits = voluntary_exits.clone();
+        let post_voluntary_exits =
+            warp::path!("eth" / "v1" / "beacon" / "pool" / "voluntary_exits")
+                .and(warp::post())
+                .and(warp::body::json())
+                .map(move |exit: SignedVoluntaryExit| {
+                    inner_voluntary_exits.write().push(exit);
+                    warp::reply()
+                });
+
+        let routes = get_node_version
+            .or(get_config_spec)
+            .or(get_validator)
+            .or(post_voluntary_exits);
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let (listening_socket, server) = warp::serve(routes).bind_with_graceful_shutdown(
+            (Ipv4Addr::new(127, 0, 0, 1), 0),
+            async {
+                let _ = shutdown_rx.await;
+            },
+        );
+        tokio::spawn(server);
+
+        let url = SensitiveUrl::parse(&format!(
+            "http://{}:{}",
+            listening_socket.ip(),
+            listening_socket.port()
+        ))
+        .unwrap();
+
+        Self {
+            url,
+            voluntary_exits,
+            _server_shutdown: shutdown_tx,
+        }
+    }
+}
+
 #[test]
 fn invalid_pubkey() {
     let runtime = build_runtime();
@@ -608,6 +1122,94 @@ fn routes_with_invalid_auth() {
                     .await
             })
             .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .post_lighthouse_validators_exit(&PublicKeyBytes::empty())
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .get_lighthouse_validators_exit(&PublicKeyBytes::empty())
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move { client.get_keystores().await })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .post_keystores(&ImportKeystoresRequest {
+                        keystores: vec![],
+                        passwords: vec![],
+                        slashing_protection: None,
+                    })
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .delete_keystores(&DeleteKeystoresRequest { pubkeys: vec![] })
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move { client.get_remotekeys().await })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .post_remotekeys(&ImportRemotekeysRequest {
+                        remote_keys: vec![],
+                    })
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .delete_remotekeys(&DeleteRemotekeysRequest { pubkeys: vec![] })
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .delete_lighthouse_keystores(&DeleteKeystoresRequest { pubkeys: vec![] })
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client.get_fee_recipient(&PublicKeyBytes::empty()).await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .post_fee_recipient(
+                        &PublicKeyBytes::empty(),
+                        &UpdateFeeRecipientRequest {
+                            ethaddress: <_>::default(),
+                        },
+                    )
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client.delete_fee_recipient(&PublicKeyBytes::empty()).await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client.get_gas_limit(&PublicKeyBytes::empty()).await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client
+                    .post_gas_limit(
+                        &PublicKeyBytes::empty(),
+                        &UpdateGasLimitRequest { gas_limit: 0 },
+                    )
+                    .await
+            })
+            .await
+            .test_with_invalid_auth(|client| async move {
+                client.delete_gas_limit(&PublicKeyBytes::empty()).await
+            })
+            .await
     });
 }
 
@@ -744,3 +1346,112 @@ fn web3signer_validator_creation() {
             .assert_validators_count(1);
     });
 }
+
+#[test]
+fn std_keystores() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime)
+            .await
+            .test_std_keystores()
+            .await;
+    });
+}
+
+#[test]
+fn lighthouse_keystores_export() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime)
+            .await
+            .test_lighthouse_keystores_export()
+            .await;
+    });
+}
+
+#[test]
+fn std_remotekeys() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime)
+            .await
+            .test_std_remotekeys()
+            .await;
+    });
+}
+
+#[test]
+fn fee_recipient_and_gas_limit() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime)
+            .await
+            .create_hd_validators(HdValidatorScenario {
+                count: 1,
+                specify_mnemonic: false,
+                key_derivation_path_offset: 0,
+                disabled: vec![],
+            })
+            .await
+            .test_fee_recipient_and_gas_limit()
+            .await;
+    });
+}
+
+#[test]
+fn bearer_auth() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        ApiTester::new(weak_runtime).await.test_bearer_auth().await;
+    });
+}
+
+#[test]
+fn validator_exit() {
+    let runtime = build_runtime();
+    let weak_runtime = Arc::downgrade(&runtime);
+    runtime.block_on(async {
+        let spec = E::default_spec();
+
+        // The validator may exit once it has been active for the shard committee period.
+        let eligible_epoch = spec.shard_committee_period;
+        let beacon_node = MockBeaconNode::new(Epoch::new(0));
+        ApiTester::new_with_beacon_node(
+            weak_runtime.clone(),
+            Some(beacon_node.url.clone()),
+            Epoch::new(eligible_epoch).start_slot(E::slots_per_epoch()),
+        )
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_post_validators_exit(&beacon_node)
+        .await;
+
+        let beacon_node = MockBeaconNode::new(Epoch::new(0));
+        ApiTester::new_with_beacon_node(
+            weak_runtime,
+            Some(beacon_node.url.clone()),
+            Epoch::new(eligible_epoch - 1).start_slot(E::slots_per_epoch()),
+        )
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 1,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![],
+        })
+        .await
+        .test_post_validators_exit_ineligible(&beacon_node)
+        .await;
+    });
+}
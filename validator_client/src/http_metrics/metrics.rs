@@ -1,7 +1,9 @@
 use super::Context;
-use slot_clock::SlotClock;
+use crate::DutiesService;
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
-use types::EthSpec;
+use types::{EthSpec, PublicKeyBytes, Slot};
 
 pub const SUCCESS: &str = "success";
 pub const SLASHABLE: &str = "slashable";
@@ -11,6 +13,10 @@ pub const FULL_UPDATE: &str = "full_update";
 pub const BEACON_BLOCK: &str = "beacon_block";
 pub const BEACON_BLOCK_HTTP_GET: &str = "beacon_block_http_get";
 pub const BEACON_BLOCK_HTTP_POST: &str = "beacon_block_http_post";
+pub const BEACON_BLOCK_SIGN: &str = "beacon_block_sign";
+pub const BLINDED_BEACON_BLOCK_HTTP_GET: &str = "blinded_beacon_block_http_get";
+pub const BLINDED_BEACON_BLOCK_HTTP_POST: &str = "blinded_beacon_block_http_post";
+pub const BLINDED_BEACON_BLOCK_SIGN: &str = "blinded_beacon_block_sign";
 pub const ATTESTATIONS: &str = "attestations";
 pub const ATTESTATIONS_HTTP_GET: &str = "attestations_http_get";
 pub const ATTESTATIONS_HTTP_POST: &str = "attestations_http_post";
@@ -32,6 +38,8 @@ pub const UPDATE_PROPOSERS: &str = "update_proposers";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const ATTESTER: &str = "attester";
+pub const PROPOSER: &str = "proposer";
 
 pub use lighthouse_metrics::*;
 
@@ -130,6 +138,16 @@ lazy_static::lazy_static! {
         "The number of beacon node requests for each endpoint",
         &["endpoint"]
     );
+    pub static ref ENDPOINT_LATENCY: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "bn_endpoint_latency_milliseconds",
+        "The latency of the most recent health check for each endpoint",
+        &["endpoint"]
+    );
+    pub static ref ENDPOINT_SYNC_DISTANCE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "bn_endpoint_sync_distance",
+        "The sync distance reported by the most recent health check for each endpoint",
+        &["endpoint"]
+    );
 
     pub static ref ETH2_FALLBACK_CONFIGURED: Result<IntGauge> = try_create_int_gauge(
         "sync_eth2_fallback_configured",
@@ -140,6 +158,33 @@ lazy_static::lazy_static! {
         "sync_eth2_fallback_connected",
         "Set to 1 if connected to atleast one synced eth2 fallback node, otherwise set to 0",
     );
+    /*
+     * Validator performance metrics
+     */
+    pub static ref VALIDATORS_EFFECTIVE: Result<IntGauge> = try_create_int_gauge(
+        "vc_validators_effective",
+        "Number of validators whose attestation was included on-chain in the last checked epoch"
+    );
+    pub static ref VALIDATOR_LAST_ATTESTATION_SLOT: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_validator_last_attestation_slot",
+        "The slot of the most recent attestation by each validator which was included on-chain",
+        &["validator"]
+    );
+    pub static ref VALIDATOR_ATTESTATION_INCLUSION_DISTANCE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_validator_attestation_inclusion_distance",
+        "The inclusion distance of the most recent attestation by each validator",
+        &["validator"]
+    );
+    pub static ref VALIDATOR_MISSED_DUTIES_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_validator_missed_duties_total",
+        "Total count of duties by each validator which were not included on-chain",
+        &["validator", "duty"]
+    );
+    pub static ref VALIDATOR_NEXT_DUTY_SECONDS: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "vc_validator_next_duty_seconds",
+        "Seconds until the next known duty of each validator",
+        &["validator", "duty"]
+    );
     /*
      * Signing Metrics
      */
@@ -186,6 +231,8 @@ pub fn gather_prometheus_metrics<T: EthSpec>(
                     &[NEXT_EPOCH],
                     duties_service.attester_count(next_epoch) as i64,
                 );
+
+                set_next_duty_gauges(duties_service, slot);
             }
         }
     }
@@ -198,3 +245,52 @@ pub fn gather_prometheus_metrics<T: EthSpec>(
 
     String::from_utf8(buffer).map_err(|e| format!("Failed to encode prometheus info: {:?}", e))
 }
+
+/// Set the time until the next attester and proposer duties of each validator, starting from
+/// `current_slot`.
+fn set_next_duty_gauges<T: EthSpec>(
+    duties_service: &DutiesService<SystemTimeSlotClock, T>,
+    current_slot: Slot,
+) {
+    let mut next_attestations: HashMap<PublicKeyBytes, Slot> = HashMap::new();
+    for (pubkey, duties) in duties_service.attesters.read().iter() {
+        if let Some(slot) = duties
+            .values()
+            .map(|(_, duty_and_proof)| duty_and_proof.duty.slot)
+            .filter(|slot| *slot >= current_slot)
+            .min()
+        {
+            next_attestations.insert(*pubkey, slot);
+        }
+    }
+
+    let mut next_proposals: HashMap<PublicKeyBytes, Slot> = HashMap::new();
+    for (_, proposers) in duties_service.proposers.read().values() {
+        for proposer in proposers.iter().filter(|p| p.slot >= current_slot) {
+            let slot = next_proposals
+                .entry(proposer.pubkey)
+                .or_insert(proposer.slot);
+            *slot = std::cmp::min(*slot, proposer.slot);
+        }
+    }
+
+    // Remove the gauges of validators without a known duty, rather than reporting a stale value.
+    if let Ok(gauge_vec) = VALIDATOR_NEXT_DUTY_SECONDS.as_ref() {
+        gauge_vec.reset();
+    }
+
+    let slot_clock = &duties_service.slot_clock;
+    for (duty, next_duties) in [(ATTESTER, next_attestations), (PROPOSER, next_proposals)] {
+        for (pubkey, slot) in next_duties {
+            // A duty in the current slot has already started.
+            let seconds = slot_clock
+                .duration_to_slot(slot)
+                .map_or(0, |duration| duration.as_secs());
+            set_int_gauge(
+                &VALIDATOR_NEXT_DUTY_SECONDS,
+                &[&format!("{:?}", pubkey), duty],
+                seconds as i64,
+            );
+        }
+    }
+}
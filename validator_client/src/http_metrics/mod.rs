@@ -14,6 +14,7 @@ use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::Arc;
 use types::EthSpec;
 use warp::{http::Response, Filter};
+use warp_utils::cors::CorsDefaults;
 
 #[derive(Debug)]
 pub enum Error {
@@ -56,6 +57,12 @@ pub struct Config {
     pub listen_addr: Ipv4Addr,
     pub listen_port: u16,
     pub allow_origin: Option<String>,
+    /// A comma-separated list of the methods allowed by CORS, replacing the defaults.
+    pub allow_methods: Option<String>,
+    /// A comma-separated list of the headers allowed by CORS, replacing the defaults.
+    pub allow_headers: Option<String>,
+    /// Whether CORS requests may include credentials.
+    pub allow_credentials: bool,
 }
 
 impl Default for Config {
@@ -65,6 +72,9 @@ impl Default for Config {
             listen_addr: Ipv4Addr::new(127, 0, 0, 1),
             listen_port: 5064,
             allow_origin: None,
+            allow_methods: None,
+            allow_headers: None,
+            allow_credentials: false,
         }
     }
 }
@@ -92,17 +102,17 @@ pub fn serve<T: EthSpec>(
     let log = ctx.log.clone();
 
     // Configure CORS.
-    let cors_builder = {
-        let builder = warp::cors()
-            .allow_method("GET")
-            .allow_headers(vec!["Content-Type"]);
-
-        warp_utils::cors::set_builder_origins(
-            builder,
-            config.allow_origin.as_deref(),
-            (config.listen_addr, config.listen_port),
-        )?
-    };
+    let cors_builder = warp_utils::cors::build_cors(
+        config.allow_origin.as_deref(),
+        config.allow_methods.as_deref(),
+        config.allow_headers.as_deref(),
+        config.allow_credentials,
+        CorsDefaults {
+            origin: (config.listen_addr, config.listen_port),
+            methods: &["GET"],
+            headers: &["Content-Type"],
+        },
+    )?;
 
     // Sanity check.
     if !config.enabled {
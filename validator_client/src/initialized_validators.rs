@@ -14,18 +14,20 @@ use account_utils::{
     },
     ZeroizeString,
 };
-use eth2_keystore::Keystore;
+use eth2_keystore::{Keystore, PlainText};
 use lighthouse_metrics::set_gauge;
 use lockfile::{Lockfile, LockfileError};
-use reqwest::{Certificate, Client, Error as ReqwestError};
+use rayon::prelude::*;
+use reqwest::{Certificate, Client, Error as ReqwestError, Identity};
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use types::{Graffiti, Keypair, PublicKey, PublicKeyBytes};
+use types::{Address, Graffiti, Keypair, PublicKey, PublicKeyBytes};
 use url::{ParseError, Url};
 
 use crate::key_cache;
@@ -65,6 +67,10 @@ pub enum Error {
     UnableToReadVotingKeystorePassword(io::Error),
     /// There was an error updating the on-disk validator definitions file.
     UnableToSaveDefinitions(validator_definitions::Error),
+    /// There was an error reading the on-disk validator definitions file.
+    UnableToOpenDefinitions(validator_definitions::Error),
+    /// There was an error searching the validators directory for keystores.
+    UnableToDiscoverKeystores(validator_definitions::Error),
     /// It is not legal to try and initialize a disabled validator definition.
     UnableToInitializeDisabledValidator,
     /// There was an error reading from stdin.
@@ -82,7 +88,20 @@ pub enum Error {
     /// Unable to read the root certificate file for the remote signer.
     InvalidWeb3SignerRootCertificateFile(io::Error),
     InvalidWeb3SignerRootCertificate(ReqwestError),
+    /// Unable to read the client identity (PKCS12) file for the remote signer.
+    InvalidWeb3SignerClientIdentityCertificateFile(io::Error),
+    InvalidWeb3SignerClientIdentityCertificate(ReqwestError),
     UnableToBuildWeb3SignerClient(ReqwestError),
+    /// The action is not permitted for the signing method of the validator (e.g., deleting the
+    /// keystore of a remote signer).
+    InvalidActionOnValidator,
+    /// Unable to remove the voting keystore from the filesystem.
+    UnableToDeleteKeystore(PathBuf, io::Error),
+    /// Unable to remove the validator directory from the filesystem.
+    UnableToDeleteValidatorDir(PathBuf, io::Error),
+    /// The keystore password is neither in the definition nor in a file, or is not UTF-8, so the
+    /// keystore cannot be exported.
+    UnableToExportKeystorePassword(PublicKey),
 }
 
 impl From<LockfileError> for Error {
@@ -226,6 +245,8 @@ impl InitializedValidator {
                 url,
                 root_certificate_path,
                 request_timeout_ms,
+                client_identity_path,
+                client_identity_password,
             } => {
                 let signing_url = build_web3_signer_url(&url, &def.voting_public_key)
                     .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
@@ -242,6 +263,17 @@ impl InitializedValidator {
                     builder
                 };
 
+                // A PKCS12 file without a password is decrypted with the empty password.
+                let builder = if let Some(path) = client_identity_path {
+                    let identity = load_pkcs12_identity(
+                        path,
+                        client_identity_password.as_deref().unwrap_or_default(),
+                    )?;
+                    builder.identity(identity)
+                } else {
+                    builder
+                };
+
                 let http_client = builder
                     .build()
                     .map_err(Error::UnableToBuildWeb3SignerClient)?;
@@ -281,6 +313,19 @@ pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate,
     Certificate::from_pem(&buf).map_err(Error::InvalidWeb3SignerRootCertificate)
 }
 
+pub fn load_pkcs12_identity<P: AsRef<Path>>(
+    pkcs12_path: P,
+    password: &str,
+) -> Result<Identity, Error> {
+    let mut buf = Vec::new();
+    File::open(&pkcs12_path)
+        .map_err(Error::InvalidWeb3SignerClientIdentityCertificateFile)?
+        .read_to_end(&mut buf)
+        .map_err(Error::InvalidWeb3SignerClientIdentityCertificateFile)?;
+    Identity::from_pkcs12_der(&buf, password)
+        .map_err(Error::InvalidWeb3SignerClientIdentityCertificate)
+}
+
 fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Result<Url, ParseError> {
     Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
 }
@@ -335,21 +380,27 @@ pub struct InitializedValidators {
     validators_dir: PathBuf,
     /// The canonical set of validators.
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
+    /// The maximum number of keystores to decrypt at once, or `None` for one per CPU.
+    decryption_threads: Option<usize>,
     /// For logging via `slog`.
     log: Logger,
 }
 
 impl InitializedValidators {
     /// Instantiates `Self`, initializing all validators in `definitions`.
+    ///
+    /// Keystores are decrypted on up to `decryption_threads` threads, or one per CPU if `None`.
     pub async fn from_definitions(
         definitions: ValidatorDefinitions,
         validators_dir: PathBuf,
+        decryption_threads: Option<usize>,
         log: Logger,
     ) -> Result<Self, Error> {
         let mut this = Self {
             validators_dir,
             definitions,
             validators: HashMap::default(),
+            decryption_threads,
             log,
         };
         this.update_validators().await?;
@@ -394,7 +445,11 @@ impl InitializedValidators {
 
         self.definitions.push(def);
 
-        self.update_validators().await?;
+        // Forget the definition if it cannot be initialized, so that it is not saved.
+        if let Err(e) = self.update_validators().await {
+            self.definitions.pop();
+            return Err(e);
+        }
 
         self.definitions
             .save(&self.validators_dir)
@@ -403,6 +458,54 @@ impl InitializedValidators {
         Ok(())
     }
 
+    /// Returns the definitions which have been added to the on-disk definitions file since it was
+    /// loaded, for example by another process. If `secrets_dir` is supplied, keystores which have
+    /// been added to the validators directory are also discovered.
+    ///
+    /// Local keystores without a password are skipped, since they would require a prompt on stdin.
+    pub fn new_definitions_on_disk(
+        &self,
+        secrets_dir: Option<&Path>,
+    ) -> Result<Vec<ValidatorDefinition>, Error> {
+        let mut on_disk = ValidatorDefinitions::open(&self.validators_dir)
+            .map_err(Error::UnableToOpenDefinitions)?;
+
+        if let Some(secrets_dir) = secrets_dir {
+            on_disk
+                .discover_local_keystores(self.validators_dir.as_path(), secrets_dir, &self.log)
+                .map_err(Error::UnableToDiscoverKeystores)?;
+        }
+
+        let known: HashSet<&PublicKey> = self
+            .definitions
+            .as_slice()
+            .iter()
+            .map(|def| &def.voting_public_key)
+            .collect();
+
+        Ok(on_disk
+            .as_slice()
+            .iter()
+            .filter(|def| !known.contains(&def.voting_public_key))
+            .filter(|def| match &def.signing_definition {
+                SigningDefinition::LocalKeystore {
+                    voting_keystore_password_path: None,
+                    voting_keystore_password: None,
+                    ..
+                } if def.enabled => {
+                    warn!(
+                        self.log,
+                        "Not loading keystore without a password";
+                        "voting_pubkey" => ?def.voting_public_key,
+                    );
+                    false
+                }
+                _ => true,
+            })
+            .cloned()
+            .collect())
+    }
+
     /// Returns a slice of all defined validators (regardless of their enabled state).
     pub fn validator_definitions(&self) -> &[ValidatorDefinition] {
         self.definitions.as_slice()
@@ -422,6 +525,231 @@ impl InitializedValidators {
         self.validators.get(public_key).and_then(|v| v.graffiti)
     }
 
+    /// Returns the `suggested_fee_recipient` for a given public key specified in the
+    /// `ValidatorDefinitions`, regardless of whether the validator is enabled.
+    pub fn suggested_fee_recipient(&self, public_key: &PublicKey) -> Option<Address> {
+        self.definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *public_key)
+            .and_then(|def| def.suggested_fee_recipient)
+    }
+
+    /// Returns the `gas_limit` for a given public key specified in the `ValidatorDefinitions`,
+    /// regardless of whether the validator is enabled.
+    pub fn gas_limit(&self, public_key: &PublicKey) -> Option<u64> {
+        self.definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *public_key)
+            .and_then(|def| def.gas_limit)
+    }
+
+    /// Returns the `builder_proposals` for a given public key specified in the
+    /// `ValidatorDefinitions`, regardless of whether the validator is enabled.
+    pub fn builder_proposals(&self, public_key: &PublicKey) -> Option<bool> {
+        self.definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *public_key)
+            .and_then(|def| def.builder_proposals)
+    }
+
+    /// Sets the `suggested_fee_recipient` of the `ValidatorDefinition` for `voting_public_key`,
+    /// saving the definitions to disk. Setting `None` removes any existing fee recipient.
+    pub fn set_validator_fee_recipient(
+        &mut self,
+        voting_public_key: &PublicKey,
+        suggested_fee_recipient: Option<Address>,
+    ) -> Result<(), Error> {
+        self.update_definition(voting_public_key, |def| {
+            def.suggested_fee_recipient = suggested_fee_recipient
+        })
+    }
+
+    /// Sets the `gas_limit` of the `ValidatorDefinition` for `voting_public_key`, saving the
+    /// definitions to disk. Setting `None` removes any existing gas limit.
+    pub fn set_validator_gas_limit(
+        &mut self,
+        voting_public_key: &PublicKey,
+        gas_limit: Option<u64>,
+    ) -> Result<(), Error> {
+        self.update_definition(voting_public_key, |def| def.gas_limit = gas_limit)
+    }
+
+    /// Applies `update` to the `ValidatorDefinition` for `voting_public_key` and saves the
+    /// definitions to disk.
+    fn update_definition<F: FnOnce(&mut ValidatorDefinition)>(
+        &mut self,
+        voting_public_key: &PublicKey,
+        update: F,
+    ) -> Result<(), Error> {
+        let def = self
+            .definitions
+            .as_mut_slice()
+            .iter_mut()
+            .find(|def| def.voting_public_key == *voting_public_key)
+            .ok_or_else(|| Error::ValidatorNotInitialized(voting_public_key.clone()))?;
+        update(def);
+
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)
+    }
+
+    /// Removes the validator with `pubkey` from `self`, deleting its keystore from the
+    /// filesystem.
+    ///
+    /// If `is_local_keystore` is `false` then the validator must be a remote signer, and no files
+    /// other than the definitions are modified.
+    ///
+    /// ## Notes
+    ///
+    /// The keystore is removed from the key cache before it is deleted, so that the cache remains
+    /// decryptable. If the keystore is the only keystore in a validator directory of
+    /// `self.validators_dir` (i.e., it was created by Lighthouse) then the whole directory is
+    /// deleted, otherwise only the keystore file is deleted.
+    pub async fn delete_definition_and_keystore(
+        &mut self,
+        pubkey: &PublicKey,
+        is_local_keystore: bool,
+    ) -> Result<(), Error> {
+        let def = self
+            .definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *pubkey)
+            .cloned()
+            .ok_or_else(|| Error::ValidatorNotInitialized(pubkey.clone()))?;
+
+        let voting_keystore_path = match (&def.signing_definition, is_local_keystore) {
+            (
+                SigningDefinition::LocalKeystore {
+                    voting_keystore_path,
+                    ..
+                },
+                true,
+            ) => Some(voting_keystore_path.clone()),
+            (SigningDefinition::Web3Signer { .. }, false) => None,
+            _ => return Err(Error::InvalidActionOnValidator),
+        };
+
+        if let Some(voting_keystore_path) = &voting_keystore_path {
+            self.remove_from_key_cache(voting_keystore_path).await?;
+        }
+
+        // Drop the initialized validator, which releases the keystore lockfile.
+        self.validators.remove(&pubkey.compress());
+
+        if let Some(voting_keystore_path) = voting_keystore_path {
+            self.delete_keystore_or_validator_dir(&voting_keystore_path)?;
+        }
+
+        self.definitions
+            .retain(|def| def.voting_public_key != *pubkey);
+        self.definitions
+            .save(&self.validators_dir)
+            .map_err(Error::UnableToSaveDefinitions)?;
+
+        // Update the enabled and total validator counts
+        set_gauge(
+            &crate::http_metrics::metrics::ENABLED_VALIDATORS_COUNT,
+            self.num_enabled() as i64,
+        );
+        set_gauge(
+            &crate::http_metrics::metrics::TOTAL_VALIDATORS_COUNT,
+            self.num_total() as i64,
+        );
+        Ok(())
+    }
+
+    /// Returns the voting keystore of the validator with `pubkey`, along with its password.
+    ///
+    /// Only validators with local keystores whose passwords are stored on disk can be exported.
+    pub fn export_keystore(&self, pubkey: &PublicKey) -> Result<(Keystore, ZeroizeString), Error> {
+        let def = self
+            .definitions
+            .as_slice()
+            .iter()
+            .find(|def| def.voting_public_key == *pubkey)
+            .ok_or_else(|| Error::ValidatorNotInitialized(pubkey.clone()))?;
+
+        match &def.signing_definition {
+            SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password_path,
+                voting_keystore_password,
+            } => {
+                let password = match (voting_keystore_password, voting_keystore_password_path) {
+                    (Some(password), _) => password.clone(),
+                    (None, Some(path)) => {
+                        let password = read_password(path)
+                            .map_err(Error::UnableToReadVotingKeystorePassword)?;
+                        String::from_utf8(password.as_bytes().to_vec())
+                            .map_err(|_| Error::UnableToExportKeystorePassword(pubkey.clone()))?
+                            .into()
+                    }
+                    (None, None) => {
+                        return Err(Error::UnableToExportKeystorePassword(pubkey.clone()))
+                    }
+                };
+                Ok((open_keystore(voting_keystore_path)?, password))
+            }
+            SigningDefinition::Web3Signer { .. } => Err(Error::InvalidActionOnValidator),
+        }
+    }
+
+    /// Removes the keystore at `voting_keystore_path` from the key cache, saving the cache if it
+    /// was modified.
+    async fn remove_from_key_cache(&self, voting_keystore_path: &Path) -> Result<(), Error> {
+        let key_cache_path = KeyCache::cache_file_path(&self.validators_dir);
+        let cache_lockfile_path =
+            get_lockfile_path(&key_cache_path).ok_or(Error::BadKeyCachePath(key_cache_path))?;
+        let _cache_lockfile = Lockfile::new(cache_lockfile_path)?;
+
+        let cache =
+            KeyCache::open_or_create(&self.validators_dir).map_err(Error::UnableToOpenKeyCache)?;
+        let mut key_stores = HashMap::new();
+        let mut key_cache = self.decrypt_key_cache(cache, &mut key_stores).await?;
+
+        let uuid = match key_stores.get(voting_keystore_path) {
+            Some(key_store) => *key_store.uuid(),
+            None => *open_keystore(voting_keystore_path)?.uuid(),
+        };
+        key_cache.remove(&uuid);
+
+        if key_cache.is_modified() {
+            let validators_dir = self.validators_dir.clone();
+            tokio::task::spawn_blocking(move || key_cache.save(validators_dir))
+                .await
+                .map_err(Error::TokioJoin)?
+                .map_err(Error::UnableToOpenKeyCache)?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the validator directory containing `voting_keystore_path` if it is a direct child
+    /// of `self.validators_dir` named after the keystore, otherwise deletes only the keystore.
+    fn delete_keystore_or_validator_dir(&self, voting_keystore_path: &Path) -> Result<(), Error> {
+        let dir_to_delete = voting_keystore_path
+            .parent()
+            .filter(|dir| dir.parent() == Some(self.validators_dir.as_path()))
+            .filter(|_| {
+                voting_keystore_path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map_or(false, |name| name == validator_dir::VOTING_KEYSTORE_FILE)
+            });
+
+        match dir_to_delete {
+            Some(dir) => fs::remove_dir_all(dir)
+                .map_err(|e| Error::UnableToDeleteValidatorDir(dir.to_path_buf(), e)),
+            None => fs::remove_file(voting_keystore_path)
+                .map_err(|e| Error::UnableToDeleteKeystore(voting_keystore_path.to_path_buf(), e)),
+        }
+    }
+
     /// Sets the `InitializedValidator` and `ValidatorDefinition` `enabled` values.
     ///
     /// ## Notes
@@ -536,6 +864,111 @@ impl InitializedValidators {
         .map_err(Error::TokioJoin)
     }
 
+    /// Decrypts the keystores of any enabled validators which are neither initialized nor in
+    /// `key_cache` in parallel, adding the keypairs to `key_cache`.
+    ///
+    /// Decrypting a keystore can take several seconds, so decrypting them one at a time makes
+    /// starting with many validators slow. Keystores which cannot be decrypted here (e.g., those
+    /// without a password) are skipped, to be decrypted and have any error reported during
+    /// initialization.
+    async fn decrypt_keystores(
+        &self,
+        key_cache: &mut KeyCache,
+        key_stores: &HashMap<PathBuf, Keystore>,
+    ) -> Result<(), Error> {
+        let mut to_decrypt: Vec<(Keystore, PlainText)> = vec![];
+        for def in self.definitions.as_slice() {
+            if !def.enabled
+                || self
+                    .validators
+                    .contains_key(&def.voting_public_key.compress())
+            {
+                continue;
+            }
+
+            if let SigningDefinition::LocalKeystore {
+                voting_keystore_path,
+                voting_keystore_password_path,
+                voting_keystore_password,
+            } = &def.signing_definition
+            {
+                let keystore = match key_stores.get(voting_keystore_path) {
+                    Some(keystore) if key_cache.get(keystore.uuid()).is_none() => keystore,
+                    _ => continue,
+                };
+                let password = match (voting_keystore_password_path, voting_keystore_password) {
+                    (_, Some(password)) => password.as_ref().to_vec().into(),
+                    (Some(path), None) => match read_password(path) {
+                        Ok(password) => password,
+                        Err(_) => continue,
+                    },
+                    (None, None) => continue,
+                };
+                to_decrypt.push((keystore.clone(), password));
+            }
+        }
+
+        // There is nothing to gain from a thread pool for a single keystore.
+        if to_decrypt.len() < 2 {
+            return Ok(());
+        }
+
+        let total = to_decrypt.len();
+        let num_threads = self.decryption_threads.unwrap_or(0);
+        let log = self.log.clone();
+        info!(
+            log,
+            "Decrypting validator keystores";
+            "count" => total,
+            "threads" => self.decryption_threads.map_or("auto".to_string(), |n| n.to_string()),
+        );
+
+        let decrypted = tokio::task::spawn_blocking(move || {
+            // Zero threads selects the rayon default of one per CPU.
+            let pool = match rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+            {
+                Ok(pool) => pool,
+                Err(e) => {
+                    warn!(log, "Unable to build keystore decryption pool"; "error" => %e);
+                    return vec![];
+                }
+            };
+
+            let completed = AtomicUsize::new(0);
+            pool.install(|| {
+                to_decrypt
+                    .into_par_iter()
+                    .filter_map(|(keystore, password)| {
+                        let keypair = keystore.decrypt_keypair(password.as_bytes()).ok();
+
+                        // Log progress at each tenth of the keystores.
+                        let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                        if done * 10 / total > (done - 1) * 10 / total {
+                            info!(
+                                log,
+                                "Decrypting validator keystores";
+                                "completed" => done,
+                                "count" => total,
+                            );
+                        }
+
+                        keypair.map(|keypair| (*keystore.uuid(), keypair, password))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .await
+        .map_err(Error::TokioJoin)?;
+
+        for (uuid, keypair, password) in decrypted {
+            key_cache.add(keypair, &uuid, password);
+        }
+
+        Ok(())
+    }
+
     /// Scans `self.definitions` and attempts to initialize and validators which are not already
     /// initialized.
     ///
@@ -560,6 +993,7 @@ impl InitializedValidators {
         let cache =
             KeyCache::open_or_create(&self.validators_dir).map_err(Error::UnableToOpenKeyCache)?;
         let mut key_cache = self.decrypt_key_cache(cache, &mut key_stores).await?;
+        self.decrypt_keystores(&mut key_cache, &key_stores).await?;
 
         let mut disabled_uuids = HashSet::new();
         for def in self.definitions.as_slice() {
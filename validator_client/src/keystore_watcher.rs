@@ -0,0 +1,69 @@
+//! Periodically checks `validator_definitions.yml` and, unless auto-discovery is disabled, the
+//! validators directory for validators which have been added since the validator client started.
+//! New validators are registered with slashing protection and begin performing duties without a
+//! restart.
+
+use crate::validator_store::ValidatorStore;
+use environment::RuntimeContext;
+use slog::{error, info};
+use slot_clock::SlotClock;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The interval between checks for new validators.
+const POLL_INTERVAL: Duration = Duration::from_secs(12);
+
+/// Starts a service which adds new validators to `validator_store`. Keystores in the validators
+/// directory are only discovered if `secrets_dir` is supplied.
+pub fn start_keystore_watcher_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    secrets_dir: Option<PathBuf>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            sleep(POLL_INTERVAL).await;
+
+            let new_definitions = validator_store
+                .initialized_validators()
+                .read()
+                .new_definitions_on_disk(secrets_dir.as_deref());
+            let new_definitions = match new_definitions {
+                Ok(new_definitions) => new_definitions,
+                Err(e) => {
+                    error!(
+                        log,
+                        "Unable to check for new validators";
+                        "error" => ?e,
+                    );
+                    continue;
+                }
+            };
+
+            for def in new_definitions {
+                let voting_pubkey = def.voting_public_key.clone();
+                match validator_store.add_validator(def).await {
+                    Ok(def) => info!(
+                        log,
+                        "Loaded new validator";
+                        "voting_pubkey" => ?voting_pubkey,
+                        "enabled" => def.enabled,
+                    ),
+                    Err(e) => error!(
+                        log,
+                        "Unable to load new validator";
+                        "error" => e,
+                        "voting_pubkey" => ?voting_pubkey,
+                    ),
+                }
+            }
+        }
+    };
+
+    context.executor.spawn(future, "keystore_watcher");
+}
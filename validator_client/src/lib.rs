@@ -1,16 +1,23 @@
 mod attestation_service;
 mod beacon_node_fallback;
 mod block_service;
+mod builder_registration_service;
 mod check_synced;
 mod cli;
+mod clock_offset;
 mod config;
 mod duties_service;
+mod fee_recipient_file;
 mod graffiti_file;
 mod http_metrics;
 mod key_cache;
+mod keystore_watcher;
 mod notifier;
+mod preparation_service;
 mod signing_method;
+mod slashing_alarm;
 mod sync_committee_service;
+mod validator_performance;
 
 mod doppelganger_service;
 pub mod http_api;
@@ -22,6 +29,7 @@ pub use config::Config;
 use initialized_validators::InitializedValidators;
 use lighthouse_metrics::set_gauge;
 use monitoring_api::{MonitoringHttpClient, ProcessType};
+pub use slashing_alarm::SlashingHalt;
 pub use slashing_protection::{SlashingDatabase, SLASHING_PROTECTION_FILENAME};
 
 use crate::beacon_node_fallback::{
@@ -172,6 +180,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         let validators = InitializedValidators::from_definitions(
             validator_defs,
             config.validator_dir.clone(),
+            config.keystore_decryption_threads,
             log.clone(),
         )
         .await
@@ -249,7 +258,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .map(|(i, url)| {
                 let slot_duration = Duration::from_secs(context.eth2_config.spec.seconds_per_slot);
 
-                let mut beacon_node_http_client_builder = ClientBuilder::new();
+                let mut beacon_node_http_client_builder =
+                    ClientBuilder::new().default_headers(config.beacon_node_header_map(i)?);
 
                 // Add new custom root certificates if specified.
                 if let Some(certificates) = &config.beacon_nodes_tls_certs {
@@ -304,8 +314,12 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         );
         // Initialize the number of connected, synced fallbacks to 0.
         set_gauge(&http_metrics::metrics::ETH2_FALLBACK_CONNECTED, 0);
-        let mut beacon_nodes: BeaconNodeFallback<_, T> =
-            BeaconNodeFallback::new(candidates, context.eth2_config.spec.clone(), log.clone());
+        let mut beacon_nodes: BeaconNodeFallback<_, T> = BeaconNodeFallback::new(
+            candidates,
+            config.broadcast_publications,
+            context.eth2_config.spec.clone(),
+            log.clone(),
+        );
 
         // Perform some potentially long-running initialization tasks.
         let (genesis_time, genesis_validators_root) = tokio::select! {
@@ -325,6 +339,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         );
 
         beacon_nodes.set_slot_clock(slot_clock.clone());
+        beacon_nodes.set_genesis_validators_root(genesis_validators_root);
         let beacon_nodes = Arc::new(beacon_nodes);
         start_fallback_updater_service(context.clone(), beacon_nodes.clone())?;
 
@@ -342,6 +357,7 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
         let validator_store = Arc::new(ValidatorStore::new(
             validators,
             slashing_protection,
+            config.slashing_protection_history_epochs,
             genesis_validators_root,
             context.eth2_config.spec.clone(),
             doppelganger_service.clone(),
@@ -379,10 +395,17 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             } else {
                 RequireSynced::No
             },
+            distributed: config.distributed,
+            duties_cache_path: duties_service::duties_cache_path(&config.validator_dir),
+            persist_lock: <_>::default(),
             spec: context.eth2_config.spec.clone(),
             context: duties_context,
         });
 
+        // Load the duties persisted before a restart, so that they are available before the
+        // first poll of the beacon node.
+        duties_service::load_duties(&duties_service);
+
         // Update the metrics server.
         if let Some(ctx) = &http_metrics_ctx {
             ctx.shared.write().validator_store = Some(validator_store.clone());
@@ -396,6 +419,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .runtime_context(context.service_context("block".into()))
             .graffiti(config.graffiti)
             .graffiti_file(config.graffiti_file.clone())
+            .fee_recipient_file(config.fee_recipient_file.clone())
+            .builder_proposals(config.builder_proposals)
             .build()?;
 
         let attestation_service = AttestationServiceBuilder::new()
@@ -404,6 +429,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
+            .attest_to_optimistic_head(config.attest_to_optimistic_head)
+            .attest_early_when_block_seen(config.attest_early_when_block_seen)
             .build()?;
 
         let sync_committee_service = SyncCommitteeService::new(
@@ -472,6 +499,53 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
             info!(log, "Doppelganger protection disabled.")
         }
 
+        slashing_alarm::start_slashing_alarm_service(
+            self.context.service_context("slashing_alarm".into()),
+            self.validator_store.clone(),
+            self.duties_service.beacon_nodes.clone(),
+            self.config.halt_on_slashing,
+            self.config.slashing_webhook.clone(),
+        );
+
+        if self.config.watch_validators {
+            let secrets_dir = if self.config.disable_auto_discover {
+                None
+            } else {
+                Some(self.config.secrets_dir.clone())
+            };
+            keystore_watcher::start_keystore_watcher_service(
+                self.context.service_context("keystore_watcher".into()),
+                self.validator_store.clone(),
+                secrets_dir,
+            );
+        }
+
+        builder_registration_service::start_builder_registration_service(
+            self.context.service_context("builder_registration".into()),
+            self.validator_store.clone(),
+            self.duties_service.beacon_nodes.clone(),
+            self.duties_service.slot_clock.clone(),
+            self.config.fee_recipient_file.clone(),
+            self.config.builder_proposals,
+        );
+
+        preparation_service::start_preparation_service(
+            self.context.service_context("preparation".into()),
+            self.validator_store.clone(),
+            self.duties_service.beacon_nodes.clone(),
+            self.duties_service.slot_clock.clone(),
+            self.config.fee_recipient_file.clone(),
+        );
+
+        // Checking performance requires downloading blocks, so only do it if the metrics will
+        // be served.
+        if self.config.http_metrics.enabled {
+            validator_performance::start_validator_performance_service(
+                self.context.service_context("validator_performance".into()),
+                self.duties_service.clone(),
+            );
+        }
+
         spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
         let api_secret = ApiSecret::create_or_open(&self.config.validator_dir)?;
@@ -482,6 +556,8 @@ impl<T: EthSpec> ProductionValidatorClient<T> {
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
+                beacon_nodes: Some(self.duties_service.beacon_nodes.clone()),
+                slot_clock: self.duties_service.slot_clock.clone(),
                 spec: self.context.eth2_config.spec.clone(),
                 config: self.config.http_api.clone(),
                 log: log.clone(),
@@ -0,0 +1,116 @@
+//! Sends the fee recipients of local validators to the beacon node each epoch, so that it can
+//! prepare execution payloads for them ahead of their proposals.
+//!
+//! The beacon node forgets preparation data which isn't re-sent for a few epochs, which allows it
+//! to stop preparing payloads for validators which have moved to another validator client.
+
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::doppelganger_service::DoppelgangerStatus;
+use crate::fee_recipient_file::FeeRecipientFile;
+use crate::validator_store::ValidatorStore;
+use environment::RuntimeContext;
+use slog::{debug, error, warn, Logger};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{EthSpec, ProposerPreparationData, PublicKeyBytes};
+
+/// Starts a service which sends proposer preparation data for the validators in
+/// `validator_store` to the beacon nodes each epoch.
+pub fn start_preparation_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    slot_clock: T,
+    fee_recipient_file: Option<FeeRecipientFile>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            prepare_proposers(
+                &validator_store,
+                &beacon_nodes,
+                fee_recipient_file.clone(),
+                &log,
+            )
+            .await;
+
+            match slot_clock.duration_to_next_epoch(E::slots_per_epoch()) {
+                Some(duration) => sleep(duration).await,
+                None => {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another slot.
+                    sleep(slot_clock.slot_duration()).await;
+                }
+            }
+        }
+    };
+
+    context.executor.spawn(future, "preparation_service");
+}
+
+/// Send the preparation data of all validators with a known index and fee recipient.
+async fn prepare_proposers<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: &ValidatorStore<T, E>,
+    beacon_nodes: &BeaconNodeFallback<T, E>,
+    fee_recipient_file: Option<FeeRecipientFile>,
+    log: &Logger,
+) {
+    let pubkeys: Vec<PublicKeyBytes> = validator_store.voting_pubkeys(DoppelgangerStatus::ignored);
+
+    let preparation_data: Vec<ProposerPreparationData> = pubkeys
+        .iter()
+        .filter_map(|pubkey| {
+            // Validators without an index aren't able to propose yet.
+            let validator_index = validator_store.validator_index(pubkey)?;
+
+            // A fee recipient from the validator definitions takes precedence over the fee
+            // recipient file, as for block proposals.
+            let fee_recipient = validator_store
+                .suggested_fee_recipient(pubkey)
+                .or_else(|| {
+                    fee_recipient_file.clone().and_then(|mut f| {
+                        match f.load_fee_recipient(pubkey) {
+                            Ok(f) => f,
+                            Err(e) => {
+                                warn!(log, "Failed to read fee recipient file"; "error" => ?e);
+                                None
+                            }
+                        }
+                    })
+                })?;
+
+            Some(ProposerPreparationData {
+                validator_index,
+                fee_recipient,
+            })
+        })
+        .collect();
+
+    if preparation_data.is_empty() {
+        return;
+    }
+
+    let preparation_data_ref = &preparation_data;
+    match beacon_nodes
+        .broadcast(RequireSynced::No, |beacon_node| async move {
+            beacon_node
+                .post_validator_prepare_beacon_proposer(preparation_data_ref)
+                .await
+        })
+        .await
+    {
+        Ok(()) => debug!(
+            log,
+            "Sent proposer preparation data";
+            "count" => preparation_data.len(),
+        ),
+        Err(e) => warn!(
+            log,
+            "Unable to send proposer preparation data";
+            "error" => %e,
+            "count" => preparation_data.len(),
+        ),
+    }
+}
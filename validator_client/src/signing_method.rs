@@ -35,6 +35,7 @@ pub enum Error {
 pub enum SignableMessage<'a, T: EthSpec> {
     RandaoReveal(Epoch),
     BeaconBlock(&'a BeaconBlock<T>),
+    BlindedBeaconBlock(&'a BlindedBeaconBlock<T>),
     AttestationData(&'a AttestationData),
     SignedAggregateAndProof(&'a AggregateAndProof<T>),
     SelectionProof(Slot),
@@ -44,6 +45,8 @@ pub enum SignableMessage<'a, T: EthSpec> {
         slot: Slot,
     },
     SignedContributionAndProof(&'a ContributionAndProof<T>),
+    ValidatorRegistration(&'a ValidatorRegistrationData),
+    VoluntaryExit(&'a VoluntaryExit),
 }
 
 impl<'a, T: EthSpec> SignableMessage<'a, T> {
@@ -55,6 +58,7 @@ impl<'a, T: EthSpec> SignableMessage<'a, T> {
         match self {
             SignableMessage::RandaoReveal(epoch) => epoch.signing_root(domain),
             SignableMessage::BeaconBlock(b) => b.signing_root(domain),
+            SignableMessage::BlindedBeaconBlock(b) => b.signing_root(domain),
             SignableMessage::AttestationData(a) => a.signing_root(domain),
             SignableMessage::SignedAggregateAndProof(a) => a.signing_root(domain),
             SignableMessage::SelectionProof(slot) => slot.signing_root(domain),
@@ -63,6 +67,8 @@ impl<'a, T: EthSpec> SignableMessage<'a, T> {
                 beacon_block_root, ..
             } => beacon_block_root.signing_root(domain),
             SignableMessage::SignedContributionAndProof(c) => c.signing_root(domain),
+            SignableMessage::ValidatorRegistration(v) => v.signing_root(domain),
+            SignableMessage::VoluntaryExit(e) => e.signing_root(domain),
         }
     }
 }
@@ -101,6 +107,11 @@ pub struct SigningContext {
 impl SigningContext {
     /// Returns the `Hash256` to be mixed-in with the signature.
     pub fn domain_hash(&self, spec: &ChainSpec) -> Hash256 {
+        if self.domain == Domain::ApplicationBuilder {
+            // Builder API messages are not specific to a fork or chain.
+            return spec.get_builder_domain();
+        }
+
         spec.get_domain(
             self.epoch,
             self.domain,
@@ -160,6 +171,9 @@ impl SigningMethod {
                         Web3SignerObject::RandaoReveal { epoch }
                     }
                     SignableMessage::BeaconBlock(block) => Web3SignerObject::beacon_block(block)?,
+                    SignableMessage::BlindedBeaconBlock(_) => {
+                        return Err(Error::MergeForkNotSupported)
+                    }
                     SignableMessage::AttestationData(a) => Web3SignerObject::Attestation(a),
                     SignableMessage::SignedAggregateAndProof(a) => {
                         Web3SignerObject::AggregateAndProof(a)
@@ -180,14 +194,20 @@ impl SigningMethod {
                     SignableMessage::SignedContributionAndProof(c) => {
                         Web3SignerObject::ContributionAndProof(c)
                     }
+                    SignableMessage::ValidatorRegistration(v) => {
+                        Web3SignerObject::ValidatorRegistration(v)
+                    }
+                    SignableMessage::VoluntaryExit(e) => Web3SignerObject::VoluntaryExit(e),
                 };
 
                 // Determine the Web3Signer message type.
                 let message_type = object.message_type();
 
-                // The `fork_info` field is not required for deposits since they sign across the
-                // genesis fork version.
-                let fork_info = if let Web3SignerObject::Deposit { .. } = &object {
+                // The `fork_info` field is not required for deposits or validator registrations
+                // since they sign across the genesis fork version.
+                let fork_info = if let Web3SignerObject::Deposit { .. }
+                | Web3SignerObject::ValidatorRegistration(_) = &object
+                {
                     None
                 } else {
                     Some(ForkInfo {
@@ -0,0 +1,329 @@
+//! Listens for slashings of local validators on the `validator_slashing` event stream of the beacon
+//! node, raising a critical alert (optionally also sent to a webhook) and optionally halting signing
+//! to limit further damage.
+//!
+//! The beacon node only reports slashings of the validators it monitors, so the beacon node should
+//! be run with `--validator-monitor-auto` (or with the local validators supplied via
+//! `--validator-monitor-pubkeys`).
+
+use crate::beacon_node_fallback::{BeaconNodeFallback, RequireSynced};
+use crate::doppelganger_service::DoppelgangerStatus;
+use crate::validator_store::ValidatorStore;
+use environment::RuntimeContext;
+use eth2::types::{EventKind, EventTopic, SseValidatorSlashing};
+use futures::{Future, StreamExt};
+use reqwest::Client;
+use sensitive_url::SensitiveUrl;
+use serde_derive::{Deserialize, Serialize};
+use slog::{crit, debug, info, Logger};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::error::Error as _;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use task_executor::{ShutdownReason, TaskExecutor};
+use tokio::time::sleep;
+use types::{EthSpec, PublicKeyBytes};
+
+/// The time to wait before reconnecting to the event stream after it fails or ends.
+const RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// The timeout for alerts sent to the slashing webhook.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The action to take when a local validator is slashed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlashingHalt {
+    /// Disable the slashed validator, so that it no longer signs messages.
+    Key,
+    /// Shut down the validator client.
+    All,
+}
+
+impl FromStr for SlashingHalt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "key" => Ok(SlashingHalt::Key),
+            "all" => Ok(SlashingHalt::All),
+            other => Err(format!(
+                "Invalid slashing halt mode \"{}\", expected \"key\" or \"all\"",
+                other
+            )),
+        }
+    }
+}
+
+/// Starts a service which listens for slashings of the validators in `validator_store`.
+///
+/// If `webhook` is supplied, each slashing of a local validator is also sent to it.
+pub fn start_slashing_alarm_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
+    halt: Option<SlashingHalt>,
+    webhook: Option<SensitiveUrl>,
+) {
+    let executor = context.executor.clone();
+    let log = context.log().clone();
+    let webhook = webhook.map(|url| (Client::new(), url));
+
+    let future = async move {
+        // The endpoint and ID of the last event received, used to replay events missed whilst
+        // reconnecting. Event IDs are only meaningful to the beacon node which assigned them.
+        let mut last_event: Option<(String, u64)> = None;
+
+        loop {
+            let last_event_ref = &last_event;
+            let result = beacon_nodes
+                .first_success(RequireSynced::No, |beacon_node| async move {
+                    let endpoint = beacon_node.to_string();
+                    let last_event_id = match last_event_ref {
+                        Some((last_endpoint, id)) if *last_endpoint == endpoint => Some(*id),
+                        _ => None,
+                    };
+                    beacon_node
+                        .get_events_since::<E>(&[EventTopic::ValidatorSlashing], last_event_id)
+                        .await
+                        .map(|stream| (endpoint, stream))
+                })
+                .await;
+
+            match result {
+                Ok((endpoint, stream)) => {
+                    debug!(
+                        log,
+                        "Listening for validator slashings";
+                        "endpoint" => &endpoint,
+                    );
+
+                    futures::pin_mut!(stream);
+                    while let Some(event) = stream.next().await {
+                        match event {
+                            Ok(event) => {
+                                last_event = Some((endpoint.clone(), event.id));
+                                if let EventKind::ValidatorSlashing(slashing) = event.event {
+                                    handle_slashing(
+                                        &slashing,
+                                        &validator_store,
+                                        &executor,
+                                        halt,
+                                        webhook.as_ref(),
+                                        &log,
+                                    );
+                                }
+                            }
+                            Err(e) => {
+                                debug!(
+                                    log,
+                                    "Validator slashing event stream failed";
+                                    "error" => ?e,
+                                    "endpoint" => &endpoint,
+                                );
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    debug!(
+                        log,
+                        "Unable to subscribe to validator slashings";
+                        "error" => %e,
+                    );
+                }
+            }
+
+            sleep(RECONNECT_DELAY).await;
+        }
+    };
+
+    context.executor.spawn(future, "slashing_alarm");
+}
+
+/// Raise an alert if `slashing` is of a local validator, sending it to `webhook` if supplied and
+/// halting according to `halt`.
+fn handle_slashing<T: SlotClock + 'static, E: EthSpec>(
+    slashing: &SseValidatorSlashing,
+    validator_store: &Arc<ValidatorStore<T, E>>,
+    executor: &TaskExecutor,
+    halt: Option<SlashingHalt>,
+    webhook: Option<&(Client, SensitiveUrl)>,
+    log: &Logger,
+) {
+    let local_pubkeys: HashSet<PublicKeyBytes> =
+        validator_store.voting_pubkeys(DoppelgangerStatus::ignored);
+    if !local_pubkeys.contains(&slashing.pubkey) {
+        return;
+    }
+
+    crit!(
+        log,
+        "Local validator slashed";
+        "msg" => "the validator may be running in more than one validator client",
+        "validator_index" => slashing.validator_index,
+        "pubkey" => ?slashing.pubkey,
+        "kind" => ?slashing.kind,
+        "slot" => slashing.slot,
+        "source" => &slashing.source,
+    );
+
+    let alert = webhook.map(|(client, url)| webhook_alert(slashing, client, url, log.clone()));
+
+    match halt {
+        None => {
+            if let Some(alert) = alert {
+                executor.spawn(alert, "slashing_alarm_webhook");
+            }
+        }
+        Some(SlashingHalt::Key) => {
+            if let Some(alert) = alert {
+                executor.spawn(alert, "slashing_alarm_webhook");
+            }
+            disable_validator(slashing.pubkey, validator_store, executor);
+        }
+        Some(SlashingHalt::All) => {
+            let mut shutdown_sender = executor.shutdown_sender();
+            let log = log.clone();
+            // The alert is sent before shutting down, since the shutdown would cancel it.
+            executor.spawn(
+                async move {
+                    if let Some(alert) = alert {
+                        alert.await;
+                    }
+                    if let Err(e) =
+                        shutdown_sender.try_send(ShutdownReason::Failure("Slashing detected."))
+                    {
+                        crit!(
+                            log,
+                            "Failed to send shutdown signal";
+                            "msg" => "terminate this process immediately",
+                            "error" => ?e
+                        );
+                    }
+                },
+                "slashing_alarm_shutdown",
+            );
+        }
+    }
+}
+
+/// Returns a future which sends `slashing` to the webhook at `url` as a JSON POST request.
+fn webhook_alert(
+    slashing: &SseValidatorSlashing,
+    client: &Client,
+    url: &SensitiveUrl,
+    log: Logger,
+) -> impl Future<Output = ()> {
+    let request = client
+        .post(url.full.clone())
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(slashing);
+    let redacted_url = url.to_string();
+
+    async move {
+        match request.send().await.and_then(|r| r.error_for_status()) {
+            Ok(_) => info!(
+                log,
+                "Sent slashing alert to webhook";
+                "url" => redacted_url,
+            ),
+            // The error is logged without its URL, which may contain credentials.
+            Err(e) => crit!(
+                log,
+                "Failed to send slashing alert to webhook";
+                "url" => redacted_url,
+                "status" => ?e.status(),
+                "error" => ?e.source().map(ToString::to_string),
+            ),
+        }
+    }
+}
+
+/// Disable `pubkey` and persist the change to the validator definitions, so that it does not sign
+/// again, even after a restart.
+fn disable_validator<T: SlotClock + 'static, E: EthSpec>(
+    pubkey: PublicKeyBytes,
+    validator_store: &Arc<ValidatorStore<T, E>>,
+    executor: &TaskExecutor,
+) {
+    let validator_store = validator_store.clone();
+    let runtime = executor.runtime();
+    let log = executor.log().clone();
+
+    // The validator definitions are held behind a synchronous lock, so they are updated on a
+    // blocking thread.
+    executor.spawn_blocking(
+        move || {
+            let result = pubkey
+                .decompress()
+                .map_err(|e| format!("invalid pubkey: {:?}", e))
+                .and_then(|pubkey| {
+                    let runtime = runtime.upgrade().ok_or("runtime shutdown")?;
+                    runtime
+                        .block_on(
+                            validator_store
+                                .initialized_validators()
+                                .write()
+                                .set_validator_status(&pubkey, false),
+                        )
+                        .map_err(|e| format!("{:?}", e))
+                });
+
+            match result {
+                Ok(()) => info!(
+                    log,
+                    "Disabled slashed validator";
+                    "pubkey" => ?pubkey,
+                ),
+                Err(e) => crit!(
+                    log,
+                    "Failed to disable slashed validator";
+                    "msg" => "terminate this process immediately",
+                    "pubkey" => ?pubkey,
+                    "error" => e,
+                ),
+            }
+        },
+        "slashing_alarm_disable",
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use environment::null_logger;
+    use eth2::types::ValidatorSlashingKind;
+    use tokio::sync::mpsc;
+    use types::Slot;
+    use warp::Filter;
+
+    #[tokio::test]
+    async fn webhook_alert_posts_slashing() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let route = warp::post()
+            .and(warp::path("alert"))
+            .and(warp::body::json())
+            .map(move |slashing: SseValidatorSlashing| {
+                tx.send(slashing).unwrap();
+                warp::reply()
+            });
+        let (addr, server) = warp::serve(route).bind_ephemeral(([127, 0, 0, 1], 0));
+        tokio::spawn(server);
+
+        let url = SensitiveUrl::parse(&format!("http://{}/alert", addr)).unwrap();
+        let slashing = SseValidatorSlashing {
+            validator_index: 1,
+            pubkey: PublicKeyBytes::empty(),
+            kind: ValidatorSlashingKind::Proposer,
+            slot: Slot::new(42),
+            source: "gossip".to_string(),
+        };
+
+        webhook_alert(&slashing, &Client::new(), &url, null_logger().unwrap()).await;
+
+        assert_eq!(rx.recv().await, Some(slashing));
+    }
+}
@@ -257,7 +257,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
             .collect::<Vec<_>>();
 
         self.beacon_nodes
-            .first_success(RequireSynced::No, |beacon_node| async move {
+            .broadcast(RequireSynced::No, |beacon_node| async move {
                 beacon_node
                     .post_beacon_pool_sync_committee_signatures(committee_signatures)
                     .await
@@ -391,7 +391,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         // Publish to the beacon node.
         self.beacon_nodes
-            .first_success(RequireSynced::No, |beacon_node| async move {
+            .broadcast(RequireSynced::No, |beacon_node| async move {
                 beacon_node
                     .post_validator_contribution_and_proofs(signed_contributions)
                     .await
@@ -463,6 +463,21 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
 
         if lookahead_period > current_period {
             duty_slots.push((lookahead_slot, lookahead_period));
+        } else if slot.as_u64() % E::slots_per_epoch() == 0 {
+            // Earlier in the period, pre-register the subscriptions for the next period once per
+            // epoch as soon as its duties are known. The BN holds these back until shortly before
+            // the period starts, but will still be subscribed in time if we go offline.
+            let next_period = current_period + 1;
+            let next_period_slot = (spec.epochs_per_sync_committee_period * next_period)
+                .start_slot(E::slots_per_epoch());
+            if self
+                .duties_service
+                .sync_duties
+                .get_duties_for_slot::<E>(next_period_slot, spec)
+                .is_some()
+            {
+                duty_slots.push((next_period_slot, next_period));
+            }
         }
 
         if duty_slots.is_empty() {
@@ -0,0 +1,384 @@
+//! Checks the on-chain performance of the local validators, updating the per-validator metrics
+//! served by the metrics server.
+//!
+//! The duties of an epoch are checked two epochs later, once attestations from that epoch can no
+//! longer be included on-chain.
+
+use crate::duties_service::DutiesService;
+use crate::http_metrics::metrics;
+use crate::validator_store::DoppelgangerStatus;
+use environment::RuntimeContext;
+use eth2::types::{AttesterData, BlockId, ProposerData};
+use slog::{debug, error, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::time::sleep;
+use types::{Attestation, Epoch, EthSpec, PublicKeyBytes, Slot};
+
+/// Starts a service which checks the performance of the local validators each epoch.
+pub fn start_validator_performance_service<T: SlotClock + 'static, E: EthSpec>(
+    context: RuntimeContext<E>,
+    duties_service: Arc<DutiesService<T, E>>,
+) {
+    let log = context.log().clone();
+
+    let future = async move {
+        loop {
+            let slot_duration = duties_service.slot_clock.slot_duration();
+            match duties_service
+                .slot_clock
+                .duration_to_next_epoch(E::slots_per_epoch())
+            {
+                // Allow some time for the block in the last slot of the previous epoch to arrive.
+                Some(duration) => sleep(duration + slot_duration / 3).await,
+                None => {
+                    error!(log, "Failed to read slot clock");
+                    // If we can't read the slot clock, just wait another slot.
+                    sleep(slot_duration).await;
+                    continue;
+                }
+            }
+
+            let current_epoch = match duties_service.slot_clock.now() {
+                Some(slot) => slot.epoch(E::slots_per_epoch()),
+                None => continue,
+            };
+
+            if let Some(epoch) = current_epoch.as_u64().checked_sub(2) {
+                check_epoch(&duties_service, Epoch::new(epoch), &log).await;
+            }
+        }
+    };
+
+    context.executor.spawn(future, "validator_performance");
+}
+
+/// Check whether the attestations and blocks of the local validators in `epoch` were included
+/// on-chain.
+async fn check_epoch<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    epoch: Epoch,
+    log: &Logger,
+) {
+    // Validators in a doppelganger detection period do not perform duties.
+    let signing_pubkeys: HashSet<PublicKeyBytes> = duties_service
+        .validator_store
+        .voting_pubkeys(DoppelgangerStatus::only_safe);
+
+    let attesters: Vec<AttesterData> = duties_service
+        .attesters
+        .read()
+        .values()
+        .filter_map(|map| map.get(&epoch))
+        .map(|(_, duty_and_proof)| duty_and_proof.duty.clone())
+        .filter(|duty| signing_pubkeys.contains(&duty.pubkey))
+        .collect();
+    let proposers: Vec<ProposerData> = duties_service
+        .proposers
+        .read()
+        .get(&epoch)
+        .map(|(_, proposers)| {
+            proposers
+                .iter()
+                .filter(|proposer| signing_pubkeys.contains(&proposer.pubkey))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if attesters.is_empty() && proposers.is_empty() {
+        return;
+    }
+
+    let mut performance = EpochPerformance::new(&attesters, &proposers);
+
+    // Attestations from `epoch` may be included until the end of the following epoch.
+    let last_proposal_slot = epoch.end_slot(E::slots_per_epoch());
+    let last_inclusion_slot = (epoch + 1).end_slot(E::slots_per_epoch());
+    let mut slot = epoch.start_slot(E::slots_per_epoch());
+    while slot <= last_inclusion_slot {
+        if slot > last_proposal_slot && performance.all_attestations_included() {
+            break;
+        }
+
+        let block = match duties_service
+            .beacon_nodes
+            .first_success(duties_service.require_synced, |beacon_node| async move {
+                beacon_node
+                    .get_beacon_blocks::<E>(BlockId::Slot(slot))
+                    .await
+            })
+            .await
+        {
+            Ok(Some(response)) => response.data,
+            // Skipped slot.
+            Ok(None) => {
+                slot += 1;
+                continue;
+            }
+            Err(e) => {
+                // Give up on this epoch rather than reporting duties as missed.
+                warn!(
+                    log,
+                    "Unable to check validator performance";
+                    "error" => %e,
+                    "epoch" => epoch,
+                    "slot" => slot,
+                );
+                return;
+            }
+        };
+
+        let block = block.message();
+        performance.process_block(
+            block.slot(),
+            block.proposer_index(),
+            block.body().attestations(),
+        );
+
+        slot += 1;
+    }
+
+    let mut missed_attestations = 0;
+    for duty in &attesters {
+        let validator = format!("{:?}", duty.pubkey);
+        match performance.inclusion_distance(duty) {
+            Some(inclusion_distance) => {
+                metrics::set_int_gauge(
+                    &metrics::VALIDATOR_LAST_ATTESTATION_SLOT,
+                    &[&validator],
+                    duty.slot.as_u64() as i64,
+                );
+                metrics::set_int_gauge(
+                    &metrics::VALIDATOR_ATTESTATION_INCLUSION_DISTANCE,
+                    &[&validator],
+                    inclusion_distance as i64,
+                );
+            }
+            None => {
+                missed_attestations += 1;
+                metrics::inc_counter_vec(
+                    &metrics::VALIDATOR_MISSED_DUTIES_TOTAL,
+                    &[&validator, metrics::ATTESTER],
+                );
+            }
+        }
+    }
+
+    let mut missed_blocks = 0;
+    for proposer in &proposers {
+        if !performance.proposed(proposer) {
+            missed_blocks += 1;
+            metrics::inc_counter_vec(
+                &metrics::VALIDATOR_MISSED_DUTIES_TOTAL,
+                &[&format!("{:?}", proposer.pubkey), metrics::PROPOSER],
+            );
+        }
+    }
+
+    metrics::set_gauge(
+        &metrics::VALIDATORS_EFFECTIVE,
+        performance.effective_validators() as i64,
+    );
+
+    if missed_attestations > 0 || missed_blocks > 0 {
+        warn!(
+            log,
+            "Local validators missed duties";
+            "epoch" => epoch,
+            "missed_attestations" => missed_attestations,
+            "missed_blocks" => missed_blocks,
+        );
+    } else {
+        debug!(
+            log,
+            "Local validators performed all duties";
+            "epoch" => epoch,
+            "attestations" => attesters.len(),
+            "blocks" => proposers.len(),
+        );
+    }
+}
+
+/// The duties of the local validators in an epoch, and which of them were performed by the
+/// blocks processed so far.
+struct EpochPerformance<'a> {
+    /// The attester duties of each committee, keyed by `(slot, committee_index)`.
+    committees: HashMap<(Slot, u64), Vec<&'a AttesterData>>,
+    proposers: &'a [ProposerData],
+    /// Maps each validator to the slot of the first block which included its attestation.
+    inclusion_slots: HashMap<PublicKeyBytes, Slot>,
+    /// The slots of the blocks proposed by the local validators.
+    proposed: HashSet<Slot>,
+}
+
+impl<'a> EpochPerformance<'a> {
+    fn new(attesters: &'a [AttesterData], proposers: &'a [ProposerData]) -> Self {
+        let mut committees: HashMap<(Slot, u64), Vec<&AttesterData>> = HashMap::new();
+        for duty in attesters {
+            committees
+                .entry((duty.slot, duty.committee_index))
+                .or_default()
+                .push(duty);
+        }
+
+        Self {
+            committees,
+            proposers,
+            inclusion_slots: HashMap::new(),
+            proposed: HashSet::new(),
+        }
+    }
+
+    /// Records the duties performed by the block at `slot`. Blocks must be processed in order of
+    /// slot, so that the first block to include an attestation determines its inclusion distance.
+    fn process_block<E: EthSpec>(
+        &mut self,
+        slot: Slot,
+        proposer_index: u64,
+        attestations: &[Attestation<E>],
+    ) {
+        for proposer in self.proposers {
+            if proposer.slot == slot && proposer.validator_index == proposer_index {
+                self.proposed.insert(slot);
+            }
+        }
+
+        for attestation in attestations {
+            let duties = match self
+                .committees
+                .get(&(attestation.data.slot, attestation.data.index))
+            {
+                Some(duties) => duties,
+                None => continue,
+            };
+            for duty in duties {
+                if attestation
+                    .aggregation_bits
+                    .get(duty.validator_committee_index as usize)
+                    .unwrap_or(false)
+                {
+                    self.inclusion_slots.entry(duty.pubkey).or_insert(slot);
+                }
+            }
+        }
+    }
+
+    /// Returns the number of slots between the attestation duty and the first block which
+    /// included the attestation, or `None` if it has not been included.
+    fn inclusion_distance(&self, duty: &AttesterData) -> Option<u64> {
+        self.inclusion_slots
+            .get(&duty.pubkey)
+            .map(|inclusion_slot| inclusion_slot.saturating_sub(duty.slot).as_u64())
+    }
+
+    /// Returns `true` if the block of the proposer duty has been processed.
+    fn proposed(&self, proposer: &ProposerData) -> bool {
+        self.proposed.contains(&proposer.slot)
+    }
+
+    /// Returns the number of validators whose attestations have been included.
+    fn effective_validators(&self) -> usize {
+        self.inclusion_slots.len()
+    }
+
+    fn all_attestations_included(&self) -> bool {
+        self.inclusion_slots.len() == self.committees.values().map(Vec::len).sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        AggregateSignature, AttestationData, BitList, Checkpoint, Hash256, MainnetEthSpec,
+    };
+
+    type E = MainnetEthSpec;
+
+    fn attester(validator_index: u64, slot: u64, validator_committee_index: u64) -> AttesterData {
+        AttesterData {
+            pubkey: PublicKeyBytes::deserialize(&[validator_index as u8; 48]).unwrap(),
+            validator_index,
+            committees_at_slot: 1,
+            committee_index: 0,
+            committee_length: 4,
+            validator_committee_index,
+            slot: Slot::new(slot),
+        }
+    }
+
+    fn proposer(validator_index: u64, slot: u64) -> ProposerData {
+        ProposerData {
+            pubkey: PublicKeyBytes::deserialize(&[validator_index as u8; 48]).unwrap(),
+            validator_index,
+            slot: Slot::new(slot),
+        }
+    }
+
+    /// Returns an attestation for `slot` by the members of the committee at `aggregation_bits`.
+    fn attestation(slot: u64, aggregation_bits: &[usize]) -> Attestation<E> {
+        let mut bits = BitList::with_capacity(4).unwrap();
+        for &i in aggregation_bits {
+            bits.set(i, true).unwrap();
+        }
+        Attestation {
+            aggregation_bits: bits,
+            data: AttestationData {
+                slot: Slot::new(slot),
+                index: 0,
+                beacon_block_root: Hash256::zero(),
+                source: Checkpoint::default(),
+                target: Checkpoint::default(),
+            },
+            signature: AggregateSignature::empty(),
+        }
+    }
+
+    #[test]
+    fn inclusion_and_effectiveness() {
+        let attesters = vec![attester(1, 1, 0), attester(2, 1, 1), attester(3, 2, 2)];
+        let proposers = vec![proposer(1, 3), proposer(2, 4)];
+        let mut performance = EpochPerformance::new(&attesters, &proposers);
+
+        // The first inclusion determines the distance, even if the attestation is included again.
+        performance.process_block::<E>(Slot::new(2), 7, &[attestation(1, &[0])]);
+        performance.process_block::<E>(
+            Slot::new(3),
+            1,
+            &[attestation(1, &[0, 1]), attestation(2, &[0, 1])],
+        );
+        assert!(!performance.all_attestations_included());
+        // Validator 2 did not propose its block at slot 4.
+        performance.process_block::<E>(Slot::new(4), 9, &[attestation(2, &[2])]);
+        assert!(performance.all_attestations_included());
+
+        assert_eq!(performance.inclusion_distance(&attesters[0]), Some(1));
+        assert_eq!(performance.inclusion_distance(&attesters[1]), Some(2));
+        assert_eq!(performance.inclusion_distance(&attesters[2]), Some(2));
+        assert_eq!(performance.effective_validators(), 3);
+
+        assert!(performance.proposed(&proposers[0]));
+        assert!(!performance.proposed(&proposers[1]));
+    }
+
+    #[test]
+    fn missed_attestations() {
+        let attesters = vec![attester(1, 1, 0), attester(2, 1, 1)];
+        let mut performance = EpochPerformance::new(&attesters, &[]);
+
+        // Attestations from other slots or committee members are not counted.
+        performance.process_block::<E>(
+            Slot::new(2),
+            7,
+            &[attestation(2, &[1]), attestation(1, &[2, 3])],
+        );
+        performance.process_block::<E>(Slot::new(3), 7, &[attestation(1, &[1])]);
+
+        assert_eq!(performance.inclusion_distance(&attesters[0]), None);
+        assert_eq!(performance.inclusion_distance(&attesters[1]), Some(2));
+        assert_eq!(performance.effective_validators(), 1);
+        assert!(!performance.all_attestations_included());
+    }
+}
@@ -6,7 +6,10 @@ use crate::{
 };
 use account_utils::{validator_definitions::ValidatorDefinition, ZeroizeString};
 use parking_lot::{Mutex, RwLock};
-use slashing_protection::{NotSafe, Safe, SlashingDatabase};
+use slashing_protection::{
+    interchange::Interchange, InterchangeError, InterchangeImportOutcome, NotSafe, Safe,
+    SlashingDatabase,
+};
 use slog::{crit, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use std::iter::FromIterator;
@@ -15,11 +18,14 @@ use std::path::Path;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
 use types::{
-    attestation::Error as AttestationError, graffiti::GraffitiString, AggregateAndProof,
-    Attestation, BeaconBlock, ChainSpec, ContributionAndProof, Domain, Epoch, EthSpec, Fork,
-    Graffiti, Hash256, Keypair, PublicKeyBytes, SelectionProof, Signature, SignedAggregateAndProof,
-    SignedBeaconBlock, SignedContributionAndProof, Slot, SyncAggregatorSelectionData,
-    SyncCommitteeContribution, SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId,
+    attestation::Error as AttestationError, graffiti::GraffitiString, Address, AggregateAndProof,
+    Attestation, BeaconBlock, BeaconBlockHeader, BlindedBeaconBlock, ChainSpec,
+    ContributionAndProof, Domain, Epoch, EthSpec, Fork, Graffiti, Hash256, Keypair, PublicKeyBytes,
+    SelectionProof, Signature, SignedAggregateAndProof, SignedBeaconBlock,
+    SignedBlindedBeaconBlock, SignedContributionAndProof, SignedValidatorRegistrationData,
+    SignedVoluntaryExit, Slot, SyncAggregatorSelectionData, SyncCommitteeContribution,
+    SyncCommitteeMessage, SyncSelectionProof, SyncSubnetId, ValidatorRegistrationData,
+    VoluntaryExit,
 };
 use validator_dir::ValidatorDir;
 
@@ -44,10 +50,13 @@ impl From<SigningError> for Error {
     }
 }
 
-/// Number of epochs of slashing protection history to keep.
+/// Default number of epochs of slashing protection history to keep.
 ///
 /// This acts as a maximum safe-guard against clock drift.
-const SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
+pub const DEFAULT_SLASHING_PROTECTION_HISTORY_EPOCHS: u64 = 512;
+
+/// The gas limit reported for validators which do not have a gas limit in their definition.
+pub const DEFAULT_GAS_LIMIT: u64 = 30_000_000;
 
 struct LocalValidator {
     validator_dir: ValidatorDir,
@@ -78,6 +87,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     validators: Arc<RwLock<InitializedValidators>>,
     slashing_protection: SlashingDatabase,
     slashing_protection_last_prune: Arc<Mutex<Epoch>>,
+    slashing_protection_history_epochs: u64,
     genesis_validators_root: Hash256,
     spec: Arc<ChainSpec>,
     log: Logger,
@@ -94,6 +104,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     pub fn new(
         validators: InitializedValidators,
         slashing_protection: SlashingDatabase,
+        slashing_protection_history_epochs: u64,
         genesis_validators_root: Hash256,
         spec: ChainSpec,
         doppelganger_service: Option<Arc<DoppelgangerService>>,
@@ -105,6 +116,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             validators: Arc::new(RwLock::new(validators)),
             slashing_protection,
             slashing_protection_last_prune: Arc::new(Mutex::new(Epoch::new(0))),
+            slashing_protection_history_epochs,
             genesis_validators_root,
             spec: Arc::new(spec),
             log,
@@ -134,6 +146,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.doppelganger_service.is_some()
     }
 
+    pub fn genesis_validators_root(&self) -> Hash256 {
+        self.genesis_validators_root
+    }
+
     pub fn initialized_validators(&self) -> Arc<RwLock<InitializedValidators>> {
         self.validators.clone()
     }
@@ -190,6 +206,30 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         Ok(validator_def)
     }
 
+    /// Import slashing protection data from `interchange`, which must be for the same genesis
+    /// validators root as `self`.
+    ///
+    /// Validators in the interchange are registered with the slashing protection database, even
+    /// if they are not known to `self`.
+    pub fn import_slashing_protection(
+        &self,
+        interchange: Interchange,
+    ) -> Result<Vec<InterchangeImportOutcome>, InterchangeError> {
+        self.slashing_protection
+            .import_interchange_info(interchange, self.genesis_validators_root)
+    }
+
+    /// Export slashing protection data for the validators in `pubkeys`.
+    ///
+    /// Validators which are not registered with the slashing protection database are omitted.
+    pub fn export_slashing_protection_for_keys(
+        &self,
+        pubkeys: &[PublicKeyBytes],
+    ) -> Result<Interchange, InterchangeError> {
+        self.slashing_protection
+            .export_registered_interchange_info(self.genesis_validators_root, pubkeys)
+    }
+
     /// Attempts to resolve the pubkey to a validator index.
     ///
     /// It may return `None` if the `pubkey` is:
@@ -349,34 +389,119 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.validators.read().graffiti(validator_pubkey)
     }
 
+    pub fn suggested_fee_recipient(&self, validator_pubkey: &PublicKeyBytes) -> Option<Address> {
+        let validator_pubkey = validator_pubkey.decompress().ok()?;
+        self.validators
+            .read()
+            .suggested_fee_recipient(&validator_pubkey)
+    }
+
+    /// Returns the gas limit from the validator definition, or `DEFAULT_GAS_LIMIT` if none is set.
+    pub fn gas_limit(&self, validator_pubkey: &PublicKeyBytes) -> u64 {
+        validator_pubkey
+            .decompress()
+            .ok()
+            .and_then(|pubkey| self.validators.read().gas_limit(&pubkey))
+            .unwrap_or(DEFAULT_GAS_LIMIT)
+    }
+
+    /// Returns whether the validator should propose blocks with payloads from a builder, using
+    /// `default` unless overridden by the validator definition.
+    pub fn builder_proposals(&self, validator_pubkey: &PublicKeyBytes, default: bool) -> bool {
+        validator_pubkey
+            .decompress()
+            .ok()
+            .and_then(|pubkey| self.validators.read().builder_proposals(&pubkey))
+            .unwrap_or(default)
+    }
+
     pub async fn sign_block(
         &self,
         validator_pubkey: PublicKeyBytes,
         block: BeaconBlock<E>,
         current_slot: Slot,
     ) -> Result<SignedBeaconBlock<E>, Error> {
+        let signing_context = self.check_block_proposal(
+            validator_pubkey,
+            &block.block_header(),
+            block.epoch(),
+            current_slot,
+        )?;
+
+        let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+        let signature = signing_method
+            .get_signature(
+                SignableMessage::BeaconBlock(&block),
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await?;
+        Ok(SignedBeaconBlock::from_block(block, signature))
+    }
+
+    /// Sign a block with its execution payload replaced by a payload header.
+    ///
+    /// The signature is identical to that of the full block, so the same slashing protection
+    /// applies.
+    pub async fn sign_blinded_block(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        block: BlindedBeaconBlock<E>,
+        current_slot: Slot,
+    ) -> Result<SignedBlindedBeaconBlock<E>, Error> {
+        let signing_context = self.check_block_proposal(
+            validator_pubkey,
+            &block.block_header(),
+            block.epoch(),
+            current_slot,
+        )?;
+
+        let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+        let signature = signing_method
+            .get_signature(
+                SignableMessage::BlindedBeaconBlock(&block),
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await?;
+        Ok(SignedBlindedBeaconBlock {
+            message: block,
+            signature,
+        })
+    }
+
+    /// Checks that a block with `block_header` is safe to sign and records it in the slashing
+    /// protection database, returning the `SigningContext` for its signature.
+    fn check_block_proposal(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        block_header: &BeaconBlockHeader,
+        signing_epoch: Epoch,
+        current_slot: Slot,
+    ) -> Result<SigningContext, Error> {
         // Make sure the block slot is not higher than the current slot to avoid potential attacks.
-        if block.slot() > current_slot {
+        if block_header.slot > current_slot {
             warn!(
                 self.log,
                 "Not signing block with slot greater than current slot";
-                "block_slot" => block.slot().as_u64(),
+                "block_slot" => block_header.slot.as_u64(),
                 "current_slot" => current_slot.as_u64()
             );
             return Err(Error::GreaterThanCurrentSlot {
-                slot: block.slot(),
+                slot: block_header.slot,
                 current_slot,
             });
         }
 
-        let signing_epoch = block.epoch();
         let signing_context = self.signing_context(Domain::BeaconProposer, signing_epoch);
         let domain_hash = signing_context.domain_hash(&self.spec);
 
         // Check for slashing conditions.
         let slashing_status = self.slashing_protection.check_and_insert_block_proposal(
             &validator_pubkey,
-            &block.block_header(),
+            block_header,
             domain_hash,
         );
 
@@ -384,17 +509,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             // We can safely sign this block without slashing.
             Ok(Safe::Valid) => {
                 metrics::inc_counter_vec(&metrics::SIGNED_BLOCKS_TOTAL, &[metrics::SUCCESS]);
-
-                let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
-                let signature = signing_method
-                    .get_signature(
-                        SignableMessage::BeaconBlock(&block),
-                        signing_context,
-                        &self.spec,
-                        &self.task_executor,
-                    )
-                    .await?;
-                Ok(SignedBeaconBlock::from_block(block, signature))
+                Ok(signing_context)
             }
             Ok(Safe::SameData) => {
                 warn!(
@@ -426,6 +541,60 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
+    /// Sign a registration of the validator's preferences with a builder.
+    ///
+    /// Registrations are not slashable, so they are signed regardless of doppelganger protection.
+    pub async fn sign_validator_registration_data(
+        &self,
+        validator_registration_data: ValidatorRegistrationData,
+    ) -> Result<SignedValidatorRegistrationData, Error> {
+        let signing_method =
+            self.doppelganger_bypassed_signing_method(validator_registration_data.pubkey)?;
+        // The epoch is ignored by the builder domain.
+        let signing_context = self.signing_context(Domain::ApplicationBuilder, Epoch::new(0));
+
+        let signature = signing_method
+            .get_signature::<E>(
+                SignableMessage::ValidatorRegistration(&validator_registration_data),
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await?;
+
+        Ok(SignedValidatorRegistrationData {
+            message: validator_registration_data,
+            signature,
+        })
+    }
+
+    /// Signs a `VoluntaryExit` for the given validator.
+    ///
+    /// Exits are not slashable, so they bypass doppelganger protection.
+    pub async fn sign_voluntary_exit(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        voluntary_exit: VoluntaryExit,
+    ) -> Result<SignedVoluntaryExit, Error> {
+        let signing_epoch = voluntary_exit.epoch;
+        let signing_context = self.signing_context(Domain::VoluntaryExit, signing_epoch);
+        let signing_method = self.doppelganger_bypassed_signing_method(validator_pubkey)?;
+
+        let signature = signing_method
+            .get_signature::<E>(
+                SignableMessage::VoluntaryExit(&voluntary_exit),
+                signing_context,
+                &self.spec,
+                &self.task_executor,
+            )
+            .await?;
+
+        Ok(SignedVoluntaryExit {
+            message: voluntary_exit,
+            signature,
+        })
+    }
+
     pub async fn sign_attestation(
         &self,
         validator_pubkey: PublicKeyBytes,
@@ -699,12 +868,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// cheap to call. The `first_run` flag can be used to print a more verbose message when pruning
     /// runs.
     pub fn prune_slashing_protection_db(&self, current_epoch: Epoch, first_run: bool) {
-        // Attempt to prune every SLASHING_PROTECTION_HISTORY_EPOCHs, with a tolerance for
+        // Attempt to prune every `slashing_protection_history_epochs`, with a tolerance for
         // missing the epoch that aligns exactly.
+        let history_epochs = self.slashing_protection_history_epochs;
         let mut last_prune = self.slashing_protection_last_prune.lock();
-        if current_epoch / SLASHING_PROTECTION_HISTORY_EPOCHS
-            <= *last_prune / SLASHING_PROTECTION_HISTORY_EPOCHS
-        {
+        if current_epoch / history_epochs <= *last_prune / history_epochs {
             return;
         }
 
@@ -721,7 +889,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         let _timer = metrics::start_timer(&metrics::SLASHING_PROTECTION_PRUNE_TIMES);
 
-        let new_min_target_epoch = current_epoch.saturating_sub(SLASHING_PROTECTION_HISTORY_EPOCHS);
+        let new_min_target_epoch = current_epoch.saturating_sub(history_epochs);
         let new_min_slot = new_min_target_epoch.start_slot(E::slots_per_epoch());
 
         let all_pubkeys: Vec<_> = self.voting_pubkeys(DoppelgangerStatus::ignored);
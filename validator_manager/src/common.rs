@@ -0,0 +1,211 @@
+use account_utils::ZeroizeString;
+use clap::ArgMatches;
+use eth2::lighthouse_vc::{
+    http_client::ValidatorClientHttpClient,
+    std_types::{
+        ImportKeystoreStatus, ImportKeystoresRequest, InterchangeJsonStr, KeystoreJsonStr,
+        SingleKeystoreResponse,
+    },
+};
+use eth2_keystore::Keystore;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slashing_protection::interchange::Interchange;
+use std::fs;
+use std::path::Path;
+use types::PublicKeyBytes;
+
+pub const VALIDATORS_FILENAME: &str = "validators.json";
+
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const DEFAULT_VC_URL: &str = "http://localhost:5062";
+
+/// A validator which can be imported into a validator client.
+///
+/// A list of these is written to `validators.json`, which contains keystore passwords and must be
+/// kept secret.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ValidatorSpecification {
+    pub voting_keystore: Keystore,
+    pub voting_keystore_password: ZeroizeString,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slashing_protection: Option<Interchange>,
+}
+
+impl ValidatorSpecification {
+    pub fn voting_pubkey(&self) -> Result<PublicKeyBytes, String> {
+        self.voting_keystore
+            .public_key()
+            .map(|pubkey| pubkey.compress())
+            .ok_or_else(|| format!("Invalid keystore pubkey: {}", self.voting_keystore.pubkey()))
+    }
+
+    /// Import the validator into the validator client behind `client`, along with its slashing
+    /// protection data.
+    pub async fn import(
+        self,
+        client: &ValidatorClientHttpClient,
+    ) -> Result<ImportKeystoreStatus, String> {
+        let request = ImportKeystoresRequest {
+            keystores: vec![KeystoreJsonStr(self.voting_keystore)],
+            passwords: vec![self.voting_keystore_password],
+            slashing_protection: self.slashing_protection.map(InterchangeJsonStr),
+        };
+        let response = client
+            .post_keystores(&request)
+            .await
+            .map_err(|e| format!("Failed to import keystore: {:?}", e))?;
+
+        match response.data.as_slice() {
+            [status] if status.status == ImportKeystoreStatus::Error => Err(format!(
+                "Validator client failed to import keystore: {}",
+                status.message.as_deref().unwrap_or("unknown error")
+            )),
+            [status] => Ok(status.status),
+            statuses => Err(format!(
+                "Expected one import status, received {}",
+                statuses.len()
+            )),
+        }
+    }
+}
+
+/// Returns the slashing protection data of `interchange` which belongs to `pubkey`.
+pub fn slashing_protection_for(interchange: &Interchange, pubkey: &PublicKeyBytes) -> Interchange {
+    Interchange {
+        metadata: interchange.metadata.clone(),
+        data: interchange
+            .data
+            .iter()
+            .filter(|data| data.pubkey == *pubkey)
+            .cloned()
+            .collect(),
+    }
+}
+
+pub fn read_validators(path: &Path) -> Result<Vec<ValidatorSpecification>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Unable to open {:?}: {:?}", path, e))?;
+    serde_json::from_reader(file).map_err(|e| format!("Unable to parse {:?}: {:?}", path, e))
+}
+
+/// Write `validators` to a new file at `path`, readable only by the current user.
+pub fn write_validators(path: &Path, validators: &[ValidatorSpecification]) -> Result<(), String> {
+    if path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite it",
+            path
+        ));
+    }
+    let bytes = serde_json::to_vec_pretty(validators)
+        .map_err(|e| format!("Unable to serialize validators: {:?}", e))?;
+    filesystem::create_with_600_perms(path, &bytes)
+        .map_err(|e| format!("Unable to write {:?}: {:?}", path, e))
+}
+
+/// Parse the validator client URL given by the `flag` CLI argument.
+pub fn parse_vc_url(matches: &ArgMatches, flag: &str) -> Result<SensitiveUrl, String> {
+    let url: String = clap_utils::parse_required(matches, flag)?;
+    SensitiveUrl::parse(&url).map_err(|e| format!("Invalid --{}: {:?}", flag, e))
+}
+
+/// Connect to the validator client at `url` using the API token at `token_path`, returning the
+/// client along with the keystores it currently manages.
+pub async fn vc_http_client(
+    url: SensitiveUrl,
+    token_path: &Path,
+) -> Result<(ValidatorClientHttpClient, Vec<SingleKeystoreResponse>), String> {
+    let token = fs::read_to_string(token_path)
+        .map_err(|e| format!("Unable to read API token from {:?}: {:?}", token_path, e))?;
+    let client = ValidatorClientHttpClient::new(url.clone(), token.trim().to_string())
+        .map_err(|e| format!("Unable to create client for {}: {:?}", url, e))?;
+    let keystores = client
+        .get_keystores()
+        .await
+        .map_err(|e| format!("Unable to connect to validator client at {}: {:?}", url, e))?
+        .data;
+
+    Ok((client, keystores))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth2_keystore::KeystoreBuilder;
+    use slashing_protection::interchange::{InterchangeData, InterchangeMetadata};
+    use tempfile::tempdir;
+    use types::{Hash256, Keypair};
+
+    fn validator(slashing_protection: Option<Interchange>) -> ValidatorSpecification {
+        let password = "password".to_string();
+        ValidatorSpecification {
+            voting_keystore: KeystoreBuilder::new(
+                &Keypair::random(),
+                password.as_bytes(),
+                String::new(),
+            )
+            .unwrap()
+            .build()
+            .unwrap(),
+            voting_keystore_password: password.into(),
+            slashing_protection,
+        }
+    }
+
+    fn interchange(pubkeys: &[PublicKeyBytes]) -> Interchange {
+        Interchange {
+            metadata: InterchangeMetadata {
+                interchange_format_version: 5,
+                genesis_validators_root: Hash256::zero(),
+            },
+            data: pubkeys
+                .iter()
+                .map(|pubkey| InterchangeData {
+                    pubkey: *pubkey,
+                    signed_blocks: vec![],
+                    signed_attestations: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn validators_file_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(VALIDATORS_FILENAME);
+
+        let first = validator(None);
+        let pubkey = first.voting_pubkey().unwrap();
+        let second = validator(Some(interchange(&[pubkey])));
+        write_validators(&path, &[first, second]).unwrap();
+
+        let read = read_validators(&path).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].voting_pubkey().unwrap(), pubkey);
+        assert!(read[0].slashing_protection.is_none());
+        assert_eq!(read[1].slashing_protection, Some(interchange(&[pubkey])));
+
+        // The file is never overwritten.
+        assert!(write_validators(&path, &[]).is_err());
+        assert_eq!(read_validators(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn slashing_protection_is_filtered_by_pubkey() {
+        let pubkeys = [
+            Keypair::random().pk.compress(),
+            Keypair::random().pk.compress(),
+        ];
+        let all = interchange(&pubkeys);
+
+        assert_eq!(
+            slashing_protection_for(&all, &pubkeys[1]),
+            interchange(&pubkeys[1..])
+        );
+        assert!(
+            slashing_protection_for(&all, &Keypair::random().pk.compress())
+                .data
+                .is_empty()
+        );
+    }
+}
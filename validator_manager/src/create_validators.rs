@@ -0,0 +1,131 @@
+use crate::common::{write_validators, ValidatorSpecification, VALIDATORS_FILENAME};
+use account_manager::common::read_mnemonic_from_cli;
+use account_manager::wallet::create::STDIN_INPUTS_FLAG;
+use account_utils::eth2_keystore::{keypair_from_secret, KeystoreBuilder};
+use account_utils::random_password_string;
+use clap::{App, Arg, ArgMatches};
+use eth2_wallet::bip39::Seed;
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType};
+use std::fs;
+use std::path::PathBuf;
+
+pub const CMD: &str = "create";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+pub const FIRST_INDEX_FLAG: &str = "first-index";
+pub const COUNT_FLAG: &str = "count";
+pub const MNEMONIC_FLAG: &str = "mnemonic-path";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Derives validator keystores from a BIP-39 mnemonic and writes them, along with \
+             randomly generated passwords, to a validators.json file which can be passed to the \
+             `import` command. Nothing is sent to a validator client.",
+        )
+        .arg(
+            Arg::with_name(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The directory in which to write validators.json. It is created if it does \
+                     not exist. The file contains keystore passwords and must be kept secret.",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(FIRST_INDEX_FLAG)
+                .long(FIRST_INDEX_FLAG)
+                .value_name("FIRST_INDEX")
+                .help("The EIP-2334 index of the first validator to derive.")
+                .default_value("0")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(COUNT_FLAG)
+                .long(COUNT_FLAG)
+                .value_name("COUNT")
+                .help("The number of consecutive validators to derive, from --first-index.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(MNEMONIC_FLAG)
+                .long(MNEMONIC_FLAG)
+                .value_name("MNEMONIC_PATH")
+                .help("If present, the mnemonic will be read in from this file.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(STDIN_INPUTS_FLAG)
+                .takes_value(false)
+                .hidden(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty."),
+        )
+}
+
+pub fn cli_run(matches: &ArgMatches) -> Result<(), String> {
+    let output_path: PathBuf = clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?;
+    let first_index: u32 = clap_utils::parse_required(matches, FIRST_INDEX_FLAG)?;
+    let count: u32 = clap_utils::parse_required(matches, COUNT_FLAG)?;
+    let mnemonic_path: Option<PathBuf> = clap_utils::parse_optional(matches, MNEMONIC_FLAG)?;
+    let stdin_inputs = cfg!(windows) || matches.is_present(STDIN_INPUTS_FLAG);
+
+    let end_index = first_index
+        .checked_add(count)
+        .ok_or("--first-index plus --count is too large")?;
+
+    // Fail before the slow key derivation, rather than after it.
+    let validators_path = output_path.join(VALIDATORS_FILENAME);
+    if validators_path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite it",
+            validators_path
+        ));
+    }
+    fs::create_dir_all(&output_path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output_path, e))?;
+
+    let mnemonic = read_mnemonic_from_cli(mnemonic_path, stdin_inputs)?;
+    let seed = Seed::new(&mnemonic, "");
+
+    let validators = (first_index..end_index)
+        .map(|index| {
+            let password = random_password_string();
+
+            let (secret, path) =
+                recover_validator_secret_from_mnemonic(seed.as_bytes(), index, KeyType::Voting)
+                    .map_err(|e| format!("Unable to derive validator keys: {:?}", e))?;
+            let keypair = keypair_from_secret(secret.as_bytes())
+                .map_err(|e| format!("Unable to build keystore: {:?}", e))?;
+            let keystore = KeystoreBuilder::new(&keypair, password.as_ref(), format!("{}", path))
+                .map_err(|e| format!("Unable to build keystore: {:?}", e))?
+                .build()
+                .map_err(|e| format!("Unable to build keystore: {:?}", e))?;
+
+            eprintln!(
+                "{}/{}\tIndex: {}\t0x{}",
+                index - first_index + 1,
+                count,
+                index,
+                keystore.pubkey()
+            );
+
+            Ok(ValidatorSpecification {
+                voting_keystore: keystore,
+                voting_keystore_password: password,
+                slashing_protection: None,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    write_validators(&validators_path, &validators)?;
+    eprintln!(
+        "Wrote {} validators to {:?}",
+        validators.len(),
+        validators_path
+    );
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::common::{
+    parse_vc_url, read_validators, vc_http_client, DEFAULT_VC_URL, VC_TOKEN_FLAG, VC_URL_FLAG,
+};
+use clap::{App, Arg, ArgMatches};
+use eth2::lighthouse_vc::std_types::ImportKeystoreStatus;
+use std::path::PathBuf;
+
+pub const CMD: &str = "import";
+pub const VALIDATORS_FILE_FLAG: &str = "validators-file";
+pub const IGNORE_DUPLICATES_FLAG: &str = "ignore-duplicates";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Imports the validators of a validators.json file, as written by the `create` \
+             command, into a running validator client using the keymanager API.",
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FILE_FLAG)
+                .long(VALIDATORS_FILE_FLAG)
+                .value_name("PATH")
+                .help("The path to the validators.json file.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("The HTTP address of the validator client's keymanager API.")
+                .default_value(DEFAULT_VC_URL)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The path to the validator client's API token file.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(IGNORE_DUPLICATES_FLAG)
+                .long(IGNORE_DUPLICATES_FLAG)
+                .help(
+                    "Skip validators which the validator client already has, rather than \
+                     refusing to import any validators.",
+                )
+                .takes_value(false),
+        )
+}
+
+pub async fn cli_run(matches: &ArgMatches<'_>) -> Result<(), String> {
+    let validators_file: PathBuf = clap_utils::parse_required(matches, VALIDATORS_FILE_FLAG)?;
+    let vc_url = parse_vc_url(matches, VC_URL_FLAG)?;
+    let vc_token: PathBuf = clap_utils::parse_required(matches, VC_TOKEN_FLAG)?;
+    let ignore_duplicates = matches.is_present(IGNORE_DUPLICATES_FLAG);
+
+    let validators = read_validators(&validators_file)?;
+    let (client, existing) = vc_http_client(vc_url, &vc_token).await?;
+
+    // Check for duplicates before importing anything, so that a mistake doesn't leave the
+    // validator client with only some of the validators.
+    let mut to_import = Vec::with_capacity(validators.len());
+    for validator in validators {
+        let pubkey = validator.voting_pubkey()?;
+        if existing
+            .iter()
+            .any(|keystore| keystore.validating_pubkey == pubkey)
+        {
+            if ignore_duplicates {
+                eprintln!(
+                    "Skipping {}, which the validator client already has",
+                    pubkey
+                );
+                continue;
+            }
+            return Err(format!(
+                "The validator client already has {}. No validators were imported. Use \
+                 --{} to import the others.",
+                pubkey, IGNORE_DUPLICATES_FLAG
+            ));
+        }
+        to_import.push((pubkey, validator));
+    }
+
+    let count = to_import.len();
+    for (i, (pubkey, validator)) in to_import.into_iter().enumerate() {
+        match validator.import(&client).await {
+            Ok(ImportKeystoreStatus::Imported) => {
+                eprintln!("{}/{}\tImported {}", i + 1, count, pubkey)
+            }
+            Ok(status) => eprintln!("{}/{}\t{} {:?}", i + 1, count, pubkey, status),
+            Err(e) => {
+                return Err(format!(
+                    "Failed to import {}, after importing {} of {} validators: {}",
+                    pubkey, i, count, e
+                ))
+            }
+        }
+    }
+
+    eprintln!("Imported {} validators", count);
+    Ok(())
+}
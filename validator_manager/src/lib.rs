@@ -0,0 +1,41 @@
+//! Creates validators in bulk and imports or moves them between validator clients using the
+//! keymanager API, for operators running many validators.
+pub mod common;
+pub mod create_validators;
+pub mod import_validators;
+pub mod move_validators;
+
+use clap::{App, ArgMatches};
+use environment::Environment;
+use types::EthSpec;
+
+pub const CMD: &str = "validator-manager";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .visible_aliases(&["vm", "validator_manager"])
+        .about(
+            "Utilities for creating validators in bulk and for importing and moving them between \
+             running validator clients.",
+        )
+        .subcommand(create_validators::cli_app())
+        .subcommand(import_validators::cli_app())
+        .subcommand(move_validators::cli_app())
+}
+
+/// Run the validator manager, returning an error if the operation did not succeed.
+pub fn run<T: EthSpec>(matches: &ArgMatches<'_>, env: Environment<T>) -> Result<(), String> {
+    match matches.subcommand() {
+        (create_validators::CMD, Some(matches)) => create_validators::cli_run(matches),
+        (import_validators::CMD, Some(matches)) => {
+            env.runtime().block_on(import_validators::cli_run(matches))
+        }
+        (move_validators::CMD, Some(matches)) => {
+            env.runtime().block_on(move_validators::cli_run(matches))
+        }
+        (unknown, _) => Err(format!(
+            "{} is not a valid {} command. See --help.",
+            unknown, CMD
+        )),
+    }
+}
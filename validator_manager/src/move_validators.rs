@@ -0,0 +1,237 @@
+use crate::common::{
+    parse_vc_url, slashing_protection_for, vc_http_client, write_validators,
+    ValidatorSpecification, DEFAULT_VC_URL, VALIDATORS_FILENAME,
+};
+use clap::{App, Arg, ArgMatches};
+use eth2::lighthouse_vc::std_types::{DeleteKeystoreStatus, DeleteKeystoresRequest};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use types::PublicKeyBytes;
+
+pub const CMD: &str = "move";
+pub const SRC_VC_URL_FLAG: &str = "src-vc-url";
+pub const SRC_VC_TOKEN_FLAG: &str = "src-vc-token";
+pub const DEST_VC_URL_FLAG: &str = "dest-vc-url";
+pub const DEST_VC_TOKEN_FLAG: &str = "dest-vc-token";
+pub const VALIDATORS_FLAG: &str = "validators";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+
+pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
+    App::new(CMD)
+        .about(
+            "Moves validators from one running validator client to another using the \
+             keymanager API. The validators are deleted from the source and their slashing \
+             protection data exported in a single request, so that they cannot sign with the \
+             source once they have been imported into the destination.",
+        )
+        .arg(
+            Arg::with_name(SRC_VC_URL_FLAG)
+                .long(SRC_VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("The HTTP address of the source validator client's keymanager API.")
+                .default_value(DEFAULT_VC_URL)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(SRC_VC_TOKEN_FLAG)
+                .long(SRC_VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The path to the source validator client's API token file.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DEST_VC_URL_FLAG)
+                .long(DEST_VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("The HTTP address of the destination validator client's keymanager API.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(DEST_VC_TOKEN_FLAG)
+                .long(DEST_VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The path to the destination validator client's API token file.")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(VALIDATORS_FLAG)
+                .long(VALIDATORS_FLAG)
+                .value_name("PUBKEYS")
+                .help(
+                    "A comma-separated list of the public keys of the validators to move, or \
+                     \"all\" to move every validator with a local keystore.",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("DIRECTORY")
+                .help(
+                    "The directory in which to write a validators.json of the moved validators \
+                     before they are imported into the destination. If the move fails part-way, \
+                     the file can be passed to the `import` command. It contains keystore \
+                     passwords and must be kept secret.",
+                )
+                .required(true)
+                .takes_value(true),
+        )
+}
+
+pub async fn cli_run(matches: &ArgMatches<'_>) -> Result<(), String> {
+    let src_vc_url = parse_vc_url(matches, SRC_VC_URL_FLAG)?;
+    let src_vc_token: PathBuf = clap_utils::parse_required(matches, SRC_VC_TOKEN_FLAG)?;
+    let dest_vc_url = parse_vc_url(matches, DEST_VC_URL_FLAG)?;
+    let dest_vc_token: PathBuf = clap_utils::parse_required(matches, DEST_VC_TOKEN_FLAG)?;
+    let validators: String = clap_utils::parse_required(matches, VALIDATORS_FLAG)?;
+    let output_path: PathBuf = clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?;
+
+    if src_vc_url.full == dest_vc_url.full {
+        return Err("The source and destination validator clients must differ".to_string());
+    }
+
+    let validators_path = output_path.join(VALIDATORS_FILENAME);
+    if validators_path.exists() {
+        return Err(format!(
+            "{:?} already exists, refusing to overwrite it",
+            validators_path
+        ));
+    }
+    fs::create_dir_all(&output_path)
+        .map_err(|e| format!("Unable to create {:?}: {:?}", output_path, e))?;
+
+    let (src_client, src_keystores) = vc_http_client(src_vc_url, &src_vc_token).await?;
+    let (dest_client, dest_keystores) = vc_http_client(dest_vc_url, &dest_vc_token).await?;
+
+    let pubkeys = if validators == "all" {
+        src_keystores
+            .iter()
+            .filter(|keystore| keystore.readonly != Some(true))
+            .map(|keystore| keystore.validating_pubkey)
+            .collect::<Vec<_>>()
+    } else {
+        validators
+            .split(',')
+            .map(|pubkey| PublicKeyBytes::from_str(pubkey.trim()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Invalid --{}: {}", VALIDATORS_FLAG, e))?
+    };
+    if pubkeys.is_empty() {
+        return Err("There are no validators to move".to_string());
+    }
+
+    // Check every validator before deleting any of them.
+    for pubkey in &pubkeys {
+        if !src_keystores
+            .iter()
+            .any(|keystore| keystore.validating_pubkey == *pubkey)
+        {
+            return Err(format!(
+                "The source validator client does not have {}. No validators were moved.",
+                pubkey
+            ));
+        }
+        if dest_keystores
+            .iter()
+            .any(|keystore| keystore.validating_pubkey == *pubkey)
+        {
+            return Err(format!(
+                "The destination validator client already has {}. No validators were moved.",
+                pubkey
+            ));
+        }
+    }
+
+    let response = src_client
+        .delete_lighthouse_keystores(&DeleteKeystoresRequest {
+            pubkeys: pubkeys.clone(),
+        })
+        .await
+        .map_err(|e| format!("Failed to export validators from the source: {:?}", e))?;
+
+    let mut exported = vec![];
+    let mut not_exported = vec![];
+    for (pubkey, single) in pubkeys.iter().zip(response.data) {
+        match (
+            single.status.status,
+            single.validating_keystore,
+            single.validating_keystore_password,
+        ) {
+            (DeleteKeystoreStatus::Deleted, Some(keystore), Some(password)) => {
+                exported.push(ValidatorSpecification {
+                    voting_keystore: keystore.0,
+                    voting_keystore_password: password,
+                    slashing_protection: Some(slashing_protection_for(
+                        &response.slashing_protection,
+                        pubkey,
+                    )),
+                })
+            }
+            (status, _, _) => {
+                eprintln!(
+                    "Unable to export {}: {:?} {}",
+                    pubkey,
+                    status,
+                    single.status.message.unwrap_or_default()
+                );
+                not_exported.push(*pubkey);
+            }
+        }
+    }
+
+    // Write the validators to disk before importing them, so they can't be lost. They have
+    // already been deleted from the source, so if they can't be written they are printed to
+    // stdout instead.
+    let backup = match write_validators(&validators_path, &exported) {
+        Ok(()) => format!("{:?}", validators_path),
+        Err(e) => {
+            eprintln!("WARNING: {}. Printing the validators to stdout instead.", e);
+            let json = serde_json::to_string_pretty(&exported)
+                .map_err(|e| format!("Unable to serialize validators: {:?}", e))?;
+            println!("{}", json);
+            "the validators printed to stdout".to_string()
+        }
+    };
+
+    let count = exported.len();
+    let mut failed = 0;
+    for (i, validator) in exported.into_iter().enumerate() {
+        let pubkey = match validator.voting_pubkey() {
+            Ok(pubkey) => pubkey,
+            Err(e) => {
+                eprintln!("{}/{}\tFailed to import: {}", i + 1, count, e);
+                failed += 1;
+                continue;
+            }
+        };
+        match validator.import(&dest_client).await {
+            Ok(status) => eprintln!("{}/{}\t{} {:?}", i + 1, count, pubkey, status),
+            Err(e) => {
+                eprintln!("{}/{}\tFailed to import {}: {}", i + 1, count, pubkey, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!(
+            "{} validators were removed from the source but could not be imported into the \
+             destination. Retry with the `import` command using {}.",
+            failed, backup
+        ));
+    }
+    if !not_exported.is_empty() {
+        return Err(format!(
+            "{} validators could not be exported and remain in the source validator client",
+            not_exported.len()
+        ));
+    }
+
+    eprintln!("Moved {} validators", count);
+    Ok(())
+}